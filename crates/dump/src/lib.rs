@@ -1,12 +1,16 @@
 #![allow(clippy::type_complexity)]
 #![allow(clippy::wrong_self_convention)]
 
+use std::collections::BTreeMap;
+
 use meilisearch_types::batches::BatchId;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::keys::Key;
 use meilisearch_types::milli::update::IndexDocumentsMethod;
 use meilisearch_types::settings::Unchecked;
-use meilisearch_types::tasks::{Details, IndexSwap, KindWithContent, Status, Task, TaskId};
+use meilisearch_types::tasks::{
+    BulkOperation, Details, IndexSwap, KindWithContent, Status, Task, TaskId, TransactionOperation,
+};
 use meilisearch_types::InstanceUid;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
@@ -103,16 +107,27 @@ pub enum KindDump {
     },
     DocumentDeletion {
         documents_ids: Vec<String>,
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        expected_versions: BTreeMap<String, u64>,
     },
     DocumentClear,
     DocumentDeletionByFilter {
         filter: serde_json::Value,
     },
+    DocumentBulk {
+        primary_key: Option<String>,
+        operations: Vec<BulkOperation>,
+        allow_index_creation: bool,
+    },
     DocumentEdition {
         filter: Option<serde_json::Value>,
         context: Option<serde_json::Map<String, serde_json::Value>>,
         function: String,
     },
+    EmbeddingRegeneration {
+        filter: Option<serde_json::Value>,
+        embedders: Option<Vec<String>>,
+    },
     Settings {
         settings: Box<meilisearch_types::settings::Settings<Unchecked>>,
         is_deletion: bool,
@@ -128,6 +143,14 @@ pub enum KindDump {
     IndexSwap {
         swaps: Vec<IndexSwap>,
     },
+    IndexTransaction {
+        operations: Vec<TransactionOperation>,
+        allow_index_creation: bool,
+    },
+    IndexClone {
+        new_index_uid: String,
+        settings_only: bool,
+    },
     TaskCancelation {
         query: String,
         tasks: RoaringBitmap,
@@ -179,8 +202,11 @@ impl From<KindWithContent> for KindDump {
                 documents_count,
                 allow_index_creation,
             },
-            KindWithContent::DocumentDeletion { documents_ids, .. } => {
-                KindDump::DocumentDeletion { documents_ids }
+            KindWithContent::DocumentBulk {
+                primary_key, operations, allow_index_creation, ..
+            } => KindDump::DocumentBulk { primary_key, operations, allow_index_creation },
+            KindWithContent::DocumentDeletion { documents_ids, expected_versions, .. } => {
+                KindDump::DocumentDeletion { documents_ids, expected_versions }
             }
             KindWithContent::DocumentDeletionByFilter { filter_expr, .. } => {
                 KindDump::DocumentDeletionByFilter { filter: filter_expr }
@@ -188,6 +214,9 @@ impl From<KindWithContent> for KindDump {
             KindWithContent::DocumentEdition { filter_expr, context, function, .. } => {
                 KindDump::DocumentEdition { filter: filter_expr, context, function }
             }
+            KindWithContent::EmbeddingRegeneration { filter_expr, embedders, .. } => {
+                KindDump::EmbeddingRegeneration { filter: filter_expr, embedders }
+            }
             KindWithContent::DocumentClear { .. } => KindDump::DocumentClear,
             KindWithContent::SettingsUpdate {
                 new_settings,
@@ -203,6 +232,12 @@ impl From<KindWithContent> for KindDump {
                 KindDump::IndexUpdate { primary_key }
             }
             KindWithContent::IndexSwap { swaps } => KindDump::IndexSwap { swaps },
+            KindWithContent::IndexTransaction { operations, allow_index_creation, .. } => {
+                KindDump::IndexTransaction { operations, allow_index_creation }
+            }
+            KindWithContent::IndexClone { new_index_uid, settings_only, .. } => {
+                KindDump::IndexClone { new_index_uid, settings_only }
+            }
             KindWithContent::TaskCancelation { query, tasks } => {
                 KindDump::TaskCancelation { query, tasks }
             }
@@ -305,6 +340,11 @@ pub(crate) mod test {
             localized_attributes: Setting::NotSet,
             facet_search: Setting::NotSet,
             prefix_search: Setting::NotSet,
+            chat: Setting::NotSet,
+            ingest_pipeline: Setting::NotSet,
+            document_schema: Setting::NotSet,
+            field_types: Setting::NotSet,
+            generate_document_ids: Setting::NotSet,
             _kind: std::marker::PhantomData,
         };
         settings.check()
@@ -356,6 +396,9 @@ pub(crate) mod test {
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 12,
                         indexed_documents: Some(10),
+                        version_conflicts: Vec::new(),
+                        invalid_documents: Vec::new(),
+                        generated_document_ids: Vec::new(),
                     }),
                     error: None,
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
@@ -380,6 +423,9 @@ pub(crate) mod test {
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 2,
                         indexed_documents: None,
+                        version_conflicts: Vec::new(),
+                        invalid_documents: Vec::new(),
+                        generated_document_ids: Vec::new(),
                     }),
                     error: None,
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
@@ -109,7 +109,10 @@ impl CompatV5ToV6 {
                         {
                             v5::tasks::DocumentDeletion::Clear => v6::Kind::DocumentClear,
                             v5::tasks::DocumentDeletion::Ids(documents_ids) => {
-                                v6::Kind::DocumentDeletion { documents_ids }
+                                v6::Kind::DocumentDeletion {
+                                    documents_ids,
+                                    expected_versions: Default::default(),
+                                }
                             }
                         },
                         v5::tasks::TaskContent::SettingsUpdate {
@@ -133,6 +136,9 @@ impl CompatV5ToV6 {
                             v6::Details::DocumentAdditionOrUpdate {
                                 received_documents: received_documents as u64,
                                 indexed_documents,
+                                version_conflicts: Vec::new(),
+                                invalid_documents: Vec::new(),
+                                generated_document_ids: Vec::new(),
                             }
                         }
                         v5::Details::Settings { settings } => {
@@ -147,6 +153,7 @@ impl CompatV5ToV6 {
                         } => v6::Details::DocumentDeletion {
                             provided_ids: received_document_ids,
                             deleted_documents,
+                            version_conflicts: Vec::new(),
                         },
                         v5::Details::ClearAll { deleted_documents } => {
                             v6::Details::ClearAll { deleted_documents }
@@ -398,6 +405,11 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
             search_cutoff_ms: v6::Setting::NotSet,
             facet_search: v6::Setting::NotSet,
             prefix_search: v6::Setting::NotSet,
+            chat: v6::Setting::NotSet,
+            ingest_pipeline: v6::Setting::NotSet,
+            document_schema: v6::Setting::NotSet,
+            field_types: v6::Setting::NotSet,
+            generate_document_ids: v6::Setting::NotSet,
             _kind: std::marker::PhantomData,
         }
     }
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::{BufReader, Read};
 
@@ -129,6 +130,14 @@ impl DumpReader {
             DumpReader::Compat(compat) => compat.network(),
         }
     }
+
+    pub fn index_aliases(&self) -> Result<Option<&BTreeMap<String, Vec<String>>>> {
+        match self {
+            DumpReader::Current(current) => Ok(current.index_aliases()),
+            // Index aliases didn't exist before the v6 dumps.
+            DumpReader::Compat(_) => Ok(None),
+        }
+    }
 }
 
 impl From<V6Reader> for DumpReader {
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fs::{self, File};
 use std::io::{BufRead, BufReader, ErrorKind};
 use std::path::Path;
@@ -57,6 +58,7 @@ pub struct V6Reader {
     keys: BufReader<File>,
     features: Option<RuntimeTogglableFeatures>,
     network: Option<Network>,
+    index_aliases: Option<BTreeMap<String, Vec<String>>>,
 }
 
 impl V6Reader {
@@ -108,6 +110,13 @@ impl V6Reader {
             None
         };
 
+        let index_aliases = match fs::read(dump.path().join("aliases.json")) {
+            Ok(aliases_file) => Some(serde_json::from_reader(&*aliases_file)?),
+            // The index aliases were introduced after the network, older dumps don't have any.
+            Err(error) if error.kind() == ErrorKind::NotFound => None,
+            Err(error) => return Err(error.into()),
+        };
+
         Ok(V6Reader {
             metadata: serde_json::from_reader(&*meta_file)?,
             instance_uid,
@@ -116,6 +125,7 @@ impl V6Reader {
             keys: BufReader::new(File::open(dump.path().join("keys.jsonl"))?),
             features,
             network,
+            index_aliases,
             dump,
         })
     }
@@ -199,6 +209,10 @@ impl V6Reader {
     pub fn network(&self) -> Option<&Network> {
         self.network.as_ref()
     }
+
+    pub fn index_aliases(&self) -> Option<&BTreeMap<String, Vec<String>>> {
+        self.index_aliases.as_ref()
+    }
 }
 
 pub struct UpdateFile {
@@ -107,7 +107,17 @@ impl<'a> Dump<'a> {
         task: TaskDump,
         content_file: Option<Box<UpdateFile>>,
     ) -> Result<Task> {
-        let task_has_no_docs = matches!(task.kind, KindDump::DocumentImport { documents_count, .. } if documents_count == 0);
+        let task_has_no_docs = match &task.kind {
+            KindDump::DocumentImport { documents_count, .. } => *documents_count == 0,
+            KindDump::DocumentBulk { operations, .. } => {
+                operations.iter().all(|operation| operation.documents_count() == 0)
+            }
+            KindDump::IndexTransaction { operations, .. } => operations
+                .iter()
+                .flat_map(|operation| &operation.documents)
+                .all(|operation| operation.documents_count() == 0),
+            _ => false,
+        };
 
         let content_uuid = match content_file {
             Some(content_file) if task.status == Status::Enqueued => {
@@ -160,10 +170,22 @@ impl<'a> Dump<'a> {
                     documents_count,
                     allow_index_creation,
                 },
-                KindDump::DocumentDeletion { documents_ids } => KindWithContent::DocumentDeletion {
-                    documents_ids,
-                    index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
-                },
+                KindDump::DocumentBulk { primary_key, operations, allow_index_creation } => {
+                    KindWithContent::DocumentBulk {
+                        index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                        primary_key,
+                        content_file: content_uuid.ok_or(Error::CorruptedDump)?,
+                        operations,
+                        allow_index_creation,
+                    }
+                }
+                KindDump::DocumentDeletion { documents_ids, expected_versions } => {
+                    KindWithContent::DocumentDeletion {
+                        documents_ids,
+                        expected_versions,
+                        index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                    }
+                }
                 KindDump::DocumentDeletionByFilter { filter } => {
                     KindWithContent::DocumentDeletionByFilter {
                         filter_expr: filter,
@@ -178,6 +200,13 @@ impl<'a> Dump<'a> {
                         function,
                     }
                 }
+                KindDump::EmbeddingRegeneration { filter, embedders } => {
+                    KindWithContent::EmbeddingRegeneration {
+                        index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                        filter_expr: filter,
+                        embedders,
+                    }
+                }
                 KindDump::DocumentClear => KindWithContent::DocumentClear {
                     index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
                 },
@@ -201,6 +230,20 @@ impl<'a> Dump<'a> {
                     primary_key,
                 },
                 KindDump::IndexSwap { swaps } => KindWithContent::IndexSwap { swaps },
+                KindDump::IndexTransaction { operations, allow_index_creation } => {
+                    KindWithContent::IndexTransaction {
+                        content_file: content_uuid.ok_or(Error::CorruptedDump)?,
+                        operations,
+                        allow_index_creation,
+                    }
+                }
+                KindDump::IndexClone { new_index_uid, settings_only } => {
+                    KindWithContent::IndexClone {
+                        index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                        new_index_uid,
+                        settings_only,
+                    }
+                }
                 KindDump::TaskCancelation { query, tasks } => {
                     KindWithContent::TaskCancelation { query, tasks }
                 }
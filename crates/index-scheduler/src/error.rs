@@ -56,6 +56,20 @@ pub enum Error {
     IndexNotFound(String),
     #[error("Index `{0}` already exists.")]
     IndexAlreadyExists(String),
+    #[error("Index alias `{0}` not found.")]
+    IndexAliasNotFound(String),
+    #[error("`{0}` is already used by an index alias.")]
+    IndexAliasAlreadyExists(String),
+    #[error("Index alias `{0}` points to several indexes and can only be used to search in all of them at once.")]
+    IndexAliasReadOnly(String),
+    #[error("`{0}` is an index alias, it must be deleted with the `/aliases` route, the indexes it points to must be deleted by their uid.")]
+    IndexAliasNotDeletable(String),
+    #[error(
+        "Index alias `{alias}` cannot point to `{index}` because it is an index alias itself."
+    )]
+    InvalidIndexAliasIndexes { alias: String, index: String },
+    #[error("Indexes must be declared only once during a transaction. `{0}` was declared several times.")]
+    TransactionDuplicateIndexFound(String),
     #[error(
         "Indexes must be declared only once during a swap. `{0}` was specified several times."
     )]
@@ -194,6 +208,12 @@ impl Error {
             | Error::WithCustomErrorCode(_, _)
             | Error::BadTaskId { .. }
             | Error::IndexAlreadyExists(_)
+            | Error::IndexAliasNotFound(_)
+            | Error::IndexAliasAlreadyExists(_)
+            | Error::IndexAliasReadOnly(_)
+            | Error::IndexAliasNotDeletable(_)
+            | Error::InvalidIndexAliasIndexes { .. }
+            | Error::TransactionDuplicateIndexFound(_)
             | Error::SwapDuplicateIndexFound(_)
             | Error::SwapDuplicateIndexesFound(_)
             | Error::SwapIndexNotFound(_)
@@ -261,6 +281,12 @@ impl ErrorCode for Error {
             Error::BadTaskId { .. } => Code::BadRequest,
             Error::IndexNotFound(_) => Code::IndexNotFound,
             Error::IndexAlreadyExists(_) => Code::IndexAlreadyExists,
+            Error::IndexAliasNotFound(_) => Code::IndexAliasNotFound,
+            Error::IndexAliasAlreadyExists(_) => Code::IndexAlreadyExists,
+            Error::IndexAliasReadOnly(_) => Code::IndexAliasReadOnly,
+            Error::IndexAliasNotDeletable(_) => Code::IndexAliasNotDeletable,
+            Error::InvalidIndexAliasIndexes { .. } => Code::InvalidIndexAliasIndexes,
+            Error::TransactionDuplicateIndexFound(_) => Code::InvalidTransactionDuplicateIndexFound,
             Error::SwapDuplicateIndexesFound(_) => Code::InvalidSwapDuplicateIndexFound,
             Error::SwapDuplicateIndexFound(_) => Code::InvalidSwapDuplicateIndexFound,
             Error::SwapIndexNotFound(_) => Code::IndexNotFound,
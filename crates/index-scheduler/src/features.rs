@@ -1,6 +1,8 @@
 use std::sync::{Arc, RwLock};
 
-use meilisearch_types::features::{InstanceTogglableFeatures, Network, RuntimeTogglableFeatures};
+use meilisearch_types::features::{
+    ChatCompletionSettings, InstanceTogglableFeatures, Network, RuntimeTogglableFeatures,
+};
 use meilisearch_types::heed::types::{SerdeJson, Str};
 use meilisearch_types::heed::{Database, Env, RwTxn, WithoutTls};
 
@@ -17,6 +19,7 @@ mod db_name {
 mod db_keys {
     pub const EXPERIMENTAL_FEATURES: &str = "experimental-features";
     pub const NETWORK: &str = "network";
+    pub const CHAT_SETTINGS: &str = "chat-settings";
 }
 
 #[derive(Clone)]
@@ -24,6 +27,7 @@ pub(crate) struct FeatureData {
     persisted: Database<Str, SerdeJson<RuntimeTogglableFeatures>>,
     runtime: Arc<RwLock<RuntimeTogglableFeatures>>,
     network: Arc<RwLock<Network>>,
+    chat_settings: Arc<RwLock<ChatCompletionSettings>>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -119,6 +123,19 @@ impl RoFeatures {
         }
     }
 
+    pub fn check_chat_completions(&self, disabled_action: &'static str) -> Result<()> {
+        if self.runtime.chat_completions {
+            Ok(())
+        } else {
+            Err(FeatureNotEnabledError {
+                disabled_action,
+                feature: "chat completions",
+                issue_link: "https://github.com/orgs/meilisearch/discussions/835",
+            }
+            .into())
+        }
+    }
+
     pub fn check_composite_embedders(&self, disabled_action: &'static str) -> Result<()> {
         if self.runtime.composite_embedders {
             Ok(())
@@ -159,10 +176,16 @@ impl FeatureData {
         let network_db = runtime_features_db.remap_data_type::<SerdeJson<Network>>();
         let network: Network = network_db.get(wtxn, db_keys::NETWORK)?.unwrap_or_default();
 
+        let chat_settings_db =
+            runtime_features_db.remap_data_type::<SerdeJson<ChatCompletionSettings>>();
+        let chat_settings: ChatCompletionSettings =
+            chat_settings_db.get(wtxn, db_keys::CHAT_SETTINGS)?.unwrap_or_default();
+
         Ok(Self {
             persisted: runtime_features_db,
             runtime,
             network: Arc::new(RwLock::new(network)),
+            chat_settings: Arc::new(RwLock::new(chat_settings)),
         })
     }
 
@@ -209,4 +232,25 @@ impl FeatureData {
     pub fn network(&self) -> Network {
         Network::clone(&*self.network.read().unwrap())
     }
+
+    pub fn put_chat_settings(
+        &self,
+        mut wtxn: RwTxn,
+        new_chat_settings: ChatCompletionSettings,
+    ) -> Result<()> {
+        self.persisted.remap_data_type::<SerdeJson<ChatCompletionSettings>>().put(
+            &mut wtxn,
+            db_keys::CHAT_SETTINGS,
+            &new_chat_settings,
+        )?;
+        wtxn.commit()?;
+
+        let mut chat_settings = self.chat_settings.write().unwrap();
+        *chat_settings = new_chat_settings;
+        Ok(())
+    }
+
+    pub fn chat_settings(&self) -> ChatCompletionSettings {
+        ChatCompletionSettings::clone(&*self.chat_settings.read().unwrap())
+    }
 }
@@ -4,7 +4,7 @@ use std::time::Duration;
 use std::{fs, thread};
 
 use meilisearch_types::heed::types::{SerdeJson, Str};
-use meilisearch_types::heed::{Database, Env, RoTxn, RwTxn, WithoutTls};
+use meilisearch_types::heed::{CompactionOption, Database, Env, RoTxn, RwTxn, WithoutTls};
 use meilisearch_types::milli;
 use meilisearch_types::milli::database_stats::DatabaseStats;
 use meilisearch_types::milli::index::RollbackOutcome;
@@ -23,11 +23,12 @@ use crate::{Error, IndexBudget, IndexSchedulerOptions, Result};
 mod index_map;
 
 /// The number of database used by index mapper
-const NUMBER_OF_DATABASES: u32 = 2;
+const NUMBER_OF_DATABASES: u32 = 3;
 /// Database const names for the `IndexMapper`.
 mod db_name {
     pub const INDEX_MAPPING: &str = "index-mapping";
     pub const INDEX_STATS: &str = "index-stats";
+    pub const INDEX_ALIASES: &str = "index-aliases";
 }
 
 /// Structure managing meilisearch's indexes.
@@ -37,6 +38,7 @@ mod db_name {
 /// 2. Opening indexes and storing references to these opened indexes
 /// 3. Accessing indexes through their uuid
 /// 4. Mapping a user-defined name to each index uuid.
+/// 5. Mapping index aliases to the name of the indexes they point to.
 ///
 /// # Implementation notes
 ///
@@ -67,6 +69,11 @@ pub struct IndexMapper {
     /// Using an UUID forces to use the index_mapping table to recover the index behind a name, ensuring
     /// consistency wrt index swapping.
     pub(crate) index_stats: Database<UuidCodec, SerdeJson<IndexStats>>,
+    /// Map an index alias with the names of the indexes it points to.
+    ///
+    /// An alias pointing to a single index can be used for reads and writes,
+    /// an alias pointing to several indexes can only be used to search.
+    pub(crate) index_aliases: Database<Str, SerdeJson<Vec<String>>>,
 
     /// Path to the folder where the LMDB environments of each index are.
     base_path: PathBuf,
@@ -134,6 +141,10 @@ pub struct IndexStats {
     /// Date of the last update of the index.
     #[serde(with = "time::serde::rfc3339")]
     pub updated_at: OffsetDateTime,
+    /// Earliest `_expiresAt` date among the documents of the index.
+    /// Option: `None` when no document of the index expires.
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub earliest_expiration: Option<OffsetDateTime>,
 }
 
 impl IndexStats {
@@ -155,6 +166,9 @@ impl IndexStats {
             field_distribution: index.field_distribution(rtxn)?,
             created_at: index.created_at(rtxn)?,
             updated_at: index.updated_at(rtxn)?,
+            earliest_expiration: index
+                .earliest_expiration(rtxn)?
+                .and_then(|timestamp| OffsetDateTime::from_unix_timestamp(timestamp).ok()),
         })
     }
 }
@@ -174,6 +188,7 @@ impl IndexMapper {
             index_map: Arc::new(RwLock::new(IndexMap::new(budget.index_count))),
             index_mapping: env.create_database(wtxn, Some(db_name::INDEX_MAPPING))?,
             index_stats: env.create_database(wtxn, Some(db_name::INDEX_STATS))?,
+            index_aliases: env.create_database(wtxn, Some(db_name::INDEX_ALIASES))?,
             base_path: options.indexes_path.clone(),
             index_base_map_size: budget.map_size,
             index_growth_amount: options.index_growth_amount,
@@ -196,34 +211,8 @@ impl IndexMapper {
                 Ok(index)
             }
             Err(Error::IndexNotFound(_)) => {
-                let uuid = Uuid::new_v4();
-                self.index_mapping.put(&mut wtxn, name, &uuid)?;
-
-                let index_path = self.base_path.join(uuid.to_string());
-                fs::create_dir_all(&index_path)?;
-
-                // Error if the UUIDv4 somehow already exists in the map, since it should be fresh.
-                // This is very unlikely to happen in practice.
-                // TODO: it would be better to lazily create the index. But we need an Index::open function for milli.
-                let index = self
-                    .index_map
-                    .write()
-                    .unwrap()
-                    .create(
-                        &uuid,
-                        &index_path,
-                        date,
-                        self.enable_mdb_writemap,
-                        self.index_base_map_size,
-                        true,
-                    )
-                    .map_err(|e| Error::from_milli(e, Some(uuid.to_string())))?;
-                let index_rtxn = index.read_txn()?;
-                let stats = crate::index_mapper::IndexStats::new(&index, &index_rtxn)
-                    .map_err(|e| Error::from_milli(e, Some(name.to_string())))?;
-                self.store_stats_of(&mut wtxn, name, &stats)?;
-                drop(index_rtxn);
-
+                let (uuid, index) = self.create_unregistered_index(&wtxn, name, date)?;
+                self.register_index(&mut wtxn, name, &uuid, &index)?;
                 wtxn.commit()?;
 
                 Ok(index)
@@ -232,6 +221,137 @@ impl IndexMapper {
         }
     }
 
+    /// Create the environment of a new index without adding it to the mapping table.
+    ///
+    /// The index is not visible until it is given to [`Self::register_index`], if it never is,
+    /// it must be removed with [`Self::delete_unregistered_index`].
+    pub fn create_unregistered_index(
+        &self,
+        rtxn: &RoTxn,
+        name: &str,
+        date: Option<(OffsetDateTime, OffsetDateTime)>,
+    ) -> Result<(Uuid, Index)> {
+        if self.index_aliases.get(rtxn, name)?.is_some() {
+            return Err(Error::IndexAliasAlreadyExists(name.to_string()));
+        }
+        let uuid = Uuid::new_v4();
+        let index_path = self.base_path.join(uuid.to_string());
+        fs::create_dir_all(&index_path)?;
+
+        // Error if the UUIDv4 somehow already exists in the map, since it should be fresh.
+        // This is very unlikely to happen in practice.
+        // TODO: it would be better to lazily create the index. But we need an Index::open function for milli.
+        let index = self
+            .index_map
+            .write()
+            .unwrap()
+            .create(
+                &uuid,
+                &index_path,
+                date,
+                self.enable_mdb_writemap,
+                self.index_base_map_size,
+                true,
+            )
+            .map_err(|e| Error::from_milli(e, Some(uuid.to_string())))?;
+
+        Ok((uuid, index))
+    }
+
+    /// Add an index created by [`Self::create_unregistered_index`] to the mapping table under `name`.
+    pub fn register_index(
+        &self,
+        wtxn: &mut RwTxn,
+        name: &str,
+        uuid: &Uuid,
+        index: &Index,
+    ) -> Result<()> {
+        self.index_mapping.put(wtxn, name, uuid)?;
+
+        let index_rtxn = index.read_txn()?;
+        let stats = crate::index_mapper::IndexStats::new(index, &index_rtxn)
+            .map_err(|e| Error::from_milli(e, Some(name.to_string())))?;
+        self.store_stats_of(wtxn, name, &stats)?;
+
+        Ok(())
+    }
+
+    /// Create an index named `new_name` holding a copy of the documents and settings of the index `name`.
+    ///
+    /// The environment of the index is copied with compaction, nothing is reindexed.
+    /// No write transaction of the main environment is held during the copy, which can take a while.
+    pub fn clone_index(&self, env: &Env<WithoutTls>, name: &str, new_name: &str) -> Result<Index> {
+        let rtxn = env.read_txn()?;
+        self.ensure_name_is_free(&rtxn, new_name)?;
+        let source = self.index(&rtxn, name)?;
+        drop(rtxn);
+
+        let uuid = Uuid::new_v4();
+        let index_path = self.base_path.join(uuid.to_string());
+        fs::create_dir_all(&index_path)?;
+
+        let remove_copy = || {
+            if let Err(e) = fs::remove_dir_all(&index_path) {
+                error!(
+                    "An error happened when removing the copy of the index {name} ({uuid}): {e}"
+                );
+            }
+        };
+
+        // LMDB opens a read transaction by itself to copy the environment,
+        // so the source index stays available during the copy.
+        if let Err(e) = source.copy_to_path(index_path.join("data.mdb"), CompactionOption::Enabled)
+        {
+            remove_copy();
+            return Err(Error::from_milli(e, Some(name.to_string())));
+        }
+        drop(source);
+
+        let index = match self.index_map.write().unwrap().create(
+            &uuid,
+            &index_path,
+            None,
+            self.enable_mdb_writemap,
+            self.index_base_map_size,
+            false,
+        ) {
+            Ok(index) => index,
+            Err(e) => {
+                remove_copy();
+                return Err(Error::from_milli(e, Some(uuid.to_string())));
+            }
+        };
+
+        // The name may have been taken while the index was copied.
+        let res = || -> Result<()> {
+            let mut wtxn = env.write_txn()?;
+            self.ensure_name_is_free(&wtxn, new_name)?;
+            self.register_index(&mut wtxn, new_name, &uuid, &index)?;
+            wtxn.commit()?;
+            Ok(())
+        }();
+
+        if let Err(e) = res {
+            drop(index);
+            // closes the copy and removes it from disk
+            self.delete_unregistered_index(uuid, new_name);
+            return Err(e);
+        }
+
+        Ok(index)
+    }
+
+    /// Fails if an index or an alias is already named `name`.
+    fn ensure_name_is_free(&self, rtxn: &RoTxn, name: &str) -> Result<()> {
+        if self.index_mapping.get(rtxn, name)?.is_some() {
+            return Err(Error::IndexAlreadyExists(name.to_string()));
+        }
+        if self.index_aliases.get(rtxn, name)?.is_some() {
+            return Err(Error::IndexAliasAlreadyExists(name.to_string()));
+        }
+        Ok(())
+    }
+
     /// Removes the index from the mapping table and the in-memory index map
     /// but keeps the associated tasks.
     pub fn delete_index(&self, mut wtxn: RwTxn, name: &str) -> Result<()> {
@@ -246,8 +366,18 @@ impl IndexMapper {
         // Once we retrieved the UUID of the index we remove it from the mapping table.
         assert!(self.index_mapping.delete(&mut wtxn, name)?);
 
+        // The aliases must not point to the deleted index anymore.
+        self.remove_index_from_aliases(&mut wtxn, name)?;
+
         wtxn.commit()?;
 
+        self.delete_unregistered_index(uuid, name);
+
+        Ok(())
+    }
+
+    /// Close the environment of an index that is not in the mapping table and remove it from disk.
+    pub fn delete_unregistered_index(&self, uuid: Uuid, name: &str) {
         let mut tries = 0;
         // Attempts to remove the index from the in-memory index map in a loop.
         //
@@ -281,7 +411,7 @@ impl IndexMapper {
                     reopen.close(&mut self.index_map.write().unwrap());
                     continue;
                 }
-                Err(None) => return Ok(()),
+                Err(None) => return,
             }
         };
 
@@ -309,8 +439,6 @@ impl IndexMapper {
                 index_map.write().unwrap().end_deletion(&uuid);
             })
             .unwrap();
-
-        Ok(())
     }
 
     pub fn exists(&self, rtxn: &RoTxn, name: &str) -> Result<bool> {
@@ -566,6 +694,85 @@ impl IndexMapper {
         Ok(())
     }
 
+    /// Return the names of the indexes an alias points to, or `None` if there is no such alias.
+    pub fn alias(&self, rtxn: &RoTxn, name: &str) -> Result<Option<Vec<String>>> {
+        Ok(self.index_aliases.get(rtxn, name)?)
+    }
+
+    /// Return all the aliases along with the names of the indexes they point to.
+    pub fn aliases(&self, rtxn: &RoTxn) -> Result<Vec<(String, Vec<String>)>> {
+        self.index_aliases
+            .iter(rtxn)?
+            .map(|res| res.map_err(Error::from).map(|(name, indexes)| (name.to_string(), indexes)))
+            .collect()
+    }
+
+    /// Create an alias or repoint it to other indexes.
+    ///
+    /// The alias can't have the name of an index and must only point to existing indexes.
+    pub fn put_alias(&self, wtxn: &mut RwTxn, name: &str, indexes: &[String]) -> Result<()> {
+        if self.index_mapping.get(wtxn, name)?.is_some() {
+            return Err(Error::IndexAlreadyExists(name.to_string()));
+        }
+        for index in indexes {
+            if self.index_aliases.get(wtxn, index)?.is_some() {
+                return Err(Error::InvalidIndexAliasIndexes {
+                    alias: name.to_string(),
+                    index: index.to_string(),
+                });
+            }
+            if self.index_mapping.get(wtxn, index)?.is_none() {
+                return Err(Error::IndexNotFound(index.to_string()));
+            }
+        }
+
+        self.index_aliases.put(wtxn, name, indexes)?;
+        Ok(())
+    }
+
+    /// Delete an alias, the indexes it points to are left untouched.
+    pub fn delete_alias(&self, wtxn: &mut RwTxn, name: &str) -> Result<()> {
+        if self.index_aliases.delete(wtxn, name)? {
+            Ok(())
+        } else {
+            Err(Error::IndexAliasNotFound(name.to_string()))
+        }
+    }
+
+    /// Return the names of the indexes behind a name that is either an index or an alias.
+    pub fn resolve_aliases(&self, rtxn: &RoTxn, name: &str) -> Result<Vec<String>> {
+        Ok(self.index_aliases.get(rtxn, name)?.unwrap_or_else(|| vec![name.to_string()]))
+    }
+
+    /// Return the name of the index behind a name that is either an index or an alias.
+    ///
+    /// Fails if the name is an alias pointing to several indexes, as it can only be used to search.
+    pub fn resolve_alias(&self, rtxn: &RoTxn, name: &str) -> Result<String> {
+        match self.index_aliases.get(rtxn, name)?.as_deref() {
+            None => Ok(name.to_string()),
+            Some([index]) => Ok(index.clone()),
+            Some(_) => Err(Error::IndexAliasReadOnly(name.to_string())),
+        }
+    }
+
+    /// Remove an index from all the aliases pointing to it,
+    /// aliases that don't point to any index anymore are deleted.
+    fn remove_index_from_aliases(&self, wtxn: &mut RwTxn, index: &str) -> Result<()> {
+        let aliases = self.aliases(wtxn)?;
+        for (alias, mut indexes) in aliases {
+            if !indexes.iter().any(|name| name == index) {
+                continue;
+            }
+            indexes.retain(|name| name != index);
+            if indexes.is_empty() {
+                self.index_aliases.delete(wtxn, &alias)?;
+            } else {
+                self.index_aliases.put(wtxn, &alias, &indexes)?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn index_exists(&self, rtxn: &RoTxn, name: &str) -> Result<bool> {
         Ok(self.index_mapping.get(rtxn, name)?.is_some())
     }
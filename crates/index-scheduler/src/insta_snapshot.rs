@@ -238,8 +238,22 @@ fn snapshot_details(d: &Details) -> String {
         Details::DocumentAdditionOrUpdate {
             received_documents,
             indexed_documents,
+            version_conflicts,
+            invalid_documents,
+            generated_document_ids,
         } => {
-            format!("{{ received_documents: {received_documents}, indexed_documents: {indexed_documents:?} }}")
+            let mut details = format!("{{ received_documents: {received_documents}, indexed_documents: {indexed_documents:?}");
+            if !version_conflicts.is_empty() {
+                details.push_str(&format!(", version_conflicts: {version_conflicts:?}"));
+            }
+            if !invalid_documents.is_empty() {
+                details.push_str(&format!(", invalid_documents: {invalid_documents:?}"));
+            }
+            if !generated_document_ids.is_empty() {
+                details.push_str(&format!(", generated_document_ids: {generated_document_ids:?}"));
+            }
+            details.push_str(" }");
+            details
         }
         Details::DocumentEdition {
             deleted_documents,
@@ -252,6 +266,30 @@ fn snapshot_details(d: &Details) -> String {
                 "{{ deleted_documents: {deleted_documents:?}, edited_documents: {edited_documents:?}, context: {context:?}, function: {function:?}, original_filter: {original_filter:?} }}"
             )
         }
+        Details::DocumentBulk {
+            received_operations,
+            indexed_documents,
+            deleted_documents,
+            generated_document_ids,
+        }
+        | Details::IndexTransaction {
+            received_operations,
+            indexed_documents,
+            deleted_documents,
+            generated_document_ids,
+        } => {
+            let mut details = format!("{{ received_operations: {received_operations}, indexed_documents: {indexed_documents:?}, deleted_documents: {deleted_documents:?}");
+            if !generated_document_ids.is_empty() {
+                details.push_str(&format!(", generated_document_ids: {generated_document_ids:?}"));
+            }
+            details.push_str(" }");
+            details
+        }
+        Details::EmbeddingRegeneration { original_filter, embedders, regenerated_documents } => {
+            format!(
+                "{{ original_filter: {original_filter:?}, embedders: {embedders:?}, regenerated_documents: {regenerated_documents:?} }}"
+            )
+        }
         Details::SettingsUpdate { settings } => {
             format!("{{ settings: {settings:?} }}")
         }
@@ -261,33 +299,35 @@ fn snapshot_details(d: &Details) -> String {
         Details::DocumentDeletion {
             provided_ids: received_document_ids,
             deleted_documents,
-        } => format!("{{ received_document_ids: {received_document_ids}, deleted_documents: {deleted_documents:?} }}"),
+            version_conflicts,
+        } => {
+            if version_conflicts.is_empty() {
+                format!("{{ received_document_ids: {received_document_ids}, deleted_documents: {deleted_documents:?} }}")
+            } else {
+                format!("{{ received_document_ids: {received_document_ids}, deleted_documents: {deleted_documents:?}, version_conflicts: {version_conflicts:?} }}")
+            }
+        }
         Details::DocumentDeletionByFilter { original_filter, deleted_documents } => format!(
-           "{{ original_filter: {original_filter}, deleted_documents: {deleted_documents:?} }}"
+            "{{ original_filter: {original_filter}, deleted_documents: {deleted_documents:?} }}"
         ),
         Details::ClearAll { deleted_documents } => {
             format!("{{ deleted_documents: {deleted_documents:?} }}")
-        },
-        Details::TaskCancelation {
-            matched_tasks,
-            canceled_tasks,
-            original_filter,
-        } => {
+        }
+        Details::TaskCancelation { matched_tasks, canceled_tasks, original_filter } => {
             format!("{{ matched_tasks: {matched_tasks:?}, canceled_tasks: {canceled_tasks:?}, original_filter: {original_filter:?} }}")
         }
-        Details::TaskDeletion {
-            matched_tasks,
-            deleted_tasks,
-            original_filter,
-        } => {
+        Details::TaskDeletion { matched_tasks, deleted_tasks, original_filter } => {
             format!("{{ matched_tasks: {matched_tasks:?}, deleted_tasks: {deleted_tasks:?}, original_filter: {original_filter:?} }}")
-        },
+        }
         Details::Dump { dump_uid } => {
             format!("{{ dump_uid: {dump_uid:?} }}")
-        },
+        }
         Details::IndexSwap { swaps } => {
             format!("{{ swaps: {swaps:?} }}")
         }
+        Details::IndexClone { new_index_uid, settings_only } => {
+            format!("{{ new_index_uid: {new_index_uid:?}, settings_only: {settings_only:?} }}")
+        }
         Details::UpgradeDatabase { from, to } => {
             format!("{{ from: {from:?}, to: {to:?} }}")
         }
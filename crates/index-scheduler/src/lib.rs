@@ -11,6 +11,7 @@ scheduler waits to be woken up to process new tasks. It wakes up when:
 1. it is launched for the first time
 2. a new task is registered
 3. a batch of tasks has been processed
+4. it has been idle for a minute, to look for expired documents
 
 It is only within this thread that the scheduler is allowed to process tasks.
 On the other hand, the publicly accessible methods of the scheduler can be
@@ -38,12 +39,12 @@ pub mod versioning;
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 pub type TaskId = u32;
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io::{self, BufReader, Read};
 use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use dump::Dump;
 pub use error::Error;
@@ -51,16 +52,19 @@ pub use features::RoFeatures;
 use flate2::bufread::GzEncoder;
 use flate2::Compression;
 use meilisearch_types::batches::Batch;
-use meilisearch_types::features::{InstanceTogglableFeatures, Network, RuntimeTogglableFeatures};
+use meilisearch_types::features::{
+    ChatCompletionSettings, InstanceTogglableFeatures, Network, RuntimeTogglableFeatures,
+};
 use meilisearch_types::heed::byteorder::BE;
 use meilisearch_types::heed::types::I128;
 use meilisearch_types::heed::{self, Env, RoTxn, WithoutTls};
 use meilisearch_types::milli::index::IndexEmbeddingConfig;
-use meilisearch_types::milli::update::IndexerConfig;
+use meilisearch_types::milli::update::{IndexerConfig, SettingsChangeEstimate};
 use meilisearch_types::milli::vector::{Embedder, EmbedderOptions, EmbeddingConfigs};
 use meilisearch_types::milli::{self, Index};
+use meilisearch_types::settings::{apply_settings_to_builder, Settings, Unchecked};
 use meilisearch_types::task_view::TaskView;
-use meilisearch_types::tasks::{KindWithContent, Task};
+use meilisearch_types::tasks::{IndexSwap, KindWithContent, Task};
 use processing::ProcessingTasks;
 pub use queue::Query;
 use queue::Queue;
@@ -76,6 +80,9 @@ pub(crate) type BEI128 = I128<BE>;
 
 const TASK_SCHEDULER_SIZE_THRESHOLD_PERCENT_INT: u64 = 40;
 
+/// How often the scheduler looks for documents whose `_expiresAt` date is past.
+const DOCUMENTS_EXPIRATION_INTERVAL: Duration = Duration::from_secs(60);
+
 #[derive(Debug)]
 pub struct IndexSchedulerOptions {
     /// The path to the version file of Meilisearch.
@@ -390,11 +397,22 @@ impl IndexScheduler {
 
                 run.scheduler.wake_up.wait_timeout(std::time::Duration::from_secs(60));
 
+                let mut last_documents_expiration = Instant::now();
                 loop {
+                    if last_documents_expiration.elapsed() >= DOCUMENTS_EXPIRATION_INTERVAL {
+                        if let Err(e) = run.enqueue_expired_documents_deletions() {
+                            tracing::error!("Could not enqueue the deletion of the expired documents: {e}");
+                        }
+                        last_documents_expiration = Instant::now();
+                    }
+
                     let ret = catch_unwind(AssertUnwindSafe(|| run.tick()));
                     match ret {
                         Ok(Ok(TickOutcome::TickAgain(_))) => (),
-                        Ok(Ok(TickOutcome::WaitForSignal)) => run.scheduler.wake_up.wait(),
+                        // wake up regularly to check for expired documents
+                        Ok(Ok(TickOutcome::WaitForSignal)) => {
+                            run.scheduler.wake_up.wait_timeout(DOCUMENTS_EXPIRATION_INTERVAL);
+                        }
                         Ok(Ok(TickOutcome::StopProcessingForever)) => break,
                         Ok(Err(e)) => {
                             tracing::error!("{e}");
@@ -451,15 +469,84 @@ impl IndexScheduler {
     /// Some configurations also can't reasonably open multiple indexes at once.
     /// If you need to fetch information from or perform an action on all indexes,
     /// see the `try_for_each_index` function.
+    ///
+    /// The name can also be an index alias pointing to a single index.
     pub fn index(&self, name: &str) -> Result<Index> {
         let rtxn = self.env.read_txn()?;
-        self.index_mapper.index(&rtxn, name)
+        let name = self.index_mapper.resolve_alias(&rtxn, name)?;
+        self.index_mapper.index(&rtxn, &name)
     }
 
     /// Return the boolean referring if index exists.
     pub fn index_exists(&self, name: &str) -> Result<bool> {
         let rtxn = self.env.read_txn()?;
-        self.index_mapper.index_exists(&rtxn, name)
+        let name = self.index_mapper.resolve_alias(&rtxn, name)?;
+        self.index_mapper.index_exists(&rtxn, &name)
+    }
+
+    /// Compute what applying the `settings` to an index would change, without applying them.
+    ///
+    /// The settings are applied in a write transaction of the index that is aborted afterward,
+    /// it waits for the index to be released if a batch is currently processing it.
+    /// The embedders are not instantiated.
+    pub fn settings_dry_run(
+        &self,
+        name: &str,
+        settings: &Settings<Unchecked>,
+    ) -> Result<SettingsChangeEstimate> {
+        let index = self.index(name)?;
+        let mut wtxn = index.write_txn()?;
+        let mut builder =
+            milli::update::Settings::new(&mut wtxn, &index, self.index_mapper.indexer_config());
+        apply_settings_to_builder(&settings.clone().check(), &mut builder);
+        let estimate =
+            builder.dry_run().map_err(|err| Error::from_milli(err, Some(name.to_string())))?;
+        wtxn.abort();
+        Ok(estimate)
+    }
+
+    /// Return the uid of the index behind a name that is either an index uid or an index alias.
+    ///
+    /// Fails if the name is an alias pointing to several indexes.
+    pub fn resolve_index_uid(&self, name: &str) -> Result<String> {
+        let rtxn = self.env.read_txn()?;
+        self.index_mapper.resolve_alias(&rtxn, name)
+    }
+
+    /// Return the uids of all the indexes behind a name that is either an index uid or an index alias.
+    pub fn resolve_index_uids(&self, name: &str) -> Result<Vec<String>> {
+        let rtxn = self.env.read_txn()?;
+        self.index_mapper.resolve_aliases(&rtxn, name)
+    }
+
+    /// Return the uids of the indexes an alias points to.
+    pub fn index_alias(&self, name: &str) -> Result<Vec<String>> {
+        let rtxn = self.env.read_txn()?;
+        self.index_mapper
+            .alias(&rtxn, name)?
+            .ok_or_else(|| Error::IndexAliasNotFound(name.to_string()))
+    }
+
+    /// Return all the index aliases along with the uids of the indexes they point to.
+    pub fn index_aliases(&self) -> Result<Vec<(String, Vec<String>)>> {
+        let rtxn = self.env.read_txn()?;
+        self.index_mapper.aliases(&rtxn)
+    }
+
+    /// Create an index alias or atomically repoint it to other indexes.
+    pub fn put_index_alias(&self, name: &str, indexes: &[String]) -> Result<()> {
+        let mut wtxn = self.env.write_txn().map_err(Error::HeedTransaction)?;
+        self.index_mapper.put_alias(&mut wtxn, name, indexes)?;
+        wtxn.commit().map_err(Error::HeedTransaction)?;
+        Ok(())
+    }
+
+    /// Delete an index alias, the indexes it points to are left untouched.
+    pub fn delete_index_alias(&self, name: &str) -> Result<()> {
+        let mut wtxn = self.env.write_txn().map_err(Error::HeedTransaction)?;
+        self.index_mapper.delete_alias(&mut wtxn, name)?;
+        wtxn.commit().map_err(Error::HeedTransaction)?;
+        Ok(())
     }
 
     /// Return the name of all indexes without opening them.
@@ -632,9 +719,10 @@ impl IndexScheduler {
     /// Register a new task in the scheduler.
     ///
     /// If it fails and data was associated with the task, it tries to delete the associated data.
+    /// The index aliases used by the task are replaced by the uid of the index they point to.
     pub fn register(
         &self,
-        kind: KindWithContent,
+        mut kind: KindWithContent,
         task_id: Option<TaskId>,
         dry_run: bool,
     ) -> Result<Task> {
@@ -647,6 +735,7 @@ impl IndexScheduler {
         }
 
         let mut wtxn = self.env.write_txn()?;
+        self.resolve_index_aliases(&wtxn, &mut kind)?;
         let task = self.queue.register(&mut wtxn, &kind, task_id, dry_run)?;
 
         // If the registered task is a task cancelation
@@ -669,6 +758,69 @@ impl IndexScheduler {
         Ok(task)
     }
 
+    /// Replace the index aliases used by a task with the uid of the index they point to,
+    /// so that the task and its history are attached to the index itself.
+    fn resolve_index_aliases(&self, rtxn: &RoTxn, kind: &mut KindWithContent) -> Result<()> {
+        use KindWithContent as K;
+        let index_uids = match kind {
+            K::IndexCreation { index_uid, .. } => {
+                if self.index_mapper.alias(rtxn, index_uid)?.is_some() {
+                    return Err(Error::IndexAliasAlreadyExists(index_uid.clone()));
+                }
+                return Ok(());
+            }
+            // Deleting an alias must not delete the indexes it points to.
+            K::IndexDeletion { index_uid } => {
+                if self.index_mapper.alias(rtxn, index_uid)?.is_some() {
+                    return Err(Error::IndexAliasNotDeletable(index_uid.clone()));
+                }
+                return Ok(());
+            }
+            K::DocumentAdditionOrUpdate { index_uid, .. }
+            | K::DocumentBulk { index_uid, .. }
+            | K::DocumentDeletion { index_uid, .. }
+            | K::DocumentDeletionByFilter { index_uid, .. }
+            | K::DocumentEdition { index_uid, .. }
+            | K::EmbeddingRegeneration { index_uid, .. }
+            | K::DocumentClear { index_uid }
+            | K::SettingsUpdate { index_uid, .. }
+            | K::IndexUpdate { index_uid, .. } => vec![index_uid],
+            K::IndexSwap { swaps } => {
+                swaps.iter_mut().flat_map(|IndexSwap { indexes: (lhs, rhs) }| [lhs, rhs]).collect()
+            }
+            K::IndexTransaction { operations, .. } => {
+                operations.iter_mut().map(|operation| &mut operation.index_uid).collect()
+            }
+            K::IndexClone { index_uid, new_index_uid, .. } => {
+                if self.index_mapper.alias(rtxn, new_index_uid)?.is_some() {
+                    return Err(Error::IndexAliasAlreadyExists(new_index_uid.clone()));
+                }
+                vec![index_uid]
+            }
+            K::TaskCancelation { .. }
+            | K::TaskDeletion { .. }
+            | K::DumpCreation { .. }
+            | K::SnapshotCreation
+            | K::UpgradeDatabase { .. } => return Ok(()),
+        };
+
+        for index_uid in index_uids {
+            *index_uid = self.index_mapper.resolve_alias(rtxn, index_uid)?;
+        }
+
+        // An index and an alias pointing to it can't be updated in the same transaction.
+        if let K::IndexTransaction { operations, .. } = kind {
+            let mut index_uids = HashSet::new();
+            for operation in operations.iter() {
+                if !index_uids.insert(operation.index_uid.as_str()) {
+                    return Err(Error::TransactionDuplicateIndexFound(operation.index_uid.clone()));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Register a new task coming from a dump in the scheduler.
     /// By taking a mutable ref we're pretty sure no one will ever import a dump while actix is running.
     pub fn register_dumped_task(&mut self) -> Result<Dump> {
@@ -782,6 +934,7 @@ impl IndexScheduler {
     }
 
     pub fn index_stats(&self, index_uid: &str) -> Result<IndexStats> {
+        let index_uid = &self.resolve_index_uid(index_uid)?;
         let is_indexing = self.is_index_processing(index_uid)?;
         let rtxn = self.read_txn()?;
         let index_stats = self.index_mapper.stats_of(&rtxn, index_uid)?;
@@ -809,6 +962,16 @@ impl IndexScheduler {
         self.features.network()
     }
 
+    pub fn put_chat_settings(&self, chat_settings: ChatCompletionSettings) -> Result<()> {
+        let wtxn = self.env.write_txn().map_err(Error::HeedTransaction)?;
+        self.features.put_chat_settings(wtxn, chat_settings)?;
+        Ok(())
+    }
+
+    pub fn chat_settings(&self) -> ChatCompletionSettings {
+        self.features.chat_settings()
+    }
+
     pub fn embedders(
         &self,
         index_uid: String,
@@ -819,7 +982,8 @@ impl IndexScheduler {
             .map(
                 |IndexEmbeddingConfig {
                      name,
-                     config: milli::vector::EmbeddingConfig { embedder_options, prompt, quantized },
+                     config:
+                         milli::vector::EmbeddingConfig { embedder_options, prompt, quantized, .. },
                      ..
                  }| {
                     let prompt = Arc::new(
@@ -841,11 +1005,13 @@ impl IndexScheduler {
 
                     // add missing embedder
                     let embedder = Arc::new(
-                        Embedder::new(embedder_options.clone(), self.scheduler.embedding_cache_cap)
-                            .map_err(meilisearch_types::milli::vector::Error::from)
-                            .map_err(|err| {
-                                Error::from_milli(err.into(), Some(index_uid.clone()))
-                            })?,
+                        Embedder::new(
+                            embedder_options.clone(),
+                            self.scheduler.embedding_cache_cap,
+                            self.indexer_config().embedding_cache.as_ref(),
+                        )
+                        .map_err(meilisearch_types::milli::vector::Error::from)
+                        .map_err(|err| Error::from_milli(err.into(), Some(index_uid.clone())))?,
                     );
                     {
                         let mut embedders = self.embedders.write().unwrap();
@@ -160,6 +160,36 @@ make_enum_progress! {
     }
 }
 
+make_enum_progress! {
+    pub enum DocumentBulkProgress {
+        RetrievingConfig,
+        ComputingDocumentChanges,
+        Indexing,
+    }
+}
+
+make_enum_progress! {
+    pub enum CloneIndexProgress {
+        CopyingTheIndex,
+    }
+}
+
+make_enum_progress! {
+    pub enum IndexTransactionProgress {
+        OpeningTheIndexes,
+        ApplyingTheOperations,
+        Committing,
+    }
+}
+
+make_enum_progress! {
+    pub enum EmbeddingRegenerationProgress {
+        RetrievingConfig,
+        ComputingDocumentChanges,
+        Indexing,
+    }
+}
+
 make_enum_progress! {
     pub enum DocumentDeletionProgress {
         RetrievingConfig,
@@ -17,14 +17,19 @@ use crate::KindWithContent;
 /// Only the non-prioritised tasks that can be grouped in a batch have a corresponding [`AutobatchKind`]
 enum AutobatchKind {
     DocumentImport { allow_index_creation: bool, primary_key: Option<String> },
+    DocumentBulk { allow_index_creation: bool },
     DocumentEdition,
+    EmbeddingRegeneration,
     DocumentDeletion { by_filter: bool },
+    VersionedDocumentDeletion,
     DocumentClear,
     Settings { allow_index_creation: bool },
     IndexCreation,
     IndexDeletion,
     IndexUpdate,
     IndexSwap,
+    IndexTransaction,
+    IndexClone,
 }
 
 impl AutobatchKind {
@@ -32,6 +37,7 @@ impl AutobatchKind {
     fn allow_index_creation(&self) -> Option<bool> {
         match self {
             AutobatchKind::DocumentImport { allow_index_creation, .. }
+            | AutobatchKind::DocumentBulk { allow_index_creation }
             | AutobatchKind::Settings { allow_index_creation, .. } => Some(*allow_index_creation),
             _ => None,
         }
@@ -51,9 +57,18 @@ impl From<KindWithContent> for AutobatchKind {
             KindWithContent::DocumentAdditionOrUpdate {
                 allow_index_creation, primary_key, ..
             } => AutobatchKind::DocumentImport { allow_index_creation, primary_key },
+            KindWithContent::DocumentBulk { allow_index_creation, .. } => {
+                AutobatchKind::DocumentBulk { allow_index_creation }
+            }
             KindWithContent::DocumentEdition { .. } => AutobatchKind::DocumentEdition,
-            KindWithContent::DocumentDeletion { .. } => {
-                AutobatchKind::DocumentDeletion { by_filter: false }
+            KindWithContent::EmbeddingRegeneration { .. } => AutobatchKind::EmbeddingRegeneration,
+            KindWithContent::DocumentDeletion { expected_versions, .. } => {
+                // the versions of the documents are checked along with the document operations
+                if expected_versions.is_empty() {
+                    AutobatchKind::DocumentDeletion { by_filter: false }
+                } else {
+                    AutobatchKind::VersionedDocumentDeletion
+                }
             }
             KindWithContent::DocumentClear { .. } => AutobatchKind::DocumentClear,
             KindWithContent::DocumentDeletionByFilter { .. } => {
@@ -68,6 +83,8 @@ impl From<KindWithContent> for AutobatchKind {
             KindWithContent::IndexCreation { .. } => AutobatchKind::IndexCreation,
             KindWithContent::IndexUpdate { .. } => AutobatchKind::IndexUpdate,
             KindWithContent::IndexSwap { .. } => AutobatchKind::IndexSwap,
+            KindWithContent::IndexTransaction { .. } => AutobatchKind::IndexTransaction,
+            KindWithContent::IndexClone { .. } => AutobatchKind::IndexClone,
             KindWithContent::TaskCancelation { .. }
             | KindWithContent::TaskDeletion { .. }
             | KindWithContent::DumpCreation { .. }
@@ -89,9 +106,16 @@ pub enum BatchKind {
         primary_key: Option<String>,
         operation_ids: Vec<TaskId>,
     },
+    DocumentBulk {
+        id: TaskId,
+        allow_index_creation: bool,
+    },
     DocumentEdition {
         id: TaskId,
     },
+    EmbeddingRegeneration {
+        id: TaskId,
+    },
     DocumentDeletion {
         deletion_ids: Vec<TaskId>,
         includes_by_filter: bool,
@@ -117,6 +141,12 @@ pub enum BatchKind {
     IndexSwap {
         id: TaskId,
     },
+    IndexTransaction {
+        id: TaskId,
+    },
+    IndexClone {
+        id: TaskId,
+    },
 }
 
 impl BatchKind {
@@ -124,6 +154,7 @@ impl BatchKind {
     fn allow_index_creation(&self) -> Option<bool> {
         match self {
             BatchKind::DocumentOperation { allow_index_creation, .. }
+            | BatchKind::DocumentBulk { allow_index_creation, .. }
             | BatchKind::ClearAndSettings { allow_index_creation, .. }
             | BatchKind::Settings { allow_index_creation, .. } => Some(*allow_index_creation),
             _ => None,
@@ -182,6 +213,20 @@ impl BatchKind {
                 )),
                 false,
             ),
+            K::IndexTransaction => (
+                Break((
+                    BatchKind::IndexTransaction { id: task_id },
+                    BatchStopReason::TaskCannotBeBatched { kind, id: task_id },
+                )),
+                false,
+            ),
+            K::IndexClone => (
+                Break((
+                    BatchKind::IndexClone { id: task_id },
+                    BatchStopReason::TaskCannotBeBatched { kind, id: task_id },
+                )),
+                false,
+            ),
             K::DocumentClear => (Continue(BatchKind::DocumentClear { ids: vec![task_id] }), false),
             K::DocumentImport { allow_index_creation, primary_key: pk }
                 if primary_key.is_none() || pk.is_none() || primary_key == pk.as_deref() =>
@@ -211,6 +256,13 @@ impl BatchKind {
                 )),
                 allow_index_creation,
             ),
+            K::DocumentBulk { allow_index_creation } => (
+                Break((
+                    BatchKind::DocumentBulk { id: task_id, allow_index_creation },
+                    BatchStopReason::TaskCannotBeBatched { kind, id: task_id },
+                )),
+                allow_index_creation,
+            ),
             K::DocumentEdition => (
                 Break((
                     BatchKind::DocumentEdition { id: task_id },
@@ -218,6 +270,13 @@ impl BatchKind {
                 )),
                 false,
             ),
+            K::EmbeddingRegeneration => (
+                Break((
+                    BatchKind::EmbeddingRegeneration { id: task_id },
+                    BatchStopReason::TaskCannotBeBatched { kind, id: task_id },
+                )),
+                false,
+            ),
             K::DocumentDeletion { by_filter: includes_by_filter } => (
                 Continue(BatchKind::DocumentDeletion {
                     deletion_ids: vec![task_id],
@@ -225,6 +284,15 @@ impl BatchKind {
                 }),
                 false,
             ),
+            // the versioned deletions are applied as document operations
+            K::VersionedDocumentDeletion => (
+                Continue(BatchKind::DocumentOperation {
+                    allow_index_creation: false,
+                    primary_key: None,
+                    operation_ids: vec![task_id],
+                }),
+                false,
+            ),
             K::Settings { allow_index_creation } => (
                 Continue(BatchKind::Settings { allow_index_creation, settings_ids: vec![task_id] }),
                 allow_index_creation,
@@ -287,7 +355,7 @@ impl BatchKind {
 
         match (self, autobatch_kind) {
             // We don't batch any of these operations
-            (this, K::IndexCreation | K::IndexUpdate | K::IndexSwap | K::DocumentEdition) => Break((this, BatchStopReason::TaskCannotBeBatched { kind, id })),
+            (this, K::IndexCreation | K::IndexUpdate | K::IndexSwap | K::IndexTransaction | K::IndexClone | K::DocumentBulk { .. } | K::DocumentEdition | K::EmbeddingRegeneration) => Break((this, BatchStopReason::TaskCannotBeBatched { kind, id })),
             // We must not batch tasks that don't have the same index creation rights if the index doesn't already exists.
             (this, kind) if !index_already_exists && this.allow_index_creation() == Some(false) && kind.allow_index_creation() == Some(true) => {
                 Break((this, BatchStopReason::IndexCreationMismatch { id }))
@@ -321,7 +389,7 @@ impl BatchKind {
             }
             (
                 this @ BatchKind::DocumentClear { .. },
-                K::DocumentImport { .. } | K::Settings { .. },
+                K::DocumentImport { .. } | K::VersionedDocumentDeletion | K::Settings { .. },
             ) => Break((this, BatchStopReason::DocumentOperationWithSettings { id })),
             (
                 BatchKind::DocumentOperation { allow_index_creation: _, primary_key: _, mut operation_ids },
@@ -345,7 +413,7 @@ impl BatchKind {
             }
             (
                 BatchKind::DocumentOperation { allow_index_creation, primary_key: _, mut operation_ids },
-                K::DocumentDeletion { by_filter: false },
+                K::DocumentDeletion { by_filter: false } | K::VersionedDocumentDeletion,
             ) => {
                 operation_ids.push(id);
 
@@ -374,8 +442,21 @@ impl BatchKind {
             // we can't autobatch the deletion and import if the document deletion contained a filter
             (
                 this @ BatchKind::DocumentDeletion { deletion_ids: _, includes_by_filter: true },
-                K::DocumentImport { .. }
+                K::DocumentImport { .. } | K::VersionedDocumentDeletion
             ) => Break((this, BatchStopReason::DeletionByFilterWithDocumentOperation { id })),
+            // the versioned deletions are applied as document operations
+            (
+                BatchKind::DocumentDeletion { mut deletion_ids, includes_by_filter: false },
+                K::VersionedDocumentDeletion
+            ) => {
+                deletion_ids.push(id);
+
+                Continue(BatchKind::DocumentOperation {
+                    allow_index_creation: false,
+                    primary_key: pk,
+                    operation_ids: deletion_ids,
+                })
+            }
             // we can autobatch the deletion and import if the index already exists
             (
                 BatchKind::DocumentDeletion { mut deletion_ids, includes_by_filter: false },
@@ -425,7 +506,7 @@ impl BatchKind {
             }),
             (
                 this @ BatchKind::Settings { .. },
-                K::DocumentImport { .. } | K::DocumentDeletion { .. },
+                K::DocumentImport { .. } | K::DocumentDeletion { .. } | K::VersionedDocumentDeletion,
             ) => Break((this, BatchStopReason::SettingsWithDocumentOperation { id })),
             (
                 BatchKind::Settings { mut settings_ids, allow_index_creation },
@@ -449,7 +530,7 @@ impl BatchKind {
                     allow_index_creation,
                 })
             }
-            (this @ BatchKind::ClearAndSettings { .. }, K::DocumentImport { .. }) => Break((this, BatchStopReason::SettingsWithDocumentOperation { id })),
+            (this @ BatchKind::ClearAndSettings { .. }, K::DocumentImport { .. } | K::VersionedDocumentDeletion) => Break((this, BatchStopReason::SettingsWithDocumentOperation { id })),
             (
                 BatchKind::ClearAndSettings {
                     mut other,
@@ -482,7 +563,11 @@ impl BatchKind {
                 | BatchKind::IndexDeletion { .. }
                 | BatchKind::IndexUpdate { .. }
                 | BatchKind::IndexSwap { .. }
-                | BatchKind::DocumentEdition { .. },
+                | BatchKind::IndexTransaction { .. }
+                | BatchKind::IndexClone { .. }
+                | BatchKind::DocumentBulk { .. }
+                | BatchKind::DocumentEdition { .. }
+                | BatchKind::EmbeddingRegeneration { .. },
                 _,
             ) => {
                 unreachable!()
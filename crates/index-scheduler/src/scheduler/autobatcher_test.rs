@@ -47,6 +47,15 @@ fn doc_del() -> KindWithContent {
     KindWithContent::DocumentDeletion {
         index_uid: String::from("doggo"),
         documents_ids: Vec::new(),
+        expected_versions: Default::default(),
+    }
+}
+
+fn doc_del_ver() -> KindWithContent {
+    KindWithContent::DocumentDeletion {
+        index_uid: String::from("doggo"),
+        documents_ids: vec![String::from("1")],
+        expected_versions: [(String::from("1"), 1)].into_iter().collect(),
     }
 }
 
@@ -228,6 +237,19 @@ fn simple_different_document_operations_autobatch_together() {
     debug_snapshot!(autobatch_from(true, None, [doc_del_fil(), idx_swap()]), @"Some((DocumentDeletion { deletion_ids: [0], includes_by_filter: true }, false, Some(TaskCannotBeBatched { kind: IndexSwap, id: 1 })))");
 }
 
+#[test]
+fn versioned_deletions_autobatch_as_document_operations() {
+    // the versions of the documents are checked by the document operations
+    debug_snapshot!(autobatch_from(true, None, [doc_del_ver()]), @"Some((DocumentOperation { allow_index_creation: false, primary_key: None, operation_ids: [0] }, false, None))");
+    debug_snapshot!(autobatch_from(true, None, [doc_del(), doc_del_ver()]), @"Some((DocumentOperation { allow_index_creation: false, primary_key: None, operation_ids: [0, 1] }, false, None))");
+    debug_snapshot!(autobatch_from(true, None, [doc_del_ver(), doc_imp(ReplaceDocuments, true, None)]), @"Some((DocumentOperation { allow_index_creation: false, primary_key: None, operation_ids: [0, 1] }, false, None))");
+
+    // they can't be batched with the deletions by filter and the clears
+    debug_snapshot!(autobatch_from(true, None, [doc_del_fil(), doc_del_ver()]), @"Some((DocumentDeletion { deletion_ids: [0], includes_by_filter: true }, false, Some(DeletionByFilterWithDocumentOperation { id: 1 })))");
+    debug_snapshot!(autobatch_from(true, None, [doc_del_ver(), doc_del_fil()]), @"Some((DocumentOperation { allow_index_creation: false, primary_key: None, operation_ids: [0] }, false, Some(DocumentOperationWithDeletionByFilter { id: 1 })))");
+    debug_snapshot!(autobatch_from(true, None, [doc_clr(), doc_del_ver()]), @"Some((DocumentClear { ids: [0] }, false, Some(DocumentOperationWithSettings { id: 1 })))");
+}
+
 #[test]
 fn document_addition_doesnt_batch_with_settings() {
     // simple case
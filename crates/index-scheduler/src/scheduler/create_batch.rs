@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fmt;
 
 use meilisearch_types::heed::RoTxn;
@@ -47,6 +48,15 @@ pub(crate) enum Batch {
     IndexSwap {
         task: Task,
     },
+    IndexTransaction {
+        task: Task,
+    },
+    IndexClone {
+        index_uid: String,
+        new_index_uid: String,
+        settings_only: bool,
+        task: Task,
+    },
     UpgradeDatabase {
         tasks: Vec<Task>,
     },
@@ -56,7 +66,8 @@ pub(crate) enum Batch {
 pub(crate) enum DocumentOperation {
     Replace(Uuid),
     Update(Uuid),
-    Delete(Vec<String>),
+    /// The ids of the documents to delete along with the versions they are expected to be at.
+    Delete(Vec<String>, BTreeMap<String, u64>),
 }
 
 /// A [batch](Batch) that combines multiple tasks operating on an index.
@@ -68,10 +79,18 @@ pub(crate) enum IndexOperation {
         operations: Vec<DocumentOperation>,
         tasks: Vec<Task>,
     },
+    DocumentBulk {
+        index_uid: String,
+        task: Task,
+    },
     DocumentEdition {
         index_uid: String,
         task: Task,
     },
+    EmbeddingRegeneration {
+        index_uid: String,
+        task: Task,
+    },
     DocumentDeletion {
         index_uid: String,
         tasks: Vec<Task>,
@@ -103,7 +122,8 @@ impl Batch {
             Batch::TaskCancelation { task, .. }
             | Batch::Dump(task)
             | Batch::IndexCreation { task, .. }
-            | Batch::IndexUpdate { task, .. } => {
+            | Batch::IndexUpdate { task, .. }
+            | Batch::IndexClone { task, .. } => {
                 RoaringBitmap::from_sorted_iter(std::iter::once(task.uid)).unwrap()
             }
             Batch::SnapshotCreation(tasks)
@@ -119,7 +139,9 @@ impl Batch {
                 | IndexOperation::DocumentClear { tasks, .. } => {
                     RoaringBitmap::from_iter(tasks.iter().map(|task| task.uid))
                 }
-                IndexOperation::DocumentEdition { task, .. } => {
+                IndexOperation::DocumentBulk { task, .. }
+                | IndexOperation::DocumentEdition { task, .. }
+                | IndexOperation::EmbeddingRegeneration { task, .. } => {
                     RoaringBitmap::from_sorted_iter(std::iter::once(task.uid)).unwrap()
                 }
                 IndexOperation::DocumentClearAndSetting {
@@ -128,7 +150,7 @@ impl Batch {
                     ..
                 } => RoaringBitmap::from_iter(tasks.iter().chain(other).map(|task| task.uid)),
             },
-            Batch::IndexSwap { task } => {
+            Batch::IndexSwap { task } | Batch::IndexTransaction { task } => {
                 RoaringBitmap::from_sorted_iter(std::iter::once(task.uid)).unwrap()
             }
         }
@@ -143,11 +165,13 @@ impl Batch {
             | SnapshotCreation(_)
             | Dump(_)
             | UpgradeDatabase { .. }
-            | IndexSwap { .. } => None,
+            | IndexSwap { .. }
+            | IndexTransaction { .. } => None,
             IndexOperation { op, .. } => Some(op.index_uid()),
             IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
-            | IndexDeletion { index_uid, .. } => Some(index_uid),
+            | IndexDeletion { index_uid, .. }
+            | IndexClone { index_uid, .. } => Some(index_uid),
         }
     }
 }
@@ -167,6 +191,8 @@ impl fmt::Display for Batch {
             Batch::IndexUpdate { .. } => f.write_str("IndexUpdate")?,
             Batch::IndexDeletion { .. } => f.write_str("IndexDeletion")?,
             Batch::IndexSwap { .. } => f.write_str("IndexSwap")?,
+            Batch::IndexTransaction { .. } => f.write_str("IndexTransaction")?,
+            Batch::IndexClone { .. } => f.write_str("IndexClone")?,
             Batch::UpgradeDatabase { .. } => f.write_str("UpgradeDatabase")?,
         };
         match index_uid {
@@ -180,7 +206,9 @@ impl IndexOperation {
     pub fn index_uid(&self) -> &str {
         match self {
             IndexOperation::DocumentOperation { index_uid, .. }
+            | IndexOperation::DocumentBulk { index_uid, .. }
             | IndexOperation::DocumentEdition { index_uid, .. }
+            | IndexOperation::EmbeddingRegeneration { index_uid, .. }
             | IndexOperation::DocumentDeletion { index_uid, .. }
             | IndexOperation::DocumentClear { index_uid, .. }
             | IndexOperation::Settings { index_uid, .. }
@@ -195,9 +223,13 @@ impl fmt::Display for IndexOperation {
             IndexOperation::DocumentOperation { .. } => {
                 f.write_str("IndexOperation::DocumentOperation")
             }
+            IndexOperation::DocumentBulk { .. } => f.write_str("IndexOperation::DocumentBulk"),
             IndexOperation::DocumentEdition { .. } => {
                 f.write_str("IndexOperation::DocumentEdition")
             }
+            IndexOperation::EmbeddingRegeneration { .. } => {
+                f.write_str("IndexOperation::EmbeddingRegeneration")
+            }
             IndexOperation::DocumentDeletion { .. } => {
                 f.write_str("IndexOperation::DocumentDeletion")
             }
@@ -237,6 +269,15 @@ impl IndexScheduler {
                 },
                 must_create_index,
             })),
+            BatchKind::DocumentBulk { id, .. } => {
+                let mut task =
+                    self.queue.tasks.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                current_batch.processing(Some(&mut task));
+                Ok(Some(Batch::IndexOperation {
+                    op: IndexOperation::DocumentBulk { index_uid, task },
+                    must_create_index,
+                }))
+            }
             BatchKind::DocumentEdition { id } => {
                 let mut task =
                     self.queue.tasks.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
@@ -254,6 +295,23 @@ impl IndexScheduler {
                     _ => unreachable!(),
                 }
             }
+            BatchKind::EmbeddingRegeneration { id } => {
+                let mut task =
+                    self.queue.tasks.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                current_batch.processing(Some(&mut task));
+                match &task.kind {
+                    KindWithContent::EmbeddingRegeneration { index_uid, .. } => {
+                        Ok(Some(Batch::IndexOperation {
+                            op: IndexOperation::EmbeddingRegeneration {
+                                index_uid: index_uid.clone(),
+                                task,
+                            },
+                            must_create_index: false,
+                        }))
+                    }
+                    _ => unreachable!(),
+                }
+            }
             BatchKind::DocumentOperation { operation_ids, .. } => {
                 let tasks = self.queue.get_existing_tasks_for_processing_batch(
                     rtxn,
@@ -287,8 +345,15 @@ impl IndexScheduler {
                             }
                             _ => unreachable!("Unknown document merging method"),
                         },
-                        KindWithContent::DocumentDeletion { ref documents_ids, .. } => {
-                            operations.push(DocumentOperation::Delete(documents_ids.clone()));
+                        KindWithContent::DocumentDeletion {
+                            ref documents_ids,
+                            ref expected_versions,
+                            ..
+                        } => {
+                            operations.push(DocumentOperation::Delete(
+                                documents_ids.clone(),
+                                expected_versions.clone(),
+                            ));
                         }
                         _ => unreachable!(),
                     }
@@ -419,6 +484,24 @@ impl IndexScheduler {
                 current_batch.processing(Some(&mut task));
                 Ok(Some(Batch::IndexSwap { task }))
             }
+            BatchKind::IndexTransaction { id } => {
+                let mut task =
+                    self.queue.tasks.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                current_batch.processing(Some(&mut task));
+                Ok(Some(Batch::IndexTransaction { task }))
+            }
+            BatchKind::IndexClone { id } => {
+                let mut task =
+                    self.queue.tasks.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                current_batch.processing(Some(&mut task));
+                let (new_index_uid, settings_only) = match &task.kind {
+                    KindWithContent::IndexClone { new_index_uid, settings_only, .. } => {
+                        (new_index_uid.clone(), *settings_only)
+                    }
+                    _ => unreachable!(),
+                };
+                Ok(Some(Batch::IndexClone { index_uid, new_index_uid, settings_only, task }))
+            }
         }
     }
 
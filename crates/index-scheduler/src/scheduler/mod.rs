@@ -4,7 +4,9 @@ mod autobatcher_test;
 mod create_batch;
 mod process_batch;
 mod process_dump_creation;
+mod process_index_clone;
 mod process_index_operation;
+mod process_index_transaction;
 mod process_snapshot_creation;
 mod process_upgrade;
 #[cfg(test)]
@@ -24,12 +26,14 @@ use convert_case::{Case, Casing as _};
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::heed::{Env, WithoutTls};
 use meilisearch_types::milli;
-use meilisearch_types::tasks::Status;
+use meilisearch_types::milli::constants::RESERVED_EXPIRES_AT_FIELD_NAME;
+use meilisearch_types::tasks::{Kind, KindWithContent, Status};
 use process_batch::ProcessBatchInfo;
 use rayon::current_num_threads;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use roaring::RoaringBitmap;
 use synchronoise::SignalEvent;
+use time::OffsetDateTime;
 
 use crate::processing::{AtomicTaskStep, BatchProgress};
 use crate::{Error, IndexScheduler, IndexSchedulerOptions, Result, TickOutcome};
@@ -450,4 +454,64 @@ impl IndexScheduler {
             Ok(TickOutcome::TickAgain(processed_tasks))
         }
     }
+
+    /// Registers a deletion of the expired documents of every index containing
+    /// expired documents.
+    ///
+    /// The indexes are not opened: their earliest expiration date is read from their stats.
+    ///
+    /// The documents are deleted by filtering on their `_expiresAt` field when the task
+    /// is processed, so that the documents whose expiration date has been pushed back
+    /// in the meantime are kept.
+    ///
+    /// The indexes on which a document deletion is already enqueued are skipped,
+    /// their expired documents will be deleted during the next call.
+    pub(crate) fn enqueue_expired_documents_deletions(&self) -> Result<()> {
+        let now = OffsetDateTime::now_utc();
+
+        let rtxn = self.env.read_txn()?;
+        let enqueued_deletions = self.queue.tasks.get_status(&rtxn, Status::Enqueued)?
+            & self.queue.tasks.get_kind(&rtxn, Kind::DocumentDeletion)?;
+
+        let mut deletions = Vec::new();
+        for index_uid in self.index_mapper.index_names(&rtxn)? {
+            let stats = self.index_mapper.stats_of(&rtxn, &index_uid)?;
+            if !stats.earliest_expiration.is_some_and(|expiration| expiration <= now) {
+                continue;
+            }
+            if self
+                .queue
+                .tasks
+                .index_tasks(&rtxn, &index_uid)?
+                .intersection_len(&enqueued_deletions)
+                != 0
+            {
+                continue;
+            }
+
+            deletions.push(KindWithContent::DocumentDeletionByFilter {
+                index_uid,
+                filter_expr: serde_json::Value::String(format!(
+                    "{RESERVED_EXPIRES_AT_FIELD_NAME} <= {}",
+                    now.unix_timestamp()
+                )),
+            });
+        }
+        drop(rtxn);
+
+        if deletions.is_empty() {
+            return Ok(());
+        }
+
+        let number_of_deletions = deletions.len();
+        for deletion in deletions {
+            self.register(deletion, None, false)?;
+        }
+
+        tracing::info!(
+            "Enqueued the deletion of the expired documents of {number_of_deletions} indexes."
+        );
+
+        Ok(())
+    }
 }
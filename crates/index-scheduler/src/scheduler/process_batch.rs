@@ -237,6 +237,7 @@ impl IndexScheduler {
                         self.index_mapper.indexer_config(),
                     );
                     builder.set_primary_key(primary_key);
+                    builder.set_progress(progress.clone());
                     let must_stop_processing = self.scheduler.must_stop_processing.clone();
                     builder
                         .execute(
@@ -361,6 +362,12 @@ impl IndexScheduler {
                 task.status = Status::Succeeded;
                 Ok((vec![task], ProcessBatchInfo::default()))
             }
+            Batch::IndexTransaction { task } => self
+                .process_index_transaction(progress, task)
+                .map(|tasks| (tasks, ProcessBatchInfo::default())),
+            Batch::IndexClone { index_uid, new_index_uid, settings_only, task } => self
+                .process_index_clone(progress, index_uid, new_index_uid, settings_only, task)
+                .map(|tasks| (tasks, ProcessBatchInfo::default())),
             Batch::UpgradeDatabase { mut tasks } => {
                 let KindWithContent::UpgradeDatabase { from } = tasks.last().unwrap().kind else {
                     unreachable!();
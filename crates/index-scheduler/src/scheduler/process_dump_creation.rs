@@ -257,6 +257,8 @@ impl IndexScheduler {
         dump.create_experimental_features(features)?;
         let network = self.network();
         dump.create_network(network)?;
+        let index_aliases = self.index_aliases()?.into_iter().collect();
+        dump.create_index_aliases(index_aliases)?;
 
         let dump_uid = started_at.format(format_description!(
                     "[year repr:full][month repr:numerical][day padding:zero]-[hour padding:zero][minute padding:zero][second padding:zero][subsecond digits:3]"
@@ -0,0 +1,120 @@
+use meilisearch_types::milli::progress::Progress;
+use meilisearch_types::milli::{self};
+use meilisearch_types::settings::{apply_settings_to_builder, settings, SecretPolicy};
+use meilisearch_types::tasks::{Details, Status, Task};
+use meilisearch_types::Index;
+
+use crate::processing::CloneIndexProgress;
+use crate::{Error, IndexScheduler, Result};
+
+impl IndexScheduler {
+    /// Create the index `new_index_uid` as a copy of the index `index_uid`.
+    ///
+    /// The whole environment of the index is copied, unless only its settings must be copied,
+    /// in which case they are applied to a newly created empty index.
+    pub(super) fn process_index_clone(
+        &self,
+        progress: Progress,
+        index_uid: String,
+        new_index_uid: String,
+        settings_only: bool,
+        mut task: Task,
+    ) -> Result<Vec<Task>> {
+        progress.update_progress(CloneIndexProgress::CopyingTheIndex);
+
+        let index = if settings_only {
+            self.clone_index_settings(&index_uid, &new_index_uid)?
+        } else {
+            self.index_mapper.clone_index(&self.env, &index_uid, &new_index_uid)?
+        };
+
+        task.status = Status::Succeeded;
+        task.details =
+            Some(Details::IndexClone { new_index_uid: new_index_uid.clone(), settings_only });
+
+        // The stats of the copy are already stored when the whole index is copied.
+        if settings_only {
+            // Since the task has already been processed and this is a non-critical operation,
+            // if it fails we should not fail the entire batch.
+            let res = || -> Result<()> {
+                let mut wtxn = self.env.write_txn()?;
+                let index_rtxn = index.read_txn()?;
+                let stats = crate::index_mapper::IndexStats::new(&index, &index_rtxn)
+                    .map_err(|e| Error::from_milli(e, Some(new_index_uid.clone())))?;
+                self.index_mapper.store_stats_of(&mut wtxn, &new_index_uid, &stats)?;
+                wtxn.commit()?;
+                Ok(())
+            }();
+
+            if let Err(e) = res {
+                tracing::error!(
+                    error = &e as &dyn std::error::Error,
+                    "Could not write the stats of the index"
+                );
+            }
+        }
+
+        Ok(vec![task])
+    }
+
+    /// Create an empty index with the primary key and settings of another index.
+    fn clone_index_settings(&self, index_uid: &str, new_index_uid: &str) -> Result<Index> {
+        let rtxn = self.env.read_txn()?;
+        let source = self.index_mapper.index(&rtxn, index_uid)?;
+        if self.index_mapper.exists(&rtxn, new_index_uid)? {
+            return Err(Error::IndexAlreadyExists(new_index_uid.to_string()));
+        }
+        drop(rtxn);
+
+        let source_rtxn = source.read_txn()?;
+        let source_settings = settings(&source, &source_rtxn, SecretPolicy::RevealSecrets)
+            .map_err(|e| Error::from_milli(e, Some(index_uid.to_string())))?;
+        let primary_key = source.primary_key(&source_rtxn)?.map(String::from);
+        drop(source_rtxn);
+
+        let wtxn = self.env.write_txn()?;
+        let index = self.index_mapper.create_index(wtxn, new_index_uid, None)?;
+
+        let ret = || -> Result<()> {
+            let mut index_wtxn = index.write_txn()?;
+            let mut builder = milli::update::Settings::new(
+                &mut index_wtxn,
+                &index,
+                self.index_mapper.indexer_config(),
+            );
+            if let Some(primary_key) = primary_key {
+                builder.set_primary_key(primary_key);
+            }
+            apply_settings_to_builder(&source_settings, &mut builder);
+            let must_stop_processing = self.scheduler.must_stop_processing.clone();
+            builder
+                .execute(
+                    |indexing_step| tracing::debug!(update = ?indexing_step),
+                    || must_stop_processing.get(),
+                )
+                .map_err(|e| Error::from_milli(e, Some(new_index_uid.to_string())))?;
+            index_wtxn.commit()?;
+            Ok(())
+        }();
+
+        // The copy must not be left half-configured.
+        if let Err(e) = ret {
+            drop(index);
+            let res = self
+                .env
+                .write_txn()
+                .map_err(Error::from)
+                .and_then(|wtxn| self.index_mapper.delete_index(wtxn, new_index_uid));
+            if let Err(e) = res {
+                tracing::error!(
+                    index = %new_index_uid,
+                    error = &e as &dyn std::error::Error,
+                    "Could not delete the copy of an index that failed to be cloned"
+                );
+            }
+            return Err(e);
+        }
+
+        Ok(index)
+    }
+}
@@ -2,18 +2,23 @@ use bumpalo::collections::CollectIn;
 use bumpalo::Bump;
 use meilisearch_types::heed::RwTxn;
 use meilisearch_types::milli::documents::PrimaryKey;
+use meilisearch_types::milli::index::{db_name, main_key};
 use meilisearch_types::milli::progress::Progress;
-use meilisearch_types::milli::update::new::indexer::{self, UpdateByFunction};
+use meilisearch_types::milli::update::new::indexer::{
+    self, RegenerateEmbeddings, UpdateByFunction,
+};
 use meilisearch_types::milli::update::DocumentAdditionResult;
 use meilisearch_types::milli::{self, ChannelCongestion, Filter};
-use meilisearch_types::settings::apply_settings_to_builder;
-use meilisearch_types::tasks::{Details, KindWithContent, Status, Task};
+use meilisearch_types::settings::{apply_settings_to_builder, Settings, Unchecked};
+use meilisearch_types::tasks::{BulkOperation, Details, KindWithContent, Status, Task};
 use meilisearch_types::Index;
 use roaring::RoaringBitmap;
+use serde::de::IgnoredAny;
 
 use super::create_batch::{DocumentOperation, IndexOperation};
 use crate::processing::{
-    DocumentDeletionProgress, DocumentEditionProgress, DocumentOperationProgress, SettingsProgress,
+    DocumentBulkProgress, DocumentDeletionProgress, DocumentEditionProgress,
+    DocumentOperationProgress, EmbeddingRegenerationProgress, SettingsProgress,
 };
 use crate::{Error, IndexScheduler, Result};
 
@@ -103,12 +108,24 @@ impl IndexScheduler {
                                 .update_documents(mmap)
                                 .map_err(|e| Error::from_milli(e, Some(index_uid.clone())))?;
                         }
-                        DocumentOperation::Delete(document_ids) => {
+                        DocumentOperation::Delete(document_ids, expected_versions) => {
                             let document_ids: bumpalo::collections::vec::Vec<_> = document_ids
                                 .iter()
                                 .map(|s| &*indexer_alloc.alloc_str(s))
                                 .collect_in(&indexer_alloc);
-                            indexer.delete_documents(document_ids.into_bump_slice());
+                            if expected_versions.is_empty() {
+                                indexer.delete_documents(document_ids.into_bump_slice());
+                            } else {
+                                let expected_versions: bumpalo::collections::vec::Vec<_> =
+                                    document_ids
+                                        .iter()
+                                        .map(|id| expected_versions.get(*id).copied())
+                                        .collect_in(&indexer_alloc);
+                                indexer.delete_documents_with_versions(
+                                    document_ids.into_bump_slice(),
+                                    expected_versions.into_bump_slice(),
+                                );
+                            }
                         }
                     }
                 }
@@ -140,17 +157,27 @@ impl IndexScheduler {
                         None => task.status = Status::Succeeded,
                     }
 
+                    let version_conflicts =
+                        stats.version_conflicts.into_iter().map(Into::into).collect();
                     task.details = match task.details {
                         Some(Details::DocumentAdditionOrUpdate { received_documents, .. }) => {
                             Some(Details::DocumentAdditionOrUpdate {
                                 received_documents,
                                 indexed_documents: Some(stats.document_count),
+                                version_conflicts,
+                                invalid_documents: stats
+                                    .invalid_documents
+                                    .into_iter()
+                                    .map(Into::into)
+                                    .collect(),
+                                generated_document_ids: stats.generated_document_ids,
                             })
                         }
                         Some(Details::DocumentDeletion { provided_ids, .. }) => {
                             Some(Details::DocumentDeletion {
                                 provided_ids,
                                 deleted_documents: Some(stats.document_count),
+                                version_conflicts,
                             })
                         }
                         _ => {
@@ -193,6 +220,59 @@ impl IndexScheduler {
 
                 Ok((tasks, congestion))
             }
+            IndexOperation::DocumentBulk { index_uid, mut task } => {
+                progress.update_progress(DocumentBulkProgress::RetrievingConfig);
+
+                let (primary_key, content_file, operations) =
+                    if let KindWithContent::DocumentBulk {
+                        primary_key,
+                        content_file,
+                        operations,
+                        ..
+                    } = &task.kind
+                    {
+                        (primary_key.as_deref(), *content_file, operations)
+                    } else {
+                        unreachable!()
+                    };
+
+                let content_file = self.queue.file_store.get_update(content_file)?;
+                let mmap = unsafe { memmap2::Mmap::map(&content_file)? };
+                #[cfg(unix)]
+                mmap.advise(memmap2::Advice::Sequential)?;
+
+                let BulkOperationsOutcome {
+                    indexed_documents,
+                    deleted_documents,
+                    generated_document_ids,
+                    congestion,
+                } = self.apply_bulk_operations(
+                    index_wtxn,
+                    index,
+                    &index_uid,
+                    primary_key,
+                    &mmap,
+                    operations,
+                    progress,
+                )?;
+
+                tracing::info!(indexed_documents, deleted_documents, processed_in = ?started_processing_at.elapsed(), "document bulk done");
+
+                let received_operations = match task.details {
+                    Some(Details::DocumentBulk { received_operations, .. }) => received_operations,
+                    // In the case of a `documentBulk` the details MUST be set
+                    _ => unreachable!(),
+                };
+                task.status = Status::Succeeded;
+                task.details = Some(Details::DocumentBulk {
+                    received_operations,
+                    indexed_documents: Some(indexed_documents),
+                    deleted_documents: Some(deleted_documents),
+                    generated_document_ids,
+                });
+
+                Ok((vec![task], Some(congestion)))
+            }
             IndexOperation::DocumentEdition { index_uid, mut task } => {
                 progress.update_progress(DocumentEditionProgress::RetrievingConfig);
 
@@ -328,6 +408,121 @@ impl IndexScheduler {
 
                 Ok((vec![task], congestion))
             }
+            IndexOperation::EmbeddingRegeneration { index_uid, mut task } => {
+                progress.update_progress(EmbeddingRegenerationProgress::RetrievingConfig);
+
+                let (filter, embedders_to_regenerate) =
+                    if let KindWithContent::EmbeddingRegeneration {
+                        filter_expr, embedders, ..
+                    } = &task.kind
+                    {
+                        (filter_expr, embedders)
+                    } else {
+                        unreachable!()
+                    };
+
+                let embedding_configs = index
+                    .embedding_configs(index_wtxn)
+                    .map_err(|err| Error::from_milli(err, Some(index_uid.clone())))?;
+                if let Some(embedders_to_regenerate) = embedders_to_regenerate {
+                    if let Some(unknown) = embedders_to_regenerate
+                        .iter()
+                        .find(|name| !embedding_configs.iter().any(|config| &config.name == *name))
+                    {
+                        return Err(Error::from_milli(
+                            milli::UserError::InvalidEmbeddingRegenerationEmbedder(
+                                unknown.to_owned(),
+                            )
+                            .into(),
+                            Some(index_uid.clone()),
+                        ));
+                    }
+                }
+
+                let candidates = match filter.as_ref().map(Filter::from_json) {
+                    Some(Ok(Some(filter))) => filter
+                        .evaluate(index_wtxn, index)
+                        .map_err(|err| Error::from_milli(err, Some(index_uid.clone())))?,
+                    None | Some(Ok(None)) => index.documents_ids(index_wtxn)?,
+                    Some(Err(e)) => return Err(Error::from_milli(e, Some(index_uid.clone()))),
+                };
+
+                let (original_filter, embedders) =
+                    if let Some(Details::EmbeddingRegeneration {
+                        original_filter, embedders, ..
+                    }) = task.details
+                    {
+                        (original_filter, embedders)
+                    } else {
+                        // In the case of an `embeddingRegeneration` the details MUST be set
+                        unreachable!();
+                    };
+
+                if candidates.is_empty() {
+                    task.status = Status::Succeeded;
+                    task.details = Some(Details::EmbeddingRegeneration {
+                        original_filter,
+                        embedders,
+                        regenerated_documents: Some(0),
+                    });
+
+                    return Ok((vec![task], None));
+                }
+
+                let rtxn = index.read_txn()?;
+                let db_fields_ids_map = index.fields_ids_map(&rtxn)?;
+                let mut new_fields_ids_map = db_fields_ids_map.clone();
+                let primary_key = index.primary_key(&rtxn)?.ok_or_else(|| {
+                    Error::from_milli(
+                        milli::InternalError::DatabaseMissingEntry {
+                            db_name: db_name::MAIN,
+                            key: Some(main_key::PRIMARY_KEY_KEY),
+                        }
+                        .into(),
+                        Some(index_uid.clone()),
+                    )
+                })?;
+
+                let primary_key =
+                    PrimaryKey::new_or_insert(primary_key, &mut new_fields_ids_map)
+                        .map_err(|err| Error::from_milli(err.into(), Some(index_uid.clone())))?;
+
+                let indexer_config = self.index_mapper.indexer_config();
+                let pool = &indexer_config.thread_pool;
+
+                let candidates_count = candidates.len();
+                progress.update_progress(EmbeddingRegenerationProgress::ComputingDocumentChanges);
+                let indexer = RegenerateEmbeddings::new(candidates, embedders.clone());
+                let document_changes = indexer.into_changes(&primary_key);
+                let embedders_configs = self.embedders(index_uid.clone(), embedding_configs)?;
+
+                progress.update_progress(EmbeddingRegenerationProgress::Indexing);
+                let congestion = indexer::index(
+                    index_wtxn,
+                    index,
+                    pool,
+                    indexer_config.grenad_parameters(),
+                    &db_fields_ids_map,
+                    new_fields_ids_map,
+                    None, // cannot change primary key in EmbeddingRegeneration
+                    &document_changes,
+                    embedders_configs,
+                    &|| must_stop_processing.get(),
+                    progress,
+                )
+                .map_err(|err| Error::from_milli(err, Some(index_uid.clone())))?;
+
+                tracing::info!(regenerated_documents = candidates_count, processed_in = ?started_processing_at.elapsed(), "embedding regeneration done");
+
+                task.status = Status::Succeeded;
+                task.details = Some(Details::EmbeddingRegeneration {
+                    original_filter,
+                    embedders,
+                    regenerated_documents: Some(candidates_count),
+                });
+
+                Ok((vec![task], Some(congestion)))
+            }
             IndexOperation::DocumentDeletion { mut tasks, index_uid } => {
                 progress.update_progress(DocumentDeletionProgress::RetrievingConfig);
 
@@ -339,7 +534,10 @@ impl IndexScheduler {
                     task.status = Status::Succeeded;
 
                     match &task.kind {
-                        KindWithContent::DocumentDeletion { index_uid: _, documents_ids } => {
+                        // the deletions at an expected version are batched as document operations
+                        KindWithContent::DocumentDeletion {
+                            index_uid: _, documents_ids, ..
+                        } => {
                             for id in documents_ids {
                                 if let Some(id) = external_documents_ids.get(index_wtxn, id)? {
                                     to_delete.insert(id);
@@ -349,6 +547,7 @@ impl IndexScheduler {
                             task.details = Some(Details::DocumentDeletion {
                                 provided_ids: documents_ids.len(),
                                 deleted_documents: Some(will_be_removed),
+                                version_conflicts: Vec::new(),
                             });
                         }
                         KindWithContent::DocumentDeletionByFilter { index_uid, filter_expr } => {
@@ -452,29 +651,17 @@ impl IndexScheduler {
 
                 Ok((tasks, congestion))
             }
-            IndexOperation::Settings { index_uid, settings, mut tasks } => {
-                progress.update_progress(SettingsProgress::RetrievingAndMergingTheSettings);
-                let indexer_config = self.index_mapper.indexer_config();
-                let mut builder = milli::update::Settings::new(index_wtxn, index, indexer_config);
-
-                for (task, (_, settings)) in tasks.iter_mut().zip(settings) {
-                    let checked_settings = settings.clone().check();
-                    task.details = Some(Details::SettingsUpdate { settings: Box::new(settings) });
-                    apply_settings_to_builder(&checked_settings, &mut builder);
-
-                    // We can apply the status right now and if an update fail later
-                    // the whole batch will be marked as failed.
-                    task.status = Status::Succeeded;
-                }
-
-                progress.update_progress(SettingsProgress::ApplyTheSettings);
-                builder
-                    .execute(
-                        |indexing_step| tracing::debug!(update = ?indexing_step),
-                        || must_stop_processing.get(),
-                    )
-                    .map_err(|err| Error::from_milli(err, Some(index_uid.clone())))?;
-
+            IndexOperation::Settings { index_uid, settings, tasks } => {
+                let uncommitted_writes = false;
+                let tasks = self.apply_settings_tasks(
+                    index_wtxn,
+                    index,
+                    &index_uid,
+                    settings,
+                    tasks,
+                    uncommitted_writes,
+                    progress,
+                )?;
                 Ok((tasks, None))
             }
             IndexOperation::DocumentClearAndSetting {
@@ -493,10 +680,15 @@ impl IndexScheduler {
                     progress,
                 )?;
 
-                let (settings_tasks, _congestion) = self.apply_index_operation(
+                // the documents were cleared in the transaction
+                let uncommitted_writes = true;
+                let settings_tasks = self.apply_settings_tasks(
                     index_wtxn,
                     index,
-                    IndexOperation::Settings { index_uid, settings, tasks: settings_tasks },
+                    &index_uid,
+                    settings,
+                    settings_tasks,
+                    uncommitted_writes,
                     progress,
                 )?;
 
@@ -506,4 +698,236 @@ impl IndexScheduler {
             }
         }
     }
+
+    /// Apply the settings of the `tasks` in a single settings update.
+    ///
+    /// `uncommitted_writes` must be `true` if documents or settings were already written in the transaction.
+    #[allow(clippy::too_many_arguments)]
+    fn apply_settings_tasks(
+        &self,
+        index_wtxn: &mut RwTxn<'_>,
+        index: &Index,
+        index_uid: &str,
+        settings: Vec<(bool, Settings<Unchecked>)>,
+        mut tasks: Vec<Task>,
+        uncommitted_writes: bool,
+        progress: &Progress,
+    ) -> Result<Vec<Task>> {
+        progress.update_progress(SettingsProgress::RetrievingAndMergingTheSettings);
+        let indexer_config = self.index_mapper.indexer_config();
+        let mut builder = milli::update::Settings::new(index_wtxn, index, indexer_config);
+
+        for (task, (_, settings)) in tasks.iter_mut().zip(settings) {
+            let checked_settings = settings.clone().check();
+            task.details = Some(Details::SettingsUpdate { settings: Box::new(settings) });
+            apply_settings_to_builder(&checked_settings, &mut builder);
+
+            // We can apply the status right now and if an update fail later
+            // the whole batch will be marked as failed.
+            task.status = Status::Succeeded;
+        }
+
+        progress.update_progress(SettingsProgress::ApplyTheSettings);
+        builder.set_progress(progress.clone());
+        builder.set_uncommitted_writes(uncommitted_writes);
+        let must_stop_processing = self.scheduler.must_stop_processing.clone();
+        builder
+            .execute(
+                |indexing_step| tracing::debug!(update = ?indexing_step),
+                || must_stop_processing.get(),
+            )
+            .map_err(|err| Error::from_milli(err, Some(index_uid.to_string())))?;
+
+        Ok(tasks)
+    }
+
+    /// Apply the operations of a `documentBulk` task, or of one index of an `indexTransaction`
+    /// task, reading the documents of the replace and update operations from the payload.
+    ///
+    /// The operations are applied all together or not at all.
+    ///
+    /// The task fails if an operation expects a document at another version,
+    /// or if a document does not match the document schema of the index.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn apply_bulk_operations<'i>(
+        &self,
+        index_wtxn: &mut RwTxn<'i>,
+        index: &'i Index,
+        index_uid: &str,
+        primary_key: Option<&str>,
+        payload: &[u8],
+        operations: &[BulkOperation],
+        progress: &Progress,
+    ) -> Result<BulkOperationsOutcome> {
+        let indexer_alloc = Bump::new();
+        let must_stop_processing = self.scheduler.must_stop_processing.clone();
+
+        let rtxn = index.read_txn()?;
+        let db_fields_ids_map = index.fields_ids_map(&rtxn)?;
+        let mut new_fields_ids_map = db_fields_ids_map.clone();
+
+        // The documents of the replace and update operations follow each other in the
+        // content file, we cut it into one payload per operation.
+        let mut documents = serde_json::Deserializer::from_slice(payload).into_iter::<IgnoredAny>();
+        let mut indexer = indexer::DocumentOperation::new();
+        let mut is_deletion = Vec::with_capacity(operations.len());
+        for operation in operations {
+            match operation {
+                BulkOperation::Replace { documents_count }
+                | BulkOperation::Update { documents_count } => {
+                    let start = documents.byte_offset();
+                    skip_documents(&mut documents, *documents_count, index_uid)?;
+                    let payload = &payload[start..documents.byte_offset()];
+                    if matches!(operation, BulkOperation::Replace { .. }) {
+                        indexer.replace_documents_slice(payload);
+                    } else {
+                        indexer.update_documents_slice(payload);
+                    }
+                    is_deletion.push(false);
+                }
+                BulkOperation::Delete { documents_ids } => {
+                    let documents_ids: bumpalo::collections::vec::Vec<_> = documents_ids
+                        .iter()
+                        .map(|s| &*indexer_alloc.alloc_str(s))
+                        .collect_in(&indexer_alloc);
+                    indexer.delete_documents(documents_ids.into_bump_slice());
+                    is_deletion.push(true);
+                }
+                BulkOperation::DeleteByFilter { filter_expr } => {
+                    // The filter is evaluated on the documents as they were before the task,
+                    // which is why a `deleteByFilter` cannot follow an operation writing documents.
+                    let candidates = match Filter::from_json(filter_expr) {
+                        Ok(Some(filter)) => filter
+                            .evaluate(&rtxn, index)
+                            .map_err(|err| Error::from_milli(err, Some(index_uid.to_string())))?,
+                        Ok(None) => RoaringBitmap::new(),
+                        Err(e) => return Err(Error::from_milli(e, Some(index_uid.to_string()))),
+                    };
+                    let mut documents_ids = bumpalo::collections::Vec::new_in(&indexer_alloc);
+                    // candidates not empty => index not empty => a primary key is set
+                    if !candidates.is_empty() {
+                        for external_id in index
+                            .external_id_of(&rtxn, candidates)
+                            .map_err(|err| Error::from_milli(err, Some(index_uid.to_string())))?
+                        {
+                            let external_id = external_id.map_err(|err| {
+                                Error::from_milli(err, Some(index_uid.to_string()))
+                            })?;
+                            documents_ids.push(&*indexer_alloc.alloc_str(&external_id));
+                        }
+                    }
+                    indexer.delete_documents(documents_ids.into_bump_slice());
+                    is_deletion.push(true);
+                }
+            }
+        }
+
+        let indexer_config = self.index_mapper.indexer_config();
+        let pool = &indexer_config.thread_pool;
+
+        progress.update_progress(DocumentBulkProgress::ComputingDocumentChanges);
+        let (document_changes, operation_stats, primary_key) = indexer
+            .into_changes(
+                &indexer_alloc,
+                index,
+                &rtxn,
+                primary_key,
+                &mut new_fields_ids_map,
+                &|| must_stop_processing.get(),
+                progress.clone(),
+            )
+            .map_err(|e| Error::from_milli(e, Some(index_uid.to_string())))?;
+
+        // The operations are applied all together or not at all
+        let mut indexed_documents = 0;
+        let mut deleted_documents = 0;
+        let mut generated_document_ids = Vec::new();
+        for (stats, is_deletion) in operation_stats.into_iter().zip(is_deletion) {
+            let error = if let Some(error) = stats.error {
+                Some(error)
+            } else if let Some(conflict) = stats.version_conflicts.into_iter().next() {
+                Some(milli::UserError::DocumentVersionConflict {
+                    document_id: conflict.document_id,
+                    expected_version: conflict.expected_version,
+                    current_version: conflict.current_version,
+                })
+            } else if !stats.invalid_documents.is_empty() {
+                Some(milli::UserError::DocumentSchemaViolation {
+                    violations: stats.invalid_documents,
+                })
+            } else {
+                None
+            };
+            if let Some(error) = error {
+                return Err(Error::from_milli(
+                    milli::Error::UserError(error),
+                    Some(index_uid.to_string()),
+                ));
+            }
+            generated_document_ids.extend(stats.generated_document_ids);
+            if is_deletion {
+                deleted_documents += stats.document_count;
+            } else {
+                indexed_documents += stats.document_count;
+            }
+        }
+
+        let embedders = index
+            .embedding_configs(index_wtxn)
+            .map_err(|e| Error::from_milli(e, Some(index_uid.to_string())))?;
+        let embedders = self.embedders(index_uid.to_string(), embedders)?;
+
+        progress.update_progress(DocumentBulkProgress::Indexing);
+        let congestion = indexer::index(
+            index_wtxn,
+            index,
+            pool,
+            indexer_config.grenad_parameters(),
+            &db_fields_ids_map,
+            new_fields_ids_map,
+            primary_key,
+            &document_changes,
+            embedders,
+            &|| must_stop_processing.get(),
+            progress,
+        )
+        .map_err(|e| Error::from_milli(e, Some(index_uid.to_string())))?;
+
+        Ok(BulkOperationsOutcome {
+            indexed_documents,
+            deleted_documents,
+            generated_document_ids,
+            congestion,
+        })
+    }
+}
+
+/// The changes made to an index by [`IndexScheduler::apply_bulk_operations`].
+pub(crate) struct BulkOperationsOutcome {
+    pub indexed_documents: u64,
+    pub deleted_documents: u64,
+    /// The ids generated for the documents without a primary key value, in order.
+    pub generated_document_ids: Vec<String>,
+    pub congestion: ChannelCongestion,
+}
+
+/// Advance the stream over the next `count` documents of a content file.
+pub(crate) fn skip_documents<'de>(
+    documents: &mut serde_json::StreamDeserializer<'de, serde_json::de::SliceRead<'de>, IgnoredAny>,
+    count: u64,
+    index_uid: &str,
+) -> Result<()> {
+    for _ in 0..count {
+        match documents.next() {
+            Some(Ok(_)) => (),
+            Some(Err(e)) => {
+                return Err(Error::from_milli(
+                    milli::InternalError::SerdeJson(e).into(),
+                    Some(index_uid.to_string()),
+                ))
+            }
+            None => return Err(Error::CorruptedTaskQueue),
+        }
+    }
+    Ok(())
 }
@@ -0,0 +1,228 @@
+use meilisearch_types::milli::progress::{Progress, VariableNameStep};
+use meilisearch_types::milli::{self};
+use meilisearch_types::settings::apply_settings_to_builder;
+use meilisearch_types::tasks::{BulkOperation, Details, KindWithContent, Status, Task};
+use meilisearch_types::versioning::{VERSION_MAJOR, VERSION_MINOR, VERSION_PATCH};
+use meilisearch_types::Index;
+use serde::de::IgnoredAny;
+use uuid::Uuid;
+
+use super::process_index_operation::skip_documents;
+use crate::processing::IndexTransactionProgress;
+use crate::{Error, IndexScheduler, Result};
+
+impl IndexScheduler {
+    /// Apply the operations of an `indexTransaction` task to all of its indexes.
+    ///
+    /// Every index is updated in its own write transaction and none of them is committed
+    /// before all the operations succeeded. The indexes created by the task are only registered
+    /// once everything is ready to be committed. If anything fails before that, the created
+    /// indexes are deleted and the other ones are left untouched.
+    ///
+    /// LMDB cannot commit several environments at once. Each index records the uid of the
+    /// task in the same write transaction as its changes, if a commit fails after part of the
+    /// indexes have been committed, the task is aborted and applied again on the indexes that
+    /// don't hold its uid yet.
+    pub(super) fn process_index_transaction(
+        &self,
+        progress: Progress,
+        mut task: Task,
+    ) -> Result<Vec<Task>> {
+        let mut created_indexes = Vec::new();
+        let ret = self.apply_index_transaction(&progress, &task, &mut created_indexes);
+
+        if ret.is_err() {
+            for (index_uid, uuid) in created_indexes {
+                self.index_mapper.delete_unregistered_index(uuid, &index_uid);
+            }
+        }
+        let (indexed_documents, deleted_documents, generated_document_ids) = ret?;
+
+        let received_operations = match task.details {
+            Some(Details::IndexTransaction { received_operations, .. }) => received_operations,
+            // In the case of an `indexTransaction` the details MUST be set
+            _ => unreachable!(),
+        };
+        task.status = Status::Succeeded;
+        task.details = Some(Details::IndexTransaction {
+            received_operations,
+            indexed_documents: Some(indexed_documents),
+            deleted_documents: Some(deleted_documents),
+            generated_document_ids,
+        });
+
+        Ok(vec![task])
+    }
+
+    /// ## Return
+    /// The number of indexed and deleted documents, and the ids generated for the documents.
+    fn apply_index_transaction(
+        &self,
+        progress: &Progress,
+        task: &Task,
+        created_indexes: &mut Vec<(String, Uuid)>,
+    ) -> Result<(u64, u64, Vec<String>)> {
+        progress.update_progress(IndexTransactionProgress::OpeningTheIndexes);
+        let KindWithContent::IndexTransaction { content_file, operations, allow_index_creation } =
+            &task.kind
+        else {
+            unreachable!()
+        };
+
+        let mut indexes: Vec<Index> = Vec::with_capacity(operations.len());
+        for operation in operations {
+            let index_uid = &operation.index_uid;
+            let rtxn = self.env.read_txn()?;
+            let index = if self.index_mapper.exists(&rtxn, index_uid)? {
+                self.index_mapper.index(&rtxn, index_uid)?
+            } else if *allow_index_creation {
+                let (uuid, index) =
+                    self.index_mapper.create_unregistered_index(&rtxn, index_uid, None)?;
+                created_indexes.push((index_uid.clone(), uuid));
+                index
+            } else {
+                return Err(Error::IndexNotFound(index_uid.clone()));
+            };
+
+            let rtxn = index.read_txn()?;
+            let index_version = index.get_version(&rtxn)?.unwrap_or((1, 12, 0));
+            let package_version = (VERSION_MAJOR, VERSION_MINOR, VERSION_PATCH);
+            if index_version != package_version {
+                return Err(Error::IndexVersionMismatch {
+                    index: index_uid.clone(),
+                    index_version,
+                    package_version,
+                });
+            }
+            indexes.push(index);
+        }
+
+        let mut index_wtxns =
+            indexes.iter().map(|index| index.write_txn()).collect::<Result<Vec<_>, _>>()?;
+
+        progress.update_progress(IndexTransactionProgress::ApplyingTheOperations);
+        let content_file = self.queue.file_store.get_update(*content_file)?;
+        let mmap = unsafe { memmap2::Mmap::map(&content_file)? };
+        #[cfg(unix)]
+        mmap.advise(memmap2::Advice::Sequential)?;
+
+        // The documents of every index follow each other in the content file.
+        let mut documents = serde_json::Deserializer::from_slice(&mmap).into_iter::<IgnoredAny>();
+        let mut indexed_documents = 0;
+        let mut deleted_documents = 0;
+        let mut generated_document_ids = Vec::new();
+        let must_stop_processing = self.scheduler.must_stop_processing.clone();
+        for (step, ((operation, index), index_wtxn)) in
+            operations.iter().zip(&indexes).zip(&mut index_wtxns).enumerate()
+        {
+            let index_uid = &operation.index_uid;
+            progress.update_progress(VariableNameStep::<IndexTransactionProgress>::new(
+                format!("updating index {index_uid}"),
+                step as u32,
+                operations.len() as u32,
+            ));
+
+            let start = documents.byte_offset();
+            let documents_count =
+                operation.documents.iter().map(BulkOperation::documents_count).sum();
+            skip_documents(&mut documents, documents_count, index_uid)?;
+            if index.last_applied_transaction(index_wtxn)? == Some(task.uid) {
+                tracing::info!(
+                    index = %index_uid,
+                    "The transaction has already been applied to this index, skipping it"
+                );
+                continue;
+            }
+            index.put_last_applied_transaction(index_wtxn, task.uid)?;
+
+            let uncommitted_writes = !operation.documents.is_empty();
+            if !operation.documents.is_empty() {
+                let outcome = self.apply_bulk_operations(
+                    index_wtxn,
+                    index,
+                    index_uid,
+                    operation.primary_key.as_deref(),
+                    &mmap[start..documents.byte_offset()],
+                    &operation.documents,
+                    progress,
+                )?;
+                indexed_documents += outcome.indexed_documents;
+                deleted_documents += outcome.deleted_documents;
+                generated_document_ids.extend(outcome.generated_document_ids);
+            }
+
+            if let Some(settings) = &operation.settings {
+                let indexer_config = self.index_mapper.indexer_config();
+                let mut builder = milli::update::Settings::new(index_wtxn, index, indexer_config);
+                apply_settings_to_builder(&settings.clone().check(), &mut builder);
+                builder.set_progress(progress.clone());
+                builder.set_uncommitted_writes(uncommitted_writes);
+                builder
+                    .execute(
+                        |indexing_step| tracing::debug!(update = ?indexing_step),
+                        || must_stop_processing.get(),
+                    )
+                    .map_err(|err| Error::from_milli(err, Some(index_uid.clone())))?;
+            }
+        }
+
+        // LMDB cannot commit several environments at once. Everything that could fail
+        // has been done at this point, only an I/O error can leave part of the indexes committed.
+        progress.update_progress(IndexTransactionProgress::Committing);
+        let mut committed = false;
+        if !created_indexes.is_empty() {
+            let mut wtxn = self.env.write_txn()?;
+            for (operation, index) in operations.iter().zip(&indexes) {
+                let index_uid = &operation.index_uid;
+                if let Some((_, uuid)) = created_indexes.iter().find(|(name, _)| name == index_uid)
+                {
+                    self.index_mapper.register_index(&mut wtxn, index_uid, uuid, index)?;
+                }
+            }
+            wtxn.commit()?;
+            // The created indexes are now registered, they are completed if the task is applied again.
+            created_indexes.clear();
+            committed = true;
+        }
+
+        for index_wtxn in index_wtxns {
+            match index_wtxn.commit() {
+                Ok(()) => committed = true,
+                Err(e) if !committed => return Err(e.into()),
+                Err(e) => {
+                    tracing::error!(
+                        error = &e as &dyn std::error::Error,
+                        "Could not commit an index of a transaction, the transaction will be applied again"
+                    );
+                    return Err(Error::AbortedTask);
+                }
+            }
+        }
+
+        // if the transaction processed successfully, we're going to store the new
+        // stats of the indexes. Since the task has already been processed and
+        // this is a non-critical operation. If it fails, we should not fail
+        // the entire batch.
+        for (operation, index) in operations.iter().zip(&indexes) {
+            let index_uid = &operation.index_uid;
+            let res = || -> Result<()> {
+                let index_rtxn = index.read_txn()?;
+                let stats = crate::index_mapper::IndexStats::new(index, &index_rtxn)
+                    .map_err(|e| Error::from_milli(e, Some(index_uid.clone())))?;
+                let mut wtxn = self.env.write_txn()?;
+                self.index_mapper.store_stats_of(&mut wtxn, index_uid, &stats)?;
+                wtxn.commit()?;
+                Ok(())
+            }();
+
+            if let Err(e) = res {
+                tracing::error!(
+                    error = &e as &dyn std::error::Error,
+                    "Could not write the stats of the index"
+                );
+            }
+        }
+
+        Ok((indexed_documents, deleted_documents, generated_document_ids))
+    }
+}
@@ -729,12 +729,16 @@ fn basic_get_stats() {
       },
       "types": {
         "documentAdditionOrUpdate": 0,
+        "documentBulk": 0,
         "documentDeletion": 0,
         "documentEdition": 0,
         "dumpCreation": 0,
+        "embeddingRegeneration": 0,
+        "indexClone": 0,
         "indexCreation": 3,
         "indexDeletion": 0,
         "indexSwap": 0,
+        "indexTransaction": 0,
         "indexUpdate": 0,
         "settingsUpdate": 0,
         "snapshotCreation": 0,
@@ -762,12 +766,16 @@ fn basic_get_stats() {
       },
       "types": {
         "documentAdditionOrUpdate": 0,
+        "documentBulk": 0,
         "documentDeletion": 0,
         "documentEdition": 0,
         "dumpCreation": 0,
+        "embeddingRegeneration": 0,
+        "indexClone": 0,
         "indexCreation": 3,
         "indexDeletion": 0,
         "indexSwap": 0,
+        "indexTransaction": 0,
         "indexUpdate": 0,
         "settingsUpdate": 0,
         "snapshotCreation": 0,
@@ -802,12 +810,16 @@ fn basic_get_stats() {
       },
       "types": {
         "documentAdditionOrUpdate": 0,
+        "documentBulk": 0,
         "documentDeletion": 0,
         "documentEdition": 0,
         "dumpCreation": 0,
+        "embeddingRegeneration": 0,
+        "indexClone": 0,
         "indexCreation": 3,
         "indexDeletion": 0,
         "indexSwap": 0,
+        "indexTransaction": 0,
         "indexUpdate": 0,
         "settingsUpdate": 0,
         "snapshotCreation": 0,
@@ -843,12 +855,16 @@ fn basic_get_stats() {
       },
       "types": {
         "documentAdditionOrUpdate": 0,
+        "documentBulk": 0,
         "documentDeletion": 0,
         "documentEdition": 0,
         "dumpCreation": 0,
+        "embeddingRegeneration": 0,
+        "indexClone": 0,
         "indexCreation": 3,
         "indexDeletion": 0,
         "indexSwap": 0,
+        "indexTransaction": 0,
         "indexUpdate": 0,
         "settingsUpdate": 0,
         "snapshotCreation": 0,
@@ -78,6 +78,7 @@ fn document_addition_and_document_deletion() {
             KindWithContent::DocumentDeletion {
                 index_uid: S("doggos"),
                 documents_ids: vec![S("1"), S("2")],
+                expected_versions: Default::default(),
             },
             None,
             false,
@@ -108,6 +109,7 @@ fn document_deletion_and_document_addition() {
             KindWithContent::DocumentDeletion {
                 index_uid: S("doggos"),
                 documents_ids: vec![S("1"), S("2")],
+                expected_versions: Default::default(),
             },
             None,
             false,
@@ -1148,3 +1150,149 @@ fn test_document_addition_with_set_and_null_primary_key_inference_works() {
         .collect::<Vec<_>>();
     snapshot!(serde_json::to_string_pretty(&documents).unwrap(), name: "documents");
 }
+
+#[test]
+fn test_expired_documents_deletion() {
+    let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+    let content = r#"[
+            { "id": 1, "doggo": "jean bob", "_expiresAt": 0 },
+            { "id": 2, "catto": "jorts", "_expiresAt": 4102444800 },
+            { "id": 3, "doggo": "bork" }
+        ]"#;
+
+    let (uuid, mut file) = index_scheduler.queue.create_update_file_with_uuid(0).unwrap();
+    let documents_count = read_json(content.as_bytes(), &mut file).unwrap();
+    file.persist().unwrap();
+    index_scheduler
+        .register(
+            KindWithContent::DocumentAdditionOrUpdate {
+                index_uid: S("doggos"),
+                primary_key: Some(S("id")),
+                method: ReplaceDocuments,
+                content_file: uuid,
+                documents_count,
+                allow_index_creation: true,
+            },
+            None,
+            false,
+        )
+        .unwrap();
+    handle.advance_one_successful_batch();
+
+    // only the first document has expired
+    index_scheduler.enqueue_expired_documents_deletions().unwrap();
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let task = index_scheduler.queue.tasks.get_task(&rtxn, 1).unwrap().unwrap();
+    let KindWithContent::DocumentDeletionByFilter { index_uid, filter_expr } = task.kind else {
+        panic!("expected a deletion by filter, got {:?}", task.kind)
+    };
+    assert_eq!(index_uid, "doggos");
+    assert!(filter_expr.as_str().unwrap().starts_with("_expiresAt <= "), "{filter_expr}");
+    drop(rtxn);
+
+    // the deletion is still enqueued, no other deletion is registered
+    index_scheduler.enqueue_expired_documents_deletions().unwrap();
+    let rtxn = index_scheduler.read_txn().unwrap();
+    assert!(index_scheduler.queue.tasks.get_task(&rtxn, 2).unwrap().is_none());
+    drop(rtxn);
+
+    handle.advance_one_successful_batch();
+
+    let index = index_scheduler.index("doggos").unwrap();
+    let rtxn = index.read_txn().unwrap();
+    let field_ids_map = index.fields_ids_map(&rtxn).unwrap();
+    let field_ids = field_ids_map.ids().collect::<Vec<_>>();
+    let documents = index
+        .all_documents(&rtxn)
+        .unwrap()
+        .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+        .collect::<Vec<_>>();
+    snapshot!(serde_json::to_string_pretty(&documents).unwrap(), @r###"
+    [
+      {
+        "id": 2,
+        "catto": "jorts",
+        "_expiresAt": 4102444800
+      },
+      {
+        "id": 3,
+        "doggo": "bork"
+      }
+    ]
+    "###);
+    drop(rtxn);
+
+    // the earliest expiration date cached in the stats is in the future, nothing is registered
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let stats = index_scheduler.index_mapper.stats_of(&rtxn, "doggos").unwrap();
+    snapshot!(stats.earliest_expiration.unwrap().unix_timestamp(), @"4102444800");
+    drop(rtxn);
+    index_scheduler.enqueue_expired_documents_deletions().unwrap();
+    let rtxn = index_scheduler.read_txn().unwrap();
+    assert!(index_scheduler.queue.tasks.get_task(&rtxn, 2).unwrap().is_none());
+}
+
+#[test]
+fn test_document_update_with_invalid_operators() {
+    let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+    let contents = [
+        (ReplaceDocuments, r#"{ "id": 1, "doggo": "jean bob", "views": 41 }"#),
+        (UpdateDocuments, r#"{ "id": 1, "$inc": { "views": 1 } }"#),
+        (UpdateDocuments, r#"{ "id": 1, "$inc": { "doggo": 1 } }"#),
+    ];
+    for (id, (method, content)) in contents.into_iter().enumerate() {
+        let (uuid, mut file) =
+            index_scheduler.queue.create_update_file_with_uuid(id as u128).unwrap();
+        let documents_count = read_json(content.as_bytes(), &mut file).unwrap();
+        file.persist().unwrap();
+        index_scheduler
+            .register(
+                KindWithContent::DocumentAdditionOrUpdate {
+                    index_uid: S("doggos"),
+                    primary_key: Some(S("id")),
+                    method,
+                    content_file: uuid,
+                    documents_count,
+                    allow_index_creation: true,
+                },
+                None,
+                false,
+            )
+            .unwrap();
+        // the two updates are processed in the same batch
+        if id != 1 {
+            handle.advance_one_successful_batch();
+        }
+    }
+
+    // only the task with the invalid operator fails, the rest of its batch is processed
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let statuses = (0..3)
+        .map(|uid| index_scheduler.queue.tasks.get_task(&rtxn, uid).unwrap().unwrap().status)
+        .collect::<Vec<_>>();
+    snapshot!(format!("{statuses:?}"), @"[Succeeded, Succeeded, Failed]");
+    drop(rtxn);
+
+    // the operators are not registered as fields of the documents
+    let index = index_scheduler.index("doggos").unwrap();
+    let rtxn = index.read_txn().unwrap();
+    let field_ids_map = index.fields_ids_map(&rtxn).unwrap();
+    assert_eq!(field_ids_map.id("$inc"), None);
+    let field_ids = field_ids_map.ids().collect::<Vec<_>>();
+    let documents = index
+        .all_documents(&rtxn)
+        .unwrap()
+        .map(|ret| obkv_to_json(&field_ids, &field_ids_map, ret.unwrap().1).unwrap())
+        .collect::<Vec<_>>();
+    snapshot!(serde_json::to_string_pretty(&documents).unwrap(), @r###"
+    [
+      {
+        "id": 1,
+        "doggo": "jean bob",
+        "views": 42
+      }
+    ]
+    "###);
+}
@@ -425,6 +425,7 @@ fn import_vectors_first_and_embedder_later() {
                     ),
                 },
                 quantized: None,
+                shadow_of: None,
             },
             user_provided: RoaringBitmap<[1, 2]>,
         },
@@ -586,6 +587,7 @@ fn delete_document_containing_vector() {
             KindWithContent::DocumentDeletion {
                 index_uid: S("doggos"),
                 documents_ids: vec![S("1")],
+                expected_versions: Default::default(),
             },
             None,
             false,
@@ -622,6 +624,7 @@ fn delete_document_containing_vector() {
                         ),
                     },
                     quantized: None,
+                    shadow_of: None,
                 },
                 user_provided: RoaringBitmap<[0]>,
             },
@@ -666,6 +669,7 @@ fn delete_document_containing_vector() {
                         ),
                     },
                     quantized: None,
+                    shadow_of: None,
                 },
                 user_provided: RoaringBitmap<[]>,
             },
@@ -178,6 +178,7 @@ fn fail_in_process_batch_for_document_deletion() {
             KindWithContent::DocumentDeletion {
                 index_uid: S("doggos"),
                 documents_ids: vec![S("1")],
+                expected_versions: Default::default(),
             },
             None,
             false,
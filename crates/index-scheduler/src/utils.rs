@@ -252,7 +252,9 @@ pub fn swap_index_uid_in_task(task: &mut Task, swap: (&str, &str)) {
     let mut index_uids = vec![];
     match &mut task.kind {
         K::DocumentAdditionOrUpdate { index_uid, .. } => index_uids.push(index_uid),
+        K::DocumentBulk { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentEdition { index_uid, .. } => index_uids.push(index_uid),
+        K::EmbeddingRegeneration { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentDeletion { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentDeletionByFilter { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentClear { index_uid } => index_uids.push(index_uid),
@@ -270,21 +272,34 @@ pub fn swap_index_uid_in_task(task: &mut Task, swap: (&str, &str)) {
                 }
             }
         }
+        K::IndexTransaction { operations, .. } => {
+            for operation in operations.iter_mut() {
+                index_uids.push(&mut operation.index_uid);
+            }
+        }
+        K::IndexClone { index_uid, new_index_uid, .. } => {
+            index_uids.push(index_uid);
+            index_uids.push(new_index_uid);
+        }
         K::TaskCancelation { .. }
         | K::TaskDeletion { .. }
         | K::DumpCreation { .. }
         | K::UpgradeDatabase { .. }
         | K::SnapshotCreation => (),
     };
-    if let Some(Details::IndexSwap { swaps }) = &mut task.details {
-        for IndexSwap { indexes: (lhs, rhs) } in swaps.iter_mut() {
-            if lhs == swap.0 || lhs == swap.1 {
-                index_uids.push(lhs);
-            }
-            if rhs == swap.0 || rhs == swap.1 {
-                index_uids.push(rhs);
+    match &mut task.details {
+        Some(Details::IndexSwap { swaps }) => {
+            for IndexSwap { indexes: (lhs, rhs) } in swaps.iter_mut() {
+                if lhs == swap.0 || lhs == swap.1 {
+                    index_uids.push(lhs);
+                }
+                if rhs == swap.0 || rhs == swap.1 {
+                    index_uids.push(rhs);
+                }
             }
         }
+        Some(Details::IndexClone { new_index_uid, .. }) => index_uids.push(new_index_uid),
+        _ => (),
     }
     for index_uid in index_uids {
         if index_uid == swap.0 {
@@ -449,7 +464,11 @@ impl crate::IndexScheduler {
                             assert_eq!(&sw1, sw2);
                         }
                     }
-                    Details::DocumentAdditionOrUpdate { received_documents, indexed_documents } => {
+                    Details::DocumentAdditionOrUpdate {
+                        received_documents,
+                        indexed_documents,
+                        ..
+                    } => {
                         assert_eq!(kind.as_kind(), Kind::DocumentAdditionOrUpdate);
                         match indexed_documents {
                             Some(indexed_documents) => {
@@ -487,9 +506,75 @@ impl crate::IndexScheduler {
                             }
                         }
                     }
+                    Details::DocumentBulk { indexed_documents, deleted_documents, .. }
+                    | Details::IndexTransaction { indexed_documents, deleted_documents, .. } => {
+                        assert!(matches!(
+                            kind.as_kind(),
+                            Kind::DocumentBulk | Kind::IndexTransaction
+                        ));
+                        match (indexed_documents, deleted_documents) {
+                            (Some(indexed_documents), Some(deleted_documents)) => {
+                                assert!(matches!(
+                                    status,
+                                    Status::Succeeded | Status::Failed | Status::Canceled
+                                ));
+                                match status {
+                                    Status::Succeeded => (),
+                                    Status::Failed | Status::Canceled => {
+                                        assert_eq!(indexed_documents, 0);
+                                        assert_eq!(deleted_documents, 0);
+                                    }
+                                    status => panic!(
+                                        "{} can't have an indexed_documents set if it's {}",
+                                        kind.as_kind(),
+                                        status
+                                    ),
+                                }
+                            }
+                            (None, None) => {
+                                assert!(matches!(status, Status::Enqueued | Status::Processing))
+                            }
+                            _ => panic!(
+                                "{} must have both its indexed and deleted documents set",
+                                kind.as_kind()
+                            ),
+                        }
+                    }
+                    Details::EmbeddingRegeneration { regenerated_documents, .. } => {
+                        assert_eq!(kind.as_kind(), Kind::EmbeddingRegeneration);
+                        match regenerated_documents {
+                            Some(regenerated_documents) => {
+                                assert!(matches!(
+                                    status,
+                                    Status::Succeeded | Status::Failed | Status::Canceled
+                                ));
+                                match status {
+                                    Status::Succeeded => (),
+                                    Status::Failed | Status::Canceled => assert_eq!(regenerated_documents, 0),
+                                    status => panic!("EmbeddingRegeneration can't have a regenerated_documents set if it's {}", status),
+                                }
+                            }
+                            None => {
+                                assert!(matches!(status, Status::Enqueued | Status::Processing))
+                            }
+                        }
+                    }
                     Details::SettingsUpdate { settings: _ } => {
                         assert_eq!(kind.as_kind(), Kind::SettingsUpdate);
                     }
+                    Details::IndexClone { new_index_uid: uid1, settings_only: so1 } => {
+                        if let KindWithContent::IndexClone {
+                            new_index_uid: uid2,
+                            settings_only: so2,
+                            ..
+                        } = &kind
+                        {
+                            assert_eq!(&uid1, uid2);
+                            assert_eq!(&so1, so2);
+                        } else {
+                            panic!()
+                        }
+                    }
                     Details::IndexInfo { primary_key: pk1 } => match &kind {
                         KindWithContent::IndexCreation { index_uid, primary_key: pk2 }
                         | KindWithContent::IndexUpdate { index_uid, primary_key: pk2 } => {
@@ -507,12 +592,14 @@ impl crate::IndexScheduler {
                     Details::DocumentDeletion {
                         provided_ids: received_document_ids,
                         deleted_documents,
+                        ..
                     } => {
                         assert_eq!(kind.as_kind(), Kind::DocumentDeletion);
                         let (index_uid, documents_ids) =
                             if let KindWithContent::DocumentDeletion {
                                 ref index_uid,
                                 ref documents_ids,
+                                ..
                             } = kind
                             {
                                 (index_uid, documents_ids)
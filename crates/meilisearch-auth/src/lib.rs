@@ -12,7 +12,7 @@ use meilisearch_types::keys::{Action, CreateApiKey, Key, PatchApiKey};
 use meilisearch_types::milli::update::Setting;
 use serde::{Deserialize, Serialize};
 pub use store::open_auth_store_env;
-use store::{generate_key_as_hexa, HeedAuthStore};
+use store::{expand_actions, generate_key_as_hexa, HeedAuthStore};
 use time::OffsetDateTime;
 use uuid::Uuid;
 
@@ -97,11 +97,21 @@ impl AuthController {
     ) -> Result<AuthFilter> {
         let key = self.get_key(uid)?;
 
+        let key_authorized_actions = match key.expires_at {
+            Some(exp) if OffsetDateTime::now_utc() >= exp => HashSet::new(),
+            _ => expand_actions(&key.actions),
+        };
+
         let key_authorized_indexes = SearchRules::Set(key.indexes.into_iter().collect());
 
         let allow_index_creation = self.is_key_authorized(uid, Action::IndexesAdd, None)?;
 
-        Ok(AuthFilter { search_rules, key_authorized_indexes, allow_index_creation })
+        Ok(AuthFilter {
+            search_rules,
+            key_authorized_indexes,
+            key_authorized_actions: Some(key_authorized_actions),
+            allow_index_creation,
+        })
     }
 
     pub fn list_keys(&self) -> Result<Vec<Key>> {
@@ -168,6 +178,8 @@ impl AuthController {
 pub struct AuthFilter {
     search_rules: Option<SearchRules>,
     key_authorized_indexes: SearchRules,
+    /// The actions granted by the API key, `None` when all of them are granted.
+    key_authorized_actions: Option<HashSet<Action>>,
     allow_index_creation: bool,
 }
 
@@ -176,6 +188,7 @@ impl Default for AuthFilter {
         Self {
             search_rules: None,
             key_authorized_indexes: SearchRules::default(),
+            key_authorized_actions: None,
             allow_index_creation: true,
         }
     }
@@ -187,6 +200,12 @@ impl AuthFilter {
         self.allow_index_creation && self.is_index_authorized(index)
     }
 
+    /// Check if the API key is authorized to make the action on the index.
+    pub fn is_action_authorized(&self, action: Action, index: &str) -> bool {
+        self.key_authorized_actions.as_ref().is_none_or(|actions| actions.contains(&action))
+            && self.is_index_authorized(index)
+    }
+
     #[inline]
     /// Return true if a tenant token was used to generate the search rules.
     pub fn is_tenant_token(&self) -> bool {
@@ -197,6 +216,7 @@ impl AuthFilter {
         Self {
             search_rules: None,
             key_authorized_indexes: SearchRules::Set(allowed_indexes),
+            key_authorized_actions: None,
             allow_index_creation: false,
         }
     }
@@ -33,6 +33,56 @@ pub struct HeedAuthStore {
     action_keyid_index_expiration: Database<KeyIdActionCodec, SerdeJson<Option<OffsetDateTime>>>,
 }
 
+/// Expands the `*` actions of a key into the actions they grant.
+pub fn expand_actions(key_actions: &[Action]) -> HashSet<Action> {
+    let mut actions = HashSet::new();
+    for action in key_actions {
+        match action {
+            Action::All => actions.extend(enum_iterator::all::<Action>()),
+            Action::DocumentsAll => {
+                actions.extend(
+                    [Action::DocumentsGet, Action::DocumentsDelete, Action::DocumentsAdd].iter(),
+                );
+            }
+            Action::IndexesAll => {
+                actions.extend(
+                    [
+                        Action::IndexesAdd,
+                        Action::IndexesDelete,
+                        Action::IndexesGet,
+                        Action::IndexesUpdate,
+                        Action::IndexesSwap,
+                    ]
+                    .iter(),
+                );
+            }
+            Action::SettingsAll => {
+                actions.extend([Action::SettingsGet, Action::SettingsUpdate].iter());
+            }
+            Action::DumpsAll => {
+                actions.insert(Action::DumpsCreate);
+            }
+            Action::SnapshotsAll => {
+                actions.insert(Action::SnapshotsCreate);
+            }
+            Action::TasksAll => {
+                actions.extend([Action::TasksGet, Action::TasksDelete, Action::TasksCancel]);
+            }
+            Action::StatsAll => {
+                actions.insert(Action::StatsGet);
+            }
+            Action::MetricsAll => {
+                actions.insert(Action::MetricsGet);
+            }
+            other => {
+                actions.insert(*other);
+            }
+        }
+    }
+
+    actions
+}
+
 pub fn open_auth_store_env(path: &Path) -> heed::Result<Env<WithoutTls>> {
     let options = EnvOpenOptions::new();
     let mut options = options.read_txn_without_tls();
@@ -85,51 +135,7 @@ impl HeedAuthStore {
         // create inverted database.
         let db = self.action_keyid_index_expiration;
 
-        let mut actions = HashSet::new();
-        for action in &key.actions {
-            match action {
-                Action::All => actions.extend(enum_iterator::all::<Action>()),
-                Action::DocumentsAll => {
-                    actions.extend(
-                        [Action::DocumentsGet, Action::DocumentsDelete, Action::DocumentsAdd]
-                            .iter(),
-                    );
-                }
-                Action::IndexesAll => {
-                    actions.extend(
-                        [
-                            Action::IndexesAdd,
-                            Action::IndexesDelete,
-                            Action::IndexesGet,
-                            Action::IndexesUpdate,
-                            Action::IndexesSwap,
-                        ]
-                        .iter(),
-                    );
-                }
-                Action::SettingsAll => {
-                    actions.extend([Action::SettingsGet, Action::SettingsUpdate].iter());
-                }
-                Action::DumpsAll => {
-                    actions.insert(Action::DumpsCreate);
-                }
-                Action::SnapshotsAll => {
-                    actions.insert(Action::SnapshotsCreate);
-                }
-                Action::TasksAll => {
-                    actions.extend([Action::TasksGet, Action::TasksDelete, Action::TasksCancel]);
-                }
-                Action::StatsAll => {
-                    actions.insert(Action::StatsGet);
-                }
-                Action::MetricsAll => {
-                    actions.insert(Action::MetricsGet);
-                }
-                other => {
-                    actions.insert(*other);
-                }
-            }
-        }
+        let actions = expand_actions(&key.actions);
 
         let no_index_restriction = key.indexes.iter().any(|p| p.matches_all());
         for action in actions {
@@ -150,6 +150,7 @@ make_missing_field_convenience_builder!(MissingApiKeyActions, missing_api_key_ac
 make_missing_field_convenience_builder!(MissingApiKeyExpiresAt, missing_api_key_expires_at);
 make_missing_field_convenience_builder!(MissingApiKeyIndexes, missing_api_key_indexes);
 make_missing_field_convenience_builder!(MissingSwapIndexes, missing_swap_indexes);
+make_missing_field_convenience_builder!(MissingIndexAliasIndexes, missing_index_alias_indexes);
 make_missing_field_convenience_builder!(MissingDocumentFilter, missing_document_filter);
 make_missing_field_convenience_builder!(
     MissingFacetSearchFacetName,
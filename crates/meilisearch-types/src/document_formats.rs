@@ -16,6 +16,7 @@ use serde_json::value::RawValue;
 use serde_json::{to_writer, Map, Value};
 
 use crate::error::{Code, ErrorCode};
+use crate::tasks::BulkOperation;
 
 type Result<T> = std::result::Result<T, DocumentFormatError>;
 
@@ -40,6 +41,9 @@ impl fmt::Display for PayloadType {
 pub enum DocumentFormatError {
     Io(io::Error),
     MalformedPayload(Error, PayloadType),
+    MalformedBulkOperation(serde_json::Error),
+    /// A `deleteByFilter` operation follows an operation writing documents.
+    DeleteByFilterAfterWrite,
 }
 
 impl Display for DocumentFormatError {
@@ -78,6 +82,13 @@ impl Display for DocumentFormatError {
                 }
                 _ => write!(f, "The `{}` payload provided is malformed: `{}`.", b, me),
             },
+            Self::MalformedBulkOperation(e) => {
+                write!(f, "The bulk operations payload provided is malformed: `{e}`.")
+            }
+            Self::DeleteByFilterAfterWrite => write!(
+                f,
+                "A `deleteByFilter` operation cannot follow an `upsert` or `replace` operation, the filter being evaluated on the documents as they were before the operations.\n  - Hint: put the `deleteByFilter` operations first, or send them in another request."
+            ),
         }
     }
 }
@@ -123,7 +134,9 @@ impl ErrorCode for DocumentFormatError {
     fn error_code(&self) -> Code {
         match self {
             DocumentFormatError::Io(e) => e.error_code(),
-            DocumentFormatError::MalformedPayload(_, _) => Code::MalformedPayload,
+            DocumentFormatError::MalformedPayload(_, _)
+            | DocumentFormatError::MalformedBulkOperation(_)
+            | DocumentFormatError::DeleteByFilterAfterWrite => Code::MalformedPayload,
         }
     }
 }
@@ -275,6 +288,100 @@ pub fn read_ndjson(input: &File) -> Result<u64> {
     Ok(count)
 }
 
+/// A line of a bulk operations payload.
+#[derive(Deserialize)]
+#[serde(tag = "op", rename_all = "camelCase")]
+enum BulkLine {
+    Upsert { document: Object },
+    Replace { document: Object },
+    Delete { id: Value },
+    DeleteByFilter { filter: Value },
+}
+
+impl BulkLine {
+    /// Groups the operation with the previous one if they are of the same type,
+    /// returning the document it contains if there is one.
+    ///
+    /// Fails on a `deleteByFilter` operation following an operation writing documents.
+    fn group_into(self, operations: &mut Vec<BulkOperation>) -> Result<Option<Object>> {
+        match self {
+            BulkLine::Upsert { document } => {
+                match operations.last_mut() {
+                    Some(BulkOperation::Update { documents_count }) => *documents_count += 1,
+                    _ => operations.push(BulkOperation::Update { documents_count: 1 }),
+                }
+                Ok(Some(document))
+            }
+            BulkLine::Replace { document } => {
+                match operations.last_mut() {
+                    Some(BulkOperation::Replace { documents_count }) => *documents_count += 1,
+                    _ => operations.push(BulkOperation::Replace { documents_count: 1 }),
+                }
+                Ok(Some(document))
+            }
+            BulkLine::Delete { id } => {
+                let id = id.as_str().map(String::from).unwrap_or_else(|| id.to_string());
+                match operations.last_mut() {
+                    Some(BulkOperation::Delete { documents_ids }) => documents_ids.push(id),
+                    _ => operations.push(BulkOperation::Delete { documents_ids: vec![id] }),
+                }
+                Ok(None)
+            }
+            BulkLine::DeleteByFilter { filter } => {
+                if operations.iter().any(|operation| operation.documents_count() != 0) {
+                    return Err(DocumentFormatError::DeleteByFilterAfterWrite);
+                }
+                operations.push(BulkOperation::DeleteByFilter { filter_expr: filter });
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Reads the NDJSON bulk operations from file and writes the documents they contain
+/// in NDJSON in a file, returning the consecutive operations of the same type grouped together.
+pub fn read_bulk_operations(input: &File, output: impl io::Write) -> Result<Vec<BulkOperation>> {
+    let input = unsafe { Mmap::map(input).map_err(DocumentFormatError::Io)? };
+    let mut out = BufWriter::new(output);
+
+    let mut operations = Vec::new();
+    for line in serde_json::Deserializer::from_slice(&input).into_iter::<BulkLine>() {
+        let line = line.map_err(DocumentFormatError::MalformedBulkOperation)?;
+        if let Some(document) = line.group_into(&mut operations)? {
+            to_writer(&mut out, &document)
+                .map_err(|e| DocumentFormatError::from((PayloadType::Ndjson, e)))?;
+        }
+    }
+
+    match out.into_inner() {
+        Ok(_) => Ok(operations),
+        Err(ie) => Err(DocumentFormatError::Io(ie.into_error())),
+    }
+}
+
+/// Same as [`read_bulk_operations`] but for operations already parsed as JSON values.
+pub fn read_bulk_operations_values(
+    input: Vec<Value>,
+    output: impl io::Write,
+) -> Result<Vec<BulkOperation>> {
+    let mut out = BufWriter::new(output);
+
+    let mut operations = Vec::new();
+    for line in input {
+        let line = serde_json::from_value::<BulkLine>(line)
+            .map_err(DocumentFormatError::MalformedBulkOperation)?;
+        if let Some(document) = line.group_into(&mut operations)? {
+            to_writer(&mut out, &document)
+                .map_err(|e| DocumentFormatError::from((PayloadType::Ndjson, e)))?;
+        }
+    }
+
+    match out.into_inner() {
+        Ok(_) => Ok(operations),
+        Err(ie) => Err(DocumentFormatError::Io(ie.into_error())),
+    }
+}
+
 /// The actual handling of the deserialization process in serde
 /// avoids storing the deserialized object in memory.
 ///
@@ -198,6 +198,7 @@ ApiKeyAlreadyExists                   , InvalidRequest       , CONFLICT ;
 ApiKeyNotFound                        , InvalidRequest       , NOT_FOUND ;
 BadParameter                          , InvalidRequest       , BAD_REQUEST;
 BadRequest                            , InvalidRequest       , BAD_REQUEST;
+ChatCompletionFailed                  , System               , BAD_GATEWAY ;
 DatabaseSizeLimitReached              , Internal             , INTERNAL_SERVER_ERROR;
 DocumentNotFound                      , InvalidRequest       , NOT_FOUND;
 DumpAlreadyProcessing                 , InvalidRequest       , CONFLICT;
@@ -214,6 +215,9 @@ ImmutableApiKeyUpdatedAt              , InvalidRequest       , BAD_REQUEST;
 ImmutableIndexCreatedAt               , InvalidRequest       , BAD_REQUEST;
 ImmutableIndexUid                     , InvalidRequest       , BAD_REQUEST;
 ImmutableIndexUpdatedAt               , InvalidRequest       , BAD_REQUEST;
+IndexAliasNotDeletable                , InvalidRequest       , BAD_REQUEST ;
+IndexAliasNotFound                    , InvalidRequest       , NOT_FOUND;
+IndexAliasReadOnly                    , InvalidRequest       , BAD_REQUEST ;
 IndexAlreadyExists                    , InvalidRequest       , CONFLICT ;
 IndexCreationFailed                   , Internal             , INTERNAL_SERVER_ERROR;
 IndexNotFound                         , InvalidRequest       , NOT_FOUND;
@@ -230,6 +234,17 @@ InvalidApiKeyLimit                    , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyName                     , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyOffset                   , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyUid                      , InvalidRequest       , BAD_REQUEST ;
+InvalidChatCompletionApiKey           , InvalidRequest       , BAD_REQUEST ;
+InvalidChatCompletionHeaders          , InvalidRequest       , BAD_REQUEST ;
+InvalidChatCompletionModel            , InvalidRequest       , BAD_REQUEST ;
+InvalidChatCompletionRequest          , InvalidRequest       , BAD_REQUEST ;
+InvalidChatCompletionResponse         , InvalidRequest       , BAD_REQUEST ;
+InvalidChatCompletionSource           , InvalidRequest       , BAD_REQUEST ;
+InvalidChatCompletionSystemPrompt     , InvalidRequest       , BAD_REQUEST ;
+InvalidChatCompletionUrl              , InvalidRequest       , BAD_REQUEST ;
+InvalidChatFilter                     , InvalidRequest       , BAD_REQUEST ;
+InvalidChatQ                          , InvalidRequest       , BAD_REQUEST ;
+InvalidChatStream                     , InvalidRequest       , BAD_REQUEST ;
 InvalidContentType                    , InvalidRequest       , UNSUPPORTED_MEDIA_TYPE ;
 InvalidDocumentCsvDelimiter           , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentFields                 , InvalidRequest       , BAD_REQUEST ;
@@ -241,12 +256,22 @@ InvalidDocumentGeoField               , InvalidRequest       , BAD_REQUEST ;
 InvalidVectorDimensions               , InvalidRequest       , BAD_REQUEST ;
 InvalidVectorsType                    , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentId                     , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentExpiresAt              , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentFieldType              , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentIds                    , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentLimit                  , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentOffset                 , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentOperator               , InvalidRequest       , BAD_REQUEST ;
+IngestPipelineError                   , InvalidRequest       , BAD_REQUEST ;
+DocumentSchemaViolation               , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentVersion                , InvalidRequest       , BAD_REQUEST ;
+DocumentVersionConflict               , InvalidRequest       , CONFLICT ;
 InvalidSearchEmbedder                 , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarEmbedder                , InvalidRequest       , BAD_REQUEST ;
+InvalidEmbeddingRegenerationEmbedders , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchHybridQuery              , InvalidRequest       , BAD_REQUEST ;
+InvalidIndexAliasIndexes              , InvalidRequest       , BAD_REQUEST ;
+InvalidIndexCloneSettingsOnly         , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexLimit                     , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexOffset                    , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexPrimaryKey                , InvalidRequest       , BAD_REQUEST ;
@@ -276,6 +301,8 @@ InvalidSimilarRetrieveVectors         , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchAttributesToRetrieve     , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchRankingScoreThreshold    , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarRankingScoreThreshold   , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchDiversity                , InvalidRequest       , BAD_REQUEST ;
+InvalidSimilarDiversity               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchRetrieveVectors          , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropLength               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropMarker               , InvalidRequest       , BAD_REQUEST ;
@@ -293,6 +320,7 @@ InvalidSearchHitsPerPage              , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarLimit                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchLimit                    , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchMatchingStrategy         , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchMedia                    , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarOffset                  , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchOffset                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchPage                     , InvalidRequest       , BAD_REQUEST ;
@@ -306,6 +334,7 @@ InvalidSearchShowRankingScore         , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarShowRankingScore        , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchShowRankingScoreDetails  , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarShowRankingScoreDetails , InvalidRequest       , BAD_REQUEST ;
+InvalidSimilarVectors                 , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchSort                     , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchDistinct                 , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDisplayedAttributes    , InvalidRequest       , BAD_REQUEST ;
@@ -313,6 +342,11 @@ InvalidSettingsDistinctAttribute      , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsProximityPrecision     , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFacetSearch            , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsPrefixSearch           , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsChat                   , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsIngestPipeline         , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsDocumentSchema         , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsFieldTypes             , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsGenerateDocumentIds    , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFaceting               , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFilterableAttributes   , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsPagination             , InvalidRequest       , BAD_REQUEST ;
@@ -345,6 +379,8 @@ InvalidTaskReverse                    , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskStatuses                   , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskTypes                      , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskUids                       , InvalidRequest       , BAD_REQUEST  ;
+InvalidTransactionDocuments           , InvalidRequest       , BAD_REQUEST ;
+InvalidTransactionDuplicateIndexFound , InvalidRequest       , BAD_REQUEST ;
 InvalidBatchUids                      , InvalidRequest       , BAD_REQUEST  ;
 IoError                               , System               , UNPROCESSABLE_ENTITY;
 FeatureNotEnabled                     , InvalidRequest       , BAD_REQUEST ;
@@ -357,6 +393,7 @@ MissingAuthorizationHeader            , Auth                 , UNAUTHORIZED ;
 MissingContentType                    , InvalidRequest       , UNSUPPORTED_MEDIA_TYPE ;
 MissingDocumentId                     , InvalidRequest       , BAD_REQUEST ;
 MissingFacetSearchFacetName           , InvalidRequest       , BAD_REQUEST ;
+MissingIndexAliasIndexes              , InvalidRequest       , BAD_REQUEST ;
 MissingIndexUid                       , InvalidRequest       , BAD_REQUEST ;
 MissingMasterKey                      , Auth                 , UNAUTHORIZED ;
 MissingNetworkUrl                     , InvalidRequest       , BAD_REQUEST ;
@@ -422,12 +459,18 @@ impl ErrorCode for milli::Error {
                         Code::InvalidDocumentId
                     }
                     UserError::MissingDocumentField(_) => Code::InvalidDocumentFields,
+                    UserError::InvalidDocumentOperator { .. } => Code::InvalidDocumentOperator,
+                    UserError::InvalidDocumentVersion { .. } => Code::InvalidDocumentVersion,
+                    UserError::DocumentVersionConflict { .. } => Code::DocumentVersionConflict,
+                    UserError::InvalidDocumentExpiresAt { .. } => Code::InvalidDocumentExpiresAt,
+                    UserError::InvalidDocumentFieldType { .. } => Code::InvalidDocumentFieldType,
                     UserError::InvalidFieldForSource { .. }
                     | UserError::MissingFieldForSource { .. }
                     | UserError::InvalidOpenAiModel { .. }
                     | UserError::InvalidOpenAiModelDimensions { .. }
                     | UserError::InvalidOpenAiModelDimensionsMax { .. }
                     | UserError::InvalidSettingsDimensions { .. }
+                    | UserError::InvalidSettingsEmbedderRateLimit { .. }
                     | UserError::InvalidUrl { .. }
                     | UserError::InvalidSettingsDocumentTemplateMaxBytes { .. }
                     | UserError::InvalidPrompt(_)
@@ -437,11 +480,22 @@ impl ErrorCode for milli::Error {
                     | UserError::InvalidSettingsEmbedder { .. } => Code::InvalidSettingsEmbedders,
                     UserError::TooManyEmbedders(_) => Code::InvalidSettingsEmbedders,
                     UserError::InvalidPromptForEmbeddings(..) => Code::InvalidSettingsEmbedders,
+                    UserError::InvalidSettingsChat { .. } => Code::InvalidSettingsChat,
+                    UserError::InvalidSettingsIngestPipeline { .. } => {
+                        Code::InvalidSettingsIngestPipeline
+                    }
+                    UserError::IngestPipelineError { .. } => Code::IngestPipelineError,
+                    UserError::InvalidSettingsDocumentSchema(_) => {
+                        Code::InvalidSettingsDocumentSchema
+                    }
+                    UserError::DocumentSchemaViolation { .. } => Code::DocumentSchemaViolation,
+                    UserError::InvalidSettingsFieldTypes { .. } => Code::InvalidSettingsFieldTypes,
                     UserError::NoPrimaryKeyCandidateFound => Code::IndexPrimaryKeyNoCandidateFound,
                     UserError::MultiplePrimaryKeyCandidatesFound { .. } => {
                         Code::IndexPrimaryKeyMultipleCandidatesFound
                     }
                     UserError::PrimaryKeyCannotBeChanged(_) => Code::IndexPrimaryKeyAlreadyExists,
+                    UserError::InvalidCompositePrimaryKey(_) => Code::InvalidIndexPrimaryKey,
                     UserError::InvalidDistinctAttribute { .. } => Code::InvalidSearchDistinct,
                     UserError::SortRankingRuleMissing => Code::InvalidSearchSort,
                     UserError::InvalidFacetsDistribution { .. } => Code::InvalidSearchFacets,
@@ -455,9 +509,13 @@ impl ErrorCode for milli::Error {
                     UserError::CriterionError(_) => Code::InvalidSettingsRankingRules,
                     UserError::InvalidGeoField { .. } => Code::InvalidDocumentGeoField,
                     UserError::InvalidVectorDimensions { .. }
+                    | UserError::InvalidMultiVectorDimensions { .. }
                     | UserError::InvalidIndexingVectorDimensions { .. } => {
                         Code::InvalidVectorDimensions
                     }
+                    UserError::ExpectedMultiVectorQuery | UserError::UnexpectedMultiVectorQuery => {
+                        Code::InvalidSearchVector
+                    }
                     UserError::InvalidVectorsMapType { .. }
                     | UserError::InvalidVectorsEmbedderConf { .. } => Code::InvalidVectorsType,
                     UserError::TooManyVectors(_, _) => Code::TooManyVectors,
@@ -467,6 +525,9 @@ impl ErrorCode for milli::Error {
                     }
                     UserError::InvalidSearchEmbedder(_) => Code::InvalidSearchEmbedder,
                     UserError::InvalidSimilarEmbedder(_) => Code::InvalidSimilarEmbedder,
+                    UserError::InvalidEmbeddingRegenerationEmbedder(_) => {
+                        Code::InvalidEmbeddingRegenerationEmbedders
+                    }
                     UserError::VectorEmbeddingError(_) | UserError::DocumentEmbeddingError(_) => {
                         Code::VectorEmbeddingError
                     }
@@ -603,6 +664,18 @@ impl fmt::Display for deserr_codes::InvalidSimilarRankingScoreThreshold {
     }
 }
 
+impl fmt::Display for deserr_codes::InvalidSearchDiversity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the value of `diversity` is invalid, expected a float between `0.0` and `1.0`.")
+    }
+}
+
+impl fmt::Display for deserr_codes::InvalidSimilarDiversity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        deserr_codes::InvalidSearchDiversity.fmt(f)
+    }
+}
+
 impl fmt::Display for deserr_codes::InvalidNetworkUrl {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "the value of `url` is invalid, expected a string.")
@@ -1,6 +1,8 @@
 use std::collections::BTreeMap;
 
+use deserr::Deserr;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
 #[serde(rename_all = "camelCase", default)]
@@ -12,6 +14,7 @@ pub struct RuntimeTogglableFeatures {
     pub network: bool,
     pub get_task_documents_route: bool,
     pub composite_embedders: bool,
+    pub chat_completions: bool,
 }
 
 #[derive(Default, Debug, Clone, Copy)]
@@ -37,3 +40,45 @@ pub struct Network {
     #[serde(default)]
     pub remotes: BTreeMap<String, Remote>,
 }
+
+#[derive(Serialize, Deserialize, Deserr, ToSchema, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum ChatCompletionSource {
+    #[default]
+    OpenAi,
+    Rest,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatCompletionSettings {
+    #[serde(default)]
+    pub source: ChatCompletionSource,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub api_key: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub request: Option<serde_json::Value>,
+    #[serde(default)]
+    pub response: Option<serde_json::Value>,
+    #[serde(default)]
+    pub system_prompt: Option<String>,
+}
+
+impl ChatCompletionSettings {
+    pub const DEFAULT_OPENAI_URL: &'static str = "https://api.openai.com/v1/chat/completions";
+    pub const DEFAULT_OPENAI_MODEL: &'static str = "gpt-4o-mini";
+    pub const DEFAULT_SYSTEM_PROMPT: &'static str = "You are a helpful assistant answering questions using only the provided documents. Cite the documents you used by their number, like [1]. If the documents do not contain the answer, say so.";
+
+    pub fn hide_secrets(&mut self) {
+        if let Some(api_key) = &mut self.api_key {
+            crate::settings::hide_secret(api_key);
+        }
+    }
+}
@@ -308,6 +308,24 @@ pub enum Action {
     #[serde(rename = "network.update")]
     #[deserr(rename = "network.update")]
     NetworkUpdate,
+    #[serde(rename = "chat")]
+    #[deserr(rename = "chat")]
+    Chat,
+    #[serde(rename = "chatSettings.get")]
+    #[deserr(rename = "chatSettings.get")]
+    ChatSettingsGet,
+    #[serde(rename = "chatSettings.update")]
+    #[deserr(rename = "chatSettings.update")]
+    ChatSettingsUpdate,
+    #[serde(rename = "aliases.get")]
+    #[deserr(rename = "aliases.get")]
+    AliasesGet,
+    #[serde(rename = "aliases.update")]
+    #[deserr(rename = "aliases.update")]
+    AliasesUpdate,
+    #[serde(rename = "aliases.delete")]
+    #[deserr(rename = "aliases.delete")]
+    AliasesDelete,
 }
 
 impl Action {
@@ -349,6 +367,12 @@ impl Action {
             EXPERIMENTAL_FEATURES_UPDATE => Some(Self::ExperimentalFeaturesUpdate),
             NETWORK_GET => Some(Self::NetworkGet),
             NETWORK_UPDATE => Some(Self::NetworkUpdate),
+            CHAT => Some(Self::Chat),
+            CHAT_SETTINGS_GET => Some(Self::ChatSettingsGet),
+            CHAT_SETTINGS_UPDATE => Some(Self::ChatSettingsUpdate),
+            ALIASES_GET => Some(Self::AliasesGet),
+            ALIASES_UPDATE => Some(Self::AliasesUpdate),
+            ALIASES_DELETE => Some(Self::AliasesDelete),
             _otherwise => None,
         }
     }
@@ -397,4 +421,12 @@ pub mod actions {
 
     pub const NETWORK_GET: u8 = NetworkGet.repr();
     pub const NETWORK_UPDATE: u8 = NetworkUpdate.repr();
+
+    pub const CHAT: u8 = Chat.repr();
+    pub const CHAT_SETTINGS_GET: u8 = ChatSettingsGet.repr();
+    pub const CHAT_SETTINGS_UPDATE: u8 = ChatSettingsUpdate.repr();
+
+    pub const ALIASES_GET: u8 = AliasesGet.repr();
+    pub const ALIASES_UPDATE: u8 = AliasesUpdate.repr();
+    pub const ALIASES_DELETE: u8 = AliasesDelete.repr();
 }
@@ -11,8 +11,12 @@ use fst::IntoStreamer;
 use milli::disabled_typos_terms::DisabledTyposTerms;
 use milli::index::{IndexEmbeddingConfig, PrefixSearch};
 use milli::proximity::ProximityPrecision;
+use milli::update::chat::ChatSettings;
 use milli::update::Setting;
-use milli::{Criterion, CriterionError, FilterableAttributesRule, Index, DEFAULT_VALUES_PER_FACET};
+use milli::{
+    Criterion, CriterionError, DocumentSchemaSettings, FieldType, FilterableAttributesRule, Index,
+    IngestProcessor, DEFAULT_VALUES_PER_FACET,
+};
 use serde::{Deserialize, Serialize, Serializer};
 use utoipa::ToSchema;
 
@@ -293,6 +297,31 @@ pub struct Settings<T> {
     #[deserr(default, error = DeserrJsonError<InvalidSettingsPrefixSearch>)]
     #[schema(value_type = Option<PrefixSearchSettings>, example = json!("Hemlo"))]
     pub prefix_search: Setting<PrefixSearchSettings>,
+    /// Settings used to answer questions about the documents of the index.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsChat>)]
+    #[schema(value_type = Option<ChatSettings>)]
+    pub chat: Setting<ChatSettings>,
+    /// Transformations applied, in order, to the documents sent to the index before they are indexed.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsIngestPipeline>)]
+    #[schema(value_type = Option<Vec<IngestProcessor>>)]
+    pub ingest_pipeline: Setting<Vec<IngestProcessor>>,
+    /// JSON Schema the documents sent to the index must match.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsDocumentSchema>)]
+    #[schema(value_type = Option<DocumentSchemaSettings>)]
+    pub document_schema: Setting<DocumentSchemaSettings>,
+    /// Types of the document fields, used to convert their values before they are filtered or sorted.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsFieldTypes>)]
+    #[schema(value_type = Option<BTreeMap<String, FieldType>>, example = json!({ "releaseDate": "date" }))]
+    pub field_types: Setting<BTreeMap<String, FieldType>>,
+    /// Whether a UUIDv7 is generated as the primary key value of the documents that don't have one.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsGenerateDocumentIds>)]
+    #[schema(value_type = Option<bool>, example = json!(true))]
+    pub generate_document_ids: Setting<bool>,
 
     #[serde(skip)]
     #[deserr(skip)]
@@ -314,24 +343,25 @@ impl<T> Settings<T> {
                 continue;
             };
 
-            Self::hide_secret(api_key);
+            hide_secret(api_key);
         }
     }
+}
 
-    fn hide_secret(secret: &mut String) {
-        match secret.len() {
-            x if x < 10 => {
-                secret.replace_range(.., "XXX...");
-            }
-            x if x < 20 => {
-                secret.replace_range(2.., "XXXX...");
-            }
-            x if x < 30 => {
-                secret.replace_range(3.., "XXXXX...");
-            }
-            _x => {
-                secret.replace_range(5.., "XXXXXX...");
-            }
+/// Replaces most of a secret by `X`s, keeping a prefix that depends on the length of the secret.
+pub(crate) fn hide_secret(secret: &mut String) {
+    match secret.len() {
+        x if x < 10 => {
+            secret.replace_range(.., "XXX...");
+        }
+        x if x < 20 => {
+            secret.replace_range(2.., "XXXX...");
+        }
+        x if x < 30 => {
+            secret.replace_range(3.., "XXXXX...");
+        }
+        _x => {
+            secret.replace_range(5.., "XXXXXX...");
         }
     }
 }
@@ -359,6 +389,11 @@ impl Settings<Checked> {
             localized_attributes: Setting::Reset,
             facet_search: Setting::Reset,
             prefix_search: Setting::Reset,
+            chat: Setting::Reset,
+            ingest_pipeline: Setting::Reset,
+            document_schema: Setting::Reset,
+            field_types: Setting::Reset,
+            generate_document_ids: Setting::Reset,
             _kind: PhantomData,
         }
     }
@@ -385,6 +420,11 @@ impl Settings<Checked> {
             localized_attributes: localized_attributes_rules,
             facet_search,
             prefix_search,
+            chat,
+            ingest_pipeline,
+            document_schema,
+            field_types,
+            generate_document_ids,
             _kind,
         } = self;
 
@@ -409,6 +449,11 @@ impl Settings<Checked> {
             localized_attributes: localized_attributes_rules,
             facet_search,
             prefix_search,
+            chat,
+            ingest_pipeline,
+            document_schema,
+            field_types,
+            generate_document_ids,
             _kind: PhantomData,
         }
     }
@@ -459,6 +504,11 @@ impl Settings<Unchecked> {
             localized_attributes: self.localized_attributes,
             facet_search: self.facet_search,
             prefix_search: self.prefix_search,
+            chat: self.chat,
+            ingest_pipeline: self.ingest_pipeline,
+            document_schema: self.document_schema,
+            field_types: self.field_types,
+            generate_document_ids: self.generate_document_ids,
             _kind: PhantomData,
         }
     }
@@ -535,6 +585,11 @@ impl Settings<Unchecked> {
             },
             prefix_search: other.prefix_search.or(self.prefix_search),
             facet_search: other.facet_search.or(self.facet_search),
+            chat: other.chat.clone().or(self.chat.clone()),
+            ingest_pipeline: other.ingest_pipeline.clone().or(self.ingest_pipeline.clone()),
+            document_schema: other.document_schema.clone().or(self.document_schema.clone()),
+            field_types: other.field_types.clone().or(self.field_types.clone()),
+            generate_document_ids: other.generate_document_ids.or(self.generate_document_ids),
             _kind: PhantomData,
         }
     }
@@ -548,9 +603,9 @@ pub struct Facets {
     pub min_level_size: Option<NonZeroUsize>,
 }
 
-pub fn apply_settings_to_builder(
+pub fn apply_settings_to_builder<T>(
     settings: &Settings<Checked>,
-    builder: &mut milli::update::Settings,
+    builder: &mut milli::update::Settings<'_, '_, '_, T>,
 ) {
     let Settings {
         displayed_attributes,
@@ -573,6 +628,11 @@ pub fn apply_settings_to_builder(
         localized_attributes: localized_attributes_rules,
         facet_search,
         prefix_search,
+        chat,
+        ingest_pipeline,
+        document_schema,
+        field_types,
+        generate_document_ids,
         _kind,
     } = settings;
 
@@ -783,6 +843,38 @@ pub fn apply_settings_to_builder(
         Setting::Reset => builder.reset_facet_search(),
         Setting::NotSet => (),
     }
+
+    match chat {
+        Setting::Set(chat) => builder.set_chat(chat.clone()),
+        Setting::Reset => builder.reset_chat(),
+        Setting::NotSet => (),
+    }
+
+    match ingest_pipeline {
+        Setting::Set(ingest_pipeline) => builder.set_ingest_pipeline(ingest_pipeline.clone()),
+        Setting::Reset => builder.reset_ingest_pipeline(),
+        Setting::NotSet => (),
+    }
+
+    match document_schema {
+        Setting::Set(document_schema) => builder.set_document_schema(document_schema.clone()),
+        Setting::Reset => builder.reset_document_schema(),
+        Setting::NotSet => (),
+    }
+
+    match field_types {
+        Setting::Set(field_types) => builder.set_field_types(field_types.clone()),
+        Setting::Reset => builder.reset_field_types(),
+        Setting::NotSet => (),
+    }
+
+    match generate_document_ids {
+        Setting::Set(generate_document_ids) => {
+            builder.set_generate_document_ids(*generate_document_ids)
+        }
+        Setting::Reset => builder.reset_generate_document_ids(),
+        Setting::NotSet => (),
+    }
 }
 
 pub enum SecretPolicy {
@@ -889,6 +981,16 @@ pub fn settings(
 
     let facet_search = index.facet_search(rtxn)?;
 
+    let chat = ChatSettings::from(index.chat_config(rtxn)?);
+
+    let ingest_pipeline = index.ingest_pipeline(rtxn)?;
+
+    let document_schema = index.document_schema(rtxn)?;
+
+    let field_types = index.field_types(rtxn)?;
+
+    let generate_document_ids = index.generate_document_ids(rtxn)?;
+
     let mut settings = Settings {
         displayed_attributes: match displayed_attributes {
             Some(attrs) => Setting::Set(attrs),
@@ -927,6 +1029,14 @@ pub fn settings(
         },
         prefix_search: Setting::Set(prefix_search.unwrap_or_default()),
         facet_search: Setting::Set(facet_search),
+        chat: Setting::Set(chat),
+        ingest_pipeline: Setting::Set(ingest_pipeline),
+        document_schema: match document_schema {
+            Some(document_schema) => Setting::Set(document_schema),
+            None => Setting::Reset,
+        },
+        field_types: Setting::Set(field_types),
+        generate_document_ids: Setting::Set(generate_document_ids),
         _kind: PhantomData,
     };
 
@@ -1154,6 +1264,11 @@ pub(crate) mod test {
             search_cutoff_ms: Setting::NotSet,
             facet_search: Setting::NotSet,
             prefix_search: Setting::NotSet,
+            chat: Setting::NotSet,
+            ingest_pipeline: Setting::NotSet,
+            document_schema: Setting::NotSet,
+            field_types: Setting::NotSet,
+            generate_document_ids: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
@@ -1185,6 +1300,11 @@ pub(crate) mod test {
             search_cutoff_ms: Setting::NotSet,
             facet_search: Setting::NotSet,
             prefix_search: Setting::NotSet,
+            chat: Setting::NotSet,
+            ingest_pipeline: Setting::NotSet,
+            document_schema: Setting::NotSet,
+            field_types: Setting::NotSet,
+            generate_document_ids: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
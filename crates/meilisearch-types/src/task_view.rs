@@ -6,7 +6,10 @@ use utoipa::ToSchema;
 use crate::batches::BatchId;
 use crate::error::ResponseError;
 use crate::settings::{Settings, Unchecked};
-use crate::tasks::{serialize_duration, Details, IndexSwap, Kind, Status, Task, TaskId};
+use crate::tasks::{
+    serialize_duration, Details, DocumentSchemaViolation, DocumentVersionConflict, IndexSwap, Kind,
+    Status, Task, TaskId,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -74,12 +77,21 @@ pub struct DetailsView {
     /// Number of documents received for documentAdditionOrUpdate task.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub received_documents: Option<u64>,
+    /// Number of operations received for documentBulk and indexTransaction tasks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub received_operations: Option<u64>,
     /// Number of documents finally indexed for documentAdditionOrUpdate task or a documentAdditionOrUpdate batch of tasks.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub indexed_documents: Option<Option<u64>>,
     /// Number of documents edited for editDocumentByFunction task.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub edited_documents: Option<Option<u64>>,
+    /// Number of documents whose embeddings were regenerated for embeddingRegeneration task.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regenerated_documents: Option<Option<u64>>,
+    /// Embedders whose embeddings are regenerated for embeddingRegeneration task, `null` for all the embedders.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regenerated_embedders: Option<Option<Vec<String>>>,
     /// Value for the primaryKey field encountered if any for indexCreation or indexUpdate task.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub primary_key: Option<Option<String>>,
@@ -89,6 +101,15 @@ pub struct DetailsView {
     /// Number of documents finally deleted for documentDeletion and indexDeletion tasks.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub deleted_documents: Option<Option<u64>>,
+    /// Documents of documentAdditionOrUpdate and documentDeletion tasks that were not at the expected `_version`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_conflicts: Option<Vec<DocumentVersionConflict>>,
+    /// Documents of documentAdditionOrUpdate tasks skipped because they do not match the document schema of the index.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invalid_documents: Option<Vec<DocumentSchemaViolation>>,
+    /// Ids generated, in the order of the payload, for the documents of documentAdditionOrUpdate tasks that didn't have one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generated_document_ids: Option<Vec<String>>,
     /// Number of tasks that match the request for taskCancelation or taskDeletion tasks.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub matched_tasks: Option<u64>,
@@ -114,6 +135,12 @@ pub struct DetailsView {
     pub settings: Option<Box<Settings<Unchecked>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub swaps: Option<Vec<IndexSwap>>,
+    /// Uid of the index created by an indexClone task.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_index_uid: Option<String>,
+    /// Whether an indexClone task only copies the settings of the index.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settings_only: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub upgrade_from: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -128,6 +155,11 @@ impl DetailsView {
                 (None, Some(doc)) | (Some(doc), None) => Some(doc),
                 (Some(left), Some(right)) => Some(left + right),
             },
+            received_operations: match (self.received_operations, other.received_operations) {
+                (None, None) => None,
+                (None, Some(ops)) | (Some(ops), None) => Some(ops),
+                (Some(left), Some(right)) => Some(left + right),
+            },
             indexed_documents: match (self.indexed_documents, other.indexed_documents) {
                 (None, None) => None,
                 (None, Some(None)) | (Some(None), None) | (Some(None), Some(None)) => Some(None),
@@ -144,6 +176,22 @@ impl DetailsView {
                 }
                 (Some(Some(left)), Some(Some(right))) => Some(Some(left + right)),
             },
+            regenerated_documents: match (self.regenerated_documents, other.regenerated_documents) {
+                (None, None) => None,
+                (None, Some(None)) | (Some(None), None) | (Some(None), Some(None)) => Some(None),
+                (None | Some(None), Some(Some(doc))) | (Some(Some(doc)), None | Some(None)) => {
+                    Some(Some(doc))
+                }
+                (Some(Some(left)), Some(Some(right))) => Some(Some(left + right)),
+            },
+            regenerated_embedders: match (&self.regenerated_embedders, &other.regenerated_embedders)
+            {
+                (None, None) => None,
+                (None, Some(embedders)) | (Some(embedders), None) => Some(embedders.clone()),
+                // We should never be able to batch multiple embedding regenerations at the same time. So we return
+                // the first one we encounter but that shouldn't be an issue anyway.
+                (Some(left), Some(_right)) => Some(left.clone()),
+            },
             primary_key: match (&self.primary_key, &other.primary_key) {
                 (None, None) => None,
                 (None, Some(None)) | (Some(None), None) | (Some(None), Some(None)) => Some(None),
@@ -166,6 +214,39 @@ impl DetailsView {
                 }
                 (Some(Some(left)), Some(Some(right))) => Some(Some(left + right)),
             },
+            version_conflicts: match (
+                self.version_conflicts.clone(),
+                other.version_conflicts.clone(),
+            ) {
+                (None, None) => None,
+                (None, Some(conflicts)) | (Some(conflicts), None) => Some(conflicts),
+                (Some(mut left), Some(mut right)) => {
+                    left.append(&mut right);
+                    Some(left)
+                }
+            },
+            invalid_documents: match (
+                self.invalid_documents.clone(),
+                other.invalid_documents.clone(),
+            ) {
+                (None, None) => None,
+                (None, Some(documents)) | (Some(documents), None) => Some(documents),
+                (Some(mut left), Some(mut right)) => {
+                    left.append(&mut right);
+                    Some(left)
+                }
+            },
+            generated_document_ids: match (
+                self.generated_document_ids.clone(),
+                other.generated_document_ids.clone(),
+            ) {
+                (None, None) => None,
+                (None, Some(ids)) | (Some(ids), None) => Some(ids),
+                (Some(mut left), Some(mut right)) => {
+                    left.append(&mut right);
+                    Some(left)
+                }
+            },
             matched_tasks: match (self.matched_tasks, other.matched_tasks) {
                 (None, None) => None,
                 (None, Some(task)) | (Some(task), None) => Some(task),
@@ -238,6 +319,10 @@ impl DetailsView {
                     Some(left)
                 }
             },
+            // We should never be able to batch multiple index clones at the same time. So we return
+            // the first one we encounter but that shouldn't be an issue anyway.
+            new_index_uid: self.new_index_uid.clone().or_else(|| other.new_index_uid.clone()),
+            settings_only: self.settings_only.or(other.settings_only),
             // We want the earliest version
             upgrade_from: match (self.upgrade_from.clone(), other.upgrade_from.clone()) {
                 (None, None) => None,
@@ -257,13 +342,40 @@ impl DetailsView {
 impl From<Details> for DetailsView {
     fn from(details: Details) -> Self {
         match details {
-            Details::DocumentAdditionOrUpdate { received_documents, indexed_documents } => {
-                DetailsView {
-                    received_documents: Some(received_documents),
-                    indexed_documents: Some(indexed_documents),
-                    ..DetailsView::default()
-                }
+            Details::DocumentAdditionOrUpdate {
+                received_documents,
+                indexed_documents,
+                version_conflicts,
+                invalid_documents,
+                generated_document_ids,
+            } => DetailsView {
+                received_documents: Some(received_documents),
+                indexed_documents: Some(indexed_documents),
+                version_conflicts: (!version_conflicts.is_empty()).then_some(version_conflicts),
+                invalid_documents: (!invalid_documents.is_empty()).then_some(invalid_documents),
+                generated_document_ids: (!generated_document_ids.is_empty())
+                    .then_some(generated_document_ids),
+                ..DetailsView::default()
+            },
+            Details::DocumentBulk {
+                received_operations,
+                indexed_documents,
+                deleted_documents,
+                generated_document_ids,
             }
+            | Details::IndexTransaction {
+                received_operations,
+                indexed_documents,
+                deleted_documents,
+                generated_document_ids,
+            } => DetailsView {
+                received_operations: Some(received_operations),
+                indexed_documents: Some(indexed_documents),
+                deleted_documents: Some(deleted_documents),
+                generated_document_ids: (!generated_document_ids.is_empty())
+                    .then_some(generated_document_ids),
+                ..DetailsView::default()
+            },
             Details::DocumentEdition {
                 deleted_documents,
                 edited_documents,
@@ -278,6 +390,16 @@ impl From<Details> for DetailsView {
                 function: Some(function),
                 ..DetailsView::default()
             },
+            Details::EmbeddingRegeneration {
+                original_filter,
+                embedders,
+                regenerated_documents,
+            } => DetailsView {
+                original_filter: Some(original_filter),
+                regenerated_embedders: Some(embedders),
+                regenerated_documents: Some(regenerated_documents),
+                ..DetailsView::default()
+            },
             Details::SettingsUpdate { mut settings } => {
                 settings.hide_secrets();
                 DetailsView { settings: Some(settings), ..DetailsView::default() }
@@ -288,9 +410,11 @@ impl From<Details> for DetailsView {
             Details::DocumentDeletion {
                 provided_ids: received_document_ids,
                 deleted_documents,
+                version_conflicts,
             } => DetailsView {
                 provided_ids: Some(received_document_ids),
                 deleted_documents: Some(deleted_documents),
+                version_conflicts: (!version_conflicts.is_empty()).then_some(version_conflicts),
                 original_filter: Some(None),
                 ..DetailsView::default()
             },
@@ -327,6 +451,11 @@ impl From<Details> for DetailsView {
             Details::IndexSwap { swaps } => {
                 DetailsView { swaps: Some(swaps), ..Default::default() }
             }
+            Details::IndexClone { new_index_uid, settings_only } => DetailsView {
+                new_index_uid: Some(new_index_uid),
+                settings_only: Some(settings_only),
+                ..Default::default()
+            },
             Details::UpgradeDatabase { from, to } => DetailsView {
                 upgrade_from: Some(format!("v{}.{}.{}", from.0, from.1, from.2)),
                 upgrade_to: Some(format!("v{}.{}.{}", to.0, to.1, to.2)),
@@ -1,5 +1,5 @@
 use core::fmt;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::fmt::{Display, Write};
 use std::str::FromStr;
 
@@ -51,16 +51,20 @@ impl Task {
             | TaskCancelation { .. }
             | TaskDeletion { .. }
             | UpgradeDatabase { .. }
-            | IndexSwap { .. } => None,
+            | IndexSwap { .. }
+            | IndexTransaction { .. } => None,
             DocumentAdditionOrUpdate { index_uid, .. }
+            | DocumentBulk { index_uid, .. }
             | DocumentEdition { index_uid, .. }
+            | EmbeddingRegeneration { index_uid, .. }
             | DocumentDeletion { index_uid, .. }
             | DocumentDeletionByFilter { index_uid, .. }
             | DocumentClear { index_uid }
             | SettingsUpdate { index_uid, .. }
             | IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
-            | IndexDeletion { index_uid } => Some(index_uid),
+            | IndexDeletion { index_uid }
+            | IndexClone { index_uid, .. } => Some(index_uid),
         }
     }
 
@@ -72,8 +76,11 @@ impl Task {
     /// Return the content-uuid if there is one
     pub fn content_uuid(&self) -> Option<Uuid> {
         match self.kind {
-            KindWithContent::DocumentAdditionOrUpdate { content_file, .. } => Some(content_file),
+            KindWithContent::DocumentAdditionOrUpdate { content_file, .. }
+            | KindWithContent::DocumentBulk { content_file, .. }
+            | KindWithContent::IndexTransaction { content_file, .. } => Some(content_file),
             KindWithContent::DocumentEdition { .. }
+            | KindWithContent::EmbeddingRegeneration { .. }
             | KindWithContent::DocumentDeletion { .. }
             | KindWithContent::DocumentDeletionByFilter { .. }
             | KindWithContent::DocumentClear { .. }
@@ -82,6 +89,7 @@ impl Task {
             | KindWithContent::IndexCreation { .. }
             | KindWithContent::IndexUpdate { .. }
             | KindWithContent::IndexSwap { .. }
+            | KindWithContent::IndexClone { .. }
             | KindWithContent::TaskCancelation { .. }
             | KindWithContent::TaskDeletion { .. }
             | KindWithContent::DumpCreation { .. }
@@ -102,9 +110,20 @@ pub enum KindWithContent {
         documents_count: u64,
         allow_index_creation: bool,
     },
+    DocumentBulk {
+        index_uid: String,
+        primary_key: Option<String>,
+        /// The documents of the replace and upsert operations, in order.
+        content_file: Uuid,
+        operations: Vec<BulkOperation>,
+        allow_index_creation: bool,
+    },
     DocumentDeletion {
         index_uid: String,
         documents_ids: Vec<String>,
+        /// The version expected for the documents that must only be deleted at this version.
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        expected_versions: BTreeMap<String, u64>,
     },
     DocumentDeletionByFilter {
         index_uid: String,
@@ -116,6 +135,11 @@ pub enum KindWithContent {
         context: Option<milli::Object>,
         function: String,
     },
+    EmbeddingRegeneration {
+        index_uid: String,
+        filter_expr: Option<serde_json::Value>,
+        embedders: Option<Vec<String>>,
+    },
     DocumentClear {
         index_uid: String,
     },
@@ -139,6 +163,18 @@ pub enum KindWithContent {
     IndexSwap {
         swaps: Vec<IndexSwap>,
     },
+    IndexTransaction {
+        /// The documents of the replace and upsert operations of every index, in order.
+        content_file: Uuid,
+        operations: Vec<TransactionOperation>,
+        allow_index_creation: bool,
+    },
+    IndexClone {
+        index_uid: String,
+        new_index_uid: String,
+        /// Only the settings of the index are copied, not its documents.
+        settings_only: bool,
+    },
     TaskCancelation {
         query: String,
         tasks: RoaringBitmap,
@@ -163,11 +199,111 @@ pub struct IndexSwap {
     pub indexes: (String, String),
 }
 
+/// The changes an `indexTransaction` task applies to one of its indexes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionOperation {
+    pub index_uid: String,
+    pub primary_key: Option<String>,
+    /// Reads the documents of the content file following the ones of the previous indexes.
+    pub documents: Vec<BulkOperation>,
+    /// Applied once the document operations are done.
+    pub settings: Option<Box<Settings<Unchecked>>>,
+}
+
+impl TransactionOperation {
+    /// The number of operations of the request applied to this index.
+    pub fn operations_count(&self) -> u64 {
+        self.documents.iter().map(BulkOperation::operations_count).sum::<u64>()
+            + self.settings.is_some() as u64
+    }
+}
+
+/// Consecutive operations of the same type of a `documentBulk` task.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BulkOperation {
+    /// Replaces the next `documents_count` documents of the content file.
+    Replace {
+        documents_count: u64,
+    },
+    /// Updates the next `documents_count` documents of the content file.
+    Update {
+        documents_count: u64,
+    },
+    Delete {
+        documents_ids: Vec<String>,
+    },
+    /// Deletes the documents matching the filter before the task is applied.
+    DeleteByFilter {
+        filter_expr: serde_json::Value,
+    },
+}
+
+impl BulkOperation {
+    /// The number of documents of the content file this operation reads.
+    pub fn documents_count(&self) -> u64 {
+        match self {
+            BulkOperation::Replace { documents_count }
+            | BulkOperation::Update { documents_count } => *documents_count,
+            BulkOperation::Delete { .. } | BulkOperation::DeleteByFilter { .. } => 0,
+        }
+    }
+
+    /// The number of operations of the request grouped in this one.
+    pub fn operations_count(&self) -> u64 {
+        match self {
+            BulkOperation::Replace { documents_count }
+            | BulkOperation::Update { documents_count } => *documents_count,
+            BulkOperation::Delete { documents_ids } => documents_ids.len() as u64,
+            BulkOperation::DeleteByFilter { .. } => 1,
+        }
+    }
+}
+
+/// A document operation rejected because the document was not at the version it expected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentVersionConflict {
+    pub document_id: String,
+    pub expected_version: u64,
+    pub current_version: u64,
+}
+
+impl From<milli::update::new::indexer::VersionConflict> for DocumentVersionConflict {
+    fn from(conflict: milli::update::new::indexer::VersionConflict) -> Self {
+        let milli::update::new::indexer::VersionConflict {
+            document_id,
+            expected_version,
+            current_version,
+        } = conflict;
+        Self { document_id, expected_version, current_version }
+    }
+}
+
+/// A document skipped because it does not match the document schema of its index.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentSchemaViolation {
+    pub document_id: String,
+    pub path: String,
+    pub message: String,
+}
+
+impl From<milli::SchemaViolation> for DocumentSchemaViolation {
+    fn from(violation: milli::SchemaViolation) -> Self {
+        let milli::SchemaViolation { document_id, path, message } = violation;
+        Self { document_id, path, message }
+    }
+}
+
 impl KindWithContent {
     pub fn as_kind(&self) -> Kind {
         match self {
             KindWithContent::DocumentAdditionOrUpdate { .. } => Kind::DocumentAdditionOrUpdate,
+            KindWithContent::DocumentBulk { .. } => Kind::DocumentBulk,
             KindWithContent::DocumentEdition { .. } => Kind::DocumentEdition,
+            KindWithContent::EmbeddingRegeneration { .. } => Kind::EmbeddingRegeneration,
             KindWithContent::DocumentDeletion { .. } => Kind::DocumentDeletion,
             KindWithContent::DocumentDeletionByFilter { .. } => Kind::DocumentDeletion,
             KindWithContent::DocumentClear { .. } => Kind::DocumentDeletion,
@@ -176,6 +312,8 @@ impl KindWithContent {
             KindWithContent::IndexDeletion { .. } => Kind::IndexDeletion,
             KindWithContent::IndexUpdate { .. } => Kind::IndexUpdate,
             KindWithContent::IndexSwap { .. } => Kind::IndexSwap,
+            KindWithContent::IndexTransaction { .. } => Kind::IndexTransaction,
+            KindWithContent::IndexClone { .. } => Kind::IndexClone,
             KindWithContent::TaskCancelation { .. } => Kind::TaskCancelation,
             KindWithContent::TaskDeletion { .. } => Kind::TaskDeletion,
             KindWithContent::DumpCreation { .. } => Kind::DumpCreation,
@@ -194,7 +332,9 @@ impl KindWithContent {
             | TaskDeletion { .. }
             | UpgradeDatabase { .. } => vec![],
             DocumentAdditionOrUpdate { index_uid, .. }
+            | DocumentBulk { index_uid, .. }
             | DocumentEdition { index_uid, .. }
+            | EmbeddingRegeneration { index_uid, .. }
             | DocumentDeletion { index_uid, .. }
             | DocumentDeletionByFilter { index_uid, .. }
             | DocumentClear { index_uid }
@@ -210,6 +350,10 @@ impl KindWithContent {
                 }
                 indexes.into_iter().collect()
             }
+            IndexTransaction { operations, .. } => {
+                operations.iter().map(|operation| operation.index_uid.as_str()).collect()
+            }
+            IndexClone { index_uid, new_index_uid, .. } => vec![index_uid, new_index_uid],
         }
     }
 
@@ -221,8 +365,17 @@ impl KindWithContent {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: None,
+                    version_conflicts: Vec::new(),
+                    invalid_documents: Vec::new(),
+                    generated_document_ids: Vec::new(),
                 })
             }
+            KindWithContent::DocumentBulk { operations, .. } => Some(Details::DocumentBulk {
+                received_operations: operations.iter().map(BulkOperation::operations_count).sum(),
+                indexed_documents: None,
+                deleted_documents: None,
+                generated_document_ids: Vec::new(),
+            }),
             KindWithContent::DocumentEdition { index_uid: _, filter_expr, context, function } => {
                 Some(Details::DocumentEdition {
                     deleted_documents: None,
@@ -232,10 +385,18 @@ impl KindWithContent {
                     function: function.clone(),
                 })
             }
-            KindWithContent::DocumentDeletion { index_uid: _, documents_ids } => {
+            KindWithContent::EmbeddingRegeneration { index_uid: _, filter_expr, embedders } => {
+                Some(Details::EmbeddingRegeneration {
+                    original_filter: filter_expr.as_ref().map(|v| v.to_string()),
+                    embedders: embedders.clone(),
+                    regenerated_documents: None,
+                })
+            }
+            KindWithContent::DocumentDeletion { index_uid: _, documents_ids, .. } => {
                 Some(Details::DocumentDeletion {
                     provided_ids: documents_ids.len(),
                     deleted_documents: None,
+                    version_conflicts: Vec::new(),
                 })
             }
             KindWithContent::DocumentDeletionByFilter { index_uid: _, filter_expr } => {
@@ -257,6 +418,23 @@ impl KindWithContent {
             KindWithContent::IndexSwap { swaps } => {
                 Some(Details::IndexSwap { swaps: swaps.clone() })
             }
+            KindWithContent::IndexTransaction { operations, .. } => {
+                Some(Details::IndexTransaction {
+                    received_operations: operations
+                        .iter()
+                        .map(TransactionOperation::operations_count)
+                        .sum(),
+                    indexed_documents: None,
+                    deleted_documents: None,
+                    generated_document_ids: Vec::new(),
+                })
+            }
+            KindWithContent::IndexClone { new_index_uid, settings_only, .. } => {
+                Some(Details::IndexClone {
+                    new_index_uid: new_index_uid.clone(),
+                    settings_only: *settings_only,
+                })
+            }
             KindWithContent::TaskCancelation { query, tasks } => Some(Details::TaskCancelation {
                 matched_tasks: tasks.len(),
                 canceled_tasks: None,
@@ -286,8 +464,17 @@ impl KindWithContent {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: Some(0),
+                    version_conflicts: Vec::new(),
+                    invalid_documents: Vec::new(),
+                    generated_document_ids: Vec::new(),
                 })
             }
+            KindWithContent::DocumentBulk { operations, .. } => Some(Details::DocumentBulk {
+                received_operations: operations.iter().map(BulkOperation::operations_count).sum(),
+                indexed_documents: Some(0),
+                deleted_documents: Some(0),
+                generated_document_ids: Vec::new(),
+            }),
             KindWithContent::DocumentEdition { index_uid: _, filter_expr, context, function } => {
                 Some(Details::DocumentEdition {
                     deleted_documents: Some(0),
@@ -297,10 +484,18 @@ impl KindWithContent {
                     function: function.clone(),
                 })
             }
-            KindWithContent::DocumentDeletion { index_uid: _, documents_ids } => {
+            KindWithContent::EmbeddingRegeneration { index_uid: _, filter_expr, embedders } => {
+                Some(Details::EmbeddingRegeneration {
+                    original_filter: filter_expr.as_ref().map(|v| v.to_string()),
+                    embedders: embedders.clone(),
+                    regenerated_documents: Some(0),
+                })
+            }
+            KindWithContent::DocumentDeletion { index_uid: _, documents_ids, .. } => {
                 Some(Details::DocumentDeletion {
                     provided_ids: documents_ids.len(),
                     deleted_documents: Some(0),
+                    version_conflicts: Vec::new(),
                 })
             }
             KindWithContent::DocumentDeletionByFilter { index_uid: _, filter_expr } => {
@@ -323,6 +518,23 @@ impl KindWithContent {
             KindWithContent::IndexSwap { .. } => {
                 todo!()
             }
+            KindWithContent::IndexTransaction { operations, .. } => {
+                Some(Details::IndexTransaction {
+                    received_operations: operations
+                        .iter()
+                        .map(TransactionOperation::operations_count)
+                        .sum(),
+                    indexed_documents: Some(0),
+                    deleted_documents: Some(0),
+                    generated_document_ids: Vec::new(),
+                })
+            }
+            KindWithContent::IndexClone { new_index_uid, settings_only, .. } => {
+                Some(Details::IndexClone {
+                    new_index_uid: new_index_uid.clone(),
+                    settings_only: *settings_only,
+                })
+            }
             KindWithContent::TaskCancelation { query, tasks } => Some(Details::TaskCancelation {
                 matched_tasks: tasks.len(),
                 canceled_tasks: Some(0),
@@ -354,9 +566,19 @@ impl From<&KindWithContent> for Option<Details> {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: None,
+                    version_conflicts: Vec::new(),
+                    invalid_documents: Vec::new(),
+                    generated_document_ids: Vec::new(),
                 })
             }
+            KindWithContent::DocumentBulk { operations, .. } => Some(Details::DocumentBulk {
+                received_operations: operations.iter().map(BulkOperation::operations_count).sum(),
+                indexed_documents: None,
+                deleted_documents: None,
+                generated_document_ids: Vec::new(),
+            }),
             KindWithContent::DocumentEdition { .. } => None,
+            KindWithContent::EmbeddingRegeneration { .. } => None,
             KindWithContent::DocumentDeletion { .. } => None,
             KindWithContent::DocumentDeletionByFilter { .. } => None,
             KindWithContent::DocumentClear { .. } => None,
@@ -371,6 +593,23 @@ impl From<&KindWithContent> for Option<Details> {
                 Some(Details::IndexInfo { primary_key: primary_key.clone() })
             }
             KindWithContent::IndexSwap { .. } => None,
+            KindWithContent::IndexTransaction { operations, .. } => {
+                Some(Details::IndexTransaction {
+                    received_operations: operations
+                        .iter()
+                        .map(TransactionOperation::operations_count)
+                        .sum(),
+                    indexed_documents: None,
+                    deleted_documents: None,
+                    generated_document_ids: Vec::new(),
+                })
+            }
+            KindWithContent::IndexClone { new_index_uid, settings_only, .. } => {
+                Some(Details::IndexClone {
+                    new_index_uid: new_index_uid.clone(),
+                    settings_only: *settings_only,
+                })
+            }
             KindWithContent::TaskCancelation { query, tasks } => Some(Details::TaskCancelation {
                 matched_tasks: tasks.len(),
                 canceled_tasks: None,
@@ -490,11 +729,15 @@ pub enum Kind {
     DocumentAdditionOrUpdate,
     DocumentEdition,
     DocumentDeletion,
+    DocumentBulk,
+    EmbeddingRegeneration,
     SettingsUpdate,
     IndexCreation,
     IndexDeletion,
     IndexUpdate,
     IndexSwap,
+    IndexTransaction,
+    IndexClone,
     TaskCancelation,
     TaskDeletion,
     DumpCreation,
@@ -508,11 +751,15 @@ impl Kind {
             Kind::DocumentAdditionOrUpdate
             | Kind::DocumentEdition
             | Kind::DocumentDeletion
+            | Kind::DocumentBulk
+            | Kind::EmbeddingRegeneration
             | Kind::SettingsUpdate
             | Kind::IndexCreation
             | Kind::IndexDeletion
             | Kind::IndexUpdate => true,
             Kind::IndexSwap
+            | Kind::IndexTransaction
+            | Kind::IndexClone
             | Kind::TaskCancelation
             | Kind::TaskDeletion
             | Kind::DumpCreation
@@ -527,11 +774,15 @@ impl Display for Kind {
             Kind::DocumentAdditionOrUpdate => write!(f, "documentAdditionOrUpdate"),
             Kind::DocumentEdition => write!(f, "documentEdition"),
             Kind::DocumentDeletion => write!(f, "documentDeletion"),
+            Kind::DocumentBulk => write!(f, "documentBulk"),
+            Kind::EmbeddingRegeneration => write!(f, "embeddingRegeneration"),
             Kind::SettingsUpdate => write!(f, "settingsUpdate"),
             Kind::IndexCreation => write!(f, "indexCreation"),
             Kind::IndexDeletion => write!(f, "indexDeletion"),
             Kind::IndexUpdate => write!(f, "indexUpdate"),
             Kind::IndexSwap => write!(f, "indexSwap"),
+            Kind::IndexTransaction => write!(f, "indexTransaction"),
+            Kind::IndexClone => write!(f, "indexClone"),
             Kind::TaskCancelation => write!(f, "taskCancelation"),
             Kind::TaskDeletion => write!(f, "taskDeletion"),
             Kind::DumpCreation => write!(f, "dumpCreation"),
@@ -550,6 +801,10 @@ impl FromStr for Kind {
             Ok(Kind::IndexUpdate)
         } else if kind.eq_ignore_ascii_case("indexSwap") {
             Ok(Kind::IndexSwap)
+        } else if kind.eq_ignore_ascii_case("indexTransaction") {
+            Ok(Kind::IndexTransaction)
+        } else if kind.eq_ignore_ascii_case("indexClone") {
+            Ok(Kind::IndexClone)
         } else if kind.eq_ignore_ascii_case("indexDeletion") {
             Ok(Kind::IndexDeletion)
         } else if kind.eq_ignore_ascii_case("documentAdditionOrUpdate") {
@@ -558,6 +813,10 @@ impl FromStr for Kind {
             Ok(Kind::DocumentEdition)
         } else if kind.eq_ignore_ascii_case("documentDeletion") {
             Ok(Kind::DocumentDeletion)
+        } else if kind.eq_ignore_ascii_case("documentBulk") {
+            Ok(Kind::DocumentBulk)
+        } else if kind.eq_ignore_ascii_case("embeddingRegeneration") {
+            Ok(Kind::EmbeddingRegeneration)
         } else if kind.eq_ignore_ascii_case("settingsUpdate") {
             Ok(Kind::SettingsUpdate)
         } else if kind.eq_ignore_ascii_case("taskCancelation") {
@@ -602,6 +861,12 @@ pub enum Details {
     DocumentAdditionOrUpdate {
         received_documents: u64,
         indexed_documents: Option<u64>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        version_conflicts: Vec<DocumentVersionConflict>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        invalid_documents: Vec<DocumentSchemaViolation>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        generated_document_ids: Vec<String>,
     },
     SettingsUpdate {
         settings: Box<Settings<Unchecked>>,
@@ -612,11 +877,20 @@ pub enum Details {
     DocumentDeletion {
         provided_ids: usize,
         deleted_documents: Option<u64>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        version_conflicts: Vec<DocumentVersionConflict>,
     },
     DocumentDeletionByFilter {
         original_filter: String,
         deleted_documents: Option<u64>,
     },
+    DocumentBulk {
+        received_operations: u64,
+        indexed_documents: Option<u64>,
+        deleted_documents: Option<u64>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        generated_document_ids: Vec<String>,
+    },
     DocumentEdition {
         deleted_documents: Option<u64>,
         edited_documents: Option<u64>,
@@ -624,6 +898,11 @@ pub enum Details {
         context: Option<Object>,
         function: String,
     },
+    EmbeddingRegeneration {
+        original_filter: Option<String>,
+        embedders: Option<Vec<String>>,
+        regenerated_documents: Option<u64>,
+    },
     ClearAll {
         deleted_documents: Option<u64>,
     },
@@ -643,6 +922,17 @@ pub enum Details {
     IndexSwap {
         swaps: Vec<IndexSwap>,
     },
+    IndexTransaction {
+        received_operations: u64,
+        indexed_documents: Option<u64>,
+        deleted_documents: Option<u64>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        generated_document_ids: Vec<String>,
+    },
+    IndexClone {
+        new_index_uid: String,
+        settings_only: bool,
+    },
     UpgradeDatabase {
         from: (u32, u32, u32),
         to: (u32, u32, u32),
@@ -656,7 +946,15 @@ impl Details {
             Self::DocumentAdditionOrUpdate { indexed_documents, .. } => {
                 *indexed_documents = Some(0)
             }
+            Self::DocumentBulk { indexed_documents, deleted_documents, .. }
+            | Self::IndexTransaction { indexed_documents, deleted_documents, .. } => {
+                *indexed_documents = Some(0);
+                *deleted_documents = Some(0);
+            }
             Self::DocumentEdition { edited_documents, .. } => *edited_documents = Some(0),
+            Self::EmbeddingRegeneration { regenerated_documents, .. } => {
+                *regenerated_documents = Some(0)
+            }
             Self::DocumentDeletion { deleted_documents, .. } => *deleted_documents = Some(0),
             Self::DocumentDeletionByFilter { deleted_documents, .. } => {
                 *deleted_documents = Some(0)
@@ -668,7 +966,8 @@ impl Details {
             | Self::IndexInfo { .. }
             | Self::Dump { .. }
             | Self::UpgradeDatabase { .. }
-            | Self::IndexSwap { .. } => (),
+            | Self::IndexSwap { .. }
+            | Self::IndexClone { .. } => (),
         }
 
         details
@@ -199,7 +199,9 @@ struct Infos {
     experimental_network: bool,
     experimental_get_task_documents_route: bool,
     experimental_composite_embedders: bool,
+    experimental_chat_completions: bool,
     experimental_embedding_cache_entries: usize,
+    experimental_persistent_embedding_cache_size: u64,
     gpu_enabled: bool,
     db_path: bool,
     import_dump: bool,
@@ -248,6 +250,7 @@ impl Infos {
             experimental_max_number_of_batched_tasks,
             experimental_limit_batched_tasks_total_size,
             experimental_embedding_cache_entries,
+            experimental_persistent_embedding_cache_size,
             http_addr,
             master_key: _,
             env,
@@ -294,6 +297,7 @@ impl Infos {
             network,
             get_task_documents_route,
             composite_embedders,
+            chat_completions,
         } = features;
 
         // We're going to override every sensible information.
@@ -314,7 +318,10 @@ impl Infos {
             experimental_network: network,
             experimental_get_task_documents_route: get_task_documents_route,
             experimental_composite_embedders: composite_embedders,
+            experimental_chat_completions: chat_completions,
             experimental_embedding_cache_entries,
+            experimental_persistent_embedding_cache_size:
+                experimental_persistent_embedding_cache_size.as_u64(),
             gpu_enabled: meilisearch_types::milli::vector::is_cuda_enabled(),
             db_path: db_path != PathBuf::from("./data.ms"),
             import_dump: import_dump.is_some(),
@@ -35,6 +35,8 @@ pub enum MeilisearchHttpError {
     PaginationInFederatedQuery(usize, &'static str),
     #[error("Inside `.queries[{0}]`: Using facet options is not allowed in federated queries.\n - Hint: remove `facets` from query #{0} or remove `federation` from the request\n - Hint: pass `federation.facetsByIndex.{1}: {2:?}` for facets in federated search")]
     FacetsInFederatedQuery(usize, String, Vec<String>),
+    #[error("Inside `.queries[{0}]`: Using `diversity` is not allowed in federated queries.\n - Hint: remove `diversity` from query #{0} or remove `federation` from the request")]
+    DiversityInFederatedQuery(usize),
     #[error("Inconsistent order for values in facet `{facet}`: index `{previous_uid}` orders {previous_facet_order}, but index `{current_uid}` orders {index_facet_order}.\n - Hint: Remove `federation.mergeFacets` or change `faceting.sortFacetValuesBy` to be consistent in settings.")]
     InconsistentFacetOrder {
         facet: String,
@@ -55,6 +57,12 @@ pub enum MeilisearchHttpError {
         .0.iter().map(|uid| format!("\"{uid}\"")).collect::<Vec<_>>().join(", "), .0.len()
     )]
     SwapIndexPayloadWrongLength(Vec<IndexUid>),
+    #[error("A transaction must update at least one index.")]
+    EmptyTransaction,
+    #[error("Indexes must be declared only once during a transaction. `{0}` was declared several times.")]
+    TransactionDuplicateIndexFound(IndexUid),
+    #[error("An index alias must point to at least one index.")]
+    EmptyIndexAlias,
     #[error(transparent)]
     IndexUid(#[from] IndexUidFormatError),
     #[error(transparent)]
@@ -78,6 +86,10 @@ pub enum MeilisearchHttpError {
     Join(#[from] JoinError),
     #[error("Invalid request: missing `hybrid` parameter when `vector` is present.")]
     MissingSearchHybrid,
+    #[error("Invalid request: missing `hybrid` parameter when `media` is present.")]
+    MissingSearchHybridWithMedia,
+    #[error("Invalid request: missing `hybrid` parameter when `diversity` is present.")]
+    MissingSearchHybridWithDiversity,
 }
 
 impl MeilisearchHttpError {
@@ -101,6 +113,11 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::TooManySearchRequests(_) => Code::TooManySearchRequests,
             MeilisearchHttpError::SearchLimiterIsDown => Code::Internal,
             MeilisearchHttpError::SwapIndexPayloadWrongLength(_) => Code::InvalidSwapIndexes,
+            MeilisearchHttpError::EmptyTransaction => Code::BadRequest,
+            MeilisearchHttpError::TransactionDuplicateIndexFound(_) => {
+                Code::InvalidTransactionDuplicateIndexFound
+            }
+            MeilisearchHttpError::EmptyIndexAlias => Code::InvalidIndexAliasIndexes,
             MeilisearchHttpError::IndexUid(e) => e.error_code(),
             MeilisearchHttpError::SerdeJson(_) => Code::Internal,
             MeilisearchHttpError::HeedError(_) => Code::Internal,
@@ -110,7 +127,9 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::FileStore(_) => Code::Internal,
             MeilisearchHttpError::DocumentFormat(e) => e.error_code(),
             MeilisearchHttpError::Join(_) => Code::Internal,
-            MeilisearchHttpError::MissingSearchHybrid => Code::MissingSearchHybrid,
+            MeilisearchHttpError::MissingSearchHybrid
+            | MeilisearchHttpError::MissingSearchHybridWithMedia
+            | MeilisearchHttpError::MissingSearchHybridWithDiversity => Code::MissingSearchHybrid,
             MeilisearchHttpError::FederationOptionsInNonFederatedRequest(_) => {
                 Code::InvalidMultiSearchFederationOptions
             }
@@ -118,6 +137,7 @@ impl ErrorCode for MeilisearchHttpError {
                 Code::InvalidMultiSearchQueryPagination
             }
             MeilisearchHttpError::FacetsInFederatedQuery(..) => Code::InvalidMultiSearchQueryFacets,
+            MeilisearchHttpError::DiversityInFederatedQuery(_) => Code::InvalidSearchDiversity,
             MeilisearchHttpError::InconsistentFacetOrder { .. } => {
                 Code::InvalidMultiSearchFacetOrder
             }
@@ -9,6 +9,7 @@ use actix_web::FromRequest;
 pub use error::AuthenticationError;
 use futures::future::err;
 use futures::Future;
+use index_scheduler::IndexScheduler;
 use meilisearch_auth::{AuthController, AuthFilter};
 use meilisearch_types::error::{Code, ResponseError};
 
@@ -29,6 +30,7 @@ impl<P, D> GuardedData<P, D> {
         auth: Data<AuthController>,
         token: String,
         index: Option<String>,
+        index_scheduler: Option<Data<IndexScheduler>>,
         data: Option<D>,
     ) -> Result<Self, ResponseError>
     where
@@ -36,7 +38,7 @@ impl<P, D> GuardedData<P, D> {
     {
         let missing_master_key = auth.get_master_key().is_none();
 
-        match Self::authenticate(auth, token, index).await? {
+        match Self::authenticate(auth, token, index, index_scheduler).await? {
             Ok(filters) => match data {
                 Some(data) => Ok(Self { data, filters, _marker: PhantomData }),
                 None => Err(AuthenticationError::IrretrievableState.into()),
@@ -52,7 +54,7 @@ impl<P, D> GuardedData<P, D> {
     {
         let missing_master_key = auth.get_master_key().is_none();
 
-        match Self::authenticate(auth, String::new(), None).await? {
+        match Self::authenticate(auth, String::new(), None, None).await? {
             Ok(filters) => match data {
                 Some(data) => Ok(Self { data, filters, _marker: PhantomData }),
                 None => Err(AuthenticationError::IrretrievableState.into()),
@@ -66,13 +68,27 @@ impl<P, D> GuardedData<P, D> {
         auth: Data<AuthController>,
         token: String,
         index: Option<String>,
+        index_scheduler: Option<Data<IndexScheduler>>,
     ) -> Result<Result<AuthFilter, AuthError>, ResponseError>
     where
         P: Policy + 'static,
     {
-        tokio::task::spawn_blocking(move || P::authenticate(auth, token.as_ref(), index.as_deref()))
-            .await
-            .map_err(|e| ResponseError::from_msg(e.to_string(), Code::Internal))
+        tokio::task::spawn_blocking(move || {
+            // An index alias gives access to the indexes it points to,
+            // the API key must be authorized on all of them.
+            let indexes = match (index, index_scheduler) {
+                (Some(index), Some(index_scheduler)) => {
+                    match index_scheduler.resolve_index_uids(&index) {
+                        Ok(indexes) => indexes,
+                        Err(_) => vec![index],
+                    }
+                }
+                (index, _) => index.into_iter().collect(),
+            };
+            P::authenticate(auth, token.as_ref(), &indexes)
+        })
+        .await
+        .map_err(|e| ResponseError::from_msg(e.to_string(), Code::Internal))
     }
 }
 
@@ -108,6 +124,7 @@ impl<P: Policy + 'static, D: 'static + Clone> FromRequest for GuardedData<P, D>
                                 auth,
                                 token.to_string(),
                                 index.map(String::from),
+                                req.app_data::<Data<IndexScheduler>>().cloned(),
                                 req.app_data::<D>().cloned(),
                             )),
                             None => Box::pin(err(AuthenticationError::InvalidToken.into())),
@@ -128,7 +145,7 @@ pub trait Policy {
     fn authenticate(
         auth: Data<AuthController>,
         token: &str,
-        index: Option<&str>,
+        indexes: &[String],
     ) -> Result<AuthFilter, policies::AuthError>;
 }
 
@@ -231,7 +248,7 @@ pub mod policies {
         fn authenticate(
             auth: Data<AuthController>,
             token: &str,
-            index: Option<&str>,
+            indexes: &[String],
         ) -> Result<AuthFilter, AuthError> {
             // authenticate if token is the master key.
             // Without a master key, all routes are accessible except the key-related routes.
@@ -262,7 +279,7 @@ pub mod policies {
 
             // First check if the index is authorized in the tenant token, this is a public
             // information, we can return a nice error message.
-            if let Some(index) = index {
+            for index in indexes {
                 if !auth_filter.tenant_token_is_index_authorized(index) {
                     return Err(AuthError::TenantTokenAccessingnUnauthorizedIndex {
                         index: index.to_string(),
@@ -286,7 +303,13 @@ pub mod policies {
                     }
                 }
             }
-            if auth.is_key_authorized(key_uuid, action, index).unwrap_or(false) {
+            let is_key_authorized = |index| auth.is_key_authorized(key_uuid, action, index);
+            let authorized = if indexes.is_empty() {
+                is_key_authorized(None).unwrap_or(false)
+            } else {
+                indexes.iter().all(|index| is_key_authorized(Some(index)).unwrap_or(false))
+            };
+            if authorized {
                 return Ok(auth_filter);
             }
 
@@ -294,6 +317,39 @@ pub mod policies {
         }
     }
 
+    /// Grants authentication to the API keys authorized to write the documents or the settings of
+    /// at least one index. The routes using it must check the action of each of their operations
+    /// with [`AuthFilter::is_action_authorized`].
+    pub struct IndexWritePolicy;
+
+    impl Policy for IndexWritePolicy {
+        fn authenticate(
+            auth: Data<AuthController>,
+            token: &str,
+            _indexes: &[String],
+        ) -> Result<AuthFilter, AuthError> {
+            if auth.get_master_key().is_none_or(|mk| mk == token) {
+                return Ok(AuthFilter::default());
+            }
+
+            // tenant tokens can only be used to search.
+            let key_uuid = auth
+                .get_optional_uid_from_encoded_key(token.as_bytes())
+                .map_err(|_e| AuthError::InvalidApiKey)?
+                .ok_or(AuthError::InvalidApiKey)?;
+            let auth_filter =
+                auth.get_key_filters(key_uuid, None).map_err(|_e| AuthError::InvalidApiKey)?;
+
+            for action in [Action::DocumentsAdd, Action::DocumentsDelete, Action::SettingsUpdate] {
+                if auth.is_key_authorized(key_uuid, action, None).unwrap_or(false) {
+                    return Ok(auth_filter);
+                }
+            }
+
+            Err(AuthError::InvalidApiKey)
+        }
+    }
+
     impl<const A: u8> ActionPolicy<A> {
         fn authenticate_tenant_token(
             auth: &AuthController,
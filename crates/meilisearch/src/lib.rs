@@ -40,6 +40,7 @@ use meilisearch_types::milli::documents::{DocumentsBatchBuilder, DocumentsBatchR
 use meilisearch_types::milli::update::{
     default_thread_pool_and_threads, IndexDocumentsConfig, IndexDocumentsMethod, IndexerConfig,
 };
+use meilisearch_types::milli::vector::persistent_cache::PersistentEmbeddingCache;
 use meilisearch_types::settings::apply_settings_to_builder;
 use meilisearch_types::tasks::KindWithContent;
 use meilisearch_types::versioning::{
@@ -206,6 +207,18 @@ impl tracing_actix_web::RootSpanBuilder for AwebTracingLogger {
     }
 }
 
+fn indexer_config(opt: &Opt) -> anyhow::Result<IndexerConfig> {
+    let mut indexer_config: IndexerConfig = (&opt.indexer_options).try_into()?;
+    let embedding_cache_size = opt.experimental_persistent_embedding_cache_size.as_u64() as usize;
+    if embedding_cache_size != 0 {
+        indexer_config.embedding_cache = Some(PersistentEmbeddingCache::open(
+            &opt.db_path.join("embedding_cache"),
+            embedding_cache_size,
+        )?);
+    }
+    Ok(indexer_config)
+}
+
 enum OnFailure {
     RemoveDb,
     KeepDb,
@@ -225,7 +238,7 @@ pub fn setup_meilisearch(opt: &Opt) -> anyhow::Result<(Arc<IndexScheduler>, Arc<
         task_db_size: opt.max_task_db_size.as_u64() as usize,
         index_base_map_size: opt.max_index_size.as_u64() as usize,
         enable_mdb_writemap: opt.experimental_reduce_indexing_memory_usage,
-        indexer_config: Arc::new((&opt.indexer_options).try_into()?),
+        indexer_config: Arc::new(indexer_config(opt)?),
         autobatching_enabled: true,
         cleanup_enabled: !opt.experimental_replication_parameters,
         max_number_of_tasks: 1_000_000,
@@ -509,7 +522,11 @@ fn import_dump(
     let indexer_config = if base_config.max_threads.is_none() {
         let (thread_pool, _) = default_thread_pool_and_threads();
 
-        let _config = IndexerConfig { thread_pool, ..*base_config };
+        let _config = IndexerConfig {
+            thread_pool,
+            embedding_cache: base_config.embedding_cache.clone(),
+            ..*base_config
+        };
         backup_config = _config;
         &backup_config
     } else {
@@ -587,6 +604,13 @@ fn import_dump(
         index_scheduler.refresh_index_stats(&uid)?;
     }
 
+    // 4.4 Import the index aliases, now that the indexes they point to exist.
+    if let Some(index_aliases) = dump_reader.index_aliases()? {
+        for (alias, indexes) in index_aliases {
+            index_scheduler.put_index_alias(alias, indexes)?;
+        }
+    }
+
     // 5. Import the queue
     let mut index_scheduler_dump = index_scheduler.register_dumped_task()?;
     // 5.1. Import the batches
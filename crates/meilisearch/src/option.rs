@@ -65,6 +65,8 @@ const MEILI_EXPERIMENTAL_LIMIT_BATCHED_TASKS_TOTAL_SIZE: &str =
     "MEILI_EXPERIMENTAL_LIMIT_BATCHED_TASKS_SIZE";
 const MEILI_EXPERIMENTAL_EMBEDDING_CACHE_ENTRIES: &str =
     "MEILI_EXPERIMENTAL_EMBEDDING_CACHE_ENTRIES";
+const MEILI_EXPERIMENTAL_PERSISTENT_EMBEDDING_CACHE_SIZE: &str =
+    "MEILI_EXPERIMENTAL_PERSISTENT_EMBEDDING_CACHE_SIZE";
 const DEFAULT_CONFIG_FILE_PATH: &str = "./config.toml";
 const DEFAULT_DB_PATH: &str = "./data.ms";
 const DEFAULT_HTTP_ADDR: &str = "localhost:7700";
@@ -455,6 +457,13 @@ pub struct Opt {
     #[serde(default = "default_embedding_cache_entries")]
     pub experimental_embedding_cache_entries: usize,
 
+    /// Enables an experimental embedding cache persisted on disk, shared by search and indexing.
+    /// The value represents the maximal size of the cache, in bytes or explicitly stating a base unit (for instance: '10Gb'),
+    /// the cache being emptied when full. Disabled when set to 0.
+    #[clap(long, env = MEILI_EXPERIMENTAL_PERSISTENT_EMBEDDING_CACHE_SIZE, default_value_t = default_persistent_embedding_cache_size())]
+    #[serde(default = "default_persistent_embedding_cache_size")]
+    pub experimental_persistent_embedding_cache_size: Byte,
+
     #[serde(flatten)]
     #[clap(flatten)]
     pub indexer_options: IndexerOpts,
@@ -559,6 +568,7 @@ impl Opt {
             experimental_max_number_of_batched_tasks,
             experimental_limit_batched_tasks_total_size,
             experimental_embedding_cache_entries,
+            experimental_persistent_embedding_cache_size,
         } = self;
         export_to_env_if_not_present(MEILI_DB_PATH, db_path);
         export_to_env_if_not_present(MEILI_HTTP_ADDR, http_addr);
@@ -655,6 +665,10 @@ impl Opt {
             MEILI_EXPERIMENTAL_EMBEDDING_CACHE_ENTRIES,
             experimental_embedding_cache_entries.to_string(),
         );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_PERSISTENT_EMBEDDING_CACHE_SIZE,
+            experimental_persistent_embedding_cache_size.to_string(),
+        );
         indexer_options.export_to_env();
     }
 
@@ -978,6 +992,10 @@ fn default_embedding_cache_entries() -> usize {
     0
 }
 
+fn default_persistent_embedding_cache_size() -> Byte {
+    Byte::from_u64(0)
+}
+
 fn default_snapshot_dir() -> PathBuf {
     PathBuf::from(DEFAULT_SNAPSHOT_DIR)
 }
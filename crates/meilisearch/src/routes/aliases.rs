@@ -0,0 +1,272 @@
+use actix_web::web::{self, Data};
+use actix_web::{HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use deserr::Deserr;
+use index_scheduler::IndexScheduler;
+use itertools::Itertools;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::InvalidIndexAliasIndexes;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::keys::actions;
+use serde::Serialize;
+use tracing::debug;
+use utoipa::{OpenApi, ToSchema};
+
+use crate::analytics::{Aggregate, Analytics};
+use crate::error::MeilisearchHttpError;
+use crate::extractors::authentication::policies::ActionPolicy;
+use crate::extractors::authentication::{AuthenticationError, GuardedData};
+use crate::extractors::sequential_extractor::SeqHandler;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(list_aliases, get_alias, put_alias, delete_alias),
+    tags((
+        name = "Aliases",
+        description = "The `/aliases` route allows you to give another name to one or several indexes.
+
+An alias pointing to a single index can be used everywhere an index uid is accepted, an alias pointing to several indexes can only be used to search.
+This route is **synchronous**. This means that no task object will be returned, and an alias can be atomically repointed to other indexes.",
+    )),
+)]
+pub struct AliasesApi;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(list_aliases))).service(
+        web::resource("/{alias_uid}")
+            .route(web::get().to(get_alias))
+            .route(web::put().to(SeqHandler(put_alias)))
+            .route(web::delete().to(SeqHandler(delete_alias))),
+    );
+}
+
+/// An index alias along with the indexes it points to.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct IndexAliasView {
+    /// The name of the alias
+    #[schema(example = "movies")]
+    pub uid: String,
+    /// The indexes the alias points to
+    #[schema(example = json!(["movies_v2"]))]
+    pub indexes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct IndexAliasesView {
+    results: Vec<IndexAliasView>,
+}
+
+/// List index aliases
+///
+/// List the index aliases the API key can access.
+#[utoipa::path(
+    get,
+    path = "",
+    tag = "Aliases",
+    security(("Bearer" = ["aliases.get", "*"])),
+    responses(
+        (status = OK, description = "The index aliases are returned", body = IndexAliasesView, content_type = "application/json", example = json!(
+            {
+                "results": [
+                    { "uid": "movies", "indexes": ["movies_v2"] },
+                    { "uid": "catalog", "indexes": ["movies_v2", "series"] }
+                ]
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+async fn list_aliases(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::ALIASES_GET }>, Data<IndexScheduler>>,
+) -> Result<HttpResponse, ResponseError> {
+    let filters = index_scheduler.filters();
+    let results = index_scheduler
+        .index_aliases()?
+        .into_iter()
+        .filter(|(uid, _)| filters.is_index_authorized(uid))
+        .map(|(uid, indexes)| IndexAliasView { uid, indexes })
+        .collect();
+
+    let aliases = IndexAliasesView { results };
+    debug!(returns = ?aliases, "List index aliases");
+    Ok(HttpResponse::Ok().json(aliases))
+}
+
+/// Get an index alias
+///
+/// Get the indexes an alias points to.
+#[utoipa::path(
+    get,
+    path = "/{aliasUid}",
+    tag = "Aliases",
+    security(("Bearer" = ["aliases.get", "*"])),
+    params(("aliasUid", example = "movies", description = "Index Alias Unique Identifier", nullable = false)),
+    responses(
+        (status = OK, description = "The index alias is returned", body = IndexAliasView, content_type = "application/json", example = json!(
+            { "uid": "movies", "indexes": ["movies_v2"] }
+        )),
+        (status = 404, description = "Index alias not found", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Index alias `movies` not found.",
+                "code": "index_alias_not_found",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#index_alias_not_found"
+            }
+        )),
+    )
+)]
+async fn get_alias(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::ALIASES_GET }>, Data<IndexScheduler>>,
+    alias_uid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let alias_uid = IndexUid::try_from(alias_uid.into_inner())?;
+    if !index_scheduler.filters().is_index_authorized(&alias_uid) {
+        return Err(AuthenticationError::InvalidToken.into());
+    }
+
+    let indexes = index_scheduler.index_alias(&alias_uid)?;
+    let alias = IndexAliasView { uid: alias_uid.into_inner(), indexes };
+    debug!(returns = ?alias, "Get index alias");
+    Ok(HttpResponse::Ok().json(alias))
+}
+
+#[derive(Deserr, Debug, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct IndexAliasPayload {
+    /// The indexes the alias points to. An alias pointing to several indexes can only be used to search
+    #[deserr(error = DeserrJsonError<InvalidIndexAliasIndexes>, missing_field_error = DeserrJsonError::missing_index_alias_indexes)]
+    #[schema(value_type = Vec<String>, example = json!(["movies_v2"]))]
+    indexes: Vec<IndexUid>,
+}
+
+#[derive(Serialize)]
+struct IndexAliasUpdatedAnalytics {
+    max_indexes_per_alias: usize,
+}
+
+impl Aggregate for IndexAliasUpdatedAnalytics {
+    fn event_name(&self) -> &'static str {
+        "Index Alias Updated"
+    }
+
+    fn aggregate(self: Box<Self>, new: Box<Self>) -> Box<Self> {
+        Box::new(Self {
+            max_indexes_per_alias: self.max_indexes_per_alias.max(new.max_indexes_per_alias),
+        })
+    }
+
+    fn into_event(self: Box<Self>) -> serde_json::Value {
+        serde_json::to_value(*self).unwrap_or_default()
+    }
+}
+
+/// Create or update an index alias
+///
+/// Point an alias to one or several indexes. Repointing an existing alias is atomic.
+/// The alias can't have the name of an existing index, and must point to indexes, not to other aliases.
+#[utoipa::path(
+    put,
+    path = "/{aliasUid}",
+    tag = "Aliases",
+    security(("Bearer" = ["aliases.update", "*"])),
+    params(("aliasUid", example = "movies", description = "Index Alias Unique Identifier", nullable = false)),
+    request_body = IndexAliasPayload,
+    responses(
+        (status = OK, description = "The index alias is returned", body = IndexAliasView, content_type = "application/json", example = json!(
+            { "uid": "movies", "indexes": ["movies_v2"] }
+        )),
+        (status = 404, description = "Index not found", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Index `movies_v2` not found.",
+                "code": "index_not_found",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#index_not_found"
+            }
+        )),
+    )
+)]
+async fn put_alias(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::ALIASES_UPDATE }>, Data<IndexScheduler>>,
+    alias_uid: web::Path<String>,
+    params: AwebJson<IndexAliasPayload, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let alias_uid = IndexUid::try_from(alias_uid.into_inner())?;
+    let IndexAliasPayload { indexes } = params.into_inner();
+    debug!(alias = %alias_uid, parameters = ?indexes, "Put index alias");
+
+    // The API key must be able to access the alias and all the indexes behind it.
+    let filters = index_scheduler.filters();
+    if !filters.is_index_authorized(&alias_uid)
+        || indexes.iter().any(|index_uid| !filters.is_index_authorized(index_uid))
+    {
+        return Err(AuthenticationError::InvalidToken.into());
+    }
+
+    let indexes: Vec<String> = indexes.into_iter().map(IndexUid::into_inner).unique().collect();
+    if indexes.is_empty() {
+        return Err(MeilisearchHttpError::EmptyIndexAlias.into());
+    }
+
+    analytics.publish(IndexAliasUpdatedAnalytics { max_indexes_per_alias: indexes.len() }, &req);
+
+    let scheduler = index_scheduler.clone();
+    let alias = alias_uid.clone();
+    let indexes = tokio::task::spawn_blocking(move || {
+        scheduler.put_index_alias(&alias, &indexes).map(|()| indexes)
+    })
+    .await??;
+
+    let alias = IndexAliasView { uid: alias_uid.into_inner(), indexes };
+    debug!(returns = ?alias, "Put index alias");
+    Ok(HttpResponse::Ok().json(alias))
+}
+
+/// Delete an index alias
+///
+/// Delete an alias, the indexes it points to are left untouched.
+#[utoipa::path(
+    delete,
+    path = "/{aliasUid}",
+    tag = "Aliases",
+    security(("Bearer" = ["aliases.delete", "*"])),
+    params(("aliasUid", example = "movies", description = "Index Alias Unique Identifier", nullable = false)),
+    responses(
+        (status = NO_CONTENT, description = "The index alias has been deleted"),
+        (status = 404, description = "Index alias not found", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Index alias `movies` not found.",
+                "code": "index_alias_not_found",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#index_alias_not_found"
+            }
+        )),
+    )
+)]
+async fn delete_alias(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::ALIASES_DELETE }>, Data<IndexScheduler>>,
+    alias_uid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let alias_uid = IndexUid::try_from(alias_uid.into_inner())?;
+    if !index_scheduler.filters().is_index_authorized(&alias_uid) {
+        return Err(AuthenticationError::InvalidToken.into());
+    }
+
+    let scheduler = index_scheduler.clone();
+    tokio::task::spawn_blocking(move || scheduler.delete_index_alias(&alias_uid)).await??;
+    Ok(HttpResponse::NoContent().finish())
+}
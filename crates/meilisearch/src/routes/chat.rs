@@ -0,0 +1,259 @@
+use std::collections::BTreeMap;
+
+use actix_web::web::{self, Data};
+use actix_web::{HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use deserr::Deserr;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::{
+    InvalidChatCompletionApiKey, InvalidChatCompletionHeaders, InvalidChatCompletionModel,
+    InvalidChatCompletionRequest, InvalidChatCompletionResponse, InvalidChatCompletionSource,
+    InvalidChatCompletionSystemPrompt, InvalidChatCompletionUrl,
+};
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::features::{
+    ChatCompletionSettings as DbChatCompletionSettings, ChatCompletionSource,
+};
+use meilisearch_types::keys::actions;
+use meilisearch_types::milli::update::Setting;
+use serde::Serialize;
+use tracing::debug;
+use utoipa::{OpenApi, ToSchema};
+
+use crate::analytics::{Aggregate, Analytics};
+use crate::extractors::authentication::policies::ActionPolicy;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::search::chat::ChatCompletionClient;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(get_chat_settings, patch_chat_settings),
+    tags((
+        name = "Chat",
+        description = "The `/chat/settings` route allows you to configure the chat completion provider used to answer questions about the documents of an index with the `/indexes/{indexUid}/chat` route.
+
+This route is **synchronous**. This means that no task object will be returned, and any change to the settings will be made available immediately.",
+        external_docs(url = "https://www.meilisearch.com/docs/reference/api/chat"),
+    )),
+)]
+pub struct ChatApi;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/settings")
+            .route(web::get().to(get_chat_settings))
+            .route(web::patch().to(SeqHandler(patch_chat_settings))),
+    );
+}
+
+/// Get the chat completion settings
+///
+/// Get the chat completion provider used to answer questions. The API key is redacted.
+#[utoipa::path(
+    get,
+    path = "/settings",
+    tag = "Chat",
+    security(("Bearer" = ["chatSettings.get", "chatSettings.*", "*"])),
+    responses(
+        (status = OK, description = "The chat completion settings are returned", body = ChatCompletionSettings, content_type = "application/json", example = json!(
+            {
+                "source": "openAi",
+                "url": "https://api.openai.com/v1/chat/completions",
+                "apiKey": "sk-p...",
+                "model": "gpt-4o-mini",
+                "headers": {},
+                "request": null,
+                "response": null,
+                "systemPrompt": null
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+async fn get_chat_settings(
+    index_scheduler: GuardedData<
+        ActionPolicy<{ actions::CHAT_SETTINGS_GET }>,
+        Data<IndexScheduler>,
+    >,
+) -> Result<HttpResponse, ResponseError> {
+    index_scheduler.features().check_chat_completions("Using the /chat/settings route")?;
+
+    let mut settings = index_scheduler.chat_settings();
+    settings.hide_secrets();
+    debug!(returns = ?settings, "Get chat settings");
+    Ok(HttpResponse::Ok().json(settings))
+}
+
+#[derive(Debug, Deserr, ToSchema, Serialize)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct ChatCompletionSettings {
+    /// The kind of provider: `openAi` for OpenAI-compatible providers, `rest` for any other provider.
+    #[schema(value_type = Option<ChatCompletionSource>, example = json!("openAi"))]
+    #[deserr(default, error = DeserrJsonError<InvalidChatCompletionSource>)]
+    #[serde(default)]
+    pub source: Setting<ChatCompletionSource>,
+    /// The URL of the chat completion endpoint. Defaults to the OpenAI endpoint with the `openAi` source.
+    #[schema(value_type = Option<String>, example = json!("http://localhost:11434/v1/chat/completions"))]
+    #[deserr(default, error = DeserrJsonError<InvalidChatCompletionUrl>)]
+    #[serde(default)]
+    pub url: Setting<String>,
+    /// The API key sent as a bearer token to the provider.
+    #[schema(value_type = Option<String>, example = json!("sk-proj-..."))]
+    #[deserr(default, error = DeserrJsonError<InvalidChatCompletionApiKey>)]
+    #[serde(default)]
+    pub api_key: Setting<String>,
+    /// The model used with the `openAi` source.
+    #[schema(value_type = Option<String>, example = json!("gpt-4o-mini"))]
+    #[deserr(default, error = DeserrJsonError<InvalidChatCompletionModel>)]
+    #[serde(default)]
+    pub model: Setting<String>,
+    /// Additional headers sent to the provider.
+    #[schema(value_type = Option<BTreeMap<String, String>>)]
+    #[deserr(default, error = DeserrJsonError<InvalidChatCompletionHeaders>)]
+    #[serde(default)]
+    pub headers: Setting<BTreeMap<String, String>>,
+    /// With the `rest` source, the template of the request, containing the `{{messages}}` placeholder.
+    #[schema(value_type = Option<serde_json::Value>)]
+    #[deserr(default, error = DeserrJsonError<InvalidChatCompletionRequest>)]
+    #[serde(default)]
+    pub request: Setting<serde_json::Value>,
+    /// With the `rest` source, the template of the response, containing the `{{content}}` placeholder.
+    #[schema(value_type = Option<serde_json::Value>)]
+    #[deserr(default, error = DeserrJsonError<InvalidChatCompletionResponse>)]
+    #[serde(default)]
+    pub response: Setting<serde_json::Value>,
+    /// The instructions given to the model before the retrieved documents.
+    #[schema(value_type = Option<String>)]
+    #[deserr(default, error = DeserrJsonError<InvalidChatCompletionSystemPrompt>)]
+    #[serde(default)]
+    pub system_prompt: Setting<String>,
+}
+
+#[derive(Serialize)]
+pub struct PatchChatSettingsAnalytics {
+    source: ChatCompletionSource,
+    custom_url: bool,
+    custom_headers: bool,
+    custom_system_prompt: bool,
+}
+
+impl Aggregate for PatchChatSettingsAnalytics {
+    fn event_name(&self) -> &'static str {
+        "Chat Settings Updated"
+    }
+
+    fn aggregate(self: Box<Self>, new: Box<Self>) -> Box<Self> {
+        new
+    }
+
+    fn into_event(self: Box<Self>) -> serde_json::Value {
+        serde_json::to_value(*self).unwrap_or_default()
+    }
+}
+
+/// Configure the chat completion provider
+///
+/// Update the chat completion provider used to answer questions. The settings are validated before being saved.
+#[utoipa::path(
+    patch,
+    path = "/settings",
+    tag = "Chat",
+    request_body = ChatCompletionSettings,
+    security(("Bearer" = ["chatSettings.update", "chatSettings.*", "*"])),
+    responses(
+        (status = OK, description = "The new chat completion settings are returned", body = ChatCompletionSettings, content_type = "application/json", example = json!(
+            {
+                "source": "rest",
+                "url": "http://localhost:8080/generate",
+                "apiKey": null,
+                "model": null,
+                "headers": {},
+                "request": { "input": "{{messages}}" },
+                "response": { "output": { "text": "{{content}}" } },
+                "systemPrompt": "Answer in a single sentence."
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+async fn patch_chat_settings(
+    index_scheduler: GuardedData<
+        ActionPolicy<{ actions::CHAT_SETTINGS_UPDATE }>,
+        Data<IndexScheduler>,
+    >,
+    new_settings: AwebJson<ChatCompletionSettings, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    index_scheduler.features().check_chat_completions("Using the /chat/settings route")?;
+
+    let new_settings = new_settings.0;
+    let old_settings = index_scheduler.chat_settings();
+    debug!(parameters = ?new_settings, "Patch chat settings");
+
+    let ChatCompletionSettings {
+        source,
+        url,
+        api_key,
+        model,
+        headers,
+        request,
+        response,
+        system_prompt,
+    } = new_settings;
+
+    let mut merged_settings = DbChatCompletionSettings {
+        source: merge(source, Some(old_settings.source)).unwrap_or_default(),
+        url: merge(url, old_settings.url),
+        api_key: merge(api_key, old_settings.api_key),
+        model: merge(model, old_settings.model),
+        headers: merge(headers, Some(old_settings.headers)).unwrap_or_default(),
+        request: merge(request, old_settings.request),
+        response: merge(response, old_settings.response),
+        system_prompt: merge(system_prompt, old_settings.system_prompt),
+    };
+
+    ChatCompletionClient::new(&merged_settings)?;
+
+    analytics.publish(
+        PatchChatSettingsAnalytics {
+            source: merged_settings.source,
+            custom_url: merged_settings.url.is_some(),
+            custom_headers: !merged_settings.headers.is_empty(),
+            custom_system_prompt: merged_settings.system_prompt.is_some(),
+        },
+        &req,
+    );
+
+    index_scheduler.put_chat_settings(merged_settings.clone())?;
+
+    merged_settings.hide_secrets();
+    debug!(returns = ?merged_settings, "Patch chat settings");
+    Ok(HttpResponse::Ok().json(merged_settings))
+}
+
+fn merge<T>(new: Setting<T>, old: Option<T>) -> Option<T> {
+    match new {
+        Setting::Set(new) => Some(new),
+        Setting::Reset => None,
+        Setting::NotSet => old,
+    }
+}
@@ -0,0 +1,233 @@
+use std::time::Instant;
+
+use actix_web::web::{self, Bytes, Data};
+use actix_web::{HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use deserr::Deserr;
+use futures::StreamExt as _;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::features::ChatCompletionSettings;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::keys::actions;
+use serde::Serialize;
+use serde_json::{json, Value};
+use tracing::debug;
+use utoipa::{OpenApi, ToSchema};
+
+use super::ActionPolicy;
+use crate::analytics::{Aggregate, Analytics};
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::routes::indexes::search::search_kind;
+use crate::search::add_search_rules;
+use crate::search::chat::{
+    build_messages, retrieval_query, retrieve_documents, ChatCompletionClient,
+};
+use crate::search_queue::SearchQueue;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(chat),
+    tags(
+        (
+            name = "Chat",
+            description = "The `/chat` route answers a question about the documents of an index. The documents matching the question are retrieved with a search, rendered with the `chat` index settings, then given to the chat completion provider configured with the `/chat/settings` route.",
+            external_docs(url = "https://www.meilisearch.com/docs/reference/api/chat"),
+        ),
+    ),
+)]
+pub struct ChatApi;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::post().to(SeqHandler(chat))));
+}
+
+#[derive(Debug, Clone, Deserr, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct ChatQuery {
+    /// The question to answer.
+    #[deserr(error = DeserrJsonError<InvalidChatQ>)]
+    pub q: String,
+    /// A filter restricting the documents that can be used to answer the question.
+    #[deserr(default, error = DeserrJsonError<InvalidChatFilter>)]
+    pub filter: Option<Value>,
+    /// Whether the answer is streamed as server-sent events. Defaults to `true`.
+    #[deserr(default = true, error = DeserrJsonError<InvalidChatStream>)]
+    pub stream: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct ChatResult {
+    /// The answer of the model.
+    pub answer: String,
+    /// The ids of the documents given to the model, in the order they are numbered in the answer.
+    pub sources: Vec<String>,
+    pub processing_time_ms: u128,
+}
+
+#[derive(Default, Serialize)]
+pub struct ChatAggregator {
+    total_received: usize,
+    total_succeeded: usize,
+    total_streamed: usize,
+    filter_used: bool,
+    total_sources: usize,
+}
+
+impl Aggregate for ChatAggregator {
+    fn event_name(&self) -> &'static str {
+        "Chat Completions"
+    }
+
+    fn aggregate(self: Box<Self>, new: Box<Self>) -> Box<Self> {
+        Box::new(Self {
+            total_received: self.total_received.saturating_add(new.total_received),
+            total_succeeded: self.total_succeeded.saturating_add(new.total_succeeded),
+            total_streamed: self.total_streamed.saturating_add(new.total_streamed),
+            filter_used: self.filter_used | new.filter_used,
+            total_sources: self.total_sources.saturating_add(new.total_sources),
+        })
+    }
+
+    fn into_event(self: Box<Self>) -> serde_json::Value {
+        serde_json::to_value(*self).unwrap_or_default()
+    }
+}
+
+/// Answer a question
+///
+/// Answer a question with a chat completion provider, using the documents of the index matching the question.
+/// By default, the answer is streamed as server-sent events: a `sources` event with the ids of the documents given to the model,
+/// `delta` events with the parts of the answer, then a `done` event.
+#[utoipa::path(
+    post,
+    path = "{indexUid}/chat",
+    tag = "Chat",
+    security(("Bearer" = ["chat", "*"])),
+    params(("indexUid", example = "movies", description = "Index Unique Identifier", nullable = false)),
+    request_body = ChatQuery,
+    responses(
+        (status = 200, description = "The answer is returned", body = ChatResult, content_type = "application/json", example = json!(
+            {
+                "answer": "Shazam! is a superhero movie released in 2019 [1].",
+                "sources": ["287947"],
+                "processingTimeMs": 812
+            }
+        )),
+        (status = 404, description = "Index not found", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Index `movies` not found.",
+                "code": "index_not_found",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#index_not_found"
+            }
+        )),
+    )
+)]
+pub async fn chat(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::CHAT }>, Data<IndexScheduler>>,
+    search_queue: web::Data<SearchQueue>,
+    index_uid: web::Path<String>,
+    params: AwebJson<ChatQuery, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let before_chat = Instant::now();
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    index_scheduler
+        .features()
+        .check_chat_completions("Using the /indexes/{indexUid}/chat route")?;
+
+    let ChatQuery { q, mut filter, stream } = params.into_inner();
+    debug!(parameters = ?q, "Chat");
+
+    let mut aggregate = ChatAggregator {
+        total_received: 1,
+        total_streamed: stream as usize,
+        filter_used: filter.is_some(),
+        ..Default::default()
+    };
+
+    // Tenant token search_rules.
+    if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        add_search_rules(&mut filter, search_rules);
+    }
+
+    let settings = index_scheduler.chat_settings();
+    let client = ChatCompletionClient::new(&settings)?;
+
+    let index = index_scheduler.index(&index_uid)?;
+    let config = {
+        let rtxn = index.read_txn()?;
+        index.chat_config(&rtxn)?
+    };
+
+    let query = retrieval_query(&config, q.clone(), filter)?;
+    let search_kind =
+        search_kind(&query, index_scheduler.get_ref(), index_uid.to_string(), &index)?;
+
+    let permit = search_queue.try_get_search_permit().await?;
+    let documents = tokio::task::spawn_blocking({
+        let features = index_scheduler.features();
+        let config = config.clone();
+        move || {
+            retrieve_documents(index_uid.to_string(), &index, &config, query, search_kind, features)
+        }
+    })
+    .await;
+    permit.drop().await;
+    let documents = documents??;
+
+    let system_prompt =
+        settings.system_prompt.as_deref().unwrap_or(ChatCompletionSettings::DEFAULT_SYSTEM_PROMPT);
+    let messages = build_messages(system_prompt, &config.description, &documents, &q);
+    let sources: Vec<String> = documents.into_iter().map(|document| document.id).collect();
+    aggregate.total_sources = sources.len();
+
+    if !stream {
+        let answer = client.complete(&messages).await;
+        if answer.is_ok() {
+            aggregate.total_succeeded = 1;
+        }
+        analytics.publish(aggregate, &req);
+
+        let result = ChatResult {
+            answer: answer?,
+            sources,
+            processing_time_ms: before_chat.elapsed().as_millis(),
+        };
+        debug!(returns = ?result, "Chat");
+        return Ok(HttpResponse::Ok().json(result));
+    }
+
+    let deltas = client.stream(&messages).await;
+    if deltas.is_ok() {
+        aggregate.total_succeeded = 1;
+    }
+    analytics.publish(aggregate, &req);
+    let deltas = deltas?;
+
+    let events =
+        futures::stream::once(async move { event("sources", json!({ "sources": sources })) })
+            .chain(deltas.map(|delta| match delta {
+                Ok(content) => event("delta", json!({ "content": content })),
+                Err(error) => event("error", json!(ResponseError::from(error))),
+            }))
+            .chain(futures::stream::once(async { event("done", json!({})) }))
+            .map(Ok::<_, ResponseError>);
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .streaming(events))
+}
+
+fn event(name: &str, data: Value) -> Bytes {
+    Bytes::from(format!("event: {name}\ndata: {data}\n\n"))
+}
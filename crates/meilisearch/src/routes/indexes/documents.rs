@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::io::{ErrorKind, Seek as _};
 use std::marker::PhantomData;
 
@@ -12,7 +12,9 @@ use futures::StreamExt;
 use index_scheduler::{IndexScheduler, RoFeatures, TaskId};
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
-use meilisearch_types::document_formats::{read_csv, read_json, read_ndjson, PayloadType};
+use meilisearch_types::document_formats::{
+    read_bulk_operations, read_csv, read_json, read_ndjson, PayloadType,
+};
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::heed::RoTxn;
@@ -22,7 +24,7 @@ use meilisearch_types::milli::vector::parsed_vectors::ExplicitVectors;
 use meilisearch_types::milli::DocumentId;
 use meilisearch_types::serde_cs::vec::CS;
 use meilisearch_types::star_or::OptionStarOrList;
-use meilisearch_types::tasks::KindWithContent;
+use meilisearch_types::tasks::{BulkOperation, KindWithContent};
 use meilisearch_types::{milli, Document, Index};
 use mime::Mime;
 use once_cell::sync::Lazy;
@@ -76,7 +78,7 @@ pub struct DocumentParam {
 
 #[derive(OpenApi)]
 #[openapi(
-    paths(get_document, get_documents, delete_document, replace_documents, update_documents, clear_all_documents, delete_documents_batch, delete_documents_by_filter, edit_documents_by_function, documents_by_query_post),
+    paths(get_document, get_documents, delete_document, replace_documents, update_documents, clear_all_documents, delete_documents_batch, delete_documents_by_filter, bulk_documents, edit_documents_by_function, regenerate_embeddings, documents_by_query_post),
     tags(
         (
             name = "Documents",
@@ -100,7 +102,12 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         web::resource("/delete-batch").route(web::post().to(SeqHandler(delete_documents_batch))),
     )
     .service(web::resource("/delete").route(web::post().to(SeqHandler(delete_documents_by_filter))))
+    .service(web::resource("/bulk").route(web::post().to(SeqHandler(bulk_documents))))
     .service(web::resource("/edit").route(web::post().to(SeqHandler(edit_documents_by_function))))
+    .service(
+        web::resource("/regenerate-embeddings")
+            .route(web::post().to(SeqHandler(regenerate_embeddings))),
+    )
     .service(web::resource("/fetch").route(web::post().to(SeqHandler(documents_by_query_post))))
     .service(
         web::resource("/{document_id}")
@@ -209,6 +216,7 @@ impl<Method: AggregateMethod> Aggregate for DocumentsFetchAggregator<Method> {
 /// Get one document
 ///
 /// Get one document from its primary key.
+/// When the primary key is composite, the document id is the comma-separated list of the values of its fields.
 #[utoipa::path(
     get,
     path = "{indexUid}/documents/{documentId}",
@@ -318,9 +326,20 @@ impl Aggregate for DocumentsDeletionAggregator {
     }
 }
 
+#[derive(Debug, Deserr, IntoParams)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+#[into_params(rename_all = "camelCase", parameter_in = Query)]
+pub struct DeleteDocumentQuery {
+    /// Only delete the document if its `_version` is this one.
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentVersion>)]
+    #[param(value_type = Option<u64>)]
+    pub version: Option<Param<u64>>,
+}
+
 /// Delete a document
 ///
 /// Delete a single document by id.
+/// When the primary key is composite, the document id is the comma-separated list of the values of its fields.
 #[utoipa::path(
     delete,
     path = "{indexUid}/documents/{documentId}",
@@ -329,6 +348,7 @@ impl Aggregate for DocumentsDeletionAggregator {
     params(
         ("indexUid" = String, Path, example = "movies", description = "Index Unique Identifier", nullable = false),
         ("documentId" = String, Path, example = "853", description = "Document Identifier", nullable = false),
+        DeleteDocumentQuery,
     ),
     responses(
         (status = 200, description = "Task successfully enqueued", body = SummarizedTaskView, content_type = "application/json", example = json!(
@@ -353,11 +373,13 @@ impl Aggregate for DocumentsDeletionAggregator {
 pub async fn delete_document(
     index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_DELETE }>, Data<IndexScheduler>>,
     path: web::Path<DocumentParam>,
+    params: AwebQueryParameter<DeleteDocumentQuery, DeserrQueryParamError>,
     req: HttpRequest,
     opt: web::Data<Opt>,
     analytics: web::Data<Analytics>,
 ) -> Result<HttpResponse, ResponseError> {
     let DocumentParam { index_uid, document_id } = path.into_inner();
+    let DeleteDocumentQuery { version } = params.into_inner();
     let index_uid = IndexUid::try_from(index_uid)?;
 
     analytics.publish(
@@ -370,9 +392,14 @@ pub async fn delete_document(
         &req,
     );
 
+    let expected_versions = match version {
+        Some(Param(version)) => BTreeMap::from([(document_id.clone(), version)]),
+        None => BTreeMap::new(),
+    };
     let task = KindWithContent::DocumentDeletion {
         index_uid: index_uid.to_string(),
         documents_ids: vec![document_id],
+        expected_versions,
     };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
@@ -1093,9 +1120,208 @@ async fn copy_body_to_file(
     Ok(read_file)
 }
 
+#[derive(Deserialize, Debug, Deserr, IntoParams)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+#[into_params(parameter_in = Query, rename_all = "camelCase")]
+pub struct BulkDocumentsQuery {
+    /// The primary key of the documents, only used if the index does not have one yet.
+    #[param(example = "id")]
+    #[deserr(default, error = DeserrQueryParamError<InvalidIndexPrimaryKey>)]
+    pub primary_key: Option<String>,
+}
+
+#[derive(Serialize)]
+struct DocumentsBulkAggregator {
+    // Set to true if at least one request contained this type of operation
+    upsert: bool,
+    replace: bool,
+    delete: bool,
+    delete_by_filter: bool,
+    index_creation: bool,
+}
+
+impl Aggregate for DocumentsBulkAggregator {
+    fn event_name(&self) -> &'static str {
+        "Documents Bulk Applied"
+    }
+
+    fn aggregate(self: Box<Self>, new: Box<Self>) -> Box<Self> {
+        Box::new(Self {
+            upsert: self.upsert | new.upsert,
+            replace: self.replace | new.replace,
+            delete: self.delete | new.delete,
+            delete_by_filter: self.delete_by_filter | new.delete_by_filter,
+            index_creation: self.index_creation | new.index_creation,
+        })
+    }
+
+    fn into_event(self: Box<Self>) -> serde_json::Value {
+        serde_json::to_value(*self).unwrap_or_default()
+    }
+}
+
+/// Apply documents operations in bulk
+///
+/// Apply a list of operations on the documents of an index, all together or not at all, in a single task.
+/// The payload is in the NDJSON format, where each line is one of the following operations:
+/// - `{ "op": "upsert", "document": { .. } }` adds a document or updates it if it already exists.
+/// - `{ "op": "replace", "document": { .. } }` adds a document or replaces it if it already exists.
+/// - `{ "op": "delete", "id": <id> }` deletes a document.
+/// - `{ "op": "deleteByFilter", "filter": <filter> }` deletes the documents matching a filter.
+///
+/// The operations are applied in order, except the filters that are evaluated on the documents as they were before the task.
+/// > info
+/// > If the provided index does not exist, it will be created.
+#[utoipa::path(
+    post,
+    path = "{indexUid}/documents/bulk",
+    tag = "Documents",
+    security(("Bearer" = ["documents.*", "*"])),
+    params(
+        ("indexUid", example = "movies", description = "Index Unique Identifier", nullable = false),
+        BulkDocumentsQuery,
+    ),
+    request_body = serde_json::Value,
+    responses(
+        (status = 202, description = "Task successfully enqueued", body = SummarizedTaskView, content_type = "application/json", example = json!(
+            {
+                "taskUid": 147,
+                "indexUid": "movies",
+                "status": "enqueued",
+                "type": "documentBulk",
+                "enqueuedAt": "2024-08-08T17:05:55.791772Z"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn bulk_documents(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ALL }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebQueryParameter<BulkDocumentsQuery, DeserrQueryParamError>,
+    body: Payload,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let params = params.into_inner();
+    debug!(parameters = ?params, "Bulk documents");
+
+    let accepted_content_type = vec!["application/x-ndjson".to_string()];
+    match extract_mime_type(&req)?.as_ref().map(|m| (m.type_().as_str(), m.subtype().as_str())) {
+        Some(("application", "x-ndjson")) => (),
+        Some((type_, subtype)) => {
+            return Err(MeilisearchHttpError::InvalidContentType(
+                format!("{type_}/{subtype}"),
+                accepted_content_type,
+            )
+            .into())
+        }
+        None => return Err(MeilisearchHttpError::MissingContentType(accepted_content_type).into()),
+    }
+
+    let allow_index_creation = index_scheduler.filters().allow_index_creation(&index_uid);
+    let index_creation = index_scheduler.index_exists(&index_uid).map_or(true, |x| !x);
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+
+    let temp_file = match tempfile() {
+        Ok(file) => file,
+        Err(e) => return Err(MeilisearchHttpError::Payload(ReceivePayload(Box::new(e))).into()),
+    };
+    let read_file = copy_body_to_file(temp_file, body, PayloadType::Ndjson).await?;
+
+    let (uuid, mut update_file) = index_scheduler.queue.create_update_file(dry_run)?;
+    let features = index_scheduler.features();
+    let operations = tokio::task::spawn_blocking(move || -> Result<_, ResponseError> {
+        let operations = read_bulk_operations(&read_file, &mut update_file)
+            .map_err(MeilisearchHttpError::DocumentFormat)?;
+        for operation in &operations {
+            if let BulkOperation::DeleteByFilter { filter_expr } = operation {
+                // we ensure the filter is well formed before enqueuing it
+                crate::search::parse_filter(filter_expr, Code::InvalidDocumentFilter, features)?
+                    .ok_or(MeilisearchHttpError::EmptyFilter)?;
+            }
+        }
+        // we NEED to persist the file here because we moved the `update_file` in another task.
+        update_file.persist().map_err(MeilisearchHttpError::from)?;
+        Ok(operations)
+    })
+    .await;
+
+    let operations = match operations {
+        Ok(Ok(operations)) => operations,
+        // in this case the file has not possibly be persisted.
+        Ok(Err(e)) => return Err(e),
+        Err(e) => {
+            // Here the file MAY have been persisted or not.
+            // We don't know thus we ignore the file not found error.
+            match index_scheduler.queue.delete_update_file(uuid) {
+                Ok(()) => (),
+                Err(index_scheduler::Error::FileStore(file_store::Error::IoError(e)))
+                    if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => {
+                    tracing::warn!(
+                        index_uuid = %uuid,
+                        "Unknown error happened while deleting a malformed update file: {e}"
+                    );
+                }
+            }
+            // We still want to return the original error to the end user.
+            return Err(e.into());
+        }
+    };
+
+    analytics.publish(
+        DocumentsBulkAggregator {
+            upsert: operations.iter().any(|op| matches!(op, BulkOperation::Update { .. })),
+            replace: operations.iter().any(|op| matches!(op, BulkOperation::Replace { .. })),
+            delete: operations.iter().any(|op| matches!(op, BulkOperation::Delete { .. })),
+            delete_by_filter: operations
+                .iter()
+                .any(|op| matches!(op, BulkOperation::DeleteByFilter { .. })),
+            index_creation,
+        },
+        &req,
+    );
+
+    let task = KindWithContent::DocumentBulk {
+        index_uid: index_uid.to_string(),
+        primary_key: params.primary_key,
+        content_file: uuid,
+        operations,
+        allow_index_creation,
+    };
+
+    let scheduler = index_scheduler.clone();
+    let task =
+        match tokio::task::spawn_blocking(move || scheduler.register(task, uid, dry_run)).await? {
+            Ok(task) => task,
+            Err(e) => {
+                index_scheduler.queue.delete_update_file(uuid)?;
+                return Err(e.into());
+            }
+        };
+    let task: SummarizedTaskView = task.into();
+
+    debug!(returns = ?task, "Bulk documents");
+    Ok(HttpResponse::Accepted().json(task))
+}
+
 /// Delete documents by batch
 ///
 /// Delete a set of documents based on an array of document ids.
+/// A document id can also be given as `{ "id": <id>, "version": <version> }` to only delete the document at this `_version`.
+/// When the primary key is composite, a document id can be given as the array of the values of its fields.
 #[utoipa::path(
     post,
     path = "{indexUid}/delete-batch",
@@ -1146,13 +1372,21 @@ pub async fn delete_documents_batch(
         &req,
     );
 
-    let ids = body
-        .iter()
-        .map(|v| v.as_str().map(String::from).unwrap_or_else(|| v.to_string()))
-        .collect();
+    let mut expected_versions = BTreeMap::new();
+    let mut ids = Vec::with_capacity(body.len());
+    for value in body.iter() {
+        let (id, version) = versioned_document_id(value)?;
+        if let Some(version) = version {
+            expected_versions.insert(id.clone(), version);
+        }
+        ids.push(id);
+    }
 
-    let task =
-        KindWithContent::DocumentDeletion { index_uid: index_uid.to_string(), documents_ids: ids };
+    let task = KindWithContent::DocumentDeletion {
+        index_uid: index_uid.to_string(),
+        documents_ids: ids,
+        expected_versions,
+    };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
     let task: SummarizedTaskView =
@@ -1164,6 +1398,43 @@ pub async fn delete_documents_batch(
     Ok(HttpResponse::Accepted().json(task))
 }
 
+/// The id of a document to delete along with its expected version, if any.
+///
+/// The id of a document identified by a composite primary key can be given as the
+/// array of the values of its components.
+fn versioned_document_id(value: &Value) -> Result<(String, Option<u64>), ResponseError> {
+    let to_id = |v: &Value| match v {
+        Value::Array(components) => {
+            milli::documents::validate_composite_document_id(components.clone()).map_err(|error| {
+                ResponseError::from_msg(error.to_string(), Code::InvalidDocumentId)
+            })
+        }
+        v => Ok(v.as_str().map(String::from).unwrap_or_else(|| v.to_string())),
+    };
+    match value {
+        Value::Object(object) if object.contains_key("version") => {
+            let Some(id) = object.get("id") else {
+                return Err(ResponseError::from_msg(
+                    format!("Missing `id` in the versioned document `{value}`."),
+                    Code::InvalidDocumentId,
+                ));
+            };
+            let id = to_id(id)?;
+            match object["version"].as_u64() {
+                Some(version) => Ok((id, Some(version))),
+                None => Err(ResponseError::from_msg(
+                    format!(
+                        "The `version` of the document `{id}` must be a positive integer, found `{}`.",
+                        object["version"]
+                    ),
+                    Code::InvalidDocumentVersion,
+                )),
+            }
+        }
+        value => Ok((to_id(value)?, None)),
+    }
+}
+
 #[derive(Debug, Deserr, ToSchema)]
 #[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
 #[schema(rename_all = "camelCase")]
@@ -1385,6 +1656,128 @@ pub async fn edit_documents_by_function(
     Ok(HttpResponse::Accepted().json(task))
 }
 
+#[derive(Debug, Deserr, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct EmbeddingRegeneration {
+    /// A filter expression selecting the documents whose embeddings are regenerated. All the documents if missing.
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentFilter>)]
+    #[schema(value_type = Option<Value>)]
+    pub filter: Option<Value>,
+    /// The names of the embedders whose embeddings are regenerated. All the embedders if missing.
+    #[deserr(default, error = DeserrJsonError<InvalidEmbeddingRegenerationEmbedders>)]
+    pub embedders: Option<Vec<String>>,
+}
+
+#[derive(Serialize)]
+struct EmbeddingRegenerationAggregator {
+    // Set to true if at least one request was filtered
+    filtered: bool,
+    // Set to true if at least one request was restricted to some embedders
+    per_embedder: bool,
+}
+
+impl Aggregate for EmbeddingRegenerationAggregator {
+    fn event_name(&self) -> &'static str {
+        "Embeddings Regenerated"
+    }
+
+    fn aggregate(self: Box<Self>, new: Box<Self>) -> Box<Self> {
+        Box::new(Self {
+            filtered: self.filtered | new.filtered,
+            per_embedder: self.per_embedder | new.per_embedder,
+        })
+    }
+
+    fn into_event(self: Box<Self>) -> serde_json::Value {
+        serde_json::to_value(*self).unwrap_or_default()
+    }
+}
+
+/// Regenerate embeddings
+///
+/// Regenerate the embeddings of a set of documents, without modifying the documents.
+/// The embeddings provided by the user are left untouched.
+#[utoipa::path(
+    post,
+    path = "{indexUid}/documents/regenerate-embeddings",
+    tag = "Documents",
+    security(("Bearer" = ["documents.add", "documents.*", "*"])),
+    params(
+        ("indexUid", example = "movies", description = "Index Unique Identifier", nullable = false),
+    ),
+    request_body = EmbeddingRegeneration,
+    responses(
+        (status = 202, description = "Task successfully enqueued", body = SummarizedTaskView, content_type = "application/json", example = json!(
+            {
+                "taskUid": 147,
+                "indexUid": "movies",
+                "status": "enqueued",
+                "type": "embeddingRegeneration",
+                "enqueuedAt": "2024-08-08T17:05:55.791772Z"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn regenerate_embeddings(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ADD }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebJson<EmbeddingRegeneration, DeserrJsonError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    debug!(parameters = ?params, "Regenerate embeddings");
+
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let index_uid = index_uid.into_inner();
+    let EmbeddingRegeneration { filter, embedders } = params.into_inner();
+
+    analytics.publish(
+        EmbeddingRegenerationAggregator {
+            filtered: filter.is_some(),
+            per_embedder: embedders.is_some(),
+        },
+        &req,
+    );
+
+    if let Some(ref filter) = filter {
+        // we ensure the filter is well formed before enqueuing it
+        crate::search::parse_filter(
+            filter,
+            Code::InvalidDocumentFilter,
+            index_scheduler.features(),
+        )?
+        .ok_or(MeilisearchHttpError::EmptyFilter)?;
+    }
+    if embedders.as_ref().is_some_and(|embedders| embedders.is_empty()) {
+        return Err(ResponseError::from_msg(
+            "`.embedders` cannot be empty. Omit it to regenerate the embeddings of all the embedders".to_string(),
+            Code::InvalidEmbeddingRegenerationEmbedders,
+        ));
+    }
+
+    let task = KindWithContent::EmbeddingRegeneration { index_uid, filter_expr: filter, embedders };
+
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
+            .await??
+            .into();
+
+    debug!(returns = ?task, "Regenerate embeddings");
+    Ok(HttpResponse::Accepted().json(task))
+}
+
 /// Delete all documents
 ///
 /// Delete all documents in the specified index.
@@ -9,6 +9,7 @@ use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::locales::Locale;
+use meilisearch_types::milli::vector::parsed_vectors::QueryVector;
 use serde_json::Value;
 use tracing::debug;
 use utoipa::{OpenApi, ToSchema};
@@ -55,7 +56,8 @@ pub struct FacetSearchQuery {
     #[deserr(default, error = DeserrJsonError<InvalidSearchQ>)]
     pub q: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchVector>)]
-    pub vector: Option<Vec<f32>>,
+    #[schema(value_type = Option<Vec<f32>>)]
+    pub vector: Option<QueryVector>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHybridQuery>)]
     pub hybrid: Option<HybridQuery>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFilter>)]
@@ -333,9 +335,11 @@ impl From<FacetSearchQuery> for SearchQuery {
             crop_marker: DEFAULT_CROP_MARKER(),
             matching_strategy,
             vector,
+            media: None,
             attributes_to_search_on,
             hybrid,
             ranking_score_threshold,
+            diversity: None,
             locales,
         }
     }
@@ -28,6 +28,7 @@ use crate::extractors::sequential_extractor::SeqHandler;
 use crate::routes::is_dry_run;
 use crate::Opt;
 
+pub mod chat;
 pub mod documents;
 pub mod facet_search;
 pub mod search;
@@ -46,8 +47,9 @@ mod similar_analytics;
         (path = "/", api = facet_search::FacetSearchApi),
         (path = "/", api = similar::SimilarApi),
         (path = "/", api = settings::SettingsApi),
+        (path = "/", api = chat::ChatApi),
     ),
-    paths(list_indexes, create_index, get_index, update_index, delete_index, get_index_stats),
+    paths(list_indexes, create_index, get_index, update_index, delete_index, clone_index, get_index_stats),
     tags(
         (
             name = "Indexes",
@@ -73,11 +75,13 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
                     .route(web::delete().to(SeqHandler(delete_index))),
             )
             .service(web::resource("/stats").route(web::get().to(SeqHandler(get_index_stats))))
+            .service(web::resource("/clone").route(web::post().to(SeqHandler(clone_index))))
             .service(web::scope("/documents").configure(documents::configure))
             .service(web::scope("/search").configure(search::configure))
             .service(web::scope("/facet-search").configure(facet_search::configure))
             .service(web::scope("/similar").configure(similar::configure))
-            .service(web::scope("/settings").configure(settings::configure)),
+            .service(web::scope("/settings").configure(settings::configure))
+            .service(web::scope("/chat").configure(chat::configure)),
     );
 }
 
@@ -340,9 +344,11 @@ pub async fn get_index(
     index_uid: web::Path<String>,
 ) -> Result<HttpResponse, ResponseError> {
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    // an index alias returns the index it points to
+    let index_uid = index_scheduler.resolve_index_uid(&index_uid)?;
 
     let index = index_scheduler.index(&index_uid)?;
-    let index_view = IndexView::new(index_uid.into_inner(), &index)?;
+    let index_view = IndexView::new(index_uid, &index)?;
 
     debug!(returns = ?index_view, "Get index");
 
@@ -488,6 +494,103 @@ pub async fn delete_index(
     Ok(HttpResponse::Accepted().json(task))
 }
 
+#[derive(Deserr, Debug, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct IndexCloneRequest {
+    /// The name of the index to create
+    #[schema(example = "movies_staging")]
+    #[deserr(error = DeserrJsonError<InvalidIndexUid>, missing_field_error = DeserrJsonError::missing_index_uid)]
+    uid: IndexUid,
+    /// Only copy the primary key and settings of the index, not its documents
+    #[schema(default = false)]
+    #[deserr(default, error = DeserrJsonError<InvalidIndexCloneSettingsOnly>)]
+    settings_only: bool,
+}
+
+#[derive(Serialize)]
+struct IndexClonedAggregate {
+    settings_only: bool,
+}
+
+impl Aggregate for IndexClonedAggregate {
+    fn event_name(&self) -> &'static str {
+        "Index Cloned"
+    }
+
+    fn aggregate(self: Box<Self>, new: Box<Self>) -> Box<Self> {
+        Box::new(Self { settings_only: self.settings_only | new.settings_only })
+    }
+
+    fn into_event(self: Box<Self>) -> serde_json::Value {
+        serde_json::to_value(*self).unwrap_or_default()
+    }
+}
+
+/// Clone index
+///
+/// Create a new index holding a copy of the documents and settings of an index.
+/// The index is copied as is, its documents are not indexed again.
+#[utoipa::path(
+    post,
+    path = "/{indexUid}/clone",
+    tag = "Indexes",
+    security(("Bearer" = ["indexes.create", "indexes.*", "*"])),
+    params(("indexUid", example = "movies", description = "Index Unique Identifier", nullable = false)),
+    request_body = IndexCloneRequest,
+    responses(
+        (status = ACCEPTED, description = "Task successfully enqueued", body = SummarizedTaskView, content_type = "application/json", example = json!(
+            {
+                "taskUid": 0,
+                "indexUid": "movies",
+                "status": "enqueued",
+                "type": "indexClone",
+                "enqueuedAt": "2021-01-01T09:39:00.000000Z"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn clone_index(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::INDEXES_CREATE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: AwebJson<IndexCloneRequest, DeserrJsonError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    debug!(parameters = ?body, "Clone index");
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let IndexCloneRequest { uid, settings_only } = body.into_inner();
+
+    if !index_scheduler.filters().allow_index_creation(&uid) {
+        return Err(AuthenticationError::InvalidToken.into());
+    }
+    analytics.publish(IndexClonedAggregate { settings_only }, &req);
+
+    let task = KindWithContent::IndexClone {
+        index_uid: index_uid.into_inner(),
+        new_index_uid: uid.into_inner(),
+        settings_only,
+    };
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
+            .await??
+            .into();
+    debug!(returns = ?task, "Clone index");
+
+    Ok(HttpResponse::Accepted().json(task))
+}
+
 /// Stats of an `Index`, as known to the `stats` route.
 #[derive(Serialize, Debug, ToSchema)]
 #[serde(rename_all = "camelCase")]
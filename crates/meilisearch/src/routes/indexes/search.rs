@@ -2,6 +2,7 @@ use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
 use deserr::actix_web::{AwebJson, AwebQueryParameter};
 use index_scheduler::IndexScheduler;
+use meilisearch_auth::AuthFilter;
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
 use meilisearch_types::error::deserr_codes::*;
@@ -9,6 +10,7 @@ use meilisearch_types::error::ResponseError;
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::locales::Locale;
 use meilisearch_types::milli;
+use meilisearch_types::milli::vector::parsed_vectors::QueryVector;
 use meilisearch_types::serde_cs::vec::CS;
 use serde_json::Value;
 use tracing::debug;
@@ -22,10 +24,11 @@ use crate::extractors::sequential_extractor::SeqHandler;
 use crate::metrics::MEILISEARCH_DEGRADED_SEARCH_REQUESTS;
 use crate::routes::indexes::search_analytics::{SearchAggregator, SearchGET, SearchPOST};
 use crate::search::{
-    add_search_rules, perform_search, HybridQuery, MatchingStrategy, RankingScoreThreshold,
-    RetrieveVectors, SearchKind, SearchQuery, SearchResult, SemanticRatio, DEFAULT_CROP_LENGTH,
-    DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG,
-    DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET, DEFAULT_SEMANTIC_RATIO,
+    add_search_rules, perform_federated_search, perform_search, Diversity, FederatedSearchResult,
+    Federation, HybridQuery, MatchingStrategy, MergeFacets, RankingScoreThreshold, RetrieveVectors,
+    SearchKind, SearchQuery, SearchQueryWithIndex, SearchResult, SemanticRatio,
+    DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG,
+    DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET, DEFAULT_SEMANTIC_RATIO,
 };
 use crate::search_queue::SearchQueue;
 
@@ -129,6 +132,9 @@ pub struct SearchQueryGet {
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchRankingScoreThreshold>)]
     #[param(value_type = f32)]
     pub ranking_score_threshold: Option<RankingScoreThresholdGet>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchDiversity>)]
+    #[param(value_type = f32)]
+    pub diversity: Option<DiversityGet>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchLocales>)]
     #[param(value_type = Vec<Locale>, explode = false)]
     pub locales: Option<CS<Locale>>,
@@ -147,6 +153,19 @@ impl std::convert::TryFrom<String> for RankingScoreThresholdGet {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, deserr::Deserr)]
+#[deserr(try_from(String) = TryFrom::try_from -> InvalidSearchDiversity)]
+pub struct DiversityGet(Diversity);
+
+impl std::convert::TryFrom<String> for DiversityGet {
+    type Error = InvalidSearchDiversity;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        let f: f32 = s.parse().map_err(|_| InvalidSearchDiversity)?;
+        Ok(DiversityGet(Diversity::try_from(f)?))
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, deserr::Deserr)]
 #[deserr(try_from(String) = TryFrom::try_from -> InvalidSearchSemanticRatio)]
 pub struct SemanticRatioGet(SemanticRatio);
@@ -205,7 +224,8 @@ impl TryFrom<SearchQueryGet> for SearchQuery {
 
         Ok(Self {
             q: other.q,
-            vector: other.vector.map(CS::into_inner),
+            vector: other.vector.map(|vector| QueryVector::Single(vector.into_inner())),
+            media: None,
             offset: other.offset.0,
             limit: other.limit.0,
             page: other.page.as_deref().copied(),
@@ -229,6 +249,7 @@ impl TryFrom<SearchQueryGet> for SearchQuery {
             attributes_to_search_on: other.attributes_to_search_on.map(|o| o.into_iter().collect()),
             hybrid,
             ranking_score_threshold: other.ranking_score_threshold.map(|o| o.0),
+            diversity: other.diversity.map(|o| o.0),
             locales: other.locales.map(|o| o.into_iter().collect()),
         })
     }
@@ -328,6 +349,26 @@ pub async fn search_with_url_query(
 
     let mut query: SearchQuery = params.into_inner().try_into()?;
 
+    let mut index_uids = index_scheduler.resolve_index_uids(&index_uid)?;
+    if index_uids.len() > 1 {
+        let aggregate = SearchAggregator::<SearchGET>::from_query(&query);
+        let search_result = search_indexes_of_alias(
+            &index_scheduler,
+            index_scheduler.filters(),
+            &search_queue,
+            index_uids,
+            query,
+        )
+        .await;
+        analytics.publish(aggregate, &req);
+        let search_result = search_result?;
+
+        debug!(returns = ?search_result, "Search get");
+        return Ok(HttpResponse::Ok().json(search_result));
+    }
+    // an index alias pointing to a single index is searched as this index
+    let index_uid = index_uids.pop().map(IndexUid::new_unchecked).unwrap_or(index_uid);
+
     // Tenant token search_rules.
     if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
         add_search_rules(&mut query.filter, search_rules);
@@ -434,6 +475,26 @@ pub async fn search_with_post(
     let mut query = params.into_inner();
     debug!(parameters = ?query, "Search post");
 
+    let mut index_uids = index_scheduler.resolve_index_uids(&index_uid)?;
+    if index_uids.len() > 1 {
+        let aggregate = SearchAggregator::<SearchPOST>::from_query(&query);
+        let search_result = search_indexes_of_alias(
+            &index_scheduler,
+            index_scheduler.filters(),
+            &search_queue,
+            index_uids,
+            query,
+        )
+        .await;
+        analytics.publish(aggregate, &req);
+        let search_result = search_result?;
+
+        debug!(returns = ?search_result, "Search post");
+        return Ok(HttpResponse::Ok().json(search_result));
+    }
+    // an index alias pointing to a single index is searched as this index
+    let index_uid = index_uids.pop().map(IndexUid::new_unchecked).unwrap_or(index_uid);
+
     // Tenant token search_rules.
     if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
         add_search_rules(&mut query.filter, search_rules);
@@ -475,21 +536,110 @@ pub async fn search_with_post(
     Ok(HttpResponse::Ok().json(search_result))
 }
 
+/// Search all the indexes an index alias points to, merging their results like a federated search.
+///
+/// The search rules of the tenant token are applied to each index.
+async fn search_indexes_of_alias(
+    index_scheduler: &IndexScheduler,
+    filters: &AuthFilter,
+    search_queue: &SearchQueue,
+    index_uids: Vec<String>,
+    mut query: SearchQuery,
+) -> Result<FederatedSearchResult, ResponseError> {
+    // The pagination and facets are handled by the federation rather than by each query.
+    let (offset, limit) = match (query.page.take(), query.hits_per_page.take()) {
+        (None, None) => (query.offset, query.limit),
+        (page, hits_per_page) => {
+            let hits_per_page = hits_per_page.unwrap_or_else(DEFAULT_SEARCH_LIMIT);
+            (page.unwrap_or(1).saturating_sub(1) * hits_per_page, hits_per_page)
+        }
+    };
+    query.offset = DEFAULT_SEARCH_OFFSET();
+    query.limit = DEFAULT_SEARCH_LIMIT();
+    let facets = query.facets.take();
+    let federation = Federation {
+        limit,
+        offset,
+        facets_by_index: index_uids
+            .iter()
+            .filter(|_| facets.is_some())
+            .map(|index_uid| (IndexUid::new_unchecked(index_uid), facets.clone()))
+            .collect(),
+        merge_facets: facets.is_some().then(MergeFacets::default),
+    };
+
+    let queries = index_uids
+        .into_iter()
+        .map(|index_uid| {
+            let mut query = query.clone();
+            // Tenant token search_rules.
+            if let Some(search_rules) = filters.get_index_search_rules(&index_uid) {
+                add_search_rules(&mut query.filter, search_rules);
+            }
+            SearchQueryWithIndex::from_index_query_federation(
+                IndexUid::new_unchecked(index_uid),
+                query,
+                None,
+            )
+        })
+        .collect();
+
+    let features = index_scheduler.features();
+    let permit = search_queue.try_get_search_permit().await?;
+    let search_result =
+        perform_federated_search(index_scheduler, queries, federation, features, false).await;
+    permit.drop().await;
+    search_result
+}
+
 pub fn search_kind(
     query: &SearchQuery,
     index_scheduler: &IndexScheduler,
     index_uid: String,
     index: &milli::Index,
 ) -> Result<SearchKind, ResponseError> {
+    // diversity => the results are diversified using the embeddings of the hybrid embedder
+    if query.diversity.is_some() && query.hybrid.is_none() {
+        return Err(MeilisearchHttpError::MissingSearchHybridWithDiversity.into());
+    }
+
+    // media => the query, if any, is embedded along with the media
+    if query.media.is_some() {
+        let Some(HybridQuery { semantic_ratio, embedder }) = &query.hybrid else {
+            return Err(MeilisearchHttpError::MissingSearchHybridWithMedia.into());
+        };
+        return match query.q.as_deref() {
+            // hybrid.semantic_ratio == 0.0 => keyword
+            _ if **semantic_ratio == 0.0 => Ok(SearchKind::KeywordOnly),
+            // query, hybrid.semantic_ratio < 1.0 => hybrid
+            Some(q) if !q.trim().is_empty() && **semantic_ratio < 1.0 => SearchKind::hybrid(
+                index_scheduler,
+                index_uid,
+                index,
+                embedder,
+                **semantic_ratio,
+                query.vector.as_ref(),
+            ),
+            // no query or hybrid.semantic_ratio == 1.0 => vector
+            _ => SearchKind::semantic(
+                index_scheduler,
+                index_uid,
+                index,
+                embedder,
+                query.vector.as_ref(),
+            ),
+        };
+    }
+
     // handle with care, the order of cases matters, the semantics is subtle
-    match (query.q.as_deref(), &query.hybrid, query.vector.as_deref()) {
+    match (query.q.as_deref(), &query.hybrid, query.vector.as_ref()) {
         // empty query, no vector => placeholder search
         (Some(q), _, None) if q.trim().is_empty() => Ok(SearchKind::KeywordOnly),
         // no query, no vector => placeholder search
         (None, _, None) => Ok(SearchKind::KeywordOnly),
         // hybrid.semantic_ratio == 1.0 => vector
         (_, Some(HybridQuery { semantic_ratio, embedder }), v) if **semantic_ratio == 1.0 => {
-            SearchKind::semantic(index_scheduler, index_uid, index, embedder, v.map(|v| v.len()))
+            SearchKind::semantic(index_scheduler, index_uid, index, embedder, v)
         }
         // hybrid.semantic_ratio == 0.0 => keyword
         (_, Some(HybridQuery { semantic_ratio, embedder: _ }), _) if **semantic_ratio == 0.0 => {
@@ -497,19 +647,14 @@ pub fn search_kind(
         }
         // no query, hybrid, vector => semantic
         (None, Some(HybridQuery { semantic_ratio: _, embedder }), Some(v)) => {
-            SearchKind::semantic(index_scheduler, index_uid, index, embedder, Some(v.len()))
+            SearchKind::semantic(index_scheduler, index_uid, index, embedder, Some(v))
         }
         // query, no hybrid, no vector => keyword
         (Some(_), None, None) => Ok(SearchKind::KeywordOnly),
         // query, hybrid, maybe vector => hybrid
-        (Some(_), Some(HybridQuery { semantic_ratio, embedder }), v) => SearchKind::hybrid(
-            index_scheduler,
-            index_uid,
-            index,
-            embedder,
-            **semantic_ratio,
-            v.map(|v| v.len()),
-        ),
+        (Some(_), Some(HybridQuery { semantic_ratio, embedder }), v) => {
+            SearchKind::hybrid(index_scheduler, index_uid, index, embedder, **semantic_ratio, v)
+        }
 
         (_, None, Some(_)) => Err(MeilisearchHttpError::MissingSearchHybrid.into()),
     }
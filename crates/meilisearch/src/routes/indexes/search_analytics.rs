@@ -61,6 +61,8 @@ pub struct SearchAggregator<Method: AggregateMethod> {
     semantic_ratio: bool,
     hybrid: bool,
     retrieve_vectors: bool,
+    // Whether the results were diversified.
+    diversity: bool,
 
     // every time a search is done, we increment the counter linked to the used settings
     matching_strategy: HashMap<String, usize>,
@@ -101,6 +103,7 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
         let SearchQuery {
             q,
             vector,
+            media: _,
             offset,
             limit,
             page,
@@ -124,6 +127,7 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             attributes_to_search_on,
             hybrid,
             ranking_score_threshold,
+            diversity,
             locales,
         } = query;
 
@@ -208,6 +212,7 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             ret.semantic_ratio = hybrid.semantic_ratio != DEFAULT_SEMANTIC_RATIO();
             ret.hybrid = true;
         }
+        ret.diversity = diversity.is_some();
 
         ret
     }
@@ -277,6 +282,7 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             show_ranking_score_details,
             semantic_ratio,
             hybrid,
+            diversity,
             total_degraded,
             total_used_negative_operator,
             ranking_score_threshold,
@@ -327,6 +333,7 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
         self.retrieve_vectors |= retrieve_vectors;
         self.semantic_ratio |= semantic_ratio;
         self.hybrid |= hybrid;
+        self.diversity |= diversity;
 
         // pagination
         self.max_limit = self.max_limit.max(max_limit);
@@ -403,6 +410,7 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             show_ranking_score_details,
             semantic_ratio,
             hybrid,
+            diversity,
             total_degraded,
             total_used_negative_operator,
             ranking_score_threshold,
@@ -450,6 +458,7 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             "hybrid": {
                 "enabled": hybrid,
                 "semantic_ratio": semantic_ratio,
+                "diversity": diversity,
             },
             "pagination": {
                "max_limit": max_limit,
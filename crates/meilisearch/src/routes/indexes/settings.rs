@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
 use deserr::actix_web::AwebJson;
@@ -5,12 +7,14 @@ use index_scheduler::IndexScheduler;
 use meilisearch_types::deserr::DeserrJsonError;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::update::{EmbedderChange, ReindexStrategy, SettingsChangeEstimate};
 use meilisearch_types::settings::{
     settings, SecretPolicy, SettingEmbeddingSettings, Settings, Unchecked,
 };
 use meilisearch_types::tasks::KindWithContent;
+use serde::Serialize;
 use tracing::debug;
-use utoipa::OpenApi;
+use utoipa::{OpenApi, ToSchema};
 
 use super::settings_analytics::*;
 use crate::analytics::Analytics;
@@ -39,7 +43,7 @@ macro_rules! make_setting_routes {
 
         #[derive(OpenApi)]
         #[openapi(
-            paths(update_all, get_all, delete_all, $( $attr::get, $attr::update, $attr::delete,)*),
+            paths(update_all, get_all, delete_all, dry_run, $( $attr::get, $attr::update, $attr::delete,)*),
             tags(
                 (
                     name = "Settings",
@@ -57,6 +61,7 @@ macro_rules! make_setting_routes {
                 .route(web::patch().to(SeqHandler(update_all)))
                 .route(web::get().to(SeqHandler(get_all)))
                 .route(web::delete().to(SeqHandler(delete_all))))
+                .service(web::resource("/dry-run").route(web::post().to(SeqHandler(dry_run))))
                 $(.service($attr::resources()))*;
         }
 
@@ -508,6 +513,61 @@ make_setting_routes!(
         camelcase_attr: "prefixSearch",
         analytics: PrefixSearchAnalytics
     },
+    {
+        route: "/chat",
+        update_verb: patch,
+        value_type: meilisearch_types::milli::update::chat::ChatSettings,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsChat,
+        >,
+        attr: chat,
+        camelcase_attr: "chat",
+        analytics: ChatAnalytics
+    },
+    {
+        route: "/ingest-pipeline",
+        update_verb: put,
+        value_type: Vec<meilisearch_types::milli::IngestProcessor>,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsIngestPipeline,
+        >,
+        attr: ingest_pipeline,
+        camelcase_attr: "ingestPipeline",
+        analytics: IngestPipelineAnalytics
+    },
+    {
+        route: "/document-schema",
+        update_verb: put,
+        value_type: meilisearch_types::milli::DocumentSchemaSettings,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsDocumentSchema,
+        >,
+        attr: document_schema,
+        camelcase_attr: "documentSchema",
+        analytics: DocumentSchemaAnalytics
+    },
+    {
+        route: "/field-types",
+        update_verb: put,
+        value_type: std::collections::BTreeMap<String, meilisearch_types::milli::FieldType>,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsFieldTypes,
+        >,
+        attr: field_types,
+        camelcase_attr: "fieldTypes",
+        analytics: FieldTypesAnalytics
+    },
+    {
+        route: "/generate-document-ids",
+        update_verb: put,
+        value_type: bool,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsGenerateDocumentIds,
+        >,
+        attr: generate_document_ids,
+        camelcase_attr: "generateDocumentIds",
+        analytics: GenerateDocumentIdsAnalytics
+    },
 );
 
 #[utoipa::path(
@@ -597,6 +657,17 @@ pub async fn update_all(
             ),
             facet_search: FacetSearchAnalytics::new(new_settings.facet_search.as_ref().set()),
             prefix_search: PrefixSearchAnalytics::new(new_settings.prefix_search.as_ref().set()),
+            chat: ChatAnalytics::new(new_settings.chat.as_ref().set()),
+            ingest_pipeline: IngestPipelineAnalytics::new(
+                new_settings.ingest_pipeline.as_ref().set(),
+            ),
+            document_schema: DocumentSchemaAnalytics::new(
+                new_settings.document_schema.as_ref().set(),
+            ),
+            field_types: FieldTypesAnalytics::new(new_settings.field_types.as_ref().set()),
+            generate_document_ids: GenerateDocumentIdsAnalytics::new(
+                new_settings.generate_document_ids.as_ref().set(),
+            ),
         },
         &req,
     );
@@ -714,7 +785,296 @@ pub async fn delete_all(
     Ok(HttpResponse::Accepted().json(task))
 }
 
-fn validate_settings(
+/// How the documents of the index would be reindexed.
+#[derive(Debug, Clone, Copy, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub enum ReindexStrategyView {
+    /// No document is reindexed
+    None,
+    /// Only the fields affected by the settings update are extracted again
+    AffectedFields,
+    /// All the documents are indexed again
+    Full,
+}
+
+impl From<ReindexStrategy> for ReindexStrategyView {
+    fn from(strategy: ReindexStrategy) -> Self {
+        match strategy {
+            ReindexStrategy::None => ReindexStrategyView::None,
+            ReindexStrategy::AffectedFields => ReindexStrategyView::AffectedFields,
+            ReindexStrategy::Full => ReindexStrategyView::Full,
+        }
+    }
+}
+
+/// What a settings update would change in the embeddings of an embedder.
+#[derive(Debug, Clone, Copy, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub enum EmbedderChangeView {
+    /// All the embeddings are generated again
+    FullReindex,
+    /// The embeddings of the documents whose rendered prompt changed are generated again
+    RegeneratePrompts,
+    /// The embedder is removed and its user-provided embeddings are written back to the documents
+    WriteBack,
+    /// The embeddings are kept and quantized
+    Quantize,
+}
+
+impl From<EmbedderChange> for EmbedderChangeView {
+    fn from(change: EmbedderChange) -> Self {
+        match change {
+            EmbedderChange::FullReindex => EmbedderChangeView::FullReindex,
+            EmbedderChange::RegeneratePrompts => EmbedderChangeView::RegeneratePrompts,
+            EmbedderChange::WriteBack => EmbedderChangeView::WriteBack,
+            EmbedderChange::Quantize => EmbedderChangeView::Quantize,
+        }
+    }
+}
+
+/// Values added to, removed from or modified in a set of fields or words.
+#[derive(Debug, Default, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct FieldsChangeView {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified: Option<Vec<String>>,
+}
+
+/// The difference between the current settings of the index and the settings once updated.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct SettingsDiffView {
+    /// The fields that become searchable or are no longer searchable
+    pub searchable_attributes: FieldsChangeView,
+    /// Whether the words of all the searchable fields are extracted again
+    pub tokenization_changed: bool,
+    pub stop_words: FieldsChangeView,
+    /// The separators of the tokenizer, including the default ones
+    pub separators: FieldsChangeView,
+    pub dictionary: FieldsChangeView,
+    /// The fields on which typo tolerance is disabled
+    pub exact_attributes: FieldsChangeView,
+    pub disabled_typos_terms_changed: bool,
+    /// The fields that become part of the facet databases, are removed from them or whose facet features changed
+    pub faceted_attributes: FieldsChangeView,
+    pub sortable_attributes: FieldsChangeView,
+    /// The fields used by the ascending and descending ranking rules
+    pub ranking_rule_attributes: FieldsChangeView,
+    pub distinct_attribute_changed: bool,
+    pub proximity_precision_changed: bool,
+    pub prefix_search_changed: bool,
+    pub facet_search_changed: bool,
+    pub localized_attributes_changed: bool,
+    pub geo_changed: bool,
+    /// The embedders whose embeddings are modified
+    pub embedders: BTreeMap<String, EmbedderChangeView>,
+}
+
+/// What a settings update would change in an index, computed without updating it.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct SettingsDryRunView {
+    pub diff: SettingsDiffView,
+    pub reindex_strategy: ReindexStrategyView,
+    /// The databases that would be rebuilt, fully or partially
+    #[schema(example = json!(["facet-id-string-docids", "facet-id-f64-docids"]))]
+    pub rebuilt_databases: Vec<&'static str>,
+    /// Whether embeddings would be generated again
+    pub regenerate_embeddings: bool,
+    /// An upper bound of the number of documents that would be reindexed
+    #[schema(example = 1500)]
+    pub estimated_documents_to_reprocess: u64,
+}
+
+impl From<SettingsChangeEstimate> for SettingsDryRunView {
+    fn from(estimate: SettingsChangeEstimate) -> Self {
+        let SettingsChangeEstimate {
+            reindex_strategy,
+            searchable_fields_added,
+            searchable_fields_removed,
+            tokenization_changed,
+            stop_words_added,
+            stop_words_removed,
+            separators_added,
+            separators_removed,
+            dictionary_added,
+            dictionary_removed,
+            exact_attributes_added,
+            exact_attributes_removed,
+            disabled_typos_terms_changed,
+            faceted_fields_added,
+            faceted_fields_removed,
+            faceted_fields_modified,
+            sortable_fields_added,
+            sortable_fields_removed,
+            ranking_rule_fields_added,
+            ranking_rule_fields_removed,
+            distinct_field_changed,
+            proximity_precision_changed,
+            prefix_search_changed,
+            facet_search_changed,
+            localized_attributes_changed,
+            geo_changed,
+            embedders,
+            rebuilt_databases,
+            estimated_documents_to_reprocess,
+        } = estimate;
+
+        let regenerate_embeddings = embedders.values().any(|change| {
+            matches!(change, EmbedderChange::FullReindex | EmbedderChange::RegeneratePrompts)
+        });
+
+        SettingsDryRunView {
+            diff: SettingsDiffView {
+                searchable_attributes: FieldsChangeView {
+                    added: searchable_fields_added,
+                    removed: searchable_fields_removed,
+                    modified: None,
+                },
+                tokenization_changed,
+                stop_words: FieldsChangeView {
+                    added: stop_words_added,
+                    removed: stop_words_removed,
+                    modified: None,
+                },
+                separators: FieldsChangeView {
+                    added: separators_added,
+                    removed: separators_removed,
+                    modified: None,
+                },
+                dictionary: FieldsChangeView {
+                    added: dictionary_added,
+                    removed: dictionary_removed,
+                    modified: None,
+                },
+                exact_attributes: FieldsChangeView {
+                    added: exact_attributes_added,
+                    removed: exact_attributes_removed,
+                    modified: None,
+                },
+                disabled_typos_terms_changed,
+                faceted_attributes: FieldsChangeView {
+                    added: faceted_fields_added,
+                    removed: faceted_fields_removed,
+                    modified: Some(faceted_fields_modified),
+                },
+                sortable_attributes: FieldsChangeView {
+                    added: sortable_fields_added,
+                    removed: sortable_fields_removed,
+                    modified: None,
+                },
+                ranking_rule_attributes: FieldsChangeView {
+                    added: ranking_rule_fields_added,
+                    removed: ranking_rule_fields_removed,
+                    modified: None,
+                },
+                distinct_attribute_changed: distinct_field_changed,
+                proximity_precision_changed,
+                prefix_search_changed,
+                facet_search_changed,
+                localized_attributes_changed,
+                geo_changed,
+                embedders: embedders
+                    .into_iter()
+                    .map(|(name, change)| (name, change.into()))
+                    .collect(),
+            },
+            reindex_strategy: reindex_strategy.into(),
+            rebuilt_databases,
+            regenerate_embeddings,
+            estimated_documents_to_reprocess,
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "{indexUid}/settings/dry-run",
+    tag = "Settings",
+    security(("Bearer" = ["settings.update", "settings.*", "*"])),
+    params(("indexUid", example = "movies", description = "Index Unique Identifier", nullable = false)),
+    request_body = Settings<Unchecked>,
+    responses(
+        (status = 200, description = "The changes the settings update would make", body = SettingsDryRunView, content_type = "application/json", example = json!(
+            {
+                "diff": {
+                    "searchableAttributes": { "added": [], "removed": [] },
+                    "tokenizationChanged": false,
+                    "stopWords": { "added": [], "removed": [] },
+                    "separators": { "added": [], "removed": [] },
+                    "dictionary": { "added": [], "removed": [] },
+                    "exactAttributes": { "added": [], "removed": [] },
+                    "disabledTyposTermsChanged": false,
+                    "facetedAttributes": { "added": ["genre"], "removed": [], "modified": [] },
+                    "sortableAttributes": { "added": [], "removed": [] },
+                    "rankingRuleAttributes": { "added": [], "removed": [] },
+                    "distinctAttributeChanged": false,
+                    "proximityPrecisionChanged": false,
+                    "prefixSearchChanged": false,
+                    "facetSearchChanged": false,
+                    "localizedAttributesChanged": false,
+                    "geoChanged": false,
+                    "embedders": {}
+                },
+                "reindexStrategy": "affectedFields",
+                "rebuiltDatabases": ["facet-id-f64-docids", "facet-id-string-docids"],
+                "regenerateEmbeddings": false,
+                "estimatedDocumentsToReprocess": 1500
+            }
+        )),
+        (status = 404, description = "Index not found", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Index `movies` not found.",
+                "code": "index_not_found",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#index_not_found"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+/// Dry-run a settings update
+///
+/// Compute what updating the settings of an index would change, without updating them:
+/// the difference with the current settings, the databases that would be rebuilt,
+/// whether embeddings would be generated again and how many documents would be reindexed.
+/// The estimate is computed from the settings of the index as they are committed, without waiting for the tasks being processed.
+pub async fn dry_run(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: AwebJson<Settings<Unchecked>, DeserrJsonError>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let new_settings = body.into_inner();
+    debug!(parameters = ?new_settings, "Dry-run settings");
+    let new_settings = validate_settings(new_settings, &index_scheduler)?;
+
+    let estimate = tokio::task::spawn_blocking(move || {
+        index_scheduler.settings_dry_run(&index_uid, &new_settings)
+    })
+    .await??;
+
+    let estimate = SettingsDryRunView::from(estimate);
+    debug!(returns = ?estimate, "Dry-run settings");
+    Ok(HttpResponse::Ok().json(estimate))
+}
+
+pub(crate) fn validate_settings(
     settings: Settings<Unchecked>,
     index_scheduler: &IndexScheduler,
 ) -> Result<Settings<Unchecked>, ResponseError> {
@@ -741,5 +1101,9 @@ fn validate_settings(
         }
     }
 
+    if let Setting::Set(_) = &settings.chat {
+        features.check_chat_completions("setting `chat` in the index settings")?;
+    }
+
     Ok(settings.validate()?)
 }
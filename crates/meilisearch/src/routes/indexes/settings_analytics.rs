@@ -7,8 +7,11 @@ use std::collections::{BTreeMap, BTreeSet, HashSet};
 
 use meilisearch_types::facet_values_sort::FacetValuesSort;
 use meilisearch_types::locales::{Locale, LocalizedAttributesRuleView};
+use meilisearch_types::milli::update::chat::{ChatSearchParams, ChatSettings};
 use meilisearch_types::milli::update::Setting;
-use meilisearch_types::milli::FilterableAttributesRule;
+use meilisearch_types::milli::{
+    DocumentSchemaSettings, FieldType, FilterableAttributesRule, IngestProcessor, OnInvalidDocument,
+};
 use meilisearch_types::settings::{
     FacetingSettings, PaginationSettings, PrefixSearchSettings, ProximityPrecisionView,
     RankingRuleView, SettingEmbeddingSettings, TypoSettings,
@@ -39,6 +42,11 @@ pub struct SettingsAnalytics {
     pub non_separator_tokens: NonSeparatorTokensAnalytics,
     pub facet_search: FacetSearchAnalytics,
     pub prefix_search: PrefixSearchAnalytics,
+    pub chat: ChatAnalytics,
+    pub ingest_pipeline: IngestPipelineAnalytics,
+    pub document_schema: DocumentSchemaAnalytics,
+    pub field_types: FieldTypesAnalytics,
+    pub generate_document_ids: GenerateDocumentIdsAnalytics,
 }
 
 impl Aggregate for SettingsAnalytics {
@@ -173,6 +181,22 @@ impl Aggregate for SettingsAnalytics {
                     (Some(bq), None) | (None, Some(bq)) => Some(bq),
                     (Some(this), Some(other)) => Some(this | other),
                 },
+                shadow_embedder_used: match (
+                    self.embedders.shadow_embedder_used,
+                    new.embedders.shadow_embedder_used,
+                ) {
+                    (None, None) => None,
+                    (Some(shadow), None) | (None, Some(shadow)) => Some(shadow),
+                    (Some(this), Some(other)) => Some(this | other),
+                },
+                rate_limits_used: match (
+                    self.embedders.rate_limits_used,
+                    new.embedders.rate_limits_used,
+                ) {
+                    (None, None) => None,
+                    (Some(limits), None) | (None, Some(limits)) => Some(limits),
+                    (Some(this), Some(other)) => Some(this | other),
+                },
             },
             search_cutoff_ms: SearchCutoffMsAnalytics {
                 search_cutoff_ms: new
@@ -198,6 +222,30 @@ impl Aggregate for SettingsAnalytics {
                 set: new.prefix_search.set | self.prefix_search.set,
                 value: new.prefix_search.value.or(self.prefix_search.value),
             },
+            chat: ChatAnalytics {
+                set: new.chat.set | self.chat.set,
+                hybrid: new.chat.hybrid.or(self.chat.hybrid),
+                document_template_used: new
+                    .chat
+                    .document_template_used
+                    .or(self.chat.document_template_used),
+            },
+            ingest_pipeline: IngestPipelineAnalytics {
+                total: new.ingest_pipeline.total.or(self.ingest_pipeline.total),
+                types: new.ingest_pipeline.types.or(self.ingest_pipeline.types),
+            },
+            document_schema: DocumentSchemaAnalytics {
+                set: new.document_schema.set | self.document_schema.set,
+                on_invalid: new.document_schema.on_invalid.or(self.document_schema.on_invalid),
+            },
+            field_types: FieldTypesAnalytics {
+                total: new.field_types.total.or(self.field_types.total),
+                types: new.field_types.types.or(self.field_types.types),
+            },
+            generate_document_ids: GenerateDocumentIdsAnalytics {
+                set: new.generate_document_ids.set | self.generate_document_ids.set,
+                value: new.generate_document_ids.value.or(self.generate_document_ids.value),
+            },
         })
     }
 
@@ -504,6 +552,10 @@ pub struct EmbeddersAnalytics {
     pub document_template_max_bytes: Option<usize>,
     // |=
     pub binary_quantization_used: Option<bool>,
+    // |=
+    pub shadow_embedder_used: Option<bool>,
+    // |=
+    pub rate_limits_used: Option<bool>,
 }
 
 impl EmbeddersAnalytics {
@@ -524,6 +576,13 @@ impl EmbeddersAnalytics {
                     EmbedderSource::Ollama => sources.insert("ollama".to_string()),
                     EmbedderSource::Rest => sources.insert("rest".to_string()),
                     EmbedderSource::Composite => sources.insert("composite".to_string()),
+                    EmbedderSource::UserProvidedSparse => {
+                        sources.insert("userProvidedSparse".to_string())
+                    }
+                    EmbedderSource::Onnx => sources.insert("onnx".to_string()),
+                    EmbedderSource::UserProvidedMultiVector => {
+                        sources.insert("userProvidedMultiVector".to_string())
+                    }
                 };
             }
         };
@@ -547,6 +606,18 @@ impl EmbeddersAnalytics {
                     .filter_map(|config| config.inner.clone().set())
                     .any(|config| config.binary_quantized.set().is_some())
             }),
+            shadow_embedder_used: setting.as_ref().map(|map| {
+                map.values()
+                    .filter_map(|config| config.inner.clone().set())
+                    .any(|config| !config.shadow_of.is_not_set())
+            }),
+            rate_limits_used: setting.as_ref().map(|map| {
+                map.values().filter_map(|config| config.inner.clone().set()).any(|config| {
+                    config.max_concurrent_requests.set().is_some()
+                        || config.requests_per_minute.set().is_some()
+                        || config.tokens_per_minute.set().is_some()
+                })
+            }),
         }
     }
 
@@ -674,3 +745,102 @@ impl PrefixSearchAnalytics {
         SettingsAnalytics { prefix_search: self, ..Default::default() }
     }
 }
+
+#[derive(Serialize, Default)]
+pub struct ChatAnalytics {
+    pub set: bool,
+    pub hybrid: Option<bool>,
+    pub document_template_used: Option<bool>,
+}
+
+impl ChatAnalytics {
+    pub fn new(settings: Option<&ChatSettings>) -> Self {
+        Self {
+            set: settings.is_some(),
+            hybrid: settings.and_then(|settings| match &settings.search_parameters {
+                Setting::Set(ChatSearchParams { hybrid, .. }) => {
+                    Some(hybrid.as_ref().set().is_some())
+                }
+                _ => None,
+            }),
+            document_template_used: settings
+                .map(|settings| !settings.document_template.is_not_set()),
+        }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { chat: self, ..Default::default() }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct IngestPipelineAnalytics {
+    pub total: Option<usize>,
+    pub types: Option<BTreeSet<&'static str>>,
+}
+
+impl IngestPipelineAnalytics {
+    pub fn new(processors: Option<&Vec<IngestProcessor>>) -> Self {
+        Self {
+            total: processors.map(|processors| processors.len()),
+            types: processors.map(|processors| {
+                processors.iter().map(|processor| processor.kind.name()).collect()
+            }),
+        }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { ingest_pipeline: self, ..Default::default() }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct DocumentSchemaAnalytics {
+    pub set: bool,
+    pub on_invalid: Option<OnInvalidDocument>,
+}
+
+impl DocumentSchemaAnalytics {
+    pub fn new(settings: Option<&DocumentSchemaSettings>) -> Self {
+        Self { set: settings.is_some(), on_invalid: settings.map(|settings| settings.on_invalid) }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { document_schema: self, ..Default::default() }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct FieldTypesAnalytics {
+    pub total: Option<usize>,
+    pub types: Option<BTreeSet<FieldType>>,
+}
+
+impl FieldTypesAnalytics {
+    pub fn new(field_types: Option<&BTreeMap<String, FieldType>>) -> Self {
+        Self {
+            total: field_types.map(|field_types| field_types.len()),
+            types: field_types.map(|field_types| field_types.values().copied().collect()),
+        }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { field_types: self, ..Default::default() }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct GenerateDocumentIdsAnalytics {
+    pub set: bool,
+    pub value: Option<bool>,
+}
+
+impl GenerateDocumentIdsAnalytics {
+    pub fn new(settings: Option<&bool>) -> Self {
+        Self { set: settings.is_some(), value: settings.copied() }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { generate_document_ids: self, ..Default::default() }
+    }
+}
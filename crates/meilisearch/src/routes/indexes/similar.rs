@@ -19,8 +19,8 @@ use crate::extractors::authentication::GuardedData;
 use crate::extractors::sequential_extractor::SeqHandler;
 use crate::routes::indexes::similar_analytics::{SimilarAggregator, SimilarGET, SimilarPOST};
 use crate::search::{
-    add_search_rules, perform_similar, RankingScoreThresholdSimilar, RetrieveVectors, Route,
-    SearchKind, SimilarQuery, SimilarResult, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
+    add_search_rules, perform_similar, Diversity, RankingScoreThresholdSimilar, RetrieveVectors,
+    Route, SearchKind, SimilarQuery, SimilarResult, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
 };
 
 #[derive(OpenApi)]
@@ -278,6 +278,9 @@ pub struct SimilarQueryGet {
     #[deserr(default, error = DeserrQueryParamError<InvalidSimilarRankingScoreThreshold>, default)]
     #[param(value_type = Option<f32>)]
     pub ranking_score_threshold: Option<RankingScoreThresholdGet>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSimilarDiversity>, default)]
+    #[param(value_type = Option<f32>)]
+    pub diversity: Option<DiversityGet>,
     #[deserr(error = DeserrQueryParamError<InvalidSimilarEmbedder>)]
     pub embedder: String,
 }
@@ -295,6 +298,19 @@ impl std::convert::TryFrom<String> for RankingScoreThresholdGet {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, deserr::Deserr)]
+#[deserr(try_from(String) = TryFrom::try_from -> InvalidSimilarDiversity)]
+pub struct DiversityGet(Diversity<InvalidSimilarDiversity>);
+
+impl std::convert::TryFrom<String> for DiversityGet {
+    type Error = InvalidSimilarDiversity;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        let f: f32 = s.parse().map_err(|_| InvalidSimilarDiversity)?;
+        Ok(DiversityGet(Diversity::try_from(f)?))
+    }
+}
+
 impl From<SimilarQueryGet> for SimilarQuery {
     fn from(
         SimilarQueryGet {
@@ -308,6 +324,7 @@ impl From<SimilarQueryGet> for SimilarQuery {
             show_ranking_score_details,
             embedder,
             ranking_score_threshold,
+            diversity,
         }: SimilarQueryGet,
     ) -> Self {
         let filter = match filter {
@@ -319,7 +336,11 @@ impl From<SimilarQueryGet> for SimilarQuery {
         };
 
         SimilarQuery {
-            id: serde_json::Value::String(id.0),
+            id: Some(serde_json::Value::String(id.0)),
+            positive_ids: Vec::new(),
+            negative_ids: Vec::new(),
+            positive_vectors: Vec::new(),
+            negative_vectors: Vec::new(),
             offset: offset.0,
             limit: limit.0,
             filter,
@@ -329,6 +350,7 @@ impl From<SimilarQueryGet> for SimilarQuery {
             show_ranking_score: show_ranking_score.0,
             show_ranking_score_details: show_ranking_score_details.0,
             ranking_score_threshold: ranking_score_threshold.map(|x| x.0),
+            diversity: diversity.map(|x| x.0),
         }
     }
 }
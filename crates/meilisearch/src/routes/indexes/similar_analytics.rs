@@ -31,6 +31,8 @@ pub struct SimilarAggregator<Method: AggregateMethod> {
 
     // Whether a non-default embedder was specified
     retrieve_vectors: bool,
+    // Whether the results were diversified
+    diversity: bool,
 
     // pagination
     max_limit: usize,
@@ -52,6 +54,10 @@ impl<Method: AggregateMethod> SimilarAggregator<Method> {
     pub fn from_query(query: &SimilarQuery) -> Self {
         let SimilarQuery {
             id: _,
+            positive_ids: _,
+            negative_ids: _,
+            positive_vectors: _,
+            negative_vectors: _,
             embedder: _,
             offset,
             limit,
@@ -61,6 +67,7 @@ impl<Method: AggregateMethod> SimilarAggregator<Method> {
             show_ranking_score_details,
             filter,
             ranking_score_threshold,
+            diversity,
         } = query;
 
         let mut ret = Self::default();
@@ -99,6 +106,7 @@ impl<Method: AggregateMethod> SimilarAggregator<Method> {
         ret.ranking_score_threshold = ranking_score_threshold.is_some();
 
         ret.retrieve_vectors = *retrieve_vectors;
+        ret.diversity = diversity.is_some();
 
         ret
     }
@@ -135,6 +143,7 @@ impl<Method: AggregateMethod> Aggregate for SimilarAggregator<Method> {
             show_ranking_score_details,
             ranking_score_threshold,
             retrieve_vectors,
+            diversity,
             marker: _,
         } = *new;
 
@@ -156,6 +165,7 @@ impl<Method: AggregateMethod> Aggregate for SimilarAggregator<Method> {
         }
 
         self.retrieve_vectors |= retrieve_vectors;
+        self.diversity |= diversity;
 
         // pagination
         self.max_limit = self.max_limit.max(max_limit);
@@ -190,6 +200,7 @@ impl<Method: AggregateMethod> Aggregate for SimilarAggregator<Method> {
             show_ranking_score_details,
             ranking_score_threshold,
             retrieve_vectors,
+            diversity,
             marker: _,
         } = *self;
 
@@ -215,6 +226,7 @@ impl<Method: AggregateMethod> Aggregate for SimilarAggregator<Method> {
             },
             "vector": {
                 "retrieve_vectors": retrieve_vectors,
+                "diversity": diversity,
             },
             "pagination": {
                "max_limit": max_limit,
@@ -10,8 +10,9 @@ use meilisearch_types::error::{Code, ErrorType, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::keys::CreateApiKey;
 use meilisearch_types::milli::{
-    AttributePatterns, FilterFeatures, FilterableAttributesFeatures, FilterableAttributesPatterns,
-    FilterableAttributesRule,
+    AttributePatterns, DocumentSchemaSettings, FieldType, FilterFeatures,
+    FilterableAttributesFeatures, FilterableAttributesPatterns, FilterableAttributesRule,
+    IngestProcessor, IngestProcessorKind, OnInvalidDocument,
 };
 use meilisearch_types::settings::{
     Checked, FacetingSettings, MinWordSizeTyposSetting, PaginationSettings, Settings, TypoSettings,
@@ -26,20 +27,24 @@ use utoipa::{OpenApi, ToSchema};
 
 use self::api_key::KeyView;
 use self::indexes::documents::BrowseQuery;
-use self::indexes::{IndexCreateRequest, IndexStats, UpdateIndexRequest};
+use self::indexes::{IndexCloneRequest, IndexCreateRequest, IndexStats, UpdateIndexRequest};
 use self::logs::{GetLogs, LogMode, UpdateStderrLogs};
 use self::open_api_utils::OpenApiAuth;
 use self::tasks::AllTasks;
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
 use crate::milli::progress::{ProgressStepView, ProgressView};
+use crate::routes::aliases::{IndexAliasPayload, IndexAliasView, IndexAliasesView};
 use crate::routes::batches::AllBatches;
 use crate::routes::features::RuntimeTogglableFeatures;
-use crate::routes::indexes::documents::{DocumentDeletionByFilter, DocumentEditionByFunction};
+use crate::routes::indexes::documents::{
+    DocumentDeletionByFilter, DocumentEditionByFunction, EmbeddingRegeneration,
+};
 use crate::routes::indexes::IndexView;
 use crate::routes::multi_search::SearchResults;
 use crate::routes::network::{Network, Remote};
 use crate::routes::swap_indexes::SwapIndexesPayload;
+use crate::routes::transactions::IndexTransactionPayload;
 use crate::search::{
     FederatedSearch, FederatedSearchResult, Federation, FederationOptions, MergeFacets,
     SearchQueryWithIndex, SearchResultWithIndex, SimilarQuery, SimilarResult,
@@ -50,8 +55,10 @@ use crate::Opt;
 const PAGINATION_DEFAULT_LIMIT: usize = 20;
 const PAGINATION_DEFAULT_LIMIT_FN: fn() -> usize = || 20;
 
+mod aliases;
 mod api_key;
 pub mod batches;
+pub mod chat;
 mod dump;
 pub mod features;
 pub mod indexes;
@@ -66,6 +73,7 @@ mod swap_indexes;
 pub mod tasks;
 #[cfg(test)]
 mod tasks_test;
+mod transactions;
 
 #[derive(OpenApi)]
 #[openapi(
@@ -82,8 +90,11 @@ mod tasks_test;
         (path = "/logs", api = logs::LogsApi),
         (path = "/multi-search", api = multi_search::MultiSearchApi),
         (path = "/swap-indexes", api = swap_indexes::SwapIndexesApi),
+        (path = "/aliases", api = aliases::AliasesApi),
+        (path = "/transactions", api = transactions::TransactionsApi),
         (path = "/experimental-features", api = features::ExperimentalFeaturesApi),
         (path = "/network", api = network::NetworkApi),
+        (path = "/chat", api = chat::ChatApi),
     ),
     paths(get_health, get_version, get_stats),
     tags(
@@ -94,7 +105,7 @@ mod tasks_test;
         url = "/",
         description = "Local server",
     )),
-    components(schemas(PaginationView<KeyView>, PaginationView<IndexView>, IndexView, DocumentDeletionByFilter, AllBatches, BatchStats, ProgressStepView, ProgressView, BatchView, RuntimeTogglableFeatures, SwapIndexesPayload, DocumentEditionByFunction, MergeFacets, FederationOptions, SearchQueryWithIndex, Federation, FederatedSearch, FederatedSearchResult, SearchResults, SearchResultWithIndex, SimilarQuery, SimilarResult, PaginationView<serde_json::Value>, BrowseQuery, UpdateIndexRequest, IndexUid, IndexCreateRequest, KeyView, Action, CreateApiKey, UpdateStderrLogs, LogMode, GetLogs, IndexStats, Stats, HealthStatus, HealthResponse, VersionResponse, Code, ErrorType, AllTasks, TaskView, Status, DetailsView, ResponseError, Settings<Unchecked>, Settings<Checked>, TypoSettings, MinWordSizeTyposSetting, FacetingSettings, PaginationSettings, SummarizedTaskView, Kind, Network, Remote, FilterableAttributesRule, FilterableAttributesPatterns, AttributePatterns, FilterableAttributesFeatures, FilterFeatures))
+    components(schemas(PaginationView<KeyView>, PaginationView<IndexView>, IndexView, DocumentDeletionByFilter, AllBatches, BatchStats, ProgressStepView, ProgressView, BatchView, RuntimeTogglableFeatures, SwapIndexesPayload, IndexTransactionPayload, IndexAliasPayload, IndexAliasView, IndexAliasesView, DocumentEditionByFunction, EmbeddingRegeneration, MergeFacets, FederationOptions, SearchQueryWithIndex, Federation, FederatedSearch, FederatedSearchResult, SearchResults, SearchResultWithIndex, SimilarQuery, SimilarResult, PaginationView<serde_json::Value>, BrowseQuery, UpdateIndexRequest, IndexUid, IndexCreateRequest, IndexCloneRequest, KeyView, Action, CreateApiKey, UpdateStderrLogs, LogMode, GetLogs, IndexStats, Stats, HealthStatus, HealthResponse, VersionResponse, Code, ErrorType, AllTasks, TaskView, Status, DetailsView, ResponseError, Settings<Unchecked>, Settings<Checked>, TypoSettings, MinWordSizeTyposSetting, FacetingSettings, PaginationSettings, SummarizedTaskView, Kind, Network, Remote, FilterableAttributesRule, FilterableAttributesPatterns, AttributePatterns, FilterableAttributesFeatures, FilterFeatures, IngestProcessor, IngestProcessorKind, DocumentSchemaSettings, OnInvalidDocument, FieldType))
 )]
 pub struct MeilisearchApi;
 
@@ -111,9 +122,12 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         .service(web::scope("/indexes").configure(indexes::configure))
         .service(web::scope("/multi-search").configure(multi_search::configure))
         .service(web::scope("/swap-indexes").configure(swap_indexes::configure))
+        .service(web::scope("/aliases").configure(aliases::configure))
+        .service(web::scope("/transactions").configure(transactions::configure))
         .service(web::scope("/metrics").configure(metrics::configure))
         .service(web::scope("/experimental-features").configure(features::configure))
-        .service(web::scope("/network").configure(network::configure));
+        .service(web::scope("/network").configure(network::configure))
+        .service(web::scope("/chat").configure(chat::configure));
 
     #[cfg(feature = "swagger")]
     {
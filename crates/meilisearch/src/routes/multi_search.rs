@@ -5,6 +5,7 @@ use deserr::actix_web::AwebJson;
 use index_scheduler::IndexScheduler;
 use meilisearch_types::deserr::DeserrJsonError;
 use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::keys::actions;
 use serde::Serialize;
 use tracing::debug;
@@ -160,19 +161,40 @@ pub async fn multi_search_with_post(
 
     let features = index_scheduler.features();
 
+    // The queries on an index alias are split into one query per index before
+    // the search rules of each index are applied.
+    if federation.is_some() {
+        queries = expand_index_aliases(&index_scheduler, queries)?;
+    }
+
     // regardless of federation, check authorization and apply search rules
     let auth = 'check_authorization: {
         for (query_index, federated_query) in queries.iter_mut().enumerate() {
             let index_uid = federated_query.index_uid.as_str();
+            // An index alias is checked through the indexes it points to
+            let index_uids = if is_remote_query(federated_query) {
+                vec![index_uid.to_string()]
+            } else {
+                match index_scheduler.resolve_index_uids(index_uid).with_index(query_index) {
+                    Ok(index_uids) => index_uids,
+                    Err(err) => break 'check_authorization Err(err),
+                }
+            };
             // Check index from API key
-            if !index_scheduler.filters().is_index_authorized(index_uid) {
+            if !index_uids
+                .iter()
+                .all(|index_uid| index_scheduler.filters().is_index_authorized(index_uid))
+            {
                 break 'check_authorization Err(AuthenticationError::InvalidToken)
                     .with_index(query_index);
             }
             // Apply search rules from tenant token
-            if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(index_uid)
-            {
-                add_search_rules(&mut federated_query.filter, search_rules);
+            if let [index_uid] = index_uids.as_slice() {
+                if let Some(search_rules) =
+                    index_scheduler.filters().get_index_search_rules(index_uid)
+                {
+                    add_search_rules(&mut federated_query.filter, search_rules);
+                }
             }
         }
         Ok(())
@@ -308,3 +330,34 @@ impl<T, E: Into<ResponseError>> WithIndex for Result<T, E> {
         self.map_err(|err| (err.into(), index))
     }
 }
+
+/// Replace the local queries on an index alias pointing to several indexes by one query per index,
+/// so that the results of all the indexes are merged in the federated search.
+fn expand_index_aliases(
+    index_scheduler: &IndexScheduler,
+    queries: Vec<SearchQueryWithIndex>,
+) -> Result<Vec<SearchQueryWithIndex>, ResponseError> {
+    let mut expanded = Vec::with_capacity(queries.len());
+    for query in queries {
+        let index_uids = if is_remote_query(&query) {
+            Vec::new()
+        } else {
+            index_scheduler.resolve_index_uids(&query.index_uid)?
+        };
+        if index_uids.len() <= 1 {
+            expanded.push(query);
+            continue;
+        }
+        for index_uid in index_uids {
+            expanded.push(SearchQueryWithIndex {
+                index_uid: IndexUid::new_unchecked(index_uid),
+                ..query.clone()
+            });
+        }
+    }
+    Ok(expanded)
+}
+
+fn is_remote_query(query: &SearchQueryWithIndex) -> bool {
+    query.federation_options.as_ref().is_some_and(|options| options.remote.is_some())
+}
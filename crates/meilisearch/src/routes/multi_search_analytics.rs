@@ -42,6 +42,7 @@ impl MultiSearchAggregator {
             federation_options,
             q: _,
             vector: _,
+            media: _,
             offset: _,
             limit: _,
             page: _,
@@ -65,6 +66,7 @@ impl MultiSearchAggregator {
             attributes_to_search_on: _,
             hybrid: _,
             ranking_score_threshold: _,
+            diversity: _,
             locales: _,
         } in &federated_search.queries
         {
@@ -107,10 +107,13 @@ pub async fn swap_indexes(
                 return Err(MeilisearchHttpError::SwapIndexPayloadWrongLength(indexes).into());
             }
         };
-        if !filters.is_index_authorized(lhs) || !filters.is_index_authorized(rhs) {
+        // the index aliases are checked through the index they point to
+        let lhs = index_scheduler.resolve_index_uid(lhs)?;
+        let rhs = index_scheduler.resolve_index_uid(rhs)?;
+        if !filters.is_index_authorized(&lhs) || !filters.is_index_authorized(&rhs) {
             return Err(AuthenticationError::InvalidToken.into());
         }
-        swaps.push(IndexSwap { indexes: (lhs.to_string(), rhs.to_string()) });
+        swaps.push(IndexSwap { indexes: (lhs, rhs) });
     }
 
     let task = KindWithContent::IndexSwap { swaps };
@@ -0,0 +1,247 @@
+use std::collections::BTreeSet;
+use std::io::ErrorKind;
+
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use deserr::Deserr;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::document_formats::read_bulk_operations_values;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::settings::{Settings, Unchecked};
+use meilisearch_types::tasks::{BulkOperation, KindWithContent, TransactionOperation};
+use serde::Serialize;
+use serde_json::Value;
+use tracing::debug;
+use utoipa::{OpenApi, ToSchema};
+
+use super::{get_task_id, is_dry_run, SummarizedTaskView};
+use crate::analytics::{Aggregate, Analytics};
+use crate::error::MeilisearchHttpError;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::{AuthenticationError, GuardedData};
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::routes::indexes::settings::validate_settings;
+use crate::Opt;
+
+#[derive(OpenApi)]
+#[openapi(paths(index_transaction))]
+pub struct TransactionsApi;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::post().to(SeqHandler(index_transaction))));
+}
+
+#[derive(Deserr, Debug, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct IndexTransactionPayload {
+    /// The index to update
+    #[deserr(error = DeserrJsonError<InvalidIndexUid>, missing_field_error = DeserrJsonError::missing_index_uid)]
+    index_uid: IndexUid,
+    /// The primary key of the documents, used if the index has none
+    #[deserr(default, error = DeserrJsonError<InvalidIndexPrimaryKey>)]
+    #[schema(value_type = Option<String>, example = "id")]
+    primary_key: Option<String>,
+    /// The operations to apply on the documents of the index, in the same format as the bulk documents route
+    #[deserr(default, error = DeserrJsonError<InvalidTransactionDocuments>)]
+    #[schema(value_type = Vec<Object>)]
+    documents: Vec<Value>,
+    /// The settings to update once the document operations are done
+    #[deserr(default)]
+    #[schema(value_type = Option<Settings<Unchecked>>)]
+    settings: Option<Settings<Unchecked>>,
+}
+
+#[derive(Serialize)]
+struct IndexTransactionAnalytics {
+    indexes_number: usize,
+}
+
+impl Aggregate for IndexTransactionAnalytics {
+    fn event_name(&self) -> &'static str {
+        "Index Transaction Enqueued"
+    }
+
+    fn aggregate(self: Box<Self>, new: Box<Self>) -> Box<Self> {
+        Box::new(Self { indexes_number: self.indexes_number.max(new.indexes_number) })
+    }
+
+    fn into_event(self: Box<Self>) -> serde_json::Value {
+        serde_json::to_value(*self).unwrap_or_default()
+    }
+}
+
+/// Update several indexes atomically
+///
+/// Apply document operations and settings updates to several indexes in a single task.
+/// The transaction is atomic: either all the indexes are updated, or none are.
+/// The documents of each index are given as a list of bulk operations: `upsert`, `replace`, `delete` and `deleteByFilter`.
+/// Each index can only be declared once, its settings are applied after its documents.
+/// > info
+/// > The indexes that don't exist are created if the API key allows it. Each operation requires its own action on its index:
+/// > `documents.add` for `upsert` and `replace`, `documents.delete` for `delete` and `deleteByFilter`, and `settings.update` for the settings.
+#[utoipa::path(
+    post,
+    path = "",
+    tag = "Indexes",
+    security(("Bearer" = ["documents.add", "documents.delete", "settings.update", "documents.*", "settings.*", "*"])),
+    request_body = Vec<IndexTransactionPayload>,
+    responses(
+        (status = 202, description = "Task successfully enqueued", body = SummarizedTaskView, content_type = "application/json", example = json!(
+            {
+                "taskUid": 3,
+                "indexUid": null,
+                "status": "enqueued",
+                "type": "indexTransaction",
+                "enqueuedAt": "2021-08-12T10:00:00.000000Z"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn index_transaction(
+    index_scheduler: GuardedData<IndexWritePolicy, Data<IndexScheduler>>,
+    params: AwebJson<Vec<IndexTransactionPayload>, DeserrJsonError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let params = params.into_inner();
+    debug!(parameters = ?params, "Index transaction");
+    analytics.publish(IndexTransactionAnalytics { indexes_number: params.len() }, &req);
+
+    if params.is_empty() {
+        return Err(MeilisearchHttpError::EmptyTransaction.into());
+    }
+
+    let filters = index_scheduler.filters();
+    let mut indexes = BTreeSet::new();
+    let mut resolved_index_uids = Vec::with_capacity(params.len());
+    let mut allow_index_creation = true;
+    for IndexTransactionPayload { index_uid, settings, .. } in &params {
+        // the index aliases are checked through the index they point to
+        let resolved_index_uid = index_scheduler.resolve_index_uid(index_uid)?;
+        if !filters.is_index_authorized(&resolved_index_uid)
+            || (settings.is_some()
+                && !filters.is_action_authorized(Action::SettingsUpdate, &resolved_index_uid))
+        {
+            return Err(AuthenticationError::InvalidToken.into());
+        }
+        if !indexes.insert(resolved_index_uid.clone()) {
+            return Err(
+                MeilisearchHttpError::TransactionDuplicateIndexFound(index_uid.clone()).into()
+            );
+        }
+        allow_index_creation &= filters.allow_index_creation(&resolved_index_uid);
+        resolved_index_uids.push(resolved_index_uid);
+    }
+
+    let mut payloads = Vec::with_capacity(params.len());
+    for IndexTransactionPayload { index_uid, primary_key, documents, settings } in params {
+        let settings = match settings {
+            Some(settings) => Some(Box::new(validate_settings(settings, &index_scheduler)?)),
+            None => None,
+        };
+        payloads.push((index_uid.into_inner(), primary_key, documents, settings));
+    }
+
+    let uid = get_task_id(&req, &opt)?;
+    let dry_run = is_dry_run(&req, &opt)?;
+
+    let (uuid, mut update_file) = index_scheduler.queue.create_update_file(dry_run)?;
+    let features = index_scheduler.features();
+    let operations = tokio::task::spawn_blocking(move || -> Result<_, ResponseError> {
+        let mut operations = Vec::with_capacity(payloads.len());
+        for (index_uid, primary_key, documents, settings) in payloads {
+            let documents = read_bulk_operations_values(documents, &mut update_file)
+                .map_err(MeilisearchHttpError::DocumentFormat)?;
+            for operation in &documents {
+                if let BulkOperation::DeleteByFilter { filter_expr } = operation {
+                    // we ensure the filter is well formed before enqueuing it
+                    crate::search::parse_filter(
+                        filter_expr,
+                        Code::InvalidDocumentFilter,
+                        features,
+                    )?
+                    .ok_or(MeilisearchHttpError::EmptyFilter)?;
+                }
+            }
+            operations.push(TransactionOperation { index_uid, primary_key, documents, settings });
+        }
+        // we NEED to persist the file here because we moved the `update_file` in another task.
+        update_file.persist().map_err(MeilisearchHttpError::from)?;
+        Ok(operations)
+    })
+    .await;
+
+    let operations = match operations {
+        Ok(Ok(operations)) => operations,
+        // in this case the file has not possibly be persisted.
+        Ok(Err(e)) => return Err(e),
+        Err(e) => {
+            // Here the file MAY have been persisted or not.
+            // We don't know thus we ignore the file not found error.
+            match index_scheduler.queue.delete_update_file(uuid) {
+                Ok(()) => (),
+                Err(index_scheduler::Error::FileStore(file_store::Error::IoError(e)))
+                    if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => {
+                    tracing::warn!(
+                        index_uuid = %uuid,
+                        "Unknown error happened while deleting a malformed update file: {e}"
+                    );
+                }
+            }
+            // We still want to return the original error to the end user.
+            return Err(e.into());
+        }
+    };
+
+    let unauthorized_operation = operations.iter().zip(&resolved_index_uids).any(
+        |(TransactionOperation { documents, .. }, index_uid)| {
+            documents.iter().any(|operation| {
+                let action = match operation {
+                    BulkOperation::Replace { .. } | BulkOperation::Update { .. } => {
+                        Action::DocumentsAdd
+                    }
+                    BulkOperation::Delete { .. } | BulkOperation::DeleteByFilter { .. } => {
+                        Action::DocumentsDelete
+                    }
+                };
+                !index_scheduler.filters().is_action_authorized(action, index_uid)
+            })
+        },
+    );
+    if unauthorized_operation {
+        index_scheduler.queue.delete_update_file(uuid)?;
+        return Err(AuthenticationError::InvalidToken.into());
+    }
+
+    let task =
+        KindWithContent::IndexTransaction { content_file: uuid, operations, allow_index_creation };
+
+    let scheduler = index_scheduler.clone();
+    let task =
+        match tokio::task::spawn_blocking(move || scheduler.register(task, uid, dry_run)).await? {
+            Ok(task) => task,
+            Err(e) => {
+                index_scheduler.queue.delete_update_file(uuid)?;
+                return Err(e.into());
+            }
+        };
+    let task: SummarizedTaskView = task.into();
+
+    debug!(returns = ?task, "Index transaction");
+    Ok(HttpResponse::Accepted().json(task))
+}
@@ -0,0 +1,144 @@
+//! Retrieval of the documents given to a chat completion provider to answer a question.
+
+use std::time::Duration;
+
+use index_scheduler::RoFeatures;
+use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::milli::index::{ChatConfig, ChatSearchConfig};
+use meilisearch_types::milli::prompt::Prompt;
+use meilisearch_types::milli::{self, Index, TimeBudget};
+use serde::Serialize;
+use serde_json::Value;
+
+use super::{
+    prepare_search, search_from_kind, HybridQuery, RankingScoreThreshold, SearchKind, SearchQuery,
+    SemanticRatio, DEFAULT_SEMANTIC_RATIO,
+};
+
+mod provider;
+
+pub use provider::{ChatCompletionClient, ChatCompletionError};
+
+/// A message sent to the chat completion provider.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChatMessage {
+    pub role: &'static str,
+    pub content: String,
+}
+
+/// A document retrieved to answer a question, rendered with the document template of the index.
+#[derive(Debug, Clone)]
+pub struct RetrievedDocument {
+    pub id: String,
+    pub rendered: String,
+}
+
+/// Builds the search query retrieving the documents given to the model.
+pub fn retrieval_query(
+    config: &ChatConfig,
+    q: String,
+    filter: Option<Value>,
+) -> Result<SearchQuery, ResponseError> {
+    let search_parameters = &config.search_parameters;
+
+    let hybrid = match &search_parameters.hybrid {
+        Some(hybrid) => Some(HybridQuery {
+            semantic_ratio: match hybrid.semantic_ratio {
+                Some(ratio) => SemanticRatio::try_from(ratio).map_err(|_| {
+                    invalid_settings(format!(
+                        "`.chat.searchParameters.hybrid.semanticRatio` must be between `0.0` and `1.0`, found `{ratio}`"
+                    ))
+                })?,
+                None => DEFAULT_SEMANTIC_RATIO(),
+            },
+            embedder: hybrid.embedder.clone(),
+        }),
+        None => None,
+    };
+
+    let ranking_score_threshold = match search_parameters.ranking_score_threshold {
+        Some(threshold) => Some(RankingScoreThreshold::try_from(threshold).map_err(|_| {
+            invalid_settings(format!(
+                "`.chat.searchParameters.rankingScoreThreshold` must be between `0.0` and `1.0`, found `{threshold}`"
+            ))
+        })?),
+        None => None,
+    };
+
+    Ok(SearchQuery {
+        q: Some(q),
+        limit: search_parameters.limit.unwrap_or(ChatSearchConfig::DEFAULT_LIMIT),
+        offset: 0,
+        filter,
+        hybrid,
+        ranking_score_threshold,
+        ..Default::default()
+    })
+}
+
+/// Searches the index and renders the matching documents with the document template of the chat settings.
+pub fn retrieve_documents(
+    index_uid: String,
+    index: &Index,
+    config: &ChatConfig,
+    query: SearchQuery,
+    search_kind: SearchKind,
+    features: RoFeatures,
+) -> Result<Vec<RetrievedDocument>, ResponseError> {
+    let rtxn = index.read_txn()?;
+    let time_budget = match index.search_cutoff(&rtxn)? {
+        Some(cutoff) => TimeBudget::new(Duration::from_millis(cutoff)),
+        None => TimeBudget::default(),
+    };
+
+    let (search, _is_finite_pagination, _max_total_hits, _offset, _limit) =
+        prepare_search(index, &rtxn, &query, &search_kind, time_budget, features)?;
+    let (milli::SearchResult { documents_ids, .. }, _semantic_hit_count) =
+        search_from_kind(index_uid, search_kind, search)?;
+
+    let prompt = Prompt::try_from(config.prompt.clone()).map_err(milli::Error::from)?;
+    let external_ids = index.external_id_of(&rtxn, documents_ids.iter().copied())?;
+
+    let mut documents = Vec::with_capacity(documents_ids.len());
+    for (docid, external_id) in documents_ids.iter().copied().zip(external_ids) {
+        let external_id = external_id?;
+        if let Some(rendered) = prompt.render_stored_document(index, &rtxn, docid, &external_id)? {
+            documents.push(RetrievedDocument { id: external_id, rendered });
+        }
+    }
+
+    Ok(documents)
+}
+
+/// Builds the conversation sent to the model: the instructions, then the question along with the numbered documents.
+pub fn build_messages(
+    system_prompt: &str,
+    description: &str,
+    documents: &[RetrievedDocument],
+    question: &str,
+) -> Vec<ChatMessage> {
+    let mut instructions = system_prompt.to_string();
+    if !description.is_empty() {
+        instructions.push_str("\n\nThe documents come from an index described as follows: ");
+        instructions.push_str(description);
+    }
+
+    let mut content = String::from("Documents:\n");
+    if documents.is_empty() {
+        content.push_str("(no document matches the question)\n");
+    }
+    for (i, document) in documents.iter().enumerate() {
+        content.push_str(&format!("[{}] {}\n", i + 1, document.rendered));
+    }
+    content.push_str("\nQuestion: ");
+    content.push_str(question);
+
+    vec![
+        ChatMessage { role: "system", content: instructions },
+        ChatMessage { role: "user", content },
+    ]
+}
+
+fn invalid_settings(message: String) -> ResponseError {
+    ResponseError::from_msg(message, Code::InvalidSettingsChat)
+}
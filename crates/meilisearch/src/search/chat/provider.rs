@@ -0,0 +1,320 @@
+//! Client of the chat completion provider configured with the `/chat/settings` route.
+
+use std::time::Duration;
+
+use futures::stream::{BoxStream, StreamExt as _};
+use meilisearch_types::error::{Code, ErrorCode};
+use meilisearch_types::features::{ChatCompletionSettings, ChatCompletionSource};
+use meilisearch_types::milli::vector::json_template::ValueTemplate;
+use reqwest::{Client, Response};
+use serde_json::{json, Value};
+
+use super::ChatMessage;
+
+const MESSAGES_PLACEHOLDER: &str = "{{messages}}";
+const CONTENT_PLACEHOLDER: &str = "{{content}}";
+const REPEAT_PLACEHOLDER: &str = "{{..}}";
+
+#[derive(Debug, thiserror::Error)]
+pub enum ChatCompletionError {
+    #[error("`.url`: a `url` is required when using the `rest` source")]
+    MissingUrl,
+    #[error("`.url`: `{url}` is not a valid URL: {error}")]
+    InvalidUrl { url: String, error: url::ParseError },
+    #[error("`.request`: a `request` template is required when using the `rest` source")]
+    MissingRequest,
+    #[error("`.request`: {0}")]
+    InvalidRequest(String),
+    #[error("`.response`: a `response` template is required when using the `rest` source")]
+    MissingResponse,
+    #[error("`.response`: {0}")]
+    InvalidResponse(String),
+    #[error("`.{0}` is only available with the `rest` source")]
+    OnlyForRest(&'static str),
+    #[error("could not reach the chat completion provider: {0}")]
+    CouldNotSendRequest(reqwest::Error),
+    #[error("the chat completion provider responded with code {}: {response}", status.as_u16())]
+    ProviderError { status: reqwest::StatusCode, response: String },
+    #[error("could not parse the response of the chat completion provider: {0}")]
+    CouldNotParseResponse(String),
+}
+
+impl ErrorCode for ChatCompletionError {
+    fn error_code(&self) -> Code {
+        match self {
+            ChatCompletionError::MissingUrl | ChatCompletionError::InvalidUrl { .. } => {
+                Code::InvalidChatCompletionUrl
+            }
+            ChatCompletionError::MissingRequest | ChatCompletionError::InvalidRequest(_) => {
+                Code::InvalidChatCompletionRequest
+            }
+            ChatCompletionError::MissingResponse | ChatCompletionError::InvalidResponse(_) => {
+                Code::InvalidChatCompletionResponse
+            }
+            ChatCompletionError::OnlyForRest(_) => Code::InvalidChatCompletionSource,
+            ChatCompletionError::CouldNotSendRequest(_)
+            | ChatCompletionError::ProviderError { .. }
+            | ChatCompletionError::CouldNotParseResponse(_) => Code::ChatCompletionFailed,
+        }
+    }
+}
+
+enum Source {
+    OpenAi { model: String },
+    Rest { request: ValueTemplate, response: ValueTemplate },
+}
+
+/// Sends conversations to the chat completion provider and reads back the answer of the model.
+pub struct ChatCompletionClient {
+    client: Client,
+    url: String,
+    api_key: Option<String>,
+    headers: Vec<(String, String)>,
+    source: Source,
+}
+
+impl ChatCompletionClient {
+    /// Validates the settings and prepares a client from them.
+    pub fn new(settings: &ChatCompletionSettings) -> Result<Self, ChatCompletionError> {
+        let url = match (&settings.url, settings.source) {
+            (Some(url), _) => {
+                if let Err(error) = url::Url::parse(url) {
+                    return Err(ChatCompletionError::InvalidUrl { url: url.clone(), error });
+                }
+                url.clone()
+            }
+            (None, ChatCompletionSource::OpenAi) => {
+                ChatCompletionSettings::DEFAULT_OPENAI_URL.to_string()
+            }
+            (None, ChatCompletionSource::Rest) => return Err(ChatCompletionError::MissingUrl),
+        };
+
+        let source = match settings.source {
+            ChatCompletionSource::OpenAi => {
+                if settings.request.is_some() {
+                    return Err(ChatCompletionError::OnlyForRest("request"));
+                }
+                if settings.response.is_some() {
+                    return Err(ChatCompletionError::OnlyForRest("response"));
+                }
+                Source::OpenAi {
+                    model: settings.model.clone().unwrap_or_else(|| {
+                        ChatCompletionSettings::DEFAULT_OPENAI_MODEL.to_string()
+                    }),
+                }
+            }
+            ChatCompletionSource::Rest => {
+                let request =
+                    settings.request.clone().ok_or(ChatCompletionError::MissingRequest)?;
+                let request = ValueTemplate::new(request, MESSAGES_PLACEHOLDER, REPEAT_PLACEHOLDER)
+                    .map_err(|error| {
+                        ChatCompletionError::InvalidRequest(error.error_message(
+                            "request",
+                            MESSAGES_PLACEHOLDER,
+                            REPEAT_PLACEHOLDER,
+                        ))
+                    })?;
+                let response =
+                    settings.response.clone().ok_or(ChatCompletionError::MissingResponse)?;
+                let response =
+                    ValueTemplate::new(response, CONTENT_PLACEHOLDER, REPEAT_PLACEHOLDER).map_err(
+                        |error| {
+                            ChatCompletionError::InvalidResponse(error.error_message(
+                                "response",
+                                CONTENT_PLACEHOLDER,
+                                REPEAT_PLACEHOLDER,
+                            ))
+                        },
+                    )?;
+                if request.has_array_value() {
+                    return Err(ChatCompletionError::InvalidRequest(format!(
+                        "\"{REPEAT_PLACEHOLDER}\" cannot be used, the messages are injected as a single array"
+                    )));
+                }
+                if response.has_array_value() {
+                    return Err(ChatCompletionError::InvalidResponse(format!(
+                        "\"{REPEAT_PLACEHOLDER}\" cannot be used, the answer is a single string"
+                    )));
+                }
+                Source::Rest { request, response }
+            }
+        };
+
+        let client = reqwest::ClientBuilder::new()
+            .connect_timeout(Duration::from_secs(10))
+            .build()
+            .map_err(ChatCompletionError::CouldNotSendRequest)?;
+
+        Ok(Self {
+            client,
+            url,
+            api_key: settings.api_key.clone(),
+            headers: settings.headers.clone().into_iter().collect(),
+            source,
+        })
+    }
+
+    /// Asks the provider for the complete answer of the model.
+    pub async fn complete(&self, messages: &[ChatMessage]) -> Result<String, ChatCompletionError> {
+        let response = self.send(messages, false).await?;
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|error| ChatCompletionError::CouldNotParseResponse(error.to_string()))?;
+        self.extract_answer(body)
+    }
+
+    /// Asks the provider for the answer of the model, returning the parts of the answer as they are generated.
+    ///
+    /// The `rest` source does not support streaming, so its answer is returned as a single part.
+    pub async fn stream(
+        &self,
+        messages: &[ChatMessage],
+    ) -> Result<BoxStream<'static, Result<String, ChatCompletionError>>, ChatCompletionError> {
+        let (response, answer) = match self.source {
+            Source::OpenAi { .. } => (Some(self.send(messages, true).await?), None),
+            Source::Rest { .. } => (None, Some(self.complete(messages).await?)),
+        };
+
+        let state = DeltaStream { response, answer, buffer: Vec::new(), finished: false };
+        Ok(futures::stream::unfold(state, |mut state| async move {
+            state.next_delta().await.map(|delta| (delta, state))
+        })
+        .boxed())
+    }
+
+    async fn send(
+        &self,
+        messages: &[ChatMessage],
+        stream: bool,
+    ) -> Result<Response, ChatCompletionError> {
+        let body = match &self.source {
+            Source::OpenAi { model } => {
+                json!({ "model": model, "messages": messages, "stream": stream })
+            }
+            Source::Rest { request, .. } => {
+                let messages = serde_json::to_value(messages)
+                    .map_err(|error| ChatCompletionError::InvalidRequest(error.to_string()))?;
+                request.inject(std::iter::once(messages)).map_err(|_| {
+                    ChatCompletionError::InvalidRequest(format!(
+                        "could not inject the messages in place of \"{MESSAGES_PLACEHOLDER}\""
+                    ))
+                })?
+            }
+        };
+
+        let mut request = self.client.post(&self.url).json(&body);
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|error| ChatCompletionError::CouldNotSendRequest(error.without_url()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let response = response.text().await.unwrap_or_default();
+            return Err(ChatCompletionError::ProviderError { status, response });
+        }
+        Ok(response)
+    }
+
+    fn extract_answer(&self, body: Value) -> Result<String, ChatCompletionError> {
+        match &self.source {
+            Source::OpenAi { .. } => body
+                .pointer("/choices/0/message/content")
+                .and_then(Value::as_str)
+                .map(ToOwned::to_owned)
+                .ok_or_else(|| {
+                    ChatCompletionError::CouldNotParseResponse(
+                        "missing `choices[0].message.content`".to_string(),
+                    )
+                }),
+            Source::Rest { response, .. } => {
+                let mut answers: Vec<String> = response.extract(body).map_err(|error| {
+                    ChatCompletionError::CouldNotParseResponse(error.error_message(
+                        "response",
+                        CONTENT_PLACEHOLDER,
+                        "a string",
+                    ))
+                })?;
+                // the template has a single placeholder, which is always extracted
+                Ok(answers.pop().unwrap_or_default())
+            }
+        }
+    }
+}
+
+/// Reads the server-sent events of an OpenAI-compatible provider, one delta of the answer at a time.
+struct DeltaStream {
+    response: Option<Response>,
+    answer: Option<String>,
+    buffer: Vec<u8>,
+    finished: bool,
+}
+
+impl DeltaStream {
+    async fn next_delta(&mut self) -> Option<Result<String, ChatCompletionError>> {
+        if let Some(answer) = self.answer.take() {
+            return Some(Ok(answer));
+        }
+        self.response.as_ref()?;
+
+        loop {
+            if let Some(end) = self.buffer.iter().position(|byte| *byte == b'\n') {
+                let line: Vec<u8> = self.buffer.drain(..=end).collect();
+                let line = String::from_utf8_lossy(&line);
+                let Some(data) = line.trim().strip_prefix("data:") else { continue };
+                let data = data.trim();
+                if data == "[DONE]" {
+                    self.response = None;
+                    return None;
+                }
+                match parse_event(data) {
+                    Ok(Some(delta)) => return Some(Ok(delta)),
+                    Ok(None) => continue,
+                    Err(error) => {
+                        self.response = None;
+                        return Some(Err(error));
+                    }
+                }
+            }
+
+            if self.finished {
+                self.response = None;
+                return None;
+            }
+
+            let Some(response) = self.response.as_mut() else { return None };
+            match response.chunk().await {
+                Ok(Some(chunk)) => self.buffer.extend_from_slice(&chunk),
+                Ok(None) => {
+                    // flush the last line when it is not terminated by a newline
+                    self.finished = true;
+                    self.buffer.push(b'\n');
+                }
+                Err(error) => {
+                    self.response = None;
+                    return Some(Err(ChatCompletionError::CouldNotSendRequest(
+                        error.without_url(),
+                    )));
+                }
+            }
+        }
+    }
+}
+
+fn parse_event(data: &str) -> Result<Option<String>, ChatCompletionError> {
+    let event: Value = serde_json::from_str(data)
+        .map_err(|error| ChatCompletionError::CouldNotParseResponse(error.to_string()))?;
+    Ok(event
+        .pointer("/choices/0/delta/content")
+        .and_then(Value::as_str)
+        .filter(|delta| !delta.is_empty())
+        .map(ToOwned::to_owned))
+}
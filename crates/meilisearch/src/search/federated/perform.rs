@@ -474,6 +474,10 @@ impl PartitionedQueries {
             .into());
         }
 
+        if federated_query.diversity.is_some() {
+            return Err(MeilisearchHttpError::DiversityInFederatedQuery(query_index).into());
+        }
+
         let (index_uid, query, federation_options) = federated_query.into_index_query_federation();
 
         let federation_options = federation_options.unwrap_or_default();
@@ -800,14 +804,15 @@ impl SearchByIndex {
                     None => TimeBudget::default(),
                 };
 
-                let (mut search, _is_finite_pagination, _max_total_hits, _offset) = prepare_search(
-                    &index,
-                    &rtxn,
-                    &query,
-                    &search_kind,
-                    time_budget,
-                    params.features,
-                )?;
+                let (mut search, _is_finite_pagination, _max_total_hits, _offset, _limit) =
+                    prepare_search(
+                        &index,
+                        &rtxn,
+                        &query,
+                        &search_kind,
+                        time_budget,
+                        params.features,
+                    )?;
 
                 search.scoring_strategy(milli::score_details::ScoringStrategy::Detailed);
                 search.offset(0);
@@ -1,11 +1,12 @@
 use core::fmt;
 use std::cmp::min;
 use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::marker::PhantomData;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use deserr::Deserr;
+use deserr::{DeserializeError, Deserr, MergeWithError};
 use either::Either;
 use index_scheduler::RoFeatures;
 use indexmap::IndexMap;
@@ -17,8 +18,8 @@ use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::locales::Locale;
 use meilisearch_types::milli::score_details::{ScoreDetails, ScoringStrategy};
-use meilisearch_types::milli::vector::parsed_vectors::ExplicitVectors;
-use meilisearch_types::milli::vector::Embedder;
+use meilisearch_types::milli::vector::parsed_vectors::{ExplicitVectors, QueryVector};
+use meilisearch_types::milli::vector::{Embedder, Embedding};
 use meilisearch_types::milli::{
     FacetValueHit, InternalError, OrderBy, PatternMatch, SearchForFacetValues, TimeBudget,
 };
@@ -26,8 +27,9 @@ use meilisearch_types::settings::DEFAULT_PAGINATION_MAX_TOTAL_HITS;
 use meilisearch_types::{milli, Document};
 use milli::tokenizer::{Language, TokenizerBuilder};
 use milli::{
-    AscDesc, FieldId, FieldsIdsMap, Filter, FormatOptions, Index, LocalizedAttributesRule,
-    MatchBounds, MatcherBuilder, SortError, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    AscDesc, Diversify, DocumentId, FieldId, FieldsIdsMap, Filter, FormatOptions, Index,
+    LocalizedAttributesRule, MatchBounds, MatcherBuilder, SortError, TermsMatchingStrategy,
+    DEFAULT_VALUES_PER_FACET,
 };
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -38,6 +40,7 @@ use utoipa::ToSchema;
 
 use crate::error::MeilisearchHttpError;
 
+pub mod chat;
 mod federated;
 pub use federated::{
     perform_federated_search, FederatedSearch, FederatedSearchResult, Federation,
@@ -62,7 +65,10 @@ pub struct SearchQuery {
     #[deserr(default, error = DeserrJsonError<InvalidSearchQ>)]
     pub q: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchVector>)]
-    pub vector: Option<Vec<f32>>,
+    #[schema(value_type = Option<Vec<f32>>)]
+    pub vector: Option<QueryVector>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchMedia>)]
+    pub media: Option<Value>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHybridQuery>)]
     pub hybrid: Option<HybridQuery>,
     #[deserr(default = DEFAULT_SEARCH_OFFSET(), error = DeserrJsonError<InvalidSearchOffset>)]
@@ -115,6 +121,11 @@ pub struct SearchQuery {
     pub attributes_to_search_on: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchRankingScoreThreshold>)]
     pub ranking_score_threshold: Option<RankingScoreThreshold>,
+    /// Reorders the results to favor variety, using the embedder of `hybrid`.
+    /// From `0.0`, keeping the ranking order, to `1.0`, only favoring variety.
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDiversity>)]
+    #[schema(value_type = Option<f32>)]
+    pub diversity: Option<Diversity>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>)]
     pub locales: Option<Vec<Locale>>,
 }
@@ -154,6 +165,75 @@ impl std::convert::TryFrom<f64> for RankingScoreThresholdSimilar {
     }
 }
 
+/// How much the results are reordered to favor variety over relevance, from `0.0` to `1.0`.
+///
+/// Generic over the error code returned for an invalid value, as both the search and the similar routes accept it.
+pub struct Diversity<C = InvalidSearchDiversity> {
+    value: f32,
+    _code: PhantomData<C>,
+}
+
+impl<C> Diversity<C> {
+    /// The `lambda` of the maximal marginal relevance, the weight of the relevance against the variety.
+    pub fn lambda(&self) -> f32 {
+        1.0 - self.value
+    }
+}
+
+impl<C: Default> std::convert::TryFrom<f32> for Diversity<C> {
+    type Error = C;
+
+    fn try_from(f: f32) -> Result<Self, Self::Error> {
+        // the suggested "fix" is: `!(0.0..=1.0).contains(&f)`` which is allegedly less readable
+        #[allow(clippy::manual_range_contains)]
+        if f > 1.0 || f < 0.0 {
+            Err(C::default())
+        } else {
+            Ok(Self { value: f, _code: PhantomData })
+        }
+    }
+}
+
+impl<C, E> Deserr<E> for Diversity<C>
+where
+    C: Default,
+    E: DeserializeError + MergeWithError<C>,
+{
+    fn deserialize_from_value<V: deserr::IntoValue>(
+        value: deserr::Value<V>,
+        location: deserr::ValuePointerRef,
+    ) -> Result<Self, E> {
+        let f = f32::deserialize_from_value(value, location)?;
+        Self::try_from(f).map_err(|code| deserr::take_cf_content(E::merge(None, code, location)))
+    }
+}
+
+impl<C> Serialize for Diversity<C> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.value.serialize(serializer)
+    }
+}
+
+impl<C> fmt::Debug for Diversity<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Diversity").field(&self.value).finish()
+    }
+}
+
+impl<C> Clone for Diversity<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for Diversity<C> {}
+
+impl<C> PartialEq for Diversity<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
 // Since this structure is logged A LOT we're going to reduce the number of things it logs to the bare minimum.
 // - Only what IS used, we know everything else is set to None so there is no need to print it
 // - Re-order the most important field to debug first
@@ -162,6 +242,7 @@ impl fmt::Debug for SearchQuery {
         let Self {
             q,
             vector,
+            media,
             hybrid,
             offset,
             limit,
@@ -185,6 +266,7 @@ impl fmt::Debug for SearchQuery {
             matching_strategy,
             attributes_to_search_on,
             ranking_score_threshold,
+            diversity,
             locales,
         } = self;
 
@@ -207,14 +289,19 @@ impl fmt::Debug for SearchQuery {
             debug.field("retrieve_vectors", &retrieve_vectors);
         }
         if let Some(v) = vector {
-            if v.len() < 10 {
-                debug.field("vector", &v);
-            } else {
-                debug.field(
+            match v {
+                QueryVector::Single(v) if v.len() < 10 => debug.field("vector", &v),
+                QueryVector::Single(v) => debug.field(
                     "vector",
                     &format!("[{}, {}, {}, ... {} dimensions]", v[0], v[1], v[2], v.len()),
-                );
-            }
+                ),
+                QueryVector::Matrix(m) => {
+                    debug.field("vector", &format!("[... {} vectors]", m.len()))
+                }
+            };
+        }
+        if media.is_some() {
+            debug.field("media", &"[media]");
         }
         if let Some(hybrid) = hybrid {
             debug.field("hybrid", &hybrid);
@@ -266,6 +353,9 @@ impl fmt::Debug for SearchQuery {
         if let Some(ranking_score_threshold) = ranking_score_threshold {
             debug.field("ranking_score_threshold", &ranking_score_threshold);
         }
+        if let Some(diversity) = diversity {
+            debug.field("diversity", &diversity);
+        }
 
         if let Some(locales) = locales {
             debug.field("locales", &locales);
@@ -300,14 +390,14 @@ impl SearchKind {
         index_uid: String,
         index: &Index,
         embedder_name: &str,
-        vector_len: Option<usize>,
+        vector: Option<&QueryVector>,
     ) -> Result<Self, ResponseError> {
         let (embedder_name, embedder, quantized) = Self::embedder(
             index_scheduler,
             index_uid,
             index,
             embedder_name,
-            vector_len,
+            vector,
             Route::Search,
         )?;
         Ok(Self::SemanticOnly { embedder_name, embedder, quantized })
@@ -319,14 +409,14 @@ impl SearchKind {
         index: &Index,
         embedder_name: &str,
         semantic_ratio: f32,
-        vector_len: Option<usize>,
+        vector: Option<&QueryVector>,
     ) -> Result<Self, ResponseError> {
         let (embedder_name, embedder, quantized) = Self::embedder(
             index_scheduler,
             index_uid,
             index,
             embedder_name,
-            vector_len,
+            vector,
             Route::Search,
         )?;
         Ok(Self::Hybrid { embedder_name, embedder, quantized, semantic_ratio })
@@ -337,7 +427,7 @@ impl SearchKind {
         index_uid: String,
         index: &Index,
         embedder_name: &str,
-        vector_len: Option<usize>,
+        vector: Option<&QueryVector>,
         route: Route,
     ) -> Result<(String, Arc<Embedder>, bool), ResponseError> {
         let rtxn = index.read_txn()?;
@@ -356,16 +446,8 @@ impl SearchKind {
             })
             .map_err(milli::Error::from)?;
 
-        if let Some(vector_len) = vector_len {
-            if vector_len != embedder.dimensions() {
-                return Err(meilisearch_types::milli::Error::UserError(
-                    meilisearch_types::milli::UserError::InvalidVectorDimensions {
-                        expected: embedder.dimensions(),
-                        found: vector_len,
-                    },
-                )
-                .into());
-            }
+        if let Some(vector) = vector {
+            vector.check_dimensions(&embedder).map_err(milli::Error::from)?;
         }
 
         Ok((embedder_name.to_owned(), embedder, quantized))
@@ -424,7 +506,10 @@ pub struct SearchQueryWithIndex {
     #[deserr(default, error = DeserrJsonError<InvalidSearchQ>)]
     pub q: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchQ>)]
-    pub vector: Option<Vec<f32>>,
+    #[schema(value_type = Option<Vec<f32>>)]
+    pub vector: Option<QueryVector>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchMedia>)]
+    pub media: Option<Value>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHybridQuery>)]
     pub hybrid: Option<HybridQuery>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchOffset>)]
@@ -471,6 +556,9 @@ pub struct SearchQueryWithIndex {
     pub attributes_to_search_on: Option<Vec<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchRankingScoreThreshold>, default)]
     pub ranking_score_threshold: Option<RankingScoreThreshold>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDiversity>, default)]
+    #[schema(value_type = Option<f32>)]
+    pub diversity: Option<Diversity>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>, default)]
     pub locales: Option<Vec<Locale>>,
 
@@ -505,6 +593,7 @@ impl SearchQueryWithIndex {
         let SearchQuery {
             q,
             vector,
+            media,
             hybrid,
             offset,
             limit,
@@ -528,6 +617,7 @@ impl SearchQueryWithIndex {
             matching_strategy,
             attributes_to_search_on,
             ranking_score_threshold,
+            diversity,
             locales,
         } = query;
 
@@ -535,6 +625,7 @@ impl SearchQueryWithIndex {
             index_uid,
             q,
             vector,
+            media,
             hybrid,
             offset: if offset == DEFAULT_SEARCH_OFFSET() { None } else { Some(offset) },
             limit: if limit == DEFAULT_SEARCH_LIMIT() { None } else { Some(limit) },
@@ -558,6 +649,7 @@ impl SearchQueryWithIndex {
             matching_strategy,
             attributes_to_search_on,
             ranking_score_threshold,
+            diversity,
             locales,
             federation_options,
         }
@@ -569,6 +661,7 @@ impl SearchQueryWithIndex {
             federation_options,
             q,
             vector,
+            media,
             offset,
             limit,
             page,
@@ -592,6 +685,7 @@ impl SearchQueryWithIndex {
             attributes_to_search_on,
             hybrid,
             ranking_score_threshold,
+            diversity,
             locales,
         } = self;
         (
@@ -599,6 +693,7 @@ impl SearchQueryWithIndex {
             SearchQuery {
                 q,
                 vector,
+                media,
                 offset: offset.unwrap_or(DEFAULT_SEARCH_OFFSET()),
                 limit: limit.unwrap_or(DEFAULT_SEARCH_LIMIT()),
                 page,
@@ -622,6 +717,7 @@ impl SearchQueryWithIndex {
                 attributes_to_search_on,
                 hybrid,
                 ranking_score_threshold,
+                diversity,
                 locales,
                 // do not use ..Default::default() here,
                 // rather add any missing field from `SearchQuery` to `SearchQueryWithIndex`
@@ -634,9 +730,23 @@ impl SearchQueryWithIndex {
 #[derive(Debug, Clone, PartialEq, Deserr, ToSchema)]
 #[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
 pub struct SimilarQuery {
-    #[deserr(error = DeserrJsonError<InvalidSimilarId>)]
-    #[schema(value_type = String)]
-    pub id: serde_json::Value,
+    #[deserr(default, error = DeserrJsonError<InvalidSimilarId>)]
+    #[schema(value_type = Option<String>)]
+    pub id: Option<serde_json::Value>,
+    /// Documents the results should be similar to, in addition to `id`
+    #[deserr(default, error = DeserrJsonError<InvalidSimilarId>)]
+    #[schema(value_type = Vec<String>)]
+    pub positive_ids: Vec<serde_json::Value>,
+    /// Documents the results should not be similar to
+    #[deserr(default, error = DeserrJsonError<InvalidSimilarId>)]
+    #[schema(value_type = Vec<String>)]
+    pub negative_ids: Vec<serde_json::Value>,
+    /// Vectors the results should be similar to
+    #[deserr(default, error = DeserrJsonError<InvalidSimilarVectors>)]
+    pub positive_vectors: Vec<Vec<f32>>,
+    /// Vectors the results should not be similar to
+    #[deserr(default, error = DeserrJsonError<InvalidSimilarVectors>)]
+    pub negative_vectors: Vec<Vec<f32>>,
     #[deserr(default = DEFAULT_SEARCH_OFFSET(), error = DeserrJsonError<InvalidSimilarOffset>)]
     pub offset: usize,
     #[deserr(default = DEFAULT_SEARCH_LIMIT(), error = DeserrJsonError<InvalidSimilarLimit>)]
@@ -656,6 +766,11 @@ pub struct SimilarQuery {
     #[deserr(default, error = DeserrJsonError<InvalidSimilarRankingScoreThreshold>, default)]
     #[schema(value_type = f64)]
     pub ranking_score_threshold: Option<RankingScoreThresholdSimilar>,
+    /// Reorders the results to favor variety.
+    /// From `0.0`, keeping the similarity order, to `1.0`, only favoring variety.
+    #[deserr(default, error = DeserrJsonError<InvalidSimilarDiversity>, default)]
+    #[schema(value_type = Option<f32>)]
+    pub diversity: Option<Diversity<InvalidSimilarDiversity>>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -882,6 +997,22 @@ pub fn add_search_rules(filter: &mut Option<Value>, rules: IndexSearchRules) {
     }
 }
 
+fn embed_media(
+    embedder: &Embedder,
+    q: Option<&str>,
+    media: &Value,
+) -> Result<Embedding, ResponseError> {
+    let span = tracing::trace_span!(target: "search::vector", "embed_media");
+    let _entered = span.enter();
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+
+    Ok(embedder
+        .embed_search_media(q, media, Some(deadline))
+        .map_err(milli::vector::Error::from)
+        .map_err(milli::Error::from)?)
+}
+
 fn prepare_search<'t>(
     index: &'t Index,
     rtxn: &'t RoTxn,
@@ -889,7 +1020,7 @@ fn prepare_search<'t>(
     search_kind: &SearchKind,
     time_budget: TimeBudget,
     features: RoFeatures,
-) -> Result<(milli::Search<'t>, bool, usize, usize), ResponseError> {
+) -> Result<(milli::Search<'t>, bool, usize, usize, usize), ResponseError> {
     let mut search = index.search(rtxn);
     search.time_budget(time_budget);
     if let Some(ranking_score_threshold) = query.ranking_score_threshold {
@@ -906,19 +1037,32 @@ fn prepare_search<'t>(
                 search.query(q);
             }
         }
+        SearchKind::SemanticOnly { embedder_name, embedder, quantized }
+            if embedder.is_sparse() && query.vector.is_none() && query.media.is_none() =>
+        {
+            // sparse embedders compute their vector from the query during the search
+            if let Some(q) = &query.q {
+                search.query(q);
+            }
+            search.semantic(embedder_name.clone(), embedder.clone(), *quantized, None);
+        }
         SearchKind::SemanticOnly { embedder_name, embedder, quantized } => {
-            let vector = match query.vector.clone() {
-                Some(vector) => vector,
-                None => {
+            let vector = match (query.vector.clone(), &query.media) {
+                (Some(vector), _) => vector,
+                (None, Some(media)) => {
+                    QueryVector::Single(embed_media(embedder, query.q.as_deref(), media)?)
+                }
+                (None, None) => {
                     let span = tracing::trace_span!(target: "search::vector", "embed_one");
                     let _entered = span.enter();
 
                     let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
 
-                    embedder
+                    let vector = embedder
                         .embed_search(query.q.as_ref().unwrap(), Some(deadline))
                         .map_err(milli::vector::Error::from)
-                        .map_err(milli::Error::from)?
+                        .map_err(milli::Error::from)?;
+                    QueryVector::Single(vector)
                 }
             };
 
@@ -928,13 +1072,15 @@ fn prepare_search<'t>(
             if let Some(q) = &query.q {
                 search.query(q);
             }
-            // will be embedded in hybrid search if necessary
-            search.semantic(
-                embedder_name.clone(),
-                embedder.clone(),
-                *quantized,
-                query.vector.clone(),
-            );
+            let vector = match (query.vector.clone(), &query.media) {
+                // the media cannot be embedded by the hybrid search from the query alone
+                (None, Some(media)) => {
+                    Some(QueryVector::Single(embed_media(embedder, query.q.as_deref(), media)?))
+                }
+                // will be embedded in hybrid search if necessary
+                (vector, _) => vector,
+            };
+            search.semantic(embedder_name.clone(), embedder.clone(), *quantized, vector);
         }
     }
 
@@ -956,6 +1102,7 @@ fn prepare_search<'t>(
         if query.show_ranking_score
             || query.show_ranking_score_details
             || query.ranking_score_threshold.is_some()
+            || query.diversity.is_some()
         {
             ScoringStrategy::Detailed
         } else {
@@ -979,8 +1126,14 @@ fn prepare_search<'t>(
     let offset = min(offset, max_total_hits);
     let limit = min(limit, max_total_hits.saturating_sub(offset));
 
-    search.offset(offset);
-    search.limit(limit);
+    if query.diversity.is_some() {
+        // retrieve more candidates than requested, so that varied documents can be picked among them
+        search.offset(0);
+        search.limit(Diversify::window(offset, limit).min(max_total_hits));
+    } else {
+        search.offset(offset);
+        search.limit(limit);
+    }
 
     if let Some(ref filter) = query.filter {
         if let Some(facets) = parse_filter(filter, Code::InvalidSearchFilter, features)? {
@@ -1003,7 +1156,7 @@ fn prepare_search<'t>(
         search.locales(locales.iter().copied().map(Into::into).collect());
     }
 
-    Ok((search, is_finite_pagination, max_total_hits, offset))
+    Ok((search, is_finite_pagination, max_total_hits, offset, limit))
 }
 
 pub fn perform_search(
@@ -1021,7 +1174,7 @@ pub fn perform_search(
         None => TimeBudget::default(),
     };
 
-    let (search, is_finite_pagination, max_total_hits, offset) =
+    let (search, is_finite_pagination, max_total_hits, offset, effective_limit) =
         prepare_search(index, &rtxn, &query, &search_kind, time_budget, features)?;
 
     let (
@@ -1036,6 +1189,17 @@ pub fn perform_search(
         semantic_hit_count,
     ) = search_from_kind(index_uid, search_kind, search)?;
 
+    let (documents_ids, document_scores) = match (query.diversity, &query.hybrid) {
+        (Some(diversity), Some(HybridQuery { embedder, .. })) => Diversify::new(
+            index,
+            &rtxn,
+            embedder,
+            diversity.lambda(),
+        )
+        .execute(documents_ids, document_scores, offset, effective_limit)?,
+        _ => (documents_ids, document_scores),
+    };
+
     let SearchQuery {
         q,
         limit,
@@ -1058,6 +1222,8 @@ pub fn perform_search(
         locales,
         // already used in prepare_search
         vector: _,
+        diversity: _,
+        media: _,
         hybrid: _,
         offset: _,
         ranking_score_threshold: _,
@@ -1548,7 +1714,7 @@ pub fn perform_facet_search(
             .collect()
     });
 
-    let (search, _, _, _) =
+    let (search, _, _, _, _) =
         prepare_search(index, &rtxn, &search_query, &search_kind, time_budget, features)?;
     let mut facet_search = SearchForFacetValues::new(
         facet_name,
@@ -1573,6 +1739,32 @@ pub fn perform_facet_search(
     })
 }
 
+/// Converts the external ids of the examples to internal ids, along with the path of each id in the query.
+fn similar_internal_ids(
+    index: &Index,
+    rtxn: &RoTxn,
+    ids: impl Iterator<Item = (String, Value)>,
+) -> Result<Vec<DocumentId>, ResponseError> {
+    let mut internal_ids = Vec::new();
+    for (path, id) in ids {
+        let id: ExternalDocumentId = id.try_into().map_err(|error| {
+            let msg = format!("Invalid value at `{path}`: {error}");
+            ResponseError::from_msg(msg, Code::InvalidSimilarId)
+        })?;
+
+        // using let-else rather than `?` so that the borrow checker identifies we're always returning here,
+        // preventing a use-after-move
+        let Some(internal_id) = index.external_documents_ids().get(rtxn, &id)? else {
+            return Err(ResponseError::from_msg(
+                MeilisearchHttpError::DocumentNotFound(id.into_inner()).to_string(),
+                Code::NotFoundSimilarId,
+            ));
+        };
+        internal_ids.push(internal_id);
+    }
+    Ok(internal_ids)
+}
+
 pub fn perform_similar(
     index: &Index,
     query: SimilarQuery,
@@ -1587,6 +1779,10 @@ pub fn perform_similar(
 
     let SimilarQuery {
         id,
+        positive_ids,
+        negative_ids,
+        positive_vectors,
+        negative_vectors,
         offset,
         limit,
         filter: _,
@@ -1596,29 +1792,38 @@ pub fn perform_similar(
         show_ranking_score,
         show_ranking_score_details,
         ranking_score_threshold,
+        diversity,
     } = query;
 
-    let id: ExternalDocumentId = id.try_into().map_err(|error| {
-        let msg = format!("Invalid value at `.id`: {error}");
-        ResponseError::from_msg(msg, Code::InvalidSimilarId)
-    })?;
-
-    // using let-else rather than `?` so that the borrow checker identifies we're always returning here,
-    // preventing a use-after-move
-    let Some(internal_id) = index.external_documents_ids().get(&rtxn, &id)? else {
+    if id.is_none() && positive_ids.is_empty() && positive_vectors.is_empty() {
         return Err(ResponseError::from_msg(
-            MeilisearchHttpError::DocumentNotFound(id.into_inner()).to_string(),
-            Code::NotFoundSimilarId,
+            "Missing field `id`: one of `id`, `positiveIds` or `positiveVectors` must be specified"
+                .to_string(),
+            Code::InvalidSimilarId,
         ));
+    }
+
+    let positive = id.map(|id| (".id".to_string(), id)).into_iter().chain(
+        positive_ids.into_iter().enumerate().map(|(i, id)| (format!(".positiveIds[{i}]"), id)),
+    );
+    let positive = similar_internal_ids(index, &rtxn, positive)?;
+    let negative =
+        negative_ids.into_iter().enumerate().map(|(i, id)| (format!(".negativeIds[{i}]"), id));
+    let negative = similar_internal_ids(index, &rtxn, negative)?;
+
+    let (similar_offset, similar_limit) = match diversity {
+        // retrieve more candidates than requested, so that varied documents can be picked among them
+        Some(_) => (0, Diversify::window(offset, limit)),
+        None => (offset, limit),
     };
 
     let mut similar = milli::Similar::new(
-        internal_id,
-        offset,
-        limit,
+        positive,
+        similar_offset,
+        similar_limit,
         index,
         &rtxn,
-        embedder_name,
+        embedder_name.clone(),
         embedder,
         quantized,
     );
@@ -1633,6 +1838,11 @@ pub fn perform_similar(
         similar.ranking_score_threshold(ranking_score_threshold.0);
     }
 
+    similar
+        .negative(negative)
+        .positive_vectors(positive_vectors)
+        .negative_vectors(negative_vectors);
+
     let milli::SearchResult {
         documents_ids,
         matching_words: _,
@@ -1647,6 +1857,12 @@ pub fn perform_similar(
         err => err.into(),
     })?;
 
+    let (documents_ids, document_scores) = match diversity {
+        Some(diversity) => Diversify::new(index, &rtxn, &embedder_name, diversity.lambda())
+            .execute(documents_ids, document_scores, offset, limit)?,
+        None => (documents_ids, document_scores),
+    };
+
     let format = AttributesFormat {
         attributes_to_retrieve,
         retrieve_vectors,
@@ -0,0 +1,352 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn alias_points_to_an_index() {
+    let server = Server::new().await;
+    let (task, _code) =
+        server.index("movies_v1").add_documents(json!([{ "id": 1, "title": "Carol" }]), None).await;
+    server.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = server.put_alias("movies", json!({ "indexes": ["movies_v1"] })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "uid": "movies",
+      "indexes": [
+        "movies_v1"
+      ]
+    }
+    "###);
+
+    // the task is attached to the index behind the alias
+    let alias = server.index("movies");
+    let (task, code) = alias.add_documents(json!([{ "id": 2, "title": "Shazam" }]), None).await;
+    snapshot!(code, @"202 Accepted");
+    snapshot!(task["indexUid"], @r###""movies_v1""###);
+    server.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = alias.search_post(json!({})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1,
+        "title": "Carol"
+      },
+      {
+        "id": 2,
+        "title": "Shazam"
+      }
+    ]
+    "###);
+
+    let (response, code) = alias.get().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["uid"], @r###""movies_v1""###);
+
+    // repointing the alias
+    let (task, _code) = server
+        .index("movies_v2")
+        .add_documents(json!([{ "id": 3, "title": "Escape Room" }]), None)
+        .await;
+    server.wait_task(task.uid()).await.succeeded();
+
+    let (_response, code) = server.put_alias("movies", json!({ "indexes": ["movies_v2"] })).await;
+    snapshot!(code, @"200 OK");
+
+    let (response, code) = alias.search_post(json!({})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 3,
+        "title": "Escape Room"
+      }
+    ]
+    "###);
+
+    let (response, code) = server.list_aliases().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": [
+        {
+          "uid": "movies",
+          "indexes": [
+            "movies_v2"
+          ]
+        }
+      ]
+    }
+    "###);
+
+    let (_response, code) = server.delete_alias("movies").await;
+    snapshot!(code, @"204 No Content");
+    let (response, code) = alias.search_post(json!({})).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""index_not_found""###);
+}
+
+#[actix_rt::test]
+async fn alias_points_to_several_indexes() {
+    let server = Server::new().await;
+    let (task, _code) =
+        server.index("movies").add_documents(json!([{ "id": 1, "title": "Carol" }]), None).await;
+    server.wait_task(task.uid()).await.succeeded();
+    let (task, _code) =
+        server.index("series").add_documents(json!([{ "id": 1, "title": "Dark" }]), None).await;
+    server.wait_task(task.uid()).await.succeeded();
+
+    let (_response, code) =
+        server.put_alias("catalog", json!({ "indexes": ["movies", "series"] })).await;
+    snapshot!(code, @"200 OK");
+
+    // the results of both indexes are merged
+    let alias = server.index("catalog");
+    let (response, code) = alias.search_post(json!({ "attributesToRetrieve": ["title"] })).await;
+    snapshot!(code, @"200 OK");
+    let mut titles: Vec<_> =
+        response["hits"].as_array().unwrap().iter().map(|hit| hit["title"].clone()).collect();
+    titles.sort_by_key(|title| title.to_string());
+    snapshot!(json_string!(titles), @r###"
+    [
+      "Carol",
+      "Dark"
+    ]
+    "###);
+    snapshot!(response["estimatedTotalHits"], @"2");
+
+    let (response, code) = server
+        .multi_search(json!({ "federation": {}, "queries": [{ "indexUid": "catalog" }] }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["estimatedTotalHits"], @"2");
+
+    // but the alias can't be used to write
+    let (response, code) = alias.add_documents(json!([{ "id": 2, "title": "Shazam" }]), None).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Index alias `catalog` points to several indexes and can only be used to search in all of them at once.",
+      "code": "index_alias_read_only",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#index_alias_read_only"
+    }
+    "###);
+
+    // deleting an index removes it from the aliases
+    let (task, _code) = server.delete_index("series").await;
+    server.wait_task(task.uid()).await.succeeded();
+    let (response, code) = server.get_alias("catalog").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["indexes"]), @r###"
+    [
+      "movies"
+    ]
+    "###);
+
+    let (task, _code) = server.delete_index("movies").await;
+    server.wait_task(task.uid()).await.succeeded();
+    let (response, code) = server.get_alias("catalog").await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""index_alias_not_found""###);
+}
+
+#[actix_rt::test]
+async fn error_alias() {
+    let server = Server::new().await;
+    let (task, _code) = server.index("movies").create(None).await;
+    server.wait_task(task.uid()).await.succeeded();
+    let (_response, code) = server.put_alias("films", json!({ "indexes": ["movies"] })).await;
+    snapshot!(code, @"200 OK");
+
+    let (response, code) = server.put_alias("movies", json!({ "indexes": ["movies"] })).await;
+    snapshot!(code, @"409 Conflict");
+    snapshot!(response["code"], @r###""index_already_exists""###);
+
+    let (response, code) = server.put_alias("catalog", json!({ "indexes": ["series"] })).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""index_not_found""###);
+
+    let (response, code) = server.put_alias("catalog", json!({ "indexes": ["films"] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Index alias `catalog` cannot point to `films` because it is an index alias itself.",
+      "code": "invalid_index_alias_indexes",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_index_alias_indexes"
+    }
+    "###);
+
+    let (response, code) = server.put_alias("catalog", json!({ "indexes": [] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_index_alias_indexes""###);
+
+    let (response, code) = server.put_alias("catalog", json!({})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""missing_index_alias_indexes""###);
+
+    let (response, code) = server.get_alias("catalog").await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""index_alias_not_found""###);
+
+    let (response, code) = server.delete_alias("catalog").await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""index_alias_not_found""###);
+
+    // deleting an alias as an index doesn't delete the index it points to
+    let (response, code) = server.delete_index("films").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`films` is an index alias, it must be deleted with the `/aliases` route, the indexes it points to must be deleted by their uid.",
+      "code": "index_alias_not_deletable",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#index_alias_not_deletable"
+    }
+    "###);
+    let (response, code) = server.index("films").get().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["uid"], @r###""movies""###);
+
+    // an index can't be created with the name of an alias
+    let (response, code) = server.create_index(json!({ "uid": "films" })).await;
+    snapshot!(code, @"409 Conflict");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`films` is already used by an index alias.",
+      "code": "index_already_exists",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#index_already_exists"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn alias_in_api_key_indexes() {
+    let mut server = Server::new_auth().await;
+    server.use_admin_key("MASTER_KEY").await;
+    let index = server.index("movies_v1");
+    let (task, _code) = index.add_documents(json!([{ "id": 1, "title": "Carol" }]), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+    drop(index);
+    let (_response, code) = server.put_alias("movies", json!({ "indexes": ["movies_v1"] })).await;
+    snapshot!(code, @"200 OK");
+
+    // a key on the alias doesn't give access to the index it points to
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server
+        .add_api_key(json!({ "indexes": ["movies"], "actions": ["search"], "expiresAt": null }))
+        .await;
+    assert_eq!(code, 201, "{response}");
+    let key = response["key"].as_str().unwrap();
+    server.use_api_key(key);
+
+    let (response, code) = server.index("movies").search_post(json!({})).await;
+    snapshot!(code, @"403 Forbidden");
+    snapshot!(response["code"], @r###""invalid_api_key""###);
+
+    let (response, code) = server
+        .multi_search(json!({ "federation": {}, "queries": [{ "indexUid": "movies" }] }))
+        .await;
+    snapshot!(code, @"403 Forbidden");
+    snapshot!(response["code"], @r###""invalid_api_key""###);
+
+    // a key on the index gives access to it through the alias
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server
+        .add_api_key(json!({ "indexes": ["movies_v1"], "actions": ["search"], "expiresAt": null }))
+        .await;
+    assert_eq!(code, 201, "{response}");
+    let key = response["key"].as_str().unwrap();
+    server.use_api_key(key);
+
+    let (response, code) = server.index("movies").search_post(json!({})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["estimatedTotalHits"], @"1");
+
+    let (response, code) = server.get_alias("movies").await;
+    snapshot!(code, @"403 Forbidden");
+    snapshot!(response["code"], @r###""invalid_api_key""###);
+}
+
+#[actix_rt::test]
+async fn alias_applies_the_tenant_token_search_rules_of_each_index() {
+    use jsonwebtoken::{encode, EncodingKey, Header};
+
+    let mut server = Server::new_auth().await;
+    server.use_admin_key("MASTER_KEY").await;
+    for (uid, documents) in [
+        (
+            "movies",
+            json!([{ "id": 1, "title": "Carol", "public": true }, { "id": 2, "title": "Shazam", "public": false }]),
+        ),
+        (
+            "series",
+            json!([{ "id": 1, "title": "Dark", "public": true }, { "id": 2, "title": "Lost", "public": false }]),
+        ),
+    ] {
+        let index = server.index(uid);
+        let (task, _code) = index.update_settings_filterable_attributes(json!(["public"])).await;
+        index.wait_task(task.uid()).await.succeeded();
+        let (task, _code) = index.add_documents(documents, None).await;
+        index.wait_task(task.uid()).await.succeeded();
+    }
+    let (_response, code) = server.put_alias("films", json!({ "indexes": ["movies"] })).await;
+    snapshot!(code, @"200 OK");
+    let (_response, code) =
+        server.put_alias("catalog", json!({ "indexes": ["movies", "series"] })).await;
+    snapshot!(code, @"200 OK");
+
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server
+        .add_api_key(json!({ "indexes": ["*"], "actions": ["search"], "expiresAt": null }))
+        .await;
+    assert_eq!(code, 201, "{response}");
+    let key = response["key"].as_str().unwrap();
+    let uid = response["uid"].as_str().unwrap();
+    let claims = serde_json::json!({
+        "apiKeyUid": uid,
+        "searchRules": { "movies": { "filter": "public = true" }, "series": { "filter": "public = true" } },
+    });
+    let tenant_token =
+        encode(&Header::default(), &claims, &EncodingKey::from_secret(key.as_bytes())).unwrap();
+    server.use_api_key(&tenant_token);
+
+    let (response, code) =
+        server.index("films").search_post(json!({ "attributesToRetrieve": ["title"] })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "title": "Carol"
+      }
+    ]
+    "###);
+
+    let (response, code) =
+        server.index("catalog").search_post(json!({ "attributesToRetrieve": ["title"] })).await;
+    snapshot!(code, @"200 OK");
+    let mut titles: Vec<_> =
+        response["hits"].as_array().unwrap().iter().map(|hit| hit["title"].clone()).collect();
+    titles.sort_by_key(|title| title.to_string());
+    snapshot!(json_string!(titles), @r###"
+    [
+      "Carol",
+      "Dark"
+    ]
+    "###);
+
+    let (response, code) = server
+        .multi_search(json!({
+            "federation": {},
+            "queries": [{ "indexUid": "catalog", "attributesToRetrieve": ["title"] }],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["estimatedTotalHits"], @"2");
+}
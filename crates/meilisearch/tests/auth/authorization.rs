@@ -30,9 +30,14 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("PATCH",   "/indexes/products/") =>                               hashset!{"indexes.update", "indexes.*", "*"},
             ("GET",     "/indexes/products/") =>                               hashset!{"indexes.get", "indexes.*", "*"},
             ("DELETE",  "/indexes/products/") =>                               hashset!{"indexes.delete", "indexes.*", "*"},
+            ("POST",    "/indexes/products/clone") =>                          hashset!{"indexes.create", "indexes.*", "*"},
             ("POST",    "/indexes") =>                                         hashset!{"indexes.create", "indexes.*", "*"},
             ("GET",     "/indexes") =>                                         hashset!{"indexes.get", "indexes.*", "*"},
             ("POST",    "/swap-indexes") =>                                    hashset!{"indexes.swap", "indexes.*", "*"},
+            ("GET",     "/aliases") =>                                         hashset!{"aliases.get", "*"},
+            ("GET",     "/aliases/products") =>                                hashset!{"aliases.get", "*"},
+            ("PUT",     "/aliases/products") =>                                hashset!{"aliases.update", "*"},
+            ("DELETE",  "/aliases/products") =>                                hashset!{"aliases.delete", "*"},
             ("GET",     "/indexes/products/settings") =>                       hashset!{"settings.get", "settings.*", "*"},
             ("GET",     "/indexes/products/settings/displayed-attributes") =>  hashset!{"settings.get", "settings.*", "*"},
             ("GET",     "/indexes/products/settings/distinct-attribute") =>    hashset!{"settings.get", "settings.*", "*"},
@@ -44,6 +49,7 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("GET",     "/indexes/products/settings/synonyms") =>              hashset!{"settings.get", "settings.*", "*"},
             ("DELETE",  "/indexes/products/settings") =>                       hashset!{"settings.update", "settings.*", "*"},
             ("PATCH",   "/indexes/products/settings") =>                       hashset!{"settings.update", "settings.*", "*"},
+            ("POST",    "/indexes/products/settings/dry-run") =>               hashset!{"settings.update", "settings.*", "*"},
             ("PATCH",   "/indexes/products/settings/typo-tolerance") =>        hashset!{"settings.update", "settings.*", "*"},
             ("PUT",     "/indexes/products/settings/displayed-attributes") =>  hashset!{"settings.update", "settings.*", "*"},
             ("PUT",     "/indexes/products/settings/distinct-attribute") =>    hashset!{"settings.update", "settings.*", "*"},
@@ -70,6 +76,9 @@ pub static AUTHORIZATIONS: Lazy<HashMap<(&'static str, &'static str), HashSet<&'
             ("PATCH",   "/experimental-features") =>                           hashset!{"experimental.update", "*"},
             ("GET",   "/network") =>                                           hashset!{"network.get", "*"},
             ("PATCH",   "/network") =>                                         hashset!{"network.update", "*"},
+            ("POST",    "/indexes/products/chat") =>                           hashset!{"chat", "*"},
+            ("GET",     "/chat/settings") =>                                   hashset!{"chatSettings.get", "*"},
+            ("PATCH",   "/chat/settings") =>                                   hashset!{"chatSettings.update", "*"},
         };
 
         authorizations
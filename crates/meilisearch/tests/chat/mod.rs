@@ -0,0 +1,407 @@
+use meili_snap::{json_string, snapshot};
+use wiremock::matchers::{header, method, path};
+use wiremock::{Mock, MockServer, Request, ResponseTemplate};
+
+use crate::common::index::Index;
+use crate::common::{Server, Value};
+use crate::json;
+
+/// Starts an OpenAI-compatible provider answering from the first document it is given.
+async fn create_openai_mock() -> MockServer {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/v1/chat/completions"))
+        .and(header("authorization", "Bearer sk-test-key"))
+        .respond_with(|req: &Request| {
+            let body: Value = req.body_json().unwrap();
+            let question = body["messages"][1]["content"].as_str().unwrap_or_default();
+            if body["model"] != json!("tiny-model")
+                || !question.contains("[1] kefir is a dog")
+                || !question.ends_with("Question: kefir")
+            {
+                return ResponseTemplate::new(400).set_body_json(json!({ "unexpected": body }));
+            }
+
+            if body["stream"] == json!(true) {
+                ResponseTemplate::new(200).set_body_raw(
+                    concat!(
+                        "data: {\"choices\":[{\"delta\":{\"role\":\"assistant\"}}]}\n\n",
+                        "data: {\"choices\":[{\"delta\":{\"content\":\"Kefir is \"}}]}\n\n",
+                        "data: {\"choices\":[{\"delta\":{\"content\":\"a dog [1].\"}}]}\n\n",
+                        "data: [DONE]\n\n",
+                    ),
+                    "text/event-stream",
+                )
+            } else {
+                ResponseTemplate::new(200).set_body_json(json!({
+                    "choices": [{ "message": { "role": "assistant", "content": "Kefir is a dog [1]." } }]
+                }))
+            }
+        })
+        .mount(&mock_server)
+        .await;
+
+    mock_server
+}
+
+async fn create_index_with_documents(server: &Server) -> Index<'_> {
+    let index = server.index("doggos");
+    let (response, code) = index
+        .add_documents(
+            json!([
+                { "id": 1, "name": "kefir", "breed": "dog" },
+                { "id": 2, "name": "intel", "breed": "cat" },
+            ]),
+            None,
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = index
+        .update_settings(json!({
+            "chat": {
+                "description": "The pets of the team",
+                "documentTemplate": "{{doc.name}} is a {{doc.breed}}",
+            }
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    index
+}
+
+#[actix_rt::test]
+async fn error_chat_not_enabled() {
+    let server = Server::new().await;
+
+    let (response, code) = server.get_chat_settings().await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Using the /chat/settings route requires enabling the `chat completions` experimental feature. See https://github.com/orgs/meilisearch/discussions/835",
+      "code": "feature_not_enabled",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#feature_not_enabled"
+    }
+    "###);
+
+    let index = server.index("doggos");
+    let (response, code) = index.chat(json!({ "q": "kefir" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Using the /indexes/{indexUid}/chat route requires enabling the `chat completions` experimental feature. See https://github.com/orgs/meilisearch/discussions/835",
+      "code": "feature_not_enabled",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#feature_not_enabled"
+    }
+    "###);
+
+    let (response, code) =
+        index.update_settings(json!({ "chat": { "description": "pets" } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "setting `chat` in the index settings requires enabling the `chat completions` experimental feature. See https://github.com/orgs/meilisearch/discussions/835",
+      "code": "feature_not_enabled",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#feature_not_enabled"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn chat_settings() {
+    let server = Server::new().await;
+    let (_, code) = server.set_features(json!({ "chatCompletions": true })).await;
+    snapshot!(code, @"200 OK");
+
+    let (response, code) = server.get_chat_settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "source": "openAi",
+      "url": null,
+      "apiKey": null,
+      "model": null,
+      "headers": {},
+      "request": null,
+      "response": null,
+      "systemPrompt": null
+    }
+    "###);
+
+    let (response, code) = server
+        .set_chat_settings(json!({
+            "url": "http://localhost:11434/v1/chat/completions",
+            "apiKey": "sk-a-very-secret-key",
+            "headers": { "x-team": "search" },
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "source": "openAi",
+      "url": "http://localhost:11434/v1/chat/completions",
+      "apiKey": "sk-XXXXX...",
+      "model": null,
+      "headers": {
+        "x-team": "search"
+      },
+      "request": null,
+      "response": null,
+      "systemPrompt": null
+    }
+    "###);
+
+    // fields that are not sent are kept, `null` resets them
+    let (response, code) = server.set_chat_settings(json!({ "headers": null })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["headers"]), @"{}");
+    snapshot!(json_string!(response["apiKey"]), @r###""sk-XXXXX...""###);
+
+    let (response, code) = server.set_chat_settings(json!({ "source": "rest" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`.request`: a `request` template is required when using the `rest` source",
+      "code": "invalid_chat_completion_request",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_chat_completion_request"
+    }
+    "###);
+
+    let (response, code) = server.set_chat_settings(json!({ "url": "not a url" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`.url`: `not a url` is not a valid URL: relative URL without a base",
+      "code": "invalid_chat_completion_url",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_chat_completion_url"
+    }
+    "###);
+
+    let (response, code) =
+        server.set_chat_settings(json!({ "response": { "text": "{{content}}" } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`.response` is only available with the `rest` source",
+      "code": "invalid_chat_completion_source",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_chat_completion_source"
+    }
+    "###);
+
+    // the failed updates were not applied
+    let (response, code) = server.get_chat_settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["source"]), @r###""openAi""###);
+    snapshot!(json_string!(response["url"]), @r###""http://localhost:11434/v1/chat/completions""###);
+}
+
+#[actix_rt::test]
+async fn index_chat_settings() {
+    let server = Server::new().await;
+    let (_, code) = server.set_features(json!({ "chatCompletions": true })).await;
+    snapshot!(code, @"200 OK");
+    let index = create_index_with_documents(&server).await;
+
+    let (response, code) =
+        index.update_settings(json!({ "chat": { "searchParameters": { "limit": 1 } } })).await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = index.settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["chat"]), @r###"
+    {
+      "description": "The pets of the team",
+      "documentTemplate": "{{doc.name}} is a {{doc.breed}}",
+      "documentTemplateMaxBytes": 400,
+      "searchParameters": {
+        "limit": 1,
+        "hybrid": null,
+        "rankingScoreThreshold": null
+      }
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(
+            json!({ "chat": { "searchParameters": { "rankingScoreThreshold": 1.5 } } }),
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await.failed();
+    snapshot!(json_string!(task["error"]["code"]), @r###""invalid_settings_chat""###);
+
+    let (response, code) =
+        index.update_settings(json!({ "chat": { "documentTemplate": "{{doc.name" } })).await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await.failed();
+    snapshot!(json_string!(task["error"]["code"]), @r###""invalid_settings_chat""###);
+
+    // resetting the chat settings restores the defaults
+    let (response, code) = index.update_settings(json!({ "chat": null })).await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+    let (response, _) = index.settings().await;
+    snapshot!(json_string!(response["chat"]["description"]), @r###""""###);
+    snapshot!(json_string!(response["chat"]["searchParameters"]["limit"]), @"5");
+}
+
+#[actix_rt::test]
+async fn chat_openai() {
+    let server = Server::new().await;
+    let (_, code) = server.set_features(json!({ "chatCompletions": true })).await;
+    snapshot!(code, @"200 OK");
+    let mock_server = create_openai_mock().await;
+    let (_, code) = server
+        .set_chat_settings(json!({
+            "url": format!("{}/v1/chat/completions", mock_server.uri()),
+            "apiKey": "sk-test-key",
+            "model": "tiny-model",
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    let index = create_index_with_documents(&server).await;
+
+    let (response, code) = index.chat(json!({ "q": "kefir", "stream": false })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".processingTimeMs" => "[duration]" }), @r###"
+    {
+      "answer": "Kefir is a dog [1].",
+      "sources": [
+        "1"
+      ],
+      "processingTimeMs": "[duration]"
+    }
+    "###);
+
+    let (response, code) = index.chat_stream(json!({ "q": "kefir" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response, @r###"
+    event: sources
+    data: {"sources":["1"]}
+
+    event: delta
+    data: {"content":"Kefir is "}
+
+    event: delta
+    data: {"content":"a dog [1]."}
+
+    event: done
+    data: {}
+    "###);
+
+    // the filter is checked like the filter of a search
+    let (response, code) =
+        index.chat(json!({ "q": "kefir", "filter": "breed = cat", "stream": false })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response["code"]), @r###""invalid_search_filter""###);
+}
+
+#[actix_rt::test]
+async fn chat_provider_error() {
+    let server = Server::new().await;
+    let (_, code) = server.set_features(json!({ "chatCompletions": true })).await;
+    snapshot!(code, @"200 OK");
+    let mock_server = create_openai_mock().await;
+    let (_, code) = server
+        .set_chat_settings(json!({
+            "url": format!("{}/v1/chat/completions", mock_server.uri()),
+            "apiKey": "sk-test-key",
+            "model": "another-model",
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    let index = create_index_with_documents(&server).await;
+
+    let (response, code) = index.chat(json!({ "q": "kefir", "stream": false })).await;
+    snapshot!(code, @"502 Bad Gateway");
+    snapshot!(json_string!(response["code"]), @r###""chat_completion_failed""###);
+
+    let (response, code) = index.chat_stream(json!({ "q": "kefir" })).await;
+    snapshot!(code, @"502 Bad Gateway");
+    assert!(response.contains("chat_completion_failed"), "{response}");
+
+    let (response, code) = index.chat(json!({ "stream": false })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `q`",
+      "code": "invalid_chat_q",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_chat_q"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn chat_rest() {
+    let server = Server::new().await;
+    let (_, code) = server.set_features(json!({ "chatCompletions": true })).await;
+    snapshot!(code, @"200 OK");
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/generate"))
+        .respond_with(|req: &Request| {
+            let body: Value = req.body_json().unwrap();
+            let system = body["input"][0]["content"].as_str().unwrap_or_default();
+            let question = body["input"][1]["content"].as_str().unwrap_or_default();
+            if body["temperature"] != json!(0)
+                || system != "Answer in one sentence.\n\nThe documents come from an index described as follows: The pets of the team"
+                || !question.contains("[1] intel is a cat")
+            {
+                return ResponseTemplate::new(400).set_body_json(json!({ "unexpected": body }));
+            }
+            ResponseTemplate::new(200).set_body_json(json!({ "output": { "text": "Intel is a cat [1]." } }))
+        })
+        .mount(&mock_server)
+        .await;
+
+    let (response, code) = server
+        .set_chat_settings(json!({
+            "source": "rest",
+            "url": format!("{}/generate", mock_server.uri()),
+            "request": { "input": "{{messages}}", "temperature": 0 },
+            "response": { "output": { "text": "{{content}}" } },
+            "systemPrompt": "Answer in one sentence.",
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["source"]), @r###""rest""###);
+    let index = create_index_with_documents(&server).await;
+
+    let (response, code) = index.chat(json!({ "q": "intel", "stream": false })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".processingTimeMs" => "[duration]" }), @r###"
+    {
+      "answer": "Intel is a cat [1].",
+      "sources": [
+        "2"
+      ],
+      "processingTimeMs": "[duration]"
+    }
+    "###);
+
+    // the answer of the `rest` source is streamed in a single delta
+    let (response, code) = index.chat_stream(json!({ "q": "intel" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response, @r###"
+    event: sources
+    data: {"sources":["2"]}
+
+    event: delta
+    data: {"content":"Intel is a cat [1]."}
+
+    event: done
+    data: {}
+    "###);
+}
@@ -88,6 +88,11 @@ impl<'a> Index<'a, Owned> {
         self.service.delete(url).await
     }
 
+    pub async fn clone_index(&self, body: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/clone", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, body, self.encoder).await
+    }
+
     pub async fn add_documents(
         &self,
         documents: Value,
@@ -155,6 +160,17 @@ impl<'a> Index<'a, Owned> {
         self.service.post_encoded(url, body, self.encoder).await
     }
 
+    pub async fn bulk_documents(&self, payload: &str) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/documents/bulk", urlencode(self.uid.as_ref()));
+        self.service.post_str(url, payload, vec![("content-type", "application/x-ndjson")]).await
+    }
+
+    pub async fn regenerate_embeddings(&self, body: Value) -> (Value, StatusCode) {
+        let url =
+            format!("/indexes/{}/documents/regenerate-embeddings", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, body, self.encoder).await
+    }
+
     pub async fn clear_all_documents(&self) -> (Value, StatusCode) {
         let url = format!("/indexes/{}/documents", urlencode(self.uid.as_ref()));
         self.service.delete(url).await
@@ -176,6 +192,11 @@ impl<'a> Index<'a, Owned> {
         self._update_settings(settings).await
     }
 
+    pub async fn settings_dry_run(&self, settings: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/settings/dry-run", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, settings, self.encoder).await
+    }
+
     pub async fn update_settings_displayed_attributes(
         &self,
         settings: Value,
@@ -546,6 +567,17 @@ impl<State> Index<'_, State> {
         self.service.post_encoded(url, query, self.encoder).await
     }
 
+    pub async fn chat(&self, query: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/chat", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, query, self.encoder).await
+    }
+
+    /// Asks a streamed answer, returning the server-sent events as text.
+    pub async fn chat_stream(&self, query: Value) -> (String, StatusCode) {
+        let url = format!("/indexes/{}/chat", urlencode(self.uid.as_ref()));
+        self.service.post_raw(url, query).await
+    }
+
     pub async fn get_distinct_attribute(&self) -> (Value, StatusCode) {
         let url =
             format!("/indexes/{}/settings/{}", urlencode(self.uid.as_ref()), "distinct-attribute");
@@ -181,6 +181,10 @@ impl Server<Owned> {
         self.service.patch("/network", value).await
     }
 
+    pub async fn set_chat_settings(&self, value: Value) -> (Value, StatusCode) {
+        self.service.patch("/chat/settings", value).await
+    }
+
     pub async fn get_metrics(&self) -> (Value, StatusCode) {
         self.service.get("/metrics").await
     }
@@ -388,6 +392,26 @@ impl<State> Server<State> {
         self.service.post("/swap-indexes", value).await
     }
 
+    pub async fn index_transaction(&self, value: Value) -> (Value, StatusCode) {
+        self.service.post("/transactions", value).await
+    }
+
+    pub async fn list_aliases(&self) -> (Value, StatusCode) {
+        self.service.get("/aliases").await
+    }
+
+    pub async fn get_alias(&self, uid: impl AsRef<str>) -> (Value, StatusCode) {
+        self.service.get(format!("/aliases/{}", urlencoding::encode(uid.as_ref()))).await
+    }
+
+    pub async fn put_alias(&self, uid: impl AsRef<str>, value: Value) -> (Value, StatusCode) {
+        self.service.put(format!("/aliases/{}", urlencoding::encode(uid.as_ref())), value).await
+    }
+
+    pub async fn delete_alias(&self, uid: impl AsRef<str>) -> (Value, StatusCode) {
+        self.service.delete(format!("/aliases/{}", urlencoding::encode(uid.as_ref()))).await
+    }
+
     pub async fn cancel_tasks(&self, value: &str) -> (Value, StatusCode) {
         self.service.post(format!("/tasks/cancel?{}", value), json!(null)).await
     }
@@ -441,6 +465,10 @@ impl<State> Server<State> {
     pub async fn get_network(&self) -> (Value, StatusCode) {
         self.service.get("/network").await
     }
+
+    pub async fn get_chat_settings(&self) -> (Value, StatusCode) {
+        self.service.get("/chat/settings").await
+    }
 }
 
 pub fn default_settings(dir: impl AsRef<Path>) -> Opt {
@@ -43,6 +43,13 @@ impl Service {
     }
 
     /// Send a test post request from a text body.
+    /// Sends a JSON body and returns the raw body of the response, for routes that do not answer with JSON.
+    pub async fn post_raw(&self, url: impl AsRef<str>, body: Value) -> (String, StatusCode) {
+        let mut req = test::TestRequest::post().uri(url.as_ref());
+        req = self.encode(req, body, Encoder::Plain);
+        self.request_raw(req).await
+    }
+
     pub async fn post_str(
         &self,
         url: impl AsRef<str>,
@@ -163,6 +170,20 @@ impl Service {
         (response, status_code)
     }
 
+    pub async fn request_raw(&self, mut req: test::TestRequest) -> (String, StatusCode) {
+        let app = self.init_web_app().await;
+
+        if let Some(api_key) = &self.api_key {
+            req = req.insert_header(("Authorization", ["Bearer ", api_key].concat()));
+        }
+        let req = req.to_request();
+        let res = test::call_service(&app, req).await;
+        let status_code = res.status();
+
+        let body = test::read_body(res).await;
+        (String::from_utf8_lossy(&body).into_owned(), status_code)
+    }
+
     fn encode(&self, req: TestRequest, body: Value, encoder: Encoder) -> TestRequest {
         let bytes = serde_json::to_string(&body).expect("Failed to serialize test data to json");
         let encoded_body = encoder.encode(bytes);
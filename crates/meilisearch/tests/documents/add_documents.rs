@@ -2871,6 +2871,47 @@ async fn error_primary_key_inference() {
     "###);
 }
 
+#[actix_rt::test]
+async fn add_documents_with_generated_ids() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, code) = index.update_settings(json!({ "generateDocumentIds": true })).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    // there is no primary key candidate, the default primary key is used
+    let documents = json!([
+        { "level": "info", "message": "started" },
+        { "id": "custom", "level": "warn", "message": "slow" },
+        { "level": "error", "message": "stopped" },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.succeeded();
+    snapshot!(task["details"]["indexedDocuments"], @"3");
+    let generated_ids = task["details"]["generatedDocumentIds"].as_array().unwrap().clone();
+    assert_eq!(generated_ids.len(), 2, "{task}");
+    // UUIDv7s are ordered by generation time
+    assert!(generated_ids[0].as_str() < generated_ids[1].as_str());
+
+    let (response, code) = index.get().await;
+    assert_eq!(code, 200, "{response}");
+    snapshot!(response["primaryKey"], @r###""id""###);
+
+    let url = format!("/indexes/{}/documents/{}", index.uid, generated_ids[1].as_str().unwrap());
+    let (document, code) = index.service.get(url).await;
+    assert_eq!(code, 200, "{document}");
+    assert_eq!(document["id"], generated_ids[1]);
+    snapshot!(json_string!(document, { ".id" => "[uuid]" }), @r###"
+    {
+      "id": "[uuid]",
+      "level": "error",
+      "message": "stopped"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn add_documents_with_primary_key_twice() {
     let server = Server::new_shared();
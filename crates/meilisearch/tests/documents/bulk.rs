@@ -0,0 +1,168 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::{GetAllDocumentsOptions, Server};
+use crate::json;
+
+#[actix_rt::test]
+async fn bulk_operations_are_applied_in_a_single_task() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, code) = index.update_settings(json!({ "filterableAttributes": ["genre"] })).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 1, "title": "kefir", "genre": "comedy" },
+        { "id": 2, "title": "intel", "genre": "comedy" },
+        { "id": 3, "title": "scream", "genre": "horror" },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let payload = r#"{ "op": "deleteByFilter", "filter": "genre = horror" }
+{ "op": "upsert", "document": { "id": 4, "title": "doggo", "genre": "horror" } }
+{ "op": "replace", "document": { "id": 1, "title": "kefirou" } }
+{ "op": "delete", "id": 2 }"#;
+    let (task, code) = index.bulk_documents(payload).await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.succeeded();
+    snapshot!(task["type"], @r###""documentBulk""###);
+    snapshot!(json_string!(task["details"]), @r###"
+    {
+      "receivedOperations": 4,
+      "indexedDocuments": 2,
+      "deletedDocuments": 2
+    }
+    "###);
+
+    // the filter is evaluated before the documents are written
+    let (documents, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200);
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "title": "kefirou"
+      },
+      {
+        "id": 4,
+        "title": "doggo",
+        "genre": "horror"
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn bulk_operations_are_all_or_nothing() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, code) = index.add_documents(json!([{ "id": 1, "title": "kefir" }]), None).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let payload = r#"{ "op": "delete", "id": 1 }
+{ "op": "upsert", "document": { "id": 2, "title": "intel" } }
+{ "op": "upsert", "document": { "title": "doggo" } }"#;
+    let (task, code) = index.bulk_documents(payload).await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.failed();
+    snapshot!(task["error"]["code"], @r###""missing_document_id""###);
+    snapshot!(json_string!(task["details"]), @r###"
+    {
+      "receivedOperations": 3,
+      "indexedDocuments": 0,
+      "deletedDocuments": 0
+    }
+    "###);
+
+    let (documents, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200);
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "title": "kefir"
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn error_bulk_operations() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.bulk_documents(r#"{ "op": "rename", "id": 1 }"#).await;
+    assert_eq!(code, 400, "{response}");
+    snapshot!(response["code"], @r###""malformed_payload""###);
+
+    let (response, code) =
+        index.bulk_documents(r#"{ "op": "deleteByFilter", "filter": "genre = " }"#).await;
+    assert_eq!(code, 400, "{response}");
+    snapshot!(response["code"], @r###""invalid_document_filter""###);
+    // the filter would be evaluated on the documents as they were before the upsert
+    let payload = r#"{ "op": "upsert", "document": { "id": 1, "genre": "horror" } }
+{ "op": "deleteByFilter", "filter": "genre = horror" }"#;
+    let (response, code) = index.bulk_documents(payload).await;
+    assert_eq!(code, 400, "{response}");
+    snapshot!(response, @r###"
+    {
+      "message": "A `deleteByFilter` operation cannot follow an `upsert` or `replace` operation, the filter being evaluated on the documents as they were before the operations.\n  - Hint: put the `deleteByFilter` operations first, or send them in another request.",
+      "code": "malformed_payload",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#malformed_payload"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn bulk_operations_fail_on_version_conflicts() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, code) = index.add_documents(json!([{ "id": 1, "title": "kefir" }]), None).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let payload = r#"{ "op": "upsert", "document": { "id": 2, "title": "intel" } }
+{ "op": "upsert", "document": { "id": 1, "title": "kefirou", "_version": 42 } }"#;
+    let (task, code) = index.bulk_documents(payload).await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.failed();
+    snapshot!(task["error"]["code"], @r###""document_version_conflict""###);
+
+    let (documents, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200);
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "title": "kefir"
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn bulk_operations_report_the_generated_document_ids() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, code) = index.update_settings(json!({ "generateDocumentIds": true })).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (task, code) = index.add_documents(json!([{ "id": "kefir" }]), Some("id")).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let payload = r#"{ "op": "upsert", "document": { "title": "doggo" } }"#;
+    let (task, code) = index.bulk_documents(payload).await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.succeeded();
+    assert_eq!(task["details"]["generatedDocumentIds"].as_array().unwrap().len(), 1, "{task}");
+}
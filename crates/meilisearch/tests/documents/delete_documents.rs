@@ -37,6 +37,52 @@ async fn delete_one_document() {
     assert_eq!(code, 404);
 }
 
+#[actix_rt::test]
+async fn delete_documents_with_composite_primary_key() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+    let (task, code) = index
+        .add_documents(
+            json!([
+                { "tenant_id": "acme", "sku": 1, "content": "foo" },
+                { "tenant_id": "acme", "sku": 2, "content": "bar" },
+                { "tenant_id": "globex", "sku": 1, "content": "baz" },
+            ]),
+            Some("tenant_id,sku"),
+        )
+        .await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let url = format!("/indexes/{}/documents/acme,1", index.uid);
+    let (document, code) = index.service.get(url.clone()).await;
+    assert_eq!(code, 200, "{document}");
+    snapshot!(json_string!(document), @r###"
+    {
+      "tenant_id": "acme",
+      "sku": 1,
+      "content": "foo"
+    }
+    "###);
+
+    let (task, code) = index.service.delete(url.clone()).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+    let (_response, code) = index.service.get(url).await;
+    assert_eq!(code, 404);
+
+    let (task, code) = index.delete_batch_raw(json!([["acme", 2], "globex,1"])).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+    let (response, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200, "{response}");
+    snapshot!(json_string!(response["results"]), @"[]");
+
+    let (response, code) = index.delete_batch_raw(json!([["acme", "not valid"]])).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_document_id""###);
+}
+
 #[actix_rt::test]
 async fn clear_all_documents_unexisting_index() {
     let server = Server::new_shared();
@@ -0,0 +1,87 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn search_ignores_expired_documents() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "title": "kefir", "_expiresAt": 0 },
+        { "id": 2, "title": "kefir le chat", "_expiresAt": 4102444800 },
+        { "id": 3, "title": "kefir le chien" },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    index
+        .search(json!({ "q": "kefir", "attributesToRetrieve": ["id"] }), |response, code| {
+            assert_eq!(code, 200, "{response}");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 2
+              },
+              {
+                "id": 3
+              }
+            ]
+            "###);
+            snapshot!(response["estimatedTotalHits"], @"2");
+        })
+        .await;
+
+    // the expired document is still stored until the scheduler deletes it
+    let (_document, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+}
+
+#[actix_rt::test]
+async fn delete_documents_by_expiration_date() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "title": "kefir", "_expiresAt": 4102444800_u64 },
+        { "id": 2, "title": "kefir le chat", "_expiresAt": 4102444900_u64 },
+        { "id": 3, "title": "kefir le chien" },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    // the expiration date can be filtered on without being filterable
+    let (task, code) =
+        index.delete_document_by_filter(json!({ "filter": "_expiresAt <= 4102444800" })).await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.succeeded();
+    snapshot!(task["details"]["deletedDocuments"], @"1");
+
+    let (response, code) = index.get_document(1, None).await;
+    assert_eq!(code, 404, "{response}");
+    let (response, code) = index.get_document(2, None).await;
+    assert_eq!(code, 200, "{response}");
+}
+
+#[actix_rt::test]
+async fn error_invalid_document_expires_at() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, code) = index
+        .add_documents(json!([{ "id": 1, "title": "kefir", "_expiresAt": "tomorrow" }]), None)
+        .await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.failed();
+    snapshot!(task["error"], @r###"
+    {
+      "message": "The `_expiresAt` of the document `1` must be a Unix timestamp in seconds, found `\"tomorrow\"`.",
+      "code": "invalid_document_expires_at",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_expires_at"
+    }
+    "###);
+}
@@ -0,0 +1,156 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn ingest_pipeline_transforms_documents() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, code) = index
+        .update_settings(json!({
+            "ingestPipeline": [
+                { "type": "rename", "field": "Title", "to": "title" },
+                { "type": "lowercase", "field": "genre" },
+                { "type": "split", "field": "tags", "separator": "," },
+                { "type": "parseDate", "field": "released", "format": "[year]-[month]-[day]" },
+                { "type": "setDefault", "field": "rating", "value": 0 },
+                { "type": "compute", "field": "label", "expression": "doc.title + \" (\" + doc.genre + \")\"" },
+                { "type": "drop", "field": "internal" },
+            ]
+        }))
+        .await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        {
+            "id": 1,
+            "Title": "Kefir",
+            "genre": "CAT",
+            "tags": "fluffy, white,",
+            "released": "2024-01-01",
+            "internal": "secret",
+        },
+        { "id": 2, "Title": "Intel", "genre": "Dog", "rating": 4 },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (document, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    assert_eq!(
+        document,
+        json!({
+            "id": 1,
+            "title": "Kefir",
+            "genre": "cat",
+            "tags": ["fluffy", "white"],
+            "released": 1704067200,
+            "rating": 0,
+            "label": "Kefir (cat)",
+        })
+    );
+
+    let (document, code) = index.get_document(2, None).await;
+    assert_eq!(code, 200);
+    assert_eq!(
+        document,
+        json!({ "id": 2, "title": "Intel", "genre": "dog", "rating": 4, "label": "Intel (dog)" })
+    );
+
+    // partial updates are transformed before being merged with the stored documents
+    let (task, code) = index.update_documents(json!([{ "id": 2, "genre": "CAT" }]), None).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (document, code) = index.get_document(2, None).await;
+    assert_eq!(code, 200);
+    snapshot!(json_string!(document["genre"]), @r###""cat""###);
+    snapshot!(json_string!(document["rating"]), @"0");
+}
+
+#[actix_rt::test]
+async fn error_invalid_ingest_pipeline() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, code) = index
+        .update_settings(json!({ "ingestPipeline": [{ "type": "split", "field": "tags" }] }))
+        .await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.failed();
+    snapshot!(task["error"], @r###"
+    {
+      "message": "`.ingestPipeline[0]`: a `split` processor requires `separator`",
+      "code": "invalid_settings_ingest_pipeline",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_ingest_pipeline"
+    }
+    "###);
+
+    let (task, code) = index
+        .update_settings(
+            json!({ "ingestPipeline": [{ "type": "parseDate", "field": "released" }] }),
+        )
+        .await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (task, code) =
+        index.add_documents(json!([{ "id": 1, "released": "yesterday" }]), None).await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.failed();
+    snapshot!(task["error"], @r###"
+    {
+      "message": "Could not apply the ingest pipeline to the document `1`: `released` is not a valid date: \"yesterday\".",
+      "code": "ingest_pipeline_error",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#ingest_pipeline_error"
+    }
+    "###);
+
+    // the primary key is read once the pipeline is applied
+    let (task, code) = index
+        .update_settings(json!({ "ingestPipeline": [{ "type": "drop", "field": "id" }] }))
+        .await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (task, code) = index.add_documents(json!([{ "id": 1, "title": "kefir" }]), None).await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.failed();
+    snapshot!(task["error"]["code"], @r###""missing_document_id""###);
+}
+
+#[actix_rt::test]
+async fn ingest_pipeline_produces_the_primary_key() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, code) = index
+        .update_settings(
+            json!({ "ingestPipeline": [{ "type": "rename", "field": "ID", "to": "id" }] }),
+        )
+        .await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (task, code) = index
+        .add_documents(json!([{ "ID": 1, "title": "kefir" }, { "ID": 2, "title": "intel" }]), None)
+        .await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.get().await;
+    assert_eq!(code, 200, "{response}");
+    snapshot!(response["primaryKey"], @r###""id""###);
+
+    let (document, code) = index.get_document(2, None).await;
+    assert_eq!(code, 200, "{document}");
+    snapshot!(document["id"], @"2");
+    snapshot!(document["title"], @r###""intel""###);
+    assert!(document.get("ID").is_none(), "{document}");
+}
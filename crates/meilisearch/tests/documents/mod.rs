@@ -1,5 +1,10 @@
 mod add_documents;
+mod bulk;
 mod delete_documents;
 mod errors;
+mod expiration;
 mod get_documents;
+mod ingest_pipeline;
+mod schema;
 mod update_documents;
+mod versions;
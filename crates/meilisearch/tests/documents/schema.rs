@@ -0,0 +1,125 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn documents_not_matching_the_schema_fail_the_task() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, code) = index
+        .update_settings(json!({
+            "documentSchema": {
+                "schema": {
+                    "type": "object",
+                    "required": ["title"],
+                    "properties": {
+                        "title": { "type": "string" },
+                        "price": { "type": "number", "minimum": 0 },
+                    },
+                },
+            }
+        }))
+        .await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 1, "title": "kefir", "price": 3 },
+        { "id": 2, "title": "intel", "price": "4" },
+        { "id": 3, "price": -1 },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.failed();
+    snapshot!(task["error"], @r###"
+    {
+      "message": "Some documents do not match the document schema: document `2` at `.price`: expected number, found a string, document `3` at `.title`: is a required field, document `3` at `.price`: must be greater than or equal to 0.",
+      "code": "document_schema_violation",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#document_schema_violation"
+    }
+    "###);
+
+    let (response, code) = index.get_all_documents_raw("").await;
+    assert_eq!(code, 200, "{response}");
+    snapshot!(response["total"], @"0");
+
+    // partial updates do not need the required fields
+    let (task, code) = index.add_documents(json!([{ "id": 1, "title": "kefir" }]), None).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, code) = index.update_documents(json!([{ "id": 1, "price": 2.5 }]), None).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+}
+
+#[actix_rt::test]
+async fn documents_not_matching_the_schema_are_skipped() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, code) = index
+        .update_settings(json!({
+            "documentSchema": {
+                "schema": { "properties": { "price": { "type": "number" } } },
+                "onInvalid": "skip",
+            }
+        }))
+        .await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let documents = json!([
+        { "id": 1, "price": 3 },
+        { "id": 2, "price": "4" },
+        { "id": 3 },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.succeeded();
+    snapshot!(json_string!(task["details"]), @r###"
+    {
+      "receivedDocuments": 3,
+      "indexedDocuments": 2,
+      "invalidDocuments": [
+        {
+          "documentId": "2",
+          "path": ".price",
+          "message": "expected number, found a string"
+        }
+      ]
+    }
+    "###);
+
+    let (_document, code) = index.get_document(2, None).await;
+    assert_eq!(code, 404);
+}
+
+#[actix_rt::test]
+async fn error_invalid_document_schema() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, code) = index
+        .update_settings(json!({
+            "documentSchema": { "schema": { "properties": { "price": { "type": "float" } } } }
+        }))
+        .await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.failed();
+    snapshot!(task["error"], @r###"
+    {
+      "message": "`.documentSchema`: `.properties.price.type`: unknown type `float`",
+      "code": "invalid_settings_document_schema",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_document_schema"
+    }
+    "###);
+
+    let (response, code) =
+        index.update_settings(json!({ "documentSchema": { "onInvalid": "ignore" } })).await;
+    assert_eq!(code, 400, "{response}");
+    snapshot!(response["code"], @r###""invalid_settings_document_schema""###);
+}
@@ -93,6 +93,89 @@ async fn update_document() {
     "###);
 }
 
+#[actix_rt::test]
+async fn update_document_with_operators() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        {
+            "doc_id": 1,
+            "title": "kefir",
+            "views": 41,
+            "tags": ["old", "cute"],
+            "author": { "name": "echo", "nickname": "e" },
+        }
+    ]);
+    let (response, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202);
+    index.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+        {
+            "doc_id": 1,
+            "$inc": { "views": 1, "stats.likes": 2 },
+            "$set": { "author.name": "kefir" },
+            "$unset": ["author.nickname"],
+            "$push": { "tags": "new" },
+            "$pull": { "tags": "old" },
+        }
+    ]);
+    let (response, code) = index.update_documents(documents, None).await;
+    assert_eq!(code, 202, "response: {}", response);
+    index.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    snapshot!(json_string!(response), @r###"
+    {
+      "doc_id": 1,
+      "title": "kefir",
+      "views": 42,
+      "tags": [
+        "cute",
+        "new"
+      ],
+      "author": {
+        "name": "kefir"
+      },
+      "stats": {
+        "likes": 2
+      }
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn error_update_document_with_operators() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) =
+        index.add_documents(json!([{ "doc_id": 1, "title": "kefir" }]), None).await;
+    assert_eq!(code, 202);
+    index.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([{ "doc_id": 1, "$inc": { "title": 1 } }]);
+    let (response, code) = index.update_documents(documents, None).await;
+    assert_eq!(code, 202, "response: {}", response);
+    let response = index.wait_task(response.uid()).await.failed();
+    snapshot!(response["error"], @r###"
+    {
+      "message": "Could not apply the operators of the document `1`: `$inc`: `title` is not a number, found `\"kefir\"`",
+      "code": "invalid_document_operator",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_operator"
+    }
+    "###);
+
+    let documents = json!([{ "doc_id": 1, "$set": { "doc_id": 2 } }]);
+    let (response, code) = index.update_documents(documents, None).await;
+    assert_eq!(code, 202, "response: {}", response);
+    let response = index.wait_task(response.uid()).await.failed();
+    snapshot!(response["error"]["message"], @r###""Could not apply the operators of the document `1`: `$set` cannot modify the primary key `doc_id`""###);
+}
+
 #[actix_rt::test]
 async fn update_document_gzip_encoded() {
     let server = Server::new_shared();
@@ -0,0 +1,184 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn versioned_document_writes() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "title": "kefir", "_version": 0 },
+        { "id": 2, "title": "intel" },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (document, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    snapshot!(json_string!(document), @r###"
+    {
+      "id": 1,
+      "title": "kefir",
+      "_version": 1
+    }
+    "###);
+
+    // the document is not at the version the update expects anymore
+    let (task, code) =
+        index.update_documents(json!([{ "id": 1, "title": "kefirou", "_version": 0 }]), None).await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.succeeded();
+    snapshot!(json_string!(task["details"]), @r###"
+    {
+      "receivedDocuments": 1,
+      "indexedDocuments": 0,
+      "versionConflicts": [
+        {
+          "documentId": "1",
+          "expectedVersion": 0,
+          "currentVersion": 1
+        }
+      ]
+    }
+    "###);
+
+    let (task, code) =
+        index.update_documents(json!([{ "id": 1, "title": "kefirou", "_version": 1 }]), None).await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.succeeded();
+    snapshot!(json_string!(task["details"]), @r###"
+    {
+      "receivedDocuments": 1,
+      "indexedDocuments": 1
+    }
+    "###);
+
+    let (document, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    snapshot!(json_string!(document), @r###"
+    {
+      "id": 1,
+      "title": "kefirou",
+      "_version": 2
+    }
+    "###);
+
+    // documents that are not versioned are always written
+    let (task, code) =
+        index.update_documents(json!([{ "id": 2, "title": "intel le chat" }]), None).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (document, code) = index.get_document(2, None).await;
+    assert_eq!(code, 200);
+    snapshot!(json_string!(document), @r###"
+    {
+      "id": 2,
+      "title": "intel le chat"
+    }
+    "###);
+
+    // the writes without a version still bump the version of the versioned documents
+    let (task, code) =
+        index.update_documents(json!([{ "id": 1, "title": "kefir le chat" }]), None).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (document, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    snapshot!(json_string!(document), @r###"
+    {
+      "id": 1,
+      "title": "kefir le chat",
+      "_version": 3
+    }
+    "###);
+
+    let (task, code) =
+        index.update_documents(json!([{ "id": 1, "title": "kefirou", "_version": 2 }]), None).await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.succeeded();
+    snapshot!(json_string!(task["details"]["versionConflicts"]), @r###"
+    [
+      {
+        "documentId": "1",
+        "expectedVersion": 2,
+        "currentVersion": 3
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn versioned_document_deletions() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "title": "kefir", "_version": 0 },
+        { "id": 2, "title": "intel", "_version": 0 },
+    ]);
+    let (task, code) = index.add_documents(documents, None).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (task, code) =
+        index.delete_batch_raw(json!([{ "id": 1, "version": 0 }, { "id": 2, "version": 1 }])).await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.succeeded();
+    snapshot!(json_string!(task["details"]), @r###"
+    {
+      "providedIds": 2,
+      "deletedDocuments": 1,
+      "versionConflicts": [
+        {
+          "documentId": "1",
+          "expectedVersion": 0,
+          "currentVersion": 1
+        }
+      ],
+      "originalFilter": null
+    }
+    "###);
+
+    let (_document, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    let (_document, code) = index.get_document(2, None).await;
+    assert_eq!(code, 404);
+
+    let url = format!("/indexes/{}/documents/1?version=1", index.uid);
+    let (task, code) = index.service.delete(url).await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.succeeded();
+    snapshot!(task["details"]["deletedDocuments"], @"1");
+
+    let (_document, code) = index.get_document(1, None).await;
+    assert_eq!(code, 404);
+}
+
+#[actix_rt::test]
+async fn error_invalid_document_version() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, code) =
+        index.add_documents(json!([{ "id": 1, "title": "kefir", "_version": "one" }]), None).await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.failed();
+    snapshot!(task["error"], @r###"
+    {
+      "message": "The `_version` of the document `1` must be a positive integer, found `\"one\"`.",
+      "code": "invalid_document_version",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_version"
+    }
+    "###);
+
+    let url = format!("/indexes/{}/documents/1?version=-1", index.uid);
+    let (response, code) = index.service.delete(url).await;
+    assert_eq!(code, 400, "{response}");
+    snapshot!(response["code"], @r###""invalid_document_version""###);
+}
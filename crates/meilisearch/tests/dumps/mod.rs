@@ -103,7 +103,21 @@ async fn import_dump_v1_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "chat": {
+        "description": "",
+        "documentTemplate": "{% for field in fields %}{% if field.is_searchable and field.value != nil %}{{ field.name }}: {{ field.value }}\n{% endif %}{% endfor %}",
+        "documentTemplateMaxBytes": 400,
+        "searchParameters": {
+          "limit": 5,
+          "hybrid": null,
+          "rankingScoreThreshold": null
+        }
+      },
+      "ingestPipeline": [],
+      "documentSchema": null,
+      "fieldTypes": {},
+      "generateDocumentIds": false
     }
     "###
     );
@@ -277,7 +291,21 @@ async fn import_dump_v1_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "chat": {
+        "description": "",
+        "documentTemplate": "{% for field in fields %}{% if field.is_searchable and field.value != nil %}{{ field.name }}: {{ field.value }}\n{% endif %}{% endfor %}",
+        "documentTemplateMaxBytes": 400,
+        "searchParameters": {
+          "limit": 5,
+          "hybrid": null,
+          "rankingScoreThreshold": null
+        }
+      },
+      "ingestPipeline": [],
+      "documentSchema": null,
+      "fieldTypes": {},
+      "generateDocumentIds": false
     }
     "###
     );
@@ -450,7 +478,21 @@ async fn import_dump_v1_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "chat": {
+        "description": "",
+        "documentTemplate": "{% for field in fields %}{% if field.is_searchable and field.value != nil %}{{ field.name }}: {{ field.value }}\n{% endif %}{% endfor %}",
+        "documentTemplateMaxBytes": 400,
+        "searchParameters": {
+          "limit": 5,
+          "hybrid": null,
+          "rankingScoreThreshold": null
+        }
+      },
+      "ingestPipeline": [],
+      "documentSchema": null,
+      "fieldTypes": {},
+      "generateDocumentIds": false
     }
     "###
     );
@@ -609,7 +651,21 @@ async fn import_dump_v2_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "chat": {
+        "description": "",
+        "documentTemplate": "{% for field in fields %}{% if field.is_searchable and field.value != nil %}{{ field.name }}: {{ field.value }}\n{% endif %}{% endfor %}",
+        "documentTemplateMaxBytes": 400,
+        "searchParameters": {
+          "limit": 5,
+          "hybrid": null,
+          "rankingScoreThreshold": null
+        }
+      },
+      "ingestPipeline": [],
+      "documentSchema": null,
+      "fieldTypes": {},
+      "generateDocumentIds": false
     }
     "###
     );
@@ -780,7 +836,21 @@ async fn import_dump_v2_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "chat": {
+        "description": "",
+        "documentTemplate": "{% for field in fields %}{% if field.is_searchable and field.value != nil %}{{ field.name }}: {{ field.value }}\n{% endif %}{% endfor %}",
+        "documentTemplateMaxBytes": 400,
+        "searchParameters": {
+          "limit": 5,
+          "hybrid": null,
+          "rankingScoreThreshold": null
+        }
+      },
+      "ingestPipeline": [],
+      "documentSchema": null,
+      "fieldTypes": {},
+      "generateDocumentIds": false
     }
     "###
     );
@@ -950,7 +1020,21 @@ async fn import_dump_v2_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "chat": {
+        "description": "",
+        "documentTemplate": "{% for field in fields %}{% if field.is_searchable and field.value != nil %}{{ field.name }}: {{ field.value }}\n{% endif %}{% endfor %}",
+        "documentTemplateMaxBytes": 400,
+        "searchParameters": {
+          "limit": 5,
+          "hybrid": null,
+          "rankingScoreThreshold": null
+        }
+      },
+      "ingestPipeline": [],
+      "documentSchema": null,
+      "fieldTypes": {},
+      "generateDocumentIds": false
     }
     "###
     );
@@ -1109,7 +1193,21 @@ async fn import_dump_v3_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "chat": {
+        "description": "",
+        "documentTemplate": "{% for field in fields %}{% if field.is_searchable and field.value != nil %}{{ field.name }}: {{ field.value }}\n{% endif %}{% endfor %}",
+        "documentTemplateMaxBytes": 400,
+        "searchParameters": {
+          "limit": 5,
+          "hybrid": null,
+          "rankingScoreThreshold": null
+        }
+      },
+      "ingestPipeline": [],
+      "documentSchema": null,
+      "fieldTypes": {},
+      "generateDocumentIds": false
     }
     "###
     );
@@ -1280,7 +1378,21 @@ async fn import_dump_v3_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "chat": {
+        "description": "",
+        "documentTemplate": "{% for field in fields %}{% if field.is_searchable and field.value != nil %}{{ field.name }}: {{ field.value }}\n{% endif %}{% endfor %}",
+        "documentTemplateMaxBytes": 400,
+        "searchParameters": {
+          "limit": 5,
+          "hybrid": null,
+          "rankingScoreThreshold": null
+        }
+      },
+      "ingestPipeline": [],
+      "documentSchema": null,
+      "fieldTypes": {},
+      "generateDocumentIds": false
     }
     "###
     );
@@ -1450,7 +1562,21 @@ async fn import_dump_v3_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "chat": {
+        "description": "",
+        "documentTemplate": "{% for field in fields %}{% if field.is_searchable and field.value != nil %}{{ field.name }}: {{ field.value }}\n{% endif %}{% endfor %}",
+        "documentTemplateMaxBytes": 400,
+        "searchParameters": {
+          "limit": 5,
+          "hybrid": null,
+          "rankingScoreThreshold": null
+        }
+      },
+      "ingestPipeline": [],
+      "documentSchema": null,
+      "fieldTypes": {},
+      "generateDocumentIds": false
     }
     "###
     );
@@ -1609,7 +1735,21 @@ async fn import_dump_v4_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "chat": {
+        "description": "",
+        "documentTemplate": "{% for field in fields %}{% if field.is_searchable and field.value != nil %}{{ field.name }}: {{ field.value }}\n{% endif %}{% endfor %}",
+        "documentTemplateMaxBytes": 400,
+        "searchParameters": {
+          "limit": 5,
+          "hybrid": null,
+          "rankingScoreThreshold": null
+        }
+      },
+      "ingestPipeline": [],
+      "documentSchema": null,
+      "fieldTypes": {},
+      "generateDocumentIds": false
     }
     "###
     );
@@ -1780,7 +1920,21 @@ async fn import_dump_v4_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "chat": {
+        "description": "",
+        "documentTemplate": "{% for field in fields %}{% if field.is_searchable and field.value != nil %}{{ field.name }}: {{ field.value }}\n{% endif %}{% endfor %}",
+        "documentTemplateMaxBytes": 400,
+        "searchParameters": {
+          "limit": 5,
+          "hybrid": null,
+          "rankingScoreThreshold": null
+        }
+      },
+      "ingestPipeline": [],
+      "documentSchema": null,
+      "fieldTypes": {},
+      "generateDocumentIds": false
     }
     "###
     );
@@ -1950,7 +2104,21 @@ async fn import_dump_v4_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "chat": {
+        "description": "",
+        "documentTemplate": "{% for field in fields %}{% if field.is_searchable and field.value != nil %}{{ field.name }}: {{ field.value }}\n{% endif %}{% endfor %}",
+        "documentTemplateMaxBytes": 400,
+        "searchParameters": {
+          "limit": 5,
+          "hybrid": null,
+          "rankingScoreThreshold": null
+        }
+      },
+      "ingestPipeline": [],
+      "documentSchema": null,
+      "fieldTypes": {},
+      "generateDocumentIds": false
     }
     "###
     );
@@ -2187,7 +2355,8 @@ async fn import_dump_v6_containing_experimental_features() {
       "containsFilter": false,
       "network": false,
       "getTaskDocumentsRoute": false,
-      "compositeEmbedders": false
+      "compositeEmbedders": false,
+      "chatCompletions": false
     }
     "###);
 
@@ -2240,7 +2409,21 @@ async fn import_dump_v6_containing_experimental_features() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "chat": {
+        "description": "",
+        "documentTemplate": "{% for field in fields %}{% if field.is_searchable and field.value != nil %}{{ field.name }}: {{ field.value }}\n{% endif %}{% endfor %}",
+        "documentTemplateMaxBytes": 400,
+        "searchParameters": {
+          "limit": 5,
+          "hybrid": null,
+          "rankingScoreThreshold": null
+        }
+      },
+      "ingestPipeline": [],
+      "documentSchema": null,
+      "fieldTypes": {},
+      "generateDocumentIds": false
     }
     "###);
 
@@ -2312,7 +2495,8 @@ async fn import_dump_v6_containing_batches_and_enqueued_tasks() {
       "containsFilter": false,
       "network": false,
       "getTaskDocumentsRoute": false,
-      "compositeEmbedders": false
+      "compositeEmbedders": false,
+      "chatCompletions": false
     }
     "###);
 
@@ -2417,7 +2601,8 @@ async fn generate_and_import_dump_containing_vectors() {
       "containsFilter": false,
       "network": false,
       "getTaskDocumentsRoute": false,
-      "compositeEmbedders": false
+      "compositeEmbedders": false,
+      "chatCompletions": false
     }
     "###);
 
@@ -2482,7 +2667,21 @@ async fn generate_and_import_dump_containing_vectors() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "chat": {
+        "description": "",
+        "documentTemplate": "{% for field in fields %}{% if field.is_searchable and field.value != nil %}{{ field.name }}: {{ field.value }}\n{% endif %}{% endfor %}",
+        "documentTemplateMaxBytes": 400,
+        "searchParameters": {
+          "limit": 5,
+          "hybrid": null,
+          "rankingScoreThreshold": null
+        }
+      },
+      "ingestPipeline": [],
+      "documentSchema": null,
+      "fieldTypes": {},
+      "generateDocumentIds": false
     }
     "###);
 
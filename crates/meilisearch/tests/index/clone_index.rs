@@ -0,0 +1,122 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::{GetAllDocumentsOptions, Server};
+use crate::json;
+
+#[actix_rt::test]
+async fn clone_index() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+    let clone = server.unique_index();
+
+    let (task, code) = index.update_settings(json!({ "filterableAttributes": ["genre"] })).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, code) = index
+        .add_documents(json!([{ "id": 1, "title": "Carol", "genre": "romance" }]), Some("id"))
+        .await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (task, code) = index.clone_index(json!({ "uid": clone.uid })).await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.succeeded();
+    snapshot!(task["type"], @r###""indexClone""###);
+    assert_eq!(task["indexUid"], json!(index.uid));
+    assert_eq!(task["details"]["newIndexUid"], json!(clone.uid));
+    snapshot!(task["details"]["settingsOnly"], @"false");
+
+    let (response, code) = clone.get().await;
+    assert_eq!(code, 200, "{response}");
+    snapshot!(response["primaryKey"], @r###""id""###);
+    let (documents, code) = clone.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200, "{documents}");
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "title": "Carol",
+        "genre": "romance"
+      }
+    ]
+    "###);
+    let (response, code) = clone.search_post(json!({ "filter": "genre = romance" })).await;
+    assert_eq!(code, 200, "{response}");
+    snapshot!(response["estimatedTotalHits"], @"1");
+
+    // the copy is independent from the original index
+    let (task, code) = clone.add_documents(json!([{ "id": 2, "title": "Shazam" }]), None).await;
+    assert_eq!(code, 202, "{task}");
+    clone.wait_task(task.uid()).await.succeeded();
+    let (stats, code) = index.stats().await;
+    assert_eq!(code, 200, "{stats}");
+    snapshot!(stats["numberOfDocuments"], @"1");
+}
+
+#[actix_rt::test]
+async fn clone_index_settings_only() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+    let clone = server.unique_index();
+
+    let (task, code) = index
+        .add_documents(json!([{ "id": 1, "title": "Carol", "genre": "romance" }]), Some("id"))
+        .await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, code) = index.update_settings(json!({ "filterableAttributes": ["genre"] })).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (task, code) = index.clone_index(json!({ "uid": clone.uid, "settingsOnly": true })).await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.succeeded();
+    snapshot!(task["details"]["settingsOnly"], @"true");
+
+    let (response, code) = clone.get().await;
+    assert_eq!(code, 200, "{response}");
+    snapshot!(response["primaryKey"], @r###""id""###);
+    let (settings, code) = clone.settings().await;
+    assert_eq!(code, 200, "{settings}");
+    snapshot!(json_string!(settings["filterableAttributes"]), @r###"
+    [
+      "genre"
+    ]
+    "###);
+    let (documents, code) = clone.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200, "{documents}");
+    snapshot!(json_string!(documents["results"]), @"[]");
+}
+
+#[actix_rt::test]
+async fn error_clone_index() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+    let other = server.unique_index();
+
+    let (response, code) = index.clone_index(json!({})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""missing_index_uid""###);
+
+    let (response, code) = index.clone_index(json!({ "uid": other.uid, "settingsOnly": 1 })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_index_clone_settings_only""###);
+
+    // the index to clone doesn't exist
+    let (task, code) = index.clone_index(json!({ "uid": other.uid })).await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.failed();
+    snapshot!(task["error"]["code"], @r###""index_not_found""###);
+
+    // the new index already exists
+    let (task, code) = index.create(None).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, code) = other.create(None).await;
+    assert_eq!(code, 202, "{task}");
+    other.wait_task(task.uid()).await.succeeded();
+    let (task, code) = index.clone_index(json!({ "uid": other.uid })).await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.failed();
+    snapshot!(task["error"]["code"], @r###""index_already_exists""###);
+}
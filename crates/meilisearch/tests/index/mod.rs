@@ -1,3 +1,4 @@
+mod clone_index;
 mod create_index;
 mod delete_index;
 mod errors;
@@ -1,5 +1,7 @@
+mod aliases;
 mod auth;
 mod batches;
+mod chat;
 mod common;
 mod dashboard;
 mod documents;
@@ -15,6 +17,7 @@ mod snapshot;
 mod stats;
 mod swap_indexes;
 mod tasks;
+mod transactions;
 mod upgrade;
 mod vector;
 
@@ -420,6 +420,35 @@ async fn search_invalid_threshold() {
     "###);
 }
 
+#[actix_rt::test]
+async fn search_invalid_diversity() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) =
+        index.search_post(json!({"hybrid": {"embedder": "default"}, "diversity": 1.5})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.diversity`: the value of `diversity` is invalid, expected a float between `0.0` and `1.0`.",
+      "code": "invalid_search_diversity",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_diversity"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"q": "doggo", "diversity": 0.5})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid request: missing `hybrid` parameter when `diversity` is present.",
+      "code": "missing_search_hybrid",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#missing_search_hybrid"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_non_filterable_facets() {
     let server = Server::new_shared();
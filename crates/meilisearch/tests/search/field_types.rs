@@ -0,0 +1,204 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 1, "releaseDate": "2023-12-31T23:00:00Z", "price": "12", "code": 42 },
+        { "id": 2, "releaseDate": "2024-01-02T00:00:00Z", "price": 8, "code": "42" },
+        { "id": 3, "releaseDate": "2024-03-01T10:30:00+01:00", "price": " 30 ", "code": 7 },
+    ])
+});
+
+#[actix_rt::test]
+async fn typed_fields_are_filterable_and_sortable() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["releaseDate", "price", "code"],
+            "sortableAttributes": ["releaseDate"],
+            "fieldTypes": { "releaseDate": "date", "price": "number", "code": "keyword" },
+        }))
+        .await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (task, code) = index.add_documents(DOCUMENTS.clone(), None).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    index
+        .search(
+            json!({
+                "filter": "releaseDate > \"2024-01-01T00:00:00Z\"",
+                "sort": ["releaseDate:desc"],
+                "attributesToRetrieve": ["id"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{response}");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 3
+                  },
+                  {
+                    "id": 2
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "filter": "releaseDate \"2024-01-01T00:00:00Z\" TO \"2024-02-01T00:00:00Z\"",
+                "attributesToRetrieve": ["id"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{response}");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 2
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({ "filter": "price >= 10", "attributesToRetrieve": ["id"] }),
+            |response, code| {
+                assert_eq!(code, 200, "{response}");
+                snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 1
+              },
+              {
+                "id": 3
+              }
+            ]
+            "###);
+            },
+        )
+        .await;
+
+    index
+        .search(json!({ "facets": ["code"], "limit": 0 }), |response, code| {
+            assert_eq!(code, 200, "{response}");
+            snapshot!(json_string!(response["facetDistribution"]), @r###"
+            {
+              "code": {
+                "42": 2,
+                "7": 1
+              }
+            }
+            "###);
+        })
+        .await;
+
+    // the documents are stored as they were sent
+    let (document, code) = index.get_document(1, None).await;
+    assert_eq!(code, 200);
+    snapshot!(json_string!(document["releaseDate"]), @r###""2023-12-31T23:00:00Z""###);
+}
+
+#[actix_rt::test]
+async fn declaring_field_types_converts_the_indexed_documents() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, code) =
+        index.update_settings(json!({ "filterableAttributes": ["releaseDate"] })).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (task, code) = index.add_documents(DOCUMENTS.clone(), None).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (task, code) =
+        index.update_settings(json!({ "fieldTypes": { "releaseDate": "date" } })).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    index
+        .search(
+            json!({ "filter": "releaseDate > 1704067200", "attributesToRetrieve": ["id"] }),
+            |response, code| {
+                assert_eq!(code, 200, "{response}");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 2
+                  },
+                  {
+                    "id": 3
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn error_document_not_matching_its_field_types() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["releaseDate"],
+            "fieldTypes": { "releaseDate": "date" },
+        }))
+        .await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (task, code) =
+        index.add_documents(json!([{ "id": 1, "releaseDate": "yesterday" }]), None).await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.failed();
+    snapshot!(task["error"], @r###"
+    {
+      "message": "The field `releaseDate` of the document `1` is declared as a date but contains `\"yesterday\"`.",
+      "code": "invalid_document_field_type",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_field_type"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn error_invalid_field_types() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) =
+        index.update_settings(json!({ "fieldTypes": { "releaseDate": "datetime" } })).await;
+    assert_eq!(code, 400, "{response}");
+    snapshot!(response["code"], @r###""invalid_settings_field_types""###);
+
+    let (task, code) =
+        index.update_settings(json!({ "fieldTypes": { "_geo.lat": "number" } })).await;
+    assert_eq!(code, 202, "{task}");
+    let task = index.wait_task(task.uid()).await.failed();
+    snapshot!(task["error"], @r###"
+    {
+      "message": "`.fieldTypes._geo.lat`: the type of the reserved `_geo.lat` field cannot be declared",
+      "code": "invalid_settings_field_types",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_field_types"
+    }
+    "###);
+}
@@ -464,6 +464,21 @@ async fn query_combination() {
     }
     "###);
 
+    // matrix of vectors with a single-vector embedder
+    let (response, code) = index
+    .search_post(json!({"vector": [[1.0, 0.0]], "hybrid": {"embedder": "default", "semanticRatio": 1.0}, "showRankingScore": true, "retrieveVectors": true}))
+    .await;
+
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Invalid vector: only multi-vector embedders accept an array of vectors, `vector` must be a single vector.",
+      "code": "invalid_search_vector",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_vector"
+    }
+    "###);
+
     // full vector
     let (response, code) = index
     .search_post(json!({"vector": [1.0, 0.0], "hybrid": {"embedder": "default", "semanticRatio": 1.0}, "showRankingScore": true, "retrieveVectors": true}))
@@ -579,6 +594,53 @@ async fn distinct_is_applied() {
     snapshot!(response["estimatedTotalHits"], @"2");
 }
 
+#[actix_rt::test]
+async fn diversity() {
+    let server = Server::new_shared();
+    let documents = json!([
+        {"id": 1, "_vectors": {"default": [1.0, 0.0]}},
+        // near duplicate of the first document
+        {"id": 2, "_vectors": {"default": [0.99, 0.1]}},
+        {"id": 3, "_vectors": {"default": [0.7, 0.7]}},
+    ]);
+    let index = index_with_documents_user_provided(server, &documents).await;
+
+    // without diversity, the near duplicate comes second
+    let (response, code) = index
+        .search_post(
+            json!({"vector": [1.0, 0.0], "hybrid": {"semanticRatio": 1.0, "embedder": "default"}}),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"], @r###"[{"id":1},{"id":2},{"id":3}]"###);
+
+    // a diversity of 0.0 only takes the relevance into account
+    let (response, code) = index
+        .search_post(json!({"vector": [1.0, 0.0], "hybrid": {"semanticRatio": 1.0, "embedder": "default"}, "diversity": 0.0}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"], @r###"[{"id":1},{"id":2},{"id":3}]"###);
+
+    let (response, code) = index
+        .search_post(json!({"vector": [1.0, 0.0], "hybrid": {"semanticRatio": 1.0, "embedder": "default"}, "diversity": 0.5}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"], @r###"[{"id":1},{"id":3},{"id":2}]"###);
+
+    // pagination applies to the diversified results
+    let (response, code) = index
+        .search_post(json!({"vector": [1.0, 0.0], "hybrid": {"semanticRatio": 1.0, "embedder": "default"}, "diversity": 0.5, "offset": 1, "limit": 1}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"], @r###"[{"id":3}]"###);
+    snapshot!(response["estimatedTotalHits"], @"3");
+
+    let (response, code) =
+        index.similar_post(json!({"id": 1, "embedder": "default", "diversity": 0.5})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"], @r###"[{"id":2},{"id":3}]"###);
+}
+
 #[actix_rt::test]
 async fn retrieve_vectors() {
     let server = Server::new_shared();
@@ -4,6 +4,7 @@
 mod distinct;
 mod errors;
 mod facet_search;
+mod field_types;
 mod filters;
 mod formatted;
 mod geo;
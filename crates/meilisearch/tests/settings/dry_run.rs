@@ -0,0 +1,158 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn dry_run_settings_update() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, code) = index
+        .add_documents(
+            json!([
+                { "id": 1, "title": "Carol", "genre": "romance" },
+                { "id": 2, "title": "Wonder Woman", "genre": "action" },
+                { "id": 3, "title": "Shazam" }
+            ]),
+            Some("id"),
+        )
+        .await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) =
+        index.settings_dry_run(json!({ "filterableAttributes": ["genre"] })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "diff": {
+        "searchableAttributes": {
+          "added": [],
+          "removed": []
+        },
+        "tokenizationChanged": false,
+        "stopWords": {
+          "added": [],
+          "removed": []
+        },
+        "separators": {
+          "added": [],
+          "removed": []
+        },
+        "dictionary": {
+          "added": [],
+          "removed": []
+        },
+        "exactAttributes": {
+          "added": [],
+          "removed": []
+        },
+        "disabledTyposTermsChanged": false,
+        "facetedAttributes": {
+          "added": [
+            "genre"
+          ],
+          "removed": [],
+          "modified": []
+        },
+        "sortableAttributes": {
+          "added": [],
+          "removed": []
+        },
+        "rankingRuleAttributes": {
+          "added": [],
+          "removed": []
+        },
+        "distinctAttributeChanged": false,
+        "proximityPrecisionChanged": false,
+        "prefixSearchChanged": false,
+        "facetSearchChanged": false,
+        "localizedAttributesChanged": false,
+        "geoChanged": false,
+        "embedders": {}
+      },
+      "reindexStrategy": "affectedFields",
+      "rebuiltDatabases": [
+        "facet-id-f64-docids",
+        "facet-id-string-docids",
+        "facet-id-exists-docids",
+        "facet-id-is-null-docids",
+        "facet-id-is-empty-docids",
+        "field-id-docid-facet-f64s",
+        "field-id-docid-facet-strings",
+        "facet-id-normalized-string-strings",
+        "facet-id-string-fst"
+      ],
+      "regenerateEmbeddings": false,
+      "estimatedDocumentsToReprocess": 2
+    }
+    "###);
+
+    // the settings are not updated
+    let (settings, code) = index.settings().await;
+    assert_eq!(code, 200, "{settings}");
+    snapshot!(json_string!(settings["filterableAttributes"]), @"[]");
+
+    let (response, code) =
+        index.settings_dry_run(json!({ "proximityPrecision": "byAttribute" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["diff"]["tokenizationChanged"], @"true");
+    snapshot!(response["diff"]["proximityPrecisionChanged"], @"true");
+    snapshot!(response["reindexStrategy"], @r###""full""###);
+    snapshot!(response["estimatedDocumentsToReprocess"], @"3");
+
+    let (response, code) = index
+        .settings_dry_run(json!({ "stopWords": ["the"], "sortableAttributes": ["title"] }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["diff"]["stopWords"]), @r###"
+    {
+      "added": [
+        "the"
+      ],
+      "removed": []
+    }
+    "###);
+    snapshot!(json_string!(response["diff"]["sortableAttributes"]), @r###"
+    {
+      "added": [
+        "title"
+      ],
+      "removed": []
+    }
+    "###);
+    snapshot!(response["reindexStrategy"], @r###""full""###);
+}
+
+#[actix_rt::test]
+async fn dry_run_settings_update_without_reindexing() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (task, code) = index.create(None).await;
+    assert_eq!(code, 202, "{task}");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) =
+        index.settings_dry_run(json!({ "displayedAttributes": ["title"] })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["reindexStrategy"], @r###""none""###);
+    snapshot!(json_string!(response["rebuiltDatabases"]), @"[]");
+    snapshot!(response["estimatedDocumentsToReprocess"], @"0");
+}
+
+#[actix_rt::test]
+async fn error_dry_run_settings_update() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.settings_dry_run(json!({ "filterableAttributes": 1 })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_settings_filterable_attributes""###);
+
+    let (response, code) =
+        index.settings_dry_run(json!({ "filterableAttributes": ["genre"] })).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""index_not_found""###);
+}
@@ -169,6 +169,31 @@ test_setting_routes!(
         update_verb: put,
         default_value: "indexingTime"
     },
+    {
+        setting: chat,
+        update_verb: patch,
+        default_value: {"description": "", "documentTemplate": "{% for field in fields %}{% if field.is_searchable and field.value != nil %}{{ field.name }}: {{ field.value }}\n{% endif %}{% endfor %}", "documentTemplateMaxBytes": 400, "searchParameters": {"limit": 5, "hybrid": null, "rankingScoreThreshold": null}}
+    },
+    {
+        setting: ingest_pipeline,
+        update_verb: put,
+        default_value: []
+    },
+    {
+        setting: document_schema,
+        update_verb: put,
+        default_value: null
+    },
+    {
+        setting: field_types,
+        update_verb: put,
+        default_value: {}
+    },
+    {
+        setting: generate_document_ids,
+        update_verb: put,
+        default_value: false
+    },
     {
         setting: proximity_precision,
         update_verb: put,
@@ -203,7 +228,7 @@ async fn get_settings() {
     let (response, code) = index.settings().await;
     assert_eq!(code, 200);
     let settings = response.as_object().unwrap();
-    assert_eq!(settings.keys().len(), 20);
+    assert_eq!(settings.keys().len(), 25);
     assert_eq!(settings["displayedAttributes"], json!(["*"]));
     assert_eq!(settings["searchableAttributes"], json!(["*"]));
     assert_eq!(settings["filterableAttributes"], json!([]));
@@ -237,6 +262,14 @@ async fn get_settings() {
     assert_eq!(settings["prefixSearch"], json!("indexingTime"));
     assert_eq!(settings["facetSearch"], json!(true));
     assert_eq!(settings["embedders"], json!({}));
+    assert_eq!(
+        settings["chat"]["searchParameters"],
+        json!({"limit": 5, "hybrid": null, "rankingScoreThreshold": null})
+    );
+    assert_eq!(settings["ingestPipeline"], json!([]));
+    assert_eq!(settings["documentSchema"], json!(null));
+    assert_eq!(settings["fieldTypes"], json!({}));
+    assert_eq!(settings["generateDocumentIds"], json!(false));
 }
 
 #[actix_rt::test]
@@ -339,7 +372,21 @@ async fn secrets_are_hidden_in_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "prefixSearch": "indexingTime",
+      "chat": {
+        "description": "",
+        "documentTemplate": "{% for field in fields %}{% if field.is_searchable and field.value != nil %}{{ field.name }}: {{ field.value }}\n{% endif %}{% endfor %}",
+        "documentTemplateMaxBytes": 400,
+        "searchParameters": {
+          "limit": 5,
+          "hybrid": null,
+          "rankingScoreThreshold": null
+        }
+      },
+      "ingestPipeline": [],
+      "documentSchema": null,
+      "fieldTypes": {},
+      "generateDocumentIds": false
     }
     "###);
 
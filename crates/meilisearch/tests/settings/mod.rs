@@ -1,4 +1,5 @@
 mod distinct;
+mod dry_run;
 mod errors;
 mod get_settings;
 mod prefix_search_settings;
@@ -127,6 +127,23 @@ async fn similar_invalid_ranking_score_threshold() {
     "###);
 }
 
+#[actix_rt::test]
+async fn similar_invalid_diversity() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.similar_post(json!({"diversity": -0.5})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.diversity`: the value of `diversity` is invalid, expected a float between `0.0` and `1.0`.",
+      "code": "invalid_similar_diversity",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_similar_diversity"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn similar_invalid_id() {
     let server = Server::new_shared();
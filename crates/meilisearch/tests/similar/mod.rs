@@ -702,3 +702,91 @@ async fn limit_and_offset() {
         )
         .await;
 }
+
+#[actix_rt::test]
+async fn positive_and_negative_examples() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .update_settings(json!({
+        "embedders": {
+            "manual": {
+                "source": "userProvided",
+                "dimensions": 3,
+            }
+        },
+        "filterableAttributes": ["title"]}))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await;
+
+    let documents = DOCUMENTS.clone();
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    // the examples are never returned
+    let (response, code) = index
+        .similar_post(json!({
+            "positiveIds": ["287947", "299537"],
+            "negativeIds": [143],
+            "attributesToRetrieve": ["title"],
+            "embedder": "manual"
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "title": "How to Train Your Dragon: The Hidden World"
+      },
+      {
+        "title": "Escape Room"
+      }
+    ]
+    "###);
+
+    // raw vectors can be used as examples
+    let (response, code) = index
+        .similar_post(json!({
+            "positiveVectors": [[0.1, 0.6, 0.8]],
+            "negativeIds": ["522681"],
+            "attributesToRetrieve": ["title"],
+            "embedder": "manual"
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "title": "All Quiet on the Western Front"
+      },
+      {
+        "title": "Captain Marvel"
+      },
+      {
+        "title": "How to Train Your Dragon: The Hidden World"
+      },
+      {
+        "title": "Shazam!"
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .similar_post(json!({
+            "negativeIds": ["522681"],
+            "embedder": "manual"
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `id`: one of `id`, `positiveIds` or `positiveVectors` must be specified",
+      "code": "invalid_similar_id",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_similar_id"
+    }
+    "###);
+}
@@ -0,0 +1,221 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::{GetAllDocumentsOptions, Server};
+use crate::json;
+
+#[actix_rt::test]
+async fn transaction_updates_several_indexes() {
+    let server = Server::new_shared();
+    let products = server.unique_index();
+    let variants = server.unique_index();
+
+    let (task, code) = products.add_documents(json!([{ "id": 1, "title": "shirt" }]), None).await;
+    assert_eq!(code, 202, "{task}");
+    products.wait_task(task.uid()).await.succeeded();
+
+    let (task, code) = server
+        .index_transaction(json!([
+            {
+                "indexUid": products.uid,
+                "documents": [
+                    { "op": "upsert", "document": { "id": 1, "title": "blue shirt" } },
+                    { "op": "upsert", "document": { "id": 2, "title": "pants" } },
+                ],
+            },
+            {
+                "indexUid": variants.uid,
+                "primaryKey": "sku",
+                "documents": [{ "op": "replace", "document": { "sku": "a", "product": 1 } }],
+                "settings": { "filterableAttributes": ["product"] },
+            },
+        ]))
+        .await;
+    assert_eq!(code, 202, "{task}");
+    let task = products.wait_task(task.uid()).await.succeeded();
+    snapshot!(task["type"], @r###""indexTransaction""###);
+    snapshot!(task["indexUid"], @"null");
+    snapshot!(json_string!(task["details"]), @r###"
+    {
+      "receivedOperations": 4,
+      "indexedDocuments": 3,
+      "deletedDocuments": 0
+    }
+    "###);
+
+    let (documents, code) = products.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200);
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "title": "blue shirt"
+      },
+      {
+        "id": 2,
+        "title": "pants"
+      }
+    ]
+    "###);
+
+    let (documents, code) = variants.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200);
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "sku": "a",
+        "product": 1
+      }
+    ]
+    "###);
+    let (settings, code) = variants.settings().await;
+    assert_eq!(code, 200);
+    snapshot!(json_string!(settings["filterableAttributes"]), @r###"
+    [
+      "product"
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn transaction_is_all_or_nothing() {
+    let server = Server::new_shared();
+    let products = server.unique_index();
+    let variants = server.unique_index();
+
+    let (task, code) = products.add_documents(json!([{ "id": 1, "title": "shirt" }]), None).await;
+    assert_eq!(code, 202, "{task}");
+    products.wait_task(task.uid()).await.succeeded();
+
+    let (task, code) = server
+        .index_transaction(json!([
+            {
+                "indexUid": products.uid,
+                "documents": [{ "op": "delete", "id": 1 }],
+            },
+            {
+                "indexUid": variants.uid,
+                "primaryKey": "sku",
+                "documents": [{ "op": "replace", "document": { "product": 1 } }],
+            },
+        ]))
+        .await;
+    assert_eq!(code, 202, "{task}");
+    let task = products.wait_task(task.uid()).await.failed();
+    snapshot!(task["error"]["code"], @r###""missing_document_id""###);
+    snapshot!(json_string!(task["details"]), @r###"
+    {
+      "receivedOperations": 2,
+      "indexedDocuments": 0,
+      "deletedDocuments": 0
+    }
+    "###);
+
+    let (documents, code) = products.get_all_documents(GetAllDocumentsOptions::default()).await;
+    assert_eq!(code, 200);
+    snapshot!(json_string!(documents["results"]), @r###"
+    [
+      {
+        "id": 1,
+        "title": "shirt"
+      }
+    ]
+    "###);
+
+    // the index created by the transaction has been removed
+    let (response, code) = variants.get().await;
+    assert_eq!(code, 404, "{response}");
+}
+
+#[actix_rt::test]
+async fn error_transaction() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = server.index_transaction(json!([])).await;
+    assert_eq!(code, 400, "{response}");
+    snapshot!(response["code"], @r###""bad_request""###);
+
+    let (response, code) = server
+        .index_transaction(json!([{ "indexUid": index.uid }, { "indexUid": index.uid }]))
+        .await;
+    assert_eq!(code, 400, "{response}");
+    snapshot!(response["code"], @r###""invalid_transaction_duplicate_index_found""###);
+
+    let (response, code) =
+        server.index_transaction(json!([{ "indexUid": index.uid, "documents": {} }])).await;
+    assert_eq!(code, 400, "{response}");
+    snapshot!(response["code"], @r###""invalid_transaction_documents""###);
+
+    let (response, code) = server
+        .index_transaction(json!([{ "indexUid": index.uid, "documents": [{ "op": "rename" }] }]))
+        .await;
+    assert_eq!(code, 400, "{response}");
+    snapshot!(response["code"], @r###""malformed_payload""###);
+}
+
+#[actix_rt::test]
+async fn transaction_checks_the_action_of_each_operation() {
+    let mut server = Server::new_auth().await;
+    server.use_admin_key("MASTER_KEY").await;
+    let index = server.index("products");
+    let (task, _code) = index.add_documents(json!([{ "id": 1, "title": "shirt" }]), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+    drop(index);
+
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server
+        .add_api_key(
+            json!({ "indexes": ["products"], "actions": ["documents.add"], "expiresAt": null }),
+        )
+        .await;
+    assert_eq!(code, 201, "{response}");
+    let key = response["key"].as_str().unwrap();
+    server.use_api_key(key);
+
+    let (response, code) = server
+        .index_transaction(json!([{
+            "indexUid": "products",
+            "documents": [{ "op": "upsert", "document": { "id": 2, "title": "pants" } }],
+        }]))
+        .await;
+    assert_eq!(code, 202, "{response}");
+
+    let (response, code) = server
+        .index_transaction(json!([{
+            "indexUid": "products",
+            "documents": [{ "op": "delete", "id": 1 }],
+        }]))
+        .await;
+    snapshot!(code, @"403 Forbidden");
+    snapshot!(response["code"], @r###""invalid_api_key""###);
+
+    let (response, code) = server
+        .index_transaction(json!([{
+            "indexUid": "products",
+            "settings": { "filterableAttributes": ["title"] },
+        }]))
+        .await;
+    snapshot!(code, @"403 Forbidden");
+    snapshot!(response["code"], @r###""invalid_api_key""###);
+
+    let (response, code) = server
+        .index_transaction(json!([{
+            "indexUid": "variants",
+            "documents": [{ "op": "upsert", "document": { "id": 1 } }],
+        }]))
+        .await;
+    snapshot!(code, @"403 Forbidden");
+    snapshot!(response["code"], @r###""invalid_api_key""###);
+
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server
+        .add_api_key(json!({ "indexes": ["*"], "actions": ["search"], "expiresAt": null }))
+        .await;
+    assert_eq!(code, 201, "{response}");
+    let key = response["key"].as_str().unwrap();
+    server.use_api_key(key);
+
+    let (response, code) = server.index_transaction(json!([{ "indexUid": "products" }])).await;
+    snapshot!(code, @"403 Forbidden");
+    snapshot!(response["code"], @r###""invalid_api_key""###);
+}
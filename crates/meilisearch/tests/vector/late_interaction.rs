@@ -0,0 +1,131 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn search_with_late_interaction() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "colbert": {
+                  "source": "userProvidedMultiVector",
+                  "dimensions": 2,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+      {"id": 0, "name": "kefir", "_vectors": { "colbert": [[1.0, 0.0], [0.0, 1.0]] }},
+      {"id": 1, "name": "echo", "_vectors": { "colbert": [[1.0, 0.0]] }},
+      {"id": 2, "name": "intel", "_vectors": { "colbert": [[-1.0, 0.0]] }},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    // the query matrix is made of the vectors `[1.0, 0.0]` and `[0.0, 1.0]`
+    let (response, code) = index
+        .search_post(json!({
+            "vector": [[1.0, 0.0], [0.0, 1.0]],
+            "hybrid": {"semanticRatio": 1.0, "embedder": "colbert"},
+            "attributesToRetrieve": ["id"],
+            "showRankingScore": true,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 0,
+        "_rankingScore": 1.0
+      },
+      {
+        "id": 1,
+        "_rankingScore": 0.75
+      },
+      {
+        "id": 2,
+        "_rankingScore": 0.25
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn late_interaction_query_dimensions() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "colbert": {
+                  "source": "userProvidedMultiVector",
+                  "dimensions": 2,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({
+            "vector": [[1.0, 0.0], [0.0, 1.0, 0.0]],
+            "hybrid": {"semanticRatio": 1.0, "embedder": "colbert"},
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid vector dimensions: vector #1 of the query matrix has dimensions 3, the multi-vector embedder requires 2.",
+      "code": "invalid_vector_dimensions",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_vector_dimensions"
+    }
+    "###);
+
+    // the query matrix cannot be flattened
+    let (response, code) = index
+        .search_post(json!({
+            "vector": [1.0, 0.0, 0.0, 1.0],
+            "hybrid": {"semanticRatio": 1.0, "embedder": "colbert"},
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid vector: the embedder is a multi-vector embedder, `vector` must be an array of vectors.",
+      "code": "invalid_search_vector",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_vector"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn late_interaction_rejects_binary_quantized() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "colbert": {
+                  "source": "userProvidedMultiVector",
+                  "dimensions": 2,
+                  "binaryQuantized": true,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_settings_embedders""###);
+}
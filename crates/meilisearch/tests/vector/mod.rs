@@ -1,9 +1,12 @@
 mod binary_quantized;
+mod late_interaction;
 #[cfg(feature = "test-ollama")]
 mod ollama;
+mod onnx;
 mod openai;
 mod rest;
 mod settings;
+mod sparse;
 
 use std::str::FromStr;
 
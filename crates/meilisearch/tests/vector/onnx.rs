@@ -0,0 +1,124 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::json;
+use crate::vector::get_server_vector;
+
+#[actix_rt::test]
+async fn missing_model() {
+    let server = get_server_vector().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "onnx": {
+                  "source": "onnx",
+                  "model": "this/model/does/not/exist",
+                  "pooling": "useModel",
+                  "normalize": true,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await;
+    snapshot!(json_string!(task["error"]), @r###"
+    {
+      "message": "Index `doggo`: Error while generating embeddings: user error: the directory of the ONNX models is not set or does not exist\n  - Hint: the instance administrator must set `MEILI_EXPERIMENTAL_ONNX_MODELS_DIR` to the directory containing the models",
+      "code": "vector_embedding_error",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#vector_embedding_error"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn model_outside_of_models_dir() {
+    let server = get_server_vector().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "onnx": {
+                  "source": "onnx",
+                  "model": "/etc/model",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "`.embedders.onnx`: `model` must be a path relative to the models directory, got the absolute path `/etc/model`.",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "onnx": {
+                  "source": "onnx",
+                  "model": "models/../../model",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "`.embedders.onnx`: `model` must not contain `..`, got `models/../../model`.",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn bad_parameters() {
+    let server = get_server_vector().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "onnx": {
+                  "source": "onnx",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "`.embedders.onnx`: Missing field `model` (note: this field is mandatory for source `onnx`)",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "hf": {
+                  "source": "huggingFace",
+                  "normalize": true,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "`.embedders.hf`: Field `normalize` unavailable for source `huggingFace`.\n  - note: `normalize` is available for sources: `onnx`\n  - note: available fields for source `huggingFace`: `source`, `model`, `revision`, `pooling`, `documentTemplate`, `documentTemplateMaxBytes`, `distribution`, `binaryQuantized`",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+}
@@ -1,11 +1,12 @@
 use std::collections::BTreeMap;
 
+use byte_unit::{Byte, Unit};
 use meili_snap::{json_string, snapshot};
 use reqwest::IntoUrl;
 use wiremock::matchers::{method, path};
 use wiremock::{Mock, MockServer, Request, ResponseTemplate};
 
-use crate::common::Value;
+use crate::common::{default_settings, Server, Value};
 use crate::json;
 use crate::vector::{get_server_vector, GetAllDocumentsOptions};
 
@@ -2111,3 +2112,355 @@ async fn searchable_reindex() {
     }
     "###);
 }
+
+async fn create_mock_with_media() -> (MockServer, Value) {
+    let mock_server = MockServer::start().await;
+
+    let image_to_embedding: BTreeMap<_, _> = vec![
+        // image -> embedding
+        ("https://example.com/kefir.jpg", [1.0, 0.0, 0.0]),
+        ("https://example.com/intel.jpg", [0.0, 1.0, 0.0]),
+        ("https://example.com/cat.jpg", [0.9, 0.1, 0.0]),
+    ]
+    // turn into btree
+    .into_iter()
+    .collect();
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .respond_with(move |req: &Request| {
+            let body: Value = req.body_json().unwrap();
+            let embedding = match body["image"].as_str() {
+                Some(image) => image_to_embedding.get(image).unwrap_or(&[99., 99., 99.]),
+                None => &[0.0, 0.0, 1.0],
+            };
+            ResponseTemplate::new(200).set_body_json(json!({ "data": embedding }))
+        })
+        .mount(&mock_server)
+        .await;
+    let url = mock_server.uri();
+
+    let embedder_settings = json!({
+        "source": "rest",
+        "url": url,
+        "dimensions": 3,
+        "request": {
+          "input": "{{text}}",
+          "image": "{{doc.image}}"
+        },
+        "response": {
+          "data": "{{embedding}}"
+        },
+        "documentTemplate": "{{doc.name}}",
+    });
+
+    (mock_server, embedder_settings)
+}
+
+#[actix_rt::test]
+async fn server_media() {
+    let (_mock, setting) = create_mock_with_media().await;
+    let server = get_server_vector().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": setting,
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+    let documents = json!([
+      {"id": 0, "name": "kefir", "image": "https://example.com/kefir.jpg"},
+      {"id": 1, "name": "echo"},
+      {"id": 2, "name": "intel", "image": "https://example.com/intel.jpg"},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    let task = index.wait_task(value.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+
+    let (documents, _code) = index
+        .get_all_documents(GetAllDocumentsOptions { retrieve_vectors: true, ..Default::default() })
+        .await;
+    snapshot!(json_string!(documents["results"].as_array().unwrap().iter().map(|doc| doc["_vectors"]["rest"]["embeddings"].clone()).collect::<Vec<_>>()), @r###"
+    [
+      [
+        [
+          1.0,
+          0.0,
+          0.0
+        ]
+      ],
+      [
+        [
+          0.0,
+          0.0,
+          1.0
+        ]
+      ],
+      [
+        [
+          0.0,
+          1.0,
+          0.0
+        ]
+      ]
+    ]
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "media": "https://example.com/cat.jpg",
+            "hybrid": {"semanticRatio": 1.0, "embedder": "rest"},
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 0
+      },
+      {
+        "id": 2
+      },
+      {
+        "id": 1
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "media": "https://example.com/cat.jpg",
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["message"], @r###""Invalid request: missing `hybrid` parameter when `media` is present.""###);
+}
+
+#[actix_rt::test]
+async fn persistent_embedding_cache() {
+    let (mock, setting) = create_mock().await;
+    let temp = tempfile::tempdir().unwrap();
+    let mut options = default_settings(temp.path());
+    options.experimental_persistent_embedding_cache_size =
+        Byte::from_u64_with_unit(10, Unit::MiB).unwrap();
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": setting,
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+      {"id": 0, "name": "kefir"},
+      {"id": 1, "name": "intel"},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+    let embedding_requests = mock.received_requests().await.unwrap().len();
+
+    // the rendered texts are unchanged, so the regenerated embeddings are all taken from the cache
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": {
+                  "documentTemplate": "{{ doc.name }}"
+              },
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+    snapshot!(mock.received_requests().await.unwrap().len() - embedding_requests, @"0");
+
+    // search queries consult the cache too
+    let (response, code) = index
+        .search_post(json!({
+            "q": "intel",
+            "hybrid": {"semanticRatio": 1.0, "embedder": "rest"},
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"][0]), @r###"
+    {
+      "id": 1
+    }
+    "###);
+    snapshot!(mock.received_requests().await.unwrap().len() - embedding_requests, @"0");
+}
+
+#[actix_rt::test]
+async fn shadow_embedder_promotion() {
+    let (mock, setting) = create_mock().await;
+    let server = get_server_vector().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": setting,
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+      {"id": 0, "name": "kefir"},
+      {"id": 1, "name": "intel"},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    // the shadowed embedder must exist
+    let mut shadow = setting.clone();
+    shadow["shadowOf"] = serde_json::json!("missing");
+    let (response, code) = index.update_settings(json!({ "embedders": { "next": shadow } })).await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await.failed();
+    snapshot!(task["error"]["message"], @r###""`.embedders.next`: The embedder `missing` shadowed by this embedder does not exist.\n  - Hint: promote or remove the shadow embedder before removing `missing`.""###);
+
+    let mut shadow = setting.clone();
+    shadow["documentTemplateMaxBytes"] = serde_json::json!(300);
+    shadow["shadowOf"] = serde_json::json!("rest");
+    let (response, code) = index.update_settings(json!({ "embedders": { "next": shadow } })).await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let (settings, code) = index.settings().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(settings["embedders"]["next"]["shadowOf"], @r###""rest""###);
+
+    // promoting the shadow does not regenerate any embedding
+    let embedding_requests = mock.received_requests().await.unwrap().len();
+    let (response, code) =
+        index.update_settings(json!({ "embedders": { "next": { "shadowOf": null } } })).await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+    snapshot!(mock.received_requests().await.unwrap().len() - embedding_requests, @"0");
+
+    let (settings, code) = index.settings().await;
+    snapshot!(code, @"200 OK");
+    let embedders: Vec<_> = settings["embedders"].as_object().unwrap().keys().collect();
+    snapshot!(format!("{embedders:?}"), @r###"["rest"]"###);
+    snapshot!(settings["embedders"]["rest"]["documentTemplateMaxBytes"], @"300");
+    snapshot!(settings["embedders"]["rest"]["shadowOf"], @"null");
+
+    let (response, code) = index
+        .search_post(json!({
+            "q": "intel",
+            "hybrid": {"semanticRatio": 1.0, "embedder": "rest"},
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"][0]), @r###"
+    {
+      "id": 1
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn regenerate_embeddings() {
+    let (mock, setting) = create_mock().await;
+    let temp = tempfile::tempdir().unwrap();
+    let mut options = default_settings(temp.path());
+    options.experimental_persistent_embedding_cache_size =
+        Byte::from_u64_with_unit(10, Unit::MiB).unwrap();
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "filterableAttributes": ["id"],
+          "embedders": {
+              "rest": setting,
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+      {"id": 0, "name": "kefir"},
+      {"id": 1, "name": "intel"},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+    let embedding_requests = mock.received_requests().await.unwrap().len();
+
+    // only the filtered document is embedded again, bypassing the cache
+    let (response, code) =
+        index.regenerate_embeddings(json!({ "filter": "id = 1", "embedders": ["rest"] })).await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await.succeeded();
+    snapshot!(json_string!(task["details"]), @r###"
+    {
+      "regeneratedDocuments": 1,
+      "regeneratedEmbedders": [
+        "rest"
+      ],
+      "originalFilter": "\"id = 1\""
+    }
+    "###);
+    snapshot!(mock.received_requests().await.unwrap().len() - embedding_requests, @"1");
+
+    let (documents, _code) = index
+        .get_all_documents(GetAllDocumentsOptions { retrieve_vectors: true, ..Default::default() })
+        .await;
+    snapshot!(json_string!(documents["results"][1]["_vectors"]), @r###"
+    {
+      "rest": {
+        "embeddings": [
+          [
+            1.0,
+            1.0,
+            1.0
+          ]
+        ],
+        "regenerate": true
+      }
+    }
+    "###);
+
+    let (response, code) = index.regenerate_embeddings(json!({ "embedders": ["doggo"] })).await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await.failed();
+    snapshot!(json_string!(task["error"]), @r###"
+    {
+      "message": "Index `doggo`: Cannot find embedder with name `doggo`.",
+      "code": "invalid_embedding_regeneration_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_embedding_regeneration_embedders"
+    }
+    "###);
+
+    let (response, code) = index.regenerate_embeddings(json!({ "embedders": [] })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "`.embedders` cannot be empty. Omit it to regenerate the embeddings of all the embedders",
+      "code": "invalid_embedding_regeneration_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_embedding_regeneration_embedders"
+    }
+    "###);
+}
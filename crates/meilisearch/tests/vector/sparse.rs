@@ -0,0 +1,150 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::Server;
+use crate::json;
+
+#[actix_rt::test]
+async fn search_sparse_vectors() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "sparse": {
+                  "source": "userProvidedSparse",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+      {"id": 0, "name": "kefir", "_vectors": { "sparse": { "embeddings": { "dog": 2.0, "cat": 0.5 }, "regenerate": false } }},
+      {"id": 1, "name": "echo", "_vectors": { "sparse": { "embeddings": { "cat": 1.0 }, "regenerate": false } }},
+      {"id": 2, "name": "intel", "_vectors": { "sparse": { "embeddings": { "dog": 0.5 }, "regenerate": false } }},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({
+            "q": "dog",
+            "hybrid": {"semanticRatio": 1.0, "embedder": "sparse"},
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 0
+      },
+      {
+        "id": 2
+      },
+      {
+        "id": 1
+      }
+    ]
+    "###);
+
+    let (value, code) = index.delete_document(0).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({
+            "q": "cat",
+            "hybrid": {"semanticRatio": 1.0, "embedder": "sparse"},
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 1
+      },
+      {
+        "id": 2
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn sparse_embedder_rejects_binary_quantized() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "sparse": {
+                  "source": "userProvidedSparse",
+                  "binaryQuantized": true,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_settings_embedders""###);
+}
+
+#[actix_rt::test]
+async fn sparse_vectors_are_normalized() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "sparse": {
+                  "source": "userProvidedSparse",
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+      {"id": 0, "name": "kefir", "_vectors": { "sparse": { "embeddings": { "Dog": 2.0 }, "regenerate": false } }},
+      {"id": 1, "name": "echo", "_vectors": { "sparse": { "embeddings": { "cat": 1.0 }, "regenerate": false } }},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({
+            "q": "DOG",
+            "hybrid": {"semanticRatio": 1.0, "embedder": "sparse"},
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 0
+      },
+      {
+        "id": 1
+      }
+    ]
+    "###);
+
+    let long_term = "a".repeat(506);
+    let documents = json!([
+      {"id": 2, "name": "intel", "_vectors": { "sparse": { "embeddings": { long_term: 1.0 }, "regenerate": false } }},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    let task = index.wait_task(value.uid()).await.failed();
+    snapshot!(task["error"]["code"], @r###""invalid_vectors_type""###);
+    assert!(task["error"]["message"].as_str().unwrap().contains("is longer than 505 bytes"));
+}
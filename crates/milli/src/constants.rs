@@ -11,3 +11,5 @@ const fn parse_u32(s: &str) -> u32 {
 
 pub const RESERVED_VECTORS_FIELD_NAME: &str = "_vectors";
 pub const RESERVED_GEO_FIELD_NAME: &str = "_geo";
+pub const RESERVED_VERSION_FIELD_NAME: &str = "_version";
+pub const RESERVED_EXPIRES_AT_FIELD_NAME: &str = "_expiresAt";
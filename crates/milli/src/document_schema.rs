@@ -0,0 +1,591 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use deserr::Deserr;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+use crate::update::new::indexer::field_operators::is_operator;
+
+/// A JSON Schema the documents sent to an index must match.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct DocumentSchemaSettings {
+    /// The JSON Schema of the documents.
+    ///
+    /// Supports the `type`, `enum`, `const`, `properties`, `required`, `additionalProperties`, `items`,
+    /// `minItems`, `maxItems`, `minLength`, `maxLength`, `minimum`, `maximum`, `exclusiveMinimum`,
+    /// `exclusiveMaximum`, `allOf`, `anyOf`, `oneOf` and `not` keywords.
+    #[schema(value_type = Object, example = json!({ "type": "object", "properties": { "price": { "type": "number" } } }))]
+    pub schema: Value,
+    /// What to do with the documents that do not match the schema.
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to `fail`
+    #[serde(default)]
+    #[deserr(default)]
+    pub on_invalid: OnInvalidDocument,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum OnInvalidDocument {
+    /// The whole document addition fails.
+    #[default]
+    Fail,
+    /// The invalid documents are skipped and reported in the details of the task.
+    Skip,
+}
+
+/// A document that does not match the document schema of the index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaViolation {
+    pub document_id: String,
+    /// The path to the invalid value, `.` being the whole document.
+    pub path: String,
+    pub message: String,
+}
+
+/// Formats the violations reported in an error, at most 20 of them.
+pub(crate) fn format_violations(violations: &[SchemaViolation]) -> String {
+    const MAX_REPORTED: usize = 20;
+
+    let mut message = String::new();
+    for (i, SchemaViolation { document_id, path, message: reason }) in
+        violations.iter().take(MAX_REPORTED).enumerate()
+    {
+        if i != 0 {
+            message.push_str(", ");
+        }
+        let _ = write!(message, "document `{document_id}` at `{path}`: {reason}");
+    }
+    if violations.len() > MAX_REPORTED {
+        let _ = write!(message, ", and {} more", violations.len() - MAX_REPORTED);
+    }
+    message
+}
+
+/// The document schema of an index, compiled to validate documents.
+pub struct DocumentSchema {
+    root: Schema,
+    on_invalid: OnInvalidDocument,
+}
+
+impl DocumentSchema {
+    /// Compiles the schema, returning the reason why it is invalid otherwise.
+    pub fn new(settings: &DocumentSchemaSettings) -> Result<Self, String> {
+        let root = Schema::compile(&settings.schema, "")?;
+        Ok(Self { root, on_invalid: settings.on_invalid })
+    }
+
+    pub fn on_invalid(&self) -> OnInvalidDocument {
+        self.on_invalid
+    }
+
+    /// Returns all the reasons why the document does not match the schema.
+    ///
+    /// A `partial` document, from an update, is only validated on the fields it contains
+    /// and can contain field operators.
+    pub fn validate(
+        &self,
+        document_id: &str,
+        document: &Value,
+        partial: bool,
+    ) -> Vec<SchemaViolation> {
+        let mut errors = Vec::new();
+        self.root.validate_with(document, "", partial, &mut errors);
+
+        errors
+            .into_iter()
+            .map(|(path, message)| SchemaViolation {
+                document_id: document_id.to_string(),
+                path: if path.is_empty() { String::from(".") } else { path },
+                message,
+            })
+            .collect()
+    }
+}
+
+enum Schema {
+    Bool(bool),
+    Object(Box<ObjectSchema>),
+}
+
+#[derive(Default)]
+struct ObjectSchema {
+    types: Option<Vec<JsonType>>,
+    enum_values: Option<Vec<Value>>,
+    constant: Option<Value>,
+    properties: BTreeMap<String, Schema>,
+    required: Vec<String>,
+    additional_properties: Option<Schema>,
+    items: Option<Schema>,
+    min_items: Option<usize>,
+    max_items: Option<usize>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+    minimum: Option<f64>,
+    maximum: Option<f64>,
+    exclusive_minimum: Option<f64>,
+    exclusive_maximum: Option<f64>,
+    all_of: Vec<Schema>,
+    any_of: Vec<Schema>,
+    one_of: Vec<Schema>,
+    not: Option<Schema>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum JsonType {
+    Null,
+    Boolean,
+    Integer,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+impl JsonType {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "null" => JsonType::Null,
+            "boolean" => JsonType::Boolean,
+            "integer" => JsonType::Integer,
+            "number" => JsonType::Number,
+            "string" => JsonType::String,
+            "array" => JsonType::Array,
+            "object" => JsonType::Object,
+            _ => return None,
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            JsonType::Null => "null",
+            JsonType::Boolean => "boolean",
+            JsonType::Integer => "integer",
+            JsonType::Number => "number",
+            JsonType::String => "string",
+            JsonType::Array => "array",
+            JsonType::Object => "object",
+        }
+    }
+
+    fn matches(&self, value: &Value) -> bool {
+        match (self, value) {
+            (JsonType::Null, Value::Null)
+            | (JsonType::Boolean, Value::Bool(_))
+            | (JsonType::Number, Value::Number(_))
+            | (JsonType::String, Value::String(_))
+            | (JsonType::Array, Value::Array(_))
+            | (JsonType::Object, Value::Object(_)) => true,
+            (JsonType::Integer, Value::Number(number)) => {
+                number.is_i64()
+                    || number.is_u64()
+                    || number.as_f64().is_some_and(|f| f.fract() == 0.0)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn value_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+/// The keywords of JSON Schema that are not supported, rather than silently ignored.
+const UNSUPPORTED_KEYWORDS: &[&str] = &[
+    "$ref",
+    "$defs",
+    "definitions",
+    "if",
+    "then",
+    "else",
+    "pattern",
+    "patternProperties",
+    "propertyNames",
+    "dependentRequired",
+    "dependentSchemas",
+    "dependencies",
+    "contains",
+    "prefixItems",
+    "uniqueItems",
+    "multipleOf",
+    "minProperties",
+    "maxProperties",
+    "unevaluatedProperties",
+    "unevaluatedItems",
+];
+
+impl Schema {
+    fn compile(value: &Value, path: &str) -> Result<Self, String> {
+        let object = match value {
+            Value::Bool(b) => return Ok(Schema::Bool(*b)),
+            Value::Object(object) => object,
+            otherwise => {
+                return Err(format!(
+                    "`{}` must be an object or a boolean, found {}",
+                    display_path(path),
+                    value_type_name(otherwise)
+                ))
+            }
+        };
+
+        let mut schema = ObjectSchema::default();
+        for (keyword, value) in object {
+            let keyword_path = format!("{path}.{keyword}");
+            let invalid = |expected: &str| {
+                format!("`{keyword_path}` must be {expected}, found {}", value_type_name(value))
+            };
+
+            match keyword.as_str() {
+                "type" => {
+                    let names = match value {
+                        Value::String(name) => vec![name.as_str()],
+                        Value::Array(names) => names
+                            .iter()
+                            .map(|name| {
+                                name.as_str()
+                                    .ok_or_else(|| invalid("a string or an array of strings"))
+                            })
+                            .collect::<Result<_, _>>()?,
+                        _ => return Err(invalid("a string or an array of strings")),
+                    };
+                    let types = names
+                        .into_iter()
+                        .map(|name| {
+                            JsonType::from_name(name)
+                                .ok_or_else(|| format!("`{keyword_path}`: unknown type `{name}`"))
+                        })
+                        .collect::<Result<_, _>>()?;
+                    schema.types = Some(types);
+                }
+                "enum" => {
+                    let values = value.as_array().ok_or_else(|| invalid("an array"))?;
+                    schema.enum_values = Some(values.clone());
+                }
+                "const" => schema.constant = Some(value.clone()),
+                "properties" => {
+                    let properties = value.as_object().ok_or_else(|| invalid("an object"))?;
+                    for (name, property) in properties {
+                        let property_path = format!("{keyword_path}.{name}");
+                        schema
+                            .properties
+                            .insert(name.clone(), Schema::compile(property, &property_path)?);
+                    }
+                }
+                "required" => {
+                    let fields = value.as_array().ok_or_else(|| invalid("an array of strings"))?;
+                    schema.required = fields
+                        .iter()
+                        .map(|field| {
+                            field
+                                .as_str()
+                                .map(String::from)
+                                .ok_or_else(|| invalid("an array of strings"))
+                        })
+                        .collect::<Result<_, _>>()?;
+                }
+                "additionalProperties" => {
+                    schema.additional_properties = Some(Schema::compile(value, &keyword_path)?)
+                }
+                "items" => schema.items = Some(Schema::compile(value, &keyword_path)?),
+                "minItems" | "maxItems" | "minLength" | "maxLength" => {
+                    let bound = value
+                        .as_u64()
+                        .map(|bound| bound as usize)
+                        .ok_or_else(|| invalid("a positive integer"))?;
+                    match keyword.as_str() {
+                        "minItems" => schema.min_items = Some(bound),
+                        "maxItems" => schema.max_items = Some(bound),
+                        "minLength" => schema.min_length = Some(bound),
+                        _ => schema.max_length = Some(bound),
+                    }
+                }
+                "minimum" | "maximum" | "exclusiveMinimum" | "exclusiveMaximum" => {
+                    let bound = value.as_f64().ok_or_else(|| invalid("a number"))?;
+                    match keyword.as_str() {
+                        "minimum" => schema.minimum = Some(bound),
+                        "maximum" => schema.maximum = Some(bound),
+                        "exclusiveMinimum" => schema.exclusive_minimum = Some(bound),
+                        _ => schema.exclusive_maximum = Some(bound),
+                    }
+                }
+                "allOf" | "anyOf" | "oneOf" => {
+                    let schemas = value.as_array().ok_or_else(|| invalid("an array"))?;
+                    let schemas = schemas
+                        .iter()
+                        .enumerate()
+                        .map(|(i, schema)| Schema::compile(schema, &format!("{keyword_path}[{i}]")))
+                        .collect::<Result<_, _>>()?;
+                    match keyword.as_str() {
+                        "allOf" => schema.all_of = schemas,
+                        "anyOf" => schema.any_of = schemas,
+                        _ => schema.one_of = schemas,
+                    }
+                }
+                "not" => schema.not = Some(Schema::compile(value, &keyword_path)?),
+                keyword if UNSUPPORTED_KEYWORDS.contains(&keyword) => {
+                    return Err(format!(
+                        "`{keyword_path}`: the `{keyword}` keyword is not supported"
+                    ))
+                }
+                // annotations such as `title`, `description` or `$schema` do not validate anything
+                _ => (),
+            }
+        }
+
+        Ok(Schema::Object(Box::new(schema)))
+    }
+
+    fn validate(&self, value: &Value, path: &str, errors: &mut Vec<(String, String)>) {
+        self.validate_with(value, path, false, errors)
+    }
+
+    /// Validates a value, skipping the `required` keyword and the field operators when `partial`.
+    fn validate_with(
+        &self,
+        value: &Value,
+        path: &str,
+        partial: bool,
+        errors: &mut Vec<(String, String)>,
+    ) {
+        let schema = match self {
+            Schema::Bool(true) => return,
+            Schema::Bool(false) => {
+                errors.push((path.to_string(), String::from("no value is allowed")));
+                return;
+            }
+            Schema::Object(schema) => schema,
+        };
+
+        schema.validate_common(value, path, errors);
+
+        match value {
+            Value::Object(object) => {
+                if !partial {
+                    for field in &schema.required {
+                        if !object.contains_key(field) {
+                            errors.push((
+                                format!("{path}.{field}"),
+                                String::from("is a required field"),
+                            ));
+                        }
+                    }
+                }
+
+                for (field, value) in object {
+                    if partial && is_operator(field) {
+                        continue;
+                    }
+                    let field_path = format!("{path}.{field}");
+                    match (schema.properties.get(field), &schema.additional_properties) {
+                        (Some(property), _) => property.validate(value, &field_path, errors),
+                        (None, Some(Schema::Bool(false))) => {
+                            errors.push((field_path, String::from("is not an allowed field")))
+                        }
+                        (None, Some(additional)) => additional.validate(value, &field_path, errors),
+                        (None, None) => (),
+                    }
+                }
+            }
+            Value::String(s) => {
+                let length = s.chars().count();
+                if let Some(min) = schema.min_length.filter(|min| length < *min) {
+                    errors.push((
+                        path.to_string(),
+                        format!("must be at least {min} characters long"),
+                    ));
+                }
+                if let Some(max) = schema.max_length.filter(|max| length > *max) {
+                    errors
+                        .push((path.to_string(), format!("must be at most {max} characters long")));
+                }
+            }
+            Value::Number(number) => {
+                let number = number.as_f64().unwrap_or_default();
+                if let Some(min) = schema.minimum.filter(|min| number < *min) {
+                    errors.push((
+                        path.to_string(),
+                        format!("must be greater than or equal to {min}"),
+                    ));
+                }
+                if let Some(max) = schema.maximum.filter(|max| number > *max) {
+                    errors.push((path.to_string(), format!("must be less than or equal to {max}")));
+                }
+                if let Some(min) = schema.exclusive_minimum.filter(|min| number <= *min) {
+                    errors.push((path.to_string(), format!("must be greater than {min}")));
+                }
+                if let Some(max) = schema.exclusive_maximum.filter(|max| number >= *max) {
+                    errors.push((path.to_string(), format!("must be less than {max}")));
+                }
+            }
+            Value::Array(values) => {
+                if let Some(min) = schema.min_items.filter(|min| values.len() < *min) {
+                    errors.push((path.to_string(), format!("must contain at least {min} items")));
+                }
+                if let Some(max) = schema.max_items.filter(|max| values.len() > *max) {
+                    errors.push((path.to_string(), format!("must contain at most {max} items")));
+                }
+                if let Some(items) = &schema.items {
+                    for (i, value) in values.iter().enumerate() {
+                        items.validate(value, &format!("{path}[{i}]"), errors);
+                    }
+                }
+            }
+            Value::Null | Value::Bool(_) => (),
+        }
+
+        schema.validate_combinators(value, path, errors);
+    }
+
+    fn is_valid(&self, value: &Value) -> bool {
+        let mut errors = Vec::new();
+        self.validate(value, "", &mut errors);
+        errors.is_empty()
+    }
+}
+
+impl ObjectSchema {
+    /// Validates the keywords applying to values of any type.
+    fn validate_common(&self, value: &Value, path: &str, errors: &mut Vec<(String, String)>) {
+        if let Some(types) = &self.types {
+            if !types.iter().any(|t| t.matches(value)) {
+                let expected = types.iter().map(JsonType::name).collect::<Vec<_>>().join(" or ");
+                errors.push((
+                    path.to_string(),
+                    format!("expected {expected}, found {}", value_type_name(value)),
+                ));
+            }
+        }
+        if let Some(values) = &self.enum_values {
+            if !values.contains(value) {
+                errors
+                    .push((path.to_string(), format!("{value} is not one of the allowed values")));
+            }
+        }
+        if let Some(constant) = &self.constant {
+            if constant != value {
+                errors.push((path.to_string(), format!("must be {constant}, found {value}")));
+            }
+        }
+    }
+
+    fn validate_combinators(&self, value: &Value, path: &str, errors: &mut Vec<(String, String)>) {
+        for schema in &self.all_of {
+            schema.validate(value, path, errors);
+        }
+        if !self.any_of.is_empty() && !self.any_of.iter().any(|schema| schema.is_valid(value)) {
+            errors.push((
+                path.to_string(),
+                String::from("does not match any of the `anyOf` schemas"),
+            ));
+        }
+        if !self.one_of.is_empty() {
+            let matching = self.one_of.iter().filter(|schema| schema.is_valid(value)).count();
+            if matching != 1 {
+                errors.push((
+                    path.to_string(),
+                    format!("must match exactly one of the `oneOf` schemas, matches {matching}"),
+                ));
+            }
+        }
+        if let Some(not) = &self.not {
+            if not.is_valid(value) {
+                errors.push((path.to_string(), String::from("must not match the `not` schema")));
+            }
+        }
+    }
+}
+
+fn display_path(path: &str) -> &str {
+    if path.is_empty() {
+        "."
+    } else {
+        path
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    fn schema(schema: Value) -> DocumentSchema {
+        DocumentSchema::new(&DocumentSchemaSettings { schema, on_invalid: OnInvalidDocument::Fail })
+            .unwrap()
+    }
+
+    fn violations(schema: &DocumentSchema, document: Value, partial: bool) -> Vec<String> {
+        schema
+            .validate("1", &document, partial)
+            .into_iter()
+            .map(|SchemaViolation { path, message, .. }| format!("{path}: {message}"))
+            .collect()
+    }
+
+    #[test]
+    fn validate_documents() {
+        let schema = schema(json!({
+            "type": "object",
+            "required": ["id", "title"],
+            "properties": {
+                "title": { "type": "string", "minLength": 1 },
+                "price": { "type": "number", "minimum": 0 },
+                "tags": { "type": "array", "items": { "type": "string" }, "maxItems": 2 },
+                "kind": { "enum": ["book", "movie"] },
+            },
+        }));
+
+        let valid =
+            json!({ "id": 1, "title": "kefir", "price": 3.5, "tags": ["cat"], "kind": "book" });
+        assert!(violations(&schema, valid, false).is_empty());
+
+        let invalid = json!({ "id": 1, "price": "3.5", "tags": ["cat", 2, "dog"], "kind": "game" });
+        insta::assert_debug_snapshot!(violations(&schema, invalid, false), @r###"
+        [
+            ".title: is a required field",
+            ".price: expected number, found a string",
+            ".tags: must contain at most 2 items",
+            ".tags[1]: expected string, found a number",
+            ".kind: \"game\" is not one of the allowed values",
+        ]
+        "###);
+
+        // partial documents do not need the required fields and may contain operators
+        let partial = json!({ "id": 1, "price": -1, "$inc": { "views": 1 } });
+        insta::assert_debug_snapshot!(violations(&schema, partial, true), @r###"
+        [
+            ".price: must be greater than or equal to 0",
+        ]
+        "###);
+    }
+
+    #[test]
+    fn invalid_schemas() {
+        let compile = |schema| {
+            DocumentSchema::new(&DocumentSchemaSettings {
+                schema,
+                on_invalid: OnInvalidDocument::Fail,
+            })
+            .err()
+            .unwrap()
+        };
+
+        insta::assert_snapshot!(compile(json!(3)), @"`.` must be an object or a boolean, found a number");
+        insta::assert_snapshot!(compile(json!({ "properties": { "price": { "type": "float" } } })), @"`.properties.price.type`: unknown type `float`");
+        insta::assert_snapshot!(compile(json!({ "$ref": "#/definitions/price" })), @"`.$ref`: the `$ref` keyword is not supported");
+    }
+}
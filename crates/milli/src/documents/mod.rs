@@ -13,8 +13,9 @@ pub use builder::DocumentsBatchBuilder;
 pub use enriched::{EnrichedDocument, EnrichedDocumentsBatchCursor, EnrichedDocumentsBatchReader};
 use obkv::KvReader;
 pub use primary_key::{
-    validate_document_id_str, validate_document_id_value, DocumentIdExtractionError, FieldIdMapper,
-    PrimaryKey, DEFAULT_PRIMARY_KEY,
+    validate_composite_document_id, validate_document_id_str, validate_document_id_value,
+    validate_primary_key, DocumentIdExtractionError, FieldIdMapper, PrimaryKey,
+    DEFAULT_PRIMARY_KEY,
 };
 pub use reader::{DocumentsBatchCursor, DocumentsBatchCursorError, DocumentsBatchReader};
 use serde::{Deserialize, Serialize};
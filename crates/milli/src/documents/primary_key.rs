@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::iter;
 use std::ops::ControlFlow;
 use std::result::Result as StdResult;
@@ -14,6 +15,10 @@ use crate::{FieldId, InternalError, Object, Result, UserError};
 /// The symbol used to define levels in a nested primary key.
 const PRIMARY_KEY_SPLIT_SYMBOL: char = '.';
 
+/// The symbol used to separate the fields of a composite primary key,
+/// and the values of these fields in the external document id.
+const PRIMARY_KEY_COMPOSITE_SYMBOL: char = ',';
+
 /// The default primary that is used when not specified.
 pub const DEFAULT_PRIMARY_KEY: &str = "id";
 
@@ -41,11 +46,13 @@ where
 }
 
 /// A type that represent the type of primary key that has been set
-/// for this index, a classic flat one or a nested one.
+/// for this index, a classic flat one, a nested one or a composite one
+/// made of several (possibly nested) fields.
 #[derive(Debug, Clone, Copy)]
 pub enum PrimaryKey<'a> {
     Flat { name: &'a str, field_id: FieldId },
     Nested { name: &'a str },
+    Composite { name: &'a str },
 }
 
 pub enum DocumentIdExtractionError {
@@ -56,7 +63,9 @@ pub enum DocumentIdExtractionError {
 
 impl<'a> PrimaryKey<'a> {
     pub fn new(path: &'a str, fields: &impl FieldIdMapper) -> Option<Self> {
-        Some(if path.contains(PRIMARY_KEY_SPLIT_SYMBOL) {
+        Some(if path.contains(PRIMARY_KEY_COMPOSITE_SYMBOL) {
+            Self::Composite { name: path }
+        } else if path.contains(PRIMARY_KEY_SPLIT_SYMBOL) {
             Self::Nested { name: path }
         } else {
             let field_id = fields.id(path)?;
@@ -68,7 +77,9 @@ impl<'a> PrimaryKey<'a> {
         path: &'a str,
         fields: &mut impl MutFieldIdMapper,
     ) -> StdResult<Self, UserError> {
-        Ok(if path.contains(PRIMARY_KEY_SPLIT_SYMBOL) {
+        Ok(if path.contains(PRIMARY_KEY_COMPOSITE_SYMBOL) {
+            Self::Composite { name: path }
+        } else if path.contains(PRIMARY_KEY_SPLIT_SYMBOL) {
             Self::Nested { name: path }
         } else {
             let field_id = fields.insert(path).ok_or(UserError::AttributeLimitReached)?;
@@ -80,9 +91,16 @@ impl<'a> PrimaryKey<'a> {
         match self {
             PrimaryKey::Flat { name, .. } => name,
             PrimaryKey::Nested { name } => name,
+            PrimaryKey::Composite { name } => name,
         }
     }
 
+    /// Returns the names of the fields that make up the primary key,
+    /// a single one unless the primary key is composite.
+    pub fn components(&self) -> impl Iterator<Item = &'a str> {
+        self.name().split(PRIMARY_KEY_COMPOSITE_SYMBOL).map(str::trim)
+    }
+
     pub fn document_id(
         &self,
         document: &obkv::KvReader<FieldId>,
@@ -130,6 +148,24 @@ impl<'a> PrimaryKey<'a> {
                     None => Ok(Err(DocumentIdExtractionError::MissingDocumentId)),
                 }
             }
+            PrimaryKey::Composite { .. } => {
+                let mut document_id = String::new();
+                for component in self.components() {
+                    let Some(component) = PrimaryKey::new(component, fields) else {
+                        return Ok(Err(DocumentIdExtractionError::MissingDocumentId));
+                    };
+                    match component.document_id(document, fields)? {
+                        Ok(value) => push_document_id_component(&mut document_id, &value),
+                        Err(error) => return Ok(Err(error)),
+                    }
+                }
+                match validate_composite_document_id_len(document_id) {
+                    Ok(document_id) => Ok(Ok(document_id)),
+                    Err(user_error) => {
+                        Ok(Err(DocumentIdExtractionError::InvalidDocumentId(user_error)))
+                    }
+                }
+            }
         }
     }
 
@@ -191,6 +227,20 @@ impl<'a> PrimaryKey<'a> {
                     crate::documents::Error::InvalidDocumentFormat,
                 ))?)
             }
+            PrimaryKey::Composite { .. } => {
+                let mut document_id = bumpalo::collections::String::new_in(indexer);
+                for component in self.components() {
+                    let component = PrimaryKey::new(component, db_fields_ids_map).ok_or(
+                        InternalError::DocumentsError(
+                            crate::documents::Error::InvalidDocumentFormat,
+                        ),
+                    )?;
+                    let value =
+                        component.extract_docid_from_db(document, db_fields_ids_map, indexer)?;
+                    push_document_id_component(&mut document_id, value.to_de());
+                }
+                Ok(DeOrBumpStr::Bump(document_id.into_bump_str()))
+            }
         }
     }
 
@@ -201,6 +251,22 @@ impl<'a> PrimaryKey<'a> {
         indexer: &'bump Bump,
     ) -> Result<DeOrBumpStr<'pl, 'bump>> {
         use serde::Deserializer as _;
+
+        if let PrimaryKey::Composite { .. } = self {
+            // Every component is extracted in its own pass over the document,
+            // inserting the same fields in the fields ids map again is a no-op.
+            let mut document_id = bumpalo::collections::String::new_in(indexer);
+            for component in self.components() {
+                let component = PrimaryKey::Nested { name: component };
+                let value =
+                    component.extract_fields_and_docid(document, new_fields_ids_map, indexer)?;
+                push_document_id_component(&mut document_id, value.to_de());
+            }
+            let document_id = document_id.into_bump_str();
+            validate_composite_document_id_len(document_id)?;
+            return Ok(DeOrBumpStr::Bump(document_id));
+        }
+
         let res = document
             .deserialize_map(crate::update::new::indexer::de::FieldAndDocidExtractor::new(
                 new_fields_ids_map,
@@ -289,6 +355,57 @@ pub fn validate_document_id_str(document_id: &str) -> Option<&str> {
     }
 }
 
+/// Returns an error if the primary key is composite and one of its fields is empty
+/// or declared several times.
+pub fn validate_primary_key(primary_key: &str) -> StdResult<(), UserError> {
+    if !primary_key.contains(PRIMARY_KEY_COMPOSITE_SYMBOL) {
+        return Ok(());
+    }
+
+    let mut components = HashSet::new();
+    for component in primary_key.split(PRIMARY_KEY_COMPOSITE_SYMBOL).map(str::trim) {
+        if component.is_empty() || !components.insert(component) {
+            return Err(UserError::InvalidCompositePrimaryKey(primary_key.to_string()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Builds the external id of a document identified by a composite primary key
+/// from the values of its components, in the order of the primary key.
+pub fn validate_composite_document_id(components: Vec<Value>) -> StdResult<String, UserError> {
+    let mut document_id = String::new();
+    for component in components {
+        push_document_id_component(&mut document_id, &validate_document_id_value(component)?);
+    }
+    validate_composite_document_id_len(document_id)
+}
+
+/// Returns an error if the composite document id doesn't fit in a single document id.
+fn validate_composite_document_id_len<S: AsRef<str>>(document_id: S) -> StdResult<S, UserError> {
+    if document_id.as_ref().len() >= 512 {
+        Err(UserError::InvalidDocumentId {
+            document_id: Value::String(document_id.as_ref().to_string()),
+        })
+    } else {
+        Ok(document_id)
+    }
+}
+
+/// Appends a component to a composite document id.
+///
+/// The values of the components can't contain the separator, so the composite id is unambiguous.
+fn push_document_id_component<S>(document_id: &mut S, component: &str)
+where
+    S: std::fmt::Write + std::ops::Deref<Target = str>,
+{
+    if !document_id.is_empty() {
+        document_id.write_char(PRIMARY_KEY_COMPOSITE_SYMBOL).unwrap();
+    }
+    document_id.write_str(component).unwrap();
+}
+
 pub fn validate_document_id_value(document_id: Value) -> StdResult<String, UserError> {
     match document_id {
         Value::String(string) => match validate_document_id_str(&string) {
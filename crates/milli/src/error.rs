@@ -12,10 +12,11 @@ use serde_json::Value;
 use thiserror::Error;
 
 use crate::constants::RESERVED_GEO_FIELD_NAME;
+use crate::document_schema::format_violations;
 use crate::documents::{self, DocumentsBatchCursorError};
 use crate::thread_pool_no_abort::PanicCatched;
 use crate::vector::settings::EmbeddingSettings;
-use crate::{CriterionError, DocumentId, FieldId, Object, SortError};
+use crate::{CriterionError, DocumentId, FieldId, FieldType, Object, SchemaViolation, SortError};
 
 pub fn is_reserved_keyword(keyword: &str) -> bool {
     [RESERVED_GEO_FIELD_NAME, "_geoDistance", "_geoPoint", "_geoRadius", "_geoBoundingBox"]
@@ -121,6 +122,21 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
 and can not be more than 511 bytes.", .document_id.to_string()
     )]
     InvalidDocumentId { document_id: Value },
+    #[error("Could not apply the operators of the document `{document_id}`: {message}")]
+    InvalidDocumentOperator { document_id: String, message: String },
+    #[error("The `_version` of the document `{document_id}` must be a positive integer, found `{version}`.")]
+    InvalidDocumentVersion { document_id: String, version: Value },
+    #[error("The document `{document_id}` is at version {current_version}, not at the expected version {expected_version}.")]
+    DocumentVersionConflict { document_id: String, expected_version: u64, current_version: u64 },
+    #[error("The `_expiresAt` of the document `{document_id}` must be a Unix timestamp in seconds, found `{expires_at}`.")]
+    InvalidDocumentExpiresAt { document_id: String, expires_at: Value },
+    #[error("The field `{field}` of the document `{document_id}` is declared as a {expected} but contains `{value}`.")]
+    InvalidDocumentFieldType {
+        document_id: String,
+        field: String,
+        expected: FieldType,
+        value: Value,
+    },
     #[error("Invalid facet distribution: {}",
         if .invalid_facets_name.len() == 1 {
             let field = .invalid_facets_name.iter().next().unwrap();
@@ -154,6 +170,12 @@ and can not be more than 511 bytes.", .document_id.to_string()
     InvalidGeoField(#[from] Box<GeoError>),
     #[error("Invalid vector dimensions: expected: `{}`, found: `{}`.", .expected, .found)]
     InvalidVectorDimensions { expected: usize, found: usize },
+    #[error("Invalid vector dimensions: vector #{row} of the query matrix has dimensions {found}, the multi-vector embedder requires {expected}.")]
+    InvalidMultiVectorDimensions { row: usize, expected: usize, found: usize },
+    #[error("Invalid vector: the embedder is a multi-vector embedder, `vector` must be an array of vectors.")]
+    ExpectedMultiVectorQuery,
+    #[error("Invalid vector: only multi-vector embedders accept an array of vectors, `vector` must be a single vector.")]
+    UnexpectedMultiVectorQuery,
     #[error("Invalid vector dimensions in document with id `{document_id}` in `._vectors.{embedder_name}`.\n  - note: embedding #{embedding_index} has dimensions {found}\n  - note: embedder `{embedder_name}` requires {expected}")]
     InvalidIndexingVectorDimensions {
         embedder_name: String,
@@ -271,6 +293,8 @@ and can not be more than 511 bytes.", .document_id.to_string()
     NoSpaceLeftOnDevice,
     #[error("Index already has a primary key: `{0}`.")]
     PrimaryKeyCannotBeChanged(String),
+    #[error("The composite primary key `{0}` is invalid, its fields must be non-empty and declared only once.")]
+    InvalidCompositePrimaryKey(String),
     #[error(transparent)]
     SerdeJson(serde_json::Error),
     #[error(transparent)]
@@ -293,6 +317,8 @@ and can not be more than 511 bytes.", .document_id.to_string()
     InvalidSearchEmbedder(String),
     #[error("Cannot find embedder with name `{0}`.")]
     InvalidSimilarEmbedder(String),
+    #[error("Cannot find embedder with name `{0}`.")]
+    InvalidEmbeddingRegenerationEmbedder(String),
     #[error("Too many vectors for document with id {0}: found {1}, but limited to 256.")]
     TooManyVectors(String, usize),
     #[error("`.embedders.{embedder_name}`: Field `{field}` unavailable for source `{source_}`{for_context}.{available_sources}{available_fields}{available_contexts}",
@@ -371,10 +397,24 @@ and can not be more than 511 bytes.", .document_id.to_string()
     InvalidSettingsEmbedder { embedder_name: String, message: String },
     #[error("`.embedders.{embedder_name}.dimensions`: `dimensions` cannot be zero")]
     InvalidSettingsDimensions { embedder_name: String },
+    #[error("`.embedders.{embedder_name}.{field}`: `{field}` cannot be zero")]
+    InvalidSettingsEmbedderRateLimit { embedder_name: String, field: &'static str },
     #[error(
         "`.embedders.{embedder_name}.binaryQuantized`: Cannot disable the binary quantization.\n - Note: Binary quantization is a lossy operation that cannot be reverted.\n - Hint: Add a new embedder that is non-quantized and regenerate the vectors."
     )]
     InvalidDisableBinaryQuantization { embedder_name: String },
+    #[error("`.chat.{field}`: {message}")]
+    InvalidSettingsChat { field: &'static str, message: String },
+    #[error("`.ingestPipeline[{index}]`: {message}")]
+    InvalidSettingsIngestPipeline { index: usize, message: String },
+    #[error("Could not apply the ingest pipeline to the document `{document_id}`: {message}.")]
+    IngestPipelineError { document_id: String, message: String },
+    #[error("`.documentSchema`: {0}")]
+    InvalidSettingsDocumentSchema(String),
+    #[error("Some documents do not match the document schema: {}.", format_violations(.violations))]
+    DocumentSchemaViolation { violations: Vec<SchemaViolation> },
+    #[error("`.fieldTypes.{field}`: the type of the reserved `{field}` field cannot be declared")]
+    InvalidSettingsFieldTypes { field: String },
     #[error("`.embedders.{embedder_name}.documentTemplateMaxBytes`: `documentTemplateMaxBytes` cannot be zero")]
     InvalidSettingsDocumentTemplateMaxBytes { embedder_name: String },
     #[error("`.embedders.{embedder_name}.url`: could not parse `{url}`: {inner_error}")]
@@ -1,8 +1,14 @@
+use std::borrow::Cow;
 use std::error::Error;
 use std::fmt;
 use std::str::FromStr;
 
+use deserr::Deserr;
 use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use utoipa::ToSchema;
 
 #[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum FacetType {
@@ -43,3 +49,130 @@ impl fmt::Display for InvalidFacetType {
 }
 
 impl Error for InvalidFacetType {}
+
+/// The type declared for a field in the `fieldTypes` setting.
+///
+/// The values of a typed field are converted before being stored in the facet databases,
+/// the documents themselves are kept as they were sent.
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialOrd,
+    Ord,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    Deserr,
+    ToSchema,
+)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum FieldType {
+    /// An RFC 3339 date or a Unix timestamp in seconds, stored as a number.
+    Date,
+    /// A string, numbers and booleans are stored as strings.
+    Keyword,
+    /// A number, numeric strings are stored as numbers.
+    Number,
+    /// A boolean, the `"true"` and `"false"` strings are accepted.
+    Boolean,
+}
+
+impl FieldType {
+    /// Converts a facet value to this type.
+    ///
+    /// Arrays, objects, `null` and empty strings are returned as is, `None` is returned
+    /// if the value cannot be converted.
+    pub fn coerce<'a>(&self, value: &'a Value) -> Option<Cow<'a, Value>> {
+        match (self, value) {
+            (_, Value::Null | Value::Array(_) | Value::Object(_)) => Some(Cow::Borrowed(value)),
+            (_, Value::String(s)) if s.is_empty() => Some(Cow::Borrowed(value)),
+            (FieldType::Date | FieldType::Number, Value::Number(_)) => Some(Cow::Borrowed(value)),
+            (FieldType::Date, Value::String(s)) => parse_date(s).map(Value::Number).map(Cow::Owned),
+            (FieldType::Number, Value::String(s)) => {
+                parse_number(s).map(Value::Number).map(Cow::Owned)
+            }
+            (FieldType::Keyword, Value::String(_)) => Some(Cow::Borrowed(value)),
+            (FieldType::Keyword, Value::Number(n)) => {
+                Some(Cow::Owned(Value::String(n.to_string())))
+            }
+            (FieldType::Keyword, Value::Bool(b)) => Some(Cow::Owned(Value::String(b.to_string()))),
+            (FieldType::Boolean, Value::Bool(_)) => Some(Cow::Borrowed(value)),
+            (FieldType::Boolean, Value::String(s)) => {
+                let s = s.trim();
+                if s.eq_ignore_ascii_case("true") {
+                    Some(Cow::Owned(Value::Bool(true)))
+                } else if s.eq_ignore_ascii_case("false") {
+                    Some(Cow::Owned(Value::Bool(false)))
+                } else {
+                    None
+                }
+            }
+            (FieldType::Date | FieldType::Number | FieldType::Boolean, _) => None,
+        }
+    }
+}
+
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldType::Date => f.write_str("date"),
+            FieldType::Keyword => f.write_str("keyword"),
+            FieldType::Number => f.write_str("number"),
+            FieldType::Boolean => f.write_str("boolean"),
+        }
+    }
+}
+
+/// Parses an RFC 3339 date into a Unix timestamp in seconds.
+fn parse_date(s: &str) -> Option<Number> {
+    let date = OffsetDateTime::parse(s.trim(), &Rfc3339).ok()?;
+    if date.nanosecond() == 0 {
+        Some(Number::from(date.unix_timestamp()))
+    } else {
+        Number::from_f64(date.unix_timestamp_nanos() as f64 / 1_000_000_000.0)
+    }
+}
+
+fn parse_number(s: &str) -> Option<Number> {
+    let s = s.trim();
+    if let Ok(n) = s.parse::<i64>() {
+        return Some(Number::from(n));
+    }
+    s.parse::<f64>().ok().filter(|n| n.is_finite()).and_then(Number::from_f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::FieldType;
+
+    #[test]
+    fn coerce_field_types() {
+        let coerce =
+            |field_type: FieldType, value| field_type.coerce(&value).map(|v| v.into_owned());
+
+        assert_eq!(coerce(FieldType::Date, json!("2024-01-02T00:00:00Z")), Some(json!(1704153600)));
+        assert_eq!(
+            coerce(FieldType::Date, json!("2024-01-02T00:00:00.5+00:00")),
+            Some(json!(1704153600.5))
+        );
+        assert_eq!(coerce(FieldType::Date, json!(1704153600)), Some(json!(1704153600)));
+        assert_eq!(coerce(FieldType::Date, json!("2024-01-02")), None);
+        assert_eq!(coerce(FieldType::Number, json!(" 42 ")), Some(json!(42)));
+        assert_eq!(coerce(FieldType::Number, json!("4.5")), Some(json!(4.5)));
+        assert_eq!(coerce(FieldType::Number, json!("NaN")), None);
+        assert_eq!(coerce(FieldType::Number, json!(true)), None);
+        assert_eq!(coerce(FieldType::Keyword, json!(42)), Some(json!("42")));
+        assert_eq!(coerce(FieldType::Keyword, json!(false)), Some(json!("false")));
+        assert_eq!(coerce(FieldType::Boolean, json!("TRUE")), Some(json!(true)));
+        assert_eq!(coerce(FieldType::Boolean, json!(1)), None);
+        assert_eq!(coerce(FieldType::Boolean, json!(["yes"])), Some(json!(["yes"])));
+        assert_eq!(coerce(FieldType::Number, json!("")), Some(json!("")));
+        assert_eq!(coerce(FieldType::Number, json!(null)), Some(json!(null)));
+    }
+}
@@ -2,5 +2,5 @@ mod facet_type;
 mod facet_value;
 pub mod value_encoding;
 
-pub use self::facet_type::FacetType;
+pub use self::facet_type::{FacetType, FieldType};
 pub use self::facet_value::FacetValue;
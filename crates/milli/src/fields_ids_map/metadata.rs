@@ -6,10 +6,12 @@ use heed::RoTxn;
 
 use super::FieldsIdsMap;
 use crate::attribute_patterns::{match_field_legacy, PatternMatch};
-use crate::constants::{RESERVED_GEO_FIELD_NAME, RESERVED_VECTORS_FIELD_NAME};
+use crate::constants::{
+    RESERVED_EXPIRES_AT_FIELD_NAME, RESERVED_GEO_FIELD_NAME, RESERVED_VECTORS_FIELD_NAME,
+};
 use crate::{
-    is_faceted_by, FieldId, FilterableAttributesFeatures, FilterableAttributesRule, Index,
-    LocalizedAttributesRule, Result, Weight,
+    is_faceted_by, FieldId, FieldType, FilterableAttributesFeatures, FilterableAttributesRule,
+    Index, LocalizedAttributesRule, Result, Weight,
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -24,10 +26,14 @@ pub struct Metadata {
     pub asc_desc: bool,
     /// The field is a geo field (`_geo`, `_geo.lat`, `_geo.lng`).
     pub geo: bool,
+    /// The field is the expiration date of the document (`_expiresAt`).
+    pub expires_at: bool,
     /// The id of the localized attributes rule if the field is localized.
     pub localized_attributes_rule_id: Option<NonZeroU16>,
     /// The id of the filterable attributes rule if the field is filterable.
     pub filterable_attributes_rule_id: Option<NonZeroU16>,
+    /// The type declared for the field in the `fieldTypes` setting.
+    pub field_type: Option<FieldType>,
 }
 
 #[derive(Debug, Clone)]
@@ -181,9 +187,17 @@ impl Metadata {
         self.geo
     }
 
-    /// Returns `true` if the field is part of the facet databases. (sortable, distinct, asc_desc, filterable or facet searchable)
+    pub fn is_expires_at(&self) -> bool {
+        self.expires_at
+    }
+
+    pub fn field_type(&self) -> Option<FieldType> {
+        self.field_type
+    }
+
+    /// Returns `true` if the field is part of the facet databases. (sortable, distinct, asc_desc, filterable, facet searchable or `_expiresAt`)
     pub fn is_faceted(&self, rules: &[FilterableAttributesRule]) -> bool {
-        if self.is_distinct() || self.is_sortable() || self.is_asc_desc() {
+        if self.is_distinct() || self.is_sortable() || self.is_asc_desc() || self.is_expires_at() {
             return true;
         }
 
@@ -198,7 +212,10 @@ impl Metadata {
     pub fn require_facet_level_database(&self, rules: &[FilterableAttributesRule]) -> bool {
         let features = self.filterable_attributes_features(rules);
 
-        self.is_sortable() || self.is_asc_desc() || features.is_filterable_comparison()
+        self.is_sortable()
+            || self.is_asc_desc()
+            || self.is_expires_at()
+            || features.is_filterable_comparison()
     }
 }
 
@@ -210,6 +227,7 @@ pub struct MetadataBuilder {
     localized_attributes: Option<Vec<LocalizedAttributesRule>>,
     distinct_attribute: Option<String>,
     asc_desc_attributes: HashSet<String>,
+    field_types: BTreeMap<String, FieldType>,
 }
 
 impl MetadataBuilder {
@@ -222,6 +240,7 @@ impl MetadataBuilder {
         let localized_attributes = index.localized_attributes_rules(rtxn)?;
         let distinct_attribute = index.distinct_field(rtxn)?.map(|s| s.to_string());
         let asc_desc_attributes = index.asc_desc_fields(rtxn)?;
+        let field_types = index.field_types(rtxn)?;
 
         Ok(Self::new(
             searchable_attributes,
@@ -230,6 +249,7 @@ impl MetadataBuilder {
             localized_attributes,
             distinct_attribute,
             asc_desc_attributes,
+            field_types,
         ))
     }
 
@@ -243,6 +263,7 @@ impl MetadataBuilder {
         localized_attributes: Option<Vec<LocalizedAttributesRule>>,
         distinct_attribute: Option<String>,
         asc_desc_attributes: HashSet<String>,
+        field_types: BTreeMap<String, FieldType>,
     ) -> Self {
         let searchable_attributes = match searchable_attributes {
             Some(fields) if fields.iter().any(|f| f == "*") => None,
@@ -257,6 +278,7 @@ impl MetadataBuilder {
             localized_attributes,
             distinct_attribute,
             asc_desc_attributes,
+            field_types,
         }
     }
 
@@ -269,8 +291,10 @@ impl MetadataBuilder {
                 distinct: false,
                 asc_desc: false,
                 geo: false,
+                expires_at: false,
                 localized_attributes_rule_id: None,
                 filterable_attributes_rule_id: None,
+                field_type: None,
             };
         }
 
@@ -295,8 +319,10 @@ impl MetadataBuilder {
                 distinct: false,
                 asc_desc: false,
                 geo: true,
+                expires_at: false,
                 localized_attributes_rule_id: None,
                 filterable_attributes_rule_id,
+                field_type: None,
             };
         }
 
@@ -313,6 +339,8 @@ impl MetadataBuilder {
         let distinct =
             self.distinct_attribute.as_ref().is_some_and(|distinct_field| field == distinct_field);
         let asc_desc = self.asc_desc_attributes.contains(field);
+        // The expiration date is always faceted so that expired documents can be found
+        let expires_at = field == RESERVED_EXPIRES_AT_FIELD_NAME;
 
         let localized_attributes_rule_id = self
             .localized_attributes
@@ -322,14 +350,18 @@ impl MetadataBuilder {
             // saturating_add(1): make `id` `NonZero`
             .map(|id| NonZeroU16::new(id.saturating_add(1).try_into().unwrap()).unwrap());
 
+        let field_type = self.field_types.get(field).copied();
+
         Metadata {
             searchable,
             sortable,
             distinct,
             asc_desc,
             geo: false,
+            expires_at,
             localized_attributes_rule_id,
             filterable_attributes_rule_id,
+            field_type,
         }
     }
 
@@ -5,7 +5,7 @@ use utoipa::ToSchema;
 
 use crate::{
     attribute_patterns::{match_distinct_field, match_field_legacy, PatternMatch},
-    constants::RESERVED_GEO_FIELD_NAME,
+    constants::{RESERVED_EXPIRES_AT_FIELD_NAME, RESERVED_GEO_FIELD_NAME},
     AttributePatterns,
 };
 
@@ -303,6 +303,11 @@ pub fn match_faceted_field(
     asc_desc_fields: &HashSet<String>,
     distinct_field: &Option<String>,
 ) -> PatternMatch {
+    // The expiration date of the documents is always faceted
+    if field_name == RESERVED_EXPIRES_AT_FIELD_NAME {
+        return PatternMatch::Match;
+    }
+
     // Check if the field matches any filterable or facet searchable field
     let mut selection = match_pattern_by_features(field_name, filterable_fields, &|features| {
         features.is_facet_searchable() || features.is_filterable()
@@ -1,6 +1,7 @@
 use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs::File;
+use std::ops::Bound;
 use std::path::Path;
 
 use heed::types::*;
@@ -10,7 +11,10 @@ use roaring::RoaringBitmap;
 use rstar::RTree;
 use serde::{Deserialize, Serialize};
 
-use crate::constants::{self, RESERVED_GEO_FIELD_NAME, RESERVED_VECTORS_FIELD_NAME};
+use crate::constants::{
+    self, RESERVED_EXPIRES_AT_FIELD_NAME, RESERVED_GEO_FIELD_NAME, RESERVED_VECTORS_FIELD_NAME,
+    RESERVED_VERSION_FIELD_NAME,
+};
 use crate::database_stats::DatabaseStats;
 use crate::documents::PrimaryKey;
 use crate::error::{InternalError, UserError};
@@ -21,16 +25,18 @@ use crate::heed_codec::facet::{
     FieldIdCodec, OrderedF64Codec,
 };
 use crate::heed_codec::version::VersionCodec;
-use crate::heed_codec::{BEU16StrCodec, FstSetCodec, StrBEU16Codec, StrRefCodec};
+use crate::heed_codec::{BEU16StrCodec, BytesRefCodec, FstSetCodec, StrBEU16Codec, StrRefCodec};
 use crate::order_by_map::OrderByMap;
+use crate::prompt::PromptData;
 use crate::proximity::ProximityPrecision;
+use crate::search::facet::{find_docids_of_facet_within_bounds, get_first_facet_value};
 use crate::vector::{ArroyStats, ArroyWrapper, Embedding, EmbeddingConfig};
 use crate::{
-    default_criteria, CboRoaringBitmapCodec, Criterion, DocumentId, ExternalDocumentsIds,
-    FacetDistribution, FieldDistribution, FieldId, FieldIdMapMissingEntry, FieldIdWordCountCodec,
-    FieldidsWeightsMap, FilterableAttributesRule, GeoPoint, LocalizedAttributesRule, ObkvCodec,
-    Result, RoaringBitmapCodec, RoaringBitmapLenCodec, Search, U8StrStrCodec, Weight, BEU16, BEU32,
-    BEU64,
+    default_criteria, CboRoaringBitmapCodec, Criterion, DocumentId, DocumentSchemaSettings,
+    ExternalDocumentsIds, FacetDistribution, FieldDistribution, FieldId, FieldIdMapMissingEntry,
+    FieldIdWordCountCodec, FieldType, FieldidsWeightsMap, FilterableAttributesRule, GeoPoint,
+    IngestProcessor, LocalizedAttributesRule, ObkvCodec, Result, RoaringBitmapCodec,
+    RoaringBitmapLenCodec, Search, U8StrStrCodec, Weight, BEU16, BEU32, BEU64,
 };
 
 pub const DEFAULT_MIN_WORD_LEN_ONE_TYPO: u8 = 5;
@@ -77,8 +83,15 @@ pub mod main_key {
     pub const LOCALIZED_ATTRIBUTES_RULES: &str = "localized_attributes_rules";
     pub const FACET_SEARCH: &str = "facet_search";
     pub const PREFIX_SEARCH: &str = "prefix_search";
+    pub const CHAT: &str = "chat";
+    pub const INGEST_PIPELINE: &str = "ingest_pipeline";
+    pub const DOCUMENT_SCHEMA: &str = "document_schema";
+    pub const FIELD_TYPES: &str = "field_types";
+    pub const GENERATE_DOCUMENT_IDS: &str = "generate_document_ids";
     pub const DOCUMENTS_STATS: &str = "documents_stats";
     pub const DISABLED_TYPOS_TERMS: &str = "disabled_typos_terms";
+    pub const EXPIRES_AT_FIELD_ID: &str = "expires_at_field_id";
+    pub const LAST_APPLIED_TRANSACTION: &str = "last_applied_transaction";
 }
 
 pub mod db_name {
@@ -106,9 +119,10 @@ pub mod db_name {
     pub const FIELD_ID_DOCID_FACET_STRINGS: &str = "field-id-docid-facet-strings";
     pub const VECTOR_EMBEDDER_CATEGORY_ID: &str = "vector-embedder-category-id";
     pub const VECTOR_ARROY: &str = "vector-arroy";
+    pub const VECTOR_SPARSE: &str = "vector-sparse";
     pub const DOCUMENTS: &str = "documents";
 }
-const NUMBER_OF_DBS: u32 = 25;
+const NUMBER_OF_DBS: u32 = 26;
 
 #[derive(Clone)]
 pub struct Index {
@@ -173,6 +187,8 @@ pub struct Index {
     pub embedder_category_id: Database<Str, U8>,
     /// Vector store based on arroy™.
     pub vector_arroy: arroy::Database<Unspecified>,
+    /// Inverted index of the sparse vectors, see [`crate::vector::sparse::SparseVectorStore`].
+    pub vector_sparse: Database<Bytes, Bytes>,
 
     /// Maps the document id to the document as an obkv store.
     pub(crate) documents: Database<BEU32, ObkvCodec>,
@@ -230,6 +246,7 @@ impl Index {
         let embedder_category_id =
             env.create_database(&mut wtxn, Some(VECTOR_EMBEDDER_CATEGORY_ID))?;
         let vector_arroy = env.create_database(&mut wtxn, Some(VECTOR_ARROY))?;
+        let vector_sparse = env.create_database(&mut wtxn, Some(VECTOR_SPARSE))?;
 
         let documents = env.create_database(&mut wtxn, Some(DOCUMENTS))?;
 
@@ -257,6 +274,7 @@ impl Index {
             field_id_docid_facet_f64s,
             field_id_docid_facet_strings,
             vector_arroy,
+            vector_sparse,
             embedder_category_id,
             documents,
         };
@@ -533,6 +551,9 @@ impl Index {
 
     /// Writes the fields ids map which associate the documents keys with an internal field id
     /// (i.e. `u8`), this field id is used to identify fields in the obkv documents.
+    ///
+    /// The field id of `_expiresAt` is also stored on its own so that the searches
+    /// can ignore the expired documents without reading the whole map.
     pub(crate) fn put_fields_ids_map(
         &self,
         wtxn: &mut RwTxn<'_>,
@@ -542,7 +563,16 @@ impl Index {
             wtxn,
             main_key::FIELDS_IDS_MAP_KEY,
             map,
-        )
+        )?;
+        match map.id(RESERVED_EXPIRES_AT_FIELD_NAME) {
+            Some(fid) => {
+                self.main.remap_types::<Str, BEU16>().put(wtxn, main_key::EXPIRES_AT_FIELD_ID, &fid)
+            }
+            None => {
+                self.main.remap_key_type::<Str>().delete(wtxn, main_key::EXPIRES_AT_FIELD_ID)?;
+                Ok(())
+            }
+        }
     }
 
     /// Returns the fields ids map which associate the documents keys with an internal field id
@@ -1353,6 +1383,52 @@ impl Index {
             .map_err(Into::into)
     }
 
+    /// Returns the version of a document, stored in its `_version` field.
+    ///
+    /// Documents that do not exist or are not versioned are at version `0`.
+    pub fn document_version(&self, rtxn: &RoTxn, id: DocumentId) -> Result<u64> {
+        let Some(document) = self.documents.get(rtxn, &id)? else { return Ok(0) };
+        let Some(fid) = self.fields_ids_map(rtxn)?.id(RESERVED_VERSION_FIELD_NAME) else {
+            return Ok(0);
+        };
+        Ok(document.get(fid).and_then(|value| serde_json::from_slice(value).ok()).unwrap_or(0))
+    }
+
+    /// Returns the ids of the documents whose `_expiresAt` timestamp, in seconds since
+    /// the Unix epoch, is lower than or equal to `now`.
+    pub fn expired_documents_ids(&self, rtxn: &RoTxn, now: i64) -> Result<RoaringBitmap> {
+        let mut docids = RoaringBitmap::new();
+        let Some(fid) =
+            self.main.remap_types::<Str, BEU16>().get(rtxn, main_key::EXPIRES_AT_FIELD_ID)?
+        else {
+            return Ok(docids);
+        };
+        let (left, right) = (Bound::Unbounded, Bound::Included(now as f64));
+        find_docids_of_facet_within_bounds::<OrderedF64Codec>(
+            rtxn,
+            self.facet_id_f64_docids,
+            fid,
+            &left,
+            &right,
+            None,
+            &mut docids,
+        )?;
+        Ok(docids)
+    }
+
+    /// Returns the earliest `_expiresAt` timestamp, in seconds since the Unix epoch,
+    /// among the documents of the index, or `None` if no document expires.
+    pub fn earliest_expiration(&self, rtxn: &RoTxn) -> Result<Option<i64>> {
+        let Some(fid) =
+            self.main.remap_types::<Str, BEU16>().get(rtxn, main_key::EXPIRES_AT_FIELD_ID)?
+        else {
+            return Ok(None);
+        };
+        let db = self.facet_id_f64_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>();
+        let first = get_first_facet_value::<OrderedF64Codec, _>(rtxn, db, fid)?;
+        Ok(first.map(|timestamp| timestamp.floor() as i64))
+    }
+
     /// Returns an iterator over the requested documents. The next item will be an error if a document is missing.
     pub fn iter_documents<'a, 't: 'a>(
         &'a self,
@@ -1676,6 +1752,119 @@ impl Index {
         self.main.remap_key_type::<Str>().delete(txn, main_key::PREFIX_SEARCH)
     }
 
+    pub fn chat_config(&self, txn: &RoTxn<'_>) -> heed::Result<ChatConfig> {
+        self.main
+            .remap_types::<Str, SerdeJson<ChatConfig>>()
+            .get(txn, main_key::CHAT)
+            .map(|config| config.unwrap_or_default())
+    }
+
+    pub(crate) fn put_chat_config(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: &ChatConfig,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<ChatConfig>>().put(txn, main_key::CHAT, val)
+    }
+
+    pub(crate) fn delete_chat_config(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::CHAT)
+    }
+
+    pub fn ingest_pipeline(&self, txn: &RoTxn<'_>) -> heed::Result<Vec<IngestProcessor>> {
+        self.main
+            .remap_types::<Str, SerdeJson<Vec<IngestProcessor>>>()
+            .get(txn, main_key::INGEST_PIPELINE)
+            .map(|processors| processors.unwrap_or_default())
+    }
+
+    pub(crate) fn put_ingest_pipeline(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: &[IngestProcessor],
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<&[IngestProcessor]>>().put(
+            txn,
+            main_key::INGEST_PIPELINE,
+            &val,
+        )
+    }
+
+    pub(crate) fn delete_ingest_pipeline(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::INGEST_PIPELINE)
+    }
+
+    pub fn document_schema(&self, txn: &RoTxn<'_>) -> heed::Result<Option<DocumentSchemaSettings>> {
+        self.main
+            .remap_types::<Str, SerdeJson<DocumentSchemaSettings>>()
+            .get(txn, main_key::DOCUMENT_SCHEMA)
+    }
+
+    pub(crate) fn put_document_schema(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: &DocumentSchemaSettings,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<DocumentSchemaSettings>>().put(
+            txn,
+            main_key::DOCUMENT_SCHEMA,
+            val,
+        )
+    }
+
+    pub(crate) fn delete_document_schema(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::DOCUMENT_SCHEMA)
+    }
+
+    /// Returns the types declared for the fields of the documents.
+    pub fn field_types(&self, txn: &RoTxn<'_>) -> heed::Result<BTreeMap<String, FieldType>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<BTreeMap<String, FieldType>>>()
+            .get(txn, main_key::FIELD_TYPES)?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_field_types(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: &BTreeMap<String, FieldType>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<BTreeMap<String, FieldType>>>().put(
+            txn,
+            main_key::FIELD_TYPES,
+            val,
+        )
+    }
+
+    pub(crate) fn delete_field_types(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::FIELD_TYPES)
+    }
+
+    /// Returns whether an id is generated for the documents missing a primary key value.
+    pub fn generate_document_ids(&self, txn: &RoTxn<'_>) -> heed::Result<bool> {
+        self.main
+            .remap_types::<Str, SerdeBincode<bool>>()
+            .get(txn, main_key::GENERATE_DOCUMENT_IDS)
+            .map(|v| v.unwrap_or(false))
+    }
+
+    pub(crate) fn put_generate_document_ids(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: bool,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<bool>>().put(
+            txn,
+            main_key::GENERATE_DOCUMENT_IDS,
+            &val,
+        )
+    }
+
+    pub(crate) fn delete_generate_document_ids(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::GENERATE_DOCUMENT_IDS)
+    }
+
     pub fn facet_search(&self, txn: &RoTxn<'_>) -> heed::Result<bool> {
         self.main
             .remap_types::<Str, SerdeBincode<bool>>()
@@ -1759,6 +1948,27 @@ impl Index {
         self.main.remap_key_type::<Str>().delete(wtxn, main_key::SEARCH_CUTOFF)
     }
 
+    /* last applied transaction */
+
+    /// Records that the changes of the transaction task `task_uid` are part of this index,
+    /// it must be written in the same write transaction as the changes.
+    pub fn put_last_applied_transaction(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        task_uid: u32,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, BEU32>().put(
+            wtxn,
+            main_key::LAST_APPLIED_TRANSACTION,
+            &task_uid,
+        )
+    }
+
+    /// The uid of the last transaction task whose changes were committed in this index.
+    pub fn last_applied_transaction(&self, rtxn: &RoTxn<'_>) -> heed::Result<Option<u32>> {
+        self.main.remap_types::<Str, BEU32>().get(rtxn, main_key::LAST_APPLIED_TRANSACTION)
+    }
+
     pub fn embeddings(
         &self,
         rtxn: &RoTxn<'_>,
@@ -1766,7 +1976,11 @@ impl Index {
     ) -> Result<BTreeMap<String, Vec<Embedding>>> {
         let mut res = BTreeMap::new();
         let embedding_configs = self.embedding_configs(rtxn)?;
-        for config in embedding_configs {
+        // sparse vectors are kept in the documents and have no arroy store
+        for config in embedding_configs
+            .into_iter()
+            .filter(|config| !config.config.embedder_options.is_sparse())
+        {
             let embedder_id = self.embedder_category_id.get(rtxn, &config.name)?.unwrap();
             let reader =
                 ArroyWrapper::new(self.vector_arroy, embedder_id, config.config.quantized());
@@ -1784,7 +1998,11 @@ impl Index {
     pub fn arroy_stats(&self, rtxn: &RoTxn<'_>) -> Result<ArroyStats> {
         let mut stats = ArroyStats::default();
         let embedding_configs = self.embedding_configs(rtxn)?;
-        for config in embedding_configs {
+        // sparse vectors are kept in the documents and have no arroy store
+        for config in embedding_configs
+            .into_iter()
+            .filter(|config| !config.config.embedder_options.is_sparse())
+        {
             let embedder_id = self.embedder_category_id.get(rtxn, &config.name)?.unwrap();
             let reader =
                 ArroyWrapper::new(self.vector_arroy, embedder_id, config.config.quantized());
@@ -1832,6 +2050,7 @@ impl Index {
             field_id_docid_facet_f64s,
             field_id_docid_facet_strings,
             vector_arroy,
+            vector_sparse,
             embedder_category_id,
             documents,
         } = self;
@@ -1903,6 +2122,7 @@ impl Index {
             field_id_docid_facet_strings.stat(rtxn).map(compute_size)?,
         );
         sizes.insert("vector_arroy", vector_arroy.stat(rtxn).map(compute_size)?);
+        sizes.insert("vector_sparse", vector_sparse.stat(rtxn).map(compute_size)?);
         sizes.insert("embedder_category_id", embedder_category_id.stat(rtxn).map(compute_size)?);
         sizes.insert("documents", documents.stat(rtxn).map(compute_size)?);
 
@@ -1932,6 +2152,33 @@ pub enum PrefixSearch {
     Disabled,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatConfig {
+    pub description: String,
+    pub prompt: PromptData,
+    pub search_parameters: ChatSearchConfig,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatSearchConfig {
+    pub limit: Option<usize>,
+    pub hybrid: Option<ChatHybridConfig>,
+    pub ranking_score_threshold: Option<f64>,
+}
+
+impl ChatSearchConfig {
+    pub const DEFAULT_LIMIT: usize = 5;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatHybridConfig {
+    pub embedder: String,
+    pub semantic_ratio: Option<f32>,
+}
+
 #[derive(Debug)]
 pub enum RollbackOutcome {
     VersionMismatch {
@@ -0,0 +1,330 @@
+use deserr::Deserr;
+use rhai::{Dynamic, Engine, OptimizationLevel, Scope, AST};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use time::format_description::well_known::Rfc3339;
+use time::format_description::OwnedFormatItem;
+use time::{Date, OffsetDateTime, PrimitiveDateTime};
+use utoipa::ToSchema;
+
+use crate::constants::RESERVED_VERSION_FIELD_NAME;
+
+/// A transformation applied to the incoming documents of an index, before they are indexed.
+///
+/// The processors of an ingest pipeline are applied in order, on the top-level fields of the documents
+/// as they are sent, so that partial updates are transformed before being merged with the stored documents.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct IngestProcessor {
+    /// The transformation applied by the processor.
+    #[serde(rename = "type")]
+    #[deserr(rename = "type")]
+    pub kind: IngestProcessorKind,
+    /// The field transformed by the processor.
+    pub field: String,
+    /// The field written by the `rename` and `copy` processors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub to: Option<String>,
+    /// The separator used by the `split` processors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub separator: Option<String>,
+    /// The format of the dates parsed by the `parseDate` processors.
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to RFC 3339 dates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    #[schema(example = "[year]-[month]-[day]")]
+    pub format: Option<String>,
+    /// The value set by the `setDefault` processors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    #[schema(value_type = Option<serde_json::Value>)]
+    pub value: Option<Value>,
+    /// The rhai expression computed by the `compute` processors, where `doc` is the document.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    #[schema(example = "doc.firstName + \" \" + doc.lastName")]
+    pub expression: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum IngestProcessorKind {
+    /// Moves the value of `field` to `to`.
+    Rename,
+    /// Removes `field`.
+    Drop,
+    /// Copies the value of `field` to `to`.
+    Copy,
+    /// Lowercases the string, or the strings of the array, of `field`.
+    Lowercase,
+    /// Splits the string of `field` on `separator` into an array of trimmed strings.
+    Split,
+    /// Replaces the date of `field` by its Unix timestamp, in seconds.
+    ParseDate,
+    /// Sets `field` to `value` when it is missing or `null`.
+    SetDefault,
+    /// Sets `field` to the result of `expression`.
+    Compute,
+}
+
+impl IngestProcessorKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            IngestProcessorKind::Rename => "rename",
+            IngestProcessorKind::Drop => "drop",
+            IngestProcessorKind::Copy => "copy",
+            IngestProcessorKind::Lowercase => "lowercase",
+            IngestProcessorKind::Split => "split",
+            IngestProcessorKind::ParseDate => "parseDate",
+            IngestProcessorKind::SetDefault => "setDefault",
+            IngestProcessorKind::Compute => "compute",
+        }
+    }
+}
+
+/// The processors of an index, validated and compiled to be applied to documents.
+pub struct IngestPipeline {
+    processors: Vec<Processor>,
+    engine: Engine,
+}
+
+enum Processor {
+    Rename { field: String, to: String },
+    Drop { field: String },
+    Copy { field: String, to: String },
+    Lowercase { field: String },
+    Split { field: String, separator: String },
+    ParseDate { field: String, format: Option<OwnedFormatItem> },
+    SetDefault { field: String, value: Value },
+    Compute { field: String, ast: AST },
+}
+
+impl IngestPipeline {
+    /// Validates and compiles the processors.
+    ///
+    /// On error, returns the position of the invalid processor along with the reason.
+    pub fn new(processors: &[IngestProcessor]) -> Result<Self, (usize, String)> {
+        let engine = engine();
+        let processors = processors
+            .iter()
+            .enumerate()
+            .map(|(i, processor)| Processor::new(processor, &engine).map_err(|e| (i, e)))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { processors, engine })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.processors.is_empty()
+    }
+
+    /// Applies the processors, in order, to the document.
+    ///
+    /// The processors are applied before the primary key of the document is read,
+    /// they can produce it, for instance by renaming a field.
+    pub fn apply(&self, document: &mut Map<String, Value>) -> Result<(), String> {
+        for processor in &self.processors {
+            processor.apply(document, &self.engine)?;
+        }
+        Ok(())
+    }
+}
+
+impl Processor {
+    fn new(processor: &IngestProcessor, engine: &Engine) -> Result<Self, String> {
+        let IngestProcessor { kind, field, to, separator, format, value, expression } = processor;
+        let kind_name = kind.name();
+
+        let check_unused = |name: &str, is_set: bool| {
+            if is_set {
+                Err(format!("`{name}` cannot be used by a `{kind_name}` processor"))
+            } else {
+                Ok(())
+            }
+        };
+        let required = |name: &str, value: &Option<String>| {
+            value.clone().ok_or_else(|| format!("a `{kind_name}` processor requires `{name}`"))
+        };
+
+        if field.is_empty() {
+            return Err(String::from("`field` cannot be empty"));
+        }
+        let field = field.clone();
+
+        if !matches!(kind, IngestProcessorKind::Rename | IngestProcessorKind::Copy) {
+            check_unused("to", to.is_some())?;
+        }
+        if !matches!(kind, IngestProcessorKind::Split) {
+            check_unused("separator", separator.is_some())?;
+        }
+        if !matches!(kind, IngestProcessorKind::ParseDate) {
+            check_unused("format", format.is_some())?;
+        }
+        if !matches!(kind, IngestProcessorKind::SetDefault) {
+            check_unused("value", value.is_some())?;
+        }
+        if !matches!(kind, IngestProcessorKind::Compute) {
+            check_unused("expression", expression.is_some())?;
+        }
+
+        let processor = match kind {
+            IngestProcessorKind::Rename => Processor::Rename { field, to: required("to", to)? },
+            IngestProcessorKind::Drop => Processor::Drop { field },
+            IngestProcessorKind::Copy => Processor::Copy { field, to: required("to", to)? },
+            IngestProcessorKind::Lowercase => Processor::Lowercase { field },
+            IngestProcessorKind::Split => {
+                let separator = required("separator", separator)?;
+                if separator.is_empty() {
+                    return Err(String::from("`separator` cannot be empty"));
+                }
+                Processor::Split { field, separator }
+            }
+            IngestProcessorKind::ParseDate => {
+                let format = format
+                    .as_deref()
+                    .map(time::format_description::parse_owned::<1>)
+                    .transpose()
+                    .map_err(|e| format!("invalid `format`: {e}"))?;
+                Processor::ParseDate { field, format }
+            }
+            IngestProcessorKind::SetDefault => {
+                let value = value
+                    .clone()
+                    .ok_or_else(|| format!("a `{kind_name}` processor requires `value`"))?;
+                Processor::SetDefault { field, value }
+            }
+            IngestProcessorKind::Compute => {
+                let expression = required("expression", expression)?;
+                let ast = engine
+                    .compile_expression(expression)
+                    .map_err(|e| format!("invalid `expression`: {e}"))?;
+                Processor::Compute { field, ast }
+            }
+        };
+
+        if processor.writes(RESERVED_VERSION_FIELD_NAME) {
+            return Err(format!(
+                "`{RESERVED_VERSION_FIELD_NAME}` cannot be modified by a processor"
+            ));
+        }
+        Ok(processor)
+    }
+
+    /// Whether the processor modifies or removes the given field.
+    fn writes(&self, name: &str) -> bool {
+        match self {
+            Processor::Rename { field, to } => field == name || to == name,
+            Processor::Copy { to, .. } => to == name,
+            Processor::Drop { field }
+            | Processor::Lowercase { field }
+            | Processor::Split { field, .. }
+            | Processor::ParseDate { field, .. }
+            | Processor::SetDefault { field, .. }
+            | Processor::Compute { field, .. } => field == name,
+        }
+    }
+
+    fn apply(&self, document: &mut Map<String, Value>, engine: &Engine) -> Result<(), String> {
+        match self {
+            Processor::Rename { field, to } => {
+                if let Some(value) = document.remove(field) {
+                    document.insert(to.clone(), value);
+                }
+            }
+            Processor::Drop { field } => {
+                document.remove(field);
+            }
+            Processor::Copy { field, to } => {
+                if let Some(value) = document.get(field).cloned() {
+                    document.insert(to.clone(), value);
+                }
+            }
+            Processor::Lowercase { field } => match document.get_mut(field) {
+                Some(Value::String(s)) => *s = s.to_lowercase(),
+                Some(Value::Array(values)) => {
+                    for value in values {
+                        if let Value::String(s) = value {
+                            *s = s.to_lowercase();
+                        }
+                    }
+                }
+                _ => (),
+            },
+            Processor::Split { field, separator } => {
+                if let Some(value @ Value::String(_)) = document.get_mut(field) {
+                    let Value::String(s) = value.take() else { unreachable!() };
+                    *value = s
+                        .split(separator.as_str())
+                        .map(str::trim)
+                        .filter(|part| !part.is_empty())
+                        .map(|part| Value::String(part.to_string()))
+                        .collect();
+                }
+            }
+            Processor::ParseDate { field, format } => match document.get_mut(field) {
+                Some(value @ Value::String(_)) => {
+                    let timestamp = parse_date(value.as_str().unwrap(), format.as_ref())
+                        .ok_or_else(|| format!("`{field}` is not a valid date: {value}"))?;
+                    *value = Value::from(timestamp);
+                }
+                // missing fields, nulls and timestamps are left untouched
+                None | Some(Value::Null | Value::Number(_)) => (),
+                Some(value) => return Err(format!("`{field}` is not a valid date: {value}")),
+            },
+            Processor::SetDefault { field, value } => match document.get(field) {
+                None | Some(Value::Null) => {
+                    document.insert(field.clone(), value.clone());
+                }
+                Some(_) => (),
+            },
+            Processor::Compute { field, ast } => {
+                let doc = rhai::serde::to_dynamic(&*document).map_err(|e| e.to_string())?;
+                let mut scope = Scope::new();
+                scope.push_constant_dynamic("doc", doc);
+                let result = engine
+                    .eval_ast_with_scope::<Dynamic>(&mut scope, ast)
+                    .map_err(|e| format!("could not compute `{field}`: {e}"))?;
+                // an expression evaluating to `()` does not set the field
+                if !result.is_unit() {
+                    let result = serde_json::to_value(&result).map_err(|e| e.to_string())?;
+                    document.insert(field.clone(), result);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parses a date to a Unix timestamp, in seconds.
+///
+/// Dates without an offset are considered UTC, and dates without a time are considered at midnight.
+fn parse_date(date: &str, format: Option<&OwnedFormatItem>) -> Option<i64> {
+    let date = match format {
+        None => OffsetDateTime::parse(date, &Rfc3339).ok()?,
+        Some(format) => OffsetDateTime::parse(date, format)
+            .or_else(|_| PrimitiveDateTime::parse(date, format).map(PrimitiveDateTime::assume_utc))
+            .or_else(|_| Date::parse(date, format).map(|date| date.midnight().assume_utc()))
+            .ok()?,
+    };
+    Some(date.unix_timestamp())
+}
+
+fn engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_optimization_level(OptimizationLevel::Full);
+    engine.set_max_call_levels(1000);
+    engine.set_max_operations(1_000_000);
+    engine.set_max_variables(1000);
+    engine.set_max_functions(30);
+    engine.set_max_expr_depths(100, 1000);
+    engine.set_max_string_size(1024 * 1024 * 1024); // 1 GiB
+    engine.set_max_array_size(10_000);
+    engine.set_max_map_size(10_000);
+    engine
+}
@@ -13,6 +13,7 @@ mod attribute_patterns;
 mod criterion;
 pub mod database_stats;
 pub mod disabled_typos_terms;
+mod document_schema;
 mod error;
 mod external_documents_ids;
 pub mod facet;
@@ -20,6 +21,7 @@ mod fields_ids_map;
 mod filterable_attributes_rules;
 pub mod heed_codec;
 pub mod index;
+mod ingest_pipeline;
 mod localized_attributes_rules;
 pub mod order_by_map;
 pub mod prompt;
@@ -58,10 +60,14 @@ pub use self::asc_desc::{AscDesc, AscDescError, Member, SortError};
 pub use self::attribute_patterns::AttributePatterns;
 pub use self::attribute_patterns::PatternMatch;
 pub use self::criterion::{default_criteria, Criterion, CriterionError};
+pub use self::document_schema::{
+    DocumentSchema, DocumentSchemaSettings, OnInvalidDocument, SchemaViolation,
+};
 pub use self::error::{
     Error, FieldIdMapMissingEntry, InternalError, SerializationError, UserError,
 };
 pub use self::external_documents_ids::ExternalDocumentsIds;
+pub use self::facet::FieldType;
 pub use self::fieldids_weights_map::FieldidsWeightsMap;
 pub use self::fields_ids_map::{FieldsIdsMap, GlobalFieldsIdsMap};
 pub use self::filterable_attributes_rules::{
@@ -75,7 +81,9 @@ pub use self::heed_codec::{
     UncheckedU8StrStrCodec,
 };
 pub use self::index::Index;
+pub use self::ingest_pipeline::{IngestPipeline, IngestProcessor, IngestProcessorKind};
 pub use self::localized_attributes_rules::LocalizedAttributesRule;
+pub use self::search::diversity::Diversify;
 pub use self::search::facet::{FacetValueHit, SearchForFacetValues};
 pub use self::search::similar::Similar;
 pub use self::search::{
@@ -8,6 +8,7 @@ use std::cell::RefCell;
 use std::convert::TryFrom;
 use std::fmt::Debug;
 use std::num::NonZeroUsize;
+use std::sync::RwLock;
 
 use bumpalo::Bump;
 use document::ParseableDocument;
@@ -18,6 +19,7 @@ use self::context::Context;
 use self::document::Document;
 use crate::fields_ids_map::metadata::FieldIdMapWithMetadata;
 use crate::update::del_add::DelAdd;
+use crate::update::new::document::DocumentFromDb;
 use crate::GlobalFieldsIdsMap;
 
 pub struct Prompt {
@@ -140,6 +142,31 @@ impl Prompt {
             .expect("render can only write UTF-8 because all inputs and processing preserve utf-8"))
     }
 
+    /// Renders a document stored in the index, truncated to the maximum number of bytes of the prompt.
+    ///
+    /// Returns `None` if there is no document with this internal id.
+    pub fn render_stored_document(
+        &self,
+        index: &crate::Index,
+        rtxn: &heed::RoTxn<'_>,
+        docid: crate::DocumentId,
+        external_docid: &str,
+    ) -> crate::Result<Option<String>> {
+        let doc_alloc = Bump::new();
+        let db_fields_ids_map = index.fields_ids_map(rtxn)?;
+        let Some(document) = DocumentFromDb::new(docid, rtxn, index, &db_fields_ids_map)? else {
+            return Ok(None);
+        };
+        let fields_ids_map = RwLock::new(index.fields_ids_map_with_metadata(rtxn)?);
+        let global_fields_ids_map = RefCell::new(GlobalFieldsIdsMap::new(&fields_ids_map));
+        let rendered =
+            self.render_document(external_docid, document, &global_fields_ids_map, &doc_alloc)?;
+
+        let mut rendered = rendered.to_owned();
+        truncate(&mut rendered, self.max_bytes.unwrap_or_else(default_max_bytes).get());
+        Ok(Some(rendered))
+    }
+
     pub fn render_kvdeladd(
         &self,
         document: &obkv::KvReaderU16,
@@ -0,0 +1,167 @@
+use heed::RoTxn;
+
+use crate::score_details::ScoreDetails;
+use crate::vector::{ArroyWrapper, Embedding};
+use crate::{DocumentId, Index, Result, UserError};
+
+/// Number of candidates fetched for each requested result when diversifying.
+///
+/// MMR can only reorder the documents it is given, so it needs a window of candidates
+/// larger than the page to pick varied documents from.
+pub const DIVERSITY_CANDIDATES_FACTOR: usize = 4;
+
+/// Reorders ranked documents using maximal marginal relevance (MMR).
+///
+/// Documents are picked greedily, each time selecting the document maximizing
+/// `lambda * relevance - (1 - lambda) * similarity`, where `similarity` is the highest cosine similarity
+/// between the stored embeddings of the document and those of the documents already picked.
+/// A `lambda` of `1.0` keeps the original order, while a `lambda` of `0.0` only favors variety.
+pub struct Diversify<'a> {
+    index: &'a Index,
+    rtxn: &'a RoTxn<'a>,
+    embedder_name: &'a str,
+    lambda: f32,
+}
+
+impl<'a> Diversify<'a> {
+    pub fn new(index: &'a Index, rtxn: &'a RoTxn<'a>, embedder_name: &'a str, lambda: f32) -> Self {
+        Self { index, rtxn, embedder_name, lambda }
+    }
+
+    /// The number of candidates to retrieve to return `offset + limit` diversified documents.
+    pub fn window(offset: usize, limit: usize) -> usize {
+        offset.saturating_add(limit).saturating_mul(DIVERSITY_CANDIDATES_FACTOR)
+    }
+
+    /// Reorders the `documents_ids` and their `document_scores`, keeping only the documents
+    /// between `offset` and `offset + limit` in the diversified order.
+    pub fn execute(
+        &self,
+        documents_ids: Vec<DocumentId>,
+        document_scores: Vec<Vec<ScoreDetails>>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<(Vec<DocumentId>, Vec<Vec<ScoreDetails>>)> {
+        let count = offset.saturating_add(limit).min(documents_ids.len());
+        if count == 0 {
+            return Ok((Vec::new(), Vec::new()));
+        }
+
+        let embedder_id = self
+            .index
+            .embedder_category_id
+            .get(self.rtxn, self.embedder_name)?
+            .ok_or_else(|| UserError::InvalidSearchEmbedder(self.embedder_name.to_owned()))?;
+        let quantized = self
+            .index
+            .embedding_configs(self.rtxn)?
+            .into_iter()
+            .find(|config| config.name == self.embedder_name)
+            .is_some_and(|config| config.config.quantized());
+        let reader = ArroyWrapper::new(self.index.vector_arroy, embedder_id, quantized);
+
+        let mut remaining = Vec::with_capacity(documents_ids.len());
+        for (docid, scores) in documents_ids.into_iter().zip(document_scores) {
+            let relevance = ScoreDetails::global_score(scores.iter()) as f32;
+            let embeddings: Vec<Embedding> = reader
+                .item_vectors(self.rtxn, docid)?
+                .into_iter()
+                .map(|mut embedding| {
+                    normalize(&mut embedding);
+                    embedding
+                })
+                .collect();
+            remaining.push(Candidate { docid, scores, relevance, embeddings, similarity: 0.0 });
+        }
+
+        let mut documents_ids = Vec::with_capacity(count);
+        let mut document_scores = Vec::with_capacity(count);
+        while documents_ids.len() < count {
+            let Some(best) = remaining
+                .iter()
+                .enumerate()
+                .map(|(i, candidate)| (i, candidate.mmr(self.lambda)))
+                // ties are resolved in favor of the most relevant document, which comes first
+                .fold(None, |best: Option<(usize, f32)>, (i, mmr)| match best {
+                    Some((_, best_mmr)) if best_mmr >= mmr => best,
+                    _ => Some((i, mmr)),
+                })
+                .map(|(i, _)| i)
+            else {
+                break;
+            };
+
+            // `remove` rather than `swap_remove` to keep the candidates in relevance order
+            let selected = remaining.remove(best);
+            for candidate in &mut remaining {
+                let similarity = max_similarity(&candidate.embeddings, &selected.embeddings);
+                candidate.similarity = candidate.similarity.max(similarity);
+            }
+
+            documents_ids.push(selected.docid);
+            document_scores.push(selected.scores);
+        }
+
+        let documents_ids = documents_ids.into_iter().skip(offset).collect();
+        let document_scores = document_scores.into_iter().skip(offset).collect();
+        Ok((documents_ids, document_scores))
+    }
+}
+
+struct Candidate {
+    docid: DocumentId,
+    scores: Vec<ScoreDetails>,
+    relevance: f32,
+    /// normalized embeddings of the document
+    embeddings: Vec<Embedding>,
+    /// highest similarity with the documents selected so far
+    similarity: f32,
+}
+
+impl Candidate {
+    fn mmr(&self, lambda: f32) -> f32 {
+        lambda * self.relevance - (1.0 - lambda) * self.similarity
+    }
+}
+
+/// The highest cosine similarity between any pair of normalized embeddings,
+/// or `0.0` if one of the documents has no embedding.
+fn max_similarity(left: &[Embedding], right: &[Embedding]) -> f32 {
+    left.iter()
+        .flat_map(|left| {
+            right.iter().map(move |right| left.iter().zip(right).map(|(l, r)| l * r).sum::<f32>())
+        })
+        .fold(0.0, f32::max)
+}
+
+fn normalize(embedding: &mut Embedding) {
+    let norm = embedding.iter().map(|value| value * value).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        embedding.iter_mut().for_each(|value| *value /= norm);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f32::consts::FRAC_1_SQRT_2;
+
+    use super::*;
+
+    #[test]
+    fn similarity_of_normalized_embeddings() {
+        let mut a = vec![1.0, 0.0];
+        let mut b = vec![3.0, 3.0];
+        let mut c = vec![0.0, -2.0];
+        normalize(&mut a);
+        normalize(&mut b);
+        normalize(&mut c);
+
+        assert_eq!(max_similarity(&[a.clone()], &[a.clone()]), 1.0);
+        assert!((max_similarity(&[a.clone()], &[b.clone()]) - FRAC_1_SQRT_2).abs() < 1e-6);
+        // opposite embeddings don't make a document look less similar than having no embedding
+        assert_eq!(max_similarity(&[a.clone()], &[c.clone()]), 0.0);
+        assert_eq!(max_similarity(&[], &[a.clone()]), 0.0);
+        // the closest pair of embeddings wins
+        assert_eq!(max_similarity(&[c, a.clone()], &[a]), 1.0);
+    }
+}
@@ -1,4 +1,5 @@
-use std::collections::BTreeSet;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::{Debug, Display};
 use std::ops::Bound::{self, Excluded, Included, Unbounded};
 
@@ -11,7 +12,7 @@ use roaring::{MultiOps, RoaringBitmap};
 use serde_json::Value;
 
 use super::facet_range_search;
-use crate::constants::RESERVED_GEO_FIELD_NAME;
+use crate::constants::{RESERVED_EXPIRES_AT_FIELD_NAME, RESERVED_GEO_FIELD_NAME};
 use crate::error::{Error, UserError};
 use crate::filterable_attributes_rules::{filtered_matching_patterns, matching_features};
 use crate::heed_codec::facet::{
@@ -19,7 +20,7 @@ use crate::heed_codec::facet::{
 };
 use crate::index::db_name::FACET_ID_STRING_DOCIDS;
 use crate::{
-    distance_between_two_points, lat_lng_to_xyz, FieldId, FieldsIdsMap,
+    distance_between_two_points, lat_lng_to_xyz, FieldId, FieldType, FieldsIdsMap,
     FilterableAttributesFeatures, FilterableAttributesRule, Index, InternalError, Result,
     SerializationError,
 };
@@ -237,7 +238,7 @@ impl<'a> Filter<'a> {
         let filterable_attributes_rules = index.filterable_attributes_rules(rtxn)?;
         for fid in self.condition.fids(MAX_FILTER_DEPTH) {
             let attribute = fid.value();
-            if matching_features(attribute, &filterable_attributes_rules)
+            if filter_features(attribute, &filterable_attributes_rules)
                 .is_some_and(|(_, features)| features.is_filterable())
             {
                 continue;
@@ -253,7 +254,15 @@ impl<'a> Filter<'a> {
             }))?;
         }
 
-        self.inner_evaluate(rtxn, index, &fields_ids_map, &filterable_attributes_rules, None)
+        let field_types = index.field_types(rtxn)?;
+        self.inner_evaluate(
+            rtxn,
+            index,
+            &fields_ids_map,
+            &filterable_attributes_rules,
+            &field_types,
+            None,
+        )
     }
 
     fn evaluate_operator(
@@ -498,6 +507,7 @@ impl<'a> Filter<'a> {
         index: &Index,
         field_ids_map: &FieldsIdsMap,
         filterable_attribute_rules: &[FilterableAttributesRule],
+        field_types: &BTreeMap<String, FieldType>,
         universe: Option<&RoaringBitmap>,
     ) -> Result<RoaringBitmap> {
         if universe.is_some_and(|u| u.is_empty()) {
@@ -512,6 +522,7 @@ impl<'a> Filter<'a> {
                     index,
                     field_ids_map,
                     filterable_attribute_rules,
+                    field_types,
                     universe,
                 )?;
                 match universe {
@@ -527,13 +538,15 @@ impl<'a> Filter<'a> {
                     return Ok(RoaringBitmap::new());
                 };
                 let Some((rule_index, features)) =
-                    matching_features(fid.value(), filterable_attribute_rules)
+                    filter_features(fid.value(), filterable_attribute_rules)
                 else {
                     return Ok(RoaringBitmap::new());
                 };
 
+                let is_date = field_types.get(fid.value()) == Some(&FieldType::Date);
                 els.iter()
-                    .map(|el| Condition::Equal(el.clone()))
+                    .map(|el| if is_date { date_token(el) } else { el.clone() })
+                    .map(Condition::Equal)
                     .map(|op| {
                         Self::evaluate_operator(
                             rtxn, index, field_id, universe, &op, &features, rule_index,
@@ -546,12 +559,17 @@ impl<'a> Filter<'a> {
                     return Ok(RoaringBitmap::new());
                 };
                 let Some((rule_index, features)) =
-                    matching_features(fid.value(), filterable_attribute_rules)
+                    filter_features(fid.value(), filterable_attribute_rules)
                 else {
                     return Ok(RoaringBitmap::new());
                 };
 
-                Self::evaluate_operator(rtxn, index, field_id, universe, op, &features, rule_index)
+                let op = match field_types.get(fid.value()) {
+                    Some(FieldType::Date) => Cow::Owned(date_condition(op)),
+                    _ => Cow::Borrowed(op),
+                };
+
+                Self::evaluate_operator(rtxn, index, field_id, universe, &op, &features, rule_index)
             }
             FilterCondition::Or(subfilters) => subfilters
                 .iter()
@@ -563,6 +581,7 @@ impl<'a> Filter<'a> {
                         index,
                         field_ids_map,
                         filterable_attribute_rules,
+                        field_types,
                         universe,
                     )
                 })
@@ -576,6 +595,7 @@ impl<'a> Filter<'a> {
                         index,
                         field_ids_map,
                         filterable_attribute_rules,
+                        field_types,
                         universe,
                     )?;
                     for f in subfilters_iter {
@@ -591,6 +611,7 @@ impl<'a> Filter<'a> {
                             index,
                             field_ids_map,
                             filterable_attribute_rules,
+                            field_types,
                             Some(&bitmap),
                         )?;
                     }
@@ -695,6 +716,7 @@ impl<'a> Filter<'a> {
                         index,
                         field_ids_map,
                         filterable_attribute_rules,
+                        field_types,
                         universe,
                     )?;
 
@@ -729,6 +751,7 @@ impl<'a> Filter<'a> {
                             index,
                             field_ids_map,
                             filterable_attribute_rules,
+                            field_types,
                             universe,
                         )?;
 
@@ -744,6 +767,7 @@ impl<'a> Filter<'a> {
                             index,
                             field_ids_map,
                             filterable_attribute_rules,
+                            field_types,
                             universe,
                         )?;
 
@@ -761,6 +785,7 @@ impl<'a> Filter<'a> {
                             index,
                             field_ids_map,
                             filterable_attribute_rules,
+                            field_types,
                             universe,
                         )?
                     };
@@ -782,6 +807,47 @@ impl<'a> Filter<'a> {
     }
 }
 
+/// Converts the RFC 3339 dates compared to a date field into Unix timestamps.
+fn date_condition<'a>(condition: &Condition<'a>) -> Condition<'a> {
+    match condition {
+        Condition::GreaterThan(token) => Condition::GreaterThan(date_token(token)),
+        Condition::GreaterThanOrEqual(token) => Condition::GreaterThanOrEqual(date_token(token)),
+        Condition::Equal(token) => Condition::Equal(date_token(token)),
+        Condition::NotEqual(token) => Condition::NotEqual(date_token(token)),
+        Condition::LowerThan(token) => Condition::LowerThan(date_token(token)),
+        Condition::LowerThanOrEqual(token) => Condition::LowerThanOrEqual(date_token(token)),
+        Condition::Between { from, to } => {
+            Condition::Between { from: date_token(from), to: date_token(to) }
+        }
+        condition => condition.clone(),
+    }
+}
+
+fn date_token<'a>(token: &Token<'a>) -> Token<'a> {
+    let date = Value::String(token.value().to_string());
+    match FieldType::Date.coerce(&date).as_deref() {
+        Some(Value::Number(timestamp)) => {
+            Token::new(token.original_span(), Some(timestamp.to_string()))
+        }
+        _ => token.clone(),
+    }
+}
+
+/// Returns the index of the rule matching the field and its filterable features.
+///
+/// The expiration date of the documents is always faceted and can be compared,
+/// so that the expired documents can be deleted by filter.
+fn filter_features(
+    field_name: &str,
+    filterable_attribute_rules: &[FilterableAttributesRule],
+) -> Option<(usize, FilterableAttributesFeatures)> {
+    matching_features(field_name, filterable_attribute_rules).or_else(|| {
+        (field_name == RESERVED_EXPIRES_AT_FIELD_NAME).then(|| {
+            (filterable_attribute_rules.len(), FilterableAttributesFeatures::legacy_default())
+        })
+    })
+}
+
 fn generate_filter_error(
     rtxn: &heed::RoTxn<'_>,
     index: &Index,
@@ -7,6 +7,7 @@ use roaring::RoaringBitmap;
 use crate::score_details::{ScoreDetails, ScoreValue, ScoringStrategy};
 use crate::search::new::{distinct_fid, distinct_single_docid};
 use crate::search::SemanticSearch;
+use crate::vector::parsed_vectors::QueryVector;
 use crate::{Index, MatchingWords, Result, Search, SearchResult};
 
 struct ScoreWithRatioResult {
@@ -235,7 +236,12 @@ impl Search<'_> {
         };
 
         let vector_query = match vector {
-            Some(vector_query) => vector_query,
+            Some(vector_query) => Some(vector_query),
+            // the sparse vector is computed from the query when executing the semantic search
+            None if embedder.is_sparse() => {
+                search.query = Some(query);
+                None
+            }
             None => {
                 // attempt to embed the vector
                 let span = tracing::trace_span!(target: "search::hybrid", "embed_one");
@@ -244,7 +250,7 @@ impl Search<'_> {
                 let deadline = std::time::Instant::now() + std::time::Duration::from_secs(3);
 
                 match embedder.embed_search(&query, Some(deadline)) {
-                    Ok(embedding) => embedding,
+                    Ok(embedding) => Some(QueryVector::Single(embedding)),
                     Err(error) => {
                         tracing::error!(error=%error, "Embedding failed");
                         return Ok(return_keyword_results(
@@ -258,7 +264,7 @@ impl Search<'_> {
         };
 
         search.semantic =
-            Some(SemanticSearch { vector: Some(vector_query), embedder_name, embedder, quantized });
+            Some(SemanticSearch { vector: vector_query, embedder_name, embedder, quantized });
 
         // TODO: would be better to have two distinct functions at this point
         let vector_results = search.execute()?;
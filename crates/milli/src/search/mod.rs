@@ -8,9 +8,10 @@ use roaring::bitmap::RoaringBitmap;
 
 pub use self::facet::{FacetDistribution, Filter, OrderBy, DEFAULT_VALUES_PER_FACET};
 pub use self::new::matches::{FormatOptions, MatchBounds, MatcherBuilder, MatchingWords};
-use self::new::{execute_vector_search, PartialSearchResult, VectorStoreStats};
+use self::new::{execute_vector_search, PartialSearchResult, VectorStoreStats, VectorTarget};
 use crate::filterable_attributes_rules::{filtered_matching_patterns, matching_features};
 use crate::score_details::{ScoreDetails, ScoringStrategy};
+use crate::vector::parsed_vectors::QueryVector;
 use crate::vector::Embedder;
 use crate::{
     execute_search, filtered_universe, AscDesc, DefaultSearchLogger, DocumentId, Error, Index,
@@ -22,6 +23,7 @@ static LEVDIST0: Lazy<LevBuilder> = Lazy::new(|| LevBuilder::new(0, true));
 static LEVDIST1: Lazy<LevBuilder> = Lazy::new(|| LevBuilder::new(1, true));
 static LEVDIST2: Lazy<LevBuilder> = Lazy::new(|| LevBuilder::new(2, true));
 
+pub mod diversity;
 pub mod facet;
 mod fst_utils;
 pub mod hybrid;
@@ -30,7 +32,7 @@ pub mod similar;
 
 #[derive(Debug, Clone)]
 pub struct SemanticSearch {
-    vector: Option<Vec<f32>>,
+    vector: Option<QueryVector>,
     embedder_name: String,
     embedder: Arc<Embedder>,
     quantized: bool,
@@ -92,7 +94,7 @@ impl<'a> Search<'a> {
         embedder_name: String,
         embedder: Arc<Embedder>,
         quantized: bool,
-        vector: Option<Vec<f32>>,
+        vector: Option<QueryVector>,
     ) -> &mut Search<'a> {
         self.semantic = Some(SemanticSearch { embedder_name, embedder, quantized, vector });
         self
@@ -231,9 +233,37 @@ impl<'a> Search<'a> {
             used_negative_operator,
         } = match self.semantic.as_ref() {
             Some(SemanticSearch { vector: Some(vector), embedder_name, embedder, quantized }) => {
+                vector.check_dimensions(embedder)?;
+                let target = match vector {
+                    QueryVector::Single(vector) => VectorTarget::Dense(vector.clone()),
+                    QueryVector::Matrix(matrix) => VectorTarget::Multi(matrix.clone()),
+                };
                 execute_vector_search(
                     &mut ctx,
-                    vector,
+                    &target,
+                    self.scoring_strategy,
+                    universe,
+                    &self.sort_criteria,
+                    &self.distinct,
+                    self.geo_param,
+                    self.offset,
+                    self.limit,
+                    embedder_name,
+                    embedder,
+                    *quantized,
+                    self.time_budget.clone(),
+                    self.ranking_score_threshold,
+                )?
+            }
+            // sparse embedders derive their target from the text of the query
+            Some(SemanticSearch { vector: None, embedder_name, embedder, quantized })
+                if embedder.is_sparse() =>
+            {
+                let query = self.query.as_deref().unwrap_or_default();
+                let target = embedder.embed_sparse_query(query).unwrap_or_default();
+                execute_vector_search(
+                    &mut ctx,
+                    &VectorTarget::Sparse(target),
                     self.scoring_strategy,
                     universe,
                     &self.sort_criteria,
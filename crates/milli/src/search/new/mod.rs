@@ -52,6 +52,7 @@ pub use self::geo_sort::{Parameter as GeoSortParameter, Strategy as GeoSortStrat
 use self::graph_based_ranking_rule::Words;
 use self::interner::Interned;
 use self::vector_sort::VectorSort;
+pub use self::vector_sort::VectorTarget;
 use crate::attribute_patterns::{match_pattern, PatternMatch};
 use crate::constants::RESERVED_GEO_FIELD_NAME;
 use crate::index::PrefixSearch;
@@ -373,7 +374,7 @@ fn get_ranking_rules_for_vector<'ctx>(
     sort_criteria: &Option<Vec<AscDesc>>,
     geo_param: geo_sort::Parameter,
     limit_plus_offset: usize,
-    target: &[f32],
+    target: &VectorTarget,
     embedder_name: &str,
     embedder: &Embedder,
     quantized: bool,
@@ -399,7 +400,7 @@ fn get_ranking_rules_for_vector<'ctx>(
                     let vector_candidates = ctx.index.documents_ids(ctx.txn)?;
                     let vector_sort = VectorSort::new(
                         ctx,
-                        target.to_vec(),
+                        target.clone(),
                         vector_candidates,
                         limit_plus_offset,
                         embedder_name,
@@ -614,17 +615,25 @@ pub fn filtered_universe(
     txn: &RoTxn<'_>,
     filters: &Option<Filter<'_>>,
 ) -> Result<RoaringBitmap> {
-    Ok(if let Some(filters) = filters {
+    let universe = if let Some(filters) = filters {
         filters.evaluate(txn, index)?
     } else {
         index.documents_ids(txn)?
-    })
+    };
+
+    if universe.is_empty() {
+        return Ok(universe);
+    }
+
+    // expired documents are ignored until the scheduler deletes them
+    let now = time::OffsetDateTime::now_utc().unix_timestamp();
+    Ok(universe - index.expired_documents_ids(txn, now)?)
 }
 
 #[allow(clippy::too_many_arguments)]
 pub fn execute_vector_search(
     ctx: &mut SearchContext<'_>,
-    vector: &[f32],
+    vector: &VectorTarget,
     scoring_strategy: ScoringStrategy,
     universe: RoaringBitmap,
     sort_criteria: &Option<Vec<AscDesc>>,
@@ -6,12 +6,25 @@ use roaring::RoaringBitmap;
 use super::ranking_rules::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait};
 use super::VectorStoreStats;
 use crate::score_details::{self, ScoreDetails};
+use crate::vector::late_interaction;
+use crate::vector::sparse::{SparseVector, SparseVectorStore};
 use crate::vector::{ArroyWrapper, DistributionShift, Embedder};
 use crate::{DocumentId, Result, SearchContext, SearchLogger};
 
+/// The vector documents are compared to.
+#[derive(Debug, Clone)]
+pub enum VectorTarget {
+    /// Searched in the arroy database of the embedder.
+    Dense(Vec<f32>),
+    /// Searched in the inverted index of a sparse embedder.
+    Sparse(SparseVector),
+    /// Matrix of vectors searched in the arroy database of a multi-vector embedder, see [`late_interaction`].
+    Multi(Vec<Vec<f32>>),
+}
+
 pub struct VectorSort<Q: RankingRuleQueryTrait> {
     query: Option<Q>,
-    target: Vec<f32>,
+    target: VectorTarget,
     vector_candidates: RoaringBitmap,
     cached_sorted_docids: std::vec::IntoIter<(DocumentId, f32)>,
     limit: usize,
@@ -23,7 +36,7 @@ pub struct VectorSort<Q: RankingRuleQueryTrait> {
 impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
     pub fn new(
         ctx: &SearchContext<'_>,
-        target: Vec<f32>,
+        target: VectorTarget,
         vector_candidates: RoaringBitmap,
         limit: usize,
         embedder_name: &str,
@@ -53,15 +66,57 @@ impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
         ctx: &mut SearchContext<'_>,
         vector_candidates: &RoaringBitmap,
     ) -> Result<()> {
-        let target = &self.target;
-
         let before = Instant::now();
-        let reader = ArroyWrapper::new(ctx.index.vector_arroy, self.embedder_index, self.quantized);
-        let results = reader.nns_by_vector(ctx.txn, target, self.limit, Some(vector_candidates))?;
+        let mut total_queries = 1;
+        let results = match &self.target {
+            VectorTarget::Dense(target) => {
+                let reader =
+                    ArroyWrapper::new(ctx.index.vector_arroy, self.embedder_index, self.quantized);
+                reader.nns_by_vector(ctx.txn, target, self.limit, Some(vector_candidates))?
+            }
+            VectorTarget::Multi(target) => {
+                let reader =
+                    ArroyWrapper::new(ctx.index.vector_arroy, self.embedder_index, self.quantized);
+                // candidates are the nearest neighbors of any vector of the query...
+                let mut candidates = RoaringBitmap::new();
+                for vector in target {
+                    let neighbors = reader.nns_by_vector(
+                        ctx.txn,
+                        vector,
+                        self.limit,
+                        Some(vector_candidates),
+                    )?;
+                    candidates.extend(neighbors.into_iter().map(|(docid, _)| docid));
+                }
+                total_queries = target.len();
+
+                // ...then rescored against all the vectors of the query
+                let mut results = Vec::with_capacity(candidates.len() as usize);
+                for docid in candidates {
+                    let document = reader.item_vectors(ctx.txn, docid)?;
+                    let similarity =
+                        late_interaction::max_sim(target.iter().map(Vec::as_slice), &document);
+                    results.push((docid, 1.0 - similarity));
+                }
+                results.sort_by(|(_, left), (_, right)| left.total_cmp(right));
+                results
+            }
+            VectorTarget::Sparse(target) => {
+                let store = SparseVectorStore::new(ctx.index.vector_sparse, self.embedder_index);
+                let results = store.search(ctx.txn, target, self.limit, Some(vector_candidates))?;
+                // convert to a distance so that all kinds of targets are scored the same way
+                results
+                    .into_iter()
+                    .map(|(docid, dot)| {
+                        (docid, 1.0 - SparseVector::similarity(dot, self.distribution_shift))
+                    })
+                    .collect()
+            }
+        };
         self.cached_sorted_docids = results.into_iter();
         *ctx.vector_store_stats.get_or_insert_default() += VectorStoreStats {
             total_time: before.elapsed(),
-            total_queries: 1,
+            total_queries,
             total_results: self.cached_sorted_docids.len(),
         };
 
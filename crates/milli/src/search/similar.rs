@@ -3,11 +3,19 @@ use std::sync::Arc;
 use roaring::RoaringBitmap;
 
 use crate::score_details::{self, ScoreDetails};
-use crate::vector::{ArroyWrapper, Embedder};
-use crate::{filtered_universe, DocumentId, Filter, Index, Result, SearchResult};
+use crate::vector::{ArroyWrapper, Embedder, Embedding};
+use crate::{filtered_universe, DocumentId, Filter, Index, Result, SearchResult, UserError};
+
+/// Weight of the negative examples relative to the positive ones when computing the query vector.
+const NEGATIVE_EXAMPLES_WEIGHT: f32 = 0.5;
 
 pub struct Similar<'a> {
-    id: DocumentId,
+    /// Documents the results should be similar to.
+    positive: Vec<DocumentId>,
+    /// Documents the results should not be similar to.
+    negative: Vec<DocumentId>,
+    positive_vectors: Vec<Embedding>,
+    negative_vectors: Vec<Embedding>,
     // this should be linked to the String in the query
     filter: Option<Filter<'a>>,
     offset: usize,
@@ -23,7 +31,7 @@ pub struct Similar<'a> {
 impl<'a> Similar<'a> {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
-        id: DocumentId,
+        positive: Vec<DocumentId>,
         offset: usize,
         limit: usize,
         index: &'a Index,
@@ -33,7 +41,10 @@ impl<'a> Similar<'a> {
         quantized: bool,
     ) -> Self {
         Self {
-            id,
+            positive,
+            negative: Vec::new(),
+            positive_vectors: Vec::new(),
+            negative_vectors: Vec::new(),
             filter: None,
             offset,
             limit,
@@ -56,11 +67,30 @@ impl<'a> Similar<'a> {
         self
     }
 
+    /// Documents the results should not be similar to.
+    pub fn negative(&mut self, negative: Vec<DocumentId>) -> &mut Self {
+        self.negative = negative;
+        self
+    }
+
+    /// Raw vectors the results should be similar to, combined with the positive documents.
+    pub fn positive_vectors(&mut self, vectors: Vec<Embedding>) -> &mut Self {
+        self.positive_vectors = vectors;
+        self
+    }
+
+    /// Raw vectors the results should not be similar to, combined with the negative documents.
+    pub fn negative_vectors(&mut self, vectors: Vec<Embedding>) -> &mut Self {
+        self.negative_vectors = vectors;
+        self
+    }
+
     pub fn execute(&self) -> Result<SearchResult> {
         let mut universe = filtered_universe(self.index, self.rtxn, &self.filter)?;
 
-        // we never want to receive the docid
-        universe.remove(self.id);
+        // we never want to receive the examples
+        let examples: RoaringBitmap = self.positive.iter().chain(&self.negative).copied().collect();
+        universe -= &examples;
 
         let universe = universe;
 
@@ -70,19 +100,27 @@ impl<'a> Similar<'a> {
             )?;
 
         let reader = ArroyWrapper::new(self.index.vector_arroy, embedder_index, self.quantized);
-        let results = reader.nns_by_item(
-            self.rtxn,
-            self.id,
-            self.limit + self.offset + 1,
-            Some(&universe),
-        )?;
+        let limit = self.limit + self.offset + examples.len() as usize;
+        let results = match (
+            self.positive.as_slice(),
+            self.negative.as_slice(),
+            self.positive_vectors.as_slice(),
+            self.negative_vectors.as_slice(),
+        ) {
+            // a single document, search by item directly
+            ([id], [], [], []) => reader.nns_by_item(self.rtxn, *id, limit, Some(&universe))?,
+            _ => match self.query_vector(&reader)? {
+                Some(vector) => reader.nns_by_vector(self.rtxn, &vector, limit, Some(&universe))?,
+                // none of the positive examples has a vector
+                None => Vec::new(),
+            },
+        };
 
         let mut documents_ids = Vec::with_capacity(self.limit);
         let mut document_scores = Vec::with_capacity(self.limit);
         // list of documents we've already seen, so that we don't return the same document multiple times.
-        // initialized to the target document, that we never want to return.
-        let mut documents_seen = RoaringBitmap::new();
-        documents_seen.insert(self.id);
+        // initialized to the examples, that we never want to return.
+        let mut documents_seen = examples;
 
         let mut candidates = universe;
 
@@ -129,4 +167,65 @@ impl<'a> Similar<'a> {
             used_negative_operator: false,
         })
     }
+
+    /// Combines the examples into a single query vector.
+    ///
+    /// The query vector is the centroid of the positive examples, moved away from the centroid of the negative examples.
+    /// Returns `None` if there is no positive example with a vector.
+    fn query_vector(&self, reader: &ArroyWrapper) -> Result<Option<Embedding>> {
+        let dimensions = self.embedder.dimensions();
+
+        let positive = self.centroid(reader, &self.positive, &self.positive_vectors, dimensions)?;
+        let Some(mut vector) = positive else {
+            return Ok(None);
+        };
+
+        if let Some(negative) =
+            self.centroid(reader, &self.negative, &self.negative_vectors, dimensions)?
+        {
+            for (value, negative) in vector.iter_mut().zip(negative) {
+                *value -= NEGATIVE_EXAMPLES_WEIGHT * negative;
+            }
+        }
+
+        Ok(Some(vector))
+    }
+
+    /// The mean of the vectors of the documents and of the raw vectors, or `None` if there is no vector at all.
+    fn centroid(
+        &self,
+        reader: &ArroyWrapper,
+        documents: &[DocumentId],
+        vectors: &[Embedding],
+        dimensions: usize,
+    ) -> Result<Option<Embedding>> {
+        let mut sum = vec![0.0; dimensions];
+        let mut count = 0usize;
+
+        for vector in vectors {
+            if vector.len() != dimensions {
+                return Err(UserError::InvalidVectorDimensions {
+                    expected: dimensions,
+                    found: vector.len(),
+                }
+                .into());
+            }
+            sum.iter_mut().zip(vector).for_each(|(sum, value)| *sum += value);
+            count += 1;
+        }
+
+        for docid in documents {
+            for vector in reader.item_vectors(self.rtxn, *docid)? {
+                sum.iter_mut().zip(&vector).for_each(|(sum, value)| *sum += value);
+                count += 1;
+            }
+        }
+
+        if count == 0 {
+            return Ok(None);
+        }
+
+        sum.iter_mut().for_each(|sum| *sum /= count as f32);
+        Ok(Some(sum))
+    }
 }
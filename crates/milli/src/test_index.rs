@@ -122,11 +122,14 @@ impl TempIndex {
         update: impl Fn(&mut Settings<'_, '_, '_>),
     ) -> Result<(), crate::error::Error> {
         let mut wtxn = self.write_txn().unwrap();
-        self.update_settings_using_wtxn(&mut wtxn, update)?;
+        let mut builder = update::Settings::new(&mut wtxn, &self.inner, &self.indexer_config);
+        update(&mut builder);
+        builder.execute(drop, || false)?;
         wtxn.commit().unwrap();
         Ok(())
     }
 
+    /// Updates the settings in a transaction where documents or settings may already have been written.
     pub fn update_settings_using_wtxn<'t>(
         &'t self,
         wtxn: &mut RwTxn<'t>,
@@ -134,6 +137,7 @@ impl TempIndex {
     ) -> Result<(), crate::error::Error> {
         let mut builder = update::Settings::new(wtxn, &self.inner, &self.indexer_config);
         update(&mut builder);
+        builder.set_uncommitted_writes(true);
         builder.execute(drop, || false)?;
         Ok(())
     }
@@ -0,0 +1,197 @@
+use std::num::NonZeroUsize;
+
+use deserr::Deserr;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::index::{ChatConfig, ChatHybridConfig, ChatSearchConfig};
+use crate::prompt::{default_max_bytes, Prompt, PromptData};
+use crate::update::Setting;
+use crate::UserError;
+
+/// Settings used to answer questions about the documents of an index with a chat completion provider.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct ChatSettings {
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<String>)]
+    /// A description of the content of the index, given to the model along with the retrieved documents.
+    pub description: Setting<String>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<String>)]
+    /// A liquid template used to render each retrieved document before it is sent to the model.
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to the default document template of embedders
+    pub document_template: Setting<String>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<usize>)]
+    /// Rendered documents are truncated to this number of bytes.
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to 400
+    pub document_template_max_bytes: Setting<NonZeroUsize>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<ChatSearchParams>)]
+    /// Parameters of the search retrieving the documents given to the model.
+    pub search_parameters: Setting<ChatSearchParams>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct ChatSearchParams {
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<usize>)]
+    /// Maximum number of documents given to the model.
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to 5
+    pub limit: Setting<usize>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<ChatHybridQuery>)]
+    /// Retrieve documents with a hybrid search using this embedder.
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to a keyword search
+    pub hybrid: Setting<ChatHybridQuery>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<f64>)]
+    /// Documents ranked below this score are not given to the model.
+    pub ranking_score_threshold: Setting<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct ChatHybridQuery {
+    /// Name of the embedder used for the semantic part of the search.
+    pub embedder: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    /// Defaults to `0.5`.
+    pub semantic_ratio: Option<f32>,
+}
+
+// The floats of the chat settings are deserialized from JSON, which cannot represent NaN.
+impl Eq for ChatSearchParams {}
+impl Eq for ChatHybridQuery {}
+
+impl ChatSettings {
+    /// Applies the settings on top of an existing configuration, validating the resulting configuration.
+    pub(crate) fn apply_to(self, config: &mut ChatConfig) -> crate::Result<()> {
+        let ChatSettings {
+            description,
+            document_template,
+            document_template_max_bytes,
+            search_parameters,
+        } = self;
+
+        apply(&mut config.description, description, String::new);
+        apply(&mut config.prompt.template, document_template, || PromptData::default().template);
+        match document_template_max_bytes {
+            Setting::Set(max_bytes) => config.prompt.max_bytes = Some(max_bytes),
+            Setting::Reset => config.prompt.max_bytes = Some(default_max_bytes()),
+            Setting::NotSet => (),
+        }
+
+        match search_parameters {
+            Setting::Set(ChatSearchParams { limit, hybrid, ranking_score_threshold }) => {
+                let search = &mut config.search_parameters;
+                match limit {
+                    Setting::Set(limit) => search.limit = Some(limit),
+                    Setting::Reset => search.limit = None,
+                    Setting::NotSet => (),
+                }
+                match hybrid {
+                    Setting::Set(ChatHybridQuery { embedder, semantic_ratio }) => {
+                        if let Some(ratio) = semantic_ratio {
+                            check_ratio("searchParameters.hybrid.semanticRatio", ratio as f64)?;
+                        }
+                        search.hybrid = Some(ChatHybridConfig { embedder, semantic_ratio })
+                    }
+                    Setting::Reset => search.hybrid = None,
+                    Setting::NotSet => (),
+                }
+                match ranking_score_threshold {
+                    Setting::Set(threshold) => {
+                        check_ratio("searchParameters.rankingScoreThreshold", threshold)?;
+                        search.ranking_score_threshold = Some(threshold)
+                    }
+                    Setting::Reset => search.ranking_score_threshold = None,
+                    Setting::NotSet => (),
+                }
+            }
+            Setting::Reset => config.search_parameters = ChatSearchConfig::default(),
+            Setting::NotSet => (),
+        }
+
+        Prompt::try_from(config.prompt.clone()).map_err(|error| {
+            UserError::InvalidSettingsChat {
+                field: "documentTemplate",
+                message: format!("Invalid template: {error}."),
+            }
+        })?;
+
+        Ok(())
+    }
+}
+
+impl From<ChatConfig> for ChatSettings {
+    fn from(config: ChatConfig) -> Self {
+        let ChatConfig { description, prompt, search_parameters } = config;
+        let ChatSearchConfig { limit, hybrid, ranking_score_threshold } = search_parameters;
+        ChatSettings {
+            description: Setting::Set(description),
+            document_template: Setting::Set(prompt.template),
+            document_template_max_bytes: Setting::Set(
+                prompt.max_bytes.unwrap_or_else(default_max_bytes),
+            ),
+            search_parameters: Setting::Set(ChatSearchParams {
+                limit: Setting::Set(limit.unwrap_or(ChatSearchConfig::DEFAULT_LIMIT)),
+                hybrid: match hybrid {
+                    Some(ChatHybridConfig { embedder, semantic_ratio }) => {
+                        Setting::Set(ChatHybridQuery { embedder, semantic_ratio })
+                    }
+                    None => Setting::Reset,
+                },
+                ranking_score_threshold: match ranking_score_threshold {
+                    Some(threshold) => Setting::Set(threshold),
+                    None => Setting::Reset,
+                },
+            }),
+        }
+    }
+}
+
+fn apply<T>(value: &mut T, setting: Setting<T>, default: impl FnOnce() -> T) {
+    match setting {
+        Setting::Set(new) => *value = new,
+        Setting::Reset => *value = default(),
+        Setting::NotSet => (),
+    }
+}
+
+fn check_ratio(field: &'static str, value: f64) -> crate::Result<()> {
+    if (0.0..=1.0).contains(&value) {
+        Ok(())
+    } else {
+        Err(UserError::InvalidSettingsChat {
+            field,
+            message: format!("expected a float between `0.0` and `1.0`, found `{value}`"),
+        }
+        .into())
+    }
+}
@@ -46,6 +46,7 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
             field_id_docid_facet_f64s,
             field_id_docid_facet_strings,
             vector_arroy,
+            vector_sparse,
             embedder_category_id: _,
             documents,
         } = self.index;
@@ -93,6 +94,7 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
         field_id_docid_facet_strings.clear(self.wtxn)?;
         // vector
         vector_arroy.clear(self.wtxn)?;
+        vector_sparse.clear(self.wtxn)?;
 
         documents.clear(self.wtxn)?;
 
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet};
 use std::convert::TryInto;
 use std::fs::File;
@@ -19,7 +20,9 @@ use crate::facet::value_encoding::f64_into_bytes;
 use crate::update::del_add::{DelAdd, KvReaderDelAdd, KvWriterDelAdd};
 use crate::update::index_documents::{create_writer, writer_into_reader};
 use crate::update::settings::InnerIndexSettingsDiff;
-use crate::{CboRoaringBitmapCodec, DocumentId, FieldId, Result, MAX_FACET_VALUE_LENGTH};
+use crate::{
+    CboRoaringBitmapCodec, DocumentId, FieldId, FieldType, Result, MAX_FACET_VALUE_LENGTH,
+};
 
 /// The length of the elements that are always in the buffer when inserting new values.
 const TRUNCATE_SIZE: usize = size_of::<FieldId>() + size_of::<DocumentId>();
@@ -112,9 +115,11 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
                         (field_id, None, add_value)
                     }
                     EitherOrBoth::Both(&field_id, _) => {
-                        // during settings update, recompute the changing settings only unless a global change is detected.
+                        // during settings update, recompute the changing settings only unless a global change is detected
+                        // or the declared type of the field changed.
                         if settings_diff.settings_update_only
                             && !settings_diff.global_facet_settings_changed()
+                            && !settings_diff.field_type_changed(field_id)
                         {
                             continue;
                         }
@@ -165,10 +170,20 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
                         .new
                         .geo_fields_ids
                         .is_some_and(|(lat, lng)| field_id == lat || field_id == lng);
-                    let del_filterable_values =
-                        del_value.map(|value| extract_facet_values(&value, del_geo_support));
-                    let add_filterable_values =
-                        add_value.map(|value| extract_facet_values(&value, add_geo_support));
+                    let del_field_type = settings_diff
+                        .old
+                        .fields_ids_map
+                        .metadata(field_id)
+                        .and_then(|metadata| metadata.field_type());
+                    let add_field_type = settings_diff
+                        .new
+                        .fields_ids_map
+                        .metadata(field_id)
+                        .and_then(|metadata| metadata.field_type());
+                    let del_filterable_values = del_value
+                        .map(|value| extract_facet_values(&value, del_geo_support, del_field_type));
+                    let add_filterable_values = add_value
+                        .map(|value| extract_facet_values(&value, add_geo_support, add_field_type));
 
                     // Those closures are just here to simplify things a bit.
                     let mut insert_numbers_diff = |del_numbers, add_numbers| {
@@ -513,15 +528,29 @@ enum FilterableValues {
 }
 
 /// Extracts the facet values of a JSON field.
-fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
+///
+/// The values are converted to the declared type of the field, the ones that
+/// cannot be converted are ignored.
+fn extract_facet_values(
+    value: &Value,
+    geo_field: bool,
+    field_type: Option<FieldType>,
+) -> FilterableValues {
     fn inner_extract_facet_values(
         value: &Value,
         can_recurse: bool,
         output_numbers: &mut Vec<f64>,
         output_strings: &mut Vec<(String, String)>,
         geo_field: bool,
+        field_type: Option<FieldType>,
     ) {
-        match value {
+        let value = match field_type.map(|field_type| field_type.coerce(value)) {
+            Some(Some(value)) => value,
+            Some(None) => return,
+            None => Cow::Borrowed(value),
+        };
+
+        match &*value {
             Value::Null => (),
             Value::Bool(b) => output_strings.push((b.to_string(), b.to_string())),
             Value::Number(number) => {
@@ -553,6 +582,7 @@ fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
                             output_numbers,
                             output_strings,
                             geo_field,
+                            field_type,
                         );
                     }
                 }
@@ -569,7 +599,14 @@ fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
         otherwise => {
             let mut numbers = Vec::new();
             let mut strings = Vec::new();
-            inner_extract_facet_values(otherwise, true, &mut numbers, &mut strings, geo_field);
+            inner_extract_facet_values(
+                otherwise,
+                true,
+                &mut numbers,
+                &mut strings,
+                geo_field,
+                field_type,
+            );
             FilterableValues::Values { numbers, strings }
         }
     }
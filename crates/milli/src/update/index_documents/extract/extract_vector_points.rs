@@ -8,6 +8,7 @@ use std::sync::Arc;
 
 use bytemuck::cast_slice;
 use grenad::Writer;
+use obkv::{KvReaderU16, KvWriterU16};
 use ordered_float::OrderedFloat;
 use roaring::RoaringBitmap;
 use serde_json::Value;
@@ -17,14 +18,15 @@ use crate::constants::RESERVED_VECTORS_FIELD_NAME;
 use crate::error::FaultSource;
 use crate::fields_ids_map::metadata::FieldIdMapWithMetadata;
 use crate::index::IndexEmbeddingConfig;
+use crate::prompt::error::RenderPromptError;
 use crate::prompt::Prompt;
 use crate::update::del_add::{DelAdd, KvReaderDelAdd, KvWriterDelAdd};
 use crate::update::settings::InnerIndexSettingsDiff;
 use crate::vector::error::{EmbedErrorKind, PossibleEmbeddingMistakes, UnusedVectorsDistribution};
 use crate::vector::parsed_vectors::{ParsedVectorsDiff, VectorState};
 use crate::vector::settings::ReindexAction;
-use crate::vector::{Embedder, Embedding};
-use crate::{try_split_array_at, DocumentId, FieldId, Result, ThreadPoolNoAbort};
+use crate::vector::{Embedder, EmbedderInput, Embedding};
+use crate::{try_split_array_at, DocumentId, FieldId, InternalError, Result, ThreadPoolNoAbort};
 
 /// The length of the elements that are always in the buffer when inserting new values.
 const TRUNCATE_SIZE: usize = size_of::<DocumentId>();
@@ -54,21 +56,85 @@ enum VectorStateDelta {
     // Add the vector computed from the specified prompt
     // Remove any previous vector
     // Note: changing the value of the prompt **does require** recording this delta
-    NowGenerated(String),
+    NowGenerated(GeneratedInput),
 }
 
 impl VectorStateDelta {
-    fn into_values(self) -> (bool, String, Vec<Vec<f32>>) {
+    fn into_values(self) -> (bool, GeneratedInput, Vec<Vec<f32>>) {
         match self {
             VectorStateDelta::NoChange => Default::default(),
             VectorStateDelta::NowRemoved => (true, Default::default(), Default::default()),
             // We always delete the previous vectors
             VectorStateDelta::NowManual(add) => (true, Default::default(), add),
-            VectorStateDelta::NowGenerated(prompt) => (true, prompt, Default::default()),
+            VectorStateDelta::NowGenerated(input) => (true, input, Default::default()),
         }
     }
 }
 
+/// The prompt rendered from a document, along with the raw JSON value of its media field
+/// for the embedders whose `request` contains a media placeholder.
+///
+/// Stored in the prompts grenad as an obkv of the [`PROMPT_TEXT`] and [`PROMPT_MEDIA`] fields.
+#[derive(Debug, Default, PartialEq)]
+struct GeneratedInput {
+    prompt: String,
+    media: Option<Vec<u8>>,
+}
+
+const PROMPT_TEXT: u16 = 0;
+const PROMPT_MEDIA: u16 = 1;
+
+impl GeneratedInput {
+    fn render(
+        obkv: &obkv::KvReader<FieldId>,
+        prompt: &Prompt,
+        media_field: Option<&str>,
+        side: DelAdd,
+        fields_ids_map: &FieldIdMapWithMetadata,
+    ) -> std::result::Result<Self, RenderPromptError> {
+        let media = media_field
+            .and_then(|media_field| fields_ids_map.as_fields_ids_map().id(media_field))
+            .and_then(|fid| obkv.get(fid))
+            .and_then(|deladd| KvReaderDelAdd::from_slice(deladd).get(side))
+            .map(<[u8]>::to_vec);
+        let prompt = prompt.render_kvdeladd(obkv, side, fields_ids_map)?;
+        Ok(Self { prompt, media })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.prompt.is_empty() && self.media.is_none()
+    }
+
+    fn write(&self, buffer: &mut Vec<u8>) -> Result<()> {
+        buffer.clear();
+        let mut writer = KvWriterU16::new(buffer);
+        writer.insert(PROMPT_TEXT, self.prompt.as_bytes())?;
+        if let Some(media) = &self.media {
+            writer.insert(PROMPT_MEDIA, media)?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+
+    fn read(bytes: &[u8]) -> Self {
+        let reader = KvReaderU16::from_slice(bytes);
+        // SAFETY: precondition, the grenad value was written from a string
+        let prompt = unsafe { std::str::from_utf8_unchecked(reader.get(PROMPT_TEXT).unwrap()) };
+        Self { prompt: prompt.to_owned(), media: reader.get(PROMPT_MEDIA).map(<[u8]>::to_vec) }
+    }
+
+    fn as_embedder_input(&self, has_media: bool) -> Result<EmbedderInput<'_>> {
+        if !has_media {
+            return Ok(EmbedderInput::Text(&self.prompt));
+        }
+        let media = match &self.media {
+            Some(media) => Some(serde_json::from_slice(media).map_err(InternalError::SerdeJson)?),
+            None => None,
+        };
+        Ok(EmbedderInput::Media { text: &self.prompt, media })
+    }
+}
+
 struct EmbedderVectorExtractor {
     embedder_name: String,
     embedder: Arc<Embedder>,
@@ -265,6 +331,10 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
         // document operation
 
         for (embedder_name, (embedder, prompt, _quantized)) in configs.into_iter() {
+            // sparse vectors are kept in the documents and indexed separately
+            if embedder.is_sparse() {
+                continue;
+            }
             // (docid, _index) -> KvWriterDelAdd -> Vector
             let manual_vectors_writer = create_writer(
                 indexer.chunk_compression_type,
@@ -302,6 +372,7 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
     }
 
     let mut key_buffer = Vec::new();
+    let mut prompt_buffer = Vec::new();
     let mut cursor = obkv_documents.into_cursor()?;
     while let Some((key, value)) = cursor.move_on_next()? {
         // this must always be serialized as (docid, external_docid);
@@ -339,7 +410,11 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
             action,
         } in extractors.iter_mut()
         {
-            let embedder_is_manual = matches!(**embedder, Embedder::UserProvided(_));
+            let embedder_is_manual = matches!(
+                **embedder,
+                Embedder::UserProvided(_) | Embedder::UserProvidedMultiVector(_)
+            );
+            let media_field = embedder.media_field();
 
             let (old, new) = parsed_vectors.remove(embedder_name);
             let delta = match action {
@@ -380,7 +455,7 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
                             );
                             continue;
                         }
-                        regenerate_prompt(obkv, prompt, new_fields_ids_map)?
+                        regenerate_prompt(obkv, prompt, media_field, new_fields_ids_map)?
                     }
                 },
                 // prompt regeneration is only triggered for existing embedders
@@ -397,6 +472,7 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
                         regenerate_if_prompt_changed(
                             obkv,
                             (old_prompt, prompt),
+                            media_field,
                             (old_fields_ids_map, new_fields_ids_map),
                         )?
                     } else {
@@ -411,6 +487,7 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
                     docid,
                     obkv,
                     prompt,
+                    media_field,
                     (add_to_user_provided, remove_from_user_provided),
                     (old, new),
                     (old_fields_ids_map, new_fields_ids_map),
@@ -426,6 +503,7 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
                 prompts_writer,
                 manual_vectors_writer,
                 &mut key_buffer,
+                &mut prompt_buffer,
                 delta,
             )?;
         }
@@ -481,6 +559,7 @@ fn extract_vector_document_diff(
     docid: DocumentId,
     obkv: &obkv::KvReader<FieldId>,
     prompt: &Prompt,
+    media_field: Option<&str>,
     (add_to_user_provided, remove_from_user_provided): (&mut RoaringBitmap, &mut RoaringBitmap),
     (old, new): (VectorState, VectorState),
     (old_fields_ids_map, new_fields_ids_map): (&FieldIdMapWithMetadata, &FieldIdMapWithMetadata),
@@ -530,18 +609,31 @@ fn extract_vector_document_diff(
                     return Ok(VectorStateDelta::NoChange);
                 }
                 // Don't give up if the old prompt was failing
-                let old_prompt = Some(&prompt).map(|p| {
-                    p.render_kvdeladd(obkv, DelAdd::Deletion, old_fields_ids_map)
-                        .unwrap_or_default()
+                let old_input = Some(&prompt).map(|p| {
+                    GeneratedInput::render(
+                        obkv,
+                        p,
+                        media_field,
+                        DelAdd::Deletion,
+                        old_fields_ids_map,
+                    )
+                    .unwrap_or_default()
                 });
-                let new_prompt =
-                    prompt.render_kvdeladd(obkv, DelAdd::Addition, new_fields_ids_map)?;
-                if old_prompt.as_ref() != Some(&new_prompt) {
-                    let old_prompt = old_prompt.unwrap_or_default();
+                let new_input = GeneratedInput::render(
+                    obkv,
+                    prompt,
+                    media_field,
+                    DelAdd::Addition,
+                    new_fields_ids_map,
+                )?;
+                if old_input.as_ref() != Some(&new_input) {
+                    let old_input = old_input.unwrap_or_default();
                     tracing::trace!(
-                        "🚀 Changing prompt from\n{old_prompt}\n===to===\n{new_prompt}"
+                        "🚀 Changing prompt from\n{}\n===to===\n{}",
+                        old_input.prompt,
+                        new_input.prompt
                     );
-                    VectorStateDelta::NowGenerated(new_prompt)
+                    VectorStateDelta::NowGenerated(new_input)
                 } else {
                     tracing::trace!("⏭️ Prompt unmodified, skipping");
                     VectorStateDelta::NoChange
@@ -567,8 +659,10 @@ fn extract_vector_document_diff(
                     return Ok(VectorStateDelta::NoChange);
                 }
                 // becomes autogenerated
-                VectorStateDelta::NowGenerated(prompt.render_kvdeladd(
+                VectorStateDelta::NowGenerated(GeneratedInput::render(
                     obkv,
+                    prompt,
+                    media_field,
                     DelAdd::Addition,
                     new_fields_ids_map,
                 )?)
@@ -605,27 +699,36 @@ fn extract_vector_document_diff(
 fn regenerate_if_prompt_changed(
     obkv: &obkv::KvReader<FieldId>,
     (old_prompt, new_prompt): (&Prompt, &Prompt),
+    media_field: Option<&str>,
     (old_fields_ids_map, new_fields_ids_map): (&FieldIdMapWithMetadata, &FieldIdMapWithMetadata),
 ) -> Result<VectorStateDelta> {
-    let old_prompt = old_prompt
-        .render_kvdeladd(obkv, DelAdd::Deletion, old_fields_ids_map)
-        .unwrap_or(Default::default());
-    let new_prompt = new_prompt.render_kvdeladd(obkv, DelAdd::Addition, new_fields_ids_map)?;
+    let old_input =
+        GeneratedInput::render(obkv, old_prompt, media_field, DelAdd::Deletion, old_fields_ids_map)
+            .unwrap_or(Default::default());
+    let new_input = GeneratedInput::render(
+        obkv,
+        new_prompt,
+        media_field,
+        DelAdd::Addition,
+        new_fields_ids_map,
+    )?;
 
-    if new_prompt == old_prompt {
+    if new_input == old_input {
         return Ok(VectorStateDelta::NoChange);
     }
-    Ok(VectorStateDelta::NowGenerated(new_prompt))
+    Ok(VectorStateDelta::NowGenerated(new_input))
 }
 
 fn regenerate_prompt(
     obkv: &obkv::KvReader<FieldId>,
     prompt: &Prompt,
+    media_field: Option<&str>,
     new_fields_ids_map: &FieldIdMapWithMetadata,
 ) -> Result<VectorStateDelta> {
-    let prompt = prompt.render_kvdeladd(obkv, DelAdd::Addition, new_fields_ids_map)?;
+    let input =
+        GeneratedInput::render(obkv, prompt, media_field, DelAdd::Addition, new_fields_ids_map)?;
 
-    Ok(VectorStateDelta::NowGenerated(prompt))
+    Ok(VectorStateDelta::NowGenerated(input))
 }
 
 /// We cannot compute the diff between both Del and Add vectors.
@@ -635,16 +738,18 @@ fn push_vectors_diff(
     prompts_writer: &mut Writer<BufWriter<File>>,
     manual_vectors_writer: &mut Writer<BufWriter<File>>,
     key_buffer: &mut Vec<u8>,
+    prompt_buffer: &mut Vec<u8>,
     delta: VectorStateDelta,
 ) -> Result<()> {
-    let (must_remove, prompt, mut add_vectors) = delta.into_values();
+    let (must_remove, input, mut add_vectors) = delta.into_values();
     if must_remove {
         key_buffer.truncate(TRUNCATE_SIZE);
         remove_vectors_writer.insert(&key_buffer, [])?;
     }
-    if !prompt.is_empty() {
+    if !input.is_empty() {
         key_buffer.truncate(TRUNCATE_SIZE);
-        prompts_writer.insert(&key_buffer, prompt.as_bytes())?;
+        input.write(prompt_buffer)?;
+        prompts_writer.insert(&key_buffer, &prompt_buffer)?;
     }
 
     // We sort and dedup the vectors
@@ -703,8 +808,6 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
 
     while let Some((key, value)) = cursor.move_on_next()? {
         let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
-        // SAFETY: precondition, the grenad value was saved from a string
-        let prompt = unsafe { std::str::from_utf8_unchecked(value) };
         if current_chunk.len() == current_chunk.capacity() {
             chunks.push(std::mem::replace(
                 &mut current_chunk,
@@ -715,7 +818,7 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
                 Vec::with_capacity(n_vectors_per_chunk),
             ));
         };
-        current_chunk.push(prompt.to_owned());
+        current_chunk.push(GeneratedInput::read(value));
         current_chunk_ids.push(docid);
 
         if chunks.len() == chunks.capacity() {
@@ -780,13 +883,23 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
 
 fn embed_chunks(
     embedder: &Embedder,
-    text_chunks: Vec<Vec<String>>,
+    input_chunks: Vec<Vec<GeneratedInput>>,
     embedder_name: &str,
     possible_embedding_mistakes: &PossibleEmbeddingMistakes,
     unused_vectors_distribution: &UnusedVectorsDistribution,
     request_threads: &ThreadPoolNoAbort,
 ) -> Result<Vec<Vec<Embedding>>> {
-    match embedder.embed_index(text_chunks, request_threads) {
+    let has_media = embedder.media_field().is_some();
+    let input_chunks = input_chunks
+        .iter()
+        .map(|inputs| {
+            inputs
+                .iter()
+                .map(|input| input.as_embedder_input(has_media))
+                .collect::<Result<Vec<_>>>()
+        })
+        .collect::<Result<Vec<_>>>()?;
+    match embedder.embed_index(input_chunks, request_threads) {
         Ok(chunks) => Ok(chunks),
         Err(error) => {
             if let FaultSource::Bug = error.fault {
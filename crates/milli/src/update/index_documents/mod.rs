@@ -1404,6 +1404,64 @@ mod tests {
         assert!(external_documents_ids.get(&rtxn, "1").unwrap().is_some());
     }
 
+    #[test]
+    fn index_documents_with_composite_primary_key() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("tenant_id, sku.code".to_owned());
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "tenant_id": "acme", "sku": { "code": 1 }, "title": "The first document" },
+                { "tenant_id": "acme", "sku": { "code": 2 }, "title": "The second document" },
+                { "tenant_id": "globex", "sku.code": 1, "title": "The third document" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let external_documents_ids = index.external_documents_ids();
+        assert!(external_documents_ids.get(&rtxn, "acme,1").unwrap().is_some());
+        assert!(external_documents_ids.get(&rtxn, "acme,2").unwrap().is_some());
+        assert!(external_documents_ids.get(&rtxn, "globex,1").unwrap().is_some());
+        drop(rtxn);
+
+        // a document must define every component of the primary key
+        let error = index
+            .add_documents(documents!({ "tenant_id": "acme", "title": "No sku" }))
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            crate::Error::UserError(crate::UserError::MissingDocumentId { .. })
+        ));
+    }
+
+    #[test]
+    fn invalid_composite_primary_key() {
+        let index = TempIndex::new();
+
+        for primary_key in ["tenant_id,", "tenant_id, ,sku", "tenant_id,sku, tenant_id"] {
+            let error = index
+                .update_settings(|settings| {
+                    settings.set_primary_key(primary_key.to_owned());
+                })
+                .unwrap_err();
+            assert!(
+                matches!(
+                    error,
+                    crate::Error::UserError(crate::UserError::InvalidCompositePrimaryKey(_))
+                ),
+                "{primary_key}: {error}"
+            );
+        }
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.primary_key(&rtxn).unwrap(), None);
+    }
+
     #[test]
     fn test_facets_generation() {
         let index = TempIndex::new();
@@ -2754,6 +2812,7 @@ mod tests {
                         model: Setting::NotSet,
                         revision: Setting::NotSet,
                         pooling: Setting::NotSet,
+                        normalize: Setting::NotSet,
                         api_key: Setting::NotSet,
                         dimensions: Setting::Set(3),
                         document_template: Setting::NotSet,
@@ -2763,9 +2822,13 @@ mod tests {
                         response: Setting::NotSet,
                         distribution: Setting::NotSet,
                         headers: Setting::NotSet,
+                        max_concurrent_requests: Setting::NotSet,
+                        requests_per_minute: Setting::NotSet,
+                        tokens_per_minute: Setting::NotSet,
                         search_embedder: Setting::NotSet,
                         indexing_embedder: Setting::NotSet,
                         binary_quantized: Setting::NotSet,
+                        shadow_of: Setting::NotSet,
                     }),
                 );
                 settings.set_embedder_settings(embedders);
@@ -2791,11 +2854,16 @@ mod tests {
         insta::assert_snapshot!(embedder_name, @"manual");
         insta::assert_debug_snapshot!(user_provided, @"RoaringBitmap<[0, 1, 2]>");
         let embedder = std::sync::Arc::new(
-            crate::vector::Embedder::new(embedder.embedder_options, 0).unwrap(),
+            crate::vector::Embedder::new(embedder.embedder_options, 0, None).unwrap(),
         );
         let res = index
             .search(&rtxn)
-            .semantic(embedder_name, embedder, false, Some([0.0, 1.0, 2.0].to_vec()))
+            .semantic(
+                embedder_name,
+                embedder,
+                false,
+                Some(crate::vector::parsed_vectors::QueryVector::Single([0.0, 1.0, 2.0].to_vec())),
+            )
             .execute()
             .unwrap();
         assert_eq!(res.documents_ids.len(), 3);
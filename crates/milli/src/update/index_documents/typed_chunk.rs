@@ -157,6 +157,7 @@ pub(crate) fn write_typed_chunk_into_index(
             let embedders: BTreeSet<_> = index
                 .embedding_configs(wtxn)?
                 .into_iter()
+                .filter(|IndexEmbeddingConfig { config, .. }| !config.embedder_options.is_sparse())
                 .map(|IndexEmbeddingConfig { name, .. }| name)
                 .collect();
             let mut vectors_buffer = Vec::new();
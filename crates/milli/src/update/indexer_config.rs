@@ -1,6 +1,7 @@
 use grenad::CompressionType;
 
 use super::GrenadParameters;
+use crate::vector::persistent_cache::PersistentEmbeddingCache;
 use crate::{thread_pool_no_abort::ThreadPoolNoAbort, ThreadPoolNoAbortBuilder};
 
 #[derive(Debug)]
@@ -15,6 +16,8 @@ pub struct IndexerConfig {
     pub thread_pool: ThreadPoolNoAbort,
     pub max_positions_per_attributes: Option<u32>,
     pub skip_index_budget: bool,
+    /// Embeddings cache consulted before calling the embedders, if enabled.
+    pub embedding_cache: Option<PersistentEmbeddingCache>,
 }
 
 impl IndexerConfig {
@@ -63,6 +66,7 @@ impl Default for IndexerConfig {
             chunk_compression_level: None,
             max_positions_per_attributes: None,
             skip_index_budget: false,
+            embedding_cache: None,
         }
     }
 }
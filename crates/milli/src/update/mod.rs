@@ -6,13 +6,17 @@ pub use self::facet::incremental::FacetsUpdateIncrementalInner;
 pub use self::index_documents::*;
 pub use self::indexer_config::{default_thread_pool_and_threads, IndexerConfig};
 pub use self::new::ChannelCongestion;
-pub use self::settings::{validate_embedding_settings, Setting, Settings};
+pub use self::settings::{
+    validate_embedding_settings, EmbedderChange, ReindexStrategy, Setting, Settings,
+    SettingsChangeEstimate,
+};
 pub use self::update_step::UpdateIndexingStep;
 pub use self::word_prefix_docids::WordPrefixDocids;
 pub use self::words_prefix_integer_docids::WordPrefixIntegerDocids;
 pub use self::words_prefixes_fst::WordsPrefixesFst;
 
 mod available_ids;
+pub mod chat;
 mod clear_documents;
 mod concurrent_available_ids;
 pub(crate) mod del_add;
@@ -398,6 +398,7 @@ pub enum Database {
     FacetIdStringDocids,
     FieldIdDocidFacetStrings,
     FieldIdDocidFacetF64s,
+    VectorSparse,
 }
 
 impl Database {
@@ -419,6 +420,7 @@ impl Database {
             Database::FacetIdStringDocids => index.facet_id_string_docids.remap_types(),
             Database::FieldIdDocidFacetStrings => index.field_id_docid_facet_strings.remap_types(),
             Database::FieldIdDocidFacetF64s => index.field_id_docid_facet_f64s.remap_types(),
+            Database::VectorSparse => index.vector_sparse,
         }
     }
 
@@ -440,6 +442,7 @@ impl Database {
             Database::FacetIdStringDocids => db_name::FACET_ID_STRING_DOCIDS,
             Database::FieldIdDocidFacetStrings => db_name::FIELD_ID_DOCID_FACET_STRINGS,
             Database::FieldIdDocidFacetF64s => db_name::FIELD_ID_DOCID_FACET_F64S,
+            Database::VectorSparse => db_name::VECTOR_SPARSE,
         }
     }
 }
@@ -481,6 +484,10 @@ impl<'b> ExtractorBbqueueSender<'b> {
         GeoSender(self)
     }
 
+    pub fn sparse_vectors<'a>(&'a self) -> SparseVectorSender<'a, 'b> {
+        SparseVectorSender(self)
+    }
+
     fn delete_vector(&self, docid: DocumentId) -> crate::Result<()> {
         let max_grant = self.max_grant;
         let refcell = self.producers.get().unwrap();
@@ -948,6 +955,21 @@ impl EmbeddingSender<'_, '_> {
     }
 }
 
+#[derive(Clone, Copy)]
+pub struct SparseVectorSender<'a, 'b>(&'a ExtractorBbqueueSender<'b>);
+
+impl SparseVectorSender<'_, '_> {
+    /// Writes an entry of the inverted index of the sparse vectors.
+    pub fn set_posting(&self, key: &[u8], weight: &[u8]) -> crate::Result<()> {
+        self.0.write_key_value(Database::VectorSparse, key, weight)
+    }
+
+    /// Deletes an entry of the inverted index of the sparse vectors.
+    pub fn delete_posting(&self, key: &[u8]) -> crate::Result<()> {
+        self.0.delete_entry(Database::VectorSparse, key)
+    }
+}
+
 #[derive(Clone, Copy)]
 pub struct GeoSender<'a, 'b>(&'a ExtractorBbqueueSender<'b>);
 
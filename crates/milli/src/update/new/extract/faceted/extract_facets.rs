@@ -1,6 +1,7 @@
 use std::cell::RefCell;
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 use std::ops::DerefMut as _;
+use std::sync::RwLock;
 
 use bumpalo::collections::Vec as BVec;
 use bumpalo::Bump;
@@ -10,7 +11,7 @@ use serde_json::Value;
 use super::super::cache::BalancedCaches;
 use super::facet_document::{extract_document_facets, extract_geo_document};
 use super::FacetKind;
-use crate::fields_ids_map::metadata::Metadata;
+use crate::fields_ids_map::metadata::{FieldIdMapWithMetadata, Metadata};
 use crate::filterable_attributes_rules::match_faceted_field;
 use crate::heed_codec::facet::OrderedF64Codec;
 use crate::update::del_add::DelAdd;
@@ -23,8 +24,12 @@ use crate::update::new::ref_cell_ext::RefCellExt as _;
 use crate::update::new::steps::IndexingStep;
 use crate::update::new::thread_local::{FullySend, ThreadLocal};
 use crate::update::new::DocumentChange;
+use crate::update::settings::InnerIndexSettingsDiff;
 use crate::update::GrenadParameters;
-use crate::{DocumentId, FieldId, FilterableAttributesRule, Result, MAX_FACET_VALUE_LENGTH};
+use crate::{
+    DocumentId, FieldId, FilterableAttributesRule, GlobalFieldsIdsMap, Result,
+    MAX_FACET_VALUE_LENGTH,
+};
 
 pub struct FacetedExtractorData<'a, 'b> {
     sender: &'a FieldIdDocidFacetSender<'a, 'b>,
@@ -70,6 +75,48 @@ impl<'extractor> Extractor<'extractor> for FacetedExtractorData<'_, '_> {
     }
 }
 
+/// Extracts the facet values of the fields affected by a settings change.
+pub struct FacetedSettingsExtractorData<'a, 'b> {
+    sender: &'a FieldIdDocidFacetSender<'a, 'b>,
+    grenad_parameters: &'a GrenadParameters,
+    buckets: usize,
+    settings_diff: &'a InnerIndexSettingsDiff,
+    old_fields_ids_map: &'a RwLock<FieldIdMapWithMetadata>,
+    fields_to_reindex: &'a BTreeSet<FieldId>,
+}
+
+impl<'extractor> Extractor<'extractor> for FacetedSettingsExtractorData<'_, '_> {
+    type Data = RefCell<BalancedCaches<'extractor>>;
+
+    fn init_data(&self, extractor_alloc: &'extractor Bump) -> Result<Self::Data> {
+        Ok(RefCell::new(BalancedCaches::new_in(
+            self.buckets,
+            self.grenad_parameters.max_memory_by_thread(),
+            extractor_alloc,
+        )))
+    }
+
+    fn process<'doc>(
+        &self,
+        changes: impl Iterator<Item = Result<DocumentChange<'doc>>>,
+        context: &DocumentChangeContext<Self::Data>,
+    ) -> Result<()> {
+        let mut old_fields_ids_map = GlobalFieldsIdsMap::new(self.old_fields_ids_map);
+        for change in changes {
+            let change = change?;
+            FacetedDocidsExtractor::extract_settings_change(
+                context,
+                self.settings_diff,
+                self.fields_to_reindex,
+                &mut old_fields_ids_map,
+                change,
+                self.sender,
+            )?
+        }
+        Ok(())
+    }
+}
+
 pub struct FacetedDocidsExtractor;
 
 impl FacetedDocidsExtractor {
@@ -136,6 +183,7 @@ impl FacetedDocidsExtractor {
 
                 extract_document_facets(
                     inner.current(rtxn, index, context.db_fields_ids_map)?,
+                    None,
                     new_fields_ids_map.deref_mut(),
                     filterable_attributes,
                     sortable_fields,
@@ -173,6 +221,7 @@ impl FacetedDocidsExtractor {
                 if has_changed_for_facets {
                     extract_document_facets(
                         inner.current(rtxn, index, context.db_fields_ids_map)?,
+                        None,
                         new_fields_ids_map.deref_mut(),
                         filterable_attributes,
                         sortable_fields,
@@ -183,6 +232,7 @@ impl FacetedDocidsExtractor {
 
                     extract_document_facets(
                         inner.merged(rtxn, index, context.db_fields_ids_map)?,
+                        Some(inner.external_document_id()),
                         new_fields_ids_map.deref_mut(),
                         filterable_attributes,
                         sortable_fields,
@@ -215,6 +265,7 @@ impl FacetedDocidsExtractor {
 
                 extract_document_facets(
                     inner.inserted(),
+                    Some(inner.external_document_id()),
                     new_fields_ids_map.deref_mut(),
                     filterable_attributes,
                     sortable_fields,
@@ -238,6 +289,88 @@ impl FacetedDocidsExtractor {
         Ok(())
     }
 
+    /// Deletes the facet values of the document extracted with the old settings and adds
+    /// the ones extracted with the new settings, only for the `fields_to_reindex`.
+    fn extract_settings_change(
+        context: &DocumentChangeContext<RefCell<BalancedCaches>>,
+        settings_diff: &InnerIndexSettingsDiff,
+        fields_to_reindex: &BTreeSet<FieldId>,
+        old_fields_ids_map: &mut GlobalFieldsIdsMap,
+        document_change: DocumentChange,
+        sender: &FieldIdDocidFacetSender,
+    ) -> Result<()> {
+        // the documents themselves are left unchanged by a settings change
+        let DocumentChange::Update(inner) = document_change else {
+            return Ok(());
+        };
+
+        let index = context.index;
+        let rtxn = &context.rtxn;
+        let mut new_fields_ids_map = context.new_fields_ids_map.borrow_mut_or_yield();
+        let mut cached_sorter = context.data.borrow_mut_or_yield();
+        let mut del_add_facet_value = DelAddFacetValue::new(&context.doc_alloc);
+        let docid = inner.docid();
+        let InnerIndexSettingsDiff { old, new, .. } = settings_diff;
+
+        extract_document_facets(
+            inner.current(rtxn, index, context.db_fields_ids_map)?,
+            None,
+            old_fields_ids_map,
+            &old.filterable_attributes_rules,
+            &old.sortable_fields,
+            &old.asc_desc_fields,
+            &old.distinct_field,
+            &mut |fid: FieldId, meta: Metadata, depth: perm_json_p::Depth, value: &Value| {
+                if !fields_to_reindex.contains(&fid) {
+                    return Ok(());
+                }
+                Self::facet_fn_with_options(
+                    &context.doc_alloc,
+                    cached_sorter.deref_mut(),
+                    BalancedCaches::insert_del_u32,
+                    &mut del_add_facet_value,
+                    DelAddFacetValue::insert_del,
+                    docid,
+                    fid,
+                    meta,
+                    &old.filterable_attributes_rules,
+                    depth,
+                    value,
+                )
+            },
+        )?;
+
+        extract_document_facets(
+            inner.current(rtxn, index, context.db_fields_ids_map)?,
+            None,
+            new_fields_ids_map.deref_mut(),
+            &new.filterable_attributes_rules,
+            &new.sortable_fields,
+            &new.asc_desc_fields,
+            &new.distinct_field,
+            &mut |fid: FieldId, meta: Metadata, depth: perm_json_p::Depth, value: &Value| {
+                if !fields_to_reindex.contains(&fid) {
+                    return Ok(());
+                }
+                Self::facet_fn_with_options(
+                    &context.doc_alloc,
+                    cached_sorter.deref_mut(),
+                    BalancedCaches::insert_add_u32,
+                    &mut del_add_facet_value,
+                    DelAddFacetValue::insert_add,
+                    docid,
+                    fid,
+                    meta,
+                    &new.filterable_attributes_rules,
+                    depth,
+                    value,
+                )
+            },
+        )?;
+
+        del_add_facet_value.send_data(docid, sender, &context.doc_alloc)
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn facet_fn_with_options<'extractor, 'doc>(
         doc_alloc: &'doc Bump,
@@ -517,4 +650,57 @@ impl FacetedDocidsExtractor {
 
         Ok(datastore.into_iter().map(RefCell::into_inner).collect())
     }
+
+    /// Extracts the facet values of the `fields_to_reindex` from the documents of the index,
+    /// as a deletion with the old settings and as an addition with the new ones.
+    #[allow(clippy::too_many_arguments)]
+    #[tracing::instrument(level = "trace", skip_all, target = "indexing::extract::faceted")]
+    pub fn run_settings_extraction<
+        'pl,
+        'fid,
+        'indexer,
+        'index,
+        'extractor,
+        DC: DocumentChanges<'pl>,
+        MSP,
+    >(
+        document_changes: &DC,
+        indexing_context: IndexingContext<'fid, 'indexer, 'index, MSP>,
+        extractor_allocs: &'extractor mut ThreadLocal<FullySend<Bump>>,
+        sender: &FieldIdDocidFacetSender,
+        settings_diff: &InnerIndexSettingsDiff,
+        old_fields_ids_map: &RwLock<FieldIdMapWithMetadata>,
+        fields_to_reindex: &BTreeSet<FieldId>,
+        step: IndexingStep,
+    ) -> Result<Vec<BalancedCaches<'extractor>>>
+    where
+        MSP: Fn() -> bool + Sync,
+    {
+        let datastore = ThreadLocal::new();
+
+        {
+            let span =
+                tracing::trace_span!(target: "indexing::documents::extract", "docids_extraction");
+            let _entered = span.enter();
+
+            let extractor = FacetedSettingsExtractorData {
+                grenad_parameters: indexing_context.grenad_parameters,
+                buckets: rayon::current_num_threads(),
+                sender,
+                settings_diff,
+                old_fields_ids_map,
+                fields_to_reindex,
+            };
+            extract(
+                document_changes,
+                &extractor,
+                indexing_context,
+                extractor_allocs,
+                &datastore,
+                step,
+            )?;
+        }
+
+        Ok(datastore.into_iter().map(RefCell::into_inner).collect())
+    }
 }
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::HashSet;
 
 use serde_json::Value;
@@ -13,9 +14,14 @@ use crate::{
 
 use crate::filterable_attributes_rules::match_faceted_field;
 
+/// Calls `facet_fn` on the faceted values of the document, converted to their declared field type.
+///
+/// The values that do not match their declared type are reported as an error when an
+/// `external_document_id` is given, and ignored otherwise.
 #[allow(clippy::too_many_arguments)]
 pub fn extract_document_facets<'doc>(
     document: impl Document<'doc>,
+    external_document_id: Option<&str>,
     field_id_map: &mut GlobalFieldsIdsMap,
     filterable_attributes: &[FilterableAttributesRule],
     sortable_fields: &HashSet<String>,
@@ -38,7 +44,23 @@ pub fn extract_document_facets<'doc>(
     let mut extract_field = |name: &str, depth: perm_json_p::Depth, value: &Value| -> Result<()> {
         match field_id_map.id_with_metadata_or_insert(name) {
             Some((field_id, meta)) => {
-                facet_fn(field_id, meta, depth, value)?;
+                let value = match meta.field_type() {
+                    Some(field_type) => match (field_type.coerce(value), external_document_id) {
+                        (Some(value), _) => value,
+                        (None, Some(document_id)) => {
+                            return Err(UserError::InvalidDocumentFieldType {
+                                document_id: document_id.to_string(),
+                                field: name.to_string(),
+                                expected: field_type,
+                                value: value.clone(),
+                            }
+                            .into())
+                        }
+                        (None, None) => return Ok(()),
+                    },
+                    None => Cow::Borrowed(value),
+                };
+                facet_fn(field_id, meta, depth, &value)?;
 
                 Ok(())
             }
@@ -2,12 +2,15 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::mem::size_of;
 use std::ops::DerefMut as _;
+use std::sync::RwLock;
 
 use bumpalo::collections::vec::Vec as BumpVec;
 use bumpalo::Bump;
 
 use super::match_searchable_field;
-use super::tokenize_document::{tokenizer_builder, DocumentTokenizer};
+use super::tokenize_document::{tokenizer_builder, DocumentTokenizer, TokenizerSettings};
+use crate::disabled_typos_terms::DisabledTyposTerms;
+use crate::fields_ids_map::metadata::FieldIdMapWithMetadata;
 use crate::update::new::extract::cache::BalancedCaches;
 use crate::update::new::extract::perm_json_p::contained_in;
 use crate::update::new::indexer::document_changes::{
@@ -17,7 +20,10 @@ use crate::update::new::ref_cell_ext::RefCellExt as _;
 use crate::update::new::steps::IndexingStep;
 use crate::update::new::thread_local::{FullySend, MostlySend, ThreadLocal};
 use crate::update::new::DocumentChange;
-use crate::{bucketed_position, DocumentId, FieldId, Result, MAX_POSITION_PER_ATTRIBUTE};
+use crate::update::settings::{InnerIndexSettings, InnerIndexSettingsDiff};
+use crate::{
+    bucketed_position, DocumentId, FieldId, GlobalFieldsIdsMap, Result, MAX_POSITION_PER_ATTRIBUTE,
+};
 
 const MAX_COUNTED_WORDS: usize = 30;
 
@@ -241,6 +247,48 @@ impl<'extractor> Extractor<'extractor> for WordDocidsExtractorData<'_> {
     }
 }
 
+/// Extracts the words of the fields affected by a settings change.
+pub struct WordDocidsSettingsExtractorData<'a> {
+    old_tokenizer: DocumentTokenizer<'a>,
+    new_tokenizer: DocumentTokenizer<'a>,
+    old_exact_attributes: Vec<&'a str>,
+    new_exact_attributes: Vec<&'a str>,
+    old_fields_ids_map: &'a RwLock<FieldIdMapWithMetadata>,
+    settings_diff: &'a InnerIndexSettingsDiff,
+    max_memory_by_thread: Option<usize>,
+    buckets: usize,
+}
+
+impl<'extractor> Extractor<'extractor> for WordDocidsSettingsExtractorData<'_> {
+    type Data = RefCell<Option<WordDocidsBalancedCaches<'extractor>>>;
+
+    fn init_data(&self, extractor_alloc: &'extractor Bump) -> Result<Self::Data> {
+        Ok(RefCell::new(Some(WordDocidsBalancedCaches::new_in(
+            self.buckets,
+            self.max_memory_by_thread,
+            extractor_alloc,
+        ))))
+    }
+
+    fn process<'doc>(
+        &self,
+        changes: impl Iterator<Item = Result<DocumentChange<'doc>>>,
+        context: &DocumentChangeContext<Self::Data>,
+    ) -> Result<()> {
+        let mut old_fields_ids_map = GlobalFieldsIdsMap::new(self.old_fields_ids_map);
+        for change in changes {
+            let change = change?;
+            WordDocidsExtractors::extract_settings_change(
+                context,
+                self,
+                &mut old_fields_ids_map,
+                change,
+            )?;
+        }
+        Ok(())
+    }
+}
+
 pub struct WordDocidsExtractors;
 
 impl WordDocidsExtractors {
@@ -304,6 +352,140 @@ impl WordDocidsExtractors {
         Ok(merger)
     }
 
+    /// Extracts the words of the fields affected by a settings change from the documents of
+    /// the index, as a deletion with the old settings and as an addition with the new ones.
+    pub fn run_settings_extraction<
+        'pl,
+        'fid,
+        'indexer,
+        'index,
+        'extractor,
+        DC: DocumentChanges<'pl>,
+        MSP,
+    >(
+        document_changes: &DC,
+        indexing_context: IndexingContext<'fid, 'indexer, 'index, MSP>,
+        extractor_allocs: &'extractor mut ThreadLocal<FullySend<Bump>>,
+        settings_diff: &InnerIndexSettingsDiff,
+        old_fields_ids_map: &RwLock<FieldIdMapWithMetadata>,
+        step: IndexingStep,
+    ) -> Result<WordDocidsCaches<'extractor>>
+    where
+        MSP: Fn() -> bool + Sync,
+    {
+        let InnerIndexSettingsDiff { old, new, .. } = settings_diff;
+        let old_tokenizer_settings = TokenizerSettings::new(old)?;
+        let mut old_builder = old_tokenizer_settings.builder();
+        let old_tokenizer = old_builder.build();
+        let new_tokenizer_settings = TokenizerSettings::new(new)?;
+        let mut new_builder = new_tokenizer_settings.builder();
+        let new_tokenizer = new_builder.build();
+        let extractor_data = WordDocidsSettingsExtractorData {
+            old_tokenizer: DocumentTokenizer {
+                tokenizer: &old_tokenizer,
+                localized_attributes_rules: &old.localized_attributes_rules,
+                max_positions_per_attributes: MAX_POSITION_PER_ATTRIBUTE,
+            },
+            new_tokenizer: DocumentTokenizer {
+                tokenizer: &new_tokenizer,
+                localized_attributes_rules: &new.localized_attributes_rules,
+                max_positions_per_attributes: MAX_POSITION_PER_ATTRIBUTE,
+            },
+            old_exact_attributes: exact_attributes(old),
+            new_exact_attributes: exact_attributes(new),
+            old_fields_ids_map,
+            settings_diff,
+            max_memory_by_thread: indexing_context.grenad_parameters.max_memory_by_thread(),
+            buckets: rayon::current_num_threads(),
+        };
+        let datastore = ThreadLocal::new();
+        {
+            let span =
+                tracing::trace_span!(target: "indexing::documents::extract", "docids_extraction");
+            let _entered = span.enter();
+            extract(
+                document_changes,
+                &extractor_data,
+                indexing_context,
+                extractor_allocs,
+                &datastore,
+                step,
+            )?;
+        }
+
+        let mut merger = WordDocidsCaches::new();
+        for cache in datastore.into_iter().flat_map(RefCell::into_inner) {
+            merger.push(cache)?;
+        }
+
+        Ok(merger)
+    }
+
+    /// Deletes the words of the document extracted with the old settings and adds the ones
+    /// extracted with the new settings, only for the fields affected by the settings change.
+    fn extract_settings_change(
+        context: &DocumentChangeContext<RefCell<Option<WordDocidsBalancedCaches>>>,
+        extractor_data: &WordDocidsSettingsExtractorData,
+        old_fields_ids_map: &mut GlobalFieldsIdsMap,
+        document_change: DocumentChange,
+    ) -> Result<()> {
+        // the documents themselves are left unchanged by a settings change
+        let DocumentChange::Update(inner) = document_change else {
+            return Ok(());
+        };
+
+        let index = &context.index;
+        let rtxn = &context.rtxn;
+        let mut cached_sorter_ref = context.data.borrow_mut_or_yield();
+        let cached_sorter = cached_sorter_ref.as_mut().unwrap();
+        let mut new_fields_ids_map = context.new_fields_ids_map.borrow_mut_or_yield();
+        let new_fields_ids_map = new_fields_ids_map.deref_mut();
+        let doc_alloc = &context.doc_alloc;
+        let settings_diff = extractor_data.settings_diff;
+        let InnerIndexSettingsDiff { old, new, .. } = settings_diff;
+        let docid = inner.docid();
+
+        // the words of the other fields are kept when fields are only added to the searchable attributes
+        if !settings_diff.only_additional_searchables() {
+            let mut token_fn = |fname: &str, fid, pos, word: &str| {
+                let exact = is_exact(
+                    &extractor_data.old_exact_attributes,
+                    &old.disabled_typos_terms,
+                    fname,
+                    word,
+                );
+                cached_sorter.insert_del_u32(fid, pos, word, exact, docid, doc_alloc)
+            };
+            extractor_data.old_tokenizer.tokenize_document(
+                inner.current(rtxn, index, context.db_fields_ids_map)?,
+                old_fields_ids_map,
+                &mut token_fn,
+            )?;
+        }
+
+        let mut token_fn = |fname: &str, fid, pos, word: &str| {
+            if settings_diff.reindex_searchable_field(fname).is_none() {
+                return Ok(());
+            }
+            let exact = is_exact(
+                &extractor_data.new_exact_attributes,
+                &new.disabled_typos_terms,
+                fname,
+                word,
+            );
+            cached_sorter.insert_add_u32(fid, pos, word, exact, docid, doc_alloc)
+        };
+        extractor_data.new_tokenizer.tokenize_document(
+            inner.current(rtxn, index, context.db_fields_ids_map)?,
+            new_fields_ids_map,
+            &mut token_fn,
+        )?;
+
+        let buffer_size = size_of::<FieldId>();
+        let mut buffer = BumpVec::with_capacity_in(buffer_size, &context.doc_alloc);
+        cached_sorter.flush_fid_word_count(&mut buffer)
+    }
+
     fn extract_document_change(
         context: &DocumentChangeContext<RefCell<Option<WordDocidsBalancedCaches>>>,
         document_tokenizer: &DocumentTokenizer,
@@ -410,3 +592,18 @@ impl WordDocidsExtractors {
         cached_sorter.flush_fid_word_count(&mut buffer)
     }
 }
+
+/// Returns the names of the exact attributes of the settings.
+fn exact_attributes(settings: &InnerIndexSettings) -> Vec<&str> {
+    settings.exact_attributes.iter().filter_map(|fid| settings.fields_ids_map.name(*fid)).collect()
+}
+
+fn is_exact(
+    exact_attributes: &[&str],
+    disabled_typos_terms: &DisabledTyposTerms,
+    field_name: &str,
+    word: &str,
+) -> bool {
+    exact_attributes.iter().any(|attr| contained_in(field_name, attr))
+        || disabled_typos_terms.is_exact(word)
+}
@@ -1,11 +1,13 @@
 use std::cell::RefCell;
 use std::collections::VecDeque;
 use std::rc::Rc;
+use std::sync::RwLock;
 
 use bumpalo::Bump;
 
 use super::match_searchable_field;
-use super::tokenize_document::{tokenizer_builder, DocumentTokenizer};
+use super::tokenize_document::{tokenizer_builder, DocumentTokenizer, TokenizerSettings};
+use crate::fields_ids_map::metadata::FieldIdMapWithMetadata;
 use crate::proximity::{index_proximity, MAX_DISTANCE};
 use crate::update::new::document::Document;
 use crate::update::new::extract::cache::BalancedCaches;
@@ -16,7 +18,8 @@ use crate::update::new::ref_cell_ext::RefCellExt as _;
 use crate::update::new::steps::IndexingStep;
 use crate::update::new::thread_local::{FullySend, ThreadLocal};
 use crate::update::new::DocumentChange;
-use crate::{FieldId, GlobalFieldsIdsMap, Result, MAX_POSITION_PER_ATTRIBUTE};
+use crate::update::settings::InnerIndexSettingsDiff;
+use crate::{DocumentId, FieldId, GlobalFieldsIdsMap, Result, MAX_POSITION_PER_ATTRIBUTE};
 
 pub struct WordPairProximityDocidsExtractorData<'a> {
     tokenizer: DocumentTokenizer<'a>,
@@ -54,6 +57,46 @@ impl<'extractor> Extractor<'extractor> for WordPairProximityDocidsExtractorData<
     }
 }
 
+/// Extracts the word pair proximities of the fields affected by a settings change.
+pub struct WordPairProximitySettingsExtractorData<'a> {
+    old_tokenizer: DocumentTokenizer<'a>,
+    new_tokenizer: DocumentTokenizer<'a>,
+    old_fields_ids_map: &'a RwLock<FieldIdMapWithMetadata>,
+    settings_diff: &'a InnerIndexSettingsDiff,
+    max_memory_by_thread: Option<usize>,
+    buckets: usize,
+}
+
+impl<'extractor> Extractor<'extractor> for WordPairProximitySettingsExtractorData<'_> {
+    type Data = RefCell<BalancedCaches<'extractor>>;
+
+    fn init_data(&self, extractor_alloc: &'extractor Bump) -> Result<Self::Data> {
+        Ok(RefCell::new(BalancedCaches::new_in(
+            self.buckets,
+            self.max_memory_by_thread,
+            extractor_alloc,
+        )))
+    }
+
+    fn process<'doc>(
+        &self,
+        changes: impl Iterator<Item = Result<DocumentChange<'doc>>>,
+        context: &DocumentChangeContext<Self::Data>,
+    ) -> Result<()> {
+        let mut old_fields_ids_map = GlobalFieldsIdsMap::new(self.old_fields_ids_map);
+        for change in changes {
+            let change = change?;
+            WordPairProximityDocidsExtractor::extract_settings_change(
+                context,
+                self,
+                &mut old_fields_ids_map,
+                change,
+            )?;
+        }
+        Ok(())
+    }
+}
+
 pub struct WordPairProximityDocidsExtractor;
 
 impl WordPairProximityDocidsExtractor {
@@ -148,6 +191,7 @@ impl WordPairProximityDocidsExtractor {
                     document,
                     document_tokenizer,
                     new_fields_ids_map,
+                    &|_| true,
                     &mut word_positions,
                     &mut |(w1, w2), prox| {
                         del_word_pair_proximity.push(((w1, w2), prox));
@@ -171,6 +215,7 @@ impl WordPairProximityDocidsExtractor {
                     document,
                     document_tokenizer,
                     new_fields_ids_map,
+                    &|_| true,
                     &mut word_positions,
                     &mut |(w1, w2), prox| {
                         del_word_pair_proximity.push(((w1, w2), prox));
@@ -181,6 +226,7 @@ impl WordPairProximityDocidsExtractor {
                     document,
                     document_tokenizer,
                     new_fields_ids_map,
+                    &|_| true,
                     &mut word_positions,
                     &mut |(w1, w2), prox| {
                         add_word_pair_proximity.push(((w1, w2), prox));
@@ -193,6 +239,7 @@ impl WordPairProximityDocidsExtractor {
                     document,
                     document_tokenizer,
                     new_fields_ids_map,
+                    &|_| true,
                     &mut word_positions,
                     &mut |(w1, w2), prox| {
                         add_word_pair_proximity.push(((w1, w2), prox));
@@ -201,23 +248,169 @@ impl WordPairProximityDocidsExtractor {
             }
         }
 
-        del_word_pair_proximity.sort_unstable();
-        del_word_pair_proximity.dedup_by(|(k1, _), (k2, _)| k1 == k2);
-        for ((w1, w2), prox) in del_word_pair_proximity.iter() {
-            let key = build_key(*prox, w1, w2, &mut key_buffer);
-            cached_sorter.insert_del_u32(key, docid)?;
+        insert_word_pair_proximities(
+            cached_sorter,
+            docid,
+            del_word_pair_proximity,
+            add_word_pair_proximity,
+            &mut key_buffer,
+        )
+    }
+
+    /// Extracts the word pair proximities of the fields affected by a settings change from the
+    /// documents of the index, as a deletion with the old settings and as an addition with the new ones.
+    pub fn run_settings_extraction<
+        'pl,
+        'fid,
+        'indexer,
+        'index,
+        'extractor,
+        DC: DocumentChanges<'pl>,
+        MSP,
+    >(
+        document_changes: &DC,
+        indexing_context: IndexingContext<'fid, 'indexer, 'index, MSP>,
+        extractor_allocs: &'extractor mut ThreadLocal<FullySend<Bump>>,
+        settings_diff: &InnerIndexSettingsDiff,
+        old_fields_ids_map: &RwLock<FieldIdMapWithMetadata>,
+        step: IndexingStep,
+    ) -> Result<Vec<BalancedCaches<'extractor>>>
+    where
+        MSP: Fn() -> bool + Sync,
+    {
+        let InnerIndexSettingsDiff { old, new, .. } = settings_diff;
+        let old_tokenizer_settings = TokenizerSettings::new(old)?;
+        let mut old_builder = old_tokenizer_settings.builder();
+        let old_tokenizer = old_builder.build();
+        let new_tokenizer_settings = TokenizerSettings::new(new)?;
+        let mut new_builder = new_tokenizer_settings.builder();
+        let new_tokenizer = new_builder.build();
+        let extractor_data = WordPairProximitySettingsExtractorData {
+            old_tokenizer: DocumentTokenizer {
+                tokenizer: &old_tokenizer,
+                localized_attributes_rules: &old.localized_attributes_rules,
+                max_positions_per_attributes: MAX_POSITION_PER_ATTRIBUTE,
+            },
+            new_tokenizer: DocumentTokenizer {
+                tokenizer: &new_tokenizer,
+                localized_attributes_rules: &new.localized_attributes_rules,
+                max_positions_per_attributes: MAX_POSITION_PER_ATTRIBUTE,
+            },
+            old_fields_ids_map,
+            settings_diff,
+            max_memory_by_thread: indexing_context.grenad_parameters.max_memory_by_thread(),
+            buckets: rayon::current_num_threads(),
+        };
+        let datastore = ThreadLocal::new();
+        {
+            let span =
+                tracing::trace_span!(target: "indexing::documents::extract", "docids_extraction");
+            let _entered = span.enter();
+            extract(
+                document_changes,
+                &extractor_data,
+                indexing_context,
+                extractor_allocs,
+                &datastore,
+                step,
+            )?;
         }
 
-        add_word_pair_proximity.sort_unstable();
-        add_word_pair_proximity.dedup_by(|(k1, _), (k2, _)| k1 == k2);
-        for ((w1, w2), prox) in add_word_pair_proximity.iter() {
-            let key = build_key(*prox, w1, w2, &mut key_buffer);
-            cached_sorter.insert_add_u32(key, docid)?;
+        Ok(datastore.into_iter().map(RefCell::into_inner).collect())
+    }
+
+    /// Deletes the word pair proximities of the document extracted with the old settings and adds
+    /// the ones extracted with the new settings, only for the fields affected by the settings change.
+    fn extract_settings_change(
+        context: &DocumentChangeContext<RefCell<BalancedCaches>>,
+        extractor_data: &WordPairProximitySettingsExtractorData,
+        old_fields_ids_map: &mut GlobalFieldsIdsMap,
+        document_change: DocumentChange,
+    ) -> Result<()> {
+        // the documents themselves are left unchanged by a settings change
+        let DocumentChange::Update(inner) = document_change else {
+            return Ok(());
+        };
+
+        let doc_alloc = &context.doc_alloc;
+        let index = context.index;
+        let rtxn = &context.rtxn;
+        let settings_diff = extractor_data.settings_diff;
+
+        let mut key_buffer = bumpalo::collections::Vec::new_in(doc_alloc);
+        let mut del_word_pair_proximity = bumpalo::collections::Vec::new_in(doc_alloc);
+        let mut add_word_pair_proximity = bumpalo::collections::Vec::new_in(doc_alloc);
+
+        let mut new_fields_ids_map = context.new_fields_ids_map.borrow_mut_or_yield();
+        let new_fields_ids_map = &mut *new_fields_ids_map;
+
+        let mut cached_sorter = context.data.borrow_mut_or_yield();
+        let cached_sorter = &mut *cached_sorter;
+
+        let mut word_positions: VecDeque<(Rc<str>, u16)> =
+            VecDeque::with_capacity(MAX_DISTANCE as usize);
+
+        // the pairs of the other fields are kept when fields are only added to the searchable attributes
+        if !settings_diff.only_additional_searchables() {
+            process_document_tokens(
+                inner.current(rtxn, index, context.db_fields_ids_map)?,
+                &extractor_data.old_tokenizer,
+                old_fields_ids_map,
+                &|_| true,
+                &mut word_positions,
+                &mut |(w1, w2), prox| {
+                    del_word_pair_proximity.push(((w1, w2), prox));
+                },
+            )?;
         }
-        Ok(())
+
+        process_document_tokens(
+            inner.current(rtxn, index, context.db_fields_ids_map)?,
+            &extractor_data.new_tokenizer,
+            new_fields_ids_map,
+            &|fname| settings_diff.reindex_searchable_field(fname).is_some(),
+            &mut word_positions,
+            &mut |(w1, w2), prox| {
+                add_word_pair_proximity.push(((w1, w2), prox));
+            },
+        )?;
+
+        insert_word_pair_proximities(
+            cached_sorter,
+            inner.docid(),
+            del_word_pair_proximity,
+            add_word_pair_proximity,
+            &mut key_buffer,
+        )
     }
 }
 
+type WordPairProximity = ((Rc<str>, Rc<str>), u8);
+
+fn insert_word_pair_proximities(
+    cached_sorter: &mut BalancedCaches,
+    docid: DocumentId,
+    mut del_word_pair_proximity: bumpalo::collections::Vec<WordPairProximity>,
+    mut add_word_pair_proximity: bumpalo::collections::Vec<WordPairProximity>,
+    key_buffer: &mut bumpalo::collections::Vec<u8>,
+) -> Result<()> {
+    del_word_pair_proximity.sort_unstable();
+    del_word_pair_proximity.dedup_by(|(k1, _), (k2, _)| k1 == k2);
+    for ((w1, w2), prox) in del_word_pair_proximity.iter() {
+        let key = build_key(*prox, w1, w2, key_buffer);
+        cached_sorter.insert_del_u32(key, docid)?;
+    }
+
+    add_word_pair_proximity.sort_unstable();
+    add_word_pair_proximity.dedup_by(|(k1, _), (k2, _)| k1 == k2);
+    for ((w1, w2), prox) in add_word_pair_proximity.iter() {
+        let key = build_key(*prox, w1, w2, key_buffer);
+        cached_sorter.insert_add_u32(key, docid)?;
+    }
+
+    Ok(())
+}
+
 fn build_key<'a>(
     prox: u8,
     w1: &str,
@@ -258,11 +451,15 @@ fn process_document_tokens<'doc>(
     document: impl Document<'doc>,
     document_tokenizer: &DocumentTokenizer,
     fields_ids_map: &mut GlobalFieldsIdsMap,
+    field_selector: &impl Fn(&str) -> bool,
     word_positions: &mut VecDeque<(Rc<str>, u16)>,
     word_pair_proximity: &mut impl FnMut((Rc<str>, Rc<str>), u8),
 ) -> Result<()> {
     let mut field_id = None;
-    let mut token_fn = |_fname: &str, fid: FieldId, pos: u16, word: &str| {
+    let mut token_fn = |fname: &str, fid: FieldId, pos: u16, word: &str| {
+        if !field_selector(fname) {
+            return Ok(());
+        }
         if field_id != Some(fid) {
             field_id = Some(fid);
             drain_word_positions(word_positions, word_pair_proximity);
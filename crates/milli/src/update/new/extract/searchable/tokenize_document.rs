@@ -8,6 +8,7 @@ use crate::update::new::document::Document;
 use crate::update::new::extract::perm_json_p::{
     seek_leaf_values_in_array, seek_leaf_values_in_object, Depth,
 };
+use crate::update::settings::InnerIndexSettings;
 use crate::{
     FieldId, GlobalFieldsIdsMap, InternalError, LocalizedAttributesRule, Result, UserError,
     MAX_WORD_LENGTH,
@@ -179,6 +180,36 @@ pub fn tokenizer_builder<'a>(
     tokenizer_builder
 }
 
+/// The tokenization settings of an [`InnerIndexSettings`], from which a tokenizer is built.
+pub(crate) struct TokenizerSettings<'a> {
+    stop_words: Option<fst::Set<&'a [u8]>>,
+    allowed_separators: Option<Vec<&'a str>>,
+    dictionary: Option<Vec<&'a str>>,
+}
+
+impl<'a> TokenizerSettings<'a> {
+    pub fn new(settings: &'a InnerIndexSettings) -> Result<Self> {
+        let stop_words = settings
+            .stop_words
+            .as_ref()
+            .map(|stop_words| fst::Set::new(stop_words.as_fst().as_bytes()))
+            .transpose()?;
+        let allowed_separators =
+            settings.allowed_separators.as_ref().map(|s| s.iter().map(String::as_str).collect());
+        let dictionary =
+            settings.dictionary.as_ref().map(|s| s.iter().map(String::as_str).collect());
+        Ok(Self { stop_words, allowed_separators, dictionary })
+    }
+
+    pub fn builder(&self) -> TokenizerBuilder<'_, &[u8]> {
+        tokenizer_builder(
+            self.stop_words.as_ref(),
+            self.allowed_separators.as_deref(),
+            self.dictionary.as_deref(),
+        )
+    }
+}
+
 #[cfg(test)]
 mod test {
     use bumpalo::Bump;
@@ -234,6 +265,7 @@ mod test {
                 None,
                 None,
                 Default::default(),
+                Default::default(),
             ),
         );
 
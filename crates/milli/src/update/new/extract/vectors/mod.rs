@@ -1,4 +1,5 @@
 use std::cell::RefCell;
+use std::fmt::Debug;
 
 use bumpalo::collections::Vec as BVec;
 use bumpalo::Bump;
@@ -8,6 +9,7 @@ use super::cache::DelAddRoaringBitmap;
 use crate::error::FaultSource;
 use crate::prompt::Prompt;
 use crate::update::new::channel::EmbeddingSender;
+use crate::update::new::document::Document;
 use crate::update::new::indexer::document_changes::{DocumentChangeContext, Extractor};
 use crate::update::new::thread_local::MostlySend;
 use crate::update::new::vector_document::VectorDocument;
@@ -15,14 +17,23 @@ use crate::update::new::DocumentChange;
 use crate::vector::error::{
     EmbedErrorKind, PossibleEmbeddingMistakes, UnusedVectorsDistributionBump,
 };
-use crate::vector::{Embedder, Embedding, EmbeddingConfigs};
-use crate::{DocumentId, FieldDistribution, InternalError, Result, ThreadPoolNoAbort, UserError};
+use crate::vector::{Embedder, EmbedderInput, Embedding, EmbeddingConfigs};
+use crate::{
+    DocumentId, FieldDistribution, GlobalFieldsIdsMap, InternalError, Result, ThreadPoolNoAbort,
+    UserError,
+};
+
+mod sparse;
+
+pub use sparse::SparseVectorExtractor;
 
 pub struct EmbeddingExtractor<'a, 'b> {
     embedders: &'a EmbeddingConfigs,
     sender: EmbeddingSender<'a, 'b>,
     possible_embedding_mistakes: PossibleEmbeddingMistakes,
     threads: &'a ThreadPoolNoAbort,
+    /// Embedders whose embeddings are regenerated for all the changed documents.
+    must_regenerate: Vec<String>,
 }
 
 impl<'a, 'b> EmbeddingExtractor<'a, 'b> {
@@ -31,9 +42,10 @@ impl<'a, 'b> EmbeddingExtractor<'a, 'b> {
         sender: EmbeddingSender<'a, 'b>,
         field_distribution: &'a FieldDistribution,
         threads: &'a ThreadPoolNoAbort,
+        must_regenerate: Vec<String>,
     ) -> Self {
         let possible_embedding_mistakes = PossibleEmbeddingMistakes::new(field_distribution);
-        Self { embedders, sender, threads, possible_embedding_mistakes }
+        Self { embedders, sender, threads, possible_embedding_mistakes, must_regenerate }
     }
 }
 
@@ -61,6 +73,10 @@ impl<'extractor> Extractor<'extractor> for EmbeddingExtractor<'_, '_> {
 
         let mut all_chunks = BVec::with_capacity_in(embedders.len(), &context.doc_alloc);
         for (embedder_name, (embedder, prompt, _is_quantized)) in embedders {
+            // handled by the `SparseVectorExtractor`
+            if embedder.is_sparse() {
+                continue;
+            }
             let embedder_id =
                 context.index.embedder_category_id.get(&context.rtxn, embedder_name)?.ok_or_else(
                     || InternalError::DatabaseMissingEntry {
@@ -77,6 +93,7 @@ impl<'extractor> Extractor<'extractor> for EmbeddingExtractor<'_, '_> {
                 &self.possible_embedding_mistakes,
                 self.threads,
                 self.sender,
+                self.must_regenerate.contains(embedder_name),
                 &context.doc_alloc,
             ))
         }
@@ -108,7 +125,6 @@ impl<'extractor> Extractor<'extractor> for EmbeddingExtractor<'_, '_> {
 
                     for chunks in &mut all_chunks {
                         let embedder_name = chunks.embedder_name();
-                        let prompt = chunks.prompt();
 
                         let old_vectors = old_vectors.vectors_for_key(embedder_name)?.unwrap();
                         if let Some(new_vectors) = new_vectors.as_ref().and_then(|new_vectors| {
@@ -131,7 +147,7 @@ impl<'extractor> Extractor<'extractor> for EmbeddingExtractor<'_, '_> {
                                         })?,
                                 )?;
                             } else if new_vectors.regenerate {
-                                let new_rendered = prompt.render_document(
+                                let new_input = chunks.render_document(
                                     update.external_document_id(),
                                     update.current(
                                         &context.rtxn,
@@ -141,7 +157,7 @@ impl<'extractor> Extractor<'extractor> for EmbeddingExtractor<'_, '_> {
                                     context.new_fields_ids_map,
                                     &context.doc_alloc,
                                 )?;
-                                let old_rendered = prompt.render_document(
+                                let old_input = chunks.render_document(
                                     update.external_document_id(),
                                     update.merged(
                                         &context.rtxn,
@@ -151,17 +167,17 @@ impl<'extractor> Extractor<'extractor> for EmbeddingExtractor<'_, '_> {
                                     context.new_fields_ids_map,
                                     &context.doc_alloc,
                                 )?;
-                                if new_rendered != old_rendered {
+                                if chunks.must_regenerate() || new_input != old_input {
                                     chunks.set_autogenerated(
                                         update.docid(),
                                         update.external_document_id(),
-                                        new_rendered,
+                                        new_input,
                                         &unused_vectors_distribution,
                                     )?;
                                 }
                             }
                         } else if old_vectors.regenerate {
-                            let old_rendered = prompt.render_document(
+                            let old_input = chunks.render_document(
                                 update.external_document_id(),
                                 update.current(
                                     &context.rtxn,
@@ -171,7 +187,7 @@ impl<'extractor> Extractor<'extractor> for EmbeddingExtractor<'_, '_> {
                                 context.new_fields_ids_map,
                                 &context.doc_alloc,
                             )?;
-                            let new_rendered = prompt.render_document(
+                            let new_input = chunks.render_document(
                                 update.external_document_id(),
                                 update.merged(
                                     &context.rtxn,
@@ -181,11 +197,11 @@ impl<'extractor> Extractor<'extractor> for EmbeddingExtractor<'_, '_> {
                                 context.new_fields_ids_map,
                                 &context.doc_alloc,
                             )?;
-                            if new_rendered != old_rendered {
+                            if chunks.must_regenerate() || new_input != old_input {
                                 chunks.set_autogenerated(
                                     update.docid(),
                                     update.external_document_id(),
-                                    new_rendered,
+                                    new_input,
                                     &unused_vectors_distribution,
                                 )?;
                             }
@@ -201,7 +217,6 @@ impl<'extractor> Extractor<'extractor> for EmbeddingExtractor<'_, '_> {
 
                     for chunks in &mut all_chunks {
                         let embedder_name = chunks.embedder_name();
-                        let prompt = chunks.prompt();
                         // if no inserted vectors, then regenerate: true + no embeddings => autogenerate
                         if let Some(new_vectors) = new_vectors.as_ref().and_then(|new_vectors| {
                             new_vectors.vectors_for_key(embedder_name).transpose()
@@ -222,7 +237,7 @@ impl<'extractor> Extractor<'extractor> for EmbeddingExtractor<'_, '_> {
                                         })?,
                                 )?;
                             } else if new_vectors.regenerate {
-                                let rendered = prompt.render_document(
+                                let input = chunks.render_document(
                                     insertion.external_document_id(),
                                     insertion.inserted(),
                                     context.new_fields_ids_map,
@@ -231,12 +246,12 @@ impl<'extractor> Extractor<'extractor> for EmbeddingExtractor<'_, '_> {
                                 chunks.set_autogenerated(
                                     insertion.docid(),
                                     insertion.external_document_id(),
-                                    rendered,
+                                    input,
                                     &unused_vectors_distribution,
                                 )?;
                             }
                         } else {
-                            let rendered = prompt.render_document(
+                            let input = chunks.render_document(
                                 insertion.external_document_id(),
                                 insertion.inserted(),
                                 context.new_fields_ids_map,
@@ -245,7 +260,7 @@ impl<'extractor> Extractor<'extractor> for EmbeddingExtractor<'_, '_> {
                             chunks.set_autogenerated(
                                 insertion.docid(),
                                 insertion.external_document_id(),
-                                rendered,
+                                input,
                                 &unused_vectors_distribution,
                             )?;
                         }
@@ -269,7 +284,7 @@ impl<'extractor> Extractor<'extractor> for EmbeddingExtractor<'_, '_> {
 // 1. BVec are inside of the bumaplo
 // 2. All other fields are either trivial (u8) or references.
 struct Chunks<'a, 'b, 'extractor> {
-    texts: BVec<'a, &'a str>,
+    inputs: BVec<'a, EmbedderInput<'a>>,
     ids: BVec<'a, DocumentId>,
 
     embedder: &'a Embedder,
@@ -277,11 +292,15 @@ struct Chunks<'a, 'b, 'extractor> {
     embedder_name: &'a str,
     dimensions: usize,
     prompt: &'a Prompt,
+    media_field: Option<&'a str>,
     possible_embedding_mistakes: &'a PossibleEmbeddingMistakes,
     user_provided: &'a RefCell<EmbeddingExtractorData<'extractor>>,
     threads: &'a ThreadPoolNoAbort,
     sender: EmbeddingSender<'a, 'b>,
     has_manual_generation: Option<&'a str>,
+    /// Whether the embeddings must be regenerated even if the rendered documents did not change,
+    /// in which case the persistent cache is bypassed.
+    must_regenerate: bool,
 }
 
 impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
@@ -295,17 +314,19 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
         possible_embedding_mistakes: &'a PossibleEmbeddingMistakes,
         threads: &'a ThreadPoolNoAbort,
         sender: EmbeddingSender<'a, 'b>,
+        must_regenerate: bool,
         doc_alloc: &'a Bump,
     ) -> Self {
         let capacity = embedder.prompt_count_in_chunk_hint() * embedder.chunk_count_hint();
-        let texts = BVec::with_capacity_in(capacity, doc_alloc);
+        let inputs = BVec::with_capacity_in(capacity, doc_alloc);
         let ids = BVec::with_capacity_in(capacity, doc_alloc);
         let dimensions = embedder.dimensions();
         Self {
-            texts,
+            inputs,
             ids,
             embedder,
             prompt,
+            media_field: embedder.media_field(),
             possible_embedding_mistakes,
             threads,
             sender,
@@ -314,6 +335,7 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
             user_provided,
             has_manual_generation: None,
             dimensions,
+            must_regenerate,
         }
     }
 
@@ -321,22 +343,25 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
         &mut self,
         docid: DocumentId,
         external_docid: &'a str,
-        rendered: &'a str,
+        input: EmbedderInput<'a>,
         unused_vectors_distribution: &UnusedVectorsDistributionBump,
     ) -> Result<()> {
-        let is_manual = matches!(&self.embedder, &Embedder::UserProvided(_));
+        let is_manual = matches!(
+            &self.embedder,
+            &Embedder::UserProvided(_) | &Embedder::UserProvidedMultiVector(_)
+        );
         if is_manual {
             self.has_manual_generation.get_or_insert(external_docid);
         }
 
-        if self.texts.len() < self.texts.capacity() {
-            self.texts.push(rendered);
+        if self.inputs.len() < self.inputs.capacity() {
+            self.inputs.push(input);
             self.ids.push(docid);
             return Ok(());
         }
 
         Self::embed_chunks(
-            &mut self.texts,
+            &mut self.inputs,
             &mut self.ids,
             self.embedder,
             self.embedder_id,
@@ -346,6 +371,7 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
             self.threads,
             self.sender,
             self.has_manual_generation.take(),
+            self.must_regenerate,
         )
     }
 
@@ -354,7 +380,7 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
         unused_vectors_distribution: &UnusedVectorsDistributionBump,
     ) -> Result<()> {
         let res = Self::embed_chunks(
-            &mut self.texts,
+            &mut self.inputs,
             &mut self.ids,
             self.embedder,
             self.embedder_id,
@@ -364,6 +390,7 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
             self.threads,
             self.sender,
             self.has_manual_generation,
+            self.must_regenerate,
         );
         // optimization: don't run bvec dtors as they only contain bumpalo allocated stuff
         std::mem::forget(self);
@@ -372,7 +399,7 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
 
     #[allow(clippy::too_many_arguments)]
     pub fn embed_chunks(
-        texts: &mut BVec<'a, &'a str>,
+        inputs: &mut BVec<'a, EmbedderInput<'a>>,
         ids: &mut BVec<'a, DocumentId>,
         embedder: &Embedder,
         embedder_id: u8,
@@ -382,6 +409,7 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
         threads: &ThreadPoolNoAbort,
         sender: EmbeddingSender<'a, 'b>,
         has_manual_generation: Option<&'a str>,
+        must_regenerate: bool,
     ) -> Result<()> {
         if let Some(external_docid) = has_manual_generation {
             let mut msg = format!(
@@ -421,7 +449,12 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
             return Err(crate::Error::UserError(crate::UserError::DocumentEmbeddingError(msg)));
         }
 
-        let res = match embedder.embed_index_ref(texts.as_slice(), threads) {
+        let embeddings = if must_regenerate {
+            embedder.embed_index_ref_refreshing(inputs.as_slice(), threads)
+        } else {
+            embedder.embed_index_ref(inputs.as_slice(), threads)
+        };
+        let res = match embeddings {
             Ok(embeddings) => {
                 for (docid, embedding) in ids.into_iter().zip(embeddings) {
                     sender.set_vector(*docid, embedder_id, embedding).unwrap();
@@ -471,19 +504,50 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
                 }
             }
         };
-        texts.clear();
+        inputs.clear();
         ids.clear();
         res
     }
 
-    pub fn prompt(&self) -> &'a Prompt {
-        self.prompt
+    /// Renders the document with the template of the embedder,
+    /// along with the value of its media field if the embedder has one.
+    pub fn render_document<'d>(
+        &self,
+        external_docid: &str,
+        document: impl Document<'d> + Debug,
+        field_id_map: &RefCell<GlobalFieldsIdsMap>,
+        doc_alloc: &'a Bump,
+    ) -> Result<EmbedderInput<'a>>
+    where
+        'a: 'd,
+    {
+        let media = match self.media_field {
+            Some(media_field) => match document.top_level_field(media_field)? {
+                // copied in the allocator, as the fields of a merged document don't outlive the change
+                Some(media) => Some(
+                    serde_json::from_str(doc_alloc.alloc_str(media.get()))
+                        .map_err(InternalError::SerdeJson)?,
+                ),
+                None => None,
+            },
+            None => None,
+        };
+        let text =
+            self.prompt.render_document(external_docid, document, field_id_map, doc_alloc)?;
+        Ok(match self.media_field {
+            Some(_) => EmbedderInput::Media { text, media },
+            None => EmbedderInput::Text(text),
+        })
     }
 
     pub fn embedder_name(&self) -> &'a str {
         self.embedder_name
     }
 
+    pub fn must_regenerate(&self) -> bool {
+        self.must_regenerate
+    }
+
     fn set_regenerate(&self, docid: DocumentId, regenerate: bool) {
         let mut user_provided = self.user_provided.borrow_mut();
         let user_provided = user_provided.0.entry_ref(self.embedder_name).or_default();
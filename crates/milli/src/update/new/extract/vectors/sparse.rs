@@ -0,0 +1,185 @@
+use heed::RoTxn;
+
+use crate::update::new::channel::SparseVectorSender;
+use crate::update::new::indexer::document_changes::{DocumentChangeContext, Extractor};
+use crate::update::new::thread_local::FullySend;
+use crate::update::new::vector_document::{
+    Embeddings, VectorDocument, VectorDocumentFromDb, VectorEntry,
+};
+use crate::update::new::DocumentChange;
+use crate::vector::sparse::{SparseVector, SparseVectorStore};
+use crate::vector::EmbeddingConfigs;
+use crate::{DocumentId, Index, InternalError, Result, UserError};
+
+/// Maintains the inverted index of the sparse vectors provided in the `_vectors` field of the documents.
+pub struct SparseVectorExtractor<'a, 'b> {
+    /// Name and id of the sparse embedders
+    embedders: Vec<(String, u8)>,
+    embedding_configs: &'a EmbeddingConfigs,
+    sender: SparseVectorSender<'a, 'b>,
+}
+
+impl<'a, 'b> SparseVectorExtractor<'a, 'b> {
+    /// `None` if no embedder is sparse.
+    pub fn new(
+        rtxn: &RoTxn,
+        index: &Index,
+        embedding_configs: &'a EmbeddingConfigs,
+        sender: SparseVectorSender<'a, 'b>,
+    ) -> Result<Option<Self>> {
+        let mut embedders = Vec::new();
+        for (name, (embedder, _, _)) in embedding_configs.inner_as_ref() {
+            if !embedder.is_sparse() {
+                continue;
+            }
+            let embedder_id = index.embedder_category_id.get(rtxn, name)?.ok_or(
+                InternalError::DatabaseMissingEntry { db_name: "embedder_category_id", key: None },
+            )?;
+            embedders.push((name.clone(), embedder_id));
+        }
+
+        if embedders.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(Self { embedders, embedding_configs, sender }))
+        }
+    }
+
+    fn write_diff(
+        &self,
+        docid: DocumentId,
+        embedder_id: u8,
+        old: Option<SparseVector>,
+        new: Option<SparseVector>,
+    ) -> Result<()> {
+        if old == new {
+            return Ok(());
+        }
+        let new = new.unwrap_or_default();
+        if let Some(old) = old {
+            let removed = old.without_terms_of(&new);
+            SparseVectorStore::postings(embedder_id, docid, &removed, |key, _| {
+                self.sender.delete_posting(key)
+            })?;
+        }
+        SparseVectorStore::postings(embedder_id, docid, &new, |key, weight| {
+            self.sender.set_posting(key, weight)
+        })
+    }
+}
+
+impl<'extractor> Extractor<'extractor> for SparseVectorExtractor<'_, '_> {
+    type Data = FullySend<()>;
+
+    fn init_data<'doc>(
+        &'doc self,
+        _extractor_alloc: &'extractor bumpalo::Bump,
+    ) -> Result<Self::Data> {
+        Ok(FullySend(()))
+    }
+
+    fn process<'doc>(
+        &'doc self,
+        changes: impl Iterator<Item = Result<DocumentChange<'doc>>>,
+        context: &'doc DocumentChangeContext<Self::Data>,
+    ) -> Result<()> {
+        for change in changes {
+            let change = change?;
+            let docid = change.docid();
+            let external_docid = change.external_docid();
+            match change {
+                DocumentChange::Deletion(_) => {
+                    let Some(old_vectors) = VectorDocumentFromDb::new(
+                        docid,
+                        context.index,
+                        &context.rtxn,
+                        context.db_fields_ids_map,
+                        &context.doc_alloc,
+                    )?
+                    else {
+                        continue;
+                    };
+                    for (name, embedder_id) in &self.embedders {
+                        let old = sparse_vector(
+                            old_vectors.vectors_for_key(name)?,
+                            name,
+                            external_docid,
+                        )?;
+                        self.write_diff(docid, *embedder_id, old, None)?;
+                    }
+                }
+                DocumentChange::Update(update) => {
+                    let old_vectors = update.current_vectors(
+                        &context.rtxn,
+                        context.index,
+                        context.db_fields_ids_map,
+                        &context.doc_alloc,
+                    )?;
+                    let new_vectors = update.merged_vectors(
+                        &context.rtxn,
+                        context.index,
+                        context.db_fields_ids_map,
+                        &context.doc_alloc,
+                        self.embedding_configs,
+                    )?;
+                    for (name, embedder_id) in &self.embedders {
+                        let old = sparse_vector(
+                            old_vectors.vectors_for_key(name)?,
+                            name,
+                            external_docid,
+                        )?;
+                        let new = match &new_vectors {
+                            Some(new_vectors) => sparse_vector(
+                                new_vectors.vectors_for_key(name)?,
+                                name,
+                                external_docid,
+                            )?,
+                            None => None,
+                        };
+                        self.write_diff(docid, *embedder_id, old, new)?;
+                    }
+                }
+                DocumentChange::Insertion(insertion) => {
+                    let Some(new_vectors) =
+                        insertion.inserted_vectors(&context.doc_alloc, self.embedding_configs)?
+                    else {
+                        continue;
+                    };
+                    for (name, embedder_id) in &self.embedders {
+                        let new = sparse_vector(
+                            new_vectors.vectors_for_key(name)?,
+                            name,
+                            external_docid,
+                        )?;
+                        self.write_diff(docid, *embedder_id, None, new)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn sparse_vector(
+    entry: Option<VectorEntry<'_>>,
+    embedder_name: &str,
+    external_docid: &str,
+) -> Result<Option<SparseVector>> {
+    let Some(VectorEntry { embeddings, .. }) = entry else { return Ok(None) };
+    let vector = match embeddings {
+        None => Ok(None),
+        Some(Embeddings::FromJsonExplicit(embeddings)) => SparseVector::from_embeddings(embeddings),
+        Some(Embeddings::FromDb(embeddings)) if embeddings.is_empty() => Ok(None),
+        Some(Embeddings::FromJsonImplicityUserProvided(_) | Embeddings::FromDb(_)) => Err(
+            "sparse vectors must be provided as an object with the fields `embeddings` and `regenerate`"
+                .to_string(),
+        ),
+    };
+    vector.map_err(|error| {
+        UserError::InvalidVectorsEmbedderConf {
+            document_id: external_docid.to_string(),
+            error: format!("Could not parse `._vectors.{embedder_name}`: {error}"),
+        }
+        .into()
+    })
+}
@@ -6,6 +6,7 @@ use rustc_hash::FxBuildHasher;
 use serde::de::{DeserializeSeed, Deserializer as _, Visitor};
 use serde_json::value::RawValue;
 
+use super::field_operators;
 use crate::documents::{
     validate_document_id_str, DocumentIdExtractionError, FieldIdMapper, PrimaryKey,
 };
@@ -63,6 +64,10 @@ impl<'de, 'indexer: 'de, Mapper: MutFieldIdMapper> Visitor<'de>
                 continue;
             }
 
+            let Some(fid) = fid else {
+                // operators cannot hold the document id
+                continue;
+            };
             let Some(_fid) = fid else {
                 attribute_limit_reached = true;
                 continue;
@@ -214,8 +219,20 @@ impl<'de, 'a, V: Visitor<'de>> DeserializeSeed<'de> for ComponentsSeed<'a, V> {
 
 struct MutFieldIdMapVisitor<'a, Mapper: MutFieldIdMapper>(&'a mut Mapper);
 
+impl<'a, Mapper: MutFieldIdMapper> MutFieldIdMapVisitor<'a, Mapper> {
+    /// Inserts the field in the fields ids map, returns `None` for the operators of the update payloads
+    /// as they are not fields of the document.
+    fn insert(self, field: &str) -> (Option<Option<FieldId>>, &'a mut Mapper) {
+        if field_operators::is_operator(field) {
+            return (None, self.0);
+        }
+        let fid = self.0.insert(field);
+        (Some(fid), self.0)
+    }
+}
+
 impl<'de, 'a, Mapper: MutFieldIdMapper> Visitor<'de> for MutFieldIdMapVisitor<'a, Mapper> {
-    type Value = (Option<FieldId>, &'a mut Mapper);
+    type Value = (Option<Option<FieldId>>, &'a mut Mapper);
 
     fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(formatter, "expecting a string")
@@ -224,14 +241,14 @@ impl<'de, 'a, Mapper: MutFieldIdMapper> Visitor<'de> for MutFieldIdMapVisitor<'a
     where
         E: serde::de::Error,
     {
-        Ok((self.0.insert(v), self.0))
+        Ok(self.insert(v))
     }
 
     fn visit_str<E>(self, v: &str) -> std::result::Result<Self::Value, E>
     where
         E: serde::de::Error,
     {
-        Ok((self.0.insert(v), self.0))
+        Ok(self.insert(v))
     }
 }
 
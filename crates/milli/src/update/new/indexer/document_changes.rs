@@ -129,6 +129,18 @@ pub trait DocumentChanges<'pl // lifetime of the underlying payload
         item: &'doc Self::Item,
     ) -> Result<Option<DocumentChange<'doc>>> where 'pl: 'doc // the payload must survive the process calls
     ;
+
+    /// Whether the embeddings of `embedder_name` must be regenerated for the changed documents,
+    /// even when their rendered document template did not change.
+    fn must_regenerate_embeddings(&self, _embedder_name: &str) -> bool {
+        false
+    }
+
+    /// Whether the changes leave the documents untouched and only regenerate their embeddings,
+    /// in which case only the embeddings are extracted.
+    fn only_regenerates_embeddings(&self) -> bool {
+        false
+    }
 }
 
 pub struct IndexingContext<
@@ -8,12 +8,15 @@ use heed::RoTxn;
 use memmap2::Mmap;
 use rayon::slice::ParallelSlice;
 use rustc_hash::FxBuildHasher;
+use serde::Deserialize;
 use serde_json::value::RawValue;
-use serde_json::Deserializer;
+use serde_json::{Deserializer, Map, Value};
 
 use super::super::document_change::DocumentChange;
 use super::document_changes::{DocumentChangeContext, DocumentChanges};
+use super::field_operators;
 use super::guess_primary_key::retrieve_or_guess_primary_key;
+use crate::constants::{RESERVED_EXPIRES_AT_FIELD_NAME, RESERVED_VERSION_FIELD_NAME};
 use crate::documents::PrimaryKey;
 use crate::progress::{AtomicPayloadStep, Progress};
 use crate::update::new::document::Versions;
@@ -21,7 +24,10 @@ use crate::update::new::steps::IndexingStep;
 use crate::update::new::thread_local::MostlySend;
 use crate::update::new::{Deletion, Insertion, Update};
 use crate::update::{AvailableIds, IndexDocumentsMethod};
-use crate::{DocumentId, Error, FieldsIdsMap, Index, InternalError, Result, UserError};
+use crate::{
+    all_obkv_to_json, DocumentId, DocumentSchema, Error, FieldsIdsMap, Index, IngestPipeline,
+    InternalError, OnInvalidDocument, Result, SchemaViolation, UserError,
+};
 
 #[derive(Default)]
 pub struct DocumentOperation<'pl> {
@@ -53,11 +59,38 @@ impl<'pl> DocumentOperation<'pl> {
         Ok(())
     }
 
+    /// Append a replacement of the documents of a part of a payload.
+    ///
+    /// The part is expected to be in the NDJSON format
+    pub fn replace_documents_slice(&mut self, payload: &'pl [u8]) {
+        self.operations.push(Payload::Replace(payload));
+    }
+
+    /// Append an update of the documents of a part of a payload.
+    ///
+    /// The part is expected to be in the NDJSON format
+    pub fn update_documents_slice(&mut self, payload: &'pl [u8]) {
+        self.operations.push(Payload::Update(payload));
+    }
+
     /// Append a deletion of documents IDs.
     ///
     /// The list is a set of external documents IDs.
     pub fn delete_documents(&mut self, to_delete: &'pl [&'pl str]) {
-        self.operations.push(Payload::Deletion(to_delete))
+        self.operations.push(Payload::Deletion(to_delete, &[]))
+    }
+
+    /// Append a deletion of documents IDs, only applied to the documents at the expected version.
+    ///
+    /// `expected_versions` contains the version expected for each of the documents to delete,
+    /// `None` for the documents deleted whatever their version.
+    pub fn delete_documents_with_versions(
+        &mut self,
+        to_delete: &'pl [&'pl str],
+        expected_versions: &'pl [Option<u64>],
+    ) {
+        debug_assert_eq!(to_delete.len(), expected_versions.len());
+        self.operations.push(Payload::Deletion(to_delete, expected_versions))
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -84,6 +117,18 @@ impl<'pl> DocumentOperation<'pl> {
         let mut docids_version_offsets = hashbrown::HashMap::new();
         let mut primary_key = None;
 
+        let ingest_pipeline =
+            IngestPipeline::new(&index.ingest_pipeline(rtxn)?).map_err(|(index, message)| {
+                UserError::InvalidSettingsIngestPipeline { index, message }
+            })?;
+        let ingest_pipeline = (!ingest_pipeline.is_empty()).then_some(ingest_pipeline);
+        let document_schema = index
+            .document_schema(rtxn)?
+            .map(|settings| DocumentSchema::new(&settings))
+            .transpose()
+            .map_err(UserError::InvalidSettingsDocumentSchema)?;
+        let generate_document_ids = index.generate_document_ids(rtxn)?;
+
         let payload_count = operations.len();
         let (step, progress_step) = AtomicPayloadStep::new(payload_count as u32);
         progress.update_progress(progress_step);
@@ -95,6 +140,9 @@ impl<'pl> DocumentOperation<'pl> {
             step.store(payload_index as u32, Ordering::Relaxed);
 
             let mut bytes = 0;
+            let mut version_conflicts = Vec::new();
+            let mut invalid_documents = Vec::new();
+            let mut generated_document_ids = Vec::new();
             let result = match operation {
                 Payload::Replace(payload) => extract_addition_payload_changes(
                     indexer,
@@ -105,7 +153,13 @@ impl<'pl> DocumentOperation<'pl> {
                     new_fields_ids_map,
                     &mut available_docids,
                     &mut bytes,
+                    &mut version_conflicts,
+                    &mut invalid_documents,
+                    &mut generated_document_ids,
                     &docids_version_offsets,
+                    ingest_pipeline.as_ref(),
+                    document_schema.as_ref(),
+                    generate_document_ids,
                     IndexDocumentsMethod::ReplaceDocuments,
                     payload,
                 ),
@@ -118,17 +172,27 @@ impl<'pl> DocumentOperation<'pl> {
                     new_fields_ids_map,
                     &mut available_docids,
                     &mut bytes,
+                    &mut version_conflicts,
+                    &mut invalid_documents,
+                    &mut generated_document_ids,
                     &docids_version_offsets,
+                    ingest_pipeline.as_ref(),
+                    document_schema.as_ref(),
+                    generate_document_ids,
                     IndexDocumentsMethod::UpdateDocuments,
                     payload,
                 ),
-                Payload::Deletion(to_delete) => extract_deletion_payload_changes(
-                    index,
-                    rtxn,
-                    &mut available_docids,
-                    &docids_version_offsets,
-                    to_delete,
-                ),
+                Payload::Deletion(to_delete, expected_versions) => {
+                    extract_deletion_payload_changes(
+                        index,
+                        rtxn,
+                        &mut available_docids,
+                        &mut version_conflicts,
+                        &docids_version_offsets,
+                        to_delete,
+                        expected_versions,
+                    )
+                }
             };
 
             let mut document_count = 0;
@@ -140,10 +204,22 @@ impl<'pl> DocumentOperation<'pl> {
                     merge_version_offsets(&mut docids_version_offsets, new_docids_version_offsets);
                     None
                 }
-                Err(Error::UserError(user_error)) => Some(user_error),
+                Err(Error::UserError(user_error)) => {
+                    version_conflicts.clear();
+                    invalid_documents.clear();
+                    generated_document_ids.clear();
+                    Some(user_error)
+                }
                 Err(e) => return Err(e),
             };
-            operations_stats.push(PayloadStats { document_count, bytes, error });
+            operations_stats.push(PayloadStats {
+                document_count,
+                bytes,
+                error,
+                version_conflicts,
+                invalid_documents,
+                generated_document_ids,
+            });
         }
         step.store(payload_count as u32, Ordering::Relaxed);
 
@@ -157,7 +233,11 @@ impl<'pl> DocumentOperation<'pl> {
             .sort_unstable_by_key(|(_, po)| first_update_pointer(&po.operations).unwrap_or(0));
 
         let docids_version_offsets = docids_version_offsets.into_bump_slice();
-        Ok((DocumentOperationChanges { docids_version_offsets }, operations_stats, primary_key))
+        let changes = DocumentOperationChanges {
+            docids_version_offsets,
+            primary_key: primary_key.as_ref().map(PrimaryKey::name),
+        };
+        Ok((changes, operations_stats, primary_key))
     }
 }
 
@@ -171,19 +251,43 @@ fn extract_addition_payload_changes<'r, 'pl: 'r>(
     new_fields_ids_map: &mut FieldsIdsMap,
     available_docids: &mut AvailableIds,
     bytes: &mut u64,
+    version_conflicts: &mut Vec<VersionConflict>,
+    invalid_documents: &mut Vec<SchemaViolation>,
+    generated_document_ids: &mut Vec<String>,
     main_docids_version_offsets: &hashbrown::HashMap<&'pl str, PayloadOperations<'pl>>,
+    ingest_pipeline: Option<&IngestPipeline>,
+    document_schema: Option<&DocumentSchema>,
+    generate_document_ids: bool,
     method: IndexDocumentsMethod,
     payload: &'pl [u8],
 ) -> Result<hashbrown::HashMap<&'pl str, PayloadOperations<'pl>>> {
     use IndexDocumentsMethod::{ReplaceDocuments, UpdateDocuments};
 
     let mut new_docids_version_offsets = hashbrown::HashMap::<&str, PayloadOperations<'pl>>::new();
+    // The documents that do not match the schema of an index that must reject them
+    let mut rejected_documents = Vec::new();
+
+    let index_primary_key = index.primary_key(rtxn)?;
 
     let mut previous_offset = 0;
     let mut iter = Deserializer::from_slice(payload).into_iter::<&RawValue>();
     while let Some(doc) = iter.next().transpose().map_err(InternalError::SerdeJson)? {
         *bytes = previous_offset as u64;
 
+        // The ingest pipeline is applied once, before the primary key is read, so that it can produce it.
+        let (doc, mut content) = match ingest_pipeline {
+            Some(pipeline) => {
+                let primary_key = primary_key
+                    .as_ref()
+                    .map(PrimaryKey::name)
+                    .or(primary_key_from_op)
+                    .or(index_primary_key);
+                let doc = apply_ingest_pipeline(pipeline, doc, primary_key, indexer)?;
+                (doc, doc.get().as_bytes())
+            }
+            None => (doc, &payload[previous_offset..iter.byte_offset()]),
+        };
+
         // Only guess the primary key if it is the first document
         let retrieved_primary_key = if previous_offset == 0 {
             let doc = RawMap::from_raw_value_and_hasher(doc, FxBuildHasher, indexer)
@@ -196,6 +300,7 @@ fn extract_addition_payload_changes<'r, 'pl: 'r>(
                 new_fields_ids_map,
                 primary_key_from_op,
                 doc,
+                generate_document_ids,
             );
 
             let (pk, _has_been_changed) = match result {
@@ -210,12 +315,97 @@ fn extract_addition_payload_changes<'r, 'pl: 'r>(
             primary_key.as_ref().unwrap()
         };
 
-        let external_id =
-            retrieved_primary_key.extract_fields_and_docid(doc, new_fields_ids_map, indexer)?;
+        let (doc, external_id, is_generated) = match retrieved_primary_key.extract_fields_and_docid(
+            doc,
+            new_fields_ids_map,
+            indexer,
+        ) {
+            Ok(external_id) => (doc, external_id, false),
+            Err(Error::UserError(UserError::MissingDocumentId { .. }))
+                if generate_document_ids
+                    && !matches!(retrieved_primary_key, PrimaryKey::Composite { .. }) =>
+            {
+                let doc = with_generated_document_id(doc, retrieved_primary_key.name(), indexer)?;
+                let external_id = retrieved_primary_key.extract_fields_and_docid(
+                    doc,
+                    new_fields_ids_map,
+                    indexer,
+                )?;
+                content = doc.get().as_bytes();
+                (doc, external_id, true)
+            }
+            Err(error) => return Err(error),
+        };
 
         let external_id = external_id.to_de();
-        let current_offset = iter.byte_offset();
-        let document_offset = DocumentOffset { content: &payload[previous_offset..current_offset] };
+        let mut document_offset = DocumentOffset { content, version: None };
+
+        validate_expires_at(doc, external_id)?;
+
+        if let Some(schema) = document_schema {
+            let document: Map<String, Value> =
+                serde_json::from_str(doc.get()).map_err(InternalError::SerdeJson)?;
+            let violations =
+                schema.validate(external_id, &Value::Object(document), method == UpdateDocuments);
+            if !violations.is_empty() {
+                match schema.on_invalid() {
+                    OnInvalidDocument::Fail => rejected_documents.extend(violations),
+                    OnInvalidDocument::Skip => invalid_documents.extend(violations),
+                }
+                previous_offset = iter.byte_offset();
+                continue;
+            }
+        }
+
+        match expected_version(doc, external_id)? {
+            Some(expected_version) => {
+                let current_version = current_version(
+                    index,
+                    rtxn,
+                    external_id,
+                    main_docids_version_offsets,
+                    &new_docids_version_offsets,
+                )?;
+                if current_version != expected_version {
+                    version_conflicts.push(VersionConflict {
+                        document_id: external_id.to_string(),
+                        expected_version,
+                        current_version,
+                    });
+                    previous_offset = iter.byte_offset();
+                    continue;
+                }
+                document_offset.version = Some(expected_version + 1);
+            }
+            // Once versioned, a document gets a new version on every write, so that the writes
+            // made without a version are seen by the next versioned ones.
+            None if new_fields_ids_map.id(RESERVED_VERSION_FIELD_NAME).is_some() => {
+                let current_version = current_version(
+                    index,
+                    rtxn,
+                    external_id,
+                    main_docids_version_offsets,
+                    &new_docids_version_offsets,
+                )?;
+                if current_version != 0 {
+                    document_offset.version = Some(current_version + 1);
+                }
+            }
+            None => (),
+        }
+
+        if method == UpdateDocuments {
+            validate_operators(
+                index,
+                rtxn,
+                new_fields_ids_map,
+                external_id,
+                doc,
+                retrieved_primary_key.name(),
+                main_docids_version_offsets,
+                &new_docids_version_offsets,
+            )?;
+        }
 
         match main_docids_version_offsets.get(external_id) {
             None => {
@@ -300,9 +490,18 @@ fn extract_addition_payload_changes<'r, 'pl: 'r>(
             },
         }
 
+        // The generated ids are only reported for the documents that have been accepted.
+        if is_generated {
+            generated_document_ids.push(external_id.to_string());
+        }
+
         previous_offset = iter.byte_offset();
     }
 
+    if !rejected_documents.is_empty() {
+        return Err(UserError::DocumentSchemaViolation { violations: rejected_documents }.into());
+    }
+
     if payload.is_empty() {
         let result = retrieve_or_guess_primary_key(
             rtxn,
@@ -310,6 +509,7 @@ fn extract_addition_payload_changes<'r, 'pl: 'r>(
             new_fields_ids_map,
             primary_key_from_op,
             None,
+            generate_document_ids,
         );
         match result {
             Ok(Ok((pk, _))) => {
@@ -328,12 +528,32 @@ fn extract_deletion_payload_changes<'s, 'pl: 's>(
     index: &Index,
     rtxn: &RoTxn,
     available_docids: &mut AvailableIds,
+    version_conflicts: &mut Vec<VersionConflict>,
     main_docids_version_offsets: &hashbrown::HashMap<&'s str, PayloadOperations<'pl>>,
     to_delete: &'pl [&'pl str],
+    expected_versions: &'pl [Option<u64>],
 ) -> Result<hashbrown::HashMap<&'s str, PayloadOperations<'pl>>> {
     let mut new_docids_version_offsets = hashbrown::HashMap::<&str, PayloadOperations<'pl>>::new();
 
-    for external_id in to_delete {
+    for (i, external_id) in to_delete.iter().enumerate() {
+        if let Some(expected_version) = expected_versions.get(i).copied().flatten() {
+            let current_version = current_version(
+                index,
+                rtxn,
+                external_id,
+                main_docids_version_offsets,
+                &new_docids_version_offsets,
+            )?;
+            if current_version != expected_version {
+                version_conflicts.push(VersionConflict {
+                    document_id: external_id.to_string(),
+                    expected_version,
+                    current_version,
+                });
+                continue;
+            }
+        }
+
         match main_docids_version_offsets.get(external_id) {
             None => {
                 match index.external_documents_ids().get(rtxn, external_id) {
@@ -379,6 +599,147 @@ fn extract_deletion_payload_changes<'s, 'pl: 's>(
     Ok(new_docids_version_offsets)
 }
 
+/// The version expected by a document of a payload, from its `_version` field.
+fn expected_version(document: &RawValue, external_id: &str) -> Result<Option<u64>> {
+    #[derive(Deserialize)]
+    struct VersionedDocument {
+        #[serde(rename = "_version")]
+        version: Option<Value>,
+    }
+
+    // Most documents are not versioned, we avoid deserializing them a second time.
+    if !document.get().contains(RESERVED_VERSION_FIELD_NAME) {
+        return Ok(None);
+    }
+
+    let VersionedDocument { version } =
+        serde_json::from_str(document.get()).map_err(InternalError::SerdeJson)?;
+    match version {
+        None => Ok(None),
+        Some(version) => match version.as_u64() {
+            Some(version) => Ok(Some(version)),
+            None => Err(UserError::InvalidDocumentVersion {
+                document_id: external_id.to_string(),
+                version,
+            }
+            .into()),
+        },
+    }
+}
+
+/// Ensures that the `_expiresAt` field of a document of a payload, if any, is a timestamp.
+fn validate_expires_at(document: &RawValue, external_id: &str) -> Result<()> {
+    #[derive(Deserialize)]
+    struct ExpiringDocument {
+        #[serde(rename = "_expiresAt")]
+        expires_at: Option<Value>,
+    }
+
+    // Most documents do not expire, we avoid deserializing them a second time.
+    if !document.get().contains(RESERVED_EXPIRES_AT_FIELD_NAME) {
+        return Ok(());
+    }
+
+    let ExpiringDocument { expires_at } =
+        serde_json::from_str(document.get()).map_err(InternalError::SerdeJson)?;
+    match expires_at {
+        Some(expires_at) if !expires_at.is_number() => Err(UserError::InvalidDocumentExpiresAt {
+            document_id: external_id.to_string(),
+            expires_at,
+        }
+        .into()),
+        _ => Ok(()),
+    }
+}
+
+/// The version of a document once the operations extracted so far are applied,
+/// starting from the version of the document in the database.
+fn current_version<'s, 'pl>(
+    index: &Index,
+    rtxn: &RoTxn,
+    external_id: &str,
+    main_docids_version_offsets: &hashbrown::HashMap<&'s str, PayloadOperations<'pl>>,
+    new_docids_version_offsets: &hashbrown::HashMap<&'s str, PayloadOperations<'pl>>,
+) -> Result<u64> {
+    let main = main_docids_version_offsets.get(external_id);
+    let new = new_docids_version_offsets.get(external_id);
+
+    let mut version = match main.or(new) {
+        Some(PayloadOperations { is_new: true, .. }) => 0,
+        Some(PayloadOperations { docid, .. }) => index.document_version(rtxn, *docid)?,
+        None => match index.external_documents_ids().get(rtxn, external_id)? {
+            Some(docid) => index.document_version(rtxn, docid)?,
+            None => 0,
+        },
+    };
+
+    for operation in main.into_iter().chain(new).flat_map(|po| &po.operations) {
+        version = match operation {
+            InnerDocOp::Replace(offset) => offset.version.unwrap_or(0),
+            InnerDocOp::Update(offset) => offset.version.unwrap_or(version),
+            InnerDocOp::Deletion => 0,
+        };
+    }
+
+    Ok(version)
+}
+
+/// Ensures that the operators of a document of an update payload, if any, can be applied on the document
+/// once the operations extracted so far are applied, see [`field_operators`].
+///
+/// Validating the operators with the payload makes an invalid operator only fail the task of the payload.
+#[allow(clippy::too_many_arguments)]
+fn validate_operators<'s, 'pl>(
+    index: &Index,
+    rtxn: &RoTxn,
+    fields_ids_map: &FieldsIdsMap,
+    external_id: &str,
+    document: &RawValue,
+    primary_key: &str,
+    main_docids_version_offsets: &hashbrown::HashMap<&'s str, PayloadOperations<'pl>>,
+    new_docids_version_offsets: &hashbrown::HashMap<&'s str, PayloadOperations<'pl>>,
+) -> Result<()> {
+    // Most documents have no operators, we avoid deserializing them a second time.
+    if !document.get().contains('$') {
+        return Ok(());
+    }
+    let offset = DocumentOffset { content: document.get().as_bytes(), version: None };
+    let version = offset.parse_object()?;
+    if !version.keys().any(|field| field_operators::is_operator(field)) {
+        return Ok(());
+    }
+
+    let main = main_docids_version_offsets.get(external_id);
+    let new = new_docids_version_offsets.get(external_id);
+    let stored_document = |docid| -> Result<Map<String, Value>> {
+        all_obkv_to_json(index.document(rtxn, docid)?, fields_ids_map)
+    };
+    let mut current = match main.or(new) {
+        Some(PayloadOperations { is_new: true, .. }) => Map::new(),
+        Some(PayloadOperations { docid, .. }) => stored_document(*docid)?,
+        None => match index.external_documents_ids().get(rtxn, external_id)? {
+            Some(docid) => stored_document(docid)?,
+            None => Map::new(),
+        },
+    };
+
+    let apply_version = |document: &mut Map<String, Value>, version| {
+        field_operators::apply_version(document, version, Some(primary_key)).map_err(|message| {
+            UserError::InvalidDocumentOperator { document_id: external_id.to_string(), message }
+        })
+    };
+    for operation in main.into_iter().chain(new).flat_map(|po| &po.operations) {
+        match operation {
+            InnerDocOp::Replace(offset) => current = offset.parse_object()?,
+            InnerDocOp::Update(offset) => apply_version(&mut current, offset.parse_object()?)?,
+            InnerDocOp::Deletion => current = Map::new(),
+        }
+    }
+    apply_version(&mut current, version)?;
+
+    Ok(())
+}
+
 fn merge_version_offsets<'s, 'pl>(
     main: &mut hashbrown::HashMap<&'s str, PayloadOperations<'pl>>,
     new: hashbrown::HashMap<&'s str, PayloadOperations<'pl>>,
@@ -418,7 +779,7 @@ impl<'pl> DocumentChanges<'pl> for DocumentOperationChanges<'pl> {
         'pl: 'doc,
     {
         let (external_doc, payload_operations) = item;
-        payload_operations.merge(external_doc, &context.doc_alloc)
+        payload_operations.merge(external_doc, self.primary_key, context)
     }
 
     fn len(&self) -> usize {
@@ -428,18 +789,34 @@ impl<'pl> DocumentChanges<'pl> for DocumentOperationChanges<'pl> {
 
 pub struct DocumentOperationChanges<'pl> {
     docids_version_offsets: &'pl [(&'pl str, PayloadOperations<'pl>)],
+    /// The name of the primary key, which cannot be modified by the operators of the updates.
+    primary_key: Option<&'pl str>,
 }
 
 pub enum Payload<'pl> {
     Replace(&'pl [u8]),
     Update(&'pl [u8]),
-    Deletion(&'pl [&'pl str]),
+    Deletion(&'pl [&'pl str], &'pl [Option<u64>]),
 }
 
 pub struct PayloadStats {
     pub bytes: u64,
     pub document_count: u64,
     pub error: Option<UserError>,
+    /// The operations of the payload rejected because their document was not at the expected version.
+    pub version_conflicts: Vec<VersionConflict>,
+    /// The documents of the payload skipped because they do not match the document schema of the index.
+    pub invalid_documents: Vec<SchemaViolation>,
+    /// The ids generated, in the order of the payload, for the documents without a primary key value.
+    pub generated_document_ids: Vec<String>,
+}
+
+/// An operation on a document rejected because the document was not at the version it expected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionConflict {
+    pub document_id: String,
+    pub expected_version: u64,
+    pub current_version: u64,
 }
 
 pub struct PayloadOperations<'pl> {
@@ -491,20 +868,19 @@ impl<'pl> PayloadOperations<'pl> {
     /// Returns only the most recent version of a document based on the updates from the payloads.
     ///
     /// This function is only meant to be used when doing a replacement and not an update.
-    fn merge<'doc>(
+    fn merge<'doc, T: MostlySend + 'doc>(
         &self,
         external_doc: &'doc str,
-        doc_alloc: &'doc Bump,
+        primary_key: Option<&str>,
+        context: &'doc DocumentChangeContext<T>,
     ) -> Result<Option<DocumentChange<'doc>>>
     where
         'pl: 'doc,
     {
+        let doc_alloc = &context.doc_alloc;
         match self.operations.last() {
-            Some(InnerDocOp::Replace(DocumentOffset { content })) => {
-                let document = serde_json::from_slice(content).unwrap();
-                let document =
-                    RawMap::from_raw_value_and_hasher(document, FxBuildHasher, doc_alloc)
-                        .map_err(UserError::SerdeJson)?;
+            Some(InnerDocOp::Replace(offset)) => {
+                let document = offset.parse(doc_alloc)?;
 
                 if self.is_new {
                     Ok(Some(DocumentChange::Insertion(Insertion::create(
@@ -542,21 +918,34 @@ impl<'pl> PayloadOperations<'pl> {
                 };
 
                 // We collect the versions to generate the appropriate document.
-                let versions = operations.iter().map(|operation| {
-                    let DocumentOffset { content } = match operation {
-                        InnerDocOp::Replace(offset) | InnerDocOp::Update(offset) => offset,
+                let versions = operations
+                    .iter()
+                    .map(|operation| match operation {
+                        InnerDocOp::Replace(offset) | InnerDocOp::Update(offset) => {
+                            offset.parse(doc_alloc)
+                        }
                         InnerDocOp::Deletion => unreachable!("Deletion in document operations"),
-                    };
+                    })
+                    .collect::<Result<Vec<_>>>()?;
 
-                    let document = serde_json::from_slice(content).unwrap();
-                    let document =
-                        RawMap::from_raw_value_and_hasher(document, FxBuildHasher, doc_alloc)
-                            .map_err(UserError::SerdeJson)?;
-
-                    Ok(document)
+                // The operators need the current value of the fields, so we must build the whole document.
+                let has_operators = operations.iter().zip(&versions).any(|(operation, version)| {
+                    matches!(operation, InnerDocOp::Update(_))
+                        && field_operators::has_operators(version)
                 });
+                if has_operators {
+                    return self.apply_operators(
+                        external_doc,
+                        operations,
+                        from_scratch,
+                        primary_key,
+                        context,
+                    );
+                }
 
-                let Some(versions) = Versions::multiple(versions)? else { return Ok(None) };
+                let Some(versions) = Versions::multiple(versions.into_iter().map(Ok))? else {
+                    return Ok(None);
+                };
 
                 if self.is_new {
                     Ok(Some(DocumentChange::Insertion(Insertion::create(
@@ -584,6 +973,70 @@ impl<'pl> PayloadOperations<'pl> {
             None => unreachable!("We must not have an empty set of operations on a document"),
         }
     }
+
+    /// Builds the whole document by applying the versions one after the other on the current document,
+    /// see [`field_operators`].
+    fn apply_operators<'doc, T: MostlySend + 'doc>(
+        &self,
+        external_doc: &'doc str,
+        operations: &[InnerDocOp<'pl>],
+        from_scratch: bool,
+        primary_key: Option<&str>,
+        context: &'doc DocumentChangeContext<T>,
+    ) -> Result<Option<DocumentChange<'doc>>>
+    where
+        'pl: 'doc,
+    {
+        let mut document = if from_scratch || self.is_new {
+            Map::new()
+        } else {
+            let current = context.index.document(&context.rtxn, self.docid)?;
+            all_obkv_to_json(current, context.db_fields_ids_map)?
+        };
+
+        for operation in operations {
+            let (InnerDocOp::Replace(offset) | InnerDocOp::Update(offset)) = operation else {
+                unreachable!("Deletion in document operations")
+            };
+            let version = offset.parse_object()?;
+
+            match operation {
+                InnerDocOp::Replace(_) => document = version,
+                _ => field_operators::apply_version(&mut document, version, primary_key).map_err(
+                    |message| UserError::InvalidDocumentOperator {
+                        document_id: external_doc.to_string(),
+                        message,
+                    },
+                )?,
+            }
+            if let Some(version) = offset.version {
+                document.insert(RESERVED_VERSION_FIELD_NAME.to_string(), version.into());
+            }
+        }
+
+        let mut buffer = bumpalo::collections::Vec::new_in(&context.doc_alloc);
+        serde_json::to_writer(&mut buffer, &document).map_err(InternalError::SerdeJson)?;
+        let document =
+            serde_json::from_slice(buffer.into_bump_slice()).map_err(InternalError::SerdeJson)?;
+        let document =
+            RawMap::from_raw_value_and_hasher(document, FxBuildHasher, &context.doc_alloc)
+                .map_err(UserError::SerdeJson)?;
+
+        if self.is_new {
+            Ok(Some(DocumentChange::Insertion(Insertion::create(
+                self.docid,
+                external_doc,
+                Versions::single(document),
+            ))))
+        } else {
+            Ok(Some(DocumentChange::Update(Update::create(
+                self.docid,
+                external_doc,
+                Versions::single(document),
+                true, // the whole document is built
+            ))))
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -599,6 +1052,82 @@ pub enum InnerDocOp<'pl> {
 pub struct DocumentOffset<'pl> {
     /// The mmapped payload files.
     pub content: &'pl [u8],
+    /// The `_version` written with the document, when the operation is versioned.
+    pub version: Option<u64>,
+}
+
+impl<'pl> DocumentOffset<'pl> {
+    /// Parses the document, setting its new `_version` if the operation is versioned.
+    fn parse<'doc>(&self, doc_alloc: &'doc Bump) -> Result<RawMap<'doc, FxBuildHasher>>
+    where
+        'pl: 'doc,
+    {
+        let document = serde_json::from_slice(self.content).unwrap();
+        let mut document = RawMap::from_raw_value_and_hasher(document, FxBuildHasher, doc_alloc)
+            .map_err(UserError::SerdeJson)?;
+
+        if let Some(version) = self.version {
+            let version: &'doc str = doc_alloc.alloc_str(&version.to_string());
+            let version = serde_json::from_str(version).map_err(InternalError::SerdeJson)?;
+            document.insert(RESERVED_VERSION_FIELD_NAME, version);
+        }
+
+        Ok(document)
+    }
+
+    /// Parses the document as an object.
+    fn parse_object(&self) -> Result<Map<String, Value>> {
+        Ok(serde_json::from_slice(self.content).map_err(InternalError::SerdeJson)?)
+    }
+}
+
+/// Returns a copy of the document with a new UUIDv7 as the value of its primary key.
+///
+/// UUIDv7s are ordered by generation time, so the ids of the documents follow the order in which
+/// they were received.
+fn with_generated_document_id<'pl>(
+    document: &RawValue,
+    primary_key: &str,
+    bump: &'pl Bump,
+) -> Result<&'pl RawValue> {
+    let mut buffer = uuid::Uuid::encode_buffer();
+    let document_id = uuid::Uuid::now_v7().as_hyphenated().encode_lower(&mut buffer);
+
+    let fields: Map<String, Value> =
+        serde_json::from_str(document.get()).map_err(UserError::SerdeJson)?;
+    // The generated id is inserted as the first field of the document object.
+    let mut object = Map::with_capacity(fields.len() + 1);
+    object.insert(primary_key.to_string(), Value::String(document_id.to_string()));
+    object.extend(fields.into_iter().filter(|(field, _)| field != primary_key));
+
+    let content = serde_json::to_string(&object).map_err(InternalError::SerdeJson)?;
+    Ok(serde_json::from_str(bump.alloc_str(&content)).map_err(InternalError::SerdeJson)?)
+}
+
+/// Returns a copy of the document transformed by the ingest pipeline.
+///
+/// The errors are reported with the value of the primary key of the document as it was sent,
+/// or with the whole document when it does not have one.
+fn apply_ingest_pipeline<'pl>(
+    pipeline: &IngestPipeline,
+    document: &RawValue,
+    primary_key: Option<&str>,
+    bump: &'pl Bump,
+) -> Result<&'pl RawValue> {
+    let mut object: Map<String, Value> =
+        serde_json::from_str(document.get()).map_err(UserError::SerdeJson)?;
+    let document_id = match primary_key.and_then(|primary_key| object.get(primary_key)) {
+        Some(Value::String(id)) => id.clone(),
+        Some(id) => id.to_string(),
+        None => document.get().to_string(),
+    };
+
+    pipeline
+        .apply(&mut object)
+        .map_err(|message| UserError::IngestPipelineError { document_id, message })?;
+
+    let content = serde_json::to_string(&object).map_err(InternalError::SerdeJson)?;
+    Ok(serde_json::from_str(bump.alloc_str(&content)).map_err(InternalError::SerdeJson)?)
 }
 
 /// Returns the first pointer of the first change in a document.
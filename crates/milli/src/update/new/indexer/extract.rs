@@ -1,6 +1,6 @@
 use std::collections::BTreeMap;
 use std::sync::atomic::AtomicBool;
-use std::sync::OnceLock;
+use std::sync::{OnceLock, RwLock};
 
 use bumpalo::Bump;
 use roaring::RoaringBitmap;
@@ -12,14 +12,16 @@ use super::super::steps::IndexingStep;
 use super::super::thread_local::{FullySend, ThreadLocal};
 use super::super::FacetFieldIdsDelta;
 use super::document_changes::{extract, DocumentChanges, IndexingContext};
+use crate::fields_ids_map::metadata::FieldIdMapWithMetadata;
 use crate::index::IndexEmbeddingConfig;
 use crate::progress::MergingWordCache;
 use crate::proximity::ProximityPrecision;
-use crate::update::new::extract::EmbeddingExtractor;
+use crate::update::new::extract::{EmbeddingExtractor, SparseVectorExtractor};
 use crate::update::new::merger::merge_and_send_rtree;
 use crate::update::new::{merge_and_send_docids, merge_and_send_facet_docids, FacetDatabases};
+use crate::update::settings::InnerIndexSettingsDiff;
 use crate::vector::EmbeddingConfigs;
-use crate::{Result, ThreadPoolNoAbort, ThreadPoolNoAbortBuilder};
+use crate::{FieldDistribution, Result, ThreadPoolNoAbort, ThreadPoolNoAbortBuilder};
 
 #[allow(clippy::too_many_arguments)]
 pub(super) fn extract_all<'pl, 'extractor, DC, MSP>(
@@ -46,6 +48,25 @@ where
     let index = indexing_context.index;
     let rtxn = index.read_txn()?;
 
+    // the documents are left untouched, only their embeddings are extracted
+    if document_changes.only_regenerates_embeddings() {
+        extract_embeddings(
+            document_changes,
+            indexing_context,
+            &extractor_sender,
+            embedders,
+            extractor_allocs,
+            field_distribution,
+            &mut index_embeddings,
+            modified_docids,
+        )?;
+
+        indexing_context.progress.update_progress(IndexingStep::WaitingForDatabaseWrites);
+        finished_extraction.store(true, std::sync::atomic::Ordering::Relaxed);
+
+        return Result::Ok((FacetFieldIdsDelta::new(0, 0), index_embeddings));
+    }
+
     // document but we need to create a function that collects and compresses documents.
     let document_sender = extractor_sender.documents();
     let document_extractor = DocumentsExtractor::new(document_sender, embedders);
@@ -110,13 +131,7 @@ where
     }
 
     {
-        let WordDocidsCaches {
-            word_docids,
-            word_fid_docids,
-            exact_word_docids,
-            word_position_docids,
-            fid_word_count_docids,
-        } = {
+        let caches = {
             let span = tracing::trace_span!(target: "indexing::documents::extract", "word_docids");
             let _entered = span.enter();
             WordDocidsExtractors::run_extraction(
@@ -127,81 +142,7 @@ where
             )?
         };
 
-        indexing_context.progress.update_progress(IndexingStep::MergingWordCaches);
-
-        {
-            let span = tracing::trace_span!(target: "indexing::documents::merge", "word_docids");
-            let _entered = span.enter();
-            indexing_context.progress.update_progress(MergingWordCache::WordDocids);
-
-            merge_and_send_docids(
-                word_docids,
-                index.word_docids.remap_types(),
-                index,
-                extractor_sender.docids::<WordDocids>(),
-                &indexing_context.must_stop_processing,
-            )?;
-        }
-
-        {
-            let span =
-                tracing::trace_span!(target: "indexing::documents::merge", "word_fid_docids");
-            let _entered = span.enter();
-            indexing_context.progress.update_progress(MergingWordCache::WordFieldIdDocids);
-
-            merge_and_send_docids(
-                word_fid_docids,
-                index.word_fid_docids.remap_types(),
-                index,
-                extractor_sender.docids::<WordFidDocids>(),
-                &indexing_context.must_stop_processing,
-            )?;
-        }
-
-        {
-            let span =
-                tracing::trace_span!(target: "indexing::documents::merge", "exact_word_docids");
-            let _entered = span.enter();
-            indexing_context.progress.update_progress(MergingWordCache::ExactWordDocids);
-
-            merge_and_send_docids(
-                exact_word_docids,
-                index.exact_word_docids.remap_types(),
-                index,
-                extractor_sender.docids::<ExactWordDocids>(),
-                &indexing_context.must_stop_processing,
-            )?;
-        }
-
-        {
-            let span =
-                tracing::trace_span!(target: "indexing::documents::merge", "word_position_docids");
-            let _entered = span.enter();
-            indexing_context.progress.update_progress(MergingWordCache::WordPositionDocids);
-
-            merge_and_send_docids(
-                word_position_docids,
-                index.word_position_docids.remap_types(),
-                index,
-                extractor_sender.docids::<WordPositionDocids>(),
-                &indexing_context.must_stop_processing,
-            )?;
-        }
-
-        {
-            let span =
-                tracing::trace_span!(target: "indexing::documents::merge", "fid_word_count_docids");
-            let _entered = span.enter();
-            indexing_context.progress.update_progress(MergingWordCache::FieldIdWordCountDocids);
-
-            merge_and_send_docids(
-                fid_word_count_docids,
-                index.field_id_word_count_docids.remap_types(),
-                index,
-                extractor_sender.docids::<FidWordCountDocids>(),
-                &indexing_context.must_stop_processing,
-            )?;
-        }
+        merge_and_send_word_docids(caches, indexing_context, &extractor_sender)?;
     }
 
     // run the proximity extraction only if the precision is by word
@@ -235,46 +176,36 @@ where
         }
     }
 
-    'vectors: {
-        if index_embeddings.is_empty() {
-            break 'vectors;
-        }
-
-        let embedding_sender = extractor_sender.embeddings();
-        let extractor = EmbeddingExtractor::new(
-            embedders,
-            embedding_sender,
-            field_distribution,
-            request_threads(),
-        );
-        let mut datastore = ThreadLocal::with_capacity(rayon::current_num_threads());
-        {
-            let span = tracing::debug_span!(target: "indexing::documents::extract", "vectors");
-            let _entered = span.enter();
+    extract_embeddings(
+        document_changes,
+        indexing_context,
+        &extractor_sender,
+        embedders,
+        extractor_allocs,
+        field_distribution,
+        &mut index_embeddings,
+        modified_docids,
+    )?;
+
+    'sparse_vectors: {
+        let Some(extractor) =
+            SparseVectorExtractor::new(&rtxn, index, embedders, extractor_sender.sparse_vectors())?
+        else {
+            break 'sparse_vectors;
+        };
+        let datastore = ThreadLocal::with_capacity(rayon::current_num_threads());
 
-            extract(
-                document_changes,
-                &extractor,
-                indexing_context,
-                extractor_allocs,
-                &datastore,
-                IndexingStep::ExtractingEmbeddings,
-            )?;
-        }
-        {
-            let span = tracing::debug_span!(target: "indexing::documents::merge", "vectors");
-            let _entered = span.enter();
+        let span = tracing::debug_span!(target: "indexing::documents::extract", "sparse_vectors");
+        let _entered = span.enter();
 
-            for config in &mut index_embeddings {
-                'data: for data in datastore.iter_mut() {
-                    let data = &mut data.get_mut().0;
-                    let Some(deladd) = data.remove(&config.name) else {
-                        continue 'data;
-                    };
-                    deladd.apply_to(&mut config.user_provided, modified_docids);
-                }
-            }
-        }
+        extract(
+            document_changes,
+            &extractor,
+            indexing_context,
+            extractor_allocs,
+            &datastore,
+            IndexingStep::ExtractingEmbeddings,
+        )?;
     }
 
     'geo: {
@@ -312,6 +243,277 @@ where
     Result::Ok((facet_field_ids_delta, index_embeddings))
 }
 
+/// Extracts the embeddings of the changed documents and updates the user provided embeddings of the index.
+#[allow(clippy::too_many_arguments)]
+fn extract_embeddings<'pl, 'extractor, DC, MSP>(
+    document_changes: &DC,
+    indexing_context: IndexingContext<MSP>,
+    extractor_sender: &ExtractorBbqueueSender,
+    embedders: &EmbeddingConfigs,
+    extractor_allocs: &'extractor mut ThreadLocal<FullySend<Bump>>,
+    field_distribution: &FieldDistribution,
+    index_embeddings: &mut [IndexEmbeddingConfig],
+    modified_docids: &mut RoaringBitmap,
+) -> Result<()>
+where
+    DC: DocumentChanges<'pl>,
+    MSP: Fn() -> bool + Sync,
+{
+    if index_embeddings.is_empty() {
+        return Ok(());
+    }
+
+    let embedding_sender = extractor_sender.embeddings();
+    let must_regenerate = embedders
+        .inner_as_ref()
+        .keys()
+        .filter(|name| document_changes.must_regenerate_embeddings(name))
+        .cloned()
+        .collect();
+    let extractor = EmbeddingExtractor::new(
+        embedders,
+        embedding_sender,
+        field_distribution,
+        request_threads(),
+        must_regenerate,
+    );
+    let mut datastore = ThreadLocal::with_capacity(rayon::current_num_threads());
+    {
+        let span = tracing::debug_span!(target: "indexing::documents::extract", "vectors");
+        let _entered = span.enter();
+
+        extract(
+            document_changes,
+            &extractor,
+            indexing_context,
+            extractor_allocs,
+            &datastore,
+            IndexingStep::ExtractingEmbeddings,
+        )?;
+    }
+    {
+        let span = tracing::debug_span!(target: "indexing::documents::merge", "vectors");
+        let _entered = span.enter();
+
+        for config in index_embeddings.iter_mut() {
+            'data: for data in datastore.iter_mut() {
+                let data = &mut data.get_mut().0;
+                let Some(deladd) = data.remove(&config.name) else {
+                    continue 'data;
+                };
+                deladd.apply_to(&mut config.user_provided, modified_docids);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Extracts the changes of the databases implied by a settings change, only for the affected fields.
+///
+/// The documents and their embeddings are left untouched.
+#[allow(clippy::too_many_arguments)]
+pub(super) fn extract_settings_changes<'pl, 'extractor, DC, MSP>(
+    document_changes: &DC,
+    indexing_context: IndexingContext<MSP>,
+    indexer_span: Span,
+    extractor_sender: ExtractorBbqueueSender,
+    settings_diff: &InnerIndexSettingsDiff,
+    old_fields_ids_map: &RwLock<FieldIdMapWithMetadata>,
+    extractor_allocs: &'extractor mut ThreadLocal<FullySend<Bump>>,
+    finished_extraction: &AtomicBool,
+) -> Result<FacetFieldIdsDelta>
+where
+    DC: DocumentChanges<'pl>,
+    MSP: Fn() -> bool + Sync,
+{
+    let span =
+        tracing::trace_span!(target: "indexing::documents", parent: &indexer_span, "extract");
+    let _entered = span.enter();
+
+    let index = indexing_context.index;
+    let rtxn = index.read_txn()?;
+
+    let fields_to_reindex = settings_diff.reindex_facet_fids();
+    let facet_field_ids_delta = if fields_to_reindex.is_empty() {
+        FacetFieldIdsDelta::new(0, 0)
+    } else {
+        let caches = {
+            let span = tracing::trace_span!(target: "indexing::documents::extract", parent: &indexer_span, "faceted");
+            let _entered = span.enter();
+
+            FacetedDocidsExtractor::run_settings_extraction(
+                document_changes,
+                indexing_context,
+                extractor_allocs,
+                &extractor_sender.field_id_docid_facet_sender(),
+                settings_diff,
+                old_fields_ids_map,
+                &fields_to_reindex,
+                IndexingStep::ExtractingFacets,
+            )?
+        };
+
+        let span = tracing::trace_span!(target: "indexing::documents::merge", parent: &indexer_span, "faceted");
+        let _entered = span.enter();
+        indexing_context.progress.update_progress(IndexingStep::MergingFacetCaches);
+
+        merge_and_send_facet_docids(
+            caches,
+            FacetDatabases::new(index),
+            index,
+            &rtxn,
+            extractor_sender.facet_docids(),
+        )?
+    };
+
+    if settings_diff.reindex_searchable() {
+        let caches = {
+            let span = tracing::trace_span!(target: "indexing::documents::extract", "word_docids");
+            let _entered = span.enter();
+            WordDocidsExtractors::run_settings_extraction(
+                document_changes,
+                indexing_context,
+                extractor_allocs,
+                settings_diff,
+                old_fields_ids_map,
+                IndexingStep::ExtractingWords,
+            )?
+        };
+
+        merge_and_send_word_docids(caches, indexing_context, &extractor_sender)?;
+    }
+
+    if settings_diff.reindex_word_pair_proximities() {
+        let caches = {
+            let span = tracing::trace_span!(target: "indexing::documents::extract", "word_pair_proximity_docids");
+            let _entered = span.enter();
+
+            WordPairProximityDocidsExtractor::run_settings_extraction(
+                document_changes,
+                indexing_context,
+                extractor_allocs,
+                settings_diff,
+                old_fields_ids_map,
+                IndexingStep::ExtractingWordProximity,
+            )?
+        };
+
+        {
+            let span = tracing::trace_span!(target: "indexing::documents::merge", "word_pair_proximity_docids");
+            let _entered = span.enter();
+            indexing_context.progress.update_progress(IndexingStep::MergingWordProximity);
+
+            merge_and_send_docids(
+                caches,
+                index.word_pair_proximity_docids.remap_types(),
+                index,
+                extractor_sender.docids::<WordPairProximityDocids>(),
+                &indexing_context.must_stop_processing,
+            )?;
+        }
+    }
+
+    indexing_context.progress.update_progress(IndexingStep::WaitingForDatabaseWrites);
+    finished_extraction.store(true, std::sync::atomic::Ordering::Relaxed);
+
+    Result::Ok(facet_field_ids_delta)
+}
+
+fn merge_and_send_word_docids<MSP>(
+    caches: WordDocidsCaches,
+    indexing_context: IndexingContext<MSP>,
+    extractor_sender: &ExtractorBbqueueSender,
+) -> Result<()>
+where
+    MSP: Fn() -> bool + Sync,
+{
+    let index = indexing_context.index;
+    let WordDocidsCaches {
+        word_docids,
+        word_fid_docids,
+        exact_word_docids,
+        word_position_docids,
+        fid_word_count_docids,
+    } = caches;
+
+    indexing_context.progress.update_progress(IndexingStep::MergingWordCaches);
+
+    {
+        let span = tracing::trace_span!(target: "indexing::documents::merge", "word_docids");
+        let _entered = span.enter();
+        indexing_context.progress.update_progress(MergingWordCache::WordDocids);
+
+        merge_and_send_docids(
+            word_docids,
+            index.word_docids.remap_types(),
+            index,
+            extractor_sender.docids::<WordDocids>(),
+            &indexing_context.must_stop_processing,
+        )?;
+    }
+
+    {
+        let span = tracing::trace_span!(target: "indexing::documents::merge", "word_fid_docids");
+        let _entered = span.enter();
+        indexing_context.progress.update_progress(MergingWordCache::WordFieldIdDocids);
+
+        merge_and_send_docids(
+            word_fid_docids,
+            index.word_fid_docids.remap_types(),
+            index,
+            extractor_sender.docids::<WordFidDocids>(),
+            &indexing_context.must_stop_processing,
+        )?;
+    }
+
+    {
+        let span = tracing::trace_span!(target: "indexing::documents::merge", "exact_word_docids");
+        let _entered = span.enter();
+        indexing_context.progress.update_progress(MergingWordCache::ExactWordDocids);
+
+        merge_and_send_docids(
+            exact_word_docids,
+            index.exact_word_docids.remap_types(),
+            index,
+            extractor_sender.docids::<ExactWordDocids>(),
+            &indexing_context.must_stop_processing,
+        )?;
+    }
+
+    {
+        let span =
+            tracing::trace_span!(target: "indexing::documents::merge", "word_position_docids");
+        let _entered = span.enter();
+        indexing_context.progress.update_progress(MergingWordCache::WordPositionDocids);
+
+        merge_and_send_docids(
+            word_position_docids,
+            index.word_position_docids.remap_types(),
+            index,
+            extractor_sender.docids::<WordPositionDocids>(),
+            &indexing_context.must_stop_processing,
+        )?;
+    }
+
+    {
+        let span =
+            tracing::trace_span!(target: "indexing::documents::merge", "fid_word_count_docids");
+        let _entered = span.enter();
+        indexing_context.progress.update_progress(MergingWordCache::FieldIdWordCountDocids);
+
+        merge_and_send_docids(
+            fid_word_count_docids,
+            index.field_id_word_count_docids.remap_types(),
+            index,
+            extractor_sender.docids::<FidWordCountDocids>(),
+            &indexing_context.must_stop_processing,
+        )?;
+    }
+
+    Ok(())
+}
+
 fn request_threads() -> &'static ThreadPoolNoAbort {
     static REQUEST_THREADS: OnceLock<ThreadPoolNoAbort> = OnceLock::new();
 
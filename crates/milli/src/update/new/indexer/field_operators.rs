@@ -0,0 +1,278 @@
+//! Operators modifying the fields of a document in place, usable in the payloads of document updates.
+//!
+//! An update payload may contain, along with the fields replacing the current ones, the following operators:
+//!
+//! ```json
+//! {
+//!   "id": 1,
+//!   "$inc": { "views": 1, "stats.likes": -1 },
+//!   "$set": { "author.name": "kefir" },
+//!   "$unset": ["draft", "author.nickname"],
+//!   "$push": { "tags": "new", "history": { "$each": [1, 2] } },
+//!   "$pull": { "tags": "old" }
+//! }
+//! ```
+//!
+//! Paths are split on dots to reach nested fields. Operators are applied in the order they appear in the document,
+//! on top of the current version of the document.
+
+use bumparaw_collections::RawMap;
+use rustc_hash::FxBuildHasher;
+use serde_json::{Map, Number, Value};
+
+const INC: &str = "$inc";
+const SET: &str = "$set";
+const UNSET: &str = "$unset";
+const PUSH: &str = "$push";
+const PULL: &str = "$pull";
+const EACH: &str = "$each";
+
+/// Whether `field` is the name of an operator rather than of a field of the document.
+pub fn is_operator(field: &str) -> bool {
+    matches!(field, INC | SET | UNSET | PUSH | PULL)
+}
+
+/// Whether the version of a document contains at least an operator.
+pub fn has_operators(version: &RawMap<'_, FxBuildHasher>) -> bool {
+    version.iter().any(|(field, _)| is_operator(field))
+}
+
+/// Applies a version of a document from an update payload on `document`.
+///
+/// The fields of the version replace the ones of the document, and its operators modify them.
+/// The operators cannot modify the field of the `primary_key`.
+pub fn apply_version(
+    document: &mut Map<String, Value>,
+    version: Map<String, Value>,
+    primary_key: Option<&str>,
+) -> Result<(), String> {
+    for (field, argument) in version {
+        if !is_operator(&field) {
+            document.insert(field, argument);
+            continue;
+        }
+
+        if field == UNSET {
+            let Value::Array(paths) = argument else {
+                return Err(format!("`{UNSET}` must be an array of paths, found `{argument}`"));
+            };
+            for path in paths {
+                let Value::String(path) = path else {
+                    return Err(format!("`{UNSET}` must be an array of paths, found `{path}`"));
+                };
+                check_path(&field, &path, primary_key)?;
+                unset(document, &path);
+            }
+            continue;
+        }
+
+        let Value::Object(arguments) = argument else {
+            return Err(format!(
+                "`{field}` must be an object of paths to values, found `{argument}`"
+            ));
+        };
+        for (path, value) in arguments {
+            check_path(&field, &path, primary_key)?;
+            match field.as_str() {
+                INC => increment(document, &path, value)?,
+                SET => *entry(document, &path)? = value,
+                PUSH => push(document, &path, value)?,
+                PULL => pull(document, &path, &value)?,
+                _ => unreachable!("unknown operator {field}"),
+            }
+        }
+    }
+    Ok(())
+}
+
+fn check_path(operator: &str, path: &str, primary_key: Option<&str>) -> Result<(), String> {
+    if path.is_empty() || path.split('.').any(str::is_empty) {
+        return Err(format!("`{operator}`: `{path}` is not a valid path"));
+    }
+    if let Some(primary_key) = primary_key {
+        let overlaps = |parent: &str, child: &str| {
+            child == parent || (child.starts_with(parent) && child[parent.len()..].starts_with('.'))
+        };
+        if overlaps(path, primary_key) || overlaps(primary_key, path) {
+            return Err(format!("`{operator}` cannot modify the primary key `{primary_key}`"));
+        }
+    }
+    Ok(())
+}
+
+/// The value at `path`, creating the missing objects on the way.
+fn entry<'a>(document: &'a mut Map<String, Value>, path: &str) -> Result<&'a mut Value, String> {
+    let mut object = document;
+    let mut components = path.split('.').peekable();
+    let mut traversed = 0;
+    while let Some(component) = components.next() {
+        traversed += component.len();
+        let value = object.entry(component).or_insert(Value::Null);
+        if components.peek().is_none() {
+            return Ok(value);
+        }
+        if value.is_null() {
+            *value = Value::Object(Map::new());
+        }
+        object = match value {
+            Value::Object(object) => object,
+            _ => return Err(format!("`{}` is not an object", &path[..traversed])),
+        };
+        traversed += 1;
+    }
+    unreachable!("paths have at least one component")
+}
+
+/// The value at `path`, if any.
+fn get_mut<'a>(document: &'a mut Map<String, Value>, path: &str) -> Option<&'a mut Value> {
+    let (parent, last) = match path.rsplit_once('.') {
+        Some((parent, last)) => (Some(parent), last),
+        None => (None, path),
+    };
+    let object = match parent {
+        Some(parent) => parent
+            .split('.')
+            .try_fold(document, |object, component| object.get_mut(component)?.as_object_mut())?,
+        None => document,
+    };
+    object.get_mut(last)
+}
+
+fn unset(document: &mut Map<String, Value>, path: &str) {
+    match path.rsplit_once('.') {
+        Some((parent, last)) => {
+            if let Some(Value::Object(parent)) = get_mut(document, parent) {
+                parent.remove(last);
+            }
+        }
+        None => {
+            document.remove(path);
+        }
+    }
+}
+
+fn increment(document: &mut Map<String, Value>, path: &str, value: Value) -> Result<(), String> {
+    let Value::Number(increment) = value else {
+        return Err(format!(
+            "`{INC}`: the increment of `{path}` must be a number, found `{value}`"
+        ));
+    };
+    let current = entry(document, path)?;
+    let sum = match &*current {
+        Value::Null => increment,
+        Value::Number(number) => match (number.as_i64(), increment.as_i64()) {
+            (Some(left), Some(right)) if left.checked_add(right).is_some() => {
+                Number::from(left + right)
+            }
+            _ => {
+                let sum =
+                    number.as_f64().unwrap_or_default() + increment.as_f64().unwrap_or_default();
+                Number::from_f64(sum)
+                    .ok_or_else(|| format!("`{INC}`: `{path}` would not be a finite number"))?
+            }
+        },
+        other => return Err(format!("`{INC}`: `{path}` is not a number, found `{other}`")),
+    };
+    *current = Value::Number(sum);
+    Ok(())
+}
+
+fn push(document: &mut Map<String, Value>, path: &str, value: Value) -> Result<(), String> {
+    let values = match value {
+        Value::Object(mut object) if object.len() == 1 && object.contains_key(EACH) => {
+            match object.remove(EACH) {
+                Some(Value::Array(values)) => values,
+                Some(other) => {
+                    return Err(format!("`{PUSH}`: `{EACH}` must be an array, found `{other}`"))
+                }
+                None => unreachable!(),
+            }
+        }
+        value => vec![value],
+    };
+    let current = entry(document, path)?;
+    match current {
+        Value::Null => *current = Value::Array(values),
+        Value::Array(array) => array.extend(values),
+        other => return Err(format!("`{PUSH}`: `{path}` is not an array, found `{other}`")),
+    }
+    Ok(())
+}
+
+fn pull(document: &mut Map<String, Value>, path: &str, value: &Value) -> Result<(), String> {
+    match get_mut(document, path) {
+        None | Some(Value::Null) => Ok(()),
+        Some(Value::Array(array)) => {
+            array.retain(|element| element != value);
+            Ok(())
+        }
+        Some(other) => Err(format!("`{PULL}`: `{path}` is not an array, found `{other}`")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    fn apply(document: Value, version: Value) -> Result<Value, String> {
+        let Value::Object(mut document) = document else { panic!() };
+        let Value::Object(version) = version else { panic!() };
+        apply_version(&mut document, version, Some("id"))?;
+        Ok(Value::Object(document))
+    }
+
+    #[test]
+    fn operators() {
+        let document = json!({
+            "id": 1,
+            "views": 41,
+            "rating": 4.5,
+            "tags": ["old", "cute"],
+            "author": { "name": "echo", "nickname": "e" },
+        });
+        let version = json!({
+            "id": 1,
+            "title": "kefir",
+            "$inc": { "views": 1, "rating": 0.25, "stats.likes": 3 },
+            "$set": { "author.name": "kefir" },
+            "$unset": ["author.nickname", "missing.field"],
+            "$push": { "tags": "new", "history": { "$each": [1, 2] } },
+            "$pull": { "tags": "old" },
+        });
+        assert_eq!(
+            apply(document, version).unwrap(),
+            json!({
+                "id": 1,
+                "title": "kefir",
+                "views": 42,
+                "rating": 4.75,
+                "stats": { "likes": 3 },
+                "tags": ["cute", "new"],
+                "history": [1, 2],
+                "author": { "name": "kefir" },
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_operators() {
+        let document = json!({ "id": 1, "title": "kefir", "tags": "cute" });
+
+        let error = apply(document.clone(), json!({ "$inc": { "title": 1 } })).unwrap_err();
+        assert_eq!(error, "`$inc`: `title` is not a number, found `\"kefir\"`");
+
+        let error = apply(document.clone(), json!({ "$push": { "tags": "new" } })).unwrap_err();
+        assert_eq!(error, "`$push`: `tags` is not an array, found `\"cute\"`");
+
+        let error = apply(document.clone(), json!({ "$set": { "title.main": "a" } })).unwrap_err();
+        assert_eq!(error, "`title` is not an object");
+
+        let error = apply(document.clone(), json!({ "$unset": "title" })).unwrap_err();
+        assert_eq!(error, "`$unset` must be an array of paths, found `\"title\"`");
+
+        let error = apply(document, json!({ "$set": { "id": 2 } })).unwrap_err();
+        assert_eq!(error, "`$set` cannot modify the primary key `id`");
+    }
+}
@@ -2,19 +2,23 @@ use bumparaw_collections::RawMap;
 use heed::RoTxn;
 use rustc_hash::FxBuildHasher;
 
-use crate::documents::{PrimaryKey, DEFAULT_PRIMARY_KEY};
+use crate::documents::{validate_primary_key, PrimaryKey, DEFAULT_PRIMARY_KEY};
 use crate::update::new::StdResult;
 use crate::{FieldsIdsMap, Index, Result, UserError};
 
 /// Returns the primary key that has already been set for this index or the
 /// one we will guess by searching for the first key that contains "id" as a substring,
-/// and whether the primary key changed
+/// and whether the primary key changed.
+///
+/// When the ids of the documents are generated, the default primary key is used
+/// if there is no candidate.
 pub fn retrieve_or_guess_primary_key<'a>(
     rtxn: &'a RoTxn<'a>,
     index: &Index,
     new_fields_ids_map: &mut FieldsIdsMap,
     primary_key_from_op: Option<&'a str>,
     first_document: Option<RawMap<'a, FxBuildHasher>>,
+    generate_document_ids: bool,
 ) -> Result<StdResult<(PrimaryKey<'a>, bool), UserError>> {
     // make sure that we have a declared primary key, either fetching it from the index or attempting to guess it.
 
@@ -35,6 +39,9 @@ pub fn retrieve_or_guess_primary_key<'a>(
         // did we request a primary key in the operation?
         let primary_key = if let Some(primary_key_from_op) = primary_key_from_op {
             // set primary key from operation
+            if let Err(err) = validate_primary_key(primary_key_from_op) {
+                return Ok(Err(err));
+            }
             primary_key_from_op
         } else {
             // guess primary key
@@ -60,6 +67,7 @@ pub fn retrieve_or_guess_primary_key<'a>(
             guesses.sort_unstable();
 
             match guesses.as_slice() {
+                [] if generate_document_ids => DEFAULT_PRIMARY_KEY,
                 [] => return Ok(Err(UserError::NoPrimaryKeyCandidateFound)),
                 [name] => {
                     tracing::info!("Primary key was not specified in index. Inferred to '{name}'");
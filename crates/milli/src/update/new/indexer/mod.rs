@@ -5,11 +5,13 @@ use std::thread::{self, Builder};
 use big_s::S;
 use document_changes::{DocumentChanges, IndexingContext};
 pub use document_deletion::DocumentDeletion;
-pub use document_operation::{DocumentOperation, PayloadStats};
+pub use document_operation::{DocumentOperation, PayloadStats, VersionConflict};
 use hashbrown::HashMap;
 use heed::RwTxn;
 pub use partial_dump::PartialDump;
 pub use post_processing::recompute_word_fst_from_word_docids_database;
+pub use regenerate_embeddings::RegenerateEmbeddings;
+pub(crate) use settings_changes::reindex;
 pub use update_by_function::UpdateByFunction;
 pub use write::ChannelCongestion;
 use write::{build_vectors, update_index, write_to_db};
@@ -29,9 +31,12 @@ pub mod document_changes;
 mod document_deletion;
 mod document_operation;
 mod extract;
+pub(crate) mod field_operators;
 mod guess_primary_key;
 mod partial_dump;
 mod post_processing;
+mod regenerate_embeddings;
+mod settings_changes;
 mod update_by_function;
 mod write;
 
@@ -64,49 +69,7 @@ where
     let finished_extraction = AtomicBool::new(false);
 
     let arroy_memory = grenad_parameters.max_memory;
-
-    // We reduce the actual memory used to 5%. The reason we do this here and not in Meilisearch
-    // is because we still use the old indexer for the settings and it is highly impacted by the
-    // max memory. So we keep the changes here and will remove these changes once we use the new
-    // indexer to also index settings. Related to #5125 and #5141.
-    let grenad_parameters = GrenadParameters {
-        max_memory: grenad_parameters.max_memory.map(|mm| mm * 5 / 100),
-        ..grenad_parameters
-    };
-
-    // 5% percent of the allocated memory for the extractors, or min 100MiB
-    // 5% percent of the allocated memory for the bbqueues, or min 50MiB
-    //
-    // Minimum capacity for bbqueues
-    let minimum_total_bbbuffer_capacity = 50 * 1024 * 1024 * pool.current_num_threads(); // 50 MiB
-    let minimum_total_extractors_capacity = minimum_total_bbbuffer_capacity * 2;
-
-    let (grenad_parameters, total_bbbuffer_capacity) = grenad_parameters.max_memory.map_or(
-        (
-            GrenadParameters {
-                max_memory: Some(minimum_total_extractors_capacity),
-                ..grenad_parameters
-            },
-            minimum_total_bbbuffer_capacity,
-        ), // 100 MiB by thread by default
-        |max_memory| {
-            let total_bbbuffer_capacity = max_memory.max(minimum_total_bbbuffer_capacity);
-            let new_grenad_parameters = GrenadParameters {
-                max_memory: Some(max_memory.max(minimum_total_extractors_capacity)),
-                ..grenad_parameters
-            };
-            (new_grenad_parameters, total_bbbuffer_capacity)
-        },
-    );
-
-    LOG_MEMORY_METRICS_ONCE.call_once(|| {
-        tracing::debug!(
-            "Indexation allocated memory metrics - \
-            Total BBQueue size: {total_bbbuffer_capacity}, \
-            Total extractor memory: {:?}",
-            grenad_parameters.max_memory,
-        );
-    });
+    let (grenad_parameters, total_bbbuffer_capacity) = extractors_memory(grenad_parameters, pool);
 
     let (extractor_sender, writer_receiver) = pool
         .install(|| extractor_writer_bbqueue(&mut bbbuffers, total_bbbuffer_capacity, 1000))
@@ -169,6 +132,8 @@ where
         let arroy_writers: Result<HashMap<_, _>> = embedders
             .inner_as_ref()
             .iter()
+            // sparse vectors are written to their own inverted index
+            .filter(|(_, (embedder, _, _))| !embedder.is_sparse())
             .map(|(embedder_name, (embedder, _, was_quantized))| {
                 let embedder_index = index.embedder_category_id.get(wtxn, embedder_name)?.ok_or(
                     InternalError::DatabaseMissingEntry {
@@ -239,3 +204,53 @@ where
 
     Ok(congestion)
 }
+
+/// Splits the memory allocated to the indexation between the extractors and the bbqueues.
+///
+/// Returns the grenad parameters of the extractors and the total capacity of the bbqueues.
+fn extractors_memory(
+    grenad_parameters: GrenadParameters,
+    pool: &ThreadPoolNoAbort,
+) -> (GrenadParameters, usize) {
+    // We reduce the actual memory used to 5%.
+    let grenad_parameters = GrenadParameters {
+        max_memory: grenad_parameters.max_memory.map(|mm| mm * 5 / 100),
+        ..grenad_parameters
+    };
+
+    // 5% percent of the allocated memory for the extractors, or min 100MiB
+    // 5% percent of the allocated memory for the bbqueues, or min 50MiB
+    //
+    // Minimum capacity for bbqueues
+    let minimum_total_bbbuffer_capacity = 50 * 1024 * 1024 * pool.current_num_threads(); // 50 MiB
+    let minimum_total_extractors_capacity = minimum_total_bbbuffer_capacity * 2;
+
+    let (grenad_parameters, total_bbbuffer_capacity) = grenad_parameters.max_memory.map_or(
+        (
+            GrenadParameters {
+                max_memory: Some(minimum_total_extractors_capacity),
+                ..grenad_parameters
+            },
+            minimum_total_bbbuffer_capacity,
+        ), // 100 MiB by thread by default
+        |max_memory| {
+            let total_bbbuffer_capacity = max_memory.max(minimum_total_bbbuffer_capacity);
+            let new_grenad_parameters = GrenadParameters {
+                max_memory: Some(max_memory.max(minimum_total_extractors_capacity)),
+                ..grenad_parameters
+            };
+            (new_grenad_parameters, total_bbbuffer_capacity)
+        },
+    );
+
+    LOG_MEMORY_METRICS_ONCE.call_once(|| {
+        tracing::debug!(
+            "Indexation allocated memory metrics - \
+            Total BBQueue size: {total_bbbuffer_capacity}, \
+            Total extractor memory: {:?}",
+            grenad_parameters.max_memory,
+        );
+    });
+
+    (grenad_parameters, total_bbbuffer_capacity)
+}
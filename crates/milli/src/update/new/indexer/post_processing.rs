@@ -160,8 +160,9 @@ fn compute_facet_search_database(
         return Ok(());
     }
 
-    let localized_attributes_rules = index.localized_attributes_rules(&rtxn)?;
-    let filterable_attributes_rules = index.filterable_attributes_rules(&rtxn)?;
+    // the settings are read from the write transaction, as they may be changing in it.
+    let localized_attributes_rules = index.localized_attributes_rules(wtxn)?;
+    let filterable_attributes_rules = index.filterable_attributes_rules(wtxn)?;
     let mut facet_search_builder = FacetSearchBuilder::new(
         global_fields_ids_map,
         localized_attributes_rules.unwrap_or_default(),
@@ -213,9 +214,8 @@ fn compute_facet_level_database(
     global_fields_ids_map: &mut GlobalFieldsIdsMap,
     progress: &Progress,
 ) -> Result<()> {
-    let rtxn = index.read_txn()?;
-
-    let filterable_attributes_rules = index.filterable_attributes_rules(&rtxn)?;
+    // the settings are read from the write transaction, as they may be changing in it.
+    let filterable_attributes_rules = index.filterable_attributes_rules(wtxn)?;
     let mut deltas: Vec<_> = facet_field_ids_delta.consume_facet_string_delta().collect();
     // We move all bulks at the front and incrementals (others) at the end.
     deltas.sort_by_key(|(_, delta)| if let FacetFieldIdDelta::Bulk = delta { 0 } else { 1 });
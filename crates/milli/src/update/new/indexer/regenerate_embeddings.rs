@@ -0,0 +1,107 @@
+use bumparaw_collections::RawMap;
+use rayon::iter::IndexedParallelIterator;
+use rayon::slice::ParallelSlice as _;
+use roaring::RoaringBitmap;
+use rustc_hash::FxBuildHasher;
+use serde_json::value::RawValue;
+
+use super::document_changes::DocumentChangeContext;
+use super::DocumentChanges;
+use crate::documents::Error::InvalidDocumentFormat;
+use crate::documents::PrimaryKey;
+use crate::error::InternalError;
+use crate::update::new::document::Versions;
+use crate::update::new::thread_local::MostlySend;
+use crate::update::new::{DocumentChange, Update};
+use crate::Result;
+
+/// Regenerates the embeddings of a set of documents, leaving the documents themselves unchanged.
+pub struct RegenerateEmbeddings {
+    documents: RoaringBitmap,
+    embedders: Option<Vec<String>>,
+}
+
+pub struct RegenerateEmbeddingsChanges<'doc> {
+    primary_key: &'doc PrimaryKey<'doc>,
+    embedders: Option<Vec<String>>,
+    // It is sad that the RoaringBitmap doesn't
+    // implement IndexedParallelIterator
+    documents: Vec<u32>,
+}
+
+impl RegenerateEmbeddings {
+    /// Regenerates the embeddings of the `documents` for the given `embedders`, or for all the embedders if `None`.
+    pub fn new(documents: RoaringBitmap, embedders: Option<Vec<String>>) -> Self {
+        RegenerateEmbeddings { documents, embedders }
+    }
+
+    pub fn into_changes<'index>(
+        self,
+        primary_key: &'index PrimaryKey,
+    ) -> RegenerateEmbeddingsChanges<'index> {
+        let Self { documents, embedders } = self;
+        RegenerateEmbeddingsChanges {
+            primary_key,
+            embedders,
+            documents: documents.into_iter().collect(),
+        }
+    }
+}
+
+impl<'index> DocumentChanges<'index> for RegenerateEmbeddingsChanges<'index> {
+    type Item = u32;
+
+    fn iter(
+        &self,
+        chunk_size: usize,
+    ) -> impl IndexedParallelIterator<Item = impl AsRef<[Self::Item]>> {
+        self.documents.as_slice().par_chunks(chunk_size)
+    }
+
+    fn item_to_document_change<'doc, T: MostlySend + 'doc>(
+        &self,
+        context: &'doc DocumentChangeContext<T>,
+        docid: &'doc Self::Item,
+    ) -> Result<Option<DocumentChange<'doc>>>
+    where
+        'index: 'doc,
+    {
+        let DocumentChangeContext { index, db_fields_ids_map, rtxn: txn, doc_alloc, .. } = context;
+
+        let docid = *docid;
+
+        // safety: the document *must* exist in the database as
+        //         its ID comes from the list of documents ids.
+        let document = index.document(txn, docid)?;
+        let document_id = self
+            .primary_key
+            .document_id(document, db_fields_ids_map)?
+            .map_err(|_| InvalidDocumentFormat)?;
+
+        let no_changes: &RawValue = serde_json::from_str("{}").map_err(InternalError::SerdeJson)?;
+        let no_changes = RawMap::from_raw_value_and_hasher(no_changes, FxBuildHasher, doc_alloc)
+            .map_err(InternalError::SerdeJson)?;
+
+        // The document is left unchanged, only its embeddings are regenerated
+        Ok(Some(DocumentChange::Update(Update::create(
+            docid,
+            doc_alloc.alloc_str(&document_id),
+            Versions::single(no_changes),
+            false,
+        ))))
+    }
+
+    fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    fn must_regenerate_embeddings(&self, embedder_name: &str) -> bool {
+        self.embedders
+            .as_ref()
+            .is_none_or(|embedders| embedders.iter().any(|name| name == embedder_name))
+    }
+
+    fn only_regenerates_embeddings(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,253 @@
+use std::sync::atomic::AtomicBool;
+use std::sync::RwLock;
+use std::thread::{self, Builder};
+
+use big_s::S;
+use bumparaw_collections::RawMap;
+use hashbrown::HashMap;
+use heed::types::{Bytes, DecodeIgnore};
+use heed::RwTxn;
+use rayon::iter::IndexedParallelIterator;
+use rayon::slice::ParallelSlice as _;
+use roaring::RoaringBitmap;
+use rustc_hash::FxBuildHasher;
+use serde_json::value::RawValue;
+
+use super::document_changes::{DocumentChangeContext, DocumentChanges, IndexingContext};
+use super::write::{write_to_db, ChannelCongestion};
+use super::{extract, extractors_memory, post_processing};
+use crate::documents::Error::InvalidDocumentFormat;
+use crate::documents::PrimaryKey;
+use crate::error::InternalError;
+use crate::fields_ids_map::metadata::{FieldIdMapWithMetadata, MetadataBuilder};
+use crate::index::{db_name, main_key};
+use crate::progress::Progress;
+use crate::update::facet::clear_facet_levels_based_on_settings_diff;
+use crate::update::new::channel::extractor_writer_bbqueue;
+use crate::update::new::document::Versions;
+use crate::update::new::steps::IndexingStep;
+use crate::update::new::thread_local::{MostlySend, ThreadLocal};
+use crate::update::new::{DocumentChange, Update};
+use crate::update::settings::{InnerIndexSettings, InnerIndexSettingsDiff};
+use crate::update::GrenadParameters;
+use crate::{
+    FieldId, FieldIdMapMissingEntry, GlobalFieldsIdsMap, Index, Result, ThreadPoolNoAbort,
+};
+
+/// Extracts again, with the new indexer, the fields of all the documents affected by a settings change.
+///
+/// The documents are deleted from the databases with the old settings and added back with the new ones,
+/// only for the fields selected by the [`InnerIndexSettingsDiff`], leaving the documents themselves unchanged.
+pub(crate) fn reindex<'indexer, MSP>(
+    wtxn: &mut RwTxn,
+    index: &Index,
+    pool: &ThreadPoolNoAbort,
+    grenad_parameters: GrenadParameters,
+    settings_diff: &InnerIndexSettingsDiff,
+    must_stop_processing: &'indexer MSP,
+    progress: &'indexer Progress,
+) -> Result<ChannelCongestion>
+where
+    MSP: Fn() -> bool + Sync,
+{
+    let mut bbbuffers = Vec::new();
+    let finished_extraction = AtomicBool::new(false);
+    let (grenad_parameters, total_bbbuffer_capacity) = extractors_memory(grenad_parameters, pool);
+
+    let (extractor_sender, writer_receiver) = pool
+        .install(|| extractor_writer_bbqueue(&mut bbbuffers, total_bbbuffer_capacity, 1000))
+        .unwrap();
+
+    let db_fields_ids_map = index.fields_ids_map(wtxn)?;
+    let primary_key_name =
+        index.primary_key(wtxn)?.map(String::from).ok_or(InternalError::DatabaseMissingEntry {
+            db_name: db_name::MAIN,
+            key: Some(main_key::PRIMARY_KEY_KEY),
+        })?;
+    let primary_key = PrimaryKey::new(&primary_key_name, &db_fields_ids_map).ok_or_else(|| {
+        InternalError::FieldIdMapMissingEntry(FieldIdMapMissingEntry::FieldName {
+            field_name: primary_key_name.clone(),
+            process: "reindex",
+        })
+    })?;
+    let documents = index.documents_ids(wtxn)?;
+    let document_changes = SettingsChangeDocumentChanges::new(&primary_key, documents);
+
+    // The metadata of the fields is computed from the settings as they are being written.
+    let metadata_builder = MetadataBuilder::from_index(index, wtxn)?;
+    let new_fields_ids_map =
+        FieldIdMapWithMetadata::new(db_fields_ids_map.clone(), metadata_builder);
+    let new_fields_ids_map = RwLock::new(new_fields_ids_map);
+    let old_fields_ids_map = FieldIdMapWithMetadata::new(
+        db_fields_ids_map.clone(),
+        settings_diff.old.fields_ids_map.metadata_builder().clone(),
+    );
+    let old_fields_ids_map = RwLock::new(old_fields_ids_map);
+    let fields_ids_map_store = ThreadLocal::with_capacity(rayon::current_num_threads());
+    let mut extractor_allocs = ThreadLocal::with_capacity(rayon::current_num_threads());
+    let doc_allocs = ThreadLocal::with_capacity(rayon::current_num_threads());
+
+    let indexing_context = IndexingContext {
+        index,
+        db_fields_ids_map: &db_fields_ids_map,
+        new_fields_ids_map: &new_fields_ids_map,
+        doc_allocs: &doc_allocs,
+        fields_ids_map_store: &fields_ids_map_store,
+        must_stop_processing,
+        progress,
+        grenad_parameters: &grenad_parameters,
+    };
+
+    let congestion = thread::scope(|s| -> Result<ChannelCongestion> {
+        let indexer_span = tracing::Span::current();
+        let finished_extraction = &finished_extraction;
+        let document_changes = &document_changes;
+        let old_fields_ids_map = &old_fields_ids_map;
+        let extractor_handle =
+            Builder::new().name(S("indexer-extractors")).spawn_scoped(s, move || {
+                pool.install(move || {
+                    extract::extract_settings_changes(
+                        document_changes,
+                        indexing_context,
+                        indexer_span,
+                        extractor_sender,
+                        settings_diff,
+                        old_fields_ids_map,
+                        &mut extractor_allocs,
+                        finished_extraction,
+                    )
+                })
+                .unwrap()
+            })?;
+
+        let global_fields_ids_map = GlobalFieldsIdsMap::new(&new_fields_ids_map);
+
+        // the embeddings are never regenerated by this function
+        let congestion =
+            write_to_db(writer_receiver, finished_extraction, index, wtxn, &HashMap::new())?;
+
+        indexing_context.progress.update_progress(IndexingStep::WaitingForExtractors);
+
+        let facet_field_ids_delta = extractor_handle.join().unwrap()?;
+
+        post_processing::post_process(
+            indexing_context,
+            wtxn,
+            global_fields_ids_map,
+            facet_field_ids_delta,
+        )?;
+
+        indexing_context.progress.update_progress(IndexingStep::Finalizing);
+
+        Ok(congestion) as Result<_>
+    })?;
+
+    // required to into_inner the new_fields_ids_map
+    drop(fields_ids_map_store);
+
+    let new_fields_ids_map = new_fields_ids_map.into_inner().unwrap();
+    index.put_fields_ids_map(wtxn, new_fields_ids_map.as_fields_ids_map())?;
+    clear_facet_levels_based_on_settings_diff(wtxn, index, settings_diff)?;
+    clear_facet_search_based_on_settings_diff(wtxn, index, settings_diff)?;
+
+    Ok(congestion)
+}
+
+/// Removes the facet search entries of the fields that are no longer facet searchable.
+fn clear_facet_search_based_on_settings_diff(
+    wtxn: &mut RwTxn,
+    index: &Index,
+    settings_diff: &InnerIndexSettingsDiff,
+) -> Result<()> {
+    let InnerIndexSettingsDiff { old, new, .. } = settings_diff;
+    let is_facet_searchable = |settings: &InnerIndexSettings, fid: FieldId| {
+        settings.facet_search
+            && settings.fields_ids_map.metadata(fid).is_some_and(|metadata| {
+                metadata
+                    .filterable_attributes_features(&settings.filterable_attributes_rules)
+                    .is_facet_searchable()
+            })
+    };
+
+    for fid in settings_diff.reindex_facet_fids() {
+        if !is_facet_searchable(old, fid) || is_facet_searchable(new, fid) {
+            continue;
+        }
+
+        index.facet_id_string_fst.delete(wtxn, &fid)?;
+        let mut iter = index
+            .facet_id_normalized_string_strings
+            .remap_types::<Bytes, DecodeIgnore>()
+            .prefix_iter_mut(wtxn, &fid.to_be_bytes())?;
+        while iter.next().transpose()?.is_some() {
+            // safety: we don't keep references from inside the LMDB database.
+            unsafe { iter.del_current()? };
+        }
+    }
+
+    Ok(())
+}
+
+/// Every document of the index, replaced by itself.
+///
+/// The updates carry no new version of the documents, so that their current version is extracted both
+/// with the old and the new settings.
+pub struct SettingsChangeDocumentChanges<'doc> {
+    primary_key: &'doc PrimaryKey<'doc>,
+    // It is sad that the RoaringBitmap doesn't
+    // implement IndexedParallelIterator
+    documents: Vec<u32>,
+}
+
+impl<'doc> SettingsChangeDocumentChanges<'doc> {
+    pub fn new(primary_key: &'doc PrimaryKey<'doc>, documents: RoaringBitmap) -> Self {
+        SettingsChangeDocumentChanges { primary_key, documents: documents.into_iter().collect() }
+    }
+}
+
+impl<'index> DocumentChanges<'index> for SettingsChangeDocumentChanges<'index> {
+    type Item = u32;
+
+    fn iter(
+        &self,
+        chunk_size: usize,
+    ) -> impl IndexedParallelIterator<Item = impl AsRef<[Self::Item]>> {
+        self.documents.as_slice().par_chunks(chunk_size)
+    }
+
+    fn item_to_document_change<'doc, T: MostlySend + 'doc>(
+        &self,
+        context: &'doc DocumentChangeContext<T>,
+        docid: &'doc Self::Item,
+    ) -> Result<Option<DocumentChange<'doc>>>
+    where
+        'index: 'doc,
+    {
+        let DocumentChangeContext { index, db_fields_ids_map, rtxn: txn, doc_alloc, .. } = context;
+
+        let docid = *docid;
+
+        // safety: the document *must* exist in the database as
+        //         its ID comes from the list of documents ids.
+        let document = index.document(txn, docid)?;
+        let document_id = self
+            .primary_key
+            .document_id(document, db_fields_ids_map)?
+            .map_err(|_| InvalidDocumentFormat)?;
+
+        let no_changes: &RawValue = serde_json::from_str("{}").map_err(InternalError::SerdeJson)?;
+        let no_changes = RawMap::from_raw_value_and_hasher(no_changes, FxBuildHasher, doc_alloc)
+            .map_err(InternalError::SerdeJson)?;
+
+        Ok(Some(DocumentChange::Update(Update::create(
+            docid,
+            doc_alloc.alloc_str(&document_id),
+            Versions::single(no_changes),
+            false,
+        ))))
+    }
+
+    fn len(&self) -> usize {
+        self.documents.len()
+    }
+}
@@ -109,7 +109,9 @@ impl<'t> VectorDocumentFromDb<'t> {
             None => None,
         };
 
-        let embedding_config = index.embedding_configs(rtxn)?;
+        let mut embedding_config = index.embedding_configs(rtxn)?;
+        // sparse vectors are stored in the document itself
+        embedding_config.retain(|config| !config.config.embedder_options.is_sparse());
 
         Ok(Some(Self { docid, embedding_config, index, vectors_field, rtxn, doc_alloc }))
     }
@@ -156,10 +158,16 @@ impl<'t> VectorDocument<'t> for VectorDocumentFromDb<'t> {
     }
 
     fn vectors_for_key(&self, key: &str) -> Result<Option<VectorEntry<'t>>> {
-        Ok(match self.index.embedder_category_id.get(self.rtxn, key)? {
-            Some(embedder_id) => {
-                let config =
-                    self.embedding_config.iter().find(|config| config.name == key).unwrap();
+        let config = self.embedding_config.iter().find(|config| config.name == key);
+        Ok(match config {
+            Some(config) => {
+                let embedder_id =
+                    self.index.embedder_category_id.get(self.rtxn, key)?.ok_or_else(|| {
+                        InternalError::DatabaseMissingEntry {
+                            db_name: crate::index::db_name::VECTOR_EMBEDDER_CATEGORY_ID,
+                            key: None,
+                        }
+                    })?;
                 Some(self.entry_from_db(embedder_id, config)?)
             }
             None => match self.vectors_field.as_ref().and_then(|obkv| obkv.get(key)) {
@@ -250,7 +258,7 @@ impl<'doc> VectorDocument<'doc> for VectorDocumentFromVersions<'doc> {
                 self.external_document_id,
                 embedder,
                 vectors,
-                self.embedders.contains(embedder),
+                self.embedders.contains_dense(embedder),
             )?;
             Ok((embedder, vectors))
         })
@@ -262,7 +270,7 @@ impl<'doc> VectorDocument<'doc> for VectorDocumentFromVersions<'doc> {
             self.external_document_id,
             key,
             vectors,
-            self.embedders.contains(key),
+            self.embedders.contains_dense(key),
         )?;
         Ok(Some(vectors))
     }
@@ -11,31 +11,44 @@ use roaring::RoaringBitmap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use time::OffsetDateTime;
 
+use super::chat::ChatSettings;
 use super::del_add::{DelAdd, DelAddOperation};
 use super::index_documents::{IndexDocumentsConfig, Transform};
 use super::IndexerConfig;
 use crate::attribute_patterns::PatternMatch;
-use crate::constants::RESERVED_GEO_FIELD_NAME;
+use crate::constants::{
+    RESERVED_EXPIRES_AT_FIELD_NAME, RESERVED_GEO_FIELD_NAME, RESERVED_VECTORS_FIELD_NAME,
+    RESERVED_VERSION_FIELD_NAME,
+};
 use crate::criterion::Criterion;
 use crate::disabled_typos_terms::DisabledTyposTerms;
+use crate::documents::validate_primary_key;
 use crate::error::UserError;
-use crate::fields_ids_map::metadata::{FieldIdMapWithMetadata, MetadataBuilder};
+use crate::fields_ids_map::metadata::{FieldIdMapWithMetadata, Metadata, MetadataBuilder};
 use crate::filterable_attributes_rules::match_faceted_field;
 use crate::index::{
-    IndexEmbeddingConfig, PrefixSearch, DEFAULT_MIN_WORD_LEN_ONE_TYPO,
+    db_name, IndexEmbeddingConfig, PrefixSearch, DEFAULT_MIN_WORD_LEN_ONE_TYPO,
     DEFAULT_MIN_WORD_LEN_TWO_TYPOS,
 };
 use crate::order_by_map::OrderByMap;
+use crate::progress::{Progress, VariableNameStep};
 use crate::prompt::default_max_bytes;
 use crate::proximity::ProximityPrecision;
 use crate::update::index_documents::IndexDocumentsMethod;
+use crate::update::new::indexer;
 use crate::update::{IndexDocuments, UpdateIndexingStep};
+use crate::vector::persistent_cache::PersistentEmbeddingCache;
 use crate::vector::settings::{
     EmbedderAction, EmbedderSource, EmbeddingSettings, NestingContext, ReindexAction,
     SubEmbeddingSettings, WriteBackToDocuments,
 };
-use crate::vector::{Embedder, EmbeddingConfig, EmbeddingConfigs};
-use crate::{FieldId, FilterableAttributesRule, Index, LocalizedAttributesRule, Result};
+use crate::vector::sparse::{index_sparse_vectors_from_documents, SparseVectorStore};
+use crate::vector::{ArroyWrapper, Embedder, EmbeddingConfig, EmbeddingConfigs};
+use crate::{
+    is_faceted_by, make_enum_progress, DocumentSchema, DocumentSchemaSettings, FieldId, FieldType,
+    FilterableAttributesRule, Index, IngestPipeline, IngestProcessor, LocalizedAttributesRule,
+    Result,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum Setting<T> {
@@ -156,6 +169,9 @@ pub struct Settings<'a, 't, 'i> {
     index: &'i Index,
 
     indexer_config: &'a IndexerConfig,
+    progress: Progress,
+    /// Whether documents or settings were already written in the transaction.
+    uncommitted_writes: bool,
 
     searchable_fields: Setting<Vec<String>>,
     displayed_fields: Setting<Vec<String>>,
@@ -185,6 +201,11 @@ pub struct Settings<'a, 't, 'i> {
     localized_attributes_rules: Setting<Vec<LocalizedAttributesRule>>,
     prefix_search: Setting<PrefixSearch>,
     facet_search: Setting<bool>,
+    chat: Setting<ChatSettings>,
+    ingest_pipeline: Setting<Vec<IngestProcessor>>,
+    document_schema: Setting<DocumentSchemaSettings>,
+    field_types: Setting<BTreeMap<String, FieldType>>,
+    generate_document_ids: Setting<bool>,
 }
 
 impl<'a, 't, 'i> Settings<'a, 't, 'i> {
@@ -223,10 +244,28 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             localized_attributes_rules: Setting::NotSet,
             prefix_search: Setting::NotSet,
             facet_search: Setting::NotSet,
+            chat: Setting::NotSet,
+            ingest_pipeline: Setting::NotSet,
+            document_schema: Setting::NotSet,
+            field_types: Setting::NotSet,
+            generate_document_ids: Setting::NotSet,
             indexer_config,
+            progress: Progress::default(),
+            uncommitted_writes: false,
         }
     }
 
+    /// Sets the progress on which the reindexing of the documents reports its steps.
+    pub fn set_progress(&mut self, progress: Progress) {
+        self.progress = progress;
+    }
+
+    /// Declares that documents or settings were already written in the transaction,
+    /// so that the documents are not reindexed from the committed state of the index.
+    pub fn set_uncommitted_writes(&mut self, uncommitted_writes: bool) {
+        self.uncommitted_writes = uncommitted_writes;
+    }
+
     pub fn reset_searchable_fields(&mut self) {
         self.searchable_fields = Setting::Reset;
     }
@@ -453,6 +492,46 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.facet_search = Setting::Reset;
     }
 
+    pub fn set_chat(&mut self, value: ChatSettings) {
+        self.chat = Setting::Set(value);
+    }
+
+    pub fn reset_chat(&mut self) {
+        self.chat = Setting::Reset;
+    }
+
+    pub fn set_ingest_pipeline(&mut self, value: Vec<IngestProcessor>) {
+        self.ingest_pipeline = Setting::Set(value);
+    }
+
+    pub fn reset_ingest_pipeline(&mut self) {
+        self.ingest_pipeline = Setting::Reset;
+    }
+
+    pub fn set_document_schema(&mut self, value: DocumentSchemaSettings) {
+        self.document_schema = Setting::Set(value);
+    }
+
+    pub fn reset_document_schema(&mut self) {
+        self.document_schema = Setting::Reset;
+    }
+
+    pub fn set_field_types(&mut self, value: BTreeMap<String, FieldType>) {
+        self.field_types = Setting::Set(value);
+    }
+
+    pub fn reset_field_types(&mut self) {
+        self.field_types = Setting::Reset;
+    }
+
+    pub fn set_generate_document_ids(&mut self, value: bool) {
+        self.generate_document_ids = Setting::Set(value);
+    }
+
+    pub fn reset_generate_document_ids(&mut self) {
+        self.generate_document_ids = Setting::Reset;
+    }
+
     #[tracing::instrument(
         level = "trace"
         skip(self, progress_callback, should_abort, settings_diff),
@@ -501,6 +580,38 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(())
     }
 
+    /// Extracts again, with the new indexer, only the fields affected by the settings change.
+    #[tracing::instrument(
+        level = "trace"
+        skip(self, should_abort, settings_diff),
+        target = "indexing::documents"
+    )]
+    fn reindex_affected_fields<FA>(
+        &mut self,
+        should_abort: &FA,
+        settings_diff: InnerIndexSettingsDiff,
+    ) -> Result<()>
+    where
+        FA: Fn() -> bool + Sync,
+    {
+        // if the settings are set before any document update, we don't need to do anything.
+        if self.index.number_of_documents(self.wtxn)? == 0 {
+            return Ok(());
+        }
+
+        indexer::reindex(
+            self.wtxn,
+            self.index,
+            &self.indexer_config.thread_pool,
+            self.indexer_config.grenad_parameters(),
+            &settings_diff,
+            should_abort,
+            &self.progress,
+        )?;
+
+        Ok(())
+    }
+
     fn update_displayed(&mut self) -> Result<bool> {
         match self.displayed_fields {
             Setting::Set(ref fields) => {
@@ -785,6 +896,46 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(())
     }
 
+    fn update_field_types(&mut self) -> Result<()> {
+        match self.field_types {
+            Setting::Set(ref field_types) => {
+                let reserved = [
+                    RESERVED_GEO_FIELD_NAME,
+                    RESERVED_VECTORS_FIELD_NAME,
+                    RESERVED_EXPIRES_AT_FIELD_NAME,
+                    RESERVED_VERSION_FIELD_NAME,
+                ];
+                if let Some(field) = field_types
+                    .keys()
+                    .find(|field| reserved.iter().any(|reserved| is_faceted_by(field, reserved)))
+                {
+                    return Err(
+                        UserError::InvalidSettingsFieldTypes { field: field.clone() }.into()
+                    );
+                }
+                self.index.put_field_types(self.wtxn, field_types)?;
+            }
+            Setting::Reset => {
+                self.index.delete_field_types(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+        Ok(())
+    }
+
+    fn update_generate_document_ids(&mut self) -> Result<()> {
+        match self.generate_document_ids {
+            Setting::Set(generate_document_ids) => {
+                self.index.put_generate_document_ids(self.wtxn, generate_document_ids)?;
+            }
+            Setting::Reset => {
+                self.index.delete_generate_document_ids(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+        Ok(())
+    }
+
     fn update_criteria(&mut self) -> Result<()> {
         match &self.criteria {
             Setting::Set(criteria) => {
@@ -802,6 +953,7 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         match self.primary_key {
             Setting::Set(ref primary_key) => {
                 if self.index.number_of_documents(self.wtxn)? == 0 {
+                    validate_primary_key(primary_key)?;
                     let mut fields_ids_map = self.index.fields_ids_map(self.wtxn)?;
                     fields_ids_map.insert(primary_key).ok_or(UserError::AttributeLimitReached)?;
                     self.index.put_fields_ids_map(self.wtxn, &fields_ids_map)?;
@@ -1005,6 +1157,54 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(changed)
     }
 
+    fn update_chat(&mut self) -> Result<bool> {
+        match std::mem::take(&mut self.chat) {
+            Setting::Set(settings) => {
+                let mut config = self.index.chat_config(self.wtxn)?;
+                settings.apply_to(&mut config)?;
+                self.index.put_chat_config(self.wtxn, &config)?;
+                Ok(true)
+            }
+            Setting::Reset => Ok(self.index.delete_chat_config(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_ingest_pipeline(&mut self) -> Result<bool> {
+        match std::mem::take(&mut self.ingest_pipeline) {
+            Setting::Set(processors) => {
+                IngestPipeline::new(&processors).map_err(|(index, message)| {
+                    UserError::InvalidSettingsIngestPipeline { index, message }
+                })?;
+                let old = self.index.ingest_pipeline(self.wtxn)?;
+                if old == processors {
+                    return Ok(false);
+                }
+                self.index.put_ingest_pipeline(self.wtxn, &processors)?;
+                Ok(true)
+            }
+            Setting::Reset => Ok(self.index.delete_ingest_pipeline(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_document_schema(&mut self) -> Result<bool> {
+        match std::mem::take(&mut self.document_schema) {
+            Setting::Set(document_schema) => {
+                DocumentSchema::new(&document_schema)
+                    .map_err(UserError::InvalidSettingsDocumentSchema)?;
+                let old = self.index.document_schema(self.wtxn)?;
+                if old.as_ref() == Some(&document_schema) {
+                    return Ok(false);
+                }
+                self.index.put_document_schema(self.wtxn, &document_schema)?;
+                Ok(true)
+            }
+            Setting::Reset => Ok(self.index.delete_document_schema(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
     fn update_facet_search(&mut self) -> Result<bool> {
         let changed = match self.facet_search {
             Setting::Set(new) => {
@@ -1029,8 +1229,11 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             Setting::Reset => {
                 // all vectors should be written back to documents
                 let old_configs = self.index.embedding_configs(self.wtxn)?;
+                // sparse vectors are kept in the documents, only their inverted index must go
+                self.index.vector_sparse.clear(self.wtxn)?;
                 let remove_all: Result<BTreeMap<String, EmbedderAction>> = old_configs
                     .into_iter()
+                    .filter(|config| !config.config.embedder_options.is_sparse())
                     .map(|IndexEmbeddingConfig { name, config, user_provided }| -> Result<_> {
                         let embedder_id =
                             self.index.embedder_category_id.get(self.wtxn, &name)?.ok_or(
@@ -1074,6 +1277,10 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             .collect();
         let mut updated_configs = BTreeMap::new();
         let mut embedder_actions = BTreeMap::new();
+        // sparse embedders are never reindexed by the indexer, as their vectors stay in the documents
+        let mut new_sparse_embedders = Vec::new();
+        // shadow embedders that replace the embedder they shadow at the end of this update
+        let mut promotions = Vec::new();
         for joined in old_configs
             .into_iter()
             .merge_join_by(configs.into_iter(), |(left, _), (right, _)| left.cmp(right))
@@ -1082,8 +1289,51 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                 // updated config
                 EitherOrBoth::Both((name, (old, user_provided)), (_, new)) => {
                     let was_quantized = old.binary_quantized.set().unwrap_or_default();
+                    let was_sparse = old.source == Setting::Set(EmbedderSource::UserProvidedSparse);
+                    // resetting `shadowOf` promotes the shadow embedder
+                    let promoted_over = match (&old.shadow_of, &new) {
+                        (
+                            Setting::Set(shadowed),
+                            Setting::Set(EmbeddingSettings { shadow_of: Setting::Reset, .. }),
+                        ) => Some(shadowed.clone()),
+                        _ => None,
+                    };
                     let settings_diff = SettingsDiff::from_settings(&name, old, new)?;
                     match settings_diff {
+                        SettingsDiff::Reindex { .. }
+                        | SettingsDiff::UpdateWithoutReindex { quantize: true, .. }
+                            if promoted_over.is_some() =>
+                        {
+                            return Err(UserError::InvalidSettingsEmbedder {
+                                embedder_name: name,
+                                message: "Cannot promote a shadow embedder while modifying parameters that require reindexing it.\n  - Hint: update the shadow embedder first, then promote it once its embeddings are complete.".to_string(),
+                            }
+                            .into());
+                        }
+                        SettingsDiff::Remove if was_sparse => {
+                            tracing::debug!(embedder = name, "removing sparse embedder");
+                            if let Some(embedder_id) =
+                                self.index.embedder_category_id.get(self.wtxn, &name)?
+                            {
+                                SparseVectorStore::new(self.index.vector_sparse, embedder_id)
+                                    .clear(self.wtxn)?;
+                                self.index.embedder_category_id.delete(self.wtxn, &name)?;
+                            }
+                        }
+                        SettingsDiff::Reindex { ref updated_settings, .. }
+                            if was_sparse
+                                || updated_settings.source
+                                    == Setting::Set(EmbedderSource::UserProvidedSparse) =>
+                        {
+                            return Err(UserError::InvalidSettingsEmbedder {
+                                embedder_name: name,
+                                message: format!(
+                                    "Cannot change the source from or to `{}`.\n  - Hint: remove the embedder and add it again with the new source.",
+                                    EmbedderSource::UserProvidedSparse
+                                ),
+                            }
+                            .into());
+                        }
                         SettingsDiff::Remove => {
                             tracing::debug!(
                                 embedder = name,
@@ -1137,7 +1387,11 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                                     EmbedderAction::default().with_is_being_quantized(true),
                                 );
                             }
-                            updated_configs.insert(name, (new, user_provided));
+                            if let Some(shadowed) = promoted_over {
+                                promotions.push((name, shadowed, new, user_provided));
+                            } else {
+                                updated_configs.insert(name, (new, user_provided));
+                            }
                         }
                     }
                 }
@@ -1155,14 +1409,42 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                         &mut setting,
                     );
                     let setting = validate_embedding_settings(setting, &name)?;
-                    embedder_actions.insert(
-                        name.clone(),
-                        EmbedderAction::with_reindex(ReindexAction::FullReindex, false),
-                    );
+                    if matches!(
+                        &setting,
+                        Setting::Set(EmbeddingSettings {
+                            source: Setting::Set(EmbedderSource::UserProvidedSparse),
+                            ..
+                        })
+                    ) {
+                        new_sparse_embedders.push(name.clone());
+                    } else {
+                        embedder_actions.insert(
+                            name.clone(),
+                            EmbedderAction::with_reindex(ReindexAction::FullReindex, false),
+                        );
+                    }
                     updated_configs.insert(name, (setting, RoaringBitmap::new()));
                 }
             }
         }
+        enum PromotingShadowEmbedder {}
+        let promotion_count = promotions.len() as u32;
+        for (i, (shadow, shadowed, setting, user_provided)) in promotions.into_iter().enumerate() {
+            self.progress.update_progress(VariableNameStep::<PromotingShadowEmbedder>::new(
+                format!("Promoting the shadow embedder `{shadow}` over `{shadowed}`"),
+                i as u32,
+                promotion_count,
+            ));
+            self.promote_shadow_embedder(
+                shadow,
+                shadowed,
+                setting,
+                user_provided,
+                &mut updated_configs,
+                &embedder_actions,
+            )?;
+        }
+        validate_shadow_embedders(&updated_configs)?;
         let mut free_indices: [bool; u8::MAX as usize] = [true; u8::MAX as usize];
         for res in self.index.embedder_category_id.iter(self.wtxn)? {
             let (_name, id) = res?;
@@ -1182,6 +1464,13 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                 self.index.embedder_category_id.put(self.wtxn, name, &id)?;
             }
         }
+        let mut new_sparse_embedder_ids = Vec::new();
+        for name in new_sparse_embedders {
+            let id = find_free_index().ok_or(UserError::TooManyEmbedders(updated_configs.len()))?;
+            tracing::debug!(embedder = name, id, "assigning free id to new sparse embedder");
+            self.index.embedder_category_id.put(self.wtxn, &name, &id)?;
+            new_sparse_embedder_ids.push((name, id));
+        }
         let updated_configs: Vec<IndexEmbeddingConfig> = updated_configs
             .into_iter()
             .filter_map(|(name, (config, user_provided))| match config {
@@ -1201,9 +1490,78 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         } else {
             self.index.put_embedding_configs(self.wtxn, updated_configs)?;
         }
+        for (name, id) in new_sparse_embedder_ids {
+            index_sparse_vectors_from_documents(self.index, self.wtxn, &name, id)?;
+        }
         Ok(embedder_actions)
     }
 
+    /// Replaces the `shadowed` embedder with its `shadow`, keeping the embeddings generated for the shadow.
+    ///
+    /// The shadow takes the name of the shadowed embedder, whose embeddings are deleted.
+    fn promote_shadow_embedder(
+        &mut self,
+        shadow: String,
+        shadowed: String,
+        setting: Setting<EmbeddingSettings>,
+        user_provided: RoaringBitmap,
+        updated_configs: &mut BTreeMap<String, (Setting<EmbeddingSettings>, RoaringBitmap)>,
+        embedder_actions: &BTreeMap<String, EmbedderAction>,
+    ) -> Result<()> {
+        let invalid = |message: String| UserError::InvalidSettingsEmbedder {
+            embedder_name: shadow.clone(),
+            message,
+        };
+        if embedder_actions.contains_key(&shadowed) {
+            return Err(invalid(format!(
+                "Cannot promote the shadow embedder while `{shadowed}` is modified in the same update."
+            ))
+            .into());
+        }
+        let Some((Setting::Set(shadowed_setting), shadowed_user_provided)) =
+            updated_configs.get(&shadowed)
+        else {
+            return Err(invalid(format!(
+                "Cannot promote the shadow embedder: the embedder `{shadowed}` it shadows does not exist."
+            ))
+            .into());
+        };
+        // user-provided embeddings are attached to the name of their embedder in the documents
+        if !user_provided.is_empty() || !shadowed_user_provided.is_empty() {
+            return Err(invalid(format!(
+                "Cannot promote the shadow embedder because `{shadow}` or `{shadowed}` has user-provided embeddings.\n  - Hint: remove the user-provided embeddings, or remove `{shadowed}` and rename the shadow embedder by adding it again."
+            ))
+            .into());
+        }
+
+        let missing_id = || crate::InternalError::DatabaseMissingEntry {
+            db_name: crate::index::db_name::VECTOR_EMBEDDER_CATEGORY_ID,
+            key: None,
+        };
+        let shadowed_id =
+            self.index.embedder_category_id.get(self.wtxn, &shadowed)?.ok_or_else(missing_id)?;
+        let shadow_id =
+            self.index.embedder_category_id.get(self.wtxn, &shadow)?.ok_or_else(missing_id)?;
+        tracing::debug!(embedder = shadow, shadowed, "promoting shadow embedder");
+
+        self.progress.update_progress(ShadowEmbedderPromotion::ClearingShadowedEmbeddings);
+        let was_quantized = shadowed_setting.binary_quantized.set().unwrap_or_default();
+        let shadowed_vectors =
+            ArroyWrapper::new(self.index.vector_arroy, shadowed_id, was_quantized);
+        match shadowed_vectors.dimensions(self.wtxn) {
+            Ok(dimensions) => shadowed_vectors.clear(self.wtxn, dimensions)?,
+            // the shadowed embedder has no embeddings
+            Err(arroy::Error::MissingMetadata(_)) => (),
+            Err(error) => return Err(error.into()),
+        }
+        // the id of the shadowed embedder is freed, the shadow keeps its id under its new name
+        self.progress.update_progress(ShadowEmbedderPromotion::RenamingShadowEmbedder);
+        self.index.embedder_category_id.delete(self.wtxn, &shadow)?;
+        self.index.embedder_category_id.put(self.wtxn, &shadowed, &shadow_id)?;
+        updated_configs.insert(shadowed, (setting, user_provided));
+        Ok(())
+    }
+
     fn update_search_cutoff(&mut self) -> Result<bool> {
         let changed = match self.search_cutoff {
             Setting::Set(new) => {
@@ -1244,9 +1602,82 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         FP: Fn(UpdateIndexingStep) + Sync,
         FA: Fn() -> bool + Sync,
     {
-        self.index.set_updated_at(self.wtxn, &OffsetDateTime::now_utc())?;
+        // The new indexer reads the documents from the committed state of the index,
+        // it can't be used if the documents or settings were already changed in this transaction.
+        let committed_state = !self.uncommitted_writes;
 
         let old_inner_settings = InnerIndexSettings::from_index(self.index, self.wtxn, None)?;
+        let inner_settings_diff = self.apply_settings(old_inner_settings)?;
+
+        if inner_settings_diff.any_reindexing_needed() {
+            if committed_state && inner_settings_diff.reindex_affected_fields_only() {
+                self.reindex_affected_fields(&should_abort, inner_settings_diff)?;
+            } else {
+                self.reindex(&progress_callback, &should_abort, inner_settings_diff)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Computes what applying the settings would change in the index, without reindexing it.
+    ///
+    /// The settings are written in the transaction to compute the difference,
+    /// the caller must abort the transaction once the estimate is computed.
+    /// The embedders are not instantiated, their changes are deduced from their configurations.
+    pub fn dry_run(mut self) -> Result<SettingsChangeEstimate> {
+        let old_inner_settings = InnerIndexSettings::from_index(
+            self.index,
+            self.wtxn,
+            Some(EmbeddingConfigs::default()),
+        )?;
+        let embedding_config_updates = self.write_settings()?;
+        let mut settings_diff = self.settings_diff(
+            old_inner_settings,
+            EmbeddingConfigs::default(),
+            embedding_config_updates,
+        )?;
+
+        // the prompts are generated again when the user-defined searchables change,
+        // the embedders being left empty in the settings compared above.
+        if settings_diff.cache_user_defined_searchables {
+            for IndexEmbeddingConfig { name, config, .. } in
+                self.index.embedding_configs(self.wtxn)?
+            {
+                if config.embedder_options.uses_document_template() {
+                    let was_quantized = config.quantized();
+                    settings_diff.embedding_config_updates.entry(name).or_insert_with(|| {
+                        EmbedderAction::with_reindex(
+                            ReindexAction::RegeneratePrompts,
+                            was_quantized,
+                        )
+                    });
+                }
+            }
+        }
+
+        SettingsChangeEstimate::new(self.wtxn, self.index, &settings_diff)
+    }
+
+    /// Writes the settings in the transaction and returns how they differ from the
+    /// `old_inner_settings`, the settings of the index before they are written.
+    fn apply_settings(
+        &mut self,
+        old_inner_settings: InnerIndexSettings,
+    ) -> Result<InnerIndexSettingsDiff> {
+        let embedding_config_updates = self.write_settings()?;
+
+        // the new embedders may have to regenerate embeddings, so they consult the persistent cache
+        let embedders = embedders(
+            self.index.embedding_configs(self.wtxn)?,
+            self.indexer_config.embedding_cache.as_ref(),
+        )?;
+        self.settings_diff(old_inner_settings, embedders, embedding_config_updates)
+    }
+
+    /// Writes the settings in the transaction and returns what must be done to the embeddings.
+    fn write_settings(&mut self) -> Result<BTreeMap<String, EmbedderAction>> {
+        self.index.set_updated_at(self.wtxn, &OffsetDateTime::now_utc())?;
 
         // never trigger re-indexing
         self.update_displayed()?;
@@ -1260,10 +1691,15 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.update_sort_facet_values_by()?;
         self.update_pagination_max_total_hits()?;
         self.update_search_cutoff()?;
+        self.update_chat()?;
+        self.update_ingest_pipeline()?;
+        self.update_document_schema()?;
+        self.update_generate_document_ids()?;
 
         // could trigger re-indexing
         self.update_filterable()?;
         self.update_sortable()?;
+        self.update_field_types()?;
         self.update_stop_words()?;
         self.update_non_separator_tokens()?;
         self.update_separator_tokens()?;
@@ -1277,9 +1713,18 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.update_localized_attributes_rules()?;
         self.update_disabled_typos_terms()?;
 
-        let embedding_config_updates = self.update_embedding_configs()?;
+        self.update_embedding_configs()
+    }
 
-        let mut new_inner_settings = InnerIndexSettings::from_index(self.index, self.wtxn, None)?;
+    /// Returns how the settings written in the transaction, with the `embedders`, differ from the `old_inner_settings`.
+    fn settings_diff(
+        &mut self,
+        old_inner_settings: InnerIndexSettings,
+        embedders: EmbeddingConfigs,
+        embedding_config_updates: BTreeMap<String, EmbedderAction>,
+    ) -> Result<InnerIndexSettingsDiff> {
+        let mut new_inner_settings =
+            InnerIndexSettings::from_index(self.index, self.wtxn, Some(embedders))?;
         new_inner_settings.recompute_searchables(self.wtxn, self.index)?;
 
         let primary_key_id = self
@@ -1287,19 +1732,13 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             .primary_key(self.wtxn)?
             .and_then(|name| new_inner_settings.fields_ids_map.id(name));
         let settings_update_only = true;
-        let inner_settings_diff = InnerIndexSettingsDiff::new(
+        Ok(InnerIndexSettingsDiff::new(
             old_inner_settings,
             new_inner_settings,
             primary_key_id,
             embedding_config_updates,
             settings_update_only,
-        );
-
-        if inner_settings_diff.any_reindexing_needed() {
-            self.reindex(&progress_callback, &should_abort, inner_settings_diff)?;
-        }
-
-        Ok(())
+        ))
     }
 }
 
@@ -1459,6 +1898,35 @@ impl InnerIndexSettingsDiff {
         }
     }
 
+    /// Returns how the words of the field `field_name`, possibly nested, must be reindexed.
+    pub fn reindex_searchable_field(&self, field_name: &str) -> Option<DelAddOperation> {
+        match &self.only_additional_fields {
+            Some(only_additional_fields) if self.only_additional_searchables() => {
+                only_additional_fields
+                    .iter()
+                    .any(|field| is_faceted_by(field_name, field))
+                    .then_some(DelAddOperation::Addition)
+            }
+            _ if self.reindex_searchable() => Some(DelAddOperation::DeletionAndAddition),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the searchable attributes only gained fields and nothing else changed
+    /// in the way the words are extracted, so that the words of the other fields can be kept.
+    pub fn only_additional_searchables(&self) -> bool {
+        self.only_additional_fields.is_some()
+            && !self.cache_reindex_searchable_without_user_defined
+            && !self.cache_exact_attributes
+    }
+
+    /// Returns `true` if the word pair proximities of the documents must be extracted again.
+    pub fn reindex_word_pair_proximities(&self) -> bool {
+        self.new.proximity_precision == ProximityPrecision::ByWord
+            && (self.cache_reindex_searchable_without_user_defined
+                || self.cache_user_defined_searchables)
+    }
+
     /// List the faceted fields from the inner fid map.
     /// This is used to list the faceted fields when we are reindexing,
     /// but it can't be used in document addition because the field id map must be exhaustive.
@@ -1476,49 +1944,110 @@ impl InnerIndexSettingsDiff {
             .collect()
     }
 
+    /// Joins the faceted fields of the old and new settings by field id.
+    fn faceted_fields(
+        &self,
+    ) -> impl Iterator<Item = EitherOrBoth<(FieldId, Metadata), (FieldId, Metadata)>> + '_ {
+        merge_join_by(
+            self.old
+                .fields_ids_map
+                .iter_id_metadata()
+                .filter(|(_, metadata)| metadata.is_faceted(&self.old.filterable_attributes_rules)),
+            self.new
+                .fields_ids_map
+                .iter_id_metadata()
+                .filter(|(_, metadata)| metadata.is_faceted(&self.new.filterable_attributes_rules)),
+            |(old_fid, _), (new_fid, _)| old_fid.cmp(new_fid),
+        )
+    }
+
     pub fn facet_fids_changed(&self) -> bool {
-        for eob in merge_join_by(
-            self.old.fields_ids_map.iter().filter(|(_, _, metadata)| {
-                metadata.is_faceted(&self.old.filterable_attributes_rules)
-            }),
-            self.new.fields_ids_map.iter().filter(|(_, _, metadata)| {
-                metadata.is_faceted(&self.new.filterable_attributes_rules)
-            }),
-            |(old_fid, _, _), (new_fid, _, _)| old_fid.cmp(new_fid),
-        ) {
-            match eob {
-                // If there is a difference, we need to reindex facet databases.
-                EitherOrBoth::Left(_) | EitherOrBoth::Right(_) => return true,
-                // If the field is faceted in both old and new settings, we check the facet-searchable and facet level database.
-                EitherOrBoth::Both((_, _, old_metadata), (_, _, new_metadata)) => {
-                    // Check if the field is facet-searchable in the old and new settings.
-                    // If there is a difference, we need to reindex facet-search database.
-                    let old_filterable_features = old_metadata
+        self.faceted_fields().any(|eob| match eob {
+            // If there is a difference, we need to reindex facet databases.
+            EitherOrBoth::Left(_) | EitherOrBoth::Right(_) => true,
+            // If the field is faceted in both old and new settings, we check the facet-searchable and facet level database.
+            // If the declared type of the field changed, its facet values must be converted again.
+            EitherOrBoth::Both((_, old_metadata), (_, new_metadata)) => {
+                self.facet_databases_changed_for(old_metadata, new_metadata)
+                    || old_metadata.field_type() != new_metadata.field_type()
+            }
+        })
+    }
+
+    /// Returns `true` if a field that stays faceted must be added to or removed from
+    /// the facet-search or facet level databases.
+    fn facet_databases_changed_for(&self, old_metadata: Metadata, new_metadata: Metadata) -> bool {
+        // Check if the field is facet-searchable in the old and new settings.
+        // If there is a difference, we need to reindex facet-search database.
+        let old_filterable_features =
+            old_metadata.filterable_attributes_features(&self.old.filterable_attributes_rules);
+        let new_filterable_features =
+            new_metadata.filterable_attributes_features(&self.new.filterable_attributes_rules);
+        let is_old_facet_searchable =
+            old_filterable_features.is_facet_searchable() && self.old.facet_search;
+        let is_new_facet_searchable =
+            new_filterable_features.is_facet_searchable() && self.new.facet_search;
+        if is_old_facet_searchable != is_new_facet_searchable {
+            return true;
+        }
+
+        // Check if the field needs a facet level database in the old and new settings.
+        // If there is a difference, we need to reindex facet level databases.
+        let old_facet_level_database =
+            old_metadata.require_facet_level_database(&self.old.filterable_attributes_rules);
+        let new_facet_level_database =
+            new_metadata.require_facet_level_database(&self.new.filterable_attributes_rules);
+        old_facet_level_database != new_facet_level_database
+    }
+
+    /// Returns the fields whose facet values are extracted differently with the new settings.
+    ///
+    /// It is the case of the fields that start or stop being faceted, and of the faceted fields
+    /// whose declared type or filterable features changed.
+    pub fn reindex_facet_fids(&self) -> BTreeSet<FieldId> {
+        self.faceted_fields()
+            .filter_map(|eob| match eob {
+                EitherOrBoth::Left((fid, _)) | EitherOrBoth::Right((fid, _)) => Some(fid),
+                EitherOrBoth::Both((fid, old_metadata), (_, new_metadata)) => {
+                    let old_features = old_metadata
                         .filterable_attributes_features(&self.old.filterable_attributes_rules);
-                    let new_filterable_features = new_metadata
+                    let new_features = new_metadata
                         .filterable_attributes_features(&self.new.filterable_attributes_rules);
-                    let is_old_facet_searchable =
-                        old_filterable_features.is_facet_searchable() && self.old.facet_search;
-                    let is_new_facet_searchable =
-                        new_filterable_features.is_facet_searchable() && self.new.facet_search;
-                    if is_old_facet_searchable != is_new_facet_searchable {
-                        return true;
-                    }
-
-                    // Check if the field needs a facet level database in the old and new settings.
-                    // If there is a difference, we need to reindex facet level databases.
-                    let old_facet_level_database = old_metadata
-                        .require_facet_level_database(&self.old.filterable_attributes_rules);
-                    let new_facet_level_database = new_metadata
-                        .require_facet_level_database(&self.new.filterable_attributes_rules);
-                    if old_facet_level_database != new_facet_level_database {
-                        return true;
-                    }
+                    (old_features != new_features
+                        || old_metadata.field_type() != new_metadata.field_type())
+                    .then_some(fid)
                 }
+            })
+            .collect()
+    }
+
+    /// Returns `true` if the settings change can be applied by only extracting again
+    /// the fields it affects, instead of reindexing all the documents.
+    ///
+    /// Changes that rebuild a whole database, like toggling the facet search or changing
+    /// the proximity precision, and changes to the embedders or to the geo fields still
+    /// require a full reindexing.
+    pub fn reindex_affected_fields_only(&self) -> bool {
+        let facet_databases_changed = self.faceted_fields().any(|eob| match eob {
+            EitherOrBoth::Both((_, old_metadata), (_, new_metadata)) => {
+                self.facet_databases_changed_for(old_metadata, new_metadata)
             }
-        }
+            EitherOrBoth::Left(_) | EitherOrBoth::Right(_) => false,
+        });
 
-        false
+        !self.reindex_vectors()
+            && !self.run_geo_indexing()
+            && !self.global_facet_settings_changed()
+            && !facet_databases_changed
+            && self.old.proximity_precision == self.new.proximity_precision
+            && self.old.prefix_search == self.new.prefix_search
+    }
+
+    /// Returns `true` if the declared type of the field changed.
+    pub fn field_type_changed(&self, field_id: FieldId) -> bool {
+        let old = self.old.fields_ids_map.metadata(field_id).and_then(|m| m.field_type());
+        let new = self.new.fields_ids_map.metadata(field_id).and_then(|m| m.field_type());
+        old != new
     }
 
     pub fn global_facet_settings_changed(&self) -> bool {
@@ -1544,6 +2073,318 @@ impl InnerIndexSettingsDiff {
     }
 }
 
+/// How the documents of an index are reindexed after a settings update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReindexStrategy {
+    /// The settings update doesn't require any reindexing.
+    None,
+    /// Only the fields affected by the settings update are extracted again.
+    AffectedFields,
+    /// All the documents are indexed again.
+    Full,
+}
+
+/// What a settings update changes in the embeddings of an embedder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmbedderChange {
+    /// All the embeddings are generated again.
+    FullReindex,
+    /// The embeddings of the documents whose rendered prompt changed are generated again.
+    RegeneratePrompts,
+    /// The embedder is removed, its user-provided embeddings are written back to the documents.
+    WriteBack,
+    /// The embeddings are kept and quantized.
+    Quantize,
+}
+
+/// The changes a settings update would make to an index, computed without reindexing it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SettingsChangeEstimate {
+    pub reindex_strategy: ReindexStrategy,
+    /// Fields that become searchable.
+    pub searchable_fields_added: Vec<String>,
+    /// Fields that are no longer searchable.
+    pub searchable_fields_removed: Vec<String>,
+    /// Whether the words of all the searchable fields must be extracted again,
+    /// e.g. because the stop words, separators, dictionary or exact attributes changed.
+    pub tokenization_changed: bool,
+    pub stop_words_added: Vec<String>,
+    pub stop_words_removed: Vec<String>,
+    /// Separators added to the tokenizer, including the default ones when they are first customized.
+    pub separators_added: Vec<String>,
+    pub separators_removed: Vec<String>,
+    pub dictionary_added: Vec<String>,
+    pub dictionary_removed: Vec<String>,
+    /// Fields on which typo tolerance becomes disabled.
+    pub exact_attributes_added: Vec<String>,
+    /// Fields on which typo tolerance is enabled again.
+    pub exact_attributes_removed: Vec<String>,
+    pub disabled_typos_terms_changed: bool,
+    /// Fields that become part of the facet databases.
+    pub faceted_fields_added: Vec<String>,
+    /// Fields that are no longer part of the facet databases.
+    pub faceted_fields_removed: Vec<String>,
+    /// Faceted fields whose filterable features or type changed.
+    pub faceted_fields_modified: Vec<String>,
+    pub sortable_fields_added: Vec<String>,
+    pub sortable_fields_removed: Vec<String>,
+    /// Fields that become used by an ascending or descending ranking rule.
+    pub ranking_rule_fields_added: Vec<String>,
+    /// Fields that are no longer used by an ascending or descending ranking rule.
+    pub ranking_rule_fields_removed: Vec<String>,
+    pub distinct_field_changed: bool,
+    pub proximity_precision_changed: bool,
+    pub prefix_search_changed: bool,
+    pub facet_search_changed: bool,
+    pub localized_attributes_changed: bool,
+    pub geo_changed: bool,
+    /// The embedders whose embeddings are modified, by name.
+    pub embedders: BTreeMap<String, EmbedderChange>,
+    /// The name of the databases that would be rebuilt, fully or partially.
+    pub rebuilt_databases: Vec<&'static str>,
+    /// An upper bound of the number of documents that would be reindexed.
+    pub estimated_documents_to_reprocess: u64,
+}
+
+impl SettingsChangeEstimate {
+    pub(crate) fn new(
+        rtxn: &heed::RoTxn<'_>,
+        index: &Index,
+        settings_diff: &InnerIndexSettingsDiff,
+    ) -> Result<Self> {
+        let InnerIndexSettingsDiff { old, new, .. } = settings_diff;
+
+        let reindex_strategy = if !settings_diff.any_reindexing_needed() {
+            ReindexStrategy::None
+        } else if settings_diff.reindex_affected_fields_only() {
+            ReindexStrategy::AffectedFields
+        } else {
+            ReindexStrategy::Full
+        };
+
+        let searchable_fields = |settings: &InnerIndexSettings| -> BTreeSet<String> {
+            settings
+                .fields_ids_map
+                .iter()
+                .filter(|(_, _, metadata)| metadata.is_searchable())
+                .map(|(_, name, _)| name.to_string())
+                .collect()
+        };
+        let old_searchable_fields = searchable_fields(old);
+        let new_searchable_fields = searchable_fields(new);
+        let searchable_fields_added: Vec<_> =
+            new_searchable_fields.difference(&old_searchable_fields).cloned().collect();
+        let searchable_fields_removed: Vec<_> =
+            old_searchable_fields.difference(&new_searchable_fields).cloned().collect();
+        let tokenization_changed = settings_diff.cache_reindex_searchable_without_user_defined
+            || settings_diff.cache_exact_attributes;
+
+        let stop_words = |settings: &InnerIndexSettings| -> Result<BTreeSet<String>> {
+            match &settings.stop_words {
+                Some(stop_words) => Ok(stop_words.stream().into_strs()?.into_iter().collect()),
+                None => Ok(BTreeSet::new()),
+            }
+        };
+        let (stop_words_added, stop_words_removed) =
+            added_and_removed(&stop_words(old)?, &stop_words(new)?);
+        let separators = |settings: &InnerIndexSettings| -> BTreeSet<String> {
+            match &settings.allowed_separators {
+                Some(separators) => separators.clone(),
+                None => {
+                    charabia::separators::DEFAULT_SEPARATORS.iter().map(|s| s.to_string()).collect()
+                }
+            }
+        };
+        let (separators_added, separators_removed) =
+            added_and_removed(&separators(old), &separators(new));
+        let (dictionary_added, dictionary_removed) = added_and_removed(
+            &old.dictionary.clone().unwrap_or_default(),
+            &new.dictionary.clone().unwrap_or_default(),
+        );
+        let exact_attributes = |settings: &InnerIndexSettings| -> BTreeSet<String> {
+            settings
+                .exact_attributes
+                .iter()
+                .filter_map(|&fid| settings.fields_ids_map.name(fid).map(String::from))
+                .collect()
+        };
+        let (exact_attributes_added, exact_attributes_removed) =
+            added_and_removed(&exact_attributes(old), &exact_attributes(new));
+        let disabled_typos_terms_changed = old.disabled_typos_terms != new.disabled_typos_terms;
+
+        let field_name = |settings: &InnerIndexSettings, fid: FieldId| {
+            settings.fields_ids_map.name(fid).map(String::from)
+        };
+        let mut faceted_fields_added = Vec::new();
+        let mut faceted_fields_removed = Vec::new();
+        let mut faceted_fields_modified = Vec::new();
+        for eob in settings_diff.faceted_fields() {
+            match eob {
+                EitherOrBoth::Left((fid, _)) => faceted_fields_removed.extend(field_name(old, fid)),
+                EitherOrBoth::Right((fid, _)) => faceted_fields_added.extend(field_name(new, fid)),
+                EitherOrBoth::Both((fid, old_metadata), (_, new_metadata)) => {
+                    let old_features = old_metadata
+                        .filterable_attributes_features(&old.filterable_attributes_rules);
+                    let new_features = new_metadata
+                        .filterable_attributes_features(&new.filterable_attributes_rules);
+                    if old_features != new_features
+                        || old_metadata.field_type() != new_metadata.field_type()
+                        || settings_diff.facet_databases_changed_for(old_metadata, new_metadata)
+                    {
+                        faceted_fields_modified.extend(field_name(new, fid));
+                    }
+                }
+            }
+        }
+
+        let (sortable_fields_added, sortable_fields_removed) = added_and_removed(
+            &old.sortable_fields.iter().cloned().collect(),
+            &new.sortable_fields.iter().cloned().collect(),
+        );
+        let (ranking_rule_fields_added, ranking_rule_fields_removed) = added_and_removed(
+            &old.asc_desc_fields.iter().cloned().collect(),
+            &new.asc_desc_fields.iter().cloned().collect(),
+        );
+        let distinct_field_changed = old.distinct_field != new.distinct_field;
+
+        let proximity_precision_changed = old.proximity_precision != new.proximity_precision;
+        let prefix_search_changed = old.prefix_search != new.prefix_search;
+        let facet_search_changed = old.facet_search != new.facet_search;
+        let localized_attributes_changed =
+            old.localized_attributes_rules != new.localized_attributes_rules;
+        let geo_changed = settings_diff.run_geo_indexing();
+
+        let embedders: BTreeMap<_, _> = settings_diff
+            .embedding_config_updates
+            .iter()
+            .filter_map(|(name, action)| {
+                let change = match (action.reindex(), action.write_back()) {
+                    (Some(ReindexAction::FullReindex), _) => EmbedderChange::FullReindex,
+                    (Some(ReindexAction::RegeneratePrompts), _) => {
+                        EmbedderChange::RegeneratePrompts
+                    }
+                    (None, Some(_)) => EmbedderChange::WriteBack,
+                    (None, None) if action.is_being_quantized() => EmbedderChange::Quantize,
+                    (None, None) => return None,
+                };
+                Some((name.clone(), change))
+            })
+            .collect();
+
+        let mut rebuilt_databases = Vec::new();
+        if settings_diff.reindex_searchable() {
+            rebuilt_databases.extend([
+                db_name::WORD_DOCIDS,
+                db_name::EXACT_WORD_DOCIDS,
+                db_name::WORD_FIELD_ID_DOCIDS,
+                db_name::WORD_POSITION_DOCIDS,
+                db_name::FIELD_ID_WORD_COUNT_DOCIDS,
+            ]);
+        }
+        if settings_diff.reindex_word_pair_proximities() || proximity_precision_changed {
+            rebuilt_databases.push(db_name::WORD_PAIR_PROXIMITY_DOCIDS);
+        }
+        if settings_diff.reindex_searchable() || prefix_search_changed {
+            rebuilt_databases.extend([
+                db_name::WORD_PREFIX_DOCIDS,
+                db_name::EXACT_WORD_PREFIX_DOCIDS,
+                db_name::WORD_PREFIX_POSITION_DOCIDS,
+                db_name::WORD_PREFIX_FIELD_ID_DOCIDS,
+            ]);
+        }
+        if settings_diff.reindex_facets() || geo_changed {
+            rebuilt_databases.extend([
+                db_name::FACET_ID_F64_DOCIDS,
+                db_name::FACET_ID_STRING_DOCIDS,
+                db_name::FACET_ID_EXISTS_DOCIDS,
+                db_name::FACET_ID_IS_NULL_DOCIDS,
+                db_name::FACET_ID_IS_EMPTY_DOCIDS,
+                db_name::FIELD_ID_DOCID_FACET_F64S,
+                db_name::FIELD_ID_DOCID_FACET_STRINGS,
+            ]);
+        }
+        if settings_diff.reindex_facets() && (new.facet_search || old.facet_search) {
+            rebuilt_databases.extend([
+                db_name::FACET_ID_NORMALIZED_STRING_STRINGS,
+                db_name::FACET_ID_STRING_FST,
+            ]);
+        }
+        if !embedders.is_empty() {
+            rebuilt_databases.push(db_name::VECTOR_ARROY);
+        }
+
+        let number_of_documents = index.number_of_documents(rtxn)?;
+        let estimated_documents_to_reprocess = match reindex_strategy {
+            ReindexStrategy::None => 0,
+            ReindexStrategy::Full => number_of_documents,
+            ReindexStrategy::AffectedFields => {
+                let mut affected_fields: BTreeSet<&str> = searchable_fields_added
+                    .iter()
+                    .chain(&searchable_fields_removed)
+                    .chain(&faceted_fields_added)
+                    .chain(&faceted_fields_removed)
+                    .chain(&faceted_fields_modified)
+                    .map(String::as_str)
+                    .collect();
+                if settings_diff.reindex_searchable()
+                    && !settings_diff.only_additional_searchables()
+                {
+                    affected_fields.extend(
+                        old_searchable_fields
+                            .iter()
+                            .chain(&new_searchable_fields)
+                            .map(String::as_str),
+                    );
+                }
+
+                // Only the top-level fields are counted in the field distribution.
+                let field_distribution = index.field_distribution(rtxn)?;
+                let top_level_fields: BTreeSet<_> = affected_fields
+                    .into_iter()
+                    .map(|name| name.split('.').next().unwrap_or(name))
+                    .collect();
+                let documents: u64 = top_level_fields
+                    .into_iter()
+                    .filter_map(|name| field_distribution.get(name))
+                    .sum();
+                documents.min(number_of_documents)
+            }
+        };
+
+        Ok(SettingsChangeEstimate {
+            reindex_strategy,
+            searchable_fields_added,
+            searchable_fields_removed,
+            tokenization_changed,
+            stop_words_added,
+            stop_words_removed,
+            separators_added,
+            separators_removed,
+            dictionary_added,
+            dictionary_removed,
+            exact_attributes_added,
+            exact_attributes_removed,
+            disabled_typos_terms_changed,
+            faceted_fields_added,
+            faceted_fields_removed,
+            faceted_fields_modified,
+            sortable_fields_added,
+            sortable_fields_removed,
+            ranking_rule_fields_added,
+            ranking_rule_fields_removed,
+            distinct_field_changed,
+            proximity_precision_changed,
+            prefix_search_changed,
+            facet_search_changed,
+            localized_attributes_changed,
+            geo_changed,
+            embedders,
+            rebuilt_databases,
+            estimated_documents_to_reprocess,
+        })
+    }
+}
+
 #[derive(Clone)]
 pub(crate) struct InnerIndexSettings {
     pub stop_words: Option<fst::Set<Vec<u8>>>,
@@ -1580,7 +2421,7 @@ impl InnerIndexSettings {
         let proximity_precision = index.proximity_precision(rtxn)?.unwrap_or_default();
         let embedding_configs = match embedding_configs {
             Some(embedding_configs) => embedding_configs,
-            None => embedders(index.embedding_configs(rtxn)?)?,
+            None => embedders(index.embedding_configs(rtxn)?, None)?,
         };
         let prefix_search = index.prefix_search(rtxn)?.unwrap_or_default();
         let facet_search = index.facet_search(rtxn)?;
@@ -1662,20 +2503,23 @@ impl InnerIndexSettings {
     }
 }
 
-fn embedders(embedding_configs: Vec<IndexEmbeddingConfig>) -> Result<EmbeddingConfigs> {
+fn embedders(
+    embedding_configs: Vec<IndexEmbeddingConfig>,
+    persistent_cache: Option<&PersistentEmbeddingCache>,
+) -> Result<EmbeddingConfigs> {
     let res: Result<_> = embedding_configs
         .into_iter()
         .map(
             |IndexEmbeddingConfig {
                  name,
-                 config: EmbeddingConfig { embedder_options, prompt, quantized },
+                 config: EmbeddingConfig { embedder_options, prompt, quantized, .. },
                  ..
              }| {
                 let prompt = Arc::new(prompt.try_into().map_err(crate::Error::from)?);
 
                 let embedder = Arc::new(
                     // cache_cap: no cache needed for indexing purposes
-                    Embedder::new(embedder_options.clone(), 0)
+                    Embedder::new(embedder_options.clone(), 0, persistent_cache)
                         .map_err(crate::vector::Error::from)
                         .map_err(crate::Error::from)?,
                 );
@@ -1686,6 +2530,11 @@ fn embedders(embedding_configs: Vec<IndexEmbeddingConfig>) -> Result<EmbeddingCo
     res.map(EmbeddingConfigs::new)
 }
 
+/// Returns the values that are only in `new` and the ones that are only in `old`.
+fn added_and_removed(old: &BTreeSet<String>, new: &BTreeSet<String>) -> (Vec<String>, Vec<String>) {
+    (new.difference(old).cloned().collect(), old.difference(new).cloned().collect())
+}
+
 fn validate_prompt(
     name: &str,
     new_prompt: Setting<String>,
@@ -1717,6 +2566,48 @@ fn validate_prompt(
     }
 }
 
+make_enum_progress! {
+    pub enum ShadowEmbedderPromotion {
+        ClearingShadowedEmbeddings,
+        RenamingShadowEmbedder,
+    }
+}
+
+/// Checks that every shadow embedder shadows an existing embedder that is not a shadow itself.
+fn validate_shadow_embedders(
+    configs: &BTreeMap<String, (Setting<EmbeddingSettings>, RoaringBitmap)>,
+) -> Result<()> {
+    for (name, (setting, _)) in configs {
+        let Setting::Set(EmbeddingSettings { source, shadow_of: Setting::Set(shadowed), .. }) =
+            setting
+        else {
+            continue;
+        };
+        let message = if *source == Setting::Set(EmbedderSource::UserProvidedSparse) {
+            format!(
+                "`shadowOf` is not available for source `{}`.",
+                EmbedderSource::UserProvidedSparse
+            )
+        } else if shadowed == name {
+            "An embedder cannot shadow itself.".to_string()
+        } else {
+            match configs.get(shadowed) {
+                Some((Setting::Set(EmbeddingSettings { shadow_of: Setting::Set(_), .. }), _)) => {
+                    format!("Cannot shadow `{shadowed}`, as it is itself a shadow embedder.")
+                }
+                Some((Setting::Set(_), _)) => continue,
+                _ => format!(
+                    "The embedder `{shadowed}` shadowed by this embedder does not exist.\n  - Hint: promote or remove the shadow embedder before removing `{shadowed}`."
+                ),
+            }
+        };
+        return Err(
+            UserError::InvalidSettingsEmbedder { embedder_name: name.clone(), message }.into()
+        );
+    }
+    Ok(())
+}
+
 pub fn validate_embedding_settings(
     settings: Setting<EmbeddingSettings>,
     name: &str,
@@ -1727,6 +2618,7 @@ pub fn validate_embedding_settings(
         model,
         revision,
         pooling,
+        normalize,
         api_key,
         dimensions,
         document_template,
@@ -1738,7 +2630,11 @@ pub fn validate_embedding_settings(
         mut indexing_embedder,
         distribution,
         headers,
+        max_concurrent_requests,
+        requests_per_minute,
+        tokens_per_minute,
         binary_quantized: binary_quantize,
+        shadow_of,
     } = settings;
 
     let document_template = validate_prompt(name, document_template, document_template_max_bytes)?;
@@ -1750,6 +2646,20 @@ pub fn validate_embedding_settings(
         .into());
     }
 
+    for (field, value) in [
+        ("maxConcurrentRequests", max_concurrent_requests.set().map(|value| value as u64)),
+        ("requestsPerMinute", requests_per_minute.set()),
+        ("tokensPerMinute", tokens_per_minute.set()),
+    ] {
+        if let Some(0) = value {
+            return Err(crate::error::UserError::InvalidSettingsEmbedderRateLimit {
+                embedder_name: name.to_owned(),
+                field,
+            }
+            .into());
+        }
+    }
+
     if let Some(url) = url.as_ref().set() {
         url::Url::parse(url).map_err(|error| crate::error::UserError::InvalidUrl {
             embedder_name: name.to_owned(),
@@ -1774,6 +2684,7 @@ pub fn validate_embedding_settings(
             model,
             revision,
             pooling,
+            normalize,
             api_key,
             dimensions,
             document_template,
@@ -1785,7 +2696,11 @@ pub fn validate_embedding_settings(
             indexing_embedder,
             distribution,
             headers,
+            max_concurrent_requests,
+            requests_per_minute,
+            tokens_per_minute,
             binary_quantized: binary_quantize,
+            shadow_of,
         }));
     };
     EmbeddingSettings::check_settings(
@@ -1795,6 +2710,7 @@ pub fn validate_embedding_settings(
         &model,
         &revision,
         &pooling,
+        &normalize,
         &dimensions,
         &api_key,
         &url,
@@ -1803,6 +2719,9 @@ pub fn validate_embedding_settings(
         &document_template,
         &document_template_max_bytes,
         &headers,
+        &max_concurrent_requests,
+        &requests_per_minute,
+        &tokens_per_minute,
         &search_embedder,
         &indexing_embedder,
         &binary_quantize,
@@ -1843,7 +2762,10 @@ pub fn validate_embedding_settings(
         EmbedderSource::Ollama
         | EmbedderSource::HuggingFace
         | EmbedderSource::UserProvided
-        | EmbedderSource::Rest => {}
+        | EmbedderSource::UserProvidedSparse
+        | EmbedderSource::UserProvidedMultiVector
+        | EmbedderSource::Rest
+        | EmbedderSource::Onnx => {}
         EmbedderSource::Composite => {
             if let Setting::Set(embedder) = &search_embedder {
                 if let Some(source) = embedder.source.set() {
@@ -1873,6 +2795,7 @@ pub fn validate_embedding_settings(
                         &embedder.model,
                         &embedder.revision,
                         &embedder.pooling,
+                        &embedder.normalize,
                         &embedder.dimensions,
                         &embedder.api_key,
                         &embedder.url,
@@ -1881,6 +2804,9 @@ pub fn validate_embedding_settings(
                         &embedder.document_template,
                         &embedder.document_template_max_bytes,
                         &embedder.headers,
+                        &embedder.max_concurrent_requests,
+                        &embedder.requests_per_minute,
+                        &embedder.tokens_per_minute,
                         &search_embedder,
                         &indexing_embedder,
                         &embedder.binary_quantized,
@@ -1928,6 +2854,7 @@ pub fn validate_embedding_settings(
                         &embedder.model,
                         &embedder.revision,
                         &embedder.pooling,
+                        &embedder.normalize,
                         &embedder.dimensions,
                         &embedder.api_key,
                         &embedder.url,
@@ -1936,6 +2863,9 @@ pub fn validate_embedding_settings(
                         &embedder.document_template,
                         &embedder.document_template_max_bytes,
                         &embedder.headers,
+                        &embedder.max_concurrent_requests,
+                        &embedder.requests_per_minute,
+                        &embedder.tokens_per_minute,
                         &search_embedder,
                         &indexing_embedder,
                         &embedder.binary_quantized,
@@ -1958,6 +2888,7 @@ pub fn validate_embedding_settings(
         model,
         revision,
         pooling,
+        normalize,
         api_key,
         dimensions,
         document_template,
@@ -1969,7 +2900,11 @@ pub fn validate_embedding_settings(
         indexing_embedder,
         distribution,
         headers,
+        max_concurrent_requests,
+        requests_per_minute,
+        tokens_per_minute,
         binary_quantized: binary_quantize,
+        shadow_of,
     }))
 }
 
@@ -897,6 +897,11 @@ fn test_correct_settings_init() {
                 prefix_search,
                 facet_search,
                 disable_on_numbers,
+                chat,
+                ingest_pipeline,
+                document_schema,
+                field_types,
+                generate_document_ids,
             } = settings;
             assert!(matches!(searchable_fields, Setting::NotSet));
             assert!(matches!(displayed_fields, Setting::NotSet));
@@ -925,6 +930,11 @@ fn test_correct_settings_init() {
             assert!(matches!(prefix_search, Setting::NotSet));
             assert!(matches!(facet_search, Setting::NotSet));
             assert!(matches!(disable_on_numbers, Setting::NotSet));
+            assert!(matches!(chat, Setting::NotSet));
+            assert!(matches!(ingest_pipeline, Setting::NotSet));
+            assert!(matches!(document_schema, Setting::NotSet));
+            assert!(matches!(field_types, Setting::NotSet));
+            assert!(matches!(generate_document_ids, Setting::NotSet));
         })
         .unwrap();
 }
@@ -956,3 +966,131 @@ fn settings_must_ignore_soft_deleted() {
     let docs = docs.unwrap();
     assert_eq!(docs.len(), 5);
 }
+
+#[test]
+fn reindex_only_the_affected_fields() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|settings| {
+            settings.set_searchable_fields(vec![S("title")]);
+        })
+        .unwrap();
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "Carol", "genre": "romance" },
+            { "id": 1, "title": "Wonder Woman", "genre": "action" },
+            { "id": 2, "title": "Shazam", "genre": "action" }
+        ]))
+        .unwrap();
+
+    // A newly filterable field can be filtered on.
+    index
+        .update_settings(|settings| {
+            settings.set_filterable_fields(vec![FilterableAttributesRule::Field(S("genre"))]);
+        })
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+    let filter = Filter::from_str("genre = action").unwrap().unwrap();
+    let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+    assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![1, 2]);
+    drop(rtxn);
+
+    // A newly searchable field can be searched, the others are still searchable.
+    index
+        .update_settings(|settings| {
+            settings.set_searchable_fields(vec![S("title"), S("genre")]);
+        })
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+    let SearchResult { documents_ids, .. } =
+        index.search(&rtxn).query("romance").execute().unwrap();
+    assert_eq!(documents_ids, vec![0]);
+    let SearchResult { documents_ids, .. } = index.search(&rtxn).query("shazam").execute().unwrap();
+    assert_eq!(documents_ids, vec![2]);
+    drop(rtxn);
+
+    // A field that is no longer searchable nor filterable is removed from the databases.
+    index
+        .update_settings(|settings| {
+            settings.set_searchable_fields(vec![S("title")]);
+            settings.reset_filterable_fields();
+        })
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+    let SearchResult { documents_ids, .. } =
+        index.search(&rtxn).query("romance").execute().unwrap();
+    assert_eq!(documents_ids, Vec::<u32>::new());
+    let filter = Filter::from_str("genre = action").unwrap().unwrap();
+    let _ = filter.evaluate(&rtxn, &index).unwrap_err();
+    let genre = index.fields_ids_map(&rtxn).unwrap().id("genre").unwrap();
+    let count = index
+        .facet_id_string_docids
+        .remap_key_type::<Bytes>()
+        .prefix_iter(&rtxn, &genre.to_be_bytes())
+        .unwrap()
+        .count();
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn dry_run_settings_change() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|settings| {
+            settings.set_searchable_fields(vec![S("title")]);
+        })
+        .unwrap();
+    index
+        .add_documents(documents!([
+            { "id": 0, "title": "Carol", "genre": "romance" },
+            { "id": 1, "title": "Wonder Woman", "genre": "action" },
+            { "id": 2, "title": "Shazam" }
+        ]))
+        .unwrap();
+
+    let mut wtxn = index.write_txn().unwrap();
+    let mut builder = Settings::new(&mut wtxn, &index.inner, &index.indexer_config);
+    builder.set_filterable_fields(vec![FilterableAttributesRule::Field(S("genre"))]);
+    let estimate = builder.dry_run().unwrap();
+    wtxn.abort();
+
+    assert_eq!(estimate.reindex_strategy, ReindexStrategy::AffectedFields);
+    assert_eq!(estimate.faceted_fields_added, vec![S("genre")]);
+    assert!(estimate.searchable_fields_added.is_empty());
+    assert!(estimate.embedders.is_empty());
+    assert!(estimate.rebuilt_databases.contains(&crate::index::db_name::FACET_ID_STRING_DOCIDS));
+    assert!(!estimate.rebuilt_databases.contains(&crate::index::db_name::WORD_DOCIDS));
+    assert_eq!(estimate.estimated_documents_to_reprocess, 2);
+
+    // the settings are left untouched
+    let rtxn = index.read_txn().unwrap();
+    assert!(index.filterable_attributes_rules(&rtxn).unwrap().is_empty());
+    drop(rtxn);
+
+    let mut wtxn = index.write_txn().unwrap();
+    let mut builder = Settings::new(&mut wtxn, &index.inner, &index.indexer_config);
+    builder.set_proximity_precision(ProximityPrecision::ByAttribute);
+    let estimate = builder.dry_run().unwrap();
+    wtxn.abort();
+
+    assert_eq!(estimate.reindex_strategy, ReindexStrategy::Full);
+    assert!(estimate.proximity_precision_changed);
+    assert_eq!(estimate.estimated_documents_to_reprocess, 3);
+
+    let mut wtxn = index.write_txn().unwrap();
+    let mut builder = Settings::new(&mut wtxn, &index.inner, &index.indexer_config);
+    builder.set_stop_words(btreeset! { S("the") });
+    builder.set_searchable_fields(vec![S("title"), S("genre")]);
+    let estimate = builder.dry_run().unwrap();
+    wtxn.abort();
+
+    assert_eq!(estimate.reindex_strategy, ReindexStrategy::Full);
+    assert_eq!(estimate.stop_words_added, vec![S("the")]);
+    assert_eq!(estimate.searchable_fields_added, vec![S("genre")]);
+    assert!(estimate.tokenization_changed);
+}
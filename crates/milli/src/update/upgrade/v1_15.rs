@@ -1,7 +1,14 @@
 use heed::RwTxn;
+use roaring::RoaringBitmap;
 
 use super::UpgradeIndex;
+use crate::constants::RESERVED_EXPIRES_AT_FIELD_NAME;
+use crate::facet::FacetType;
+use crate::heed_codec::facet::{FacetGroupKey, FacetGroupKeyCodec, FacetGroupValue};
+use crate::heed_codec::BytesRefCodec;
 use crate::progress::Progress;
+use crate::search::facet::get_first_facet_value;
+use crate::update::facet::bulk::FacetsUpdateBulk;
 use crate::update::new::indexer::recompute_word_fst_from_word_docids_database;
 use crate::{make_enum_progress, Index, Result};
 
@@ -26,6 +33,15 @@ impl UpgradeIndex for Latest_V1_14_To_Latest_V1_15 {
         progress.update_progress(TypoTolerance::RecomputeWordFst);
         recompute_word_fst_from_word_docids_database(index, wtxn)?;
 
+        make_enum_progress! {
+            enum DocumentsExpiration {
+                IndexExpirationDates,
+            }
+        };
+
+        progress.update_progress(DocumentsExpiration::IndexExpirationDates);
+        index_expiration_dates(index, wtxn)?;
+
         Ok(false)
     }
 
@@ -33,3 +49,55 @@ impl UpgradeIndex for Latest_V1_14_To_Latest_V1_15 {
         (1, 15, 0)
     }
 }
+
+/// Adds the `_expiresAt` field of the existing documents to the facet databases.
+///
+/// The field is now always faceted so that the expired documents can be found, but the
+/// documents indexed before it became a reserved field were only faceted if it was filterable.
+fn index_expiration_dates(index: &Index, wtxn: &mut RwTxn) -> Result<()> {
+    let fields_ids_map = index.fields_ids_map(wtxn)?;
+    // stores the field id of `_expiresAt` on its own
+    index.put_fields_ids_map(wtxn, &fields_ids_map)?;
+
+    let Some(fid) = fields_ids_map.id(RESERVED_EXPIRES_AT_FIELD_NAME) else {
+        return Ok(());
+    };
+
+    // the field was already faceted by the settings
+    let db = index.facet_id_f64_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>();
+    if get_first_facet_value::<BytesRefCodec, _>(wtxn, db, fid)?.is_some() {
+        return Ok(());
+    }
+
+    let mut expiration_dates = Vec::new();
+    for result in index.all_documents(wtxn)? {
+        let (docid, document) = result?;
+        let date = document
+            .get(fid)
+            .and_then(|value| serde_json::from_slice::<serde_json::Value>(value).ok())
+            .and_then(|value| value.as_f64());
+        if let Some(date) = date {
+            expiration_dates.push((date, docid));
+        }
+    }
+
+    if expiration_dates.is_empty() {
+        return Ok(());
+    }
+
+    expiration_dates.sort_unstable_by(|(a, _), (b, _)| a.total_cmp(b));
+    let mut exists_docids = index.facet_id_exists_docids.get(wtxn, &fid)?.unwrap_or_default();
+    for group in expiration_dates.chunk_by(|(a, _), (b, _)| a == b) {
+        let date = group[0].0;
+        let bitmap: RoaringBitmap = group.iter().map(|(_, docid)| *docid).collect();
+        for docid in &bitmap {
+            index.field_id_docid_facet_f64s.put(wtxn, &(fid, docid, date), &())?;
+        }
+        exists_docids |= &bitmap;
+        let key = FacetGroupKey { field_id: fid, level: 0, left_bound: date };
+        index.facet_id_f64_docids.put(wtxn, &key, &FacetGroupValue { size: 1, bitmap })?;
+    }
+    index.facet_id_exists_docids.put(wtxn, &fid, &exists_docids)?;
+
+    FacetsUpdateBulk::new_not_updating_level_0(index, vec![fid], FacetType::Number).execute(wtxn)
+}
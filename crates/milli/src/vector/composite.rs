@@ -3,9 +3,10 @@ use std::time::Instant;
 use arroy::Distance;
 
 use super::error::CompositeEmbedderContainsHuggingFace;
+use super::persistent_cache::CacheScope;
 use super::{
-    hf, manual, ollama, openai, rest, DistributionShift, EmbedError, Embedding, EmbeddingCache,
-    NewEmbedderError,
+    hf, manual, ollama, onnx, openai, rest, DistributionShift, EmbedError, EmbedderInput,
+    Embedding, EmbeddingCache, NewEmbedderError,
 };
 use crate::ThreadPoolNoAbort;
 
@@ -21,6 +22,8 @@ pub enum SubEmbedder {
     Ollama(ollama::Embedder),
     /// An embedder based on making embedding queries against a generic JSON/REST embedding server.
     Rest(rest::Embedder),
+    /// An embedder based on running local models in the ONNX format with ONNX Runtime.
+    Onnx(onnx::Embedder),
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
@@ -30,9 +33,21 @@ pub enum SubEmbedderOptions {
     Ollama(ollama::EmbedderOptions),
     UserProvided(manual::EmbedderOptions),
     Rest(rest::EmbedderOptions),
+    Onnx(onnx::EmbedderOptions),
 }
 
 impl SubEmbedderOptions {
+    pub fn uses_document_template(&self) -> bool {
+        match self {
+            SubEmbedderOptions::HuggingFace(_)
+            | SubEmbedderOptions::OpenAi(_)
+            | SubEmbedderOptions::Ollama(_)
+            | SubEmbedderOptions::Rest(_)
+            | SubEmbedderOptions::Onnx(_) => true,
+            SubEmbedderOptions::UserProvided(_) => false,
+        }
+    }
+
     pub fn distribution(&self) -> Option<DistributionShift> {
         match self {
             SubEmbedderOptions::HuggingFace(embedder_options) => embedder_options.distribution,
@@ -40,6 +55,19 @@ impl SubEmbedderOptions {
             SubEmbedderOptions::Ollama(embedder_options) => embedder_options.distribution,
             SubEmbedderOptions::UserProvided(embedder_options) => embedder_options.distribution,
             SubEmbedderOptions::Rest(embedder_options) => embedder_options.distribution,
+            SubEmbedderOptions::Onnx(embedder_options) => embedder_options.distribution,
+        }
+    }
+
+    /// The options changing the embeddings produced by the embedder, `None` if it does not cache its embeddings.
+    pub fn cache_scope(&self) -> Option<CacheScope<'_>> {
+        match self {
+            SubEmbedderOptions::HuggingFace(options) => Some(options.cache_scope()),
+            SubEmbedderOptions::OpenAi(options) => Some(options.cache_scope()),
+            SubEmbedderOptions::Ollama(options) => Some(options.cache_scope()),
+            SubEmbedderOptions::Rest(options) => Some(options.cache_scope()),
+            SubEmbedderOptions::Onnx(options) => Some(options.cache_scope()),
+            SubEmbedderOptions::UserProvided(_) => None,
         }
     }
 }
@@ -143,6 +171,9 @@ impl SubEmbedder {
                 cache_cap,
                 rest::ConfigurationSource::User,
             )?),
+            SubEmbedderOptions::Onnx(options) => {
+                Self::Onnx(onnx::Embedder::new(options, cache_cap)?)
+            }
         })
     }
 
@@ -157,6 +188,7 @@ impl SubEmbedder {
             SubEmbedder::Ollama(embedder) => embedder.embed(&texts, deadline),
             SubEmbedder::UserProvided(embedder) => embedder.embed(&texts),
             SubEmbedder::Rest(embedder) => embedder.embed(texts, deadline),
+            SubEmbedder::Onnx(embedder) => embedder.embed(texts),
         }
     }
 
@@ -178,38 +210,52 @@ impl SubEmbedder {
                 .embed_ref(&[text], deadline)?
                 .pop()
                 .ok_or_else(EmbedError::missing_embedding),
+            SubEmbedder::Onnx(embedder) => embedder.embed_one(text),
         }
     }
 
-    /// Embed multiple chunks of texts.
-    ///
-    /// Each chunk is composed of one or multiple texts.
-    pub fn embed_index(
+    pub fn embed_media(
         &self,
-        text_chunks: Vec<Vec<String>>,
-        threads: &ThreadPoolNoAbort,
-    ) -> std::result::Result<Vec<Vec<Embedding>>, EmbedError> {
+        text: &str,
+        media: &serde_json::Value,
+        deadline: Option<Instant>,
+    ) -> std::result::Result<Embedding, EmbedError> {
         match self {
-            SubEmbedder::HuggingFace(embedder) => embedder.embed_index(text_chunks),
-            SubEmbedder::OpenAi(embedder) => embedder.embed_index(text_chunks, threads),
-            SubEmbedder::Ollama(embedder) => embedder.embed_index(text_chunks, threads),
-            SubEmbedder::UserProvided(embedder) => embedder.embed_index(text_chunks),
-            SubEmbedder::Rest(embedder) => embedder.embed_index(text_chunks, threads),
+            SubEmbedder::Rest(embedder) => embedder.embed_media(text, media, deadline),
+            SubEmbedder::HuggingFace(_)
+            | SubEmbedder::OpenAi(_)
+            | SubEmbedder::Ollama(_)
+            | SubEmbedder::UserProvided(_)
+            | SubEmbedder::Onnx(_) => Err(EmbedError::media_not_supported()),
         }
     }
 
-    /// Non-owning variant of [`Self::embed_index`].
+    /// Embed multiple inputs.
     pub fn embed_index_ref(
         &self,
-        texts: &[&str],
+        inputs: &[EmbedderInput<'_>],
         threads: &ThreadPoolNoAbort,
     ) -> std::result::Result<Vec<Embedding>, EmbedError> {
+        let texts = || inputs.iter().map(EmbedderInput::text).collect::<Vec<_>>();
         match self {
-            SubEmbedder::HuggingFace(embedder) => embedder.embed_index_ref(texts),
-            SubEmbedder::OpenAi(embedder) => embedder.embed_index_ref(texts, threads),
-            SubEmbedder::Ollama(embedder) => embedder.embed_index_ref(texts, threads),
-            SubEmbedder::UserProvided(embedder) => embedder.embed_index_ref(texts),
-            SubEmbedder::Rest(embedder) => embedder.embed_index_ref(texts, threads),
+            SubEmbedder::HuggingFace(embedder) => embedder.embed_index_ref(&texts()),
+            SubEmbedder::OpenAi(embedder) => embedder.embed_index_ref(&texts(), threads),
+            SubEmbedder::Ollama(embedder) => embedder.embed_index_ref(&texts(), threads),
+            SubEmbedder::UserProvided(embedder) => embedder.embed_index_ref(&texts()),
+            SubEmbedder::Rest(embedder) => embedder.embed_index_ref(inputs, threads),
+            SubEmbedder::Onnx(embedder) => embedder.embed_index_ref(&texts()),
+        }
+    }
+
+    /// The document field whose value is sent along with the rendered document, see [`EmbedderInput::Media`].
+    pub fn media_field(&self) -> Option<&str> {
+        match self {
+            SubEmbedder::Rest(embedder) => embedder.media_field(),
+            SubEmbedder::HuggingFace(_)
+            | SubEmbedder::OpenAi(_)
+            | SubEmbedder::Ollama(_)
+            | SubEmbedder::UserProvided(_)
+            | SubEmbedder::Onnx(_) => None,
         }
     }
 
@@ -221,6 +267,7 @@ impl SubEmbedder {
             SubEmbedder::Ollama(embedder) => embedder.chunk_count_hint(),
             SubEmbedder::UserProvided(_) => 100,
             SubEmbedder::Rest(embedder) => embedder.chunk_count_hint(),
+            SubEmbedder::Onnx(embedder) => embedder.chunk_count_hint(),
         }
     }
 
@@ -232,6 +279,7 @@ impl SubEmbedder {
             SubEmbedder::Ollama(embedder) => embedder.prompt_count_in_chunk_hint(),
             SubEmbedder::UserProvided(_) => 1,
             SubEmbedder::Rest(embedder) => embedder.prompt_count_in_chunk_hint(),
+            SubEmbedder::Onnx(embedder) => embedder.prompt_count_in_chunk_hint(),
         }
     }
 
@@ -240,7 +288,8 @@ impl SubEmbedder {
             SubEmbedder::HuggingFace(_)
             | SubEmbedder::OpenAi(_)
             | SubEmbedder::Ollama(_)
-            | SubEmbedder::Rest(_) => true,
+            | SubEmbedder::Rest(_)
+            | SubEmbedder::Onnx(_) => true,
             SubEmbedder::UserProvided(_) => false,
         }
     }
@@ -253,6 +302,7 @@ impl SubEmbedder {
             SubEmbedder::Ollama(embedder) => embedder.dimensions(),
             SubEmbedder::UserProvided(embedder) => embedder.dimensions(),
             SubEmbedder::Rest(embedder) => embedder.dimensions(),
+            SubEmbedder::Onnx(embedder) => embedder.dimensions(),
         }
     }
 
@@ -264,6 +314,7 @@ impl SubEmbedder {
             SubEmbedder::Ollama(embedder) => embedder.distribution(),
             SubEmbedder::UserProvided(embedder) => embedder.distribution(),
             SubEmbedder::Rest(embedder) => embedder.distribution(),
+            SubEmbedder::Onnx(embedder) => embedder.distribution(),
         }
     }
 
@@ -274,6 +325,18 @@ impl SubEmbedder {
             SubEmbedder::UserProvided(_) => None,
             SubEmbedder::Ollama(embedder) => Some(embedder.cache()),
             SubEmbedder::Rest(embedder) => Some(embedder.cache()),
+            SubEmbedder::Onnx(embedder) => Some(embedder.cache()),
+        }
+    }
+
+    pub(super) fn cache_mut(&mut self) -> Option<&mut EmbeddingCache> {
+        match self {
+            SubEmbedder::HuggingFace(embedder) => Some(embedder.cache_mut()),
+            SubEmbedder::OpenAi(embedder) => Some(embedder.cache_mut()),
+            SubEmbedder::UserProvided(_) => None,
+            SubEmbedder::Ollama(embedder) => Some(embedder.cache_mut()),
+            SubEmbedder::Rest(embedder) => Some(embedder.cache_mut()),
+            SubEmbedder::Onnx(embedder) => Some(embedder.cache_mut()),
         }
     }
 }
@@ -57,6 +57,10 @@ pub enum EmbedErrorKind {
     TensorValue(candle_core::Error),
     #[error("could not run model:\n  - {0}")]
     ModelForward(candle_core::Error),
+    #[error("could not run ONNX model:\n  - {0}")]
+    OnnxRun(ort::Error),
+    #[error("unexpected output from ONNX model:\n  - {0}")]
+    OnnxOutput(String),
     #[error("attempt to embed the following text in a configuration where embeddings must be user provided:\n  - `{0}`")]
     ManualEmbed(String),
     #[error("model not found. Meilisearch will not automatically download models from the Ollama library, please pull the model manually{}", option_info(.0.as_deref(), "server replied with "))]
@@ -79,6 +83,8 @@ pub enum EmbedErrorKind {
     RestUnauthorized(Option<String>, ConfigurationSource),
     #[error("sent too many requests to embedding server{}", option_info(.0.as_deref(), "server replied with "))]
     RestTooManyRequests(Option<String>),
+    #[error("could not send the request before the deadline without exceeding the `maxConcurrentRequests`, `requestsPerMinute` or `tokensPerMinute` of the embedder")]
+    RateLimitDeadlineExceeded,
     #[error("sent a bad request to embedding server{}{}",
     if ConfigurationSource::User == *.1 {
         "\n  - Hint: check that the `request` in the embedder configuration matches the remote server's API"
@@ -99,6 +105,8 @@ pub enum EmbedErrorKind {
     UnexpectedDimension(usize, usize),
     #[error("no embedding was produced")]
     MissingEmbedding,
+    #[error("this embedder does not accept media\n  - Hint: only embedders of source `rest` whose `request` contains a `\"{{{{doc.<field>}}}}\"` placeholder accept media")]
+    MediaNotSupported,
     #[error(transparent)]
     PanicInThreadPool(#[from] PanicCatched),
 }
@@ -127,6 +135,14 @@ impl EmbedError {
         Self { kind: EmbedErrorKind::ModelForward(inner), fault: FaultSource::Runtime }
     }
 
+    pub fn onnx_run(inner: ort::Error) -> Self {
+        Self { kind: EmbedErrorKind::OnnxRun(inner), fault: FaultSource::Runtime }
+    }
+
+    pub fn onnx_unexpected_output(message: String) -> Self {
+        Self { kind: EmbedErrorKind::OnnxOutput(message), fault: FaultSource::User }
+    }
+
     pub(crate) fn embed_on_manual_embedder(texts: String) -> EmbedError {
         Self { kind: EmbedErrorKind::ManualEmbed(texts), fault: FaultSource::User }
     }
@@ -166,6 +182,10 @@ impl EmbedError {
         }
     }
 
+    pub(crate) fn rate_limit_deadline_exceeded() -> EmbedError {
+        Self { kind: EmbedErrorKind::RateLimitDeadlineExceeded, fault: FaultSource::Runtime }
+    }
+
     pub(crate) fn rest_bad_request(
         error_response: Option<String>,
         configuration_source: ConfigurationSource,
@@ -207,6 +227,10 @@ impl EmbedError {
         Self { kind: EmbedErrorKind::MissingEmbedding, fault: FaultSource::Undecided }
     }
 
+    pub(crate) fn media_not_supported() -> EmbedError {
+        Self { kind: EmbedErrorKind::MediaNotSupported, fault: FaultSource::User }
+    }
+
     pub(crate) fn rest_extraction_error(error: String) -> EmbedError {
         Self { kind: EmbedErrorKind::RestExtractionError(error), fault: FaultSource::Runtime }
     }
@@ -288,6 +312,19 @@ impl NewEmbedderError {
         }
     }
 
+    pub fn deserialize_modules_config(
+        model_name: String,
+        modules_config_filename: PathBuf,
+        inner: serde_json::Error,
+    ) -> NewEmbedderError {
+        let deserialize_config =
+            DeserializeConfig { model_name, filename: modules_config_filename, inner };
+        Self {
+            kind: NewEmbedderErrorKind::DeserializeConfig(deserialize_config),
+            fault: FaultSource::Runtime,
+        }
+    }
+
     pub fn open_tokenizer(
         tokenizer_filename: PathBuf,
         inner: Box<dyn std::error::Error + Send + Sync>,
@@ -319,6 +356,22 @@ impl NewEmbedderError {
         Self { kind: NewEmbedderErrorKind::LoadModel(inner), fault: FaultSource::Runtime }
     }
 
+    pub fn onnx_model_not_found(path: PathBuf) -> Self {
+        Self { kind: NewEmbedderErrorKind::OnnxModelNotFound(path), fault: FaultSource::User }
+    }
+
+    pub fn onnx_invalid_model_path(message: String) -> Self {
+        Self { kind: NewEmbedderErrorKind::OnnxInvalidModelPath(message), fault: FaultSource::User }
+    }
+
+    pub fn onnx_models_dir_not_set() -> Self {
+        Self { kind: NewEmbedderErrorKind::OnnxModelsDirNotSet, fault: FaultSource::User }
+    }
+
+    pub fn load_onnx_model(inner: ort::Error) -> Self {
+        Self { kind: NewEmbedderErrorKind::LoadOnnxModel(inner), fault: FaultSource::Runtime }
+    }
+
     pub fn could_not_determine_dimension(inner: EmbedError) -> NewEmbedderError {
         Self {
             kind: NewEmbedderErrorKind::CouldNotDetermineDimension(inner),
@@ -487,6 +540,15 @@ pub enum NewEmbedderErrorKind {
     CouldNotDetermineDimension(EmbedError),
     #[error("loading model failed:\n  - {0}")]
     LoadModel(candle_core::Error),
+    // onnx
+    #[error("could not find an ONNX model at `{}`\n  - Hint: `model` must be the path to a `.onnx` file, or to a directory containing `model.onnx` or `onnx/model.onnx`", .0.display())]
+    OnnxModelNotFound(PathBuf),
+    #[error("invalid ONNX model path:\n  - {0}")]
+    OnnxInvalidModelPath(String),
+    #[error("the directory of the ONNX models is not set or does not exist\n  - Hint: the instance administrator must set `{}` to the directory containing the models", super::onnx::MODELS_DIR_ENV)]
+    OnnxModelsDirNotSet,
+    #[error("loading ONNX model failed:\n  - {0}\n  - Hint: check that the ONNX Runtime library is installed, or set `ORT_DYLIB_PATH` to its path")]
+    LoadOnnxModel(ort::Error),
     #[error("{0}")]
     CouldNotParseTemplate(String),
     #[error("unsupported Ollama URL.\n  - For `ollama` sources, the URL must end with `/api/embed` or `/api/embeddings`\n  - Got `{0}`")]
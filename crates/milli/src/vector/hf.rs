@@ -7,6 +7,7 @@ use hf_hub::{Repo, RepoType};
 use tokenizers::{PaddingParams, Tokenizer};
 
 pub use super::error::{EmbedError, Error, NewEmbedderError};
+use super::persistent_cache::CacheScope;
 use super::{DistributionShift, Embedding, EmbeddingCache};
 
 #[derive(
@@ -69,6 +70,16 @@ impl EmbedderOptions {
             pooling: OverridePooling::UseModel,
         }
     }
+
+    pub fn cache_scope(&self) -> CacheScope<'_> {
+        CacheScope {
+            source: "huggingFace",
+            model: Some(&self.model),
+            revision: self.revision.as_deref(),
+            pooling: Some(self.pooling),
+            ..Default::default()
+        }
+    }
 }
 
 impl Default for EmbedderOptions {
@@ -99,7 +110,7 @@ impl std::fmt::Debug for Embedder {
 }
 
 #[derive(Clone, Copy, serde::Deserialize)]
-struct PoolingConfig {
+pub(super) struct PoolingConfig {
     #[serde(default)]
     pub pooling_mode_cls_token: bool,
     #[serde(default)]
@@ -122,7 +133,7 @@ pub enum Pooling {
     LastToken,
 }
 impl Pooling {
-    fn override_with(&mut self, pooling: OverridePooling) {
+    pub(super) fn override_with(&mut self, pooling: OverridePooling) {
         match pooling {
             OverridePooling::UseModel => {}
             OverridePooling::ForceCls => *self = Pooling::Cls,
@@ -370,4 +381,8 @@ impl Embedder {
     pub(super) fn cache(&self) -> &EmbeddingCache {
         &self.cache
     }
+
+    pub(super) fn cache_mut(&mut self) -> &mut EmbeddingCache {
+        &mut self.cache
+    }
 }
@@ -0,0 +1,113 @@
+//! Late-interaction embedders, such as ColBERT-like models, representing texts by a matrix of token vectors.
+//!
+//! The matrix of a document is provided by the user in the `_vectors` field of the documents, as an array of vectors:
+//!
+//! ```json
+//! { "_vectors": { "colbert": { "embeddings": [[0.1, 0.3], [0.2, 0.8]], "regenerate": false } } }
+//! ```
+//!
+//! Each vector is stored in the arroy database of the embedder, like the multiple embeddings of a dense embedder,
+//! so a document matrix is limited to 256 vectors.
+//!
+//! The matrix of a query is provided in the `vector` search parameter, as an array of vectors.
+//! Candidates are retrieved from arroy using each vector of the query, then scored by [`max_sim`].
+
+use super::error::EmbedError;
+use super::DistributionShift;
+use crate::vector::Embedding;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Embedder {
+    dimensions: usize,
+    distribution: Option<DistributionShift>,
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct EmbedderOptions {
+    pub dimensions: usize,
+    pub distribution: Option<DistributionShift>,
+}
+
+impl Embedder {
+    pub fn new(options: EmbedderOptions) -> Self {
+        Self { dimensions: options.dimensions, distribution: options.distribution }
+    }
+
+    /// Matrices cannot be generated from a document template.
+    pub fn embed_one<S: AsRef<str>>(&self, text: S) -> Result<Embedding, EmbedError> {
+        Err(EmbedError::embed_on_manual_embedder(text.as_ref().chars().take(250).collect()))
+    }
+
+    /// Dimensions of each vector of a matrix.
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    pub fn distribution(&self) -> Option<DistributionShift> {
+        self.distribution
+    }
+}
+
+/// Similarity in the `[0, 1]` interval between two vectors, matching `1 - distance` for the cosine distance of arroy.
+pub fn similarity(left: &[f32], right: &[f32]) -> f32 {
+    let (mut dot, mut left_norm, mut right_norm) = (0.0, 0.0, 0.0);
+    for (left, right) in left.iter().zip(right) {
+        dot += left * right;
+        left_norm += left * left;
+        right_norm += right * right;
+    }
+    let norms = (left_norm * right_norm).sqrt();
+    if norms <= 0.0 {
+        return 0.0;
+    }
+    let cosine = (dot / norms).clamp(-1.0, 1.0);
+    (1.0 + cosine) / 2.0
+}
+
+/// MaxSim score of a document for a query: the mean over the query vectors of their best similarity with a document vector.
+///
+/// `0.0` if the document has no vector.
+pub fn max_sim<'a>(query: impl IntoIterator<Item = &'a [f32]>, document: &[Vec<f32>]) -> f32 {
+    if document.is_empty() {
+        return 0.0;
+    }
+    let (mut total, mut count) = (0.0, 0usize);
+    for query_vector in query {
+        total += document
+            .iter()
+            .map(|document_vector| similarity(query_vector, document_vector))
+            .fold(0.0, f32::max);
+        count += 1;
+    }
+    if count == 0 {
+        0.0
+    } else {
+        total / count as f32
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn max_sim_averages_best_matches() {
+        let document = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+
+        // each query vector matches a document vector exactly
+        let query: [&[f32]; 2] = [&[2.0, 0.0], &[0.0, 3.0]];
+        assert_eq!(max_sim(query, &document), 1.0);
+
+        // the second query vector is orthogonal to its best match
+        let query: [&[f32]; 2] = [&[1.0, 0.0], &[-1.0, 0.0]];
+        assert_eq!(max_sim(query, &document), 0.75);
+    }
+
+    #[test]
+    fn max_sim_without_vectors() {
+        let query: [&[f32]; 1] = [&[1.0, 0.0]];
+        assert_eq!(max_sim(query, &[]), 0.0);
+        let empty: [&[f32]; 0] = [];
+        assert_eq!(max_sim(empty, &[vec![1.0, 0.0]]), 0.0);
+    }
+}
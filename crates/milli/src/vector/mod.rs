@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::num::NonZeroUsize;
 use std::sync::{Arc, Mutex};
@@ -10,9 +11,11 @@ use heed::{RoTxn, RwTxn, Unspecified};
 use ordered_float::OrderedFloat;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
 use utoipa::ToSchema;
 
 use self::error::{EmbedError, NewEmbedderError};
+use self::persistent_cache::{CacheScope, PersistentEmbeddingCache};
 use crate::progress::Progress;
 use crate::prompt::{Prompt, PromptData};
 use crate::ThreadPoolNoAbort;
@@ -21,10 +24,15 @@ pub mod composite;
 pub mod error;
 pub mod hf;
 pub mod json_template;
+pub mod late_interaction;
 pub mod manual;
+pub mod onnx;
 pub mod openai;
 pub mod parsed_vectors;
+pub mod persistent_cache;
+pub mod rate_limit;
 pub mod settings;
+pub mod sparse;
 
 pub mod ollama;
 pub mod rest;
@@ -540,6 +548,49 @@ impl<F> Embeddings<F> {
     }
 }
 
+/// An input to embed.
+#[derive(Debug, Clone, Copy)]
+pub enum EmbedderInput<'a> {
+    /// A text, such as a document rendered with the document template.
+    Text(&'a str),
+    /// A text sent along with the media of a document, for embedders whose `request` contains a media placeholder.
+    ///
+    /// `media` is `None` if the document has no value for the media field.
+    Media { text: &'a str, media: Option<&'a RawValue> },
+}
+
+impl<'a> EmbedderInput<'a> {
+    pub fn text(&self) -> &'a str {
+        match *self {
+            EmbedderInput::Text(text) | EmbedderInput::Media { text, .. } => text,
+        }
+    }
+
+    pub fn media(&self) -> Option<&'a RawValue> {
+        match *self {
+            EmbedderInput::Text(_) => None,
+            EmbedderInput::Media { media, .. } => media,
+        }
+    }
+
+    /// The key of the embedding of this input in the persistent cache.
+    fn cache_key(&self) -> Cow<'a, str> {
+        match *self {
+            EmbedderInput::Text(text) => Cow::Borrowed(text),
+            EmbedderInput::Media { text, media } => {
+                Cow::Owned(format!("{text}\0{}", media.map_or("null", RawValue::get)))
+            }
+        }
+    }
+}
+
+impl PartialEq for EmbedderInput<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.text() == other.text()
+            && self.media().map(RawValue::get) == other.media().map(RawValue::get)
+    }
+}
+
 /// An embedder can be used to transform text into embeddings.
 #[derive(Debug)]
 pub enum Embedder {
@@ -555,11 +606,19 @@ pub enum Embedder {
     Rest(rest::Embedder),
     /// An embedder composed of an embedder at search time and an embedder at indexing time.
     Composite(composite::Embedder),
+    /// An embedder based on the user providing sparse vectors in the documents, searched using an inverted index.
+    UserProvidedSparse(sparse::Embedder),
+    /// An embedder based on running local models in the ONNX format with ONNX Runtime.
+    Onnx(onnx::Embedder),
+    /// An embedder based on the user providing matrices of vectors in the documents and queries, scored by late interaction.
+    UserProvidedMultiVector(late_interaction::Embedder),
 }
 
 #[derive(Debug)]
 struct EmbeddingCache {
     data: Option<Mutex<lru::LruCache<String, Embedding>>>,
+    /// Cache shared with the other embedders having the same options, and persisted on disk.
+    persistent: Option<PersistentEmbeddingCache>,
 }
 
 impl EmbeddingCache {
@@ -567,22 +626,27 @@ impl EmbeddingCache {
 
     pub fn new(cap: usize) -> Self {
         let data = NonZeroUsize::new(cap).map(lru::LruCache::new).map(Mutex::new);
-        Self { data }
+        Self { data, persistent: None }
     }
 
     /// Get the embedding corresponding to `text`, if any is present in the cache.
     pub fn get(&self, text: &str) -> Option<Embedding> {
-        let data = self.data.as_ref()?;
-        if text.len() > Self::MAX_TEXT_LEN {
-            return None;
+        if let Some(data) = self.data.as_ref().filter(|_| text.len() <= Self::MAX_TEXT_LEN) {
+            let mut cache = data.lock().unwrap();
+            if let Some(embedding) = cache.get(text) {
+                return Some(embedding.clone());
+            }
         }
-        let mut cache = data.lock().unwrap();
 
-        cache.get(text).cloned()
+        self.persistent.as_ref()?.get(text)
     }
 
     /// Puts a new embedding for the specified `text`
     pub fn put(&self, text: String, embedding: Embedding) {
+        if let Some(persistent) = &self.persistent {
+            persistent.put(&text, &embedding);
+        }
+
         let Some(data) = self.data.as_ref() else {
             return;
         };
@@ -606,6 +670,9 @@ pub struct EmbeddingConfig {
     pub prompt: PromptData,
     /// If this embedder is binary quantized
     pub quantized: Option<bool>,
+    /// Name of the embedder this embedder is a shadow of, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shadow_of: Option<String>,
     // TODO: add metrics and anything needed
 }
 
@@ -631,6 +698,13 @@ impl EmbeddingConfigs {
         self.0.contains_key(name)
     }
 
+    /// Whether the embedder exists and its vectors are stored in the vector store rather than in the documents.
+    ///
+    /// This is `false` for sparse embedders, whose vectors are kept in the documents.
+    pub fn contains_dense(&self, name: &str) -> bool {
+        self.0.get(name).is_some_and(|(embedder, _, _)| !embedder.is_sparse())
+    }
+
     /// Get an embedder configuration and template from its name.
     pub fn get(&self, name: &str) -> Option<(Arc<Embedder>, Arc<Prompt>, bool)> {
         self.0.get(name).cloned()
@@ -665,6 +739,9 @@ pub enum EmbedderOptions {
     UserProvided(manual::EmbedderOptions),
     Rest(rest::EmbedderOptions),
     Composite(composite::EmbedderOptions),
+    UserProvidedSparse(sparse::EmbedderOptions),
+    Onnx(onnx::EmbedderOptions),
+    UserProvidedMultiVector(late_interaction::EmbedderOptions),
 }
 
 impl Default for EmbedderOptions {
@@ -673,13 +750,62 @@ impl Default for EmbedderOptions {
     }
 }
 
+impl EmbedderOptions {
+    /// The options changing the embeddings produced by the embedder, `None` if it does not cache its embeddings.
+    pub fn cache_scope(&self) -> Option<CacheScope<'_>> {
+        match self {
+            EmbedderOptions::HuggingFace(options) => Some(options.cache_scope()),
+            EmbedderOptions::OpenAi(options) => Some(options.cache_scope()),
+            EmbedderOptions::Ollama(options) => Some(options.cache_scope()),
+            EmbedderOptions::Rest(options) => Some(options.cache_scope()),
+            EmbedderOptions::Onnx(options) => Some(options.cache_scope()),
+            EmbedderOptions::UserProvided(_)
+            | EmbedderOptions::Composite(_)
+            | EmbedderOptions::UserProvidedSparse(_)
+            | EmbedderOptions::UserProvidedMultiVector(_) => None,
+        }
+    }
+
+    /// Whether the embedder produces sparse vectors.
+    pub fn is_sparse(&self) -> bool {
+        matches!(self, EmbedderOptions::UserProvidedSparse(_))
+    }
+
+    /// Whether the embedder generates the embeddings of the documents from a document template.
+    pub fn uses_document_template(&self) -> bool {
+        match self {
+            EmbedderOptions::HuggingFace(_)
+            | EmbedderOptions::OpenAi(_)
+            | EmbedderOptions::Ollama(_)
+            | EmbedderOptions::Rest(_)
+            | EmbedderOptions::Onnx(_) => true,
+            EmbedderOptions::UserProvided(_)
+            | EmbedderOptions::UserProvidedSparse(_)
+            | EmbedderOptions::UserProvidedMultiVector(_) => false,
+            EmbedderOptions::Composite(options) => options.index.uses_document_template(),
+        }
+    }
+}
+
 impl Embedder {
     /// Spawns a new embedder built from its options.
+    ///
+    /// The embedder consults and fills the `persistent_cache`, if any, in addition to its in-memory cache of `cache_cap` entries.
     pub fn new(
         options: EmbedderOptions,
         cache_cap: usize,
+        persistent_cache: Option<&PersistentEmbeddingCache>,
     ) -> std::result::Result<Self, NewEmbedderError> {
-        Ok(match options {
+        // entries are only shared between embedders producing the same embeddings
+        let persistent_caches = persistent_cache.map(|cache| match &options {
+            EmbedderOptions::Composite(options) => (
+                options.search.cache_scope().map(|scope| cache.scoped(&scope)),
+                options.index.cache_scope().map(|scope| cache.scoped(&scope)),
+            ),
+            options => (options.cache_scope().map(|scope| cache.scoped(&scope)), None),
+        });
+
+        let mut embedder = match options {
             EmbedderOptions::HuggingFace(options) => {
                 Self::HuggingFace(hf::Embedder::new(options, cache_cap)?)
             }
@@ -700,7 +826,27 @@ impl Embedder {
             EmbedderOptions::Composite(options) => {
                 Self::Composite(composite::Embedder::new(options, cache_cap)?)
             }
-        })
+            EmbedderOptions::UserProvidedSparse(options) => {
+                Self::UserProvidedSparse(sparse::Embedder::new(options))
+            }
+            EmbedderOptions::Onnx(options) => Self::Onnx(onnx::Embedder::new(options, cache_cap)?),
+            EmbedderOptions::UserProvidedMultiVector(options) => {
+                Self::UserProvidedMultiVector(late_interaction::Embedder::new(options))
+            }
+        };
+
+        if let Some((persistent_cache, index_persistent_cache)) = persistent_caches {
+            if let Embedder::Composite(embedder) = &mut embedder {
+                if let Some(cache) = embedder.index.cache_mut() {
+                    cache.persistent = index_persistent_cache;
+                }
+            }
+            if let Some(cache) = embedder.cache_mut() {
+                cache.persistent = persistent_cache;
+            }
+        }
+
+        Ok(embedder)
     }
 
     /// Embed in search context
@@ -731,6 +877,9 @@ impl Embedder {
                 .pop()
                 .ok_or_else(EmbedError::missing_embedding),
             Embedder::Composite(embedder) => embedder.search.embed_one(text, deadline),
+            Embedder::UserProvidedSparse(embedder) => embedder.embed_one(text),
+            Embedder::Onnx(embedder) => embedder.embed_one(text),
+            Embedder::UserProvidedMultiVector(embedder) => embedder.embed_one(text),
         }?;
 
         if let Some(cache) = self.cache() {
@@ -740,37 +889,119 @@ impl Embedder {
         Ok(embedding)
     }
 
-    /// Embed multiple chunks of texts.
+    /// Embed a media, along with an optional text, in search context.
+    ///
+    /// Only `rest` embedders whose request contains a media placeholder accept media.
+    #[tracing::instrument(level = "debug", skip_all, target = "search")]
+    pub fn embed_search_media(
+        &self,
+        text: Option<&str>,
+        media: &serde_json::Value,
+        deadline: Option<Instant>,
+    ) -> std::result::Result<Embedding, EmbedError> {
+        let text = text.unwrap_or_default();
+        match self {
+            Embedder::Rest(embedder) => embedder.embed_media(text, media, deadline),
+            Embedder::Composite(embedder) => embedder.search.embed_media(text, media, deadline),
+            Embedder::HuggingFace(_)
+            | Embedder::OpenAi(_)
+            | Embedder::UserProvided(_)
+            | Embedder::Ollama(_)
+            | Embedder::UserProvidedSparse(_)
+            | Embedder::Onnx(_)
+            | Embedder::UserProvidedMultiVector(_) => Err(EmbedError::media_not_supported()),
+        }
+    }
+
+    /// Embed multiple chunks of inputs.
     ///
-    /// Each chunk is composed of one or multiple texts.
+    /// Each chunk is composed of one or multiple inputs.
     pub fn embed_index(
         &self,
-        text_chunks: Vec<Vec<String>>,
+        input_chunks: Vec<Vec<EmbedderInput<'_>>>,
         threads: &ThreadPoolNoAbort,
     ) -> std::result::Result<Vec<Vec<Embedding>>, EmbedError> {
-        match self {
-            Embedder::HuggingFace(embedder) => embedder.embed_index(text_chunks),
-            Embedder::OpenAi(embedder) => embedder.embed_index(text_chunks, threads),
-            Embedder::Ollama(embedder) => embedder.embed_index(text_chunks, threads),
-            Embedder::UserProvided(embedder) => embedder.embed_index(text_chunks),
-            Embedder::Rest(embedder) => embedder.embed_index(text_chunks, threads),
-            Embedder::Composite(embedder) => embedder.index.embed_index(text_chunks, threads),
-        }
+        let inputs: Vec<EmbedderInput<'_>> = input_chunks.iter().flatten().copied().collect();
+        let mut embeddings = self.embed_index_ref(&inputs, threads)?.into_iter();
+        Ok(input_chunks
+            .iter()
+            .map(|inputs| embeddings.by_ref().take(inputs.len()).collect())
+            .collect())
     }
 
     /// Non-owning variant of [`Self::embed_index`].
     pub fn embed_index_ref(
         &self,
-        texts: &[&str],
+        inputs: &[EmbedderInput<'_>],
+        threads: &ThreadPoolNoAbort,
+    ) -> std::result::Result<Vec<Embedding>, EmbedError> {
+        let Some(cache) = self.index_persistent_cache() else {
+            return self.embed_index_ref_uncached(inputs, threads);
+        };
+
+        let keys: Vec<_> = inputs.iter().map(EmbedderInput::cache_key).collect();
+        let cached = cache.get_many(&keys.iter().map(AsRef::<str>::as_ref).collect::<Vec<_>>());
+        let missing: Vec<(EmbedderInput<'_>, &str)> = inputs
+            .iter()
+            .zip(&keys)
+            .zip(&cached)
+            .filter(|(_, e)| e.is_none())
+            .map(|((input, key), _)| (*input, key.as_ref()))
+            .collect();
+
+        let embedded = if missing.is_empty() {
+            Vec::new()
+        } else {
+            let missing_inputs: Vec<_> = missing.iter().map(|(input, _)| *input).collect();
+            let embedded = self.embed_index_ref_uncached(&missing_inputs, threads)?;
+            cache.put_many(missing.iter().map(|(_, key)| *key).zip(&embedded));
+            embedded
+        };
+
+        let mut embedded = embedded.into_iter();
+        cached
+            .into_iter()
+            .map(|embedding| {
+                embedding.or_else(|| embedded.next()).ok_or_else(EmbedError::missing_embedding)
+            })
+            .collect()
+    }
+
+    /// Variant of [`Self::embed_index_ref`] that does not reuse the embeddings of the persistent cache,
+    /// but replaces them with the freshly computed ones.
+    pub fn embed_index_ref_refreshing(
+        &self,
+        inputs: &[EmbedderInput<'_>],
+        threads: &ThreadPoolNoAbort,
+    ) -> std::result::Result<Vec<Embedding>, EmbedError> {
+        let embeddings = self.embed_index_ref_uncached(inputs, threads)?;
+        if let Some(cache) = self.index_persistent_cache() {
+            let keys: Vec<_> = inputs.iter().map(EmbedderInput::cache_key).collect();
+            cache.put_many(keys.iter().map(AsRef::<str>::as_ref).zip(&embeddings));
+        }
+        Ok(embeddings)
+    }
+
+    fn embed_index_ref_uncached(
+        &self,
+        inputs: &[EmbedderInput<'_>],
         threads: &ThreadPoolNoAbort,
     ) -> std::result::Result<Vec<Embedding>, EmbedError> {
+        let texts = || inputs.iter().map(EmbedderInput::text).collect::<Vec<_>>();
         match self {
-            Embedder::HuggingFace(embedder) => embedder.embed_index_ref(texts),
-            Embedder::OpenAi(embedder) => embedder.embed_index_ref(texts, threads),
-            Embedder::Ollama(embedder) => embedder.embed_index_ref(texts, threads),
-            Embedder::UserProvided(embedder) => embedder.embed_index_ref(texts),
-            Embedder::Rest(embedder) => embedder.embed_index_ref(texts, threads),
-            Embedder::Composite(embedder) => embedder.index.embed_index_ref(texts, threads),
+            Embedder::HuggingFace(embedder) => embedder.embed_index_ref(&texts()),
+            Embedder::OpenAi(embedder) => embedder.embed_index_ref(&texts(), threads),
+            Embedder::Ollama(embedder) => embedder.embed_index_ref(&texts(), threads),
+            Embedder::UserProvided(embedder) => embedder.embed_index_ref(&texts()),
+            Embedder::Rest(embedder) => embedder.embed_index_ref(inputs, threads),
+            Embedder::Composite(embedder) => embedder.index.embed_index_ref(inputs, threads),
+            Embedder::UserProvidedSparse(embedder) => {
+                inputs.iter().map(|input| embedder.embed_one(input.text())).collect()
+            }
+            Embedder::Onnx(embedder) => embedder.embed_index_ref(&texts()),
+            Embedder::UserProvidedMultiVector(embedder) => {
+                inputs.iter().map(|input| embedder.embed_one(input.text())).collect()
+            }
         }
     }
 
@@ -780,9 +1011,12 @@ impl Embedder {
             Embedder::HuggingFace(embedder) => embedder.chunk_count_hint(),
             Embedder::OpenAi(embedder) => embedder.chunk_count_hint(),
             Embedder::Ollama(embedder) => embedder.chunk_count_hint(),
-            Embedder::UserProvided(_) => 100,
+            Embedder::UserProvided(_)
+            | Embedder::UserProvidedSparse(_)
+            | Embedder::UserProvidedMultiVector(_) => 100,
             Embedder::Rest(embedder) => embedder.chunk_count_hint(),
             Embedder::Composite(embedder) => embedder.index.chunk_count_hint(),
+            Embedder::Onnx(embedder) => embedder.chunk_count_hint(),
         }
     }
 
@@ -792,9 +1026,12 @@ impl Embedder {
             Embedder::HuggingFace(embedder) => embedder.prompt_count_in_chunk_hint(),
             Embedder::OpenAi(embedder) => embedder.prompt_count_in_chunk_hint(),
             Embedder::Ollama(embedder) => embedder.prompt_count_in_chunk_hint(),
-            Embedder::UserProvided(_) => 1,
+            Embedder::UserProvided(_)
+            | Embedder::UserProvidedSparse(_)
+            | Embedder::UserProvidedMultiVector(_) => 1,
             Embedder::Rest(embedder) => embedder.prompt_count_in_chunk_hint(),
             Embedder::Composite(embedder) => embedder.index.prompt_count_in_chunk_hint(),
+            Embedder::Onnx(embedder) => embedder.prompt_count_in_chunk_hint(),
         }
     }
 
@@ -807,6 +1044,10 @@ impl Embedder {
             Embedder::UserProvided(embedder) => embedder.dimensions(),
             Embedder::Rest(embedder) => embedder.dimensions(),
             Embedder::Composite(embedder) => embedder.dimensions(),
+            Embedder::Onnx(embedder) => embedder.dimensions(),
+            Embedder::UserProvidedMultiVector(embedder) => embedder.dimensions(),
+            // sparse vectors have an unbounded number of dimensions
+            Embedder::UserProvidedSparse(_) => 0,
         }
     }
 
@@ -819,6 +1060,9 @@ impl Embedder {
             Embedder::UserProvided(embedder) => embedder.distribution(),
             Embedder::Rest(embedder) => embedder.distribution(),
             Embedder::Composite(embedder) => embedder.distribution(),
+            Embedder::UserProvidedSparse(embedder) => embedder.distribution(),
+            Embedder::Onnx(embedder) => embedder.distribution(),
+            Embedder::UserProvidedMultiVector(embedder) => embedder.distribution(),
         }
     }
 
@@ -827,22 +1071,78 @@ impl Embedder {
             Embedder::HuggingFace(_)
             | Embedder::OpenAi(_)
             | Embedder::Ollama(_)
-            | Embedder::Rest(_) => true,
-            Embedder::UserProvided(_) => false,
+            | Embedder::Rest(_)
+            | Embedder::Onnx(_) => true,
+            Embedder::UserProvided(_)
+            | Embedder::UserProvidedSparse(_)
+            | Embedder::UserProvidedMultiVector(_) => false,
             Embedder::Composite(embedder) => embedder.index.uses_document_template(),
         }
     }
 
+    /// The document field whose value is sent along with the rendered document, see [`EmbedderInput::Media`].
+    pub fn media_field(&self) -> Option<&str> {
+        match self {
+            Embedder::Rest(embedder) => embedder.media_field(),
+            Embedder::Composite(embedder) => embedder.index.media_field(),
+            _ => None,
+        }
+    }
+
+    /// Whether the embedder works with sparse vectors, see [`sparse`].
+    pub fn is_sparse(&self) -> bool {
+        matches!(self, Embedder::UserProvidedSparse(_))
+    }
+
+    /// Whether the embedder scores documents by late interaction between matrices of vectors, see [`late_interaction`].
+    pub fn is_multi_vector(&self) -> bool {
+        matches!(self, Embedder::UserProvidedMultiVector(_))
+    }
+
+    /// Sparse vector used to search for `query`, `None` for dense embedders.
+    pub fn embed_sparse_query(&self, query: &str) -> Option<sparse::SparseVector> {
+        match self {
+            Embedder::UserProvidedSparse(embedder) => Some(embedder.embed_query(query)),
+            _ => None,
+        }
+    }
+
     fn cache(&self) -> Option<&EmbeddingCache> {
         match self {
             Embedder::HuggingFace(embedder) => Some(embedder.cache()),
             Embedder::OpenAi(embedder) => Some(embedder.cache()),
-            Embedder::UserProvided(_) => None,
+            Embedder::UserProvided(_)
+            | Embedder::UserProvidedSparse(_)
+            | Embedder::UserProvidedMultiVector(_) => None,
             Embedder::Ollama(embedder) => Some(embedder.cache()),
             Embedder::Rest(embedder) => Some(embedder.cache()),
             Embedder::Composite(embedder) => embedder.search.cache(),
+            Embedder::Onnx(embedder) => Some(embedder.cache()),
+        }
+    }
+
+    fn cache_mut(&mut self) -> Option<&mut EmbeddingCache> {
+        match self {
+            Embedder::HuggingFace(embedder) => Some(embedder.cache_mut()),
+            Embedder::OpenAi(embedder) => Some(embedder.cache_mut()),
+            Embedder::UserProvided(_)
+            | Embedder::UserProvidedSparse(_)
+            | Embedder::UserProvidedMultiVector(_) => None,
+            Embedder::Ollama(embedder) => Some(embedder.cache_mut()),
+            Embedder::Rest(embedder) => Some(embedder.cache_mut()),
+            Embedder::Composite(embedder) => embedder.search.cache_mut(),
+            Embedder::Onnx(embedder) => Some(embedder.cache_mut()),
         }
     }
+
+    /// The persistent cache consulted when embedding documents.
+    fn index_persistent_cache(&self) -> Option<&PersistentEmbeddingCache> {
+        let cache = match self {
+            Embedder::Composite(embedder) => embedder.index.cache(),
+            embedder => embedder.cache(),
+        };
+        cache?.persistent.as_ref()
+    }
 }
 
 /// Describes the mean and sigma of distribution of embedding similarity in the embedding space.
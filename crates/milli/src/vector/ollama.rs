@@ -4,6 +4,8 @@ use rayon::iter::{IntoParallelIterator as _, ParallelIterator as _};
 use rayon::slice::ParallelSlice as _;
 
 use super::error::{EmbedError, EmbedErrorKind, NewEmbedderError, NewEmbedderErrorKind};
+use super::persistent_cache::CacheScope;
+use super::rate_limit::RateLimits;
 use super::rest::{Embedder as RestEmbedder, EmbedderOptions as RestEmbedderOptions};
 use super::{DistributionShift, EmbeddingCache, REQUEST_PARALLELISM};
 use crate::error::FaultSource;
@@ -22,9 +24,21 @@ pub struct EmbedderOptions {
     pub api_key: Option<String>,
     pub distribution: Option<DistributionShift>,
     pub dimensions: Option<usize>,
+    #[serde(default)]
+    pub rate_limits: RateLimits,
 }
 
 impl EmbedderOptions {
+    pub fn cache_scope(&self) -> CacheScope<'_> {
+        CacheScope {
+            source: "ollama",
+            url: self.url.as_deref(),
+            model: Some(&self.embedding_model),
+            dimensions: self.dimensions,
+            ..Default::default()
+        }
+    }
+
     pub fn with_default_model(
         api_key: Option<String>,
         url: Option<String>,
@@ -36,6 +50,7 @@ impl EmbedderOptions {
             url,
             distribution: None,
             dimensions,
+            rate_limits: Default::default(),
         }
     }
 
@@ -70,6 +85,7 @@ impl EmbedderOptions {
             request,
             response,
             headers: Default::default(),
+            rate_limits: self.rate_limits,
         })
     }
 }
@@ -187,6 +203,10 @@ impl Embedder {
     pub(super) fn cache(&self) -> &EmbeddingCache {
         self.rest_embedder.cache()
     }
+
+    pub(super) fn cache_mut(&mut self) -> &mut EmbeddingCache {
+        self.rest_embedder.cache_mut()
+    }
 }
 
 fn get_ollama_path() -> String {
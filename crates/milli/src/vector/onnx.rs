@@ -0,0 +1,416 @@
+//! An embedder running local models in the ONNX format with [ONNX Runtime](https://onnxruntime.ai).
+//!
+//! Models are read from a local directory, such as a sentence-transformers model exported with `optimum`,
+//! that contains the model as `model.onnx` or `onnx/model.onnx`, along with its `tokenizer.json`.
+//! The path of a specific `.onnx` file can also be given, for instance to use a quantized variant of the model.
+//!
+//! The paths of the models are relative to the directory set by the instance administrator with the
+//! [`MODELS_DIR_ENV`] environment variable, and cannot leave it.
+
+use std::path::{Component, Path, PathBuf};
+
+use ndarray::{Array2, ArrayView3, Ix2, Ix3};
+use ort::session::builder::GraphOptimizationLevel;
+use ort::session::Session;
+use tokenizers::{PaddingParams, Tokenizer, TruncationParams};
+
+pub use super::error::{EmbedError, Error, NewEmbedderError};
+use super::hf::{OverridePooling, Pooling, PoolingConfig};
+use super::persistent_cache::CacheScope;
+use super::{DistributionShift, Embedding, EmbeddingCache};
+
+/// Maximum number of tokens of a text, longer texts are truncated.
+const MAX_TOKENS: usize = 512;
+
+/// Environment variable holding the directory the models of `onnx` embedders are read from.
+pub const MODELS_DIR_ENV: &str = "MEILI_EXPERIMENTAL_ONNX_MODELS_DIR";
+
+/// Type of the sentence-transformers module normalizing the embeddings, as declared in `modules.json`.
+const NORMALIZE_MODULE: &str = "sentence_transformers.models.Normalize";
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct EmbedderOptions {
+    /// Path to the directory containing the model, or to the `.onnx` file of the model,
+    /// relative to the models directory.
+    pub model: String,
+    pub distribution: Option<DistributionShift>,
+    pub pooling: OverridePooling,
+    /// Whether to normalize the embeddings, `None` to follow the `modules.json` of the model.
+    pub normalize: Option<bool>,
+}
+
+impl EmbedderOptions {
+    pub fn cache_scope(&self) -> CacheScope<'_> {
+        CacheScope {
+            source: "onnx",
+            model: Some(&self.model),
+            pooling: Some(self.pooling),
+            normalize: self.normalize,
+            ..Default::default()
+        }
+    }
+}
+
+/// Perform embedding of documents and queries
+pub struct Embedder {
+    session: Session,
+    tokenizer: Tokenizer,
+    options: EmbedderOptions,
+    /// Whether the model expects a `token_type_ids` input in addition to `input_ids` and `attention_mask`.
+    uses_token_type_ids: bool,
+    dimensions: usize,
+    pooling: Pooling,
+    normalize: bool,
+    cache: EmbeddingCache,
+}
+
+impl std::fmt::Debug for Embedder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Embedder")
+            .field("model", &self.options.model)
+            .field("tokenizer", &self.tokenizer)
+            .field("options", &self.options)
+            .field("pooling", &self.pooling)
+            .field("normalize", &self.normalize)
+            .finish()
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct ModuleConfig {
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+impl Embedder {
+    pub fn new(
+        options: EmbedderOptions,
+        cache_cap: usize,
+    ) -> std::result::Result<Self, NewEmbedderError> {
+        let (model_filename, directory) = model_files(&options.model)?;
+
+        let pooling_filename = directory.join("1_Pooling").join("config.json");
+        let mut pooling = match read_optional(&pooling_filename).map_err(|inner| {
+            NewEmbedderError::open_pooling_config(pooling_filename.clone(), inner)
+        })? {
+            Some(pooling) => {
+                let pooling: PoolingConfig = serde_json::from_str(&pooling).map_err(|inner| {
+                    NewEmbedderError::deserialize_pooling_config(
+                        options.model.clone(),
+                        pooling_filename,
+                        inner,
+                    )
+                })?;
+                pooling.into()
+            }
+            None => Pooling::default(),
+        };
+        pooling.override_with(options.pooling);
+
+        let normalize = match options.normalize {
+            Some(normalize) => normalize,
+            None => {
+                let modules_filename = directory.join("modules.json");
+                match read_optional(&modules_filename).map_err(|inner| {
+                    NewEmbedderError::open_config(modules_filename.clone(), inner)
+                })? {
+                    Some(modules) => {
+                        let modules: Vec<ModuleConfig> =
+                            serde_json::from_str(&modules).map_err(|inner| {
+                                NewEmbedderError::deserialize_modules_config(
+                                    options.model.clone(),
+                                    modules_filename,
+                                    inner,
+                                )
+                            })?;
+                        modules.iter().any(|module| module.kind == NORMALIZE_MODULE)
+                    }
+                    None => false,
+                }
+            }
+        };
+
+        let tokenizer_filename = directory.join("tokenizer.json");
+        let mut tokenizer = Tokenizer::from_file(&tokenizer_filename)
+            .map_err(|inner| NewEmbedderError::open_tokenizer(tokenizer_filename.clone(), inner))?;
+        if let Some(pp) = tokenizer.get_padding_mut() {
+            pp.strategy = tokenizers::PaddingStrategy::BatchLongest
+        } else {
+            let pp = PaddingParams {
+                strategy: tokenizers::PaddingStrategy::BatchLongest,
+                ..Default::default()
+            };
+            tokenizer.with_padding(Some(pp));
+        }
+        if tokenizer.get_truncation().is_none() {
+            let truncation = TruncationParams { max_length: MAX_TOKENS, ..Default::default() };
+            tokenizer
+                .with_truncation(Some(truncation))
+                .map_err(|inner| NewEmbedderError::open_tokenizer(tokenizer_filename, inner))?;
+        }
+
+        let threads = std::thread::available_parallelism().map(|x| x.get()).unwrap_or(8);
+        let session = Session::builder()
+            .and_then(|builder| builder.with_optimization_level(GraphOptimizationLevel::Level3))
+            .and_then(|builder| builder.with_intra_threads(threads))
+            .and_then(|builder| builder.commit_from_file(&model_filename))
+            .map_err(NewEmbedderError::load_onnx_model)?;
+        let uses_token_type_ids = session.inputs.iter().any(|input| input.name == "token_type_ids");
+
+        tracing::debug!(
+            model = options.model,
+            ?model_filename,
+            ?pooling,
+            normalize,
+            "model config"
+        );
+
+        let mut this = Self {
+            session,
+            tokenizer,
+            options,
+            uses_token_type_ids,
+            dimensions: 0,
+            pooling,
+            normalize,
+            cache: EmbeddingCache::new(cache_cap),
+        };
+
+        let embeddings = this
+            .embed(vec!["test".into()])
+            .map_err(NewEmbedderError::could_not_determine_dimension)?;
+        this.dimensions = embeddings.first().unwrap().len();
+
+        Ok(this)
+    }
+
+    pub fn embed(&self, texts: Vec<String>) -> std::result::Result<Vec<Embedding>, EmbedError> {
+        let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+        self.embed_index_ref(&texts)
+    }
+
+    pub fn embed_one(&self, text: &str) -> std::result::Result<Embedding, EmbedError> {
+        self.embed_batch(&[text])?.pop().ok_or_else(EmbedError::missing_embedding)
+    }
+
+    /// Embeds the texts in a single run of the model.
+    fn embed_batch(&self, texts: &[&str]) -> std::result::Result<Vec<Embedding>, EmbedError> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let encodings =
+            self.tokenizer.encode_batch(texts.to_vec(), true).map_err(EmbedError::tokenize)?;
+
+        // all encodings are padded to the longest one
+        let shape = (encodings.len(), encodings.first().map_or(0, |encoding| encoding.len()));
+        let mut input_ids = Array2::<i64>::zeros(shape);
+        let mut attention_mask = Array2::<i64>::zeros(shape);
+        let mut token_type_ids = Array2::<i64>::zeros(shape);
+        for (i, encoding) in encodings.iter().enumerate() {
+            for (j, ((id, mask), type_id)) in encoding
+                .get_ids()
+                .iter()
+                .zip(encoding.get_attention_mask())
+                .zip(encoding.get_type_ids())
+                .enumerate()
+            {
+                input_ids[[i, j]] = *id as i64;
+                attention_mask[[i, j]] = *mask as i64;
+                token_type_ids[[i, j]] = *type_id as i64;
+            }
+        }
+
+        let inputs = if self.uses_token_type_ids {
+            ort::inputs![
+                "input_ids" => input_ids,
+                "attention_mask" => attention_mask.clone(),
+                "token_type_ids" => token_type_ids,
+            ]
+        } else {
+            ort::inputs![
+                "input_ids" => input_ids,
+                "attention_mask" => attention_mask.clone(),
+            ]
+        }
+        .map_err(EmbedError::onnx_run)?;
+        let outputs = self.session.run(inputs).map_err(EmbedError::onnx_run)?;
+        let output = outputs[0].try_extract_tensor::<f32>().map_err(EmbedError::onnx_run)?;
+
+        let mut embeddings = match output.ndim() {
+            // the model already pools the embeddings of the tokens
+            2 => output
+                .into_dimensionality::<Ix2>()
+                .map_err(|error| EmbedError::onnx_unexpected_output(error.to_string()))?
+                .to_owned(),
+            3 => pool(
+                output
+                    .into_dimensionality::<Ix3>()
+                    .map_err(|error| EmbedError::onnx_unexpected_output(error.to_string()))?,
+                &attention_mask,
+                self.pooling,
+            ),
+            ndim => {
+                return Err(EmbedError::onnx_unexpected_output(format!(
+                    "expected an output of rank 2 or 3, got rank {ndim}"
+                )))
+            }
+        };
+
+        if self.normalize {
+            for mut embedding in embeddings.outer_iter_mut() {
+                let norm = embedding.dot(&embedding).sqrt();
+                if norm > 0.0 {
+                    embedding /= norm;
+                }
+            }
+        }
+
+        Ok(embeddings.outer_iter().map(|embedding| embedding.to_vec()).collect())
+    }
+
+    pub fn embed_index(
+        &self,
+        text_chunks: Vec<Vec<String>>,
+    ) -> std::result::Result<Vec<Vec<Embedding>>, EmbedError> {
+        text_chunks.into_iter().map(|prompts| self.embed(prompts)).collect()
+    }
+
+    pub fn chunk_count_hint(&self) -> usize {
+        1
+    }
+
+    pub fn prompt_count_in_chunk_hint(&self) -> usize {
+        std::thread::available_parallelism().map(|x| x.get()).unwrap_or(8)
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    pub fn distribution(&self) -> Option<DistributionShift> {
+        self.options.distribution
+    }
+
+    pub(crate) fn embed_index_ref(&self, texts: &[&str]) -> Result<Vec<Embedding>, EmbedError> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for texts in texts.chunks(self.prompt_count_in_chunk_hint()) {
+            embeddings.extend(self.embed_batch(texts)?);
+        }
+        Ok(embeddings)
+    }
+
+    pub(super) fn cache(&self) -> &EmbeddingCache {
+        &self.cache
+    }
+
+    pub(super) fn cache_mut(&mut self) -> &mut EmbeddingCache {
+        &mut self.cache
+    }
+}
+
+/// Pools the embeddings of the tokens of each text, ignoring the padding tokens.
+fn pool(
+    token_embeddings: ArrayView3<f32>,
+    attention_mask: &Array2<i64>,
+    pooling: Pooling,
+) -> Array2<f32> {
+    let (text_count, _token_count, dimensions) = token_embeddings.dim();
+    let mut embeddings = Array2::zeros((text_count, dimensions));
+    for ((tokens, mask), mut embedding) in token_embeddings
+        .outer_iter()
+        .zip(attention_mask.outer_iter())
+        .zip(embeddings.outer_iter_mut())
+    {
+        let tokens: Vec<_> = tokens
+            .outer_iter()
+            .zip(mask.iter())
+            .filter(|(_, mask)| **mask != 0)
+            .map(|(token, _)| token)
+            .collect();
+        let (Some(first), Some(last)) = (tokens.first(), tokens.last()) else {
+            continue;
+        };
+        match pooling {
+            Pooling::Cls => embedding.assign(first),
+            Pooling::LastToken => embedding.assign(last),
+            Pooling::Max => {
+                embedding.assign(first);
+                for token in &tokens[1..] {
+                    embedding.zip_mut_with(token, |max, value| *max = max.max(*value));
+                }
+            }
+            Pooling::Mean | Pooling::MeanSqrtLen => {
+                for token in &tokens {
+                    embedding += token;
+                }
+                embedding /= match pooling {
+                    Pooling::MeanSqrtLen => (tokens.len() as f32).sqrt(),
+                    _ => tokens.len() as f32,
+                };
+            }
+        }
+    }
+    embeddings
+}
+
+/// Locates the model file and the directory containing the other files of the model.
+/// Checks that `model` is a path relative to the models directory that does not leave it.
+pub fn check_model_path(model: &str) -> std::result::Result<(), String> {
+    let path = Path::new(model);
+    if path.has_root() {
+        return Err(format!(
+            "`model` must be a path relative to the models directory, got the absolute path `{model}`."
+        ));
+    }
+    if !path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(format!("`model` must not contain `..`, got `{model}`."));
+    }
+    Ok(())
+}
+
+/// Returns the path of the `.onnx` file of the `model` and the directory containing its configuration.
+fn model_files(model: &str) -> std::result::Result<(PathBuf, PathBuf), NewEmbedderError> {
+    check_model_path(model).map_err(NewEmbedderError::onnx_invalid_model_path)?;
+    let models_dir =
+        std::env::var_os(MODELS_DIR_ENV).ok_or_else(NewEmbedderError::onnx_models_dir_not_set)?;
+    let models_dir = Path::new(&models_dir)
+        .canonicalize()
+        .map_err(|_| NewEmbedderError::onnx_models_dir_not_set())?;
+    let not_found = || NewEmbedderError::onnx_model_not_found(PathBuf::from(model));
+    // symbolic links are resolved so that they cannot point outside of the models directory
+    let path = models_dir.join(model).canonicalize().map_err(|_| not_found())?;
+    if !path.starts_with(&models_dir) {
+        return Err(NewEmbedderError::onnx_invalid_model_path(format!(
+            "`model` must not point outside of the models directory, got `{model}`."
+        )));
+    }
+
+    if path.is_file() {
+        let directory = path.parent().unwrap_or(&models_dir);
+        // models exported by `optimum` keep their `.onnx` files in a dedicated subdirectory
+        let directory = match directory.parent() {
+            Some(parent) if directory.ends_with("onnx") && parent.starts_with(&models_dir) => {
+                parent
+            }
+            _ => directory,
+        };
+        return Ok((path.clone(), directory.to_owned()));
+    }
+    [path.join("model.onnx"), path.join("onnx").join("model.onnx")]
+        .into_iter()
+        .find(|filename| filename.is_file())
+        .map(|filename| (filename, path.clone()))
+        .ok_or_else(not_found)
+}
+
+/// Reads the file at `path`, returning `None` if it does not exist.
+fn read_optional(path: &Path) -> std::io::Result<Option<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
@@ -6,6 +6,8 @@ use rayon::iter::{IntoParallelIterator, ParallelIterator as _};
 use rayon::slice::ParallelSlice as _;
 
 use super::error::{EmbedError, NewEmbedderError};
+use super::persistent_cache::CacheScope;
+use super::rate_limit::RateLimits;
 use super::rest::{Embedder as RestEmbedder, EmbedderOptions as RestEmbedderOptions};
 use super::{DistributionShift, EmbeddingCache, REQUEST_PARALLELISM};
 use crate::error::FaultSource;
@@ -20,9 +22,21 @@ pub struct EmbedderOptions {
     pub embedding_model: EmbeddingModel,
     pub dimensions: Option<usize>,
     pub distribution: Option<DistributionShift>,
+    #[serde(default)]
+    pub rate_limits: RateLimits,
 }
 
 impl EmbedderOptions {
+    pub fn cache_scope(&self) -> CacheScope<'_> {
+        CacheScope {
+            source: "openAi",
+            url: self.url.as_deref(),
+            model: Some(self.embedding_model.name()),
+            dimensions: Some(self.dimensions()),
+            ..Default::default()
+        }
+    }
+
     pub fn dimensions(&self) -> usize {
         if self.embedding_model.supports_overriding_dimensions() {
             self.dimensions.unwrap_or(self.embedding_model.default_dimensions())
@@ -159,6 +173,7 @@ impl EmbedderOptions {
             dimensions: None,
             distribution: None,
             url: None,
+            rate_limits: Default::default(),
         }
     }
 }
@@ -200,6 +215,7 @@ impl Embedder {
                     ]
                 }),
                 headers: Default::default(),
+                rate_limits: options.rate_limits,
             },
             cache_cap,
             super::rest::ConfigurationSource::OpenAi,
@@ -323,6 +339,10 @@ impl Embedder {
     pub(super) fn cache(&self) -> &EmbeddingCache {
         self.rest_embedder.cache()
     }
+
+    pub(super) fn cache_mut(&mut self) -> &mut EmbeddingCache {
+        self.rest_embedder.cache_mut()
+    }
 }
 
 impl fmt::Debug for Embedder {
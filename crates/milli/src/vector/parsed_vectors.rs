@@ -5,7 +5,7 @@ use obkv::KvReader;
 use serde_json::value::RawValue;
 use serde_json::{from_slice, Value};
 
-use super::Embedding;
+use super::{Embedder, Embedding};
 use crate::index::IndexEmbeddingConfig;
 use crate::update::del_add::{DelAdd, KvReaderDelAdd};
 use crate::{DocumentId, FieldId, InternalError, UserError};
@@ -623,9 +623,98 @@ impl From<Vec<Embedding>> for VectorOrArrayOfVectors {
     }
 }
 
+/// The `vector` of a search query.
+///
+/// Multi-vector embedders are searched with a matrix, one vector per row,
+/// the other embedders with a single vector.
+#[derive(serde::Serialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum QueryVector {
+    Single(Embedding),
+    Matrix(Vec<Embedding>),
+}
+
+impl<E: DeserializeError> Deserr<E> for QueryVector {
+    fn deserialize_from_value<V: deserr::IntoValue>(
+        value: deserr::Value<V>,
+        location: deserr::ValuePointerRef<'_>,
+    ) -> Result<Self, E> {
+        match value {
+            deserr::Value::Sequence(seq) => {
+                let values: Vec<_> = seq.into_iter().map(|v| v.into_value()).collect();
+                // `[]` is an empty vector rather than a matrix without rows
+                if matches!(values.first(), Some(deserr::Value::Sequence(_))) {
+                    let matrix = values
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, v)| Embedding::deserialize_from_value(v, location.push_index(i)))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(QueryVector::Matrix(matrix))
+                } else {
+                    let vector = values
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, v)| <f32>::deserialize_from_value(v, location.push_index(i)))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(QueryVector::Single(vector))
+                }
+            }
+            value => Err(take_cf_content(E::error(
+                None,
+                deserr::ErrorKind::IncorrectValueKind {
+                    actual: value,
+                    accepted: &[deserr::ValueKind::Sequence],
+                },
+                location,
+            ))),
+        }
+    }
+}
+
+impl QueryVector {
+    /// Number of values of the vector, or of all the rows of the matrix.
+    pub fn len(&self) -> usize {
+        match self {
+            QueryVector::Single(vector) => vector.len(),
+            QueryVector::Matrix(matrix) => matrix.iter().map(Vec::len).sum(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks that the query vector has the shape and dimensions expected by the embedder.
+    pub fn check_dimensions(&self, embedder: &Embedder) -> Result<(), UserError> {
+        let expected = embedder.dimensions();
+        match self {
+            QueryVector::Single(_) if embedder.is_multi_vector() => {
+                Err(UserError::ExpectedMultiVectorQuery)
+            }
+            QueryVector::Single(vector) if vector.len() != expected => {
+                Err(UserError::InvalidVectorDimensions { expected, found: vector.len() })
+            }
+            QueryVector::Single(_) => Ok(()),
+            QueryVector::Matrix(_) if !embedder.is_multi_vector() => {
+                Err(UserError::UnexpectedMultiVectorQuery)
+            }
+            QueryVector::Matrix(matrix) => {
+                match matrix.iter().position(|row| row.len() != expected) {
+                    Some(row) => Err(UserError::InvalidMultiVectorDimensions {
+                        row,
+                        expected,
+                        found: matrix[row].len(),
+                    }),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::VectorOrArrayOfVectors;
+    use super::{QueryVector, VectorOrArrayOfVectors};
 
     fn embedding_from_str(s: &str) -> Result<VectorOrArrayOfVectors, deserr::errors::JsonError> {
         let value: serde_json::Value = serde_json::from_str(s).unwrap();
@@ -679,4 +768,24 @@ mod test {
         ]
         "###);
     }
+
+    #[test]
+    fn query_vector() {
+        let query = |s: &str| -> QueryVector {
+            let value: serde_json::Value = serde_json::from_str(s).unwrap();
+            deserr::deserialize::<_, _, deserr::errors::JsonError>(value).unwrap()
+        };
+
+        assert_eq!(query("[]"), QueryVector::Single(vec![]));
+        assert_eq!(query("[0.1, 0.2]"), QueryVector::Single(vec![0.1, 0.2]));
+        assert_eq!(query("[[0.1, 0.2]]"), QueryVector::Matrix(vec![vec![0.1, 0.2]]));
+        assert_eq!(
+            query("[[0.1, 0.2], [0.3]]"),
+            QueryVector::Matrix(vec![vec![0.1, 0.2], vec![0.3]])
+        );
+
+        // the rows of a matrix cannot be mixed with values
+        let value = serde_json::json!([[0.1], 0.2]);
+        assert!(deserr::deserialize::<QueryVector, _, deserr::errors::JsonError>(value).is_err());
+    }
 }
@@ -0,0 +1,201 @@
+//! An embedding cache persisted on disk in its own LMDB environment.
+//!
+//! The cache is shared between all indexes and is consulted both when embedding search queries and when embedding documents,
+//! so that regenerating the embeddings of unchanged texts does not require calling the embedder again.
+//!
+//! Entries are keyed by a hash of the options of the embedder changing its embeddings, see [`CacheScope`],
+//! and a hash of the rendered text.
+//! The text is stored along with the embedding so that hash collisions are detected and treated as misses.
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use heed::types::{Bytes, SerdeBincode};
+use heed::{Database, Env, EnvOpenOptions, MdbError, WithoutTls};
+use serde::{Deserialize, Serialize};
+
+use super::hf::OverridePooling;
+use super::Embedding;
+
+const EMBEDDINGS_DB_NAME: &str = "embeddings";
+
+/// Number of embeddings put one at a time, such as the embeddings of search queries,
+/// that are kept in memory to be written to the cache at once.
+const PENDING_ENTRIES_BATCH: usize = 64;
+
+/// The options of an embedder that change the embeddings it produces.
+///
+/// Credentials and rate limits are not part of the scope, so that changing them keeps the cached embeddings.
+#[derive(Debug, Default, Serialize)]
+pub struct CacheScope<'a> {
+    pub source: &'static str,
+    pub url: Option<&'a str>,
+    pub model: Option<&'a str>,
+    pub revision: Option<&'a str>,
+    pub request: Option<&'a serde_json::Value>,
+    pub response: Option<&'a serde_json::Value>,
+    pub dimensions: Option<usize>,
+    pub pooling: Option<OverridePooling>,
+    pub normalize: Option<bool>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct CachedEmbedding {
+    text: String,
+    embedding: Embedding,
+}
+
+/// Handle to the persistent embedding cache, scoped to the options of a single embedder.
+#[derive(Clone)]
+pub struct PersistentEmbeddingCache {
+    env: Env<WithoutTls>,
+    embeddings: Database<Bytes, SerdeBincode<CachedEmbedding>>,
+    /// Entries waiting to be written along with the next ones, shared by all the handles to the cache.
+    pending: Arc<Mutex<Vec<([u8; 16], CachedEmbedding)>>>,
+    /// Hash of the [`CacheScope`] of the embedder using the cache.
+    scope: u64,
+}
+
+impl std::fmt::Debug for PersistentEmbeddingCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PersistentEmbeddingCache")
+            .field("path", &self.env.path())
+            .field("scope", &self.scope)
+            .finish()
+    }
+}
+
+impl PersistentEmbeddingCache {
+    /// Opens or creates the cache at the specified path, with a maximum size of `map_size` bytes.
+    pub fn open(path: &Path, map_size: usize) -> heed::Result<Self> {
+        std::fs::create_dir_all(path)?;
+        let env = unsafe {
+            let options = EnvOpenOptions::new();
+            let mut options = options.read_txn_without_tls();
+            options.map_size(map_size).max_dbs(1).open(path)
+        }?;
+        let mut wtxn = env.write_txn()?;
+        let embeddings = env.create_database(&mut wtxn, Some(EMBEDDINGS_DB_NAME))?;
+        wtxn.commit()?;
+        Ok(Self { env, embeddings, pending: Default::default(), scope: 0 })
+    }
+
+    /// A handle to the same cache, whose entries are only shared with the embedders having the same `scope`.
+    pub fn scoped(&self, scope: &CacheScope<'_>) -> Self {
+        let scope = serde_json::to_vec(scope).unwrap_or_default();
+        Self { scope: fxhash::hash64(&scope), ..self.clone() }
+    }
+
+    fn key(&self, text: &str) -> [u8; 16] {
+        let mut key = [0; 16];
+        key[..8].copy_from_slice(&self.scope.to_be_bytes());
+        key[8..].copy_from_slice(&fxhash::hash64(text.as_bytes()).to_be_bytes());
+        key
+    }
+
+    /// Get the embedding corresponding to `text`, if any is present in the cache.
+    pub fn get(&self, text: &str) -> Option<Embedding> {
+        self.get_many(&[text]).pop().flatten()
+    }
+
+    /// Get the embeddings corresponding to each of the `texts`, if present in the cache.
+    pub fn get_many(&self, texts: &[&str]) -> Vec<Option<Embedding>> {
+        let get_many = || -> heed::Result<Vec<Option<Embedding>>> {
+            let rtxn = self.env.read_txn()?;
+            texts
+                .iter()
+                .map(|text| {
+                    let cached = self.embeddings.get(&rtxn, &self.key(text))?;
+                    // the text is compared to rule out hash collisions
+                    Ok(cached.filter(|cached| cached.text == *text).map(|cached| cached.embedding))
+                })
+                .collect()
+        };
+
+        let mut embeddings = match get_many() {
+            Ok(embeddings) => embeddings,
+            Err(error) => {
+                tracing::warn!(%error, "could not read from the embedding cache");
+                vec![None; texts.len()]
+            }
+        };
+
+        if embeddings.iter().any(Option::is_none) {
+            let pending = self.pending.lock().unwrap();
+            for (text, embedding) in texts.iter().zip(&mut embeddings) {
+                if embedding.is_some() {
+                    continue;
+                }
+                let key = self.key(text);
+                *embedding = pending
+                    .iter()
+                    .rev()
+                    .find(|(pending_key, cached)| *pending_key == key && cached.text == *text)
+                    .map(|(_, cached)| cached.embedding.clone());
+            }
+        }
+        embeddings
+    }
+
+    /// Puts a new embedding for the specified `text`.
+    ///
+    /// The embedding is kept in memory and written to the cache along with the next entries,
+    /// so that search queries do not each open a write transaction.
+    pub fn put(&self, text: &str, embedding: &Embedding) {
+        let entry = (
+            self.key(text),
+            CachedEmbedding { text: text.to_owned(), embedding: embedding.clone() },
+        );
+        let mut pending = self.pending.lock().unwrap();
+        pending.push(entry);
+        if pending.len() < PENDING_ENTRIES_BATCH {
+            return;
+        }
+        let entries = std::mem::take(&mut *pending);
+        drop(pending);
+        self.write(entries);
+    }
+
+    /// Puts new embeddings for the specified texts, typically a chunk of embedded documents.
+    ///
+    /// The embeddings are written in a single write transaction, along with the pending entries.
+    pub fn put_many<'a>(&self, entries: impl IntoIterator<Item = (&'a str, &'a Embedding)>) {
+        let mut batch = std::mem::take(&mut *self.pending.lock().unwrap());
+        batch.extend(entries.into_iter().map(|(text, embedding)| {
+            (
+                self.key(text),
+                CachedEmbedding { text: text.to_owned(), embedding: embedding.clone() },
+            )
+        }));
+        if !batch.is_empty() {
+            self.write(batch);
+        }
+    }
+
+    /// Writes the `entries` in a single write transaction.
+    ///
+    /// When the cache is full, it is emptied before storing the new embeddings.
+    fn write(&self, entries: Vec<([u8; 16], CachedEmbedding)>) {
+        let write = |clear: bool| -> heed::Result<()> {
+            let mut wtxn = self.env.write_txn()?;
+            if clear {
+                self.embeddings.clear(&mut wtxn)?;
+            }
+            for (key, cached) in &entries {
+                self.embeddings.put(&mut wtxn, key, cached)?;
+            }
+            wtxn.commit()
+        };
+
+        let result = match write(false) {
+            Err(heed::Error::Mdb(MdbError::MapFull)) => {
+                tracing::debug!("embedding cache is full, clearing it");
+                write(true)
+            }
+            result => result,
+        };
+        if let Err(error) = result {
+            tracing::warn!(%error, "could not write to the embedding cache");
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! Limits on the requests sent to remote embedders.
+//!
+//! Embedding providers usually enforce quotas per API key, that are shared by all the indexes using that key.
+//! So that these quotas are respected whatever the number of indexes, tasks and searches running at once,
+//! limiters are shared by all the embedders of the process targeting the same URL with the same API key and limits.
+//! Limiters are dropped once no embedder uses them anymore.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+
+use super::error::EmbedError;
+
+/// Limits on the requests sent by an embedder, as configured in its settings.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+pub struct RateLimits {
+    /// Maximum number of requests in flight at the same time.
+    pub max_concurrent_requests: Option<usize>,
+    /// Maximum number of requests started in a minute.
+    pub requests_per_minute: Option<u64>,
+    /// Maximum number of tokens sent in a minute, estimated from the size of the requests.
+    pub tokens_per_minute: Option<u64>,
+}
+
+impl RateLimits {
+    pub fn is_unlimited(&self) -> bool {
+        self.max_concurrent_requests.is_none()
+            && self.requests_per_minute.is_none()
+            && self.tokens_per_minute.is_none()
+    }
+}
+
+/// Rough number of bytes per token, used to estimate the number of tokens of a request.
+const BYTES_PER_TOKEN: usize = 4;
+
+/// Hash of the URL and API key of the embedders sharing a limiter, so that API keys are not retained, and their limits.
+type LimiterKey = (u64, RateLimits);
+
+static LIMITERS: Lazy<Mutex<BTreeMap<LimiterKey, Arc<RateLimiter>>>> = Lazy::new(Default::default);
+
+/// Enforces [`RateLimits`] on the requests of all the embedders sharing it.
+#[derive(Debug)]
+pub struct RateLimiter {
+    limits: RateLimits,
+    state: Mutex<State>,
+    /// Notified when a request finishes, freeing a concurrency slot.
+    slot_freed: Condvar,
+}
+
+#[derive(Debug)]
+struct State {
+    in_flight: usize,
+    requests: Option<Bucket>,
+    tokens: Option<Bucket>,
+}
+
+/// A token bucket refilled continuously, holding at most a minute worth of capacity.
+#[derive(Debug)]
+struct Bucket {
+    per_minute: f64,
+    available: f64,
+    last_refill: Instant,
+}
+
+impl Bucket {
+    fn new(per_minute: u64) -> Self {
+        let per_minute = per_minute as f64;
+        Self { per_minute, available: per_minute, last_refill: Instant::now() }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.available = (self.available + elapsed * self.per_minute / 60.0).min(self.per_minute);
+        self.last_refill = now;
+    }
+
+    /// Duration to wait before `amount` is available.
+    fn wait_time(&self, amount: f64) -> Duration {
+        // a single request can never exceed the capacity of the bucket
+        let missing = amount.min(self.per_minute) - self.available;
+        if missing <= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(missing * 60.0 / self.per_minute)
+        }
+    }
+
+    fn take(&mut self, amount: f64) {
+        self.available -= amount.min(self.per_minute);
+    }
+}
+
+/// A request authorized by a [`RateLimiter`], releasing its concurrency slot when dropped.
+pub struct Permit<'a> {
+    limiter: &'a RateLimiter,
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        if self.limiter.limits.max_concurrent_requests.is_some() {
+            let mut state = self.limiter.state.lock().unwrap();
+            state.in_flight -= 1;
+            drop(state);
+            self.limiter.slot_freed.notify_one();
+        }
+    }
+}
+
+impl RateLimiter {
+    /// The limiter shared by the embedders sending requests to `url` with `api_key`, or `None` if `limits` are unlimited.
+    ///
+    /// As this is called when embedders are built, the limiters of the embedders dropped since are dropped as well.
+    pub fn shared(url: &str, api_key: Option<&str>, limits: RateLimits) -> Option<Arc<Self>> {
+        let mut limiters = LIMITERS.lock().unwrap();
+        // only referenced by the map
+        limiters.retain(|_, limiter| Arc::strong_count(limiter) > 1);
+        if limits.is_unlimited() {
+            return None;
+        }
+        let key = (fxhash::hash64(&(url, api_key)), limits);
+        Some(limiters.entry(key).or_insert_with(|| Arc::new(Self::new(limits))).clone())
+    }
+
+    fn new(limits: RateLimits) -> Self {
+        Self {
+            limits,
+            state: Mutex::new(State {
+                in_flight: 0,
+                requests: limits.requests_per_minute.map(Bucket::new),
+                tokens: limits.tokens_per_minute.map(Bucket::new),
+            }),
+            slot_freed: Condvar::new(),
+        }
+    }
+
+    /// Waits until a request of `request_bytes` bytes can be sent.
+    ///
+    /// Fails if the request cannot be sent before the `deadline`.
+    pub fn acquire(
+        &self,
+        request_bytes: usize,
+        deadline: Option<Instant>,
+    ) -> Result<Permit<'_>, EmbedError> {
+        let tokens = request_bytes.div_ceil(BYTES_PER_TOKEN) as f64;
+        let mut state = self.state.lock().unwrap();
+        loop {
+            let now = Instant::now();
+            if let Some(max) = self.limits.max_concurrent_requests {
+                if state.in_flight >= max {
+                    state = match deadline {
+                        Some(deadline) if deadline <= now => {
+                            return Err(EmbedError::rate_limit_deadline_exceeded())
+                        }
+                        Some(deadline) => {
+                            self.slot_freed.wait_timeout(state, deadline - now).unwrap().0
+                        }
+                        None => self.slot_freed.wait(state).unwrap(),
+                    };
+                    continue;
+                }
+            }
+
+            let mut wait = Duration::ZERO;
+            if let Some(requests) = &mut state.requests {
+                requests.refill(now);
+                wait = wait.max(requests.wait_time(1.0));
+            }
+            if let Some(bucket) = &mut state.tokens {
+                bucket.refill(now);
+                wait = wait.max(bucket.wait_time(tokens));
+            }
+
+            if wait.is_zero() {
+                state.in_flight += 1;
+                if let Some(requests) = &mut state.requests {
+                    requests.take(1.0);
+                }
+                if let Some(bucket) = &mut state.tokens {
+                    bucket.take(tokens);
+                }
+                return Ok(Permit { limiter: self });
+            }
+
+            if deadline.is_some_and(|deadline| now + wait > deadline) {
+                return Err(EmbedError::rate_limit_deadline_exceeded());
+            }
+            // release the lock while waiting, so that other requests can finish
+            drop(state);
+            tracing::debug!(wait_ms = wait.as_millis(), "waiting for the embedder rate limit");
+            std::thread::sleep(wait);
+            state = self.state.lock().unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn concurrent_requests() {
+        let limits = RateLimits { max_concurrent_requests: Some(1), ..Default::default() };
+        let limiter = RateLimiter::new(limits);
+        let permit = limiter.acquire(10, None).unwrap();
+        // the only slot is taken
+        let deadline = Instant::now() + Duration::from_millis(10);
+        assert!(limiter.acquire(10, Some(deadline)).is_err());
+        drop(permit);
+        limiter.acquire(10, Some(deadline)).unwrap();
+    }
+
+    #[test]
+    fn requests_per_minute() {
+        let limits = RateLimits { requests_per_minute: Some(2), ..Default::default() };
+        let limiter = RateLimiter::new(limits);
+        let deadline = Instant::now() + Duration::from_millis(10);
+        limiter.acquire(10, Some(deadline)).unwrap();
+        limiter.acquire(10, Some(deadline)).unwrap();
+        // the next request is only allowed in 30 seconds
+        assert!(limiter.acquire(10, Some(deadline)).is_err());
+    }
+
+    #[test]
+    fn tokens_per_minute() {
+        let limits = RateLimits { tokens_per_minute: Some(100), ..Default::default() };
+        let limiter = RateLimiter::new(limits);
+        let deadline = Instant::now() + Duration::from_millis(10);
+        // 75 tokens
+        limiter.acquire(300, Some(deadline)).unwrap();
+        assert!(limiter.acquire(300, Some(deadline)).is_err());
+        // a request larger than the quota is sent when the quota is full
+        let limiter = RateLimiter::new(limits);
+        limiter.acquire(1000, Some(deadline)).unwrap();
+    }
+
+    #[test]
+    fn shared_limiters() {
+        let limits = RateLimits { requests_per_minute: Some(10), ..Default::default() };
+        let a = RateLimiter::shared("http://localhost/embed", Some("key"), limits).unwrap();
+        let b = RateLimiter::shared("http://localhost/embed", Some("key"), limits).unwrap();
+        let c = RateLimiter::shared("http://localhost/embed", Some("other"), limits).unwrap();
+        assert!(Arc::ptr_eq(&a, &b));
+        assert!(!Arc::ptr_eq(&a, &c));
+        assert!(
+            RateLimiter::shared("http://localhost/embed", None, RateLimits::default()).is_none()
+        );
+    }
+
+    #[test]
+    fn unused_limiters_are_dropped() {
+        let limits = RateLimits { requests_per_minute: Some(11), ..Default::default() };
+        let limiter = RateLimiter::shared("http://localhost/dropped", Some("key"), limits).unwrap();
+        let weak = Arc::downgrade(&limiter);
+        drop(limiter);
+        // building another embedder drops the unused limiters
+        let _other = RateLimiter::shared("http://localhost/other", Some("key"), limits).unwrap();
+        assert!(weak.upgrade().is_none());
+    }
+}
@@ -1,4 +1,5 @@
 use std::collections::BTreeMap;
+use std::sync::Arc;
 use std::time::Instant;
 
 use deserr::Deserr;
@@ -9,8 +10,11 @@ use serde::{Deserialize, Serialize};
 
 use super::error::EmbedErrorKind;
 use super::json_template::ValueTemplate;
+use super::persistent_cache::CacheScope;
+use super::rate_limit::{RateLimiter, RateLimits};
 use super::{
-    DistributionShift, EmbedError, Embedding, EmbeddingCache, NewEmbedderError, REQUEST_PARALLELISM,
+    DistributionShift, EmbedError, EmbedderInput, Embedding, EmbeddingCache, NewEmbedderError,
+    REQUEST_PARALLELISM,
 };
 use crate::error::FaultSource;
 use crate::ThreadPoolNoAbort;
@@ -90,6 +94,7 @@ struct EmbedderData {
     request: Request,
     response: Response,
     configuration_source: ConfigurationSource,
+    limiter: Option<Arc<RateLimiter>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
@@ -101,6 +106,8 @@ pub struct EmbedderOptions {
     pub request: serde_json::Value,
     pub response: serde_json::Value,
     pub headers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub rate_limits: RateLimits,
 }
 
 impl std::hash::Hash for EmbedderOptions {
@@ -115,6 +122,19 @@ impl std::hash::Hash for EmbedderOptions {
     }
 }
 
+impl EmbedderOptions {
+    pub fn cache_scope(&self) -> CacheScope<'_> {
+        CacheScope {
+            source: "rest",
+            url: Some(&self.url),
+            request: Some(&self.request),
+            response: Some(&self.response),
+            dimensions: self.dimensions,
+            ..Default::default()
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash, Deserr)]
 #[serde(rename_all = "camelCase")]
 #[deserr(rename_all = camelCase, deny_unknown_fields)]
@@ -139,6 +159,8 @@ impl Embedder {
 
         let request = Request::new(options.request)?;
         let response = Response::new(options.response, &request)?;
+        let limiter =
+            RateLimiter::shared(&options.url, options.api_key.as_deref(), options.rate_limits);
 
         let data = EmbedderData {
             client,
@@ -148,6 +170,7 @@ impl Embedder {
             response,
             configuration_source,
             headers: options.headers,
+            limiter,
         };
 
         let dimensions = if let Some(dimensions) = options.dimensions {
@@ -169,7 +192,8 @@ impl Embedder {
         texts: Vec<String>,
         deadline: Option<Instant>,
     ) -> Result<Vec<Embedding>, EmbedError> {
-        embed(&self.data, texts.as_slice(), texts.len(), Some(self.dimensions), deadline)
+        let body = self.data.request.inject_texts(&texts);
+        embed(&self.data, body, texts.len(), Some(self.dimensions), deadline)
     }
 
     pub fn embed_ref<S>(
@@ -180,7 +204,39 @@ impl Embedder {
     where
         S: AsRef<str> + Serialize,
     {
-        embed(&self.data, texts, texts.len(), Some(self.dimensions), deadline)
+        let body = self.data.request.inject_texts(texts);
+        embed(&self.data, body, texts.len(), Some(self.dimensions), deadline)
+    }
+
+    fn embed_inputs(
+        &self,
+        inputs: &[EmbedderInput<'_>],
+        deadline: Option<Instant>,
+    ) -> Result<Vec<Embedding>, EmbedError> {
+        // requests with a media have a single input
+        let media = match inputs.iter().find_map(EmbedderInput::media) {
+            // unwrap: the raw value was parsed from a valid JSON document
+            Some(media) => serde_json::from_str(media.get()).unwrap(),
+            None => serde_json::Value::Null,
+        };
+        let body = self.data.request.inject(inputs.iter().map(EmbedderInput::text), &media);
+        embed(&self.data, body, inputs.len(), Some(self.dimensions), deadline)
+    }
+
+    /// Embeds `text` along with a `media`, for embedders whose `request` contains a media placeholder.
+    pub fn embed_media(
+        &self,
+        text: &str,
+        media: &serde_json::Value,
+        deadline: Option<Instant>,
+    ) -> Result<Embedding, EmbedError> {
+        if self.media_field().is_none() {
+            return Err(EmbedError::media_not_supported());
+        }
+        let body = self.data.request.inject([text], media);
+        let mut embeddings = embed(&self.data, body, 1, Some(self.dimensions), deadline)?;
+        // unwrap: guaranteed that embeddings.len() == 1, otherwise the previous line terminated in error
+        Ok(embeddings.pop().unwrap())
     }
 
     pub fn embed_tokens(
@@ -188,7 +244,8 @@ impl Embedder {
         tokens: &[u32],
         deadline: Option<Instant>,
     ) -> Result<Embedding, EmbedError> {
-        let mut embeddings = embed(&self.data, tokens, 1, Some(self.dimensions), deadline)?;
+        let body = self.data.request.inject_texts(tokens);
+        let mut embeddings = embed(&self.data, body, 1, Some(self.dimensions), deadline)?;
         // unwrap: guaranteed that embeddings.len() == 1, otherwise the previous line terminated in error
         Ok(embeddings.pop().unwrap())
     }
@@ -216,15 +273,15 @@ impl Embedder {
 
     pub(crate) fn embed_index_ref(
         &self,
-        texts: &[&str],
+        inputs: &[EmbedderInput<'_>],
         threads: &ThreadPoolNoAbort,
     ) -> Result<Vec<Embedding>, EmbedError> {
         // This condition helps reduce the number of active rayon jobs
         // so that we avoid consuming all the LMDB rtxns and avoid stack overflows.
         if threads.active_operations() >= REQUEST_PARALLELISM {
-            let embeddings: Result<Vec<Vec<Embedding>>, _> = texts
+            let embeddings: Result<Vec<Vec<Embedding>>, _> = inputs
                 .chunks(self.prompt_count_in_chunk_hint())
-                .map(move |chunk| self.embed_ref(chunk, None))
+                .map(move |chunk| self.embed_inputs(chunk, None))
                 .collect();
 
             let embeddings = embeddings?;
@@ -232,9 +289,9 @@ impl Embedder {
         } else {
             threads
                 .install(move || {
-                    let embeddings: Result<Vec<Vec<Embedding>>, _> = texts
+                    let embeddings: Result<Vec<Vec<Embedding>>, _> = inputs
                         .par_chunks(self.prompt_count_in_chunk_hint())
-                        .map(move |chunk| self.embed_ref(chunk, None))
+                        .map(move |chunk| self.embed_inputs(chunk, None))
                         .collect();
 
                     let embeddings = embeddings?;
@@ -266,28 +323,34 @@ impl Embedder {
         self.distribution
     }
 
+    /// The document field injected in place of the media placeholder of the `request`, if any.
+    pub fn media_field(&self) -> Option<&str> {
+        self.data.request.media_field()
+    }
+
     pub(super) fn cache(&self) -> &EmbeddingCache {
         &self.cache
     }
+
+    pub(super) fn cache_mut(&mut self) -> &mut EmbeddingCache {
+        &mut self.cache
+    }
 }
 
 fn infer_dimensions(data: &EmbedderData) -> Result<usize, NewEmbedderError> {
-    let v = embed(data, ["test"].as_slice(), 1, None, None)
+    let v = embed(data, data.request.inject_texts(["test"]), 1, None, None)
         .map_err(NewEmbedderError::could_not_determine_dimension)?;
     // unwrap: guaranteed that v.len() == 1, otherwise the previous line terminated in error
     Ok(v.first().unwrap().len())
 }
 
-fn embed<S>(
+fn embed(
     data: &EmbedderData,
-    inputs: &[S],
+    body: serde_json::Value,
     expected_count: usize,
     expected_dimension: Option<usize>,
     deadline: Option<Instant>,
-) -> Result<Vec<Embedding>, EmbedError>
-where
-    S: Serialize,
-{
+) -> Result<Vec<Embedding>, EmbedError> {
     let request = data.client.post(&data.url);
     let request = if let Some(bearer) = &data.bearer {
         request.set("Authorization", bearer)
@@ -299,10 +362,15 @@ where
         request = request.set(header.as_str(), value.as_str());
     }
 
-    let body = data.request.inject_texts(inputs);
+    let body_bytes = if data.limiter.is_some() { body.to_string().len() } else { 0 };
 
     for attempt in 0..10 {
+        let permit = match &data.limiter {
+            Some(limiter) => Some(limiter.acquire(body_bytes, deadline)?),
+            None => None,
+        };
         let response = request.clone().send_json(&body);
+        drop(permit);
         let result = check_response(response, data.configuration_source).and_then(|response| {
             response_to_embedding(response, data, expected_count, expected_dimension)
         });
@@ -336,7 +404,12 @@ where
         std::thread::sleep(retry_duration);
     }
 
+    let permit = match &data.limiter {
+        Some(limiter) => Some(limiter.acquire(body_bytes, deadline)?),
+        None => None,
+    };
     let response = request.send_json(&body);
+    drop(permit);
     let result = check_response(response, data.configuration_source);
     result.map_err(Retry::into_error).and_then(|response| {
         response_to_embedding(response, data, expected_count, expected_dimension)
@@ -414,14 +487,25 @@ fn response_to_embedding(
 pub(super) const REQUEST_PLACEHOLDER: &str = "{{text}}";
 pub(super) const RESPONSE_PLACEHOLDER: &str = "{{embedding}}";
 pub(super) const REPEAT_PLACEHOLDER: &str = "{{..}}";
+pub(super) const MEDIA_PLACEHOLDER_PREFIX: &str = "{{doc.";
+pub(super) const MEDIA_PLACEHOLDER_SUFFIX: &str = "}}";
+
+/// A `"{{doc.<field>}}"` string of a request template.
+#[derive(Debug)]
+struct MediaPlaceholder {
+    placeholder: String,
+    field: String,
+}
 
 #[derive(Debug)]
 pub struct Request {
     template: ValueTemplate,
+    media: Option<MediaPlaceholder>,
 }
 
 impl Request {
     pub fn new(template: serde_json::Value) -> Result<Self, NewEmbedderError> {
+        let template_value = template.clone();
         let template = match ValueTemplate::new(template, REQUEST_PLACEHOLDER, REPEAT_PLACEHOLDER) {
             Ok(template) => template,
             Err(error) => {
@@ -431,7 +515,34 @@ impl Request {
             }
         };
 
-        Ok(Self { template })
+        let media = match media_placeholders(&template_value).as_slice() {
+            [] => None,
+            [placeholder] => Some(MediaPlaceholder {
+                field: media_field(placeholder).to_owned(),
+                placeholder: placeholder.to_string(),
+            }),
+            [first, second, ..] => {
+                return Err(NewEmbedderError::rest_could_not_parse_template(format!(
+                    r#"in `request`: Found "{second}", but "{first}" is already present. Only one media can be sent per request"#
+                )))
+            }
+        };
+
+        if let Some(media) = &media {
+            if template.has_array_value() {
+                return Err(NewEmbedderError::rest_could_not_parse_template(format!(
+                    r#"in `request`: "{}" cannot be used in a request containing "{REPEAT_PLACEHOLDER}", as only one media can be sent per request"#,
+                    media.placeholder
+                )));
+            }
+        }
+
+        Ok(Self { template, media })
+    }
+
+    /// The document field injected in place of the media placeholder, if any.
+    pub fn media_field(&self) -> Option<&str> {
+        self.media.as_ref().map(|media| media.field.as_str())
     }
 
     fn input_type(&self) -> InputType {
@@ -446,7 +557,65 @@ impl Request {
         &self,
         texts: impl IntoIterator<Item = S>,
     ) -> serde_json::Value {
-        self.template.inject(texts.into_iter().map(|s| serde_json::json!(s))).unwrap()
+        self.inject(texts, &serde_json::Value::Null)
+    }
+
+    /// Injects the texts, and the media in place of the media placeholder, if any.
+    ///
+    /// Requests with a media placeholder have a single input.
+    pub fn inject<S: Serialize>(
+        &self,
+        texts: impl IntoIterator<Item = S>,
+        media: &serde_json::Value,
+    ) -> serde_json::Value {
+        let texts = texts.into_iter().map(|s| serde_json::json!(s));
+        let mut request = self.template.inject(texts).unwrap();
+        if let Some(MediaPlaceholder { placeholder, .. }) = &self.media {
+            replace_placeholder(&mut request, placeholder, media);
+        }
+        request
+    }
+}
+
+fn media_placeholders(value: &serde_json::Value) -> Vec<&str> {
+    let mut placeholders = Vec::new();
+    let mut stack = vec![value];
+    while let Some(value) = stack.pop() {
+        match value {
+            serde_json::Value::String(s)
+                if s.starts_with(MEDIA_PLACEHOLDER_PREFIX)
+                    && s.ends_with(MEDIA_PLACEHOLDER_SUFFIX)
+                    && !placeholders.contains(&s.as_str()) =>
+            {
+                placeholders.push(s.as_str())
+            }
+            serde_json::Value::Array(values) => stack.extend(values.iter().rev()),
+            serde_json::Value::Object(map) => stack.extend(map.values().rev()),
+            _ => {}
+        }
+    }
+    placeholders
+}
+
+fn media_field(placeholder: &str) -> &str {
+    placeholder[MEDIA_PLACEHOLDER_PREFIX.len()..placeholder.len() - MEDIA_PLACEHOLDER_SUFFIX.len()]
+        .trim()
+}
+
+fn replace_placeholder(
+    value: &mut serde_json::Value,
+    placeholder: &str,
+    media: &serde_json::Value,
+) {
+    match value {
+        serde_json::Value::String(s) if s == placeholder => *value = media.clone(),
+        serde_json::Value::Array(values) => {
+            values.iter_mut().for_each(|value| replace_placeholder(value, placeholder, media))
+        }
+        serde_json::Value::Object(map) => {
+            map.values_mut().for_each(|value| replace_placeholder(value, placeholder, media))
+        }
+        _ => {}
     }
 }
 
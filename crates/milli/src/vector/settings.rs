@@ -8,6 +8,7 @@ use utoipa::ToSchema;
 
 use super::composite::SubEmbedderOptions;
 use super::hf::OverridePooling;
+use super::rate_limit::RateLimits;
 use super::{ollama, openai, DistributionShift, EmbedderOptions};
 use crate::prompt::{default_max_bytes, PromptData};
 use crate::update::Setting;
@@ -38,13 +39,16 @@ pub struct EmbeddingSettings {
     #[schema(value_type = Option<String>)]
     /// The name of the model to use.
     ///
+    /// For source `onnx`, the path to a directory containing the model, or to the `.onnx` file of the model,
+    /// relative to the directory set by the `MEILI_EXPERIMENTAL_ONNX_MODELS_DIR` environment variable.
+    ///
     /// # Mandatory
     ///
-    /// - This parameter is mandatory for source `ollama`
+    /// - This parameter is mandatory for sources `ollama`, `onnx`
     ///
     /// # Availability
     ///
-    /// - This parameter is available for sources `openAi`, `huggingFace`, `ollama`
+    /// - This parameter is available for sources `openAi`, `huggingFace`, `ollama`, `onnx`
     ///
     /// # 🔄 Reindexing
     ///
@@ -82,7 +86,7 @@ pub struct EmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `huggingFace`
+    /// - This parameter is available for sources `huggingFace`, `onnx`
     ///
     /// # 🔄 Reindexing
     ///
@@ -98,6 +102,23 @@ pub struct EmbeddingSettings {
     pub pooling: Setting<OverridePooling>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
+    #[schema(value_type = Option<bool>)]
+    /// Whether to normalize the embeddings produced by the model to unit length.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for source `onnx`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🏗️ Changing the value of this parameter always regenerates embeddings
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to `true` if the `modules.json` of the model contains a `Normalize` module, `false` otherwise
+    pub normalize: Setting<bool>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
     #[schema(value_type = Option<String>)]
     /// The API key to pass to the remote embedder while making requests.
     ///
@@ -125,11 +146,11 @@ pub struct EmbeddingSettings {
     ///
     /// # Mandatory
     ///
-    /// - This parameter is mandatory for source `userProvided`
+    /// - This parameter is mandatory for sources `userProvided`, `userProvidedMultiVector`
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `openAi`, `ollama`, `rest`, `userProvided`
+    /// - This parameter is available for source `openAi`, `ollama`, `rest`, `userProvided`, `userProvidedMultiVector`
     ///
     /// # 🔄 Reindexing
     ///
@@ -151,7 +172,7 @@ pub struct EmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for all embedders
+    /// - This parameter is available for all embedders, except for sources `userProvidedSparse`, `userProvidedMultiVector`
     ///
     /// # 🔄 Reindexing
     ///
@@ -177,7 +198,7 @@ pub struct EmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama` and `rest
+    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama`, `onnx` and `rest`
     ///
     /// # 🔄 Reindexing
     ///
@@ -190,7 +211,7 @@ pub struct EmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama` and `rest`
+    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama`, `onnx` and `rest`
     ///
     /// # 🔄 Reindexing
     ///
@@ -224,6 +245,9 @@ pub struct EmbeddingSettings {
     #[schema(value_type = Option<serde_json::Value>)]
     /// Template request to send to the remote embedder.
     ///
+    /// A string of the form `"{{doc.<field>}}"` in the template is replaced by the value of that document field,
+    /// allowing to send media such as image URLs or base64-encoded images to multimodal embedders.
+    ///
     /// # Mandatory
     ///
     /// - This parameter is mandatory for source `rest`
@@ -266,6 +290,84 @@ pub struct EmbeddingSettings {
     ///
     /// - 🌱 Changing the value of this parameter never regenerates embeddings
     pub headers: Setting<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<usize>)]
+    /// Maximum number of requests sent at the same time to the remote embedder.
+    ///
+    /// The limit is shared by all the embedders that send requests to the same `url` with the same `apiKey`
+    /// and the same limits, in all indexes, for both indexing and search.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for sources `openAi`, `ollama`, `rest`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🌱 Changing the value of this parameter never regenerates embeddings
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to no limit
+    pub max_concurrent_requests: Setting<usize>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<u64>)]
+    /// Maximum number of requests sent to the remote embedder in a minute.
+    ///
+    /// The limit is shared like `maxConcurrentRequests`.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for sources `openAi`, `ollama`, `rest`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🌱 Changing the value of this parameter never regenerates embeddings
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to no limit
+    pub requests_per_minute: Setting<u64>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<u64>)]
+    /// Maximum number of tokens sent to the remote embedder in a minute.
+    ///
+    /// The number of tokens of a request is estimated from its size, at one token every 4 bytes.
+    /// The limit is shared like `maxConcurrentRequests`.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for sources `openAi`, `ollama`, `rest`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🌱 Changing the value of this parameter never regenerates embeddings
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to no limit
+    pub tokens_per_minute: Setting<u64>,
+
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<String>)]
+    /// Name of an existing embedder that this embedder is meant to replace.
+    ///
+    /// A shadow embedder is indexed in the background like any other embedder, while the embedder it shadows
+    /// keeps serving search requests. Once the shadow is complete, resetting this parameter to `null` promotes
+    /// the shadow: it atomically replaces the shadowed embedder, taking its name, and the shadowed embedder's
+    /// embeddings are deleted.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for all sources, except for `userProvidedSparse`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🌱 Promoting a shadow embedder never regenerates embeddings
+    pub shadow_of: Setting<String>,
 
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
@@ -316,13 +418,16 @@ pub struct SubEmbeddingSettings {
     #[schema(value_type = Option<String>)]
     /// The name of the model to use.
     ///
+    /// For source `onnx`, the path to a directory containing the model, or to the `.onnx` file of the model,
+    /// relative to the directory set by the `MEILI_EXPERIMENTAL_ONNX_MODELS_DIR` environment variable.
+    ///
     /// # Mandatory
     ///
-    /// - This parameter is mandatory for source `ollama`
+    /// - This parameter is mandatory for sources `ollama`, `onnx`
     ///
     /// # Availability
     ///
-    /// - This parameter is available for sources `openAi`, `huggingFace`, `ollama`
+    /// - This parameter is available for sources `openAi`, `huggingFace`, `ollama`, `onnx`
     ///
     /// # 🔄 Reindexing
     ///
@@ -360,7 +465,7 @@ pub struct SubEmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `huggingFace`
+    /// - This parameter is available for sources `huggingFace`, `onnx`
     ///
     /// # 🔄 Reindexing
     ///
@@ -376,6 +481,23 @@ pub struct SubEmbeddingSettings {
     pub pooling: Setting<OverridePooling>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
+    #[schema(value_type = Option<bool>)]
+    /// Whether to normalize the embeddings produced by the model to unit length.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for source `onnx`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🏗️ Changing the value of this parameter always regenerates embeddings
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to `true` if the `modules.json` of the model contains a `Normalize` module, `false` otherwise
+    pub normalize: Setting<bool>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
     #[schema(value_type = Option<String>)]
     /// The API key to pass to the remote embedder while making requests.
     ///
@@ -403,11 +525,11 @@ pub struct SubEmbeddingSettings {
     ///
     /// # Mandatory
     ///
-    /// - This parameter is mandatory for source `userProvided`
+    /// - This parameter is mandatory for sources `userProvided`, `userProvidedMultiVector`
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `openAi`, `ollama`, `rest`, `userProvided`
+    /// - This parameter is available for source `openAi`, `ollama`, `rest`, `userProvided`, `userProvidedMultiVector`
     ///
     /// # 🔄 Reindexing
     ///
@@ -429,7 +551,7 @@ pub struct SubEmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama` and `rest
+    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama`, `onnx` and `rest`
     ///
     /// # 🔄 Reindexing
     ///
@@ -442,7 +564,7 @@ pub struct SubEmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama` and `rest`
+    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama`, `onnx` and `rest`
     ///
     /// # 🔄 Reindexing
     ///
@@ -476,6 +598,9 @@ pub struct SubEmbeddingSettings {
     #[schema(value_type = Option<serde_json::Value>)]
     /// Template request to send to the remote embedder.
     ///
+    /// A string of the form `"{{doc.<field>}}"` in the template is replaced by the value of that document field,
+    /// allowing to send media such as image URLs or base64-encoded images to multimodal embedders.
+    ///
     /// # Mandatory
     ///
     /// - This parameter is mandatory for source `rest`
@@ -518,6 +643,65 @@ pub struct SubEmbeddingSettings {
     ///
     /// - 🌱 Changing the value of this parameter never regenerates embeddings
     pub headers: Setting<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<usize>)]
+    /// Maximum number of requests sent at the same time to the remote embedder.
+    ///
+    /// The limit is shared by all the embedders that send requests to the same `url` with the same `apiKey`
+    /// and the same limits, in all indexes, for both indexing and search.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for sources `openAi`, `ollama`, `rest`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🌱 Changing the value of this parameter never regenerates embeddings
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to no limit
+    pub max_concurrent_requests: Setting<usize>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<u64>)]
+    /// Maximum number of requests sent to the remote embedder in a minute.
+    ///
+    /// The limit is shared like `maxConcurrentRequests`.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for sources `openAi`, `ollama`, `rest`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🌱 Changing the value of this parameter never regenerates embeddings
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to no limit
+    pub requests_per_minute: Setting<u64>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<u64>)]
+    /// Maximum number of tokens sent to the remote embedder in a minute.
+    ///
+    /// The number of tokens of a request is estimated from its size, at one token every 4 bytes.
+    /// The limit is shared like `maxConcurrentRequests`.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for sources `openAi`, `ollama`, `rest`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🌱 Changing the value of this parameter never regenerates embeddings
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to no limit
+    pub tokens_per_minute: Setting<u64>,
 
     // The following fields are provided for the sake of improving error handling
     // They should always be set to `NotSet`, otherwise an error will be returned
@@ -618,6 +802,7 @@ impl SettingsDiff {
                     mut model,
                     mut revision,
                     mut pooling,
+                    mut normalize,
                     mut api_key,
                     mut dimensions,
                     mut document_template,
@@ -628,8 +813,12 @@ impl SettingsDiff {
                     mut indexing_embedder,
                     mut distribution,
                     mut headers,
+                    mut max_concurrent_requests,
+                    mut requests_per_minute,
+                    mut tokens_per_minute,
                     mut document_template_max_bytes,
                     binary_quantized: mut binary_quantize,
+                    mut shadow_of,
                 } = old;
 
                 let EmbeddingSettings {
@@ -637,6 +826,7 @@ impl SettingsDiff {
                     model: new_model,
                     revision: new_revision,
                     pooling: new_pooling,
+                    normalize: new_normalize,
                     api_key: new_api_key,
                     dimensions: new_dimensions,
                     document_template: new_document_template,
@@ -647,8 +837,12 @@ impl SettingsDiff {
                     indexing_embedder: new_indexing_embedder,
                     distribution: new_distribution,
                     headers: new_headers,
+                    max_concurrent_requests: new_max_concurrent_requests,
+                    requests_per_minute: new_requests_per_minute,
+                    tokens_per_minute: new_tokens_per_minute,
                     document_template_max_bytes: new_document_template_max_bytes,
                     binary_quantized: new_binary_quantize,
+                    shadow_of: new_shadow_of,
                 } = new;
 
                 if matches!(binary_quantize, Setting::Set(true))
@@ -667,6 +861,7 @@ impl SettingsDiff {
                     &mut model,
                     &mut revision,
                     &mut pooling,
+                    &mut normalize,
                     &mut api_key,
                     &mut dimensions,
                     &mut document_template,
@@ -675,10 +870,14 @@ impl SettingsDiff {
                     &mut request,
                     &mut response,
                     &mut headers,
+                    &mut max_concurrent_requests,
+                    &mut requests_per_minute,
+                    &mut tokens_per_minute,
                     new_source,
                     new_model,
                     new_revision,
                     new_pooling,
+                    new_normalize,
                     new_api_key,
                     new_dimensions,
                     new_document_template,
@@ -687,6 +886,9 @@ impl SettingsDiff {
                     new_request,
                     new_response,
                     new_headers,
+                    new_max_concurrent_requests,
+                    new_requests_per_minute,
+                    new_tokens_per_minute,
                 );
 
                 let binary_quantize_changed = binary_quantize.apply(new_binary_quantize);
@@ -700,12 +902,15 @@ impl SettingsDiff {
                 )?;
 
                 distribution.apply(new_distribution);
+                // promoting a shadow is handled when updating the embedding configs
+                shadow_of.apply(new_shadow_of);
 
                 let updated_settings = EmbeddingSettings {
                     source,
                     model,
                     revision,
                     pooling,
+                    normalize,
                     api_key,
                     dimensions,
                     document_template,
@@ -716,8 +921,12 @@ impl SettingsDiff {
                     indexing_embedder,
                     distribution,
                     headers,
+                    max_concurrent_requests,
+                    requests_per_minute,
+                    tokens_per_minute,
                     document_template_max_bytes,
                     binary_quantized: binary_quantize,
+                    shadow_of,
                 };
 
                 match reindex_action {
@@ -752,6 +961,7 @@ impl SettingsDiff {
                     mut model,
                     mut revision,
                     mut pooling,
+                    mut normalize,
                     mut api_key,
                     mut dimensions,
                     mut document_template,
@@ -760,6 +970,9 @@ impl SettingsDiff {
                     mut request,
                     mut response,
                     mut headers,
+                    mut max_concurrent_requests,
+                    mut requests_per_minute,
+                    mut tokens_per_minute,
                     // phony settings
                     mut distribution,
                     mut binary_quantized,
@@ -777,6 +990,7 @@ impl SettingsDiff {
                     model: new_model,
                     revision: new_revision,
                     pooling: new_pooling,
+                    normalize: new_normalize,
                     api_key: new_api_key,
                     dimensions: new_dimensions,
                     document_template: new_document_template,
@@ -785,6 +999,9 @@ impl SettingsDiff {
                     request: new_request,
                     response: new_response,
                     headers: new_headers,
+                    max_concurrent_requests: new_max_concurrent_requests,
+                    requests_per_minute: new_requests_per_minute,
+                    tokens_per_minute: new_tokens_per_minute,
                     distribution: new_distribution,
                     binary_quantized: new_binary_quantized,
                     search_embedder: new_search_embedder,
@@ -797,6 +1014,7 @@ impl SettingsDiff {
                     &mut model,
                     &mut revision,
                     &mut pooling,
+                    &mut normalize,
                     &mut api_key,
                     &mut dimensions,
                     &mut document_template,
@@ -805,10 +1023,14 @@ impl SettingsDiff {
                     &mut request,
                     &mut response,
                     &mut headers,
+                    &mut max_concurrent_requests,
+                    &mut requests_per_minute,
+                    &mut tokens_per_minute,
                     new_source,
                     new_model,
                     new_revision,
                     new_pooling,
+                    new_normalize,
                     new_api_key,
                     new_dimensions,
                     new_document_template,
@@ -817,6 +1039,9 @@ impl SettingsDiff {
                     new_request,
                     new_response,
                     new_headers,
+                    new_max_concurrent_requests,
+                    new_requests_per_minute,
+                    new_tokens_per_minute,
                 );
 
                 // update phony settings, it is always an error to have them set.
@@ -830,6 +1055,7 @@ impl SettingsDiff {
                     model,
                     revision,
                     pooling,
+                    normalize,
                     api_key,
                     dimensions,
                     document_template,
@@ -837,6 +1063,9 @@ impl SettingsDiff {
                     request,
                     response,
                     headers,
+                    max_concurrent_requests,
+                    requests_per_minute,
+                    tokens_per_minute,
                     document_template_max_bytes,
                     distribution,
                     binary_quantized,
@@ -858,6 +1087,7 @@ impl SettingsDiff {
         model: &mut Setting<String>,
         revision: &mut Setting<String>,
         pooling: &mut Setting<OverridePooling>,
+        normalize: &mut Setting<bool>,
         api_key: &mut Setting<String>,
         dimensions: &mut Setting<usize>,
         document_template: &mut Setting<String>,
@@ -866,10 +1096,14 @@ impl SettingsDiff {
         request: &mut Setting<serde_json::Value>,
         response: &mut Setting<serde_json::Value>,
         headers: &mut Setting<BTreeMap<String, String>>,
+        max_concurrent_requests: &mut Setting<usize>,
+        requests_per_minute: &mut Setting<u64>,
+        tokens_per_minute: &mut Setting<u64>,
         new_source: Setting<EmbedderSource>,
         new_model: Setting<String>,
         new_revision: Setting<String>,
         new_pooling: Setting<OverridePooling>,
+        new_normalize: Setting<bool>,
         new_api_key: Setting<String>,
         new_dimensions: Setting<usize>,
         new_document_template: Setting<String>,
@@ -878,6 +1112,9 @@ impl SettingsDiff {
         new_request: Setting<serde_json::Value>,
         new_response: Setting<serde_json::Value>,
         new_headers: Setting<BTreeMap<String, String>>,
+        new_max_concurrent_requests: Setting<usize>,
+        new_requests_per_minute: Setting<u64>,
+        new_tokens_per_minute: Setting<u64>,
     ) {
         // **Warning**: do not use short-circuiting || here, we want all these operations applied
         if source.apply(new_source) {
@@ -888,6 +1125,7 @@ impl SettingsDiff {
                 model,
                 revision,
                 pooling,
+                normalize,
                 dimensions,
                 url,
                 request,
@@ -895,6 +1133,9 @@ impl SettingsDiff {
                 document_template,
                 document_template_max_bytes,
                 headers,
+                max_concurrent_requests,
+                requests_per_minute,
+                tokens_per_minute,
                 // send dummy values, the source cannot recursively be composite
                 &mut Setting::NotSet,
                 &mut Setting::NotSet,
@@ -909,6 +1150,9 @@ impl SettingsDiff {
         if pooling.apply(new_pooling) {
             ReindexAction::push_action(reindex_action, ReindexAction::FullReindex);
         }
+        if normalize.apply(new_normalize) {
+            ReindexAction::push_action(reindex_action, ReindexAction::FullReindex);
+        }
         if dimensions.apply(new_dimensions) {
             match *source {
                 // regenerate on dimensions change in OpenAI since truncation is supported
@@ -955,6 +1199,9 @@ impl SettingsDiff {
 
         api_key.apply(new_api_key);
         headers.apply(new_headers);
+        max_concurrent_requests.apply(new_max_concurrent_requests);
+        requests_per_minute.apply(new_requests_per_minute);
+        tokens_per_minute.apply(new_tokens_per_minute);
     }
 }
 
@@ -974,6 +1221,7 @@ fn apply_default_for_source(
     model: &mut Setting<String>,
     revision: &mut Setting<String>,
     pooling: &mut Setting<OverridePooling>,
+    normalize: &mut Setting<bool>,
     dimensions: &mut Setting<usize>,
     url: &mut Setting<String>,
     request: &mut Setting<serde_json::Value>,
@@ -981,6 +1229,9 @@ fn apply_default_for_source(
     document_template: &mut Setting<String>,
     document_template_max_bytes: &mut Setting<usize>,
     headers: &mut Setting<BTreeMap<String, String>>,
+    max_concurrent_requests: &mut Setting<usize>,
+    requests_per_minute: &mut Setting<u64>,
+    tokens_per_minute: &mut Setting<u64>,
     search_embedder: &mut Setting<SubEmbeddingSettings>,
     indexing_embedder: &mut Setting<SubEmbeddingSettings>,
 ) {
@@ -989,11 +1240,15 @@ fn apply_default_for_source(
             *model = Setting::Reset;
             *revision = Setting::Reset;
             *pooling = Setting::Reset;
+            *normalize = Setting::NotSet;
             *dimensions = Setting::NotSet;
             *url = Setting::NotSet;
             *request = Setting::NotSet;
             *response = Setting::NotSet;
             *headers = Setting::NotSet;
+            *max_concurrent_requests = Setting::NotSet;
+            *requests_per_minute = Setting::NotSet;
+            *tokens_per_minute = Setting::NotSet;
             *search_embedder = Setting::NotSet;
             *indexing_embedder = Setting::NotSet;
         }
@@ -1001,11 +1256,15 @@ fn apply_default_for_source(
             *model = Setting::Reset;
             *revision = Setting::NotSet;
             *pooling = Setting::NotSet;
+            *normalize = Setting::NotSet;
             *dimensions = Setting::Reset;
             *url = Setting::NotSet;
             *request = Setting::NotSet;
             *response = Setting::NotSet;
             *headers = Setting::NotSet;
+            *max_concurrent_requests = Setting::Reset;
+            *requests_per_minute = Setting::Reset;
+            *tokens_per_minute = Setting::Reset;
             *search_embedder = Setting::NotSet;
             *indexing_embedder = Setting::NotSet;
         }
@@ -1013,11 +1272,15 @@ fn apply_default_for_source(
             *model = Setting::Reset;
             *revision = Setting::NotSet;
             *pooling = Setting::NotSet;
+            *normalize = Setting::NotSet;
             *dimensions = Setting::NotSet;
             *url = Setting::Reset;
             *request = Setting::NotSet;
             *response = Setting::NotSet;
             *headers = Setting::NotSet;
+            *max_concurrent_requests = Setting::Reset;
+            *requests_per_minute = Setting::Reset;
+            *tokens_per_minute = Setting::Reset;
             *search_embedder = Setting::NotSet;
             *indexing_embedder = Setting::NotSet;
         }
@@ -1025,18 +1288,23 @@ fn apply_default_for_source(
             *model = Setting::NotSet;
             *revision = Setting::NotSet;
             *pooling = Setting::NotSet;
+            *normalize = Setting::NotSet;
             *dimensions = Setting::Reset;
             *url = Setting::Reset;
             *request = Setting::Reset;
             *response = Setting::Reset;
             *headers = Setting::Reset;
+            *max_concurrent_requests = Setting::Reset;
+            *requests_per_minute = Setting::Reset;
+            *tokens_per_minute = Setting::Reset;
             *search_embedder = Setting::NotSet;
             *indexing_embedder = Setting::NotSet;
         }
-        Setting::Set(EmbedderSource::UserProvided) => {
+        Setting::Set(EmbedderSource::UserProvided | EmbedderSource::UserProvidedMultiVector) => {
             *model = Setting::NotSet;
             *revision = Setting::NotSet;
             *pooling = Setting::NotSet;
+            *normalize = Setting::NotSet;
             *dimensions = Setting::Reset;
             *url = Setting::NotSet;
             *request = Setting::NotSet;
@@ -1044,6 +1312,43 @@ fn apply_default_for_source(
             *document_template = Setting::NotSet;
             *document_template_max_bytes = Setting::NotSet;
             *headers = Setting::NotSet;
+            *max_concurrent_requests = Setting::NotSet;
+            *requests_per_minute = Setting::NotSet;
+            *tokens_per_minute = Setting::NotSet;
+            *search_embedder = Setting::NotSet;
+            *indexing_embedder = Setting::NotSet;
+        }
+        Setting::Set(EmbedderSource::UserProvidedSparse) => {
+            *model = Setting::NotSet;
+            *revision = Setting::NotSet;
+            *pooling = Setting::NotSet;
+            *normalize = Setting::NotSet;
+            *dimensions = Setting::NotSet;
+            *url = Setting::NotSet;
+            *request = Setting::NotSet;
+            *response = Setting::NotSet;
+            *document_template = Setting::NotSet;
+            *document_template_max_bytes = Setting::NotSet;
+            *headers = Setting::NotSet;
+            *max_concurrent_requests = Setting::NotSet;
+            *requests_per_minute = Setting::NotSet;
+            *tokens_per_minute = Setting::NotSet;
+            *search_embedder = Setting::NotSet;
+            *indexing_embedder = Setting::NotSet;
+        }
+        Setting::Set(EmbedderSource::Onnx) => {
+            *model = Setting::Reset;
+            *revision = Setting::NotSet;
+            *pooling = Setting::Reset;
+            *normalize = Setting::Reset;
+            *dimensions = Setting::NotSet;
+            *url = Setting::NotSet;
+            *request = Setting::NotSet;
+            *response = Setting::NotSet;
+            *headers = Setting::NotSet;
+            *max_concurrent_requests = Setting::NotSet;
+            *requests_per_minute = Setting::NotSet;
+            *tokens_per_minute = Setting::NotSet;
             *search_embedder = Setting::NotSet;
             *indexing_embedder = Setting::NotSet;
         }
@@ -1051,6 +1356,7 @@ fn apply_default_for_source(
             *model = Setting::NotSet;
             *revision = Setting::NotSet;
             *pooling = Setting::NotSet;
+            *normalize = Setting::NotSet;
             *dimensions = Setting::NotSet;
             *url = Setting::NotSet;
             *request = Setting::NotSet;
@@ -1058,6 +1364,9 @@ fn apply_default_for_source(
             *document_template = Setting::NotSet;
             *document_template_max_bytes = Setting::NotSet;
             *headers = Setting::NotSet;
+            *max_concurrent_requests = Setting::NotSet;
+            *requests_per_minute = Setting::NotSet;
+            *tokens_per_minute = Setting::NotSet;
             *search_embedder = Setting::Reset;
             *indexing_embedder = Setting::Reset;
         }
@@ -1114,6 +1423,7 @@ pub enum MetaEmbeddingSetting {
     Model,
     Revision,
     Pooling,
+    Normalize,
     ApiKey,
     Dimensions,
     DocumentTemplate,
@@ -1122,6 +1432,9 @@ pub enum MetaEmbeddingSetting {
     Request,
     Response,
     Headers,
+    MaxConcurrentRequests,
+    RequestsPerMinute,
+    TokensPerMinute,
     SearchEmbedder,
     IndexingEmbedder,
     Distribution,
@@ -1136,6 +1449,7 @@ impl MetaEmbeddingSetting {
             Model => "model",
             Revision => "revision",
             Pooling => "pooling",
+            Normalize => "normalize",
             ApiKey => "apiKey",
             Dimensions => "dimensions",
             DocumentTemplate => "documentTemplate",
@@ -1144,6 +1458,9 @@ impl MetaEmbeddingSetting {
             Request => "request",
             Response => "response",
             Headers => "headers",
+            MaxConcurrentRequests => "maxConcurrentRequests",
+            RequestsPerMinute => "requestsPerMinute",
+            TokensPerMinute => "tokensPerMinute",
             SearchEmbedder => "searchEmbedder",
             IndexingEmbedder => "indexingEmbedder",
             Distribution => "distribution",
@@ -1161,6 +1478,7 @@ impl EmbeddingSettings {
         model: &Setting<String>,
         revision: &Setting<String>,
         pooling: &Setting<OverridePooling>,
+        normalize: &Setting<bool>,
         dimensions: &Setting<usize>,
         api_key: &Setting<String>,
         url: &Setting<String>,
@@ -1169,6 +1487,9 @@ impl EmbeddingSettings {
         document_template: &Setting<String>,
         document_template_max_bytes: &Setting<usize>,
         headers: &Setting<BTreeMap<String, String>>,
+        max_concurrent_requests: &Setting<usize>,
+        requests_per_minute: &Setting<u64>,
+        tokens_per_minute: &Setting<u64>,
         search_embedder: &Setting<SubEmbeddingSettings>,
         indexing_embedder: &Setting<SubEmbeddingSettings>,
         binary_quantized: &Setting<bool>,
@@ -1189,6 +1510,13 @@ impl EmbeddingSettings {
             context,
             pooling,
         )?;
+        Self::check_setting(
+            embedder_name,
+            source,
+            MetaEmbeddingSetting::Normalize,
+            context,
+            normalize,
+        )?;
         Self::check_setting(
             embedder_name,
             source,
@@ -1233,6 +1561,27 @@ impl EmbeddingSettings {
             context,
             headers,
         )?;
+        Self::check_setting(
+            embedder_name,
+            source,
+            MetaEmbeddingSetting::MaxConcurrentRequests,
+            context,
+            max_concurrent_requests,
+        )?;
+        Self::check_setting(
+            embedder_name,
+            source,
+            MetaEmbeddingSetting::RequestsPerMinute,
+            context,
+            requests_per_minute,
+        )?;
+        Self::check_setting(
+            embedder_name,
+            source,
+            MetaEmbeddingSetting::TokensPerMinute,
+            context,
+            tokens_per_minute,
+        )?;
         Self::check_setting(
             embedder_name,
             source,
@@ -1260,7 +1609,16 @@ impl EmbeddingSettings {
             MetaEmbeddingSetting::Distribution,
             context,
             distribution,
-        )
+        )?;
+        if let (EmbedderSource::Onnx, Setting::Set(model)) = (source, model) {
+            super::onnx::check_model_path(model).map_err(|message| {
+                UserError::InvalidSettingsEmbedder {
+                    embedder_name: context.embedder_name_with_context(embedder_name),
+                    message,
+                }
+            })?;
+        }
+        Ok(())
     }
 
     pub(crate) fn allowed_sources_for_field(
@@ -1320,9 +1678,52 @@ impl EmbeddingSettings {
         use MetaEmbeddingSetting::*;
         use NestingContext::*;
         match (source, field, context) {
+            // sparse vectors are not stored in arroy and cannot be quantized
+            (UserProvidedSparse, BinaryQuantized, _) => FieldStatus::Disallowed,
+            // late interaction compares the stored vectors, which quantization would degrade
+            (UserProvidedMultiVector, BinaryQuantized, _) => FieldStatus::Disallowed,
             (_, Distribution | BinaryQuantized, NotNested) => FieldStatus::Allowed,
             (_, Distribution | BinaryQuantized, _) => FieldStatus::Disallowed,
             (_, DocumentTemplate | DocumentTemplateMaxBytes, Search) => FieldStatus::Disallowed,
+            (Onnx, Model, _) => FieldStatus::Mandatory,
+            (
+                Onnx,
+                Source | Pooling | Normalize | DocumentTemplate | DocumentTemplateMaxBytes,
+                _,
+            ) => FieldStatus::Allowed,
+            (
+                Onnx,
+                Revision | ApiKey | Dimensions | Url | Request | Response | Headers
+                | SearchEmbedder | IndexingEmbedder,
+                _,
+            ) => FieldStatus::Disallowed,
+            (
+                OpenAi
+                | HuggingFace
+                | Ollama
+                | UserProvided
+                | Rest
+                | UserProvidedSparse
+                | Composite
+                | UserProvidedMultiVector,
+                Normalize,
+                _,
+            ) => FieldStatus::Disallowed,
+            (
+                OpenAi | Ollama | Rest,
+                MaxConcurrentRequests | RequestsPerMinute | TokensPerMinute,
+                _,
+            ) => FieldStatus::Allowed,
+            (
+                HuggingFace
+                | UserProvided
+                | UserProvidedSparse
+                | Composite
+                | Onnx
+                | UserProvidedMultiVector,
+                MaxConcurrentRequests | RequestsPerMinute | TokensPerMinute,
+                _,
+            ) => FieldStatus::Disallowed,
             (
                 OpenAi,
                 Source
@@ -1363,10 +1764,10 @@ impl EmbeddingSettings {
                 | IndexingEmbedder,
                 _,
             ) => FieldStatus::Disallowed,
-            (UserProvided, Dimensions, _) => FieldStatus::Mandatory,
-            (UserProvided, Source, _) => FieldStatus::Allowed,
+            (UserProvided | UserProvidedMultiVector, Dimensions, _) => FieldStatus::Mandatory,
+            (UserProvided | UserProvidedMultiVector, Source, _) => FieldStatus::Allowed,
             (
-                UserProvided,
+                UserProvided | UserProvidedMultiVector,
                 Model
                 | Revision
                 | Pooling
@@ -1395,6 +1796,24 @@ impl EmbeddingSettings {
             (Rest, Model | Revision | Pooling | SearchEmbedder | IndexingEmbedder, _) => {
                 FieldStatus::Disallowed
             }
+            (UserProvidedSparse, Source, _) => FieldStatus::Allowed,
+            (
+                UserProvidedSparse,
+                Model
+                | Revision
+                | Pooling
+                | ApiKey
+                | Dimensions
+                | DocumentTemplate
+                | DocumentTemplateMaxBytes
+                | Url
+                | Request
+                | Response
+                | Headers
+                | SearchEmbedder
+                | IndexingEmbedder,
+                _,
+            ) => FieldStatus::Disallowed,
             (Composite, SearchEmbedder | IndexingEmbedder, _) => FieldStatus::Mandatory,
             (Composite, Source, _) => FieldStatus::Allowed,
             (
@@ -1445,7 +1864,10 @@ impl EmbeddingSettings {
             (NestingContext::NotNested, _) => Ok(()),
             (
                 NestingContext::Search | NestingContext::Indexing,
-                EmbedderSource::Composite | EmbedderSource::UserProvided,
+                EmbedderSource::Composite
+                | EmbedderSource::UserProvided
+                | EmbedderSource::UserProvidedSparse
+                | EmbedderSource::UserProvidedMultiVector,
             ) => Err(UserError::InvalidSourceForNested {
                 embedder_name: context.embedder_name_with_context(embedder_name),
                 source_: source,
@@ -1455,7 +1877,8 @@ impl EmbeddingSettings {
                 EmbedderSource::OpenAi
                 | EmbedderSource::HuggingFace
                 | EmbedderSource::Ollama
-                | EmbedderSource::Rest,
+                | EmbedderSource::Rest
+                | EmbedderSource::Onnx,
             ) => Ok(()),
         }
     }
@@ -1484,6 +1907,9 @@ pub enum EmbedderSource {
     UserProvided,
     Rest,
     Composite,
+    UserProvidedSparse,
+    Onnx,
+    UserProvidedMultiVector,
 }
 
 impl std::fmt::Display for EmbedderSource {
@@ -1495,6 +1921,9 @@ impl std::fmt::Display for EmbedderSource {
             EmbedderSource::Ollama => "ollama",
             EmbedderSource::Rest => "rest",
             EmbedderSource::Composite => "composite",
+            EmbedderSource::UserProvidedSparse => "userProvidedSparse",
+            EmbedderSource::Onnx => "onnx",
+            EmbedderSource::UserProvidedMultiVector => "userProvidedMultiVector",
         };
         f.write_str(s)
     }
@@ -1517,6 +1946,7 @@ impl EmbeddingSettings {
             model: Setting::Set(model),
             revision: Setting::some_or_not_set(revision),
             pooling: Setting::Set(pooling),
+            normalize: Setting::NotSet,
             api_key: Setting::NotSet,
             dimensions: Setting::NotSet,
             document_template,
@@ -1525,10 +1955,14 @@ impl EmbeddingSettings {
             request: Setting::NotSet,
             response: Setting::NotSet,
             headers: Setting::NotSet,
+            max_concurrent_requests: Setting::NotSet,
+            requests_per_minute: Setting::NotSet,
+            tokens_per_minute: Setting::NotSet,
             search_embedder: Setting::NotSet,
             indexing_embedder: Setting::NotSet,
             distribution: Setting::some_or_not_set(distribution),
             binary_quantized: Setting::some_or_not_set(quantized),
+            shadow_of: Setting::NotSet,
         }
     }
 
@@ -1539,6 +1973,7 @@ impl EmbeddingSettings {
             embedding_model,
             dimensions,
             distribution,
+            rate_limits,
         }: super::openai::EmbedderOptions,
         document_template: Setting<String>,
         document_template_max_bytes: Setting<usize>,
@@ -1549,6 +1984,7 @@ impl EmbeddingSettings {
             model: Setting::Set(embedding_model.name().to_owned()),
             revision: Setting::NotSet,
             pooling: Setting::NotSet,
+            normalize: Setting::NotSet,
             api_key: Setting::some_or_not_set(api_key),
             dimensions: Setting::some_or_not_set(dimensions),
             document_template,
@@ -1557,20 +1993,25 @@ impl EmbeddingSettings {
             request: Setting::NotSet,
             response: Setting::NotSet,
             headers: Setting::NotSet,
+            max_concurrent_requests: Setting::some_or_not_set(rate_limits.max_concurrent_requests),
+            requests_per_minute: Setting::some_or_not_set(rate_limits.requests_per_minute),
+            tokens_per_minute: Setting::some_or_not_set(rate_limits.tokens_per_minute),
             search_embedder: Setting::NotSet,
             indexing_embedder: Setting::NotSet,
             distribution: Setting::some_or_not_set(distribution),
             binary_quantized: Setting::some_or_not_set(quantized),
+            shadow_of: Setting::NotSet,
         }
     }
 
     fn from_ollama(
         super::ollama::EmbedderOptions {
-          embedding_model,
-          url,
-          api_key,
-          distribution,
-          dimensions,
+            embedding_model,
+            url,
+            api_key,
+            distribution,
+            dimensions,
+            rate_limits,
         }: super::ollama::EmbedderOptions,
         document_template: Setting<String>,
         document_template_max_bytes: Setting<usize>,
@@ -1581,6 +2022,7 @@ impl EmbeddingSettings {
             model: Setting::Set(embedding_model),
             revision: Setting::NotSet,
             pooling: Setting::NotSet,
+            normalize: Setting::NotSet,
             api_key: Setting::some_or_not_set(api_key),
             dimensions: Setting::some_or_not_set(dimensions),
             document_template,
@@ -1589,10 +2031,14 @@ impl EmbeddingSettings {
             request: Setting::NotSet,
             response: Setting::NotSet,
             headers: Setting::NotSet,
+            max_concurrent_requests: Setting::some_or_not_set(rate_limits.max_concurrent_requests),
+            requests_per_minute: Setting::some_or_not_set(rate_limits.requests_per_minute),
+            tokens_per_minute: Setting::some_or_not_set(rate_limits.tokens_per_minute),
             search_embedder: Setting::NotSet,
             indexing_embedder: Setting::NotSet,
             distribution: Setting::some_or_not_set(distribution),
             binary_quantized: Setting::some_or_not_set(quantized),
+            shadow_of: Setting::NotSet,
         }
     }
 
@@ -1605,6 +2051,7 @@ impl EmbeddingSettings {
             model: Setting::NotSet,
             revision: Setting::NotSet,
             pooling: Setting::NotSet,
+            normalize: Setting::NotSet,
             api_key: Setting::NotSet,
             dimensions: Setting::Set(dimensions),
             document_template: Setting::NotSet,
@@ -1613,10 +2060,101 @@ impl EmbeddingSettings {
             request: Setting::NotSet,
             response: Setting::NotSet,
             headers: Setting::NotSet,
+            max_concurrent_requests: Setting::NotSet,
+            requests_per_minute: Setting::NotSet,
+            tokens_per_minute: Setting::NotSet,
             search_embedder: Setting::NotSet,
             indexing_embedder: Setting::NotSet,
             distribution: Setting::some_or_not_set(distribution),
             binary_quantized: Setting::some_or_not_set(quantized),
+            shadow_of: Setting::NotSet,
+        }
+    }
+
+    fn from_user_provided_multi_vector(
+        super::late_interaction::EmbedderOptions { dimensions, distribution }: super::late_interaction::EmbedderOptions,
+    ) -> Self {
+        Self {
+            source: Setting::Set(EmbedderSource::UserProvidedMultiVector),
+            model: Setting::NotSet,
+            revision: Setting::NotSet,
+            pooling: Setting::NotSet,
+            normalize: Setting::NotSet,
+            api_key: Setting::NotSet,
+            dimensions: Setting::Set(dimensions),
+            document_template: Setting::NotSet,
+            document_template_max_bytes: Setting::NotSet,
+            url: Setting::NotSet,
+            request: Setting::NotSet,
+            response: Setting::NotSet,
+            headers: Setting::NotSet,
+            max_concurrent_requests: Setting::NotSet,
+            requests_per_minute: Setting::NotSet,
+            tokens_per_minute: Setting::NotSet,
+            search_embedder: Setting::NotSet,
+            indexing_embedder: Setting::NotSet,
+            distribution: Setting::some_or_not_set(distribution),
+            binary_quantized: Setting::NotSet,
+            shadow_of: Setting::NotSet,
+        }
+    }
+
+    fn from_user_provided_sparse(
+        super::sparse::EmbedderOptions { distribution }: super::sparse::EmbedderOptions,
+    ) -> Self {
+        Self {
+            source: Setting::Set(EmbedderSource::UserProvidedSparse),
+            model: Setting::NotSet,
+            revision: Setting::NotSet,
+            pooling: Setting::NotSet,
+            normalize: Setting::NotSet,
+            api_key: Setting::NotSet,
+            dimensions: Setting::NotSet,
+            document_template: Setting::NotSet,
+            document_template_max_bytes: Setting::NotSet,
+            url: Setting::NotSet,
+            request: Setting::NotSet,
+            response: Setting::NotSet,
+            headers: Setting::NotSet,
+            max_concurrent_requests: Setting::NotSet,
+            requests_per_minute: Setting::NotSet,
+            tokens_per_minute: Setting::NotSet,
+            search_embedder: Setting::NotSet,
+            indexing_embedder: Setting::NotSet,
+            distribution: Setting::some_or_not_set(distribution),
+            binary_quantized: Setting::NotSet,
+            shadow_of: Setting::NotSet,
+        }
+    }
+
+    fn from_onnx(
+        super::onnx::EmbedderOptions { model, distribution, pooling, normalize }: super::onnx::EmbedderOptions,
+        document_template: Setting<String>,
+        document_template_max_bytes: Setting<usize>,
+        quantized: Option<bool>,
+    ) -> Self {
+        Self {
+            source: Setting::Set(EmbedderSource::Onnx),
+            model: Setting::Set(model),
+            revision: Setting::NotSet,
+            pooling: Setting::Set(pooling),
+            normalize: Setting::some_or_not_set(normalize),
+            api_key: Setting::NotSet,
+            dimensions: Setting::NotSet,
+            document_template,
+            document_template_max_bytes,
+            url: Setting::NotSet,
+            request: Setting::NotSet,
+            response: Setting::NotSet,
+            headers: Setting::NotSet,
+            max_concurrent_requests: Setting::NotSet,
+            requests_per_minute: Setting::NotSet,
+            tokens_per_minute: Setting::NotSet,
+            search_embedder: Setting::NotSet,
+            indexing_embedder: Setting::NotSet,
+            distribution: Setting::some_or_not_set(distribution),
+            binary_quantized: Setting::some_or_not_set(quantized),
+            shadow_of: Setting::NotSet,
         }
     }
 
@@ -1629,6 +2167,7 @@ impl EmbeddingSettings {
             response,
             distribution,
             headers,
+            rate_limits,
         }: super::rest::EmbedderOptions,
         document_template: Setting<String>,
         document_template_max_bytes: Setting<usize>,
@@ -1639,6 +2178,7 @@ impl EmbeddingSettings {
             model: Setting::NotSet,
             revision: Setting::NotSet,
             pooling: Setting::NotSet,
+            normalize: Setting::NotSet,
             api_key: Setting::some_or_not_set(api_key),
             dimensions: Setting::some_or_not_set(dimensions),
             document_template,
@@ -1648,19 +2188,23 @@ impl EmbeddingSettings {
             response: Setting::Set(response),
             distribution: Setting::some_or_not_set(distribution),
             headers: Setting::Set(headers),
+            max_concurrent_requests: Setting::some_or_not_set(rate_limits.max_concurrent_requests),
+            requests_per_minute: Setting::some_or_not_set(rate_limits.requests_per_minute),
+            tokens_per_minute: Setting::some_or_not_set(rate_limits.tokens_per_minute),
             search_embedder: Setting::NotSet,
             indexing_embedder: Setting::NotSet,
             binary_quantized: Setting::some_or_not_set(quantized),
+            shadow_of: Setting::NotSet,
         }
     }
 }
 
 impl From<EmbeddingConfig> for EmbeddingSettings {
     fn from(value: EmbeddingConfig) -> Self {
-        let EmbeddingConfig { embedder_options, prompt, quantized } = value;
+        let EmbeddingConfig { embedder_options, prompt, quantized, shadow_of } = value;
         let document_template_max_bytes =
             Setting::Set(prompt.max_bytes.unwrap_or(default_max_bytes()).get());
-        match embedder_options {
+        let mut this = match embedder_options {
             super::EmbedderOptions::HuggingFace(options) => Self::from_hugging_face(
                 options,
                 Setting::Set(prompt.template),
@@ -1682,12 +2226,24 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
             super::EmbedderOptions::UserProvided(options) => {
                 Self::from_user_provided(options, quantized)
             }
+            super::EmbedderOptions::UserProvidedSparse(options) => {
+                Self::from_user_provided_sparse(options)
+            }
+            super::EmbedderOptions::UserProvidedMultiVector(options) => {
+                Self::from_user_provided_multi_vector(options)
+            }
             super::EmbedderOptions::Rest(options) => Self::from_rest(
                 options,
                 Setting::Set(prompt.template),
                 document_template_max_bytes,
                 quantized,
             ),
+            super::EmbedderOptions::Onnx(options) => Self::from_onnx(
+                options,
+                Setting::Set(prompt.template),
+                document_template_max_bytes,
+                quantized,
+            ),
             super::EmbedderOptions::Composite(super::composite::EmbedderOptions {
                 search,
                 index,
@@ -1696,15 +2252,20 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
                 model: Setting::NotSet,
                 revision: Setting::NotSet,
                 pooling: Setting::NotSet,
+                normalize: Setting::NotSet,
                 api_key: Setting::NotSet,
                 dimensions: Setting::NotSet,
                 binary_quantized: Setting::some_or_not_set(quantized),
+                shadow_of: Setting::NotSet,
                 document_template: Setting::NotSet,
                 document_template_max_bytes: Setting::NotSet,
                 url: Setting::NotSet,
                 request: Setting::NotSet,
                 response: Setting::NotSet,
                 headers: Setting::NotSet,
+                max_concurrent_requests: Setting::NotSet,
+                requests_per_minute: Setting::NotSet,
+                tokens_per_minute: Setting::NotSet,
                 distribution: Setting::some_or_not_set(search.distribution()),
                 search_embedder: Setting::Set(SubEmbeddingSettings::from_options(
                     search,
@@ -1717,7 +2278,9 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
                     document_template_max_bytes,
                 )),
             },
-        }
+        };
+        this.shadow_of = Setting::some_or_not_set(shadow_of);
+        this
     }
 }
 
@@ -1757,6 +2320,12 @@ impl SubEmbeddingSettings {
                 document_template_max_bytes,
                 None,
             ),
+            SubEmbedderOptions::Onnx(embedder_options) => EmbeddingSettings::from_onnx(
+                embedder_options,
+                document_template,
+                document_template_max_bytes,
+                None,
+            ),
         };
         settings.into()
     }
@@ -1769,6 +2338,7 @@ impl From<EmbeddingSettings> for SubEmbeddingSettings {
             model,
             revision,
             pooling,
+            normalize,
             api_key,
             dimensions,
             document_template,
@@ -1777,16 +2347,21 @@ impl From<EmbeddingSettings> for SubEmbeddingSettings {
             request,
             response,
             headers,
+            max_concurrent_requests,
+            requests_per_minute,
+            tokens_per_minute,
             binary_quantized: _,
             search_embedder: _,
             indexing_embedder: _,
             distribution: _,
+            shadow_of: _,
         } = value;
         Self {
             source,
             model,
             revision,
             pooling,
+            normalize,
             api_key,
             dimensions,
             document_template,
@@ -1795,6 +2370,9 @@ impl From<EmbeddingSettings> for SubEmbeddingSettings {
             request,
             response,
             headers,
+            max_concurrent_requests,
+            requests_per_minute,
+            tokens_per_minute,
             distribution: Setting::NotSet,
             binary_quantized: Setting::NotSet,
             search_embedder: Setting::NotSet,
@@ -1811,6 +2389,7 @@ impl From<EmbeddingSettings> for EmbeddingConfig {
             model,
             revision,
             pooling,
+            normalize,
             api_key,
             dimensions,
             document_template,
@@ -1820,12 +2399,19 @@ impl From<EmbeddingSettings> for EmbeddingConfig {
             response,
             distribution,
             headers,
+            max_concurrent_requests,
+            requests_per_minute,
+            tokens_per_minute,
             binary_quantized,
             search_embedder,
             mut indexing_embedder,
+            shadow_of,
         } = value;
 
         this.quantized = binary_quantized.set();
+        this.shadow_of = shadow_of.set();
+        let rate_limits =
+            rate_limits(max_concurrent_requests, requests_per_minute, tokens_per_minute);
         if let Some((template, document_template_max_bytes)) =
             match (document_template, &mut indexing_embedder) {
                 (Setting::Set(template), _) => Some((template, document_template_max_bytes)),
@@ -1851,15 +2437,30 @@ impl From<EmbeddingSettings> for EmbeddingConfig {
 
         if let Some(source) = source.set() {
             this.embedder_options = match source {
-                EmbedderSource::OpenAi => {
-                    SubEmbedderOptions::openai(model, url, api_key, dimensions, distribution).into()
-                }
-                EmbedderSource::Ollama => {
-                    SubEmbedderOptions::ollama(model, url, api_key, dimensions, distribution).into()
-                }
+                EmbedderSource::OpenAi => SubEmbedderOptions::openai(
+                    model,
+                    url,
+                    api_key,
+                    dimensions,
+                    distribution,
+                    rate_limits,
+                )
+                .into(),
+                EmbedderSource::Ollama => SubEmbedderOptions::ollama(
+                    model,
+                    url,
+                    api_key,
+                    dimensions,
+                    distribution,
+                    rate_limits,
+                )
+                .into(),
                 EmbedderSource::HuggingFace => {
                     SubEmbedderOptions::hugging_face(model, revision, pooling, distribution).into()
                 }
+                EmbedderSource::Onnx => {
+                    SubEmbedderOptions::onnx(model, pooling, normalize, distribution).into()
+                }
                 EmbedderSource::UserProvided => {
                     SubEmbedderOptions::user_provided(dimensions.set().unwrap(), distribution)
                         .into()
@@ -1872,8 +2473,22 @@ impl From<EmbeddingSettings> for EmbeddingConfig {
                     headers,
                     dimensions,
                     distribution,
+                    rate_limits,
                 )
                 .into(),
+                EmbedderSource::UserProvidedSparse => {
+                    super::EmbedderOptions::UserProvidedSparse(super::sparse::EmbedderOptions {
+                        distribution: distribution.set(),
+                    })
+                }
+                EmbedderSource::UserProvidedMultiVector => {
+                    super::EmbedderOptions::UserProvidedMultiVector(
+                        super::late_interaction::EmbedderOptions {
+                            dimensions: dimensions.set().unwrap(),
+                            distribution: distribution.set(),
+                        },
+                    )
+                }
                 EmbedderSource::Composite => {
                     super::EmbedderOptions::Composite(super::composite::EmbedderOptions {
                         // it is important to give the distribution to the search here, as this is from where we'll retrieve it
@@ -1904,6 +2519,7 @@ impl SubEmbedderOptions {
             model,
             revision,
             pooling,
+            normalize,
             api_key,
             dimensions,
             // retrieved by the EmbeddingConfig
@@ -1913,6 +2529,9 @@ impl SubEmbedderOptions {
             request,
             response,
             headers,
+            max_concurrent_requests,
+            requests_per_minute,
+            tokens_per_minute,
             // phony parameters
             distribution: _,
             binary_quantized: _,
@@ -1920,12 +2539,18 @@ impl SubEmbedderOptions {
             indexing_embedder: _,
         } = settings;
 
+        let rate_limits =
+            rate_limits(max_concurrent_requests, requests_per_minute, tokens_per_minute);
         match source.set().unwrap() {
-            EmbedderSource::OpenAi => Self::openai(model, url, api_key, dimensions, distribution),
+            EmbedderSource::OpenAi => {
+                Self::openai(model, url, api_key, dimensions, distribution, rate_limits)
+            }
             EmbedderSource::HuggingFace => {
                 Self::hugging_face(model, revision, pooling, distribution)
             }
-            EmbedderSource::Ollama => Self::ollama(model, url, api_key, dimensions, distribution),
+            EmbedderSource::Ollama => {
+                Self::ollama(model, url, api_key, dimensions, distribution, rate_limits)
+            }
             EmbedderSource::UserProvided => {
                 Self::user_provided(dimensions.set().unwrap(), distribution)
             }
@@ -1937,8 +2562,12 @@ impl SubEmbedderOptions {
                 headers,
                 dimensions,
                 distribution,
+                rate_limits,
             ),
+            EmbedderSource::Onnx => Self::onnx(model, pooling, normalize, distribution),
             EmbedderSource::Composite => panic!("nested composite embedders"),
+            EmbedderSource::UserProvidedSparse => panic!("nested sparse embedders"),
+            EmbedderSource::UserProvidedMultiVector => panic!("nested multi-vector embedders"),
         }
     }
 
@@ -1948,6 +2577,7 @@ impl SubEmbedderOptions {
         api_key: Setting<String>,
         dimensions: Setting<usize>,
         distribution: Setting<DistributionShift>,
+        rate_limits: RateLimits,
     ) -> Self {
         let mut options = super::openai::EmbedderOptions::with_default_model(None);
         if let Some(model) = model.set() {
@@ -1965,6 +2595,7 @@ impl SubEmbedderOptions {
             options.dimensions = Some(dimensions);
         }
         options.distribution = distribution.set();
+        options.rate_limits = rate_limits;
         SubEmbedderOptions::OpenAi(options)
     }
     fn hugging_face(
@@ -1992,6 +2623,19 @@ impl SubEmbedderOptions {
         options.distribution = distribution.set();
         SubEmbedderOptions::HuggingFace(options)
     }
+    fn onnx(
+        model: Setting<String>,
+        pooling: Setting<OverridePooling>,
+        normalize: Setting<bool>,
+        distribution: Setting<DistributionShift>,
+    ) -> Self {
+        Self::Onnx(super::onnx::EmbedderOptions {
+            model: model.set().unwrap(),
+            distribution: distribution.set(),
+            pooling: pooling.set().unwrap_or(OverridePooling::UseModel),
+            normalize: normalize.set(),
+        })
+    }
     fn user_provided(dimensions: usize, distribution: Setting<DistributionShift>) -> Self {
         Self::UserProvided(super::manual::EmbedderOptions {
             dimensions,
@@ -2006,6 +2650,7 @@ impl SubEmbedderOptions {
         headers: Setting<BTreeMap<String, String>>,
         dimensions: Setting<usize>,
         distribution: Setting<DistributionShift>,
+        rate_limits: RateLimits,
     ) -> Self {
         Self::Rest(super::rest::EmbedderOptions {
             api_key: api_key.set(),
@@ -2015,6 +2660,7 @@ impl SubEmbedderOptions {
             response,
             distribution: distribution.set(),
             headers: headers.set().unwrap_or_default(),
+            rate_limits,
         })
     }
     fn ollama(
@@ -2023,6 +2669,7 @@ impl SubEmbedderOptions {
         api_key: Setting<String>,
         dimensions: Setting<usize>,
         distribution: Setting<DistributionShift>,
+        rate_limits: RateLimits,
     ) -> Self {
         let mut options: ollama::EmbedderOptions =
             super::ollama::EmbedderOptions::with_default_model(
@@ -2035,10 +2682,24 @@ impl SubEmbedderOptions {
         }
 
         options.distribution = distribution.set();
+        options.rate_limits = rate_limits;
         SubEmbedderOptions::Ollama(options)
     }
 }
 
+/// Limits of a remote embedder, from its settings.
+fn rate_limits(
+    max_concurrent_requests: Setting<usize>,
+    requests_per_minute: Setting<u64>,
+    tokens_per_minute: Setting<u64>,
+) -> RateLimits {
+    RateLimits {
+        max_concurrent_requests: max_concurrent_requests.set(),
+        requests_per_minute: requests_per_minute.set(),
+        tokens_per_minute: tokens_per_minute.set(),
+    }
+}
+
 impl From<SubEmbedderOptions> for EmbedderOptions {
     fn from(value: SubEmbedderOptions) -> Self {
         match value {
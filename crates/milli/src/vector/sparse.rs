@@ -0,0 +1,459 @@
+//! Sparse vectors, such as the ones produced by SPLADE-like models or BM25-like term weighting.
+//!
+//! Sparse vectors are provided by the user in the `_vectors` field of the documents,
+//! as a map of terms to weights:
+//!
+//! ```json
+//! { "_vectors": { "splade": { "embeddings": { "paris": 1.2, "capital": 0.4 }, "regenerate": false } } }
+//! ```
+//!
+//! Contrary to dense embeddings, they are kept in the documents, and indexed in an inverted index
+//! in the [`crate::Index::vector_sparse`] database. The similarity between a query and a document is the
+//! dot product of their sparse vectors.
+//!
+//! The terms of the documents and of the queries are normalized the same way, so that `Paris` matches `paris`.
+
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+
+use charabia::normalizer::{Normalize, NormalizerOption};
+use charabia::{StrDetection, Token, Tokenize};
+use heed::types::Bytes;
+use heed::{Database, RoTxn, RwTxn};
+use roaring::RoaringBitmap;
+use serde_json::value::RawValue;
+
+use super::error::EmbedError;
+use super::parsed_vectors::RawVectors;
+use super::DistributionShift;
+use crate::constants::RESERVED_VECTORS_FIELD_NAME;
+use crate::{DocumentId, Index, InternalError, Result, UserError};
+
+/// Maximum length in bytes of a term, so that the keys of the inverted index,
+/// made of the embedder index, the term, a separator and a docid, fit in the 511 bytes of an LMDB key.
+const MAX_TERM_BYTES: usize = 511 - 1 - 1 - std::mem::size_of::<DocumentId>();
+
+/// A sparse vector, mapping normalized terms to their weight.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SparseVector(BTreeMap<String, f32>);
+
+impl SparseVector {
+    /// Builds a sparse vector from its terms and weights.
+    ///
+    /// The terms are normalized, the weights of the terms normalized the same way are summed.
+    /// Terms with a weight of zero are dropped.
+    pub fn new(weights: BTreeMap<String, f32>) -> std::result::Result<Self, String> {
+        let mut vector = BTreeMap::new();
+        for (term, weight) in weights {
+            if !weight.is_finite() {
+                return Err(format!("weight of term `{term}` is not a finite number"));
+            }
+            let term = normalize_term(&term).into_owned();
+            if term.is_empty() {
+                return Err("terms of a sparse vector cannot be empty".to_string());
+            }
+            if term.contains('\0') {
+                return Err(format!("term `{}` contains a NUL character", term.escape_debug()));
+            }
+            if term.len() > MAX_TERM_BYTES {
+                return Err(format!(
+                    "term `{}…` is longer than {MAX_TERM_BYTES} bytes",
+                    term.chars().take(50).collect::<String>()
+                ));
+            }
+            *vector.entry(term).or_insert(0.0) += weight;
+        }
+        vector.retain(|_, weight| *weight != 0.0);
+        Ok(Self(vector))
+    }
+
+    /// Parses a sparse vector from the value of a `_vectors.<embedder>` entry.
+    ///
+    /// `Ok(None)` if the entry has no embeddings.
+    pub fn from_vectors_entry(value: &RawValue) -> std::result::Result<Option<Self>, String> {
+        let embeddings = match RawVectors::from_raw_value(value) {
+            Ok(RawVectors::Explicit(explicit)) => explicit.embeddings,
+            Ok(RawVectors::ImplicitlyUserProvided(None)) => None,
+            Ok(RawVectors::ImplicitlyUserProvided(Some(_))) => {
+                return Err("sparse vectors must be provided as an object with the fields `embeddings` and `regenerate`".to_string())
+            }
+            Err(_) => return Err("expected an object with the fields `embeddings` and `regenerate`".to_string()),
+        };
+        match embeddings {
+            Some(embeddings) => Self::from_embeddings(embeddings),
+            None => Ok(None),
+        }
+    }
+
+    /// Parses a sparse vector from the value of the `embeddings` field of a `_vectors.<embedder>` entry.
+    ///
+    /// `Ok(None)` if the value is `null`.
+    pub fn from_embeddings(embeddings: &RawValue) -> std::result::Result<Option<Self>, String> {
+        if embeddings.get() == "null" {
+            return Ok(None);
+        }
+        let weights: BTreeMap<String, f32> = serde_json::from_str(embeddings.get())
+            .map_err(|error| format!("`embeddings` must be a map of terms to weights: {error}"))?;
+        Self::new(weights).map(Some)
+    }
+
+    /// Builds a sparse vector from a query text, by giving each normalized word a weight equal to its number of occurrences.
+    pub fn from_query(query: &str) -> Self {
+        let mut vector = BTreeMap::new();
+        for token in query.tokenize() {
+            if token.is_word() {
+                // normalized like the terms of the documents rather than by the tokenizer
+                let term = normalize_term(&query[token.byte_start..token.byte_end]);
+                if !term.is_empty() && !term.contains('\0') && term.len() <= MAX_TERM_BYTES {
+                    *vector.entry(term.into_owned()).or_insert(0.0) += 1.0;
+                }
+            }
+        }
+        Self(vector)
+    }
+
+    /// The terms of this vector that are not in `other`, with their weights.
+    pub fn without_terms_of(&self, other: &Self) -> Self {
+        Self(
+            self.0
+                .iter()
+                .filter(|(term, _)| !other.0.contains_key(*term))
+                .map(|(term, weight)| (term.clone(), *weight))
+                .collect(),
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f32)> + '_ {
+        self.0.iter().map(|(term, weight)| (term.as_str(), *weight))
+    }
+
+    /// Dot product of two sparse vectors.
+    pub fn dot(&self, other: &Self) -> f32 {
+        let (small, large) = if self.len() <= other.len() { (self, other) } else { (other, self) };
+        small
+            .iter()
+            .filter_map(|(term, weight)| large.0.get(term).map(|other| weight * other))
+            .sum()
+    }
+
+    /// Similarity in the `[0, 1]` interval from a dot product.
+    ///
+    /// When a distribution is configured the raw dot product is used and will be shifted by the distribution,
+    /// otherwise it is squashed with `dot / (1 + dot)`.
+    pub fn similarity(dot: f32, distribution: Option<DistributionShift>) -> f32 {
+        if distribution.is_some() {
+            dot
+        } else if dot <= 0.0 {
+            0.0
+        } else {
+            dot / (1.0 + dot)
+        }
+    }
+}
+
+/// Normalizes a term of a document or of a query.
+fn normalize_term(term: &str) -> Cow<'_, str> {
+    let options = NormalizerOption { lossy: true, ..Default::default() };
+    let token = Token {
+        lemma: Cow::Borrowed(term),
+        script: StrDetection::new(term, None).script(),
+        ..Default::default()
+    };
+    token.normalize(&options).lemma
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Embedder {
+    distribution: Option<DistributionShift>,
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct EmbedderOptions {
+    pub distribution: Option<DistributionShift>,
+}
+
+impl Embedder {
+    pub fn new(options: EmbedderOptions) -> Self {
+        Self { distribution: options.distribution }
+    }
+
+    /// Sparse vectors cannot be generated from a document template.
+    pub fn embed_one<S: AsRef<str>>(&self, text: S) -> std::result::Result<Vec<f32>, EmbedError> {
+        Err(EmbedError::embed_on_manual_embedder(text.as_ref().chars().take(250).collect()))
+    }
+
+    /// Sparse vector used to search for `query`.
+    pub fn embed_query(&self, query: &str) -> SparseVector {
+        SparseVector::from_query(query)
+    }
+
+    pub fn distribution(&self) -> Option<DistributionShift> {
+        self.distribution
+    }
+}
+
+/// Inverted index of the sparse vectors of an embedder.
+///
+/// Keys are `[embedder_index][term][0][docid BE]` and values are the weight of the term for the document, as a BE `f32`.
+#[derive(Clone, Copy)]
+pub struct SparseVectorStore {
+    database: Database<Bytes, Bytes>,
+    embedder_index: u8,
+}
+
+impl SparseVectorStore {
+    pub fn new(database: Database<Bytes, Bytes>, embedder_index: u8) -> Self {
+        Self { database, embedder_index }
+    }
+
+    /// Writes the key of the posting of `term` in `buffer`, `docid` excluded.
+    fn term_prefix(embedder_index: u8, term: &str, buffer: &mut Vec<u8>) {
+        buffer.clear();
+        buffer.push(embedder_index);
+        buffer.extend_from_slice(term.as_bytes());
+        buffer.push(0);
+    }
+
+    /// Calls `f` with the key and value of each posting of the vector of a document.
+    pub fn postings(
+        embedder_index: u8,
+        docid: DocumentId,
+        vector: &SparseVector,
+        mut f: impl FnMut(&[u8], &[u8]) -> Result<()>,
+    ) -> Result<()> {
+        let mut key = Vec::new();
+        for (term, weight) in vector.iter() {
+            Self::term_prefix(embedder_index, term, &mut key);
+            key.extend_from_slice(&docid.to_be_bytes());
+            f(&key, &weight.to_be_bytes())?;
+        }
+        Ok(())
+    }
+
+    pub fn add_document(
+        &self,
+        wtxn: &mut RwTxn,
+        docid: DocumentId,
+        vector: &SparseVector,
+    ) -> Result<()> {
+        Self::postings(self.embedder_index, docid, vector, |key, value| {
+            Ok(self.database.put(wtxn, key, value)?)
+        })
+    }
+
+    pub fn del_document(
+        &self,
+        wtxn: &mut RwTxn,
+        docid: DocumentId,
+        vector: &SparseVector,
+    ) -> Result<()> {
+        Self::postings(self.embedder_index, docid, vector, |key, _| {
+            self.database.delete(wtxn, key)?;
+            Ok(())
+        })
+    }
+
+    /// Removes all the postings of the embedder.
+    pub fn clear(&self, wtxn: &mut RwTxn) -> Result<()> {
+        let mut iter = self.database.prefix_iter_mut(wtxn, &[self.embedder_index])?;
+        while iter.next().transpose()?.is_some() {
+            // safety: we don't keep references from inside the LMDB database.
+            unsafe { iter.del_current()? };
+        }
+        Ok(())
+    }
+
+    /// Returns the `limit` documents of `candidates` with the highest dot product with `query`, sorted by decreasing dot product.
+    ///
+    /// Documents sharing no term with the query are not returned.
+    pub fn search(
+        &self,
+        rtxn: &RoTxn,
+        query: &SparseVector,
+        limit: usize,
+        candidates: Option<&RoaringBitmap>,
+    ) -> Result<Vec<(DocumentId, f32)>> {
+        let mut scores: HashMap<DocumentId, f32> = HashMap::new();
+        let mut prefix = Vec::new();
+        for (term, query_weight) in query.iter() {
+            Self::term_prefix(self.embedder_index, term, &mut prefix);
+            for result in self.database.prefix_iter(rtxn, &prefix)? {
+                let (key, value) = result?;
+                let docid = decode_docid(&key[prefix.len()..])?;
+                if candidates.is_some_and(|candidates| !candidates.contains(docid)) {
+                    continue;
+                }
+                let weight = decode_weight(value)?;
+                *scores.entry(docid).or_default() += query_weight * weight;
+            }
+        }
+
+        let mut scores: Vec<_> = scores.into_iter().collect();
+        scores.sort_unstable_by(|(left_docid, left), (right_docid, right)| {
+            right.total_cmp(left).then(left_docid.cmp(right_docid))
+        });
+        scores.truncate(limit);
+        Ok(scores)
+    }
+}
+
+fn decode_docid(bytes: &[u8]) -> Result<DocumentId> {
+    let bytes = bytes.try_into().map_err(|_| sparse_decoding_error())?;
+    Ok(DocumentId::from_be_bytes(bytes))
+}
+
+fn decode_weight(bytes: &[u8]) -> Result<f32> {
+    let bytes = bytes.try_into().map_err(|_| sparse_decoding_error())?;
+    Ok(f32::from_be_bytes(bytes))
+}
+
+fn sparse_decoding_error() -> crate::Error {
+    InternalError::Serialization(crate::SerializationError::Decoding {
+        db_name: Some(crate::index::db_name::VECTOR_SPARSE),
+    })
+    .into()
+}
+
+/// Parses the sparse vector of `embedder_name` in a `_vectors` field, reporting errors for `external_docid`.
+pub fn sparse_vector_from_vectors_field(
+    vectors_field: Option<&RawValue>,
+    embedder_name: &str,
+    external_docid: &str,
+) -> Result<Option<SparseVector>> {
+    let Some(vectors_field) = vectors_field else { return Ok(None) };
+    let vectors: HashMap<&str, &RawValue> =
+        serde_json::from_str(vectors_field.get()).map_err(|_| {
+            UserError::InvalidVectorsMapType {
+                document_id: external_docid.to_string(),
+                value: serde_json::from_str(vectors_field.get()).unwrap_or_default(),
+            }
+        })?;
+    let Some(entry) = vectors.get(embedder_name) else { return Ok(None) };
+    SparseVector::from_vectors_entry(entry).map_err(|error| {
+        UserError::InvalidVectorsEmbedderConf {
+            document_id: external_docid.to_string(),
+            error: format!("Could not parse `._vectors.{embedder_name}`: {error}"),
+        }
+        .into()
+    })
+}
+
+/// Rebuilds the inverted index of a sparse embedder from the `_vectors` of the documents of the index.
+pub fn index_sparse_vectors_from_documents(
+    index: &Index,
+    wtxn: &mut RwTxn,
+    embedder_name: &str,
+    embedder_index: u8,
+) -> Result<()> {
+    let store = SparseVectorStore::new(index.vector_sparse, embedder_index);
+    store.clear(wtxn)?;
+
+    let fields_ids_map = index.fields_ids_map(wtxn)?;
+    let Some(vectors_fid) = fields_ids_map.id(RESERVED_VECTORS_FIELD_NAME) else {
+        return Ok(());
+    };
+
+    let mut postings = Vec::new();
+    {
+        let rtxn: &RoTxn = wtxn;
+        let documents_ids = index.documents_ids(rtxn)?;
+        for result in index.iter_documents(rtxn, documents_ids)? {
+            let (docid, obkv) = result?;
+            let Some(vectors_field) = obkv.get(vectors_fid) else { continue };
+            let vectors_field: &RawValue =
+                serde_json::from_slice(vectors_field).map_err(InternalError::SerdeJson)?;
+            let external_docid = match index.external_id_of(rtxn, [docid])?.into_iter().next() {
+                Some(external_docid) => external_docid?,
+                None => docid.to_string(),
+            };
+            if let Some(vector) = sparse_vector_from_vectors_field(
+                Some(vectors_field),
+                embedder_name,
+                &external_docid,
+            )? {
+                postings.push((docid, vector));
+            }
+        }
+    }
+
+    for (docid, vector) in postings {
+        store.add_document(wtxn, docid, &vector)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_sparse_vector() {
+        let raw: Box<RawValue> = serde_json::from_str(
+            r#"{ "embeddings": { "paris": 1.5, "capital": 0.5, "zero": 0.0 }, "regenerate": false }"#,
+        )
+        .unwrap();
+        let vector = SparseVector::from_vectors_entry(&raw).unwrap().unwrap();
+        assert_eq!(vector.iter().collect::<Vec<_>>(), vec![("capital", 0.5), ("paris", 1.5)]);
+
+        let raw: Box<RawValue> = serde_json::from_str(r#"null"#).unwrap();
+        assert_eq!(SparseVector::from_vectors_entry(&raw).unwrap(), None);
+
+        let raw: Box<RawValue> = serde_json::from_str(r#"[0.1, 0.2]"#).unwrap();
+        assert!(SparseVector::from_vectors_entry(&raw).is_err());
+
+        let raw: Box<RawValue> =
+            serde_json::from_str(r#"{ "embeddings": { "paris": "high" }, "regenerate": false }"#)
+                .unwrap();
+        assert!(SparseVector::from_vectors_entry(&raw).is_err());
+    }
+
+    #[test]
+    fn dot_product() {
+        let left = SparseVector::new(
+            [("a".to_string(), 1.0), ("b".to_string(), 2.0)].into_iter().collect(),
+        )
+        .unwrap();
+        let right = SparseVector::new(
+            [("b".to_string(), 3.0), ("c".to_string(), 4.0)].into_iter().collect(),
+        )
+        .unwrap();
+        assert_eq!(left.dot(&right), 6.0);
+        assert_eq!(right.dot(&left), 6.0);
+        assert_eq!(SparseVector::similarity(1.0, None), 0.5);
+        assert_eq!(SparseVector::similarity(-1.0, None), 0.0);
+    }
+
+    #[test]
+    fn query_vector() {
+        let vector = SparseVector::from_query("Hello world, hello!");
+        assert_eq!(vector.iter().collect::<Vec<_>>(), vec![("hello", 2.0), ("world", 1.0)]);
+    }
+
+    #[test]
+    fn document_terms_are_normalized_like_the_query() {
+        let document = SparseVector::new(
+            [("Paris".to_string(), 1.0), ("paris".to_string(), 0.5), ("Été".to_string(), 2.0)]
+                .into_iter()
+                .collect(),
+        )
+        .unwrap();
+        assert_eq!(document.iter().collect::<Vec<_>>(), vec![("ete", 2.0), ("paris", 1.5)]);
+        assert_eq!(SparseVector::from_query("PARIS en été").dot(&document), 3.5);
+    }
+
+    #[test]
+    fn long_terms() {
+        let term = "a".repeat(MAX_TERM_BYTES);
+        SparseVector::new([(term.clone(), 1.0)].into_iter().collect()).unwrap();
+        let error = SparseVector::new([(term + "a", 1.0)].into_iter().collect()).unwrap_err();
+        assert!(error.contains("is longer than 505 bytes"), "{error}");
+    }
+}